@@ -1,54 +1,112 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders},
     Frame,
 };
+use std::future::Future;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
 use crate::components::{
     backup::BackupComponent,
     config_editor::ConfigEditorComponent,
     cron::CronComponent,
+    custom_tab::{self, CustomTabComponent},
     disks::DiskComponent,
+    global_search::GlobalSearch,
     kernel::KernelComponent,
     logs::LogViewerComponent,
     mirror::MirrorComponent,
     network::NetworkComponent,
     package_browser::PackageBrowserComponent,
+    palette::{CommandPalette, PaletteEntry},
     package_search::PackageSearchComponent,
     sbotools::SbotoolsComponent,
     services::ServiceComponent,
     settings::SettingsComponent,
     sysinfo::SysInfoComponent,
+    tasks::{TaskInfo, TaskState, TasksComponent},
     updater::UpdaterComponent,
     user_setup::UserSetupComponent,
-    Component, Tab,
+    wizard::{SetupChoices, SetupWizard},
+    AsyncComponent, Component, ProgressSender, SearchItem, Tab, ViewState,
 };
-use crate::slackware::{CommandExecutor, SlackwareVersion};
+use crate::slackware::commands::CommandResult;
+use crate::slackware::config::{self, JournalEntry};
+use crate::slackware::remote::{self, RemoteHost};
+use crate::slackware::{audit, fsusage, mdraid, CommandExecutor, SlackwareVersion};
 use crate::ui::layout::AppLayout;
+use crate::ui::mouse;
 use crate::ui::theme::Theme;
+use crate::ui::toast::{ToastLevel, Toasts};
 use crate::ui::widgets::StatusBar;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::keymap::{Action, Key};
+use crate::utils::root;
+use crate::utils::session::Session;
+
+/// The "Undo last change" dialog
+enum UndoDialog {
+    /// Asking whether to put back the files of this change
+    Confirm(JournalEntry),
+    /// What undoing did, and whether it failed
+    Done(String, bool),
+}
+
+/// The host list: this machine, then the remote hosts from the settings
+struct HostPicker {
+    hosts: Vec<RemoteHost>,
+    /// 0 is this machine, the others `hosts[selected - 1]`
+    selected: usize,
+    /// Why the last host could not be reached
+    error: Option<String>,
+}
+
+/// A second tab shown beside the current one, which has the keys
+struct Split {
+    other: Tab,
+    /// Whether the current tab is in the left pane
+    current_left: bool,
+}
+
+/// Asking for the password sudo needs, when started as a normal user
+#[derive(Default)]
+struct PasswordPrompt {
+    input: String,
+    /// Why the last password was refused
+    error: Option<String>,
+}
+
+/// How often /proc/mdstat is checked for degraded arrays
+const RAID_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// How often filesystems are checked for running out of space, and how
+/// full one has to be to warn about it
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const DISK_FULL_PERCENT: u8 = 95;
+/// How often sudo's grace period is extended, well within its default
+/// five minutes
+const PRIVILEGE_KEEP_ALIVE: Duration = Duration::from_secs(60);
 
 /// Application messages for state updates
 #[derive(Debug, Clone)]
 pub enum Message {
     // Navigation
     Quit,
-    NextTab,
-    PrevTab,
 
     // System Update
     StartUpdate,
     ContinueUpdate,
-    UpdateStepComplete(bool, Option<String>),
-    UpdateOutput(String),
+    /// A background update step finished (step index, its result)
+    UpdateStepResult(usize, CommandResult),
 
     // sbotools
     StartSbotoolsInstall,
-    SbotoolsStepComplete(bool, Option<String>),
-    SbotoolsOutput(String),
+    SbotoolsStepResult(CommandResult),
 
     // User Setup
     CreateUser,
@@ -62,16 +120,93 @@ pub enum Message {
     SearchPackages(String),
     SearchResults(Vec<crate::slackware::packages::PackageInfo>),
     InstallPackage(String),
-    PackageInstalled(Result<(), String>),
+    /// The installed package's name, or what went wrong
+    PackageInstalled(Result<String, String>),
+
+    // Kernel
+    /// Download kernel packages from the mirror and install them side by side
+    InstallKernelPackages(Vec<String>),
+    /// Rebuild an SBo module package (name, kernel release) with sboupgrade
+    RebuildKernelModule(String, String),
+    KernelPackagesInstalled(Result<Vec<String>, String>),
+    KernelModuleRebuilt(String, Result<(), String>),
+
+    // First-run setup
+    FinishSetup(SetupChoices),
+
+    // Command palette
+    /// Switch to the tab and press the key there, if any
+    PaletteRun(Tab, Option<KeyEvent>),
+    PaletteClose,
+
+    // Global search
+    /// Switch to the tab and select the item with this id there
+    SearchJump(Tab, String),
+    SearchClose,
+
+    // Tasks
+    GotoTab(Tab),
+    CancelJob(u64),
+    ClearFinishedJobs,
+    /// The running update step was cancelled from the Tasks tab
+    UpdateCancelled,
 
     // Progress
-    ProgressUpdate(String),
+    /// A progress line from the work of the tab
+    ProgressUpdate(Tab, String),
+}
+
+/// Frames of the spinner shown in the header while jobs run
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Finished jobs kept for the Tasks tab
+const FINISHED_JOBS_KEPT: usize = 30;
+/// Output lines kept per job
+const JOB_OUTPUT_LINES: usize = 1000;
+
+/// A command running in the background, or finished and kept for the
+/// Tasks tab
+struct Job {
+    id: u64,
+    label: String,
+    tab: Tab,
+    started: Instant,
+    handle: JoinHandle<()>,
+    /// Sent in place of the job's own result when it is cancelled, so the
+    /// tab waiting on it carries on
+    on_cancel: Option<Message>,
+    /// What the job's commands printed, and where more comes in
+    output: Vec<String>,
+    output_rx: mpsc::UnboundedReceiver<String>,
+    /// Passes the output on to the job's tab
+    progress: ProgressSender,
+    /// When it finished, and whether by being cancelled
+    ended: Option<(Instant, TaskState)>,
+}
+
+impl Job {
+    fn is_running(&self) -> bool {
+        self.ended.is_none()
+    }
+
+    fn drain_output(&mut self) {
+        while let Ok(line) = self.output_rx.try_recv() {
+            self.progress.send(line.clone());
+            self.output.push(line);
+        }
+        if self.output.len() > JOB_OUTPUT_LINES {
+            self.output.drain(..self.output.len() - JOB_OUTPUT_LINES);
+        }
+    }
 }
 
 /// Main application state
 pub struct App {
     pub running: bool,
     pub current_tab: Tab,
+    split: Option<Split>,
+    /// The tab switched away from last, offered for the second pane
+    previous_tab: Tab,
     pub slackware_version: SlackwareVersion,
 
     // Original Components
@@ -93,25 +228,62 @@ pub struct App {
     pub cron: CronComponent,
     pub disks: DiskComponent,
     pub settings: SettingsComponent,
-
-    // Command executor
-    pub executor: CommandExecutor,
+    pub tasks: TasksComponent,
+    /// One per tab file in the config directory
+    pub custom_tabs: Vec<CustomTabComponent>,
 
     // Progress channel
-    pub progress_tx: mpsc::UnboundedSender<String>,
-    pub progress_rx: mpsc::UnboundedReceiver<String>,
+    pub progress_tx: mpsc::UnboundedSender<(Tab, String)>,
+    pub progress_rx: mpsc::UnboundedReceiver<(Tab, String)>,
+
+    // Results of background jobs
+    pub message_tx: mpsc::UnboundedSender<Message>,
+    pub message_rx: mpsc::UnboundedReceiver<Message>,
+    jobs: Vec<Job>,
+    next_job_id: u64,
 
     // Exit warning state
     show_exit_warning: bool,
+
+    /// First-run setup, shown over everything until finished or skipped
+    wizard: Option<SetupWizard>,
+    palette: Option<CommandPalette>,
+    search: Option<GlobalSearch>,
+    /// The '?' overlay with every key of the current tab, and its scroll
+    show_help: bool,
+    help_scroll: u16,
+    undo: Option<UndoDialog>,
+    hosts: Option<HostPicker>,
+    /// Shown over everything until sudo has a password or the user skips it
+    password: Option<PasswordPrompt>,
+    /// Whether privileged commands ran without asking at the last check,
+    /// so the prompt comes back when that lapses
+    authenticated: bool,
+    privilege_checked: Instant,
+
+    /// Degraded md arrays, shown in the header whatever the tab
+    degraded_arrays: Vec<String>,
+    raid_checked: Instant,
+    /// Background events, shown whatever the tab
+    toasts: Toasts,
+    /// Mount points already warned about as nearly full
+    full_mounts: Vec<String>,
+    disk_checked: Option<Instant>,
+    /// Last auto refresh of the current tab
+    refreshed: Instant,
 }
 
 impl App {
     pub fn new(version: SlackwareVersion) -> Self {
         let (progress_tx, progress_rx) = mpsc::unbounded_channel();
+        let (message_tx, message_rx) = mpsc::unbounded_channel();
+        let wizard = SettingsComponent::first_run().then(|| SetupWizard::new(&version));
 
-        Self {
+        let mut app = Self {
             running: true,
             current_tab: Tab::Updater,
+            split: None,
+            previous_tab: Tab::Updater,
             slackware_version: version.clone(),
 
             // Original components
@@ -133,22 +305,285 @@ impl App {
             cron: CronComponent::new(),
             disks: DiskComponent::new(),
             settings: SettingsComponent::new(),
+            tasks: TasksComponent::new(),
+            custom_tabs: custom_tab::definitions()
+                .iter()
+                .map(CustomTabComponent::new)
+                .collect(),
 
-            executor: CommandExecutor::new(),
             progress_tx,
             progress_rx,
+            message_tx,
+            message_rx,
+            jobs: Vec::new(),
+            next_job_id: 1,
 
             show_exit_warning: false,
+
+            wizard,
+            palette: None,
+            search: None,
+            show_help: false,
+            help_scroll: 0,
+            undo: None,
+            hosts: None,
+            password: None,
+            authenticated: false,
+            privilege_checked: Instant::now(),
+
+            degraded_arrays: mdraid::degraded(),
+            raid_checked: Instant::now(),
+            toasts: Toasts::default(),
+            full_mounts: Vec::new(),
+            disk_checked: None,
+            refreshed: Instant::now(),
+        };
+        let mut tab = app.settings.default_tab();
+        if app.settings.restore_session() {
+            let session = Session::load();
+            for (id, state) in &session.tabs {
+                if let Some(restored) = Tab::from_id(id) {
+                    app.restore_view_state(restored, state);
+                }
+            }
+            tab = Tab::from_id(&session.tab).unwrap_or(tab);
+        }
+        app.switch_to_tab(tab);
+        // Settings pick sudo or doas, so only now is it known what to ask
+        app.authenticated = root::authenticated();
+        if !app.authenticated && root::takes_password() {
+            app.password = Some(PasswordPrompt::default());
+        } else if !app.authenticated {
+            app.toasts.push(
+                ToastLevel::Warning,
+                tr("Read-only mode: permit this user with nopass in /etc/doas.conf"),
+            );
+        }
+        app
+    }
+
+    /// The tab's component when it shows the output of its jobs itself
+    fn async_component_mut(&mut self, tab: Tab) -> Option<&mut dyn AsyncComponent> {
+        match tab {
+            Tab::Updater => Some(&mut self.updater),
+            Tab::Sbotools => Some(&mut self.sbotools),
+            Tab::UserSetup => Some(&mut self.user_setup),
+            Tab::Mirror => Some(&mut self.mirror),
+            Tab::Packages => Some(&mut self.package_search),
+            Tab::Config => Some(&mut self.config_editor),
+            _ => None,
+        }
+    }
+
+    /// Remember the current tab and where every tab stands for the next
+    /// launch, unless sessions are not restored
+    pub fn save_session(&self) -> std::io::Result<()> {
+        if !self.settings.restore_session() {
+            return Ok(());
+        }
+        let mut session = Session {
+            tab: self.current_tab.id().to_string(),
+            ..Session::default()
+        };
+        for tab in Tab::all() {
+            session.set(tab.id(), self.view_state(tab));
+        }
+        session.save()
+    }
+
+    fn view_state(&self, tab: Tab) -> ViewState {
+        match tab {
+            Tab::Updater => self.updater.view_state(),
+            Tab::Sbotools => self.sbotools.view_state(),
+            Tab::UserSetup => self.user_setup.view_state(),
+            Tab::Mirror => self.mirror.view_state(),
+            Tab::Packages => self.package_search.view_state(),
+            Tab::Config => self.config_editor.view_state(),
+            Tab::SysInfo => self.sysinfo.view_state(),
+            Tab::Services => self.services.view_state(),
+            Tab::PackageBrowser => self.package_browser.view_state(),
+            Tab::Backup => self.backup.view_state(),
+            Tab::Network => self.network.view_state(),
+            Tab::Logs => self.logs.view_state(),
+            Tab::Kernel => self.kernel.view_state(),
+            Tab::Cron => self.cron.view_state(),
+            Tab::Disks => self.disks.view_state(),
+            Tab::Settings => self.settings.view_state(),
+            Tab::Tasks => self.tasks.view_state(),
+            Tab::Custom(i) => self.custom_tabs[i].view_state(),
         }
     }
 
-    /// Check if exit warning dialog is showing
-    pub fn is_showing_exit_warning(&self) -> bool {
-        self.show_exit_warning
+    fn restore_view_state(&mut self, tab: Tab, state: &ViewState) {
+        match tab {
+            Tab::Updater => self.updater.restore_view_state(state),
+            Tab::Sbotools => self.sbotools.restore_view_state(state),
+            Tab::UserSetup => self.user_setup.restore_view_state(state),
+            Tab::Mirror => self.mirror.restore_view_state(state),
+            Tab::Packages => self.package_search.restore_view_state(state),
+            Tab::Config => self.config_editor.restore_view_state(state),
+            Tab::SysInfo => self.sysinfo.restore_view_state(state),
+            Tab::Services => self.services.restore_view_state(state),
+            Tab::PackageBrowser => self.package_browser.restore_view_state(state),
+            Tab::Backup => self.backup.restore_view_state(state),
+            Tab::Network => self.network.restore_view_state(state),
+            Tab::Logs => self.logs.restore_view_state(state),
+            Tab::Kernel => self.kernel.restore_view_state(state),
+            Tab::Cron => self.cron.restore_view_state(state),
+            Tab::Disks => self.disks.restore_view_state(state),
+            Tab::Settings => self.settings.restore_view_state(state),
+            Tab::Tasks => self.tasks.restore_view_state(state),
+            Tab::Custom(i) => self.custom_tabs[i].restore_view_state(state),
+        }
     }
 
     /// Handle keyboard input
     pub fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        if let Some(prompt) = &mut self.password {
+            match key.code {
+                KeyCode::Enter => match root::authenticate(&prompt.input) {
+                    Ok(()) => {
+                        self.password = None;
+                        self.authenticated = true;
+                        // Root-only files, such as most logs, can be read now
+                        self.deactivate_tab(self.current_tab);
+                        self.activate_tab(self.current_tab);
+                    }
+                    Err(e) => {
+                        prompt.input.clear();
+                        prompt.error = Some(e.to_string());
+                    }
+                },
+                KeyCode::Esc => {
+                    self.password = None;
+                    self.toasts.push(
+                        ToastLevel::Warning,
+                        tr("Read-only mode: changes need root or the password"),
+                    );
+                    if !self.current_tab.browsable() {
+                        self.switch_to_tab(Tab::SysInfo);
+                    }
+                }
+                KeyCode::Backspace => {
+                    prompt.input.pop();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    prompt.input.push(c)
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some(wizard) = &mut self.wizard {
+            return wizard.handle_input(key);
+        }
+
+        if let Some(palette) = &mut self.palette {
+            return match palette.handle_input(key) {
+                Some(Message::PaletteRun(tab, key)) => {
+                    self.palette = None;
+                    self.switch_to_tab(tab);
+                    // Not when read-only mode kept the tab closed
+                    key.filter(|_| self.current_tab == tab)
+                        .and_then(|k| self.delegate_to_component(k))
+                }
+                Some(Message::PaletteClose) => {
+                    self.palette = None;
+                    None
+                }
+                message => message,
+            };
+        }
+
+        if let Some(search) = &mut self.search {
+            return match search.handle_input(key) {
+                Some(Message::SearchJump(tab, id)) => {
+                    self.search = None;
+                    self.switch_to_tab(tab);
+                    if self.current_tab == tab {
+                        self.select_item(tab, &id);
+                    }
+                    None
+                }
+                Some(Message::SearchClose) => {
+                    self.search = None;
+                    None
+                }
+                message => message,
+            };
+        }
+
+        if self.show_help {
+            let last_line = self.help_lines().len().saturating_sub(1) as u16;
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.help_scroll = self.help_scroll.saturating_sub(1)
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.help_scroll = (self.help_scroll + 1).min(last_line)
+                }
+                KeyCode::PageUp => self.help_scroll = self.help_scroll.saturating_sub(10),
+                KeyCode::PageDown => self.help_scroll = (self.help_scroll + 10).min(last_line),
+                KeyCode::Esc | KeyCode::Char('q') => self.show_help = false,
+                _ if self.settings.keymap().actions(&key).contains(&Action::Help) => {
+                    self.show_help = false
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some(dialog) = &self.undo {
+            match (dialog, key.code) {
+                (UndoDialog::Confirm(_), KeyCode::Char('y') | KeyCode::Char('Y')) => {
+                    self.undo = Some(match config::undo_last_change() {
+                        Ok(entry) => {
+                            // Show what the files hold now
+                            self.deactivate_tab(self.current_tab);
+                            self.activate_tab(self.current_tab);
+                            UndoDialog::Done(trf("Undone: {}", &[&entry.description]), false)
+                        }
+                        Err(e) => UndoDialog::Done(trf("Undo failed: {}", &[&e.to_string()]), true),
+                    });
+                }
+                (UndoDialog::Confirm(_), KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc)
+                | (UndoDialog::Done(..), _) => self.undo = None,
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some(picker) = &mut self.hosts {
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    picker.selected = picker.selected.saturating_sub(1)
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    picker.selected = (picker.selected + 1).min(picker.hosts.len())
+                }
+                KeyCode::Enter => match picker.selected.checked_sub(1) {
+                    None => {
+                        self.hosts = None;
+                        self.set_host(None);
+                    }
+                    Some(i) => {
+                        let host = picker.hosts[i].clone();
+                        match host.check() {
+                            Ok(()) => {
+                                self.hosts = None;
+                                self.set_host(Some(host));
+                            }
+                            Err(e) => picker.error = Some(e.to_string()),
+                        }
+                    }
+                },
+                KeyCode::Esc => self.hosts = None,
+                _ => {}
+            }
+            return None;
+        }
+
         // Handle exit warning dialog
         if self.show_exit_warning {
             match key.code {
@@ -157,6 +592,19 @@ impl App {
                     self.show_exit_warning = false;
                     return Some(Message::Quit);
                 }
+                KeyCode::Char('l') | KeyCode::Char('L') if self.read_only() => {
+                    self.refuse_read_only();
+                    return None;
+                }
+                KeyCode::Char('l') | KeyCode::Char('L')
+                    if self.kernel.needs_bootloader_update() =>
+                {
+                    // The kernel tab knows whether that means lilo or grub-mkconfig
+                    self.show_exit_warning = false;
+                    self.switch_to_tab(Tab::Kernel);
+                    self.kernel.prompt_bootloader_update();
+                    return None;
+                }
                 KeyCode::Char('l') | KeyCode::Char('L') => {
                     // Run lilo now
                     self.show_exit_warning = false;
@@ -174,126 +622,245 @@ impl App {
             }
         }
 
-        // Global keys
-        if key.modifiers.contains(KeyModifiers::CONTROL) {
-            match key.code {
-                KeyCode::Char('c') | KeyCode::Char('q') => {
-                    // Check if we should show exit warning
-                    if self.updater.was_lilo_skipped() && self.updater.was_kernel_updated() {
-                        self.show_exit_warning = true;
-                        return None;
-                    }
-                    return Some(Message::Quit);
-                }
-                // Additional Ctrl+shortcuts for new tabs
-                KeyCode::Char('k') => {
-                    self.switch_to_tab(Tab::Kernel);
-                    return None;
-                }
-                KeyCode::Char('j') => {
-                    self.switch_to_tab(Tab::Cron);
-                    return None;
-                }
-                KeyCode::Char('d') => {
-                    self.switch_to_tab(Tab::Disks);
-                    return None;
-                }
-                KeyCode::Char('s') => {
-                    self.switch_to_tab(Tab::Settings);
-                    return None;
-                }
-                _ => {}
+        // Global keys, as bound in Settings
+        let actions = self.settings.keymap().actions(&key);
+        if actions.contains(&Action::Quit) {
+            // Check if we should show exit warning
+            if (self.updater.was_lilo_skipped() && self.updater.was_kernel_updated())
+                || self.kernel.needs_bootloader_update()
+            {
+                self.show_exit_warning = true;
+                return None;
             }
+            return Some(Message::Quit);
         }
 
         // Block tab navigation during update or when showing dialogs
         if self.updater.is_running() || self.updater.needs_lilo_confirm() || self.updater.is_showing_summary() {
-            // Only allow updater input during update
-            if self.current_tab == Tab::Updater {
-                return self.updater.handle_input(key);
-            }
-            return None;
-        }
-
-        // Tab navigation with function keys
-        match key.code {
-            KeyCode::F(1) => {
-                self.switch_to_tab(Tab::Updater);
-                return None;
-            }
-            KeyCode::F(2) => {
-                self.switch_to_tab(Tab::Sbotools);
-                return None;
-            }
-            KeyCode::F(3) => {
-                self.switch_to_tab(Tab::UserSetup);
+            // Only allow updater input during update, and the Tasks tab
+            // to follow or cancel it
+            if actions.contains(&Action::Goto(Tab::Tasks)) {
+                self.switch_to_tab(Tab::Tasks);
                 return None;
             }
-            KeyCode::F(4) => {
-                self.switch_to_tab(Tab::Mirror);
+            if actions.contains(&Action::Goto(Tab::Updater)) {
+                self.switch_to_tab(Tab::Updater);
                 return None;
             }
-            KeyCode::F(5) => {
-                // F5 is context-sensitive - refresh current tab or switch to Packages
-                // Only switch if not already on a tab that uses F5 for refresh
-                match self.current_tab {
+            return match self.current_tab {
+                Tab::Updater => self.updater.handle_input(key),
+                Tab::Tasks => self.tasks.handle_input(key),
+                _ => None,
+            };
+        }
+
+        for action in actions {
+            match action {
+                Action::NextTab => {
+                    self.switch_to_tab(self.neighbour_tab(true));
+                    return None;
+                }
+                Action::PrevTab => {
+                    self.switch_to_tab(self.neighbour_tab(false));
+                    return None;
+                }
+                // Refresh is context-sensitive: it shares F5 with the Search
+                // tab and wins on tabs that use F5 for refresh
+                Action::Refresh => match self.current_tab {
                     Tab::Services | Tab::PackageBrowser | Tab::Backup | Tab::Network
                     | Tab::Logs | Tab::Kernel | Tab::Cron | Tab::Disks | Tab::SysInfo => {
-                        // Let the component handle F5 for refresh
-                        return self.delegate_to_component(key);
+                        return self.delegate_to_component(KeyEvent::new(
+                            KeyCode::F(5),
+                            KeyModifiers::NONE,
+                        ));
                     }
-                    _ => {
-                        self.switch_to_tab(Tab::Packages);
-                        return None;
+                    _ => {}
+                },
+                Action::Goto(tab) => {
+                    self.switch_to_tab(tab);
+                    return None;
+                }
+                // '?' is text while typing into a field
+                Action::Help if !self.is_typing() => {
+                    self.show_help = true;
+                    self.help_scroll = 0;
+                    return None;
+                }
+                Action::Help => {}
+                Action::Undo if remote::current().is_some() => {
+                    self.undo = Some(UndoDialog::Done(
+                        tr("Changes on a remote host cannot be undone").to_string(),
+                        true,
+                    ));
+                    return None;
+                }
+                Action::Undo if self.read_only() => {
+                    self.refuse_read_only();
+                    return None;
+                }
+                Action::Undo => {
+                    self.undo = Some(match config::last_change() {
+                        Some(entry) => UndoDialog::Confirm(entry),
+                        None => UndoDialog::Done(tr("Nothing to undo").to_string(), false),
+                    });
+                    return None;
+                }
+                Action::Palette => {
+                    self.palette = Some(CommandPalette::new(self.palette_entries()));
+                    return None;
+                }
+                Action::GlobalSearch => {
+                    self.search = Some(GlobalSearch::new(self.search_entries()));
+                    return None;
+                }
+                Action::Hosts => {
+                    let hosts = self.settings.remote_hosts().to_vec();
+                    let selected = remote::current()
+                        .and_then(|current| hosts.iter().position(|h| *h == current))
+                        .map_or(0, |i| i + 1);
+                    self.hosts = Some(HostPicker {
+                        hosts,
+                        selected,
+                        error: None,
+                    });
+                    return None;
+                }
+                Action::Split => {
+                    self.toggle_split();
+                    return None;
+                }
+                Action::SwitchPane => {
+                    self.switch_pane();
+                    return None;
+                }
+                Action::Confirm | Action::Search => {
+                    if let Some(forwarded) = action.forwarded_key() {
+                        return self.delegate_to_component(forwarded);
                     }
                 }
+                Action::Quit => {}
             }
-            KeyCode::F(6) => {
-                self.switch_to_tab(Tab::Config);
-                return None;
-            }
-            KeyCode::F(7) => {
-                self.switch_to_tab(Tab::SysInfo);
-                return None;
-            }
-            KeyCode::F(8) => {
-                self.switch_to_tab(Tab::Services);
-                return None;
-            }
-            KeyCode::F(9) => {
-                self.switch_to_tab(Tab::PackageBrowser);
-                return None;
-            }
-            KeyCode::F(10) => {
-                self.switch_to_tab(Tab::Backup);
-                return None;
-            }
-            KeyCode::F(11) => {
-                self.switch_to_tab(Tab::Network);
-                return None;
-            }
-            KeyCode::F(12) => {
-                self.switch_to_tab(Tab::Logs);
-                return None;
-            }
-            KeyCode::Left if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.switch_to_tab(self.current_tab.prev());
-                return None;
-            }
-            KeyCode::Right if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.switch_to_tab(self.current_tab.next());
-                return None;
-            }
-            _ => {}
         }
 
         // Delegate to current component
         self.delegate_to_component(key)
     }
 
+    /// Running as a normal user without a password for sudo or doas: only
+    /// the tabs for looking at the system are open
+    fn read_only(&self) -> bool {
+        remote::current().is_none()
+            && root::escalation().is_some()
+            && !self.authenticated
+            && self.password.is_none()
+    }
+
+    /// Whether `tab` can be switched to: only those that work over SSH
+    /// while a remote host is picked, only those for looking at the system
+    /// in read-only mode
+    fn tab_open(&self, tab: Tab) -> bool {
+        if remote::current().is_some() {
+            tab.remote_capable()
+        } else {
+            !self.read_only() || tab.browsable()
+        }
+    }
+
+    /// The next or previous tab, past the closed ones
+    fn neighbour_tab(&self, forward: bool) -> Tab {
+        let mut tab = self.current_tab;
+        loop {
+            tab = if forward { tab.next() } else { tab.prev() };
+            if tab == self.current_tab || self.tab_open(tab) {
+                return tab;
+            }
+        }
+    }
+
+    /// Administer `host` from now on, or this machine for `None`
+    fn set_host(&mut self, host: Option<RemoteHost>) {
+        let name = host.as_ref().map_or(tr("this machine"), |h| h.name.as_str());
+        self.toasts.push(ToastLevel::Info, trf("Now managing {}", &[name]));
+        remote::set_current(host);
+        if let Some(split) = self.split.take() {
+            self.deactivate_tab(split.other);
+        }
+        let tab = if self.tab_open(self.current_tab) {
+            self.current_tab
+        } else if self.tab_open(Tab::Updater) {
+            Tab::Updater
+        } else {
+            Tab::SysInfo
+        };
+        // Reloaded from the new host even when the tab stays
+        self.deactivate_tab(self.current_tab);
+        self.current_tab = tab;
+        audit::set_current_tab(tab.title());
+        self.activate_tab(tab);
+    }
+
+    /// Keys of the current tab that change the system
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        match self.current_tab {
+            Tab::Config => self.config_editor.mutating_keys(),
+            Tab::Services => self.services.mutating_keys(),
+            Tab::PackageBrowser => self.package_browser.mutating_keys(),
+            Tab::Backup => self.backup.mutating_keys(),
+            Tab::Network => self.network.mutating_keys(),
+            Tab::Logs => self.logs.mutating_keys(),
+            Tab::Kernel => self.kernel.mutating_keys(),
+            Tab::Cron => self.cron.mutating_keys(),
+            Tab::Disks => self.disks.mutating_keys(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn refuse_read_only(&mut self) {
+        self.toasts.push(
+            ToastLevel::Warning,
+            tr("Read-only mode: changes need root or the password"),
+        );
+    }
+
+    /// Whether `key` is one of `mutating_keys`. Letters typed into a
+    /// prompt are text, not actions.
+    fn is_mutating(&self, key: &KeyEvent) -> bool {
+        let typed = matches!(key.code, KeyCode::Char(_))
+            && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            && self.is_typing();
+        !typed
+            && self
+                .mutating_keys()
+                .iter()
+                // "u/d" and the like stand for several keys
+                .flat_map(|label| match *label {
+                    "/" => vec!["/"],
+                    label => label.split('/').collect(),
+                })
+                .any(|name| Key::parse(name).is_ok_and(|k| k.matches(key)))
+    }
+
     fn switch_to_tab(&mut self, tab: Tab) {
+        if !self.tab_open(tab) {
+            let reason = if remote::current().is_some() {
+                "{} is not available on a remote host"
+            } else {
+                "{} is not available in read-only mode"
+            };
+            self.toasts.push(ToastLevel::Warning, trf(reason, &[tr(tab.title())]));
+            return;
+        }
+        // Already shown in the other pane
+        if self.split.as_ref().is_some_and(|split| split.other == tab) {
+            self.switch_pane();
+            return;
+        }
         let old_tab = self.current_tab;
         self.current_tab = tab;
+        if old_tab != tab {
+            self.previous_tab = old_tab;
+        }
+        audit::set_current_tab(tab.title());
 
         // Deactivate old tab
         self.deactivate_tab(old_tab);
@@ -302,6 +869,56 @@ impl App {
         self.activate_tab(tab);
     }
 
+    /// Show the tab left last beside the current one, or the next one if
+    /// that is the current tab; close the second pane when it is open
+    fn toggle_split(&mut self) {
+        if let Some(split) = self.split.take() {
+            self.deactivate_tab(split.other);
+            return;
+        }
+        let other = if self.previous_tab != self.current_tab && self.tab_open(self.previous_tab)
+        {
+            self.previous_tab
+        } else {
+            self.neighbour_tab(true)
+        };
+        if other == self.current_tab {
+            return;
+        }
+        self.split = Some(Split {
+            other,
+            current_left: true,
+        });
+        self.activate_tab(other);
+    }
+
+    /// Give the keys to the tab in the other pane; both stay on screen
+    fn switch_pane(&mut self) {
+        let Some(split) = &mut self.split else {
+            return;
+        };
+        std::mem::swap(&mut split.other, &mut self.current_tab);
+        split.current_left = !split.current_left;
+        audit::set_current_tab(self.current_tab.title());
+    }
+
+    /// The areas of the current tab and of the other tab of the split,
+    /// each with a title row on top
+    fn pane_areas(&self, content: Rect) -> (Rect, Option<Rect>) {
+        let Some(split) = &self.split else {
+            return (content, None);
+        };
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(content);
+        if split.current_left {
+            (halves[0], Some(halves[1]))
+        } else {
+            (halves[1], Some(halves[0]))
+        }
+    }
+
     fn activate_tab(&mut self, tab: Tab) {
         match tab {
             Tab::Updater => {}
@@ -320,6 +937,150 @@ impl App {
             Tab::Cron => self.cron.on_activate(),
             Tab::Disks => self.disks.on_activate(),
             Tab::Settings => {}
+            Tab::Tasks => self.tasks.set_tasks(self.task_infos()),
+            Tab::Custom(_) => {}
+        }
+    }
+
+    /// Give the active tab a chance to do periodic work
+    pub fn on_tick(&mut self) {
+        for job in &mut self.jobs {
+            job.drain_output();
+            if job.is_running() && job.handle.is_finished() {
+                job.ended = Some((Instant::now(), TaskState::Finished));
+            }
+        }
+        let finished = self.jobs.iter().filter(|job| !job.is_running()).count();
+        let mut excess = finished.saturating_sub(FINISHED_JOBS_KEPT);
+        self.jobs.retain(|job| {
+            let drop = excess > 0 && !job.is_running();
+            if drop {
+                excess -= 1;
+            }
+            !drop
+        });
+        if self.raid_checked.elapsed() >= RAID_CHECK_INTERVAL {
+            let degraded = mdraid::degraded();
+            for array in degraded.iter().filter(|a| !self.degraded_arrays.contains(a)) {
+                self.toasts
+                    .push(ToastLevel::Error, format!("RAID array {} is degraded", array));
+            }
+            self.degraded_arrays = degraded;
+            self.raid_checked = Instant::now();
+        }
+        if self.disk_checked.map_or(true, |t| t.elapsed() >= DISK_CHECK_INTERVAL) {
+            self.check_disk_space();
+            self.disk_checked = Some(Instant::now());
+        }
+        if root::escalation().is_some()
+            && self.privilege_checked.elapsed() >= PRIVILEGE_KEEP_ALIVE
+        {
+            root::keep_alive();
+            let authenticated = root::authenticated();
+            if self.authenticated
+                && !authenticated
+                && self.password.is_none()
+                && root::takes_password()
+            {
+                self.password = Some(PasswordPrompt::default());
+            }
+            self.authenticated = authenticated;
+            self.privilege_checked = Instant::now();
+        }
+        self.toasts.on_tick();
+        for tab in self.visible_tabs() {
+            self.tick_tab(tab);
+        }
+        if let Some(interval) = self.settings.auto_refresh_interval() {
+            if self.refreshed.elapsed() >= interval {
+                for tab in self.visible_tabs() {
+                    self.refresh_tab(tab);
+                }
+                self.refreshed = Instant::now();
+            }
+        }
+    }
+
+    /// The current tab, and the other one of the split
+    fn visible_tabs(&self) -> Vec<Tab> {
+        let mut tabs = vec![self.current_tab];
+        tabs.extend(self.split.as_ref().map(|split| split.other));
+        tabs
+    }
+
+    fn tick_tab(&mut self, tab: Tab) {
+        match tab {
+            Tab::Updater => self.updater.on_tick(),
+            Tab::Sbotools => self.sbotools.on_tick(),
+            Tab::UserSetup => self.user_setup.on_tick(),
+            Tab::Mirror => self.mirror.on_tick(),
+            Tab::Packages => self.package_search.on_tick(),
+            Tab::Config => self.config_editor.on_tick(),
+            Tab::SysInfo => self.sysinfo.on_tick(),
+            Tab::Services => self.services.on_tick(),
+            Tab::PackageBrowser => self.package_browser.on_tick(),
+            Tab::Backup => self.backup.on_tick(),
+            Tab::Network => self.network.on_tick(),
+            Tab::Logs => self.logs.on_tick(),
+            Tab::Kernel => self.kernel.on_tick(),
+            Tab::Cron => self.cron.on_tick(),
+            Tab::Disks => self.disks.on_tick(),
+            Tab::Settings => self.settings.on_tick(),
+            Tab::Tasks => self.tasks.set_tasks(self.task_infos()),
+            Tab::Custom(i) => self.custom_tabs[i].on_tick(),
+        }
+    }
+
+    /// Whether the screen changes without input: the header spinner while
+    /// jobs run, or output coming into a tab on screen
+    pub fn is_busy(&self) -> bool {
+        self.jobs.iter().any(Job::is_running)
+            || self.visible_tabs().into_iter().any(|tab| self.tab_busy(tab))
+    }
+
+    fn tab_busy(&self, tab: Tab) -> bool {
+        match tab {
+            Tab::Updater => self.updater.is_busy(),
+            Tab::Sbotools => self.sbotools.is_busy(),
+            Tab::UserSetup => self.user_setup.is_busy(),
+            Tab::Mirror => self.mirror.is_busy(),
+            Tab::Packages => self.package_search.is_busy(),
+            Tab::Config => self.config_editor.is_busy(),
+            Tab::SysInfo => self.sysinfo.is_busy(),
+            Tab::Services => self.services.is_busy(),
+            Tab::PackageBrowser => self.package_browser.is_busy(),
+            Tab::Backup => self.backup.is_busy(),
+            Tab::Network => self.network.is_busy(),
+            Tab::Logs => self.logs.is_busy(),
+            Tab::Kernel => self.kernel.is_busy(),
+            Tab::Cron => self.cron.is_busy(),
+            Tab::Disks => self.disks.is_busy(),
+            Tab::Settings => self.settings.is_busy(),
+            Tab::Tasks => self.tasks.is_busy(),
+            Tab::Custom(i) => self.custom_tabs[i].is_busy(),
+        }
+    }
+
+    fn refresh_tab(&mut self, tab: Tab) {
+        match tab {
+            Tab::Updater => self.updater.on_refresh(),
+            Tab::Sbotools => self.sbotools.on_refresh(),
+            Tab::UserSetup => self.user_setup.on_refresh(),
+            Tab::Mirror => self.mirror.on_refresh(),
+            Tab::Packages => self.package_search.on_refresh(),
+            Tab::Config => self.config_editor.on_refresh(),
+            Tab::SysInfo => self.sysinfo.on_refresh(),
+            Tab::Services => self.services.on_refresh(),
+            Tab::PackageBrowser => self.package_browser.on_refresh(),
+            Tab::Backup => self.backup.on_refresh(),
+            Tab::Network => self.network.on_refresh(),
+            Tab::Logs => self.logs.on_refresh(),
+            Tab::Kernel => self.kernel.on_refresh(),
+            Tab::Cron => self.cron.on_refresh(),
+            Tab::Disks => self.disks.on_refresh(),
+            Tab::Settings => self.settings.on_refresh(),
+            Tab::Tasks => self.tasks.on_refresh(),
+            Tab::Custom(i) => self.custom_tabs[i].on_refresh(),
         }
     }
 
@@ -341,12 +1102,132 @@ impl App {
             Tab::Cron => self.cron.on_deactivate(),
             Tab::Disks => self.disks.on_deactivate(),
             Tab::Settings => self.settings.on_deactivate(),
+            Tab::Tasks => self.tasks.on_deactivate(),
+            Tab::Custom(i) => self.custom_tabs[i].on_deactivate(),
         }
     }
 
-    fn delegate_to_component(&mut self, key: KeyEvent) -> Option<Message> {
-        match self.current_tab {
-            Tab::Updater => self.updater.handle_input(key),
+    /// Handle a mouse event against `frame`, the last frame drawn: clicks
+    /// on the tab bar switch tabs, clicks on list rows select them and the
+    /// wheel scrolls the current tab
+    pub fn handle_mouse(&mut self, event: MouseEvent, frame: &Buffer) -> Option<Message> {
+        if !self.settings.mouse_enabled()
+            || !AppLayout::fits(frame.area)
+            || self.wizard.is_some()
+            || self.palette.is_some()
+            || self.search.is_some()
+            || self.show_help
+            || self.undo.is_some()
+            || self.hosts.is_some()
+            || self.password.is_some()
+            || self.show_exit_warning
+            || self.updater.is_running()
+            || self.updater.needs_lilo_confirm()
+            || self.updater.is_showing_summary()
+        {
+            return None;
+        }
+        let layout = AppLayout::new(frame.area);
+        let position = Position::new(event.column, event.row);
+        let arrow = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        // Clicking or scrolling the other side of the split gives it the keys
+        if let (_, Some(other)) = self.pane_areas(layout.content) {
+            let acts = matches!(
+                event.kind,
+                MouseEventKind::Down(_) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+            );
+            if acts && other.contains(position) {
+                self.switch_pane();
+            }
+        }
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) if layout.tabs.contains(position) => {
+                if let Some(tab) = self.tab_at(layout.tabs, position) {
+                    self.switch_to_tab(tab);
+                }
+                None
+            }
+            MouseEventKind::Down(MouseButton::Left) if layout.content.contains(position) => {
+                // Walk the selection to the clicked row, as the arrow keys would
+                let rows = mouse::rows_from_selection(frame, event.column, event.row)?;
+                let code = if rows < 0 { KeyCode::Up } else { KeyCode::Down };
+                let mut message = None;
+                for _ in 0..rows.unsigned_abs() {
+                    message = self.delegate_to_component(arrow(code)).or(message);
+                }
+                message
+            }
+            MouseEventKind::ScrollUp if layout.content.contains(position) => {
+                self.delegate_to_component(arrow(KeyCode::Up))
+            }
+            MouseEventKind::ScrollDown if layout.content.contains(position) => {
+                self.delegate_to_component(arrow(KeyCode::Down))
+            }
+            _ => None,
+        }
+    }
+
+    /// The tab whose label is at `position` in the tab bar drawn in `area`
+    fn tab_at(&self, area: Rect, position: Position) -> Option<Tab> {
+        let labels: Vec<(Option<Tab>, String)> = if position.y == area.y {
+            Tab::primary_tabs()
+                .into_iter()
+                .map(|tab| (Some(tab), self.tab_label(tab)))
+                .collect()
+        } else {
+            let mut labels: Vec<(Option<Tab>, String)> = Tab::secondary_tabs()
+                .into_iter()
+                .map(|tab| (Some(tab), self.tab_label(tab)))
+                .collect();
+            labels.push((None, " │ ".to_string()));
+            labels.extend(
+                Tab::additional_tabs()
+                    .into_iter()
+                    .map(|tab| (Some(tab), self.tab_label(tab))),
+            );
+            labels
+        };
+        let mut x = area.x;
+        for (tab, label) in labels {
+            x = x.saturating_add(Span::raw(label).width() as u16);
+            if position.x < x {
+                return tab;
+            }
+        }
+        None
+    }
+
+    /// Whether the current tab has a text field taking keys
+    fn is_typing(&self) -> bool {
+        match self.current_tab {
+            Tab::Updater => self.updater.is_typing(),
+            Tab::Sbotools => self.sbotools.is_typing(),
+            Tab::UserSetup => self.user_setup.is_typing(),
+            Tab::Mirror => self.mirror.is_typing(),
+            Tab::Packages => self.package_search.is_typing(),
+            Tab::Config => self.config_editor.is_typing(),
+            Tab::SysInfo => self.sysinfo.is_typing(),
+            Tab::Services => self.services.is_typing(),
+            Tab::PackageBrowser => self.package_browser.is_typing(),
+            Tab::Backup => self.backup.is_typing(),
+            Tab::Network => self.network.is_typing(),
+            Tab::Logs => self.logs.is_typing(),
+            Tab::Kernel => self.kernel.is_typing(),
+            Tab::Cron => self.cron.is_typing(),
+            Tab::Disks => self.disks.is_typing(),
+            Tab::Settings => self.settings.is_typing(),
+            Tab::Tasks => self.tasks.is_typing(),
+            Tab::Custom(i) => self.custom_tabs[i].is_typing(),
+        }
+    }
+
+    fn delegate_to_component(&mut self, key: KeyEvent) -> Option<Message> {
+        if self.read_only() && self.is_mutating(&key) {
+            self.refuse_read_only();
+            return None;
+        }
+        match self.current_tab {
+            Tab::Updater => self.updater.handle_input(key),
             Tab::Sbotools => self.sbotools.handle_input(key),
             Tab::UserSetup => self.user_setup.handle_input(key),
             Tab::Mirror => self.mirror.handle_input(key),
@@ -362,6 +1243,8 @@ impl App {
             Tab::Cron => self.cron.handle_input(key),
             Tab::Disks => self.disks.handle_input(key),
             Tab::Settings => self.settings.handle_input(key),
+            Tab::Tasks => self.tasks.handle_input(key),
+            Tab::Custom(i) => self.custom_tabs[i].handle_input(key),
         }
     }
 
@@ -371,60 +1254,55 @@ impl App {
             Message::Quit => {
                 self.running = false;
             }
-            Message::NextTab => {
-                self.switch_to_tab(self.current_tab.next());
-            }
-            Message::PrevTab => {
-                self.switch_to_tab(self.current_tab.prev());
-            }
 
             // System Update
             Message::StartUpdate | Message::ContinueUpdate => {
-                self.run_update_step().await;
-            }
-            Message::UpdateStepComplete(success, error) => {
-                self.updater.step_complete(success, error);
-                if !self.updater.needs_lilo_confirm() {
-                    self.run_update_step().await;
-                }
+                self.run_update_step();
             }
-            Message::UpdateOutput(line) => {
-                self.updater.add_output(line);
+            Message::UpdateStepResult(step, result) => {
+                self.update_step_done(step, result);
             }
 
             // sbotools
             Message::StartSbotoolsInstall => {
-                self.run_sbotools_step().await;
+                self.run_sbotools_step();
             }
-            Message::SbotoolsStepComplete(success, error) => {
-                self.sbotools.step_complete(success, error);
-                self.run_sbotools_step().await;
-            }
-            Message::SbotoolsOutput(line) => {
-                self.sbotools.add_output(line);
+            Message::SbotoolsStepResult(result) => {
+                self.sbotools_step_done(result);
             }
 
             // User Setup
             Message::CreateUser => {
-                self.create_user().await;
+                self.create_user();
             }
             Message::UserCreated(result) => {
                 match result {
-                    Ok(msg) => self.user_setup.set_success(msg),
-                    Err(e) => self.user_setup.set_error(e),
+                    Ok(msg) => {
+                        self.toasts.push(ToastLevel::Success, msg.clone());
+                        self.user_setup.set_success(msg);
+                    }
+                    Err(e) => {
+                        self.toasts
+                            .push(ToastLevel::Error, format!("Creating the user failed: {}", e));
+                        self.user_setup.set_error(e);
+                    }
                 }
             }
 
             // Mirror
             Message::SetMirror(url) => {
-                self.set_mirror(&url).await;
+                self.set_mirror(&url);
             }
             Message::MirrorSet(result) => {
                 match result {
                     Ok(()) => {
-                        self.mirror.set_status("Mirror updated successfully. Running slackpkg update...".to_string(), false);
+                        self.toasts.push(ToastLevel::Success, "Package list updated");
+                        self.mirror.set_status("Mirror updated successfully!".to_string(), false);
+                        self.mirror.load_mirrors();
                     }
                     Err(e) => {
+                        self.toasts
+                            .push(ToastLevel::Error, format!("Package list update failed: {}", e));
                         self.mirror.set_status(format!("Error: {}", e), true);
                     }
                 }
@@ -432,181 +1310,342 @@ impl App {
 
             // Package Search
             Message::SearchPackages(query) => {
-                self.search_packages(&query).await;
+                self.search_packages(query);
             }
             Message::SearchResults(results) => {
                 self.package_search.set_results(results);
             }
             Message::InstallPackage(name) => {
-                self.install_package(&name).await;
+                self.install_package(name);
             }
             Message::PackageInstalled(result) => {
                 match result {
-                    Ok(()) => {
-                        self.package_search.set_status("Package installed successfully".to_string(), false);
+                    Ok(name) => {
+                        self.toasts.push(ToastLevel::Success, format!("Installed {}", name));
+                        self.package_search.set_status(format!("Package '{}' installed successfully", name), false);
                     }
                     Err(e) => {
+                        self.toasts
+                            .push(ToastLevel::Error, format!("Package install failed: {}", e));
                         self.package_search.set_status(format!("Error: {}", e), true);
                     }
                 }
             }
 
-            Message::ProgressUpdate(line) => {
-                // Route to appropriate component based on current tab
-                match self.current_tab {
-                    Tab::Updater => self.updater.add_output(line),
-                    Tab::Sbotools => self.sbotools.add_output(line),
-                    _ => {}
+            Message::InstallKernelPackages(urls) => {
+                let tx = self.message_tx.clone();
+                let cancelled = Message::KernelPackagesInstalled(Err("Cancelled".to_string()));
+                let label = "Installing kernel packages".to_string();
+                self.spawn(label, Tab::Kernel, Some(cancelled), move |executor| async move {
+                    let result = install_kernel_packages(&executor, &urls).await;
+                    let _ = tx.send(Message::KernelPackagesInstalled(result));
+                });
+            }
+            Message::KernelPackagesInstalled(result) => {
+                match &result {
+                    Ok(_) => self.toasts.push(ToastLevel::Success, "Kernel packages installed"),
+                    Err(e) => self
+                        .toasts
+                        .push(ToastLevel::Error, format!("Kernel install failed: {}", e)),
+                }
+                self.kernel.packages_installed(result);
+            }
+
+            Message::RebuildKernelModule(name, release) => {
+                let tx = self.message_tx.clone();
+                let cancelled =
+                    Message::KernelModuleRebuilt(name.clone(), Err("Cancelled".to_string()));
+                let label = format!("sboupgrade {}", name);
+                self.spawn(label, Tab::Kernel, Some(cancelled), move |executor| async move {
+                    // SlackBuilds build for $KERNEL, defaulting to the running one
+                    let kernel = format!("KERNEL={}", release);
+                    let result = executor
+                        .execute("env", &[&kernel, "sboupgrade", "-f", "-r", &name])
+                        .await;
+                    let result = if result.success {
+                        Ok(())
+                    } else {
+                        Err(result.output().trim().to_string())
+                    };
+                    let _ = tx.send(Message::KernelModuleRebuilt(name, result));
+                });
+            }
+            Message::KernelModuleRebuilt(name, result) => {
+                match &result {
+                    Ok(()) => self.toasts.push(ToastLevel::Success, format!("Rebuilt {}", name)),
+                    Err(_) => self
+                        .toasts
+                        .push(ToastLevel::Error, format!("Rebuilding {} failed", name)),
                 }
+                self.kernel.module_rebuilt(&name, result);
             }
+
+            Message::FinishSetup(choices) => {
+                self.wizard = None;
+                self.finish_setup(choices);
+            }
+            // handle_input acts on these before they get here
+            Message::PaletteRun(..) | Message::PaletteClose => {}
+            Message::SearchJump(..) | Message::SearchClose => {}
+            Message::GotoTab(tab) => self.switch_to_tab(tab),
+            Message::CancelJob(id) => self.cancel_job(id),
+            Message::ClearFinishedJobs => self.jobs.retain(Job::is_running),
+            Message::UpdateCancelled => self.updater.cancel(),
+            // To the tab the line came from, whichever one is on screen;
+            // the others only show job output in the Tasks tab
+            Message::ProgressUpdate(tab, line) => {
+                if let Some(component) = self.async_component_mut(tab) {
+                    component.add_progress(line);
+                }
+            }
+        }
+    }
+
+    /// Warn once about each filesystem that fills up, again only after it
+    /// has had room for a while
+    fn check_disk_space(&mut self) {
+        let full = fsusage::nearly_full(DISK_FULL_PERCENT);
+        for usage in full.iter().filter(|u| !self.full_mounts.contains(&u.mount_point)) {
+            self.toasts.push(
+                ToastLevel::Warning,
+                format!("{} is {}% full", usage.mount_point, usage.percent),
+            );
+        }
+        self.full_mounts = full.into_iter().map(|u| u.mount_point).collect();
+    }
+
+    /// Run `job` in the background so rendering and input carry on while
+    /// it works. Jobs report back with messages on `message_tx`, which
+    /// the event loop feeds to `update`. The executor handed to `job`
+    /// records its commands under `tab` and their output for the Tasks tab.
+    fn spawn<F, Fut>(&mut self, label: String, tab: Tab, on_cancel: Option<Message>, job: F)
+    where
+        F: FnOnce(CommandExecutor) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (output_tx, output_rx) = mpsc::unbounded_channel();
+        let executor = CommandExecutor::with_progress(output_tx).with_tab(tab.title());
+        self.jobs.push(Job {
+            id: self.next_job_id,
+            label,
+            tab,
+            started: Instant::now(),
+            handle: tokio::spawn(job(executor)),
+            on_cancel,
+            output: Vec::new(),
+            output_rx,
+            progress: ProgressSender::new(tab, self.progress_tx.clone()),
+            ended: None,
+        });
+        self.next_job_id += 1;
+    }
+
+    /// Stop a job, killing the command it runs, and tell its tab
+    fn cancel_job(&mut self, id: u64) {
+        let Some(job) = self.jobs.iter_mut().find(|job| job.id == id && job.is_running()) else {
+            return;
+        };
+        job.handle.abort();
+        job.ended = Some((Instant::now(), TaskState::Cancelled));
+        job.output.push("Cancelled".to_string());
+        let label = job.label.clone();
+        if let Some(message) = job.on_cancel.take() {
+            let _ = self.message_tx.send(message);
         }
+        self.toasts
+            .push(ToastLevel::Warning, format!("Cancelled {}", label));
+    }
+
+    /// The jobs for the Tasks tab: running ones and the steps queued after
+    /// them, then finished ones, newest first
+    fn task_infos(&self) -> Vec<TaskInfo> {
+        let info = |job: &Job| TaskInfo {
+            id: Some(job.id),
+            label: job.label.clone(),
+            tab: job.tab,
+            state: job.ended.map_or(TaskState::Running, |(_, state)| state),
+            elapsed: job
+                .ended
+                .map_or(job.started.elapsed(), |(ended, _)| ended - job.started),
+            progress: job.is_running().then(|| self.job_progress(job.tab)).flatten(),
+            output: job.output.clone(),
+        };
+        let queued = |tab: Tab, steps: Vec<String>| {
+            steps.into_iter().map(move |label| TaskInfo {
+                id: None,
+                label,
+                tab,
+                state: TaskState::Queued,
+                elapsed: Duration::ZERO,
+                progress: None,
+                output: Vec::new(),
+            })
+        };
+        let mut tasks: Vec<TaskInfo> =
+            self.jobs.iter().filter(|j| j.is_running()).map(info).collect();
+        tasks.extend(queued(Tab::Updater, self.updater.queued_steps()));
+        tasks.extend(queued(Tab::Sbotools, self.sbotools.queued_steps()));
+        tasks.extend(self.jobs.iter().rev().filter(|j| !j.is_running()).map(info));
+        tasks
+    }
+
+    /// How far along the sequence a job of `tab` belongs to is
+    fn job_progress(&self, tab: Tab) -> Option<String> {
+        let (step, count) = match tab {
+            Tab::Updater if self.updater.is_running() => self.updater.progress(),
+            Tab::Sbotools if self.sbotools.is_running() => self.sbotools.progress(),
+            _ => return None,
+        };
+        Some(format!("step {}/{}", step, count))
     }
 
-    /// Run the next update step
-    async fn run_update_step(&mut self) {
+    /// Start the next update step
+    fn run_update_step(&mut self) {
         let current_step = self.updater.current_step;
         let command = self.updater.get_current_command().map(|(cmd, args)| {
             (cmd.to_string(), args.iter().map(|s| s.to_string()).collect::<Vec<_>>())
         });
 
         if let Some((cmd, args)) = command {
-            self.updater.add_output(format!("Running: {} {}", cmd, args.join(" ")));
-
-            let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-            let result = self.executor.execute(&cmd, &args_ref).await;
-
-            if !result.stdout.is_empty() {
-                for line in result.stdout.lines().take(10) {
-                    self.updater.add_output(line.to_string());
-                }
-            }
+            let label = format!("{} {}", cmd, args.join(" "));
+
+            let tx = self.message_tx.clone();
+            let cancelled = Some(Message::UpdateCancelled);
+            self.spawn(label, Tab::Updater, cancelled, move |executor| async move {
+                let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+                let result = executor.execute(&cmd, &args_ref).await;
+                let _ = tx.send(Message::UpdateStepResult(current_step, result));
+            });
+        }
+    }
 
-            // Check for kernel updates after upgrade-all step (step 2)
-            if current_step == 2 && result.success {
-                let has_kernel = self.updater.check_for_kernel_update(&result.stdout);
-                self.updater.set_kernel_updated(has_kernel);
+    /// Take the result of an update step and start the next one
+    fn update_step_done(&mut self, step: usize, result: CommandResult) {
+        // Check for kernel updates after upgrade-all step (step 2)
+        if step == 2 && result.success {
+            let has_kernel = self.updater.check_for_kernel_update(&result.stdout);
+            self.updater.set_kernel_updated(has_kernel);
+            if has_kernel {
+                // Lets the kernel tab tell whether the new kernel is worth a reboot
+                self.kernel.load_changelog();
+                self.kernel.check_modules();
             }
+        }
 
-            self.updater.step_complete(
-                result.success,
-                if result.success {
-                    None
-                } else {
-                    Some(result.stderr)
-                },
-            );
+        if !result.success {
+            self.toasts
+                .push(ToastLevel::Error, format!("Update step {} failed", step + 1));
+        }
+        self.updater.step_complete(
+            result.success,
+            if result.success {
+                None
+            } else {
+                Some(result.stderr)
+            },
+        );
+        if !self.updater.is_running() {
+            self.toasts.push(ToastLevel::Info, "System update finished");
+        }
 
-            // Continue to next step if not waiting for lilo confirmation
-            if !self.updater.needs_lilo_confirm() && self.updater.get_current_command().is_some() {
-                Box::pin(self.run_update_step()).await;
-            }
+        // Continue to next step if not waiting for lilo confirmation
+        if !self.updater.needs_lilo_confirm() {
+            self.run_update_step();
         }
     }
 
-    /// Run the next sbotools installation step
-    async fn run_sbotools_step(&mut self) {
+    /// Start the next sbotools installation step
+    fn run_sbotools_step(&mut self) {
         use crate::components::sbotools::SbotoolsCommand;
 
-        if let Some(cmd) = self.sbotools.get_current_command() {
+        let Some(cmd) = self.sbotools.get_current_command() else {
+            return;
+        };
+        let label = match &cmd {
+            SbotoolsCommand::Download { filename, .. } => {
+                self.sbotools.add_output(format!("Downloading {}...", filename));
+                format!("Downloading {}", filename)
+            }
+            SbotoolsCommand::InstallPkg { path } => {
+                self.sbotools.add_output(format!("Installing {}...", path));
+                format!("installpkg {}", path)
+            }
+            SbotoolsCommand::SbopkgSync => {
+                self.sbotools.add_output("Syncing sbopkg repository...".to_string());
+                "sbopkg -r".to_string()
+            }
+            SbotoolsCommand::SbopkgInstall { package } => {
+                self.sbotools.add_output(format!("Installing {}...", package));
+                format!("sbopkg -i {}", package)
+            }
+            SbotoolsCommand::SboconfigRepo { url } => {
+                self.sbotools.add_output(format!("Configuring repo: {}", url));
+                "sboconfig -r".to_string()
+            }
+            SbotoolsCommand::SbosnapFetch => {
+                self.sbotools.add_output("Fetching SlackBuilds snapshot...".to_string());
+                "sbosnap fetch".to_string()
+            }
+        };
+
+        let tx = self.message_tx.clone();
+        let cancelled = Some(Message::SbotoolsStepResult(CommandResult::cancelled()));
+        self.spawn(label, Tab::Sbotools, cancelled, move |executor| async move {
             let result = match cmd {
                 SbotoolsCommand::Download { url, filename } => {
-                    self.sbotools.add_output(format!("Downloading {}...", filename));
                     let output_path = format!("/tmp/{}", filename);
-                    self.executor.download_file(&url, &output_path).await
-                }
-                SbotoolsCommand::InstallPkg { path } => {
-                    self.sbotools.add_output(format!("Installing {}...", path));
-                    self.executor.installpkg(&path).await
-                }
-                SbotoolsCommand::SbopkgSync => {
-                    self.sbotools.add_output("Syncing sbopkg repository...".to_string());
-                    self.executor.sbopkg(&["-r"]).await
+                    executor.download_file(&url, &output_path).await
                 }
+                SbotoolsCommand::InstallPkg { path } => executor.installpkg(&path).await,
+                SbotoolsCommand::SbopkgSync => executor.sbopkg(&["-r"]).await,
                 SbotoolsCommand::SbopkgInstall { package } => {
-                    self.sbotools.add_output(format!("Installing {}...", package));
-                    self.executor.sbopkg(&["-i", &package]).await
-                }
-                SbotoolsCommand::SboconfigRepo { url } => {
-                    self.sbotools.add_output(format!("Configuring repo: {}", url));
-                    self.executor.sboconfig(&["-r", &url]).await
-                }
-                SbotoolsCommand::SbosnapFetch => {
-                    self.sbotools.add_output("Fetching SlackBuilds snapshot...".to_string());
-                    self.executor.sbosnap(&["fetch"]).await
+                    executor.sbopkg(&["-i", &package]).await
                 }
+                SbotoolsCommand::SboconfigRepo { url } => executor.sboconfig(&["-r", &url]).await,
+                SbotoolsCommand::SbosnapFetch => executor.sbosnap(&["fetch"]).await,
             };
+            let _ = tx.send(Message::SbotoolsStepResult(result));
+        });
+    }
 
-            if !result.stdout.is_empty() {
-                for line in result.stdout.lines().take(5) {
-                    self.sbotools.add_output(line.to_string());
-                }
-            }
-
-            self.sbotools.step_complete(
-                result.success,
-                if result.success {
-                    None
-                } else {
-                    Some(result.stderr)
-                },
-            );
-
-            // Continue if more steps
-            if self.sbotools.get_current_command().is_some() {
-                Box::pin(self.run_sbotools_step()).await;
-            }
+    /// Take the result of an sbotools step and start the next one
+    fn sbotools_step_done(&mut self, result: CommandResult) {
+        let success = result.success;
+        self.sbotools.step_complete(
+            result.success,
+            if result.success {
+                None
+            } else {
+                Some(result.stderr)
+            },
+        );
+        match (success, self.sbotools.is_running()) {
+            (false, _) => self.toasts.push(ToastLevel::Error, "sbotools installation failed"),
+            (true, false) => self.toasts.push(ToastLevel::Success, "sbotools installed"),
+            (true, true) => {}
         }
+
+        self.run_sbotools_step();
     }
 
     /// Create a new user
-    async fn create_user(&mut self) {
+    fn create_user(&mut self) {
         let username = self.user_setup.get_username().to_string();
         let password = self.user_setup.get_password().to_string();
         let groups: Vec<String> = self.user_setup.get_selected_groups();
-        let groups_ref: Vec<&str> = groups.iter().map(|s| s.as_str()).collect();
         let change_runlevel = self.user_setup.should_change_runlevel();
 
-        // Create user
-        let result = self
-            .executor
-            .useradd(&username, &groups_ref, "/bin/bash")
-            .await;
-
-        if !result.success {
-            self.user_setup.set_error(format!("Failed to create user: {}", result.stderr));
-            return;
-        }
-
-        // Set password
-        let result = self.executor.set_password(&username, &password).await;
-
-        if !result.success {
-            self.user_setup.set_error(format!("Failed to set password: {}", result.stderr));
-            return;
-        }
-
-        // Change runlevel if requested
-        if change_runlevel {
-            use crate::slackware::config::SlackwareConfig;
-            if let Err(e) = SlackwareConfig::set_default_runlevel(4) {
-                self.user_setup.set_error(format!("User created but runlevel change failed: {}", e));
-                return;
-            }
-        }
-
-        self.user_setup.set_success(format!(
-            "User '{}' created successfully!{}",
-            username,
-            if change_runlevel {
-                " Runlevel changed to 4."
-            } else {
-                ""
-            }
-        ));
+        let tx = self.message_tx.clone();
+        let cancelled = Some(Message::UserCreated(Err("Cancelled".to_string())));
+        let label = format!("Creating user {}", username);
+        self.spawn(label, Tab::UserSetup, cancelled, move |executor| async move {
+            let result = create_user(&executor, &username, &password, &groups, change_runlevel);
+            let _ = tx.send(Message::UserCreated(result.await));
+        });
     }
 
-    /// Set the active mirror
-    async fn set_mirror(&mut self, url: &str) {
+    /// Set the active mirror, then refresh the GPG key and package list
+    fn set_mirror(&mut self, url: &str) {
         use crate::slackware::config::SlackwareConfig;
 
         if let Err(e) = SlackwareConfig::set_active_mirror(url) {
@@ -614,99 +1653,268 @@ impl App {
             return;
         }
 
-        // Update GPG key
-        self.mirror.set_status("Updating GPG key...".to_string(), false);
-        let result = self.executor.slackpkg(&["update", "gpg"]).await;
+        // Keeps the tab locked, showing that it is updating, until MirrorSet
+        self.mirror.start_update();
+        let tx = self.message_tx.clone();
+        let cancelled = Some(Message::MirrorSet(Err("Cancelled".to_string())));
+        let label = "slackpkg update gpg".to_string();
+        self.spawn(label, Tab::Mirror, cancelled, move |executor| async move {
+            let result = executor.slackpkg(&["update", "gpg"]).await;
+            let result = if !result.success {
+                Err(format!("GPG update failed: {}", result.stderr))
+            } else {
+                update_package_list(&executor).await
+            };
+            let _ = tx.send(Message::MirrorSet(result));
+        });
+    }
 
-        if !result.success {
-            self.mirror.set_status(format!("GPG update failed: {}", result.stderr), true);
-            return;
-        }
+    /// Run `slackpkg update`, reporting on the Mirrors tab
+    fn update_package_list(&mut self) {
+        self.mirror.start_update();
+        let tx = self.message_tx.clone();
+        let cancelled = Some(Message::MirrorSet(Err("Cancelled".to_string())));
+        let label = "slackpkg update".to_string();
+        self.spawn(label, Tab::Mirror, cancelled, move |executor| async move {
+            let _ = tx.send(Message::MirrorSet(update_package_list(&executor).await));
+        });
+    }
 
-        // Update package list
-        self.mirror.set_status("Updating package list...".to_string(), false);
-        let result = self.executor.slackpkg(&["update"]).await;
+    /// Apply what the first-run wizard settled on. The Mirrors tab is
+    /// shown when there is a package list update to follow.
+    fn finish_setup(&mut self, choices: SetupChoices) {
+        use crate::slackware::config::SlackwareConfig;
 
-        if result.success {
-            self.mirror.set_status("Mirror updated successfully!".to_string(), false);
-            self.mirror.load_mirrors();
-        } else {
-            self.mirror.set_status(format!("Package list update failed: {}", result.stderr), true);
+        self.settings
+            .apply_setup(choices.theme, choices.default_tab, choices.confirm_actions);
+        self.switch_to_tab(choices.default_tab);
+        match (choices.mirror, choices.run_update) {
+            (Some(url), true) => {
+                self.switch_to_tab(Tab::Mirror);
+                self.set_mirror(&url);
+            }
+            (Some(url), false) => {
+                match SlackwareConfig::set_active_mirror(&url) {
+                    Ok(()) => self.mirror.set_status(format!("Mirror set to {}", url), false),
+                    Err(e) => self.mirror.set_status(format!("Failed to set mirror: {}", e), true),
+                }
+                self.mirror.load_mirrors();
+            }
+            (None, true) => {
+                self.switch_to_tab(Tab::Mirror);
+                self.update_package_list();
+            }
+            (None, false) => {}
         }
     }
 
     /// Search for packages
-    async fn search_packages(&mut self, query: &str) {
+    fn search_packages(&mut self, query: String) {
         use crate::slackware::packages::PackageManager;
 
-        let pm = PackageManager::new();
-        let results = pm.search(query).await;
-        self.package_search.set_results(results);
+        let tx = self.message_tx.clone();
+        let cancelled = Some(Message::SearchResults(Vec::new()));
+        self.spawn(format!("sbofind {}", query), Tab::Packages, cancelled, move |_| async move {
+            let results = PackageManager::new().search(&query).await;
+            let _ = tx.send(Message::SearchResults(results));
+        });
     }
 
     /// Install a package
-    async fn install_package(&mut self, name: &str) {
-        let result = self.executor.sboinstall(name).await;
-
-        if result.success {
-            self.package_search.set_status(format!("Package '{}' installed successfully", name), false);
-        } else {
-            self.package_search.set_status(format!("Installation failed: {}", result.stderr), true);
-        }
+    fn install_package(&mut self, name: String) {
+        let tx = self.message_tx.clone();
+        let cancelled = Some(Message::PackageInstalled(Err("Cancelled".to_string())));
+        let label = format!("sboinstall {}", name);
+        self.spawn(label, Tab::Packages, cancelled, move |executor| async move {
+            let result = executor.sboinstall(&name).await;
+            let result = if result.success {
+                Ok(name)
+            } else {
+                Err(result.stderr)
+            };
+            let _ = tx.send(Message::PackageInstalled(result));
+        });
     }
 
     /// Render the UI
     pub fn render(&self, frame: &mut Frame) {
+        if !AppLayout::fits(frame.area()) {
+            Self::render_too_small(frame);
+            Theme::restyle(frame.buffer_mut());
+            return;
+        }
         let layout = AppLayout::new(frame.area());
 
         // Header
-        let header = ratatui::widgets::Paragraph::new(Line::from(vec![
+        let mut header_spans = vec![
             Span::styled(" Slackware CLI Manager ", Theme::title()),
             Span::styled(
                 format!(" - {} ", self.slackware_version.display_name()),
                 Theme::muted(),
             ),
-        ]))
+        ];
+        if !self.degraded_arrays.is_empty() {
+            header_spans.push(Span::styled(
+                format!(
+                    " ⚠ RAID degraded: {} (Disks → r) ",
+                    self.degraded_arrays.join(", ")
+                ),
+                Theme::error().add_modifier(Modifier::REVERSED),
+            ));
+        }
+        let running: Vec<&Job> = self.jobs.iter().filter(|job| job.is_running()).collect();
+        if let Some(job) = running.first() {
+            let frame_index = job.started.elapsed().as_millis() / 100 % SPINNER.len() as u128;
+            let more = match running.len() {
+                1 => String::new(),
+                n => format!(" (+{} more)", n - 1),
+            };
+            header_spans.push(Span::styled(
+                format!(" {} {}{} ", SPINNER[frame_index as usize], job.label, more),
+                Theme::warning(),
+            ));
+        }
+        if self.read_only() {
+            header_spans.push(Span::styled(
+                format!(" {} ", tr("read-only")),
+                Theme::warning().add_modifier(Modifier::REVERSED),
+            ));
+        }
+        if let Some(host) = remote::current() {
+            header_spans.push(Span::styled(
+                format!(" @ {} ", host.name),
+                Theme::title().add_modifier(Modifier::REVERSED),
+            ));
+        }
+        let header = ratatui::widgets::Paragraph::new(Line::from(header_spans))
         .block(Block::default().borders(Borders::BOTTOM));
         frame.render_widget(header, layout.header);
 
         // Render tabs (two rows for F1-F6 and F7-F12)
         self.render_tabs(frame, layout.tabs);
 
-        // Content - render current component
-        match self.current_tab {
-            Tab::Updater => self.updater.render(frame, layout.content),
-            Tab::Sbotools => self.sbotools.render(frame, layout.content),
-            Tab::UserSetup => self.user_setup.render(frame, layout.content),
-            Tab::Mirror => self.mirror.render(frame, layout.content),
-            Tab::Packages => self.package_search.render(frame, layout.content),
-            Tab::Config => self.config_editor.render(frame, layout.content),
-            Tab::SysInfo => self.sysinfo.render(frame, layout.content),
-            Tab::Services => self.services.render(frame, layout.content),
-            Tab::PackageBrowser => self.package_browser.render(frame, layout.content),
-            Tab::Backup => self.backup.render(frame, layout.content),
-            Tab::Network => self.network.render(frame, layout.content),
-            Tab::Logs => self.logs.render(frame, layout.content),
-            Tab::Kernel => self.kernel.render(frame, layout.content),
-            Tab::Cron => self.cron.render(frame, layout.content),
-            Tab::Disks => self.disks.render(frame, layout.content),
-            Tab::Settings => self.settings.render(frame, layout.content),
+        // Content - render current component, and the other one of the split
+        match self.pane_areas(layout.content) {
+            (area, None) => self.render_tab(self.current_tab, frame, area),
+            (area, Some(other_area)) => {
+                self.render_pane(self.current_tab, true, frame, area);
+                if let Some(split) = &self.split {
+                    self.render_pane(split.other, false, frame, other_area);
+                }
+            }
         }
 
         // Status bar
-        let help = self.get_current_help();
-        let mut keys = vec![("Alt+←/→", "Tab"), ("Ctrl+Q", "Quit")];
+        let help = match (&self.wizard, &self.palette) {
+            _ if self.password.is_some() => vec![("Enter", tr("Confirm")), ("Esc", tr("Skip"))],
+            (Some(wizard), _) => wizard.help_text(),
+            (None, Some(palette)) => palette.help_text(),
+            (None, None) if self.search.is_some() => {
+                self.search.as_ref().map(|s| s.help_text()).unwrap_or_default()
+            }
+            (None, None) if self.show_help => vec![("↑/↓", tr("Scroll")), ("Esc", tr("Close"))],
+            (None, None) if self.undo.is_some() => vec![("Y/N", tr("Undo / Cancel"))],
+            (None, None) if self.hosts.is_some() => vec![
+                ("↑↓", tr("Select")),
+                ("Enter", tr("Connect")),
+                ("Esc", tr("Close")),
+            ],
+            (None, None) => self.help_for(self.current_tab),
+        };
+        let keymap = self.settings.keymap();
+        let tab_keys = format!(
+            "{}/{}",
+            keymap.label(Action::PrevTab),
+            keymap.label(Action::NextTab)
+        );
+        let quit_key = keymap.label(Action::Quit);
+        let mut keys = vec![(tab_keys.as_str(), tr("Tab")), (quit_key.as_str(), tr("Quit"))];
         keys.extend(help);
 
-        let status = StatusBar::new("").keys(keys);
+        let disabled = if self.read_only() {
+            self.mutating_keys()
+        } else {
+            Vec::new()
+        };
+        let status = StatusBar::new("").keys(keys).disabled(disabled);
         frame.render_widget(status, layout.status_bar);
 
+        if let Some(wizard) = &self.wizard {
+            wizard.render(frame, layout.content);
+        }
+        if let Some(palette) = &self.palette {
+            palette.render(frame, layout.content);
+        }
+        if let Some(search) = &self.search {
+            search.render(frame, layout.content);
+        }
+        if self.show_help {
+            self.render_help(frame, layout.content);
+        }
+        if let Some(dialog) = &self.undo {
+            Self::render_undo(dialog, frame, layout.content);
+        }
+        if let Some(picker) = &self.hosts {
+            Self::render_hosts(picker, frame, layout.content);
+        }
+        if let Some(prompt) = &self.password {
+            Self::render_password(prompt, frame, layout.content);
+        }
+        self.toasts.render(frame, layout.content);
+
         // Exit warning dialog (rendered on top of everything)
         if self.show_exit_warning {
-            self.render_exit_warning(frame, frame.area());
+            let bootloader_update = self.kernel.needs_bootloader_update();
+            Self::render_exit_warning(bootloader_update, frame, frame.area());
+        }
+
+        Theme::restyle(frame.buffer_mut());
+    }
+
+    fn render_tab(&self, tab: Tab, frame: &mut Frame, area: Rect) {
+        match tab {
+            Tab::Updater => self.updater.render(frame, area),
+            Tab::Sbotools => self.sbotools.render(frame, area),
+            Tab::UserSetup => self.user_setup.render(frame, area),
+            Tab::Mirror => self.mirror.render(frame, area),
+            Tab::Packages => self.package_search.render(frame, area),
+            Tab::Config => self.config_editor.render(frame, area),
+            Tab::SysInfo => self.sysinfo.render(frame, area),
+            Tab::Services => self.services.render(frame, area),
+            Tab::PackageBrowser => self.package_browser.render(frame, area),
+            Tab::Backup => self.backup.render(frame, area),
+            Tab::Network => self.network.render(frame, area),
+            Tab::Logs => self.logs.render(frame, area),
+            Tab::Kernel => self.kernel.render(frame, area),
+            Tab::Cron => self.cron.render(frame, area),
+            Tab::Disks => self.disks.render(frame, area),
+            Tab::Settings => self.settings.render(frame, area),
+            Tab::Tasks => self.tasks.render(frame, area),
+            Tab::Custom(i) => self.custom_tabs[i].render(frame, area),
         }
     }
 
+    /// One side of the split: a title row naming the tab, highlighted on
+    /// the side that has the keys, over the tab itself
+    fn render_pane(&self, tab: Tab, focused: bool, frame: &mut Frame, area: Rect) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        let (style, hint) = if focused {
+            (Theme::tab_active(), String::new())
+        } else {
+            let key = self.settings.keymap().label(Action::SwitchPane);
+            (Theme::tab_inactive(), format!(" ({})", key))
+        };
+        let title = ratatui::widgets::Paragraph::new(Line::from(vec![
+            Span::styled(format!(" {} ", tr(tab.title())), style),
+            Span::styled(hint, Theme::muted()),
+        ]));
+        frame.render_widget(title, rows[0]);
+        self.render_tab(tab, frame, rows[1]);
+    }
+
     fn render_tabs(&self, frame: &mut Frame, area: Rect) {
         // Split tabs area into two rows
         let chunks = Layout::default()
@@ -718,12 +1926,7 @@ impl App {
         let primary_tabs: Vec<Span> = Tab::primary_tabs()
             .iter()
             .map(|tab| {
-                let style = if *tab == self.current_tab {
-                    Theme::tab_active()
-                } else {
-                    Theme::tab_inactive()
-                };
-                Span::styled(format!(" {} {} ", tab.shortcut(), tab.title()), style)
+                Span::styled(self.tab_label(*tab), self.tab_style(*tab))
             })
             .collect();
 
@@ -734,12 +1937,7 @@ impl App {
         let mut secondary_spans: Vec<Span> = Tab::secondary_tabs()
             .iter()
             .map(|tab| {
-                let style = if *tab == self.current_tab {
-                    Theme::tab_active()
-                } else {
-                    Theme::tab_inactive()
-                };
-                Span::styled(format!(" {} {} ", tab.shortcut(), tab.title()), style)
+                Span::styled(self.tab_label(*tab), self.tab_style(*tab))
             })
             .collect();
 
@@ -747,20 +1945,41 @@ impl App {
         secondary_spans.push(Span::styled(" │ ", Theme::muted()));
 
         for tab in Tab::additional_tabs() {
-            let style = if tab == self.current_tab {
-                Theme::tab_active()
-            } else {
-                Theme::tab_inactive()
-            };
-            secondary_spans.push(Span::styled(format!(" {} {} ", tab.shortcut(), tab.title()), style));
+            secondary_spans.push(Span::styled(self.tab_label(tab), self.tab_style(tab)));
         }
 
         let secondary = ratatui::widgets::Paragraph::new(Line::from(secondary_spans));
         frame.render_widget(secondary, chunks[1]);
     }
 
-    fn get_current_help(&self) -> Vec<(&'static str, &'static str)> {
-        match self.current_tab {
+    /// A tab's entry in the tab bar, with the key bound to switch to it
+    /// The other pane's tab is underlined, closed tabs are greyed out
+    fn tab_style(&self, tab: Tab) -> Style {
+        if tab == self.current_tab {
+            Theme::tab_active()
+        } else if self.split.as_ref().is_some_and(|split| split.other == tab) {
+            Theme::tab_inactive().add_modifier(Modifier::UNDERLINED)
+        } else if !self.tab_open(tab) {
+            Theme::muted()
+        } else {
+            Theme::tab_inactive()
+        }
+    }
+
+    fn tab_label(&self, tab: Tab) -> String {
+        format!(" {} {} ", self.settings.keymap().label(Action::Goto(tab)), tr(tab.title()))
+    }
+
+    /// The keys of `tab` with what they do in the interface language
+    fn help_for(&self, tab: Tab) -> Vec<(&'static str, &'static str)> {
+        self.untranslated_help_for(tab)
+            .into_iter()
+            .map(|(key, description)| (key, tr(description)))
+            .collect()
+    }
+
+    fn untranslated_help_for(&self, tab: Tab) -> Vec<(&'static str, &'static str)> {
+        match tab {
             Tab::Updater => self.updater.help_text(),
             Tab::Sbotools => self.sbotools.help_text(),
             Tab::UserSetup => self.user_setup.help_text(),
@@ -777,11 +1996,313 @@ impl App {
             Tab::Cron => self.cron.help_text(),
             Tab::Disks => self.disks.help_text(),
             Tab::Settings => self.settings.help_text(),
+            Tab::Tasks => self.tasks.help_text(),
+            Tab::Custom(i) => self.custom_tabs[i].help_text(),
         }
     }
 
-    /// Render exit warning dialog
-    fn render_exit_warning(&self, frame: &mut Frame, area: Rect) {
+    /// Every tab, and every action in the tabs' help that is a single
+    /// key press, as the tabs stand now
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let mut entries: Vec<PaletteEntry> = Tab::all()
+            .into_iter()
+            .map(|tab| PaletteEntry {
+                label: trf("Go to {}", &[tr(tab.title())]),
+                tab,
+                key: None,
+            })
+            .collect();
+        for tab in Tab::all() {
+            for (key, description) in self.help_for(tab) {
+                // "a/d" and the like stand for several keys
+                if key.contains('/') && key != "/" {
+                    continue;
+                }
+                let Ok(key) = Key::parse(key) else { continue };
+                // Leaving or switching views is no action of its own
+                if matches!(key.code, KeyCode::Esc | KeyCode::Tab) {
+                    continue;
+                }
+                entries.push(PaletteEntry {
+                    label: format!("{}: {}", tr(tab.title()), description),
+                    tab,
+                    key: Some(KeyEvent::new(key.code, key.modifiers)),
+                });
+            }
+        }
+        entries
+    }
+
+    /// What the global search looks through: the items of every tab
+    /// that is open
+    fn search_entries(&self) -> Vec<(Tab, SearchItem)> {
+        Tab::all()
+            .into_iter()
+            .filter(|&tab| self.tab_open(tab))
+            .flat_map(|tab| self.search_items(tab).into_iter().map(move |item| (tab, item)))
+            .collect()
+    }
+
+    fn search_items(&self, tab: Tab) -> Vec<SearchItem> {
+        match tab {
+            Tab::Updater => self.updater.search_items(),
+            Tab::Sbotools => self.sbotools.search_items(),
+            Tab::UserSetup => self.user_setup.search_items(),
+            Tab::Mirror => self.mirror.search_items(),
+            Tab::Packages => self.package_search.search_items(),
+            Tab::Config => self.config_editor.search_items(),
+            Tab::SysInfo => self.sysinfo.search_items(),
+            Tab::Services => self.services.search_items(),
+            Tab::PackageBrowser => self.package_browser.search_items(),
+            Tab::Backup => self.backup.search_items(),
+            Tab::Network => self.network.search_items(),
+            Tab::Logs => self.logs.search_items(),
+            Tab::Kernel => self.kernel.search_items(),
+            Tab::Cron => self.cron.search_items(),
+            Tab::Disks => self.disks.search_items(),
+            Tab::Settings => self.settings.search_items(),
+            Tab::Tasks => self.tasks.search_items(),
+            Tab::Custom(i) => self.custom_tabs[i].search_items(),
+        }
+    }
+
+    fn select_item(&mut self, tab: Tab, id: &str) {
+        match tab {
+            Tab::Updater => self.updater.select_item(id),
+            Tab::Sbotools => self.sbotools.select_item(id),
+            Tab::UserSetup => self.user_setup.select_item(id),
+            Tab::Mirror => self.mirror.select_item(id),
+            Tab::Packages => self.package_search.select_item(id),
+            Tab::Config => self.config_editor.select_item(id),
+            Tab::SysInfo => self.sysinfo.select_item(id),
+            Tab::Services => self.services.select_item(id),
+            Tab::PackageBrowser => self.package_browser.select_item(id),
+            Tab::Backup => self.backup.select_item(id),
+            Tab::Network => self.network.select_item(id),
+            Tab::Logs => self.logs.select_item(id),
+            Tab::Kernel => self.kernel.select_item(id),
+            Tab::Cron => self.cron.select_item(id),
+            Tab::Disks => self.disks.select_item(id),
+            Tab::Settings => self.settings.select_item(id),
+            Tab::Tasks => self.tasks.select_item(id),
+            Tab::Custom(i) => self.custom_tabs[i].select_item(id),
+        }
+    }
+
+    /// The '?' help: what the tab does, every key the tab shows hints
+    /// for and the global keys as bound in Settings
+    fn help_lines(&self) -> Vec<Line<'static>> {
+        let key_line = |keys: String, description: String| {
+            Line::from(vec![
+                Span::styled(format!("  {:<16}", keys), Theme::key_hint()),
+                Span::raw(description),
+            ])
+        };
+        let mut lines = vec![
+            Line::from(tr(self.current_tab.description())),
+            Line::from(""),
+            Line::from(Span::styled(
+                trf("{} keys", &[tr(self.current_tab.title())]),
+                Theme::title(),
+            )),
+        ];
+        lines.extend(
+            self.help_for(self.current_tab)
+                .into_iter()
+                .map(|(key, description)| key_line(key.to_string(), description.to_string())),
+        );
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(tr("Global keys"), Theme::title())));
+        let keymap = self.settings.keymap();
+        for action in Action::all() {
+            let keys: Vec<String> = keymap.keys(action).iter().map(|k| k.to_string()).collect();
+            if !keys.is_empty() {
+                lines.push(key_line(keys.join(", "), action.description()));
+            }
+        }
+        lines
+    }
+
+    fn render_help(&self, frame: &mut Frame, area: Rect) {
+        use crate::ui::centered_rect;
+        use ratatui::widgets::{Clear, Paragraph, Wrap};
+
+        let dialog_area = centered_rect(70, 80, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let dialog = Block::default()
+            .title(format!(" {} ", trf("Help - {}", &[tr(self.current_tab.title())])))
+            .borders(Borders::ALL)
+            .border_style(Theme::title());
+        let inner = dialog.inner(dialog_area);
+        frame.render_widget(dialog, dialog_area);
+
+        let text = Paragraph::new(self.help_lines())
+            .wrap(Wrap { trim: false })
+            .scroll((self.help_scroll, 0));
+        frame.render_widget(text, inner);
+    }
+
+    fn render_undo(dialog: &UndoDialog, frame: &mut Frame, area: Rect) {
+        use crate::ui::centered_rect;
+        use ratatui::widgets::{Clear, Paragraph, Wrap};
+
+        let dialog_area = centered_rect(60, 50, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", tr("Undo Last Change")))
+            .borders(Borders::ALL)
+            .border_style(Theme::title());
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let lines = match dialog {
+            UndoDialog::Confirm(entry) => {
+                let mut lines = vec![
+                    Line::from(Span::styled(entry.description.as_str(), Theme::warning())),
+                    Line::from(Span::styled(entry.time.as_str(), Theme::muted())),
+                    Line::from(""),
+                ];
+                lines.extend(entry.files.iter().map(|file| {
+                    let action = if file.existed { tr("restore") } else { tr("remove") };
+                    Line::from(vec![
+                        Span::styled(format!("{:<8} ", action), Theme::muted()),
+                        Span::raw(file.path.display().to_string()),
+                    ])
+                }));
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled(tr("Put these files back?"), Theme::warning()),
+                    Span::raw(format!(" {}", tr("[Y]es / [N]o"))),
+                ]));
+                lines
+            }
+            UndoDialog::Done(message, is_error) => vec![
+                Line::from(Span::styled(
+                    message.as_str(),
+                    if *is_error { Theme::error() } else { Theme::success() },
+                )),
+                Line::from(""),
+                Line::from(Span::styled(tr("Press any key to close"), Theme::muted())),
+            ],
+        };
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+
+    fn render_password(prompt: &PasswordPrompt, frame: &mut Frame, area: Rect) {
+        use crate::ui::centered_rect;
+        use ratatui::widgets::{Clear, Paragraph, Wrap};
+
+        let tool = root::escalation().map_or("sudo", |tool| tool.program());
+        let dialog_area = centered_rect(60, 40, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", trf("Password for {}", &[tool])))
+            .borders(Borders::ALL)
+            .border_style(Theme::border_focused());
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let mut lines = vec![
+            Line::from(trf(
+                "Not running as root: commands that change the system run through {}.",
+                &[tool],
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(format!("{}: ", tr("Password"))),
+                Span::styled(
+                    format!("{}_", "*".repeat(prompt.input.chars().count())),
+                    Theme::input_active(),
+                ),
+            ]),
+        ];
+        if let Some(error) = &prompt.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(error.as_str(), Theme::error())));
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+
+    fn render_hosts(picker: &HostPicker, frame: &mut Frame, area: Rect) {
+        use crate::ui::centered_rect;
+        use ratatui::widgets::{Clear, Paragraph, Wrap};
+
+        let dialog_area = centered_rect(60, 50, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", tr("Hosts")))
+            .borders(Borders::ALL)
+            .border_style(Theme::border_focused());
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let current = remote::current();
+        let mut entries = vec![(tr("This machine").to_string(), String::new(), current.is_none())];
+        entries.extend(picker.hosts.iter().map(|host| {
+            (host.name.clone(), host.target.clone(), current.as_ref() == Some(host))
+        }));
+        let mut lines: Vec<Line> = entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, target, active))| {
+                let marker = if active { "● " } else { "  " };
+                let style = if i == picker.selected {
+                    Theme::list_selected()
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled(format!("{}{:<20}", marker, name), style),
+                    Span::styled(format!(" {}", target), Theme::muted()),
+                ])
+            })
+            .collect();
+        if picker.hosts.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                tr("Add hosts under [[remote_hosts]] in the settings file"),
+                Theme::muted(),
+            )));
+        }
+        if let Some(error) = &picker.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(error.as_str(), Theme::error())));
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+
+    /// Shown instead of everything else while the terminal is too small
+    fn render_too_small(frame: &mut Frame) {
+        use crate::ui::layout::{MIN_HEIGHT, MIN_WIDTH};
+        use ratatui::layout::Alignment;
+        use ratatui::widgets::{Paragraph, Wrap};
+
+        let area = frame.area();
+        let lines = vec![
+            Line::from(Span::styled(tr("Terminal too small"), Theme::warning())),
+            Line::from(trf("need {}x{}", &[&MIN_WIDTH.to_string(), &MIN_HEIGHT.to_string()])),
+            Line::from(Span::styled(
+                trf("now {}x{}", &[&area.width.to_string(), &area.height.to_string()]),
+                Theme::muted(),
+            )),
+        ];
+        // Vertically centered when there is room for it
+        let top = area.height.saturating_sub(lines.len() as u16) / 2;
+        let text_area = Rect::new(area.x, area.y + top, area.width, area.height - top);
+        let text = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .style(Theme::default());
+        frame.render_widget(text, text_area);
+    }
+
+    /// Render exit warning dialog, offering the Kernel tab's bootloader
+    /// update when `bootloader_update`, else the updater's lilo run
+    fn render_exit_warning(bootloader_update: bool, frame: &mut Frame, area: Rect) {
         use crate::ui::centered_rect;
         use ratatui::widgets::{Clear, Paragraph};
 
@@ -795,6 +2316,11 @@ impl App {
         let inner = dialog.inner(dialog_area);
         frame.render_widget(dialog, dialog_area);
 
+        let update = if bootloader_update {
+            " Update the bootloader now"
+        } else {
+            " Run lilo now"
+        };
         let text = Paragraph::new(vec![
             Line::from(""),
             Line::from(Span::styled(
@@ -817,7 +2343,7 @@ impl App {
             ]),
             Line::from(vec![
                 Span::styled("[L]", Theme::key_hint()),
-                Span::raw(" Run lilo now"),
+                Span::raw(update),
             ]),
             Line::from(vec![
                 Span::styled("[Esc]", Theme::key_hint()),
@@ -828,3 +2354,130 @@ impl App {
         frame.render_widget(text, inner);
     }
 }
+
+/// Create a user with a password, and switch to the graphical runlevel
+/// if asked, describing what was done
+async fn create_user(
+    executor: &CommandExecutor,
+    username: &str,
+    password: &str,
+    groups: &[String],
+    change_runlevel: bool,
+) -> Result<String, String> {
+    let groups_ref: Vec<&str> = groups.iter().map(|s| s.as_str()).collect();
+    let result = executor.useradd(username, &groups_ref, "/bin/bash").await;
+    if !result.success {
+        return Err(format!("Failed to create user: {}", result.stderr));
+    }
+
+    let result = executor.set_password(username, password).await;
+    if !result.success {
+        return Err(format!("Failed to set password: {}", result.stderr));
+    }
+
+    if change_runlevel {
+        use crate::slackware::config::SlackwareConfig;
+        if let Err(e) = SlackwareConfig::set_default_runlevel(4) {
+            return Err(format!("User created but runlevel change failed: {}", e));
+        }
+    }
+
+    Ok(format!(
+        "User '{}' created successfully!{}",
+        username,
+        if change_runlevel {
+            " Runlevel changed to 4."
+        } else {
+            ""
+        }
+    ))
+}
+
+async fn update_package_list(executor: &CommandExecutor) -> Result<(), String> {
+    let result = executor.slackpkg(&["update"]).await;
+    if result.success {
+        Ok(())
+    } else {
+        Err(format!("Package list update failed: {}", result.stderr))
+    }
+}
+
+/// Download and install kernel packages, returning the installed names.
+/// installpkg keeps the running kernel in place; only firmware, which
+/// is not tied to a kernel version, is upgraded.
+async fn install_kernel_packages(
+    executor: &CommandExecutor,
+    urls: &[String],
+) -> Result<Vec<String>, String> {
+    let mut installed = Vec::new();
+    for url in urls {
+        let file = url.rsplit('/').next().unwrap_or(url);
+        let path = format!("/tmp/{}", file);
+        let result = executor.download_file(url, &path).await;
+        if !result.success {
+            return Err(format!("download of {} failed: {}", file, result.stderr.trim()));
+        }
+        let result = if file.starts_with("kernel-firmware-") {
+            executor.execute("upgradepkg", &["--install-new", &path]).await
+        } else {
+            executor.installpkg(&path).await
+        };
+        let _ = std::fs::remove_file(&path);
+        if !result.success {
+            return Err(format!("{}: {}", file, result.stderr.trim()));
+        }
+        installed.push(file.trim_end_matches(".txz").to_string());
+    }
+    Ok(installed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::{assert_snapshot, render, text};
+
+    #[test]
+    fn test_exit_warning() {
+        let buffer = render(80, 24, |frame| {
+            App::render_exit_warning(false, frame, frame.area())
+        });
+        assert_snapshot("exit_warning_lilo", &buffer);
+        let buffer = render(80, 24, |frame| {
+            App::render_exit_warning(true, frame, frame.area())
+        });
+        assert_snapshot("exit_warning_bootloader", &buffer);
+    }
+
+    #[test]
+    fn test_mutating_keys() {
+        let mut app = App::new(SlackwareVersion::Current);
+        let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        app.current_tab = Tab::Network;
+        assert!(app.is_mutating(&press(KeyCode::Char('u'))));
+        assert!(app.is_mutating(&press(KeyCode::Char('d'))));
+        assert!(!app.is_mutating(&press(KeyCode::Char('i'))));
+        assert!(!app.is_mutating(&press(KeyCode::Tab)));
+        app.current_tab = Tab::Kernel;
+        assert!(app.is_mutating(&press(KeyCode::Enter)));
+        assert!(!app.is_mutating(&press(KeyCode::Char('c'))));
+    }
+
+    #[tokio::test]
+    async fn test_progress_reaches_its_tab() {
+        let mut app = App::new(SlackwareVersion::Current);
+        app.switch_to_tab(Tab::Logs);
+        app.spawn("slackpkg update".to_string(), Tab::Updater, None, |_| async {});
+        let (tx, output_rx) = mpsc::unbounded_channel();
+        app.jobs[0].output_rx = output_rx;
+        tx.send("Downloading ChangeLog.txt".to_string()).unwrap();
+
+        app.on_tick();
+        while let Ok((tab, line)) = app.progress_rx.try_recv() {
+            app.update(Message::ProgressUpdate(tab, line)).await;
+        }
+        assert_eq!(app.current_tab, Tab::Logs);
+        assert_eq!(app.jobs[0].output, ["Downloading ChangeLog.txt"]);
+        let buffer = render(100, 30, |frame| app.updater.render(frame, frame.area()));
+        assert!(text(&buffer).contains("Downloading ChangeLog.txt"));
+    }
+}
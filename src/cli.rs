@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context};
+use clap::{ArgGroup, Parser};
+
+use crate::components::backup::BackupComponent;
+use crate::components::package_browser::{InstalledPackage, PackageBrowserComponent};
+use crate::daemon;
+use crate::slackware::audit;
+
+const PACKAGES_DIR: &str = "/var/log/packages";
+
+/// What commands run from the command line are recorded under in the audit log
+const AUDIT_TAB: &str = "command line";
+
+/// slackpkg exits with 20 when there was nothing to install or upgrade
+pub const SLACKPKG_NOTHING_TO_DO: i32 = 20;
+
+/// The slackpkg commands of a full update, in order
+pub const UPDATE_STEPS: [&str; 4] = ["update", "install-new", "upgrade-all", "clean-system"];
+
+/// Manage Slackware from scripts and cron. Without an action the TUI starts.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+#[command(group(ArgGroup::new("action").args([
+    "update",
+    "list_packages",
+    "backup",
+    "service",
+    "daemon",
+])))]
+pub struct Cli {
+    /// Run slackpkg update, install-new, upgrade-all and clean-system
+    #[arg(long)]
+    pub update: bool,
+
+    /// Answer yes to slackpkg's questions (batch mode) instead of asking
+    #[arg(short, long, requires = "update")]
+    pub yes: bool,
+
+    /// List installed packages
+    #[arg(long)]
+    pub list_packages: bool,
+
+    /// Print --list-packages as JSON
+    #[arg(long, requires = "list_packages")]
+    pub json: bool,
+
+    /// Back up the configuration files, like a full backup from the Backup tab
+    #[arg(long)]
+    pub backup: bool,
+
+    /// Run an rc.d service script, e.g. `--service restart sshd`
+    #[arg(long, num_args = 2, value_names = ["ACTION", "NAME"])]
+    pub service: Option<Vec<String>>,
+
+    /// Answer JSON-RPC requests on a Unix socket: list_packages,
+    /// start_update, update_status and service
+    #[arg(long)]
+    pub daemon: bool,
+
+    /// Socket the daemon listens on
+    #[arg(long, requires = "daemon", default_value = daemon::SOCKET)]
+    pub socket: PathBuf,
+}
+
+impl Cli {
+    /// Whether an action was asked for, rather than the TUI
+    pub fn has_action(&self) -> bool {
+        self.update || self.list_packages || self.backup || self.service.is_some() || self.daemon
+    }
+
+    /// Listing packages is the only action an ordinary user can run
+    pub fn needs_root(&self) -> bool {
+        !self.list_packages
+    }
+
+    /// Run the requested action, returning the exit code
+    pub fn run(&self) -> anyhow::Result<i32> {
+        if self.update {
+            update(self.yes)
+        } else if self.list_packages {
+            list_packages(self.json)
+        } else if self.backup {
+            backup()
+        } else if let Some(args) = &self.service {
+            service(&args[0], &args[1])
+        } else if self.daemon {
+            daemon::serve(&self.socket)
+        } else {
+            Ok(0)
+        }
+    }
+}
+
+/// Installed kernel packages, to tell whether an upgrade touched them
+pub fn kernel_packages() -> Vec<String> {
+    let mut packages: Vec<String> = fs::read_dir(PACKAGES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| name.starts_with("kernel-"))
+                .collect()
+        })
+        .unwrap_or_default();
+    packages.sort();
+    packages
+}
+
+fn update(yes: bool) -> anyhow::Result<i32> {
+    let kernels = kernel_packages();
+    for step in UPDATE_STEPS {
+        println!("==> slackpkg {}", step);
+        let mut args = Vec::new();
+        if yes {
+            args.extend(["-batch=on", "-default_answer=y"]);
+        }
+        args.push(step);
+        let status = Command::new("slackpkg").args(&args).status();
+        record("slackpkg", &args, status.as_ref().ok().and_then(|s| s.code()));
+        let status = status.context("failed to run slackpkg")?;
+        match status.code() {
+            Some(0) | Some(SLACKPKG_NOTHING_TO_DO) => {}
+            code => {
+                eprintln!("slackpkg {} failed", step);
+                return Ok(code.unwrap_or(1));
+            }
+        }
+    }
+    if kernel_packages() != kernels {
+        // The TUI asks before touching the bootloader; a script should decide itself
+        println!("Kernel packages changed: update the bootloader (lilo or grub-mkconfig) before rebooting.");
+    }
+    Ok(0)
+}
+
+/// The packages in /var/log/packages, sorted by name
+pub fn installed_packages() -> anyhow::Result<Vec<InstalledPackage>> {
+    let mut names: Vec<String> = fs::read_dir(PACKAGES_DIR)
+        .with_context(|| format!("failed to read {}", PACKAGES_DIR))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    Ok(names
+        .iter()
+        .filter_map(|name| PackageBrowserComponent::parse_package_name(name))
+        .collect())
+}
+
+/// A package as --list-packages --json and the daemon give it
+pub fn package_json(package: &InstalledPackage) -> serde_json::Value {
+    serde_json::json!({
+        "name": package.name,
+        "version": package.version,
+        "arch": package.arch,
+        "build": package.build,
+    })
+}
+
+fn list_packages(json: bool) -> anyhow::Result<i32> {
+    let packages = installed_packages()?;
+    if json {
+        let packages: Vec<serde_json::Value> = packages.iter().map(package_json).collect();
+        println!("{}", serde_json::to_string_pretty(&packages)?);
+    } else {
+        for package in packages {
+            println!("{}", package.full_name);
+        }
+    }
+    Ok(0)
+}
+
+fn backup() -> anyhow::Result<i32> {
+    let (message, is_error) = BackupComponent::new().backup_now();
+    if is_error {
+        eprintln!("{}", message);
+        Ok(1)
+    } else {
+        println!("{}", message);
+        Ok(0)
+    }
+}
+
+/// The rc.d script behind `name`, checking that `action` is one it takes
+pub fn service_script(action: &str, name: &str) -> anyhow::Result<PathBuf> {
+    if !["start", "stop", "restart", "status"].contains(&action) {
+        bail!(
+            "unknown service action '{}': use start, stop, restart or status",
+            action
+        );
+    }
+    let script = if name.starts_with("rc.") {
+        name.to_string()
+    } else {
+        format!("rc.{}", name)
+    };
+    if script.contains('/') {
+        bail!("invalid service name '{}'", name);
+    }
+    let path = Path::new("/etc/rc.d").join(&script);
+    if !path.exists() {
+        bail!("no such service: {}", path.display());
+    }
+    Ok(path)
+}
+
+fn service(action: &str, name: &str) -> anyhow::Result<i32> {
+    let path = service_script(action, name)?;
+    let status = Command::new(&path).arg(action).status();
+    let program = path.to_string_lossy();
+    record(&program, &[action], status.as_ref().ok().and_then(|s| s.code()));
+    let status = status.with_context(|| format!("failed to run {}", path.display()))?;
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Append a command to the audit log, warning when that fails
+pub fn record(program: &str, args: &[&str], exit_code: Option<i32>) {
+    let argv = std::iter::once(program)
+        .chain(args.iter().copied())
+        .map(str::to_string)
+        .collect();
+    if let Err(e) = audit::record(AUDIT_TAB, argv, exit_code) {
+        eprintln!("warning: could not write {}: {}", audit::AUDIT_LOG, e);
+    }
+}
@@ -7,14 +7,35 @@ use ratatui::{
     Frame,
 };
 use std::fs;
+use std::io::{ErrorKind, Write};
+use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
 
 use crate::app::Message;
+use crate::components::package_browser::PackageBrowserComponent;
+use crate::components::settings::{AppSettings, BackupSettings};
+use crate::components::settings;
 use crate::components::Component;
+use crate::slackware::audit::{self, shell_quote};
+use crate::slackware::config::Transaction;
+use crate::slackware::proxy::ProxySettings;
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::{root, scratch};
 
 const BACKUP_DIR: &str = "/var/backups/slackware-cli-manager";
+const MANIFEST_FILE: &str = "manifest.json";
+const PACKAGES_FILE: &str = "packages.txt";
+const CHECKSUM_FILE: &str = "SHA256SUMS";
+const PACKAGES_DIR: &str = "/var/log/packages";
+const PROFILE_DIR: &str = "/etc/slackware-cli-manager/backup-profiles";
+const SNAPSHOT_ARCHIVE: &str = "etc.tar.gz";
+
+/// Paths always left out of full /etc snapshots (tar exclude patterns)
+const SNAPSHOT_EXCLUDES: &[&str] = &["etc/mtab", "etc/ld.so.cache", "*.new", "*.orig", "*~"];
 
 /// Predefined config files to backup
 const CONFIG_FILES: &[(&str, &str)] = &[
@@ -34,6 +55,64 @@ const CONFIG_FILES: &[(&str, &str)] = &[
     ("/etc/resolv.conf", "DNS configuration"),
 ];
 
+/// A named, portable set of files to back up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupProfile {
+    pub name: String,
+    pub files: Vec<String>,
+}
+
+impl BackupProfile {
+    /// Resolve a profile argument: a bare name refers to the profile
+    /// directory, anything containing '/' is treated as a file path
+    pub fn resolve_path(name_or_path: &str) -> PathBuf {
+        if name_or_path.contains('/') {
+            PathBuf::from(name_or_path)
+        } else {
+            PathBuf::from(PROFILE_DIR).join(format!("{}.toml", name_or_path))
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        toml::from_str(&content).map_err(|e| e.to_string())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            root::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        root::write_file(path, content.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    /// Names of profiles saved in the profile directory
+    pub fn available() -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(PROFILE_DIR)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .filter_map(|e| {
+                        e.file_name()
+                            .to_string_lossy()
+                            .strip_suffix(".toml")
+                            .map(|n| n.to_string())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+}
+
+/// Text prompt for profile export/import
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfilePrompt {
+    Export,
+    Import,
+}
+
 /// Backup entry information
 #[derive(Debug, Clone)]
 pub struct BackupEntry {
@@ -42,6 +121,433 @@ pub struct BackupEntry {
     pub timestamp: DateTime<Local>,
     pub size: u64,
     pub file_count: usize,
+    pub manifest: Option<BackupManifest>,
+    pub packages: Option<Vec<String>>,
+}
+
+impl BackupEntry {
+    pub fn kind(&self) -> BackupKind {
+        self.manifest
+            .as_ref()
+            .map(|m| m.kind)
+            .unwrap_or(BackupKind::Full)
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.manifest
+            .as_ref()
+            .is_some_and(|m| m.files.iter().any(|f| f.encrypted))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupKind {
+    #[default]
+    Full,
+    Incremental,
+    /// Tarball of the entire /etc directory
+    Snapshot,
+}
+
+/// A file recorded in a backup manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Original absolute path on the system
+    pub path: String,
+    /// File name inside the backup directory
+    pub stored_as: String,
+    /// Backup directory that holds the file content
+    pub stored_in: String,
+    pub mtime: u64,
+    pub size: u64,
+    /// Original file permission bits
+    #[serde(default)]
+    pub mode: Option<u32>,
+    /// Stored copy is GPG-encrypted
+    #[serde(default)]
+    pub encrypted: bool,
+}
+
+/// Describes the complete file set captured by a backup. Incremental
+/// backups only store changed files and point at their base for the rest,
+/// so a manifest always describes the full point-in-time state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub kind: BackupKind,
+    pub base: Option<String>,
+    pub files: Vec<ManifestEntry>,
+}
+
+impl BackupManifest {
+    pub fn load(backup_path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(backup_path.join(MANIFEST_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, backup_path: &Path) -> std::io::Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))?;
+        root::write_file(&backup_path.join(MANIFEST_FILE), content.as_bytes())
+    }
+
+    fn find(&self, path: &str) -> Option<&ManifestEntry> {
+        self.files.iter().find(|f| f.path == path)
+    }
+}
+
+/// Remote backup destination reached over SSH
+#[derive(Debug, Clone)]
+pub struct RemoteTarget {
+    pub host: String,
+    pub path: String,
+    pub ssh_key: Option<String>,
+}
+
+impl RemoteTarget {
+    /// Build a remote target from settings, if one is configured
+    pub fn from_settings(settings: &BackupSettings) -> Option<Self> {
+        let (host, path) = settings.remote_target.trim().split_once(':')?;
+        if host.is_empty() || path.is_empty() {
+            return None;
+        }
+
+        let ssh_key = settings.ssh_key.trim();
+        Some(Self {
+            host: host.to_string(),
+            path: path.trim_end_matches('/').to_string(),
+            ssh_key: if ssh_key.is_empty() {
+                None
+            } else {
+                Some(ssh_key.to_string())
+            },
+        })
+    }
+
+    pub fn display(&self) -> String {
+        format!("{}:{}", self.host, self.path)
+    }
+
+    /// Options shared by ssh and scp. BatchMode keeps ssh from prompting
+    /// for a password underneath the TUI.
+    fn ssh_options(&self) -> Vec<String> {
+        let mut opts = vec!["-o".to_string(), "BatchMode=yes".to_string()];
+        if let Some(key) = &self.ssh_key {
+            opts.push("-i".to_string());
+            opts.push(key.clone());
+        }
+        opts
+    }
+
+    /// The ssh command line rsync's `-e` hands to the shell, every word
+    /// quoted so key paths with spaces survive
+    fn ssh_command(&self) -> String {
+        std::iter::once("ssh".to_string())
+            .chain(self.ssh_options())
+            .map(|word| shell_quote(&word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn ssh(&self, remote_cmd: &str) -> std::io::Result<Output> {
+        Command::new("ssh")
+            .args(self.ssh_options())
+            .arg(&self.host)
+            .arg(remote_cmd)
+            .stdin(Stdio::null())
+            .output()
+    }
+
+    /// Copy a directory with rsync, falling back to scp when rsync is missing.
+    /// The local side holds root-only files, so the copy is retried through
+    /// sudo or doas when it is denied.
+    fn transfer(&self, source: &str, dest: &str) -> Result<(), String> {
+        let ssh_cmd = self.ssh_command();
+        let run = |escalated: bool| {
+            let command = |program: &str| {
+                if escalated {
+                    root::command(program)
+                } else {
+                    Command::new(program)
+                }
+            };
+            let output = match command("rsync")
+                .args(["-az", "-e", &ssh_cmd, source, dest])
+                .stdin(Stdio::null())
+                .output()
+            {
+                Err(e) if e.kind() == ErrorKind::NotFound => command("scp")
+                    .args(self.ssh_options())
+                    .args(["-r", source, dest])
+                    .stdin(Stdio::null())
+                    .output(),
+                other => other,
+            }?;
+            if output.status.success() {
+                return Ok(());
+            }
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let kind = if stderr.contains("Permission denied") {
+                ErrorKind::PermissionDenied
+            } else {
+                ErrorKind::Other
+            };
+            Err(std::io::Error::new(kind, stderr))
+        };
+
+        root::or_escalated(|| run(false), || run(true)).map_err(|e| e.to_string())
+    }
+
+    fn check(output: std::io::Result<Output>) -> Result<(), String> {
+        match output {
+            Ok(output) if output.status.success() => Ok(()),
+            Ok(output) => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Upload a local backup directory to the remote destination
+    pub fn push(&self, backup_path: &Path) -> Result<(), String> {
+        Self::check(self.ssh(&format!("mkdir -p -- {}", shell_quote(&self.path))))?;
+        self.transfer(
+            &backup_path.to_string_lossy(),
+            &format!("{}:{}/", self.host, self.path),
+        )
+    }
+
+    /// List backup names stored on the remote destination
+    pub fn list(&self) -> Result<Vec<String>, String> {
+        let output = self
+            .ssh(&format!("ls -1 -- {}", shell_quote(&self.path)))
+            .map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        let mut names: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| l.starts_with("backup_"))
+            .collect();
+        names.sort_by(|a, b| b.cmp(a));
+        Ok(names)
+    }
+
+    /// Download a remote backup into the local backup directory
+    pub fn fetch(&self, name: &str) -> Result<PathBuf, String> {
+        self.transfer(
+            &format!("{}:{}/{}", self.host, self.path, name),
+            &format!("{}/", BACKUP_DIR),
+        )?;
+        Ok(PathBuf::from(BACKUP_DIR).join(name))
+    }
+}
+
+/// A backed-up file and where its stored copy lives
+#[derive(Debug, Clone)]
+pub struct RestoreFile {
+    pub path: String,
+    pub source: PathBuf,
+    pub mode: Option<u32>,
+    pub encrypted: bool,
+}
+
+impl RestoreFile {
+    /// Read the stored copy, decrypting it if necessary
+    fn read_stored(&self) -> Result<Vec<u8>, String> {
+        let stored = root::read_file(&self.source).map_err(|e| e.to_string())?;
+        if !self.encrypted {
+            return Ok(stored);
+        }
+        gpg(&["--batch", "--quiet", "--decrypt"], &stored)
+    }
+
+    /// Write the stored copy back to its original location
+    fn restore(&self) -> Result<(), String> {
+        let path = Path::new(&self.path);
+        if self.encrypted {
+            let content = self.read_stored()?;
+            root::write_file(path, &content).map_err(|e| e.to_string())?;
+        } else {
+            root::copy(&self.source, path).map_err(|e| e.to_string())?;
+        }
+
+        // Stored copies are private, so the mode comes from the manifest
+        if let Some(mode) = self.mode {
+            root::set_mode(path, mode).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Store a copy of `source` in the backup directory, encrypting it to
+/// `recipient` with GPG when one is configured. Returns the stored file name.
+fn store_file(
+    source: &Path,
+    backup_path: &Path,
+    dest_name: &str,
+    recipient: Option<&str>,
+) -> Result<String, String> {
+    let content = root::read_file(source).map_err(|e| e.to_string())?;
+    store_content(&content, backup_path, dest_name, recipient)
+}
+
+/// Store `content` as `dest_name` in the backup directory, encrypted when
+/// there is a `recipient`. The plain content never touches the disk outside
+/// the backup, and the stored file is readable by its owner only.
+fn store_content(
+    content: &[u8],
+    backup_path: &Path,
+    dest_name: &str,
+    recipient: Option<&str>,
+) -> Result<String, String> {
+    let (stored_as, stored) = match recipient {
+        Some(recipient) => {
+            let args = ["--batch", "--yes", "--trust-model", "always", "--encrypt", "-r", recipient];
+            (format!("{}.gpg", dest_name), gpg(&args, content)?)
+        }
+        None => (dest_name.to_string(), content.to_vec()),
+    };
+    root::write_private(&backup_path.join(&stored_as), &stored).map_err(|e| e.to_string())?;
+    Ok(stored_as)
+}
+
+/// Run gpg over `input` on stdin and return what it writes to stdout
+fn gpg(args: &[&str], input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut child = Command::new("gpg")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run gpg: {}", e))?;
+    // Fed from the side so gpg never blocks on a full stdout
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_vec();
+        std::thread::spawn(move || stdin.write_all(&input))
+    });
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// How a backed-up file compares to the live system file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileChange {
+    Modified,
+    Unchanged,
+    Missing,
+}
+
+impl FileChange {
+    fn detect(file: &RestoreFile) -> Self {
+        match (root::read_file(Path::new(&file.path)), file.read_stored()) {
+            (Err(_), _) => FileChange::Missing,
+            (Ok(live), Ok(stored)) if live == stored => FileChange::Unchanged,
+            _ => FileChange::Modified,
+        }
+    }
+
+    fn label(&self) -> (&'static str, Color) {
+        match self {
+            FileChange::Modified => ("modified", Theme::colors().warning),
+            FileChange::Unchanged => ("identical", Theme::colors().muted),
+            FileChange::Missing => ("missing", Theme::colors().error),
+        }
+    }
+}
+
+/// Per-file review of a backup before it is restored
+struct RestorePreview {
+    backup_path: PathBuf,
+    files: Vec<(RestoreFile, bool, FileChange)>, // file, selected, change
+    list_state: ListState,
+    diff: Vec<String>,
+    diff_scroll: u16,
+    /// Show the backed-up file content instead of the diff
+    show_content: bool,
+    /// Scratch directory a snapshot archive was extracted into
+    extract_dir: Option<PathBuf>,
+}
+
+impl Drop for RestorePreview {
+    fn drop(&mut self) {
+        if let Some(dir) = &self.extract_dir {
+            // tar ran as root, so what it unpacked may be root's
+            let _ = root::remove(dir);
+        }
+    }
+}
+
+impl RestorePreview {
+    /// Run `diff -u` between a live file and stored content fed on stdin
+    fn run_diff(live: &str, stored: &[u8]) -> std::io::Result<String> {
+        let mut child = Command::new("diff")
+            .args(["-u", "--label", "current", "--label", "backup", live, "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(stored)?;
+        }
+        let output = child.wait_with_output()?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn selected_file(&self) -> Option<&RestoreFile> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.files.get(i))
+            .map(|(file, _, _)| file)
+    }
+
+    fn selected_files(&self) -> Vec<RestoreFile> {
+        self.files
+            .iter()
+            .filter(|(_, selected, _)| *selected)
+            .map(|(file, _, _)| file.clone())
+            .collect()
+    }
+
+    /// Diff the live file against the backed-up copy (live → backup),
+    /// or load the backed-up content when browsing
+    fn update_diff(&mut self) {
+        self.diff_scroll = 0;
+        self.diff = match self.selected_file() {
+            Some(file) if self.show_content => match file.read_stored() {
+                Ok(content) => String::from_utf8_lossy(&content)
+                    .lines()
+                    .map(|l| l.to_string())
+                    .collect(),
+                Err(e) => vec![trf("Failed to read backup copy: {}", &[&e.to_string()])],
+            },
+            Some(file) => {
+                let live = if Path::new(&file.path).exists() {
+                    file.path.clone()
+                } else {
+                    "/dev/null".to_string()
+                };
+                match file.read_stored() {
+                    Ok(stored) => match Self::run_diff(&live, &stored) {
+                        Ok(output) if output.is_empty() => {
+                            vec!["Files are identical".to_string()]
+                        }
+                        Ok(output) => output.lines().map(|l| l.to_string()).collect(),
+                        Err(e) => vec![trf("Failed to run diff: {}", &[&e.to_string()])],
+                    },
+                    Err(e) => vec![trf("Failed to read backup copy: {}", &[&e.to_string()])],
+                }
+            }
+            None => Vec::new(),
+        };
+    }
 }
 
 /// Backup & Restore Component
@@ -49,7 +555,13 @@ pub struct BackupComponent {
     mode: BackupMode,
     config_files: Vec<(String, String, bool)>, // path, description, selected
     backups: Vec<BackupEntry>,
+    settings: BackupSettings,
+    disk_free: Option<u64>,
+    remote: Option<RemoteTarget>,
+    remote_backups: Vec<String>,
     list_state: ListState,
+    preview: Option<RestorePreview>,
+    profile_prompt: Option<(ProfilePrompt, String)>,
     status_message: Option<(String, bool)>,
     show_confirm: bool,
     pending_action: Option<BackupAction>,
@@ -59,13 +571,24 @@ pub struct BackupComponent {
 pub enum BackupMode {
     Create,
     Restore,
+    Remote,
 }
 
 #[derive(Debug, Clone)]
 pub enum BackupAction {
-    CreateBackup,
-    RestoreBackup(PathBuf),
+    CreateBackup(BackupKind),
+    RestoreFiles(Vec<RestoreFile>),
     DeleteBackup(PathBuf),
+    PushRemote(PathBuf),
+    RestoreRemote(String),
+    ReinstallPackages(Vec<String>),
+}
+
+impl BackupAction {
+    /// Anything that overwrites or deletes files
+    fn always_confirm(&self) -> bool {
+        !matches!(self, BackupAction::CreateBackup(_) | BackupAction::PushRemote(_))
+    }
 }
 
 impl BackupComponent {
@@ -75,11 +598,18 @@ impl BackupComponent {
             .map(|(path, desc)| (path.to_string(), desc.to_string(), true))
             .collect();
 
+        let settings = AppSettings::load().backup;
         let mut component = Self {
             mode: BackupMode::Create,
             config_files,
             backups: Vec::new(),
+            remote: RemoteTarget::from_settings(&settings),
+            settings,
+            disk_free: None,
+            remote_backups: Vec::new(),
             list_state: ListState::default(),
+            preview: None,
+            profile_prompt: None,
             status_message: None,
             show_confirm: false,
             pending_action: None,
@@ -89,13 +619,13 @@ impl BackupComponent {
     }
 
     fn ensure_backup_dir(&self) -> std::io::Result<()> {
-        fs::create_dir_all(BACKUP_DIR)
+        root::create_dir_all(Path::new(BACKUP_DIR))
     }
 
     fn load_backups(&mut self) {
         self.backups.clear();
 
-        if let Err(_) = self.ensure_backup_dir() {
+        if self.ensure_backup_dir().is_err() {
             return;
         }
 
@@ -118,6 +648,10 @@ impl BackupComponent {
 
                         // Count files and calculate size
                         let (file_count, size) = Self::calculate_backup_stats(&path);
+                        let manifest = BackupManifest::load(&path);
+                        let packages = fs::read_to_string(path.join(PACKAGES_FILE))
+                            .ok()
+                            .map(|content| content.lines().map(|l| l.to_string()).collect());
 
                         self.backups.push(BackupEntry {
                             name,
@@ -125,13 +659,24 @@ impl BackupComponent {
                             timestamp,
                             size,
                             file_count,
+                            manifest,
+                            packages,
                         });
                     }
                 }
             }
         }
 
-        self.backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        self.backups.sort_by_key(|b| std::cmp::Reverse(b.timestamp));
+        self.disk_free = Self::free_space();
+    }
+
+    /// Free space on the filesystem holding the backup directory
+    fn free_space() -> Option<u64> {
+        let output = Command::new("df").args(["-Pk", BACKUP_DIR]).output().ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let kb: u64 = stdout.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+        Some(kb * 1024)
     }
 
     fn calculate_backup_stats(path: &Path) -> (usize, u64) {
@@ -140,6 +685,10 @@ impl BackupComponent {
 
         if let Ok(entries) = fs::read_dir(path) {
             for entry in entries.filter_map(|e| e.ok()) {
+                let name = entry.file_name();
+                if name == MANIFEST_FILE || name == PACKAGES_FILE || name == CHECKSUM_FILE {
+                    continue;
+                }
                 if let Ok(metadata) = entry.metadata() {
                     count += 1;
                     size += metadata.len();
@@ -150,21 +699,76 @@ impl BackupComponent {
         (count, size)
     }
 
-    fn create_backup(&mut self) -> Option<Message> {
+    fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+        let metadata = fs::metadata(path).ok()?;
+        let mtime = metadata
+            .modified()
+            .ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        Some((mtime, metadata.len()))
+    }
+
+    /// Most recent full backup, used as the base for incremental backups
+    fn latest_full_backup(&self) -> Option<&BackupEntry> {
+        self.backups
+            .iter()
+            .filter(|b| b.manifest.is_some() && b.kind() == BackupKind::Full)
+            .max_by_key(|b| b.timestamp)
+    }
+
+    /// Take a full backup right away, as `--backup` on the command line
+    /// does, returning the status message
+    pub fn backup_now(&mut self) -> (String, bool) {
+        self.create_backup(BackupKind::Full);
+        self.status_message
+            .take()
+            .unwrap_or_else(|| (tr("Backup finished").to_string(), false))
+    }
+
+    fn create_backup(&mut self, kind: BackupKind) -> Option<Message> {
         if let Err(e) = self.ensure_backup_dir() {
-            self.status_message = Some((format!("Failed to create backup directory: {}", e), true));
+            self.status_message =
+                Some((trf("Failed to create backup directory: {}", &[&e.to_string()]), true));
             return None;
         }
 
+        let base = match kind {
+            BackupKind::Snapshot => return self.create_snapshot(),
+            BackupKind::Full => None,
+            BackupKind::Incremental => match self.latest_full_backup() {
+                Some(base) => Some((base.name.clone(), base.manifest.clone().unwrap_or_default())),
+                None => {
+                    self.status_message = Some((
+                        tr("No full backup found to base an incremental backup on").to_string(),
+                        true,
+                    ));
+                    return None;
+                }
+            },
+        };
+
         let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-        let backup_path = PathBuf::from(BACKUP_DIR).join(format!("backup_{}", timestamp));
+        let backup_name = format!("backup_{}", timestamp);
+        let backup_path = PathBuf::from(BACKUP_DIR).join(&backup_name);
 
-        if let Err(e) = fs::create_dir_all(&backup_path) {
-            self.status_message = Some((format!("Failed to create backup: {}", e), true));
+        if let Err(e) = root::create_dir_all(&backup_path) {
+            self.status_message =
+                Some((trf("Failed to create backup: {}", &[&e.to_string()]), true));
             return None;
         }
 
+        let recipient = Some(self.settings.gpg_recipient.trim().to_string())
+            .filter(|r| !r.is_empty());
+
+        let mut manifest = BackupManifest {
+            kind,
+            base: base.as_ref().map(|(name, _)| name.clone()),
+            files: Vec::new(),
+        };
         let mut backed_up = 0;
+        let mut unchanged = 0;
         let mut failed = 0;
 
         for (path, _, selected) in &self.config_files {
@@ -172,83 +776,740 @@ impl BackupComponent {
                 continue;
             }
 
-            let source = Path::new(path);
-            if !source.exists() {
-                continue;
-            }
+            let source = Path::new(path);
+            let Some((mtime, size)) = Self::file_stamp(source) else {
+                continue;
+            };
+
+            // Unchanged since the base backup: reference its copy instead
+            if let Some(prev) = base
+                .as_ref()
+                .and_then(|(_, m)| m.find(path))
+                .filter(|prev| prev.mtime == mtime && prev.size == size)
+            {
+                manifest.files.push(prev.clone());
+                unchanged += 1;
+                continue;
+            }
+
+            // Create destination path preserving directory structure
+            let dest_name = path.replace('/', "_").trim_start_matches('_').to_string();
+
+            match store_file(source, &backup_path, &dest_name, recipient.as_deref()) {
+                Ok(stored_as) => {
+                    backed_up += 1;
+                    manifest.files.push(ManifestEntry {
+                        path: path.clone(),
+                        stored_as,
+                        stored_in: backup_name.clone(),
+                        mtime,
+                        size,
+                        mode: fs::metadata(source).ok().map(|m| m.permissions().mode()),
+                        encrypted: recipient.is_some(),
+                    });
+                }
+                Err(_) => failed += 1,
+            }
+        }
+
+        if manifest.files.is_empty() {
+            self.status_message = Some((tr("No files were backed up").to_string(), true));
+            // Remove empty backup directory
+            let _ = root::remove(&backup_path);
+            return None;
+        }
+
+        if let Err(e) = manifest.save(&backup_path) {
+            self.status_message =
+                Some((trf("Failed to write backup manifest: {}", &[&e.to_string()]), true));
+            return None;
+        }
+
+        // Snapshot the installed package set for disaster recovery
+        let packages = Self::installed_packages();
+        if !packages.is_empty() {
+            let list = packages.join("\n") + "\n";
+            let _ = root::write_file(&backup_path.join(PACKAGES_FILE), list.as_bytes());
+        }
+
+        if let Err(e) = Self::write_checksums(&backup_path) {
+            self.status_message =
+                Some((trf("Failed to write checksums: {}", &[&e.to_string()]), true));
+            return None;
+        }
+
+        self.load_backups();
+        let pruned = self.apply_retention();
+
+        let mut message = match kind {
+            BackupKind::Full => trf(
+                "Backup created: {} files backed up, {} failed",
+                &[&backed_up.to_string(), &failed.to_string()],
+            ),
+            BackupKind::Incremental | BackupKind::Snapshot => trf(
+                "Incremental backup created: {} changed, {} unchanged, {} failed",
+                &[&backed_up.to_string(), &unchanged.to_string(), &failed.to_string()],
+            ),
+        };
+        if pruned > 0 {
+            message.push_str(&format!(", {} old backup(s) pruned", pruned));
+        }
+        self.status_message = Some((message, failed > 0));
+
+        None
+    }
+
+    /// Record SHA256 checksums of everything stored in a backup directory
+    fn write_checksums(backup_path: &Path) -> Result<(), String> {
+        let mut files: Vec<String> = fs::read_dir(backup_path)
+            .map_err(|e| e.to_string())?
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name != CHECKSUM_FILE)
+            .collect();
+        files.sort();
+
+        // Stored copies are readable by root only
+        let output = root::command("sha256sum")
+            .args(&files)
+            .current_dir(backup_path)
+            .output()
+            .map_err(|e| format!("failed to run sha256sum: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        root::write_file(&backup_path.join(CHECKSUM_FILE), &output.stdout)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Re-hash a backup directory against its stored checksums.
+    /// Returns the number of verified files, or the names that failed.
+    fn verify_checksums(backup_path: &Path) -> Result<usize, Vec<String>> {
+        if !backup_path.join(CHECKSUM_FILE).exists() {
+            return Err(vec![trf("{} missing", &[CHECKSUM_FILE])]);
+        }
+
+        let output = root::command("sha256sum")
+            .args(["-c", CHECKSUM_FILE])
+            .current_dir(backup_path)
+            .output()
+            .map_err(|e| vec![format!("failed to run sha256sum: {}", e)])?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let failed: Vec<String> = stdout
+            .lines()
+            .filter(|l| !l.ends_with(": OK"))
+            .filter_map(|l| l.rsplit_once(": ").map(|(name, _)| name.to_string()))
+            .collect();
+
+        if output.status.success() && failed.is_empty() {
+            Ok(stdout.lines().count())
+        } else if failed.is_empty() {
+            Err(vec![String::from_utf8_lossy(&output.stderr).trim().to_string()])
+        } else {
+            Err(failed)
+        }
+    }
+
+    /// Verify a backup and, for incrementals, the base it depends on
+    fn verify_backup(&mut self, backup_path: &Path) -> Option<Message> {
+        let mut paths = vec![backup_path.to_path_buf()];
+        if let Some(base) = BackupManifest::load(backup_path).and_then(|m| m.base) {
+            paths.push(PathBuf::from(BACKUP_DIR).join(base));
+        }
+
+        let mut verified = 0;
+        let mut problems = Vec::new();
+        for path in &paths {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            match Self::verify_checksums(path) {
+                Ok(count) => verified += count,
+                Err(failed) => {
+                    problems.extend(failed.into_iter().map(|f| format!("{}/{}", name, f)))
+                }
+            }
+        }
+
+        self.status_message = Some(if problems.is_empty() {
+            (trf("Backup verified: {} files OK", &[&verified.to_string()]), false)
+        } else {
+            (trf("Verification FAILED: {}", &[&problems.join(", ")]), true)
+        });
+        None
+    }
+
+    /// Save the current file selection as a profile
+    fn export_profile(&mut self, name_or_path: &str) {
+        let path = BackupProfile::resolve_path(name_or_path);
+        let name = path
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| name_or_path.to_string());
+        let profile = BackupProfile {
+            name,
+            files: self
+                .config_files
+                .iter()
+                .filter(|(_, _, selected)| *selected)
+                .map(|(path, _, _)| path.clone())
+                .collect(),
+        };
+
+        self.status_message = Some(match profile.save(&path) {
+            Ok(()) => (trf("Profile exported to {}", &[&path.display().to_string()]), false),
+            Err(e) => (trf("Failed to export profile: {}", &[&e.to_string()]), true),
+        });
+    }
+
+    /// Load a profile and apply its file selection. Profiles imported from
+    /// a path are also copied into the profile directory.
+    fn import_profile(&mut self, name_or_path: &str) {
+        let path = BackupProfile::resolve_path(name_or_path);
+        let profile = match BackupProfile::load(&path) {
+            Ok(profile) => profile,
+            Err(e) => {
+                self.status_message =
+                    Some((trf("Failed to import profile: {}", &[&e.to_string()]), true));
+                return;
+            }
+        };
+
+        if !path.starts_with(PROFILE_DIR) {
+            let local = BackupProfile::resolve_path(&profile.name);
+            let _ = profile.save(&local);
+        }
+
+        for (path, _, selected) in &mut self.config_files {
+            *selected = profile.files.contains(path);
+        }
+        for file in &profile.files {
+            if !self.config_files.iter().any(|(p, _, _)| p == file) {
+                self.config_files
+                    .push((file.clone(), "Custom (from profile)".to_string(), true));
+            }
+        }
+
+        self.status_message = Some((
+            trf(
+                "Profile '{}' applied: {} files selected",
+                &[&profile.name, &profile.files.len().to_string()],
+            ),
+            false,
+        ));
+    }
+
+    /// Full names of all installed packages (name-version-arch-build)
+    fn installed_packages() -> Vec<String> {
+        let mut packages: Vec<String> = fs::read_dir(PACKAGES_DIR)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        packages.sort();
+        packages
+    }
+
+    /// Base names of packages in a backup that are no longer installed
+    fn missing_packages(backup: &BackupEntry) -> Vec<String> {
+        let installed: Vec<String> = Self::installed_packages()
+            .iter()
+            .filter_map(|p| PackageBrowserComponent::parse_package_name(p))
+            .map(|p| p.name)
+            .collect();
+
+        backup
+            .packages
+            .iter()
+            .flatten()
+            .filter_map(|p| PackageBrowserComponent::parse_package_name(p))
+            .map(|p| p.name)
+            .filter(|name| !installed.contains(name))
+            .collect()
+    }
+
+    fn reinstall_packages(&mut self, packages: &[String]) -> Option<Message> {
+        let output = audit::output(
+            root::command_with_env("slackpkg", &ProxySettings::load().env_vars())
+                .args(["-batch=on", "-default_answer=y", "install"])
+                .args(packages)
+                .stdin(Stdio::null()),
+        );
+
+        self.status_message = Some(match output {
+            Ok(output) if output.status.success() => (
+                trf("Reinstalled {} package(s) via slackpkg", &[&packages.len().to_string()]),
+                false,
+            ),
+            Ok(output) => (
+                format!(
+                    "slackpkg install failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                true,
+            ),
+            Err(e) => (trf("Failed to run slackpkg: {}", &[&e.to_string()]), true),
+        });
+        None
+    }
+
+    /// Delete backups that fall outside the configured retention policy.
+    /// Full backups that a retained incremental depends on are always kept.
+    fn apply_retention(&mut self) -> usize {
+        let keep_last = self.settings.keep_last;
+        let keep_days = self.settings.keep_days;
+        if keep_last == 0 && keep_days == 0 {
+            return 0;
+        }
+
+        let cutoff = Local::now() - chrono::Duration::days(keep_days as i64);
+        // self.backups is sorted newest first
+        let expired: Vec<bool> = self
+            .backups
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                (keep_last > 0 && i >= keep_last) || (keep_days > 0 && b.timestamp < cutoff)
+            })
+            .collect();
+
+        let needed_bases: Vec<&str> = self
+            .backups
+            .iter()
+            .zip(&expired)
+            .filter(|(_, expired)| !**expired)
+            .filter_map(|(b, _)| b.manifest.as_ref().and_then(|m| m.base.as_deref()))
+            .collect();
+
+        let mut pruned = 0;
+        for (backup, _) in self.backups.iter().zip(&expired).filter(|(_, e)| **e) {
+            if needed_bases.contains(&backup.name.as_str()) {
+                continue;
+            }
+            if root::remove(&backup.path).is_ok() {
+                pruned += 1;
+            }
+        }
+
+        if pruned > 0 {
+            self.load_backups();
+        }
+        pruned
+    }
+
+    /// Resolve the files captured by a backup to their stored copies
+    fn backup_files(backup_path: &Path) -> Vec<RestoreFile> {
+        if let Some(manifest) = BackupManifest::load(backup_path) {
+            // Each entry points at the backup holding its content, so this
+            // reconstructs the state at the time of the backup
+            return manifest
+                .files
+                .iter()
+                .map(|file| RestoreFile {
+                    path: file.path.clone(),
+                    source: PathBuf::from(BACKUP_DIR)
+                        .join(&file.stored_in)
+                        .join(&file.stored_as),
+                    mode: file.mode,
+                    encrypted: file.encrypted,
+                })
+                .collect();
+        }
+
+        let mut files = Vec::new();
+        if let Ok(entries) = fs::read_dir(backup_path) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let filename = entry.file_name().to_string_lossy().to_string();
+
+                // Convert filename back to path
+                let original_path = format!("/{}", filename.replace('_', "/"));
+
+                // Verify this is a known config file
+                if CONFIG_FILES.iter().any(|(p, _)| *p == original_path) {
+                    files.push(RestoreFile {
+                        path: original_path,
+                        source: entry.path(),
+                        mode: None,
+                        encrypted: false,
+                    });
+                }
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+
+    /// Open the restore preview for a backup
+    fn open_preview(&mut self, backup_path: &Path) {
+        let manifest = BackupManifest::load(backup_path);
+        let (files, extract_dir) = if manifest.as_ref().map(|m| m.kind)
+            == Some(BackupKind::Snapshot)
+        {
+            match Self::extract_snapshot(backup_path, manifest.as_ref()) {
+                Ok((files, dir)) => (files, Some(dir)),
+                Err(e) => {
+                    self.status_message =
+                        Some((trf("Failed to open snapshot: {}", &[&e.to_string()]), true));
+                    return;
+                }
+            }
+        } else {
+            (Self::backup_files(backup_path), None)
+        };
+
+        let files = files
+            .into_iter()
+            .map(|file| {
+                let change = FileChange::detect(&file);
+                // Identical files don't need restoring
+                let selected = change != FileChange::Unchanged;
+                (file, selected, change)
+            })
+            .collect();
+
+        let mut preview = RestorePreview {
+            backup_path: backup_path.to_path_buf(),
+            files,
+            list_state: ListState::default().with_selected(Some(0)),
+            diff: Vec::new(),
+            diff_scroll: 0,
+            show_content: false,
+            extract_dir,
+        };
+        preview.update_diff();
+        self.preview = Some(preview);
+
+        // Warn before the user relies on a corrupted backup
+        if backup_path.join(CHECKSUM_FILE).exists() {
+            if let Err(failed) = Self::verify_checksums(backup_path) {
+                self.status_message = Some((
+                    trf("Warning: backup failed verification: {}", &[&failed.join(", ")]),
+                    true,
+                ));
+            }
+        }
+    }
+
+    /// Unpack a snapshot archive into a scratch directory so its files can
+    /// be reviewed and restored like those of a regular backup
+    fn extract_snapshot(
+        backup_path: &Path,
+        manifest: Option<&BackupManifest>,
+    ) -> Result<(Vec<RestoreFile>, PathBuf), String> {
+        let archive = manifest
+            .and_then(|m| m.files.first())
+            .map(|entry| RestoreFile {
+                path: entry.path.clone(),
+                source: backup_path.join(&entry.stored_as),
+                mode: None,
+                encrypted: entry.encrypted,
+            })
+            .ok_or_else(|| "snapshot manifest is empty".to_string())?;
+        let content = archive.read_stored()?;
+
+        let dir = scratch::private_dir("slackware-cli-manager").map_err(|e| e.to_string())?;
+
+        let mut child = root::command("tar")
+            .args(["-xzf", "-", "-C"])
+            .arg(&dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to run tar: {}", e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(&content).map_err(|e| e.to_string())?;
+        }
+        let output = child.wait_with_output().map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            let _ = root::remove(&dir);
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        let mut files = Vec::new();
+        Self::collect_files(&dir, &dir, &mut files);
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok((files, dir))
+    }
+
+    fn collect_files(root: &Path, dir: &Path, files: &mut Vec<RestoreFile>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                Self::collect_files(root, &path, files);
+            } else if metadata.is_file() {
+                if let Ok(relative) = path.strip_prefix(root) {
+                    files.push(RestoreFile {
+                        path: format!("/{}", relative.display()),
+                        source: path.clone(),
+                        mode: Some(metadata.permissions().mode()),
+                        encrypted: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Create a tarball of the whole /etc directory
+    fn create_snapshot(&mut self) -> Option<Message> {
+        let backup_name = format!("backup_{}", Local::now().format("%Y%m%d_%H%M%S"));
+        let backup_path = PathBuf::from(BACKUP_DIR).join(&backup_name);
+        if let Err(e) = root::create_dir_all(&backup_path) {
+            self.status_message =
+                Some((trf("Failed to create backup: {}", &[&e.to_string()]), true));
+            return None;
+        }
+
+        // Written to stdout, so the archive of /etc (and its secrets) is
+        // only ever stored in the backup, encrypted when asked to be
+        let mut tar = root::command("tar");
+        tar.arg("-czf").arg("-");
+        let extra: Vec<String> = self
+            .settings
+            .snapshot_excludes
+            .split(',')
+            .map(|s| s.trim().trim_start_matches('/').to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        for pattern in SNAPSHOT_EXCLUDES
+            .iter()
+            .copied()
+            .chain(extra.iter().map(|s| s.as_str()))
+        {
+            tar.arg(format!("--exclude={}", pattern));
+        }
+        tar.args(["-C", "/", "etc"]).stdin(Stdio::null());
+
+        // GNU tar exits with 1 when files changed while being read
+        let archive = match tar.output() {
+            Ok(output) if matches!(output.status.code(), Some(0) | Some(1)) => Ok(output.stdout),
+            Ok(output) => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            Err(e) => Err(format!("failed to run tar: {}", e)),
+        };
 
-            // Create destination path preserving directory structure
-            let dest_name = path.replace('/', "_").trim_start_matches('_').to_string();
-            let dest = backup_path.join(&dest_name);
+        let recipient =
+            Some(self.settings.gpg_recipient.trim().to_string()).filter(|r| !r.is_empty());
+        let size = archive.as_ref().map_or(0, |archive| archive.len() as u64);
+        let stored = archive.and_then(|archive| {
+            store_content(&archive, &backup_path, SNAPSHOT_ARCHIVE, recipient.as_deref())
+        });
 
-            match fs::copy(source, &dest) {
-                Ok(_) => backed_up += 1,
-                Err(_) => failed += 1,
+        let stored_as = match stored {
+            Ok(stored_as) => stored_as,
+            Err(e) => {
+                self.status_message = Some((trf("Snapshot failed: {}", &[&e.to_string()]), true));
+                let _ = root::remove(&backup_path);
+                return None;
             }
-        }
+        };
 
-        if backed_up > 0 {
-            self.status_message = Some((
-                format!("Backup created: {} files backed up, {} failed", backed_up, failed),
-                failed > 0,
-            ));
-            self.load_backups();
-        } else {
-            self.status_message = Some(("No files were backed up".to_string(), true));
-            // Remove empty backup directory
-            let _ = fs::remove_dir(&backup_path);
+        let manifest = BackupManifest {
+            kind: BackupKind::Snapshot,
+            base: None,
+            files: vec![ManifestEntry {
+                path: "/etc".to_string(),
+                stored_as,
+                stored_in: backup_name,
+                mtime: Local::now().timestamp() as u64,
+                size,
+                mode: None,
+                encrypted: recipient.is_some(),
+            }],
+        };
+        let packages = Self::installed_packages();
+        if !packages.is_empty() {
+            let list = packages.join("\n") + "\n";
+            let _ = root::write_file(&backup_path.join(PACKAGES_FILE), list.as_bytes());
+        }
+        if let Err(e) = manifest
+            .save(&backup_path)
+            .map_err(|e| e.to_string())
+            .and_then(|()| Self::write_checksums(&backup_path))
+        {
+            self.status_message =
+                Some((trf("Failed to finalize snapshot: {}", &[&e.to_string()]), true));
+            return None;
         }
 
+        self.load_backups();
+        let pruned = self.apply_retention();
+        let mut message = format!("/etc snapshot created ({})", Self::format_size(size));
+        if pruned > 0 {
+            message.push_str(&format!(", {} old backup(s) pruned", pruned));
+        }
+        self.status_message = Some((message, false));
         None
     }
 
-    fn restore_backup(&mut self, backup_path: &Path) -> Option<Message> {
+    fn restore_files(&mut self, files: &[RestoreFile]) -> Option<Message> {
         let mut restored = 0;
         let mut failed = 0;
 
-        if let Ok(entries) = fs::read_dir(backup_path) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let filename = entry.file_name().to_string_lossy().to_string();
-
-                // Convert filename back to path
-                let original_path = format!("/{}", filename.replace('_', "/"));
+        let mut last_error = None;
 
-                // Verify this is a known config file
-                if CONFIG_FILES.iter().any(|(p, _)| *p == original_path) {
-                    match fs::copy(entry.path(), &original_path) {
-                        Ok(_) => restored += 1,
-                        Err(_) => failed += 1,
-                    }
+        let mut transaction = Transaction::begin(format!("Restore {} file(s)", files.len()));
+        for file in files {
+            let result = transaction
+                .record(Path::new(&file.path))
+                .map_err(|e| e.to_string())
+                .and_then(|_| file.restore());
+            match result {
+                Ok(()) => restored += 1,
+                Err(e) => {
+                    failed += 1;
+                    last_error = Some(e);
                 }
             }
         }
 
         self.status_message = Some((
-            format!("Restore complete: {} files restored, {} failed", restored, failed),
+            match last_error {
+                Some(e) => trf(
+                    "Restore complete: {} files restored, {} failed ({})",
+                    &[&restored.to_string(), &failed.to_string(), &e.to_string()],
+                ),
+                None => trf(
+                    "Restore complete: {} files restored, {} failed",
+                    &[&restored.to_string(), &failed.to_string()],
+                ),
+            },
             failed > 0,
         ));
+        self.preview = None;
 
         None
     }
 
     fn delete_backup(&mut self, backup_path: &Path) -> Option<Message> {
-        match fs::remove_dir_all(backup_path) {
+        let name = backup_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let dependents = self
+            .backups
+            .iter()
+            .filter(|b| b.manifest.as_ref().and_then(|m| m.base.as_deref()) == Some(name.as_str()))
+            .count();
+        if dependents > 0 {
+            self.status_message = Some((
+                trf(
+                    "Cannot delete: {} incremental backup(s) depend on this backup",
+                    &[&dependents.to_string()],
+                ),
+                true,
+            ));
+            return None;
+        }
+
+        match root::remove(backup_path) {
             Ok(_) => {
-                self.status_message = Some(("Backup deleted successfully".to_string(), false));
+                self.status_message = Some((tr("Backup deleted successfully").to_string(), false));
                 self.load_backups();
             }
             Err(e) => {
-                self.status_message = Some((format!("Failed to delete backup: {}", e), true));
+                self.status_message =
+                    Some((trf("Failed to delete backup: {}", &[&e.to_string()]), true));
+            }
+        }
+        None
+    }
+
+    fn load_remote_backups(&mut self) {
+        self.remote_backups.clear();
+
+        let Some(remote) = &self.remote else {
+            return;
+        };
+
+        match remote.list() {
+            Ok(names) => self.remote_backups = names,
+            Err(e) => {
+                self.status_message =
+                    Some((trf("Failed to list remote backups: {}", &[&e.to_string()]), true));
             }
         }
+    }
+
+    fn push_remote(&mut self, backup_path: &Path) -> Option<Message> {
+        let Some(remote) = &self.remote else {
+            self.status_message = Some((tr("No remote target configured").to_string(), true));
+            return None;
+        };
+
+        self.status_message = Some(match remote.push(backup_path) {
+            Ok(()) => (trf("Backup pushed to {}", &[&remote.display().to_string()]), false),
+            Err(e) => (trf("Remote push failed: {}", &[&e.to_string()]), true),
+        });
         None
     }
 
+    fn restore_remote(&mut self, name: &str) -> Option<Message> {
+        let Some(remote) = &self.remote else {
+            self.status_message = Some((tr("No remote target configured").to_string(), true));
+            return None;
+        };
+
+        if let Err(e) = self.ensure_backup_dir() {
+            self.status_message =
+                Some((trf("Failed to create backup directory: {}", &[&e.to_string()]), true));
+            return None;
+        }
+
+        match remote.fetch(name) {
+            Ok(local_path) => {
+                // Incremental backups also need their base to be present
+                let base = BackupManifest::load(&local_path).and_then(|m| m.base);
+                if let Some(base) = base {
+                    if !PathBuf::from(BACKUP_DIR).join(&base).exists() {
+                        if let Err(e) = remote.fetch(&base) {
+                            self.status_message = Some((
+                                trf("Failed to fetch base backup {}: {}", &[&base, &e.to_string()]),
+                                true,
+                            ));
+                            return None;
+                        }
+                    }
+                }
+                self.load_backups();
+                self.mode = BackupMode::Restore;
+                self.open_preview(&local_path);
+                None
+            }
+            Err(e) => {
+                self.status_message =
+                    Some((trf("Failed to fetch remote backup: {}", &[&e.to_string()]), true));
+                None
+            }
+        }
+    }
+
+    fn list_len(&self) -> usize {
+        match self.mode {
+            BackupMode::Create => self.config_files.len(),
+            BackupMode::Restore => self.backups.len(),
+            BackupMode::Remote => self.remote_backups.len(),
+        }
+    }
+
     fn format_size(bytes: u64) -> String {
         const KB: u64 = 1024;
         const MB: u64 = KB * 1024;
+        const GB: u64 = MB * 1024;
 
-        if bytes >= MB {
+        if bytes >= GB {
+            format!("{:.1} GB", bytes as f64 / GB as f64)
+        } else if bytes >= MB {
             format!("{:.1} MB", bytes as f64 / MB as f64)
         } else if bytes >= KB {
             format!("{:.1} KB", bytes as f64 / KB as f64)
@@ -258,6 +1519,30 @@ impl BackupComponent {
     }
 }
 
+impl BackupComponent {
+    /// Ask before running `action`, unless confirmations are off and it is low-risk
+    fn request(&mut self, action: BackupAction) -> Option<Message> {
+        if settings::confirm_actions() || action.always_confirm() {
+            self.pending_action = Some(action);
+            self.show_confirm = true;
+            None
+        } else {
+            self.execute_action(action)
+        }
+    }
+
+    fn execute_action(&mut self, action: BackupAction) -> Option<Message> {
+        match action {
+            BackupAction::CreateBackup(kind) => self.create_backup(kind),
+            BackupAction::RestoreFiles(files) => self.restore_files(&files),
+            BackupAction::DeleteBackup(path) => self.delete_backup(&path),
+            BackupAction::PushRemote(path) => self.push_remote(&path),
+            BackupAction::RestoreRemote(name) => self.restore_remote(&name),
+            BackupAction::ReinstallPackages(packages) => self.reinstall_packages(&packages),
+        }
+    }
+}
+
 impl Component for BackupComponent {
     fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
         if self.show_confirm {
@@ -265,11 +1550,7 @@ impl Component for BackupComponent {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
                     self.show_confirm = false;
                     if let Some(action) = self.pending_action.take() {
-                        return match action {
-                            BackupAction::CreateBackup => self.create_backup(),
-                            BackupAction::RestoreBackup(path) => self.restore_backup(&path),
-                            BackupAction::DeleteBackup(path) => self.delete_backup(&path),
-                        };
+                        return self.execute_action(action);
                     }
                 }
                 KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
@@ -281,19 +1562,47 @@ impl Component for BackupComponent {
             return None;
         }
 
+        if let Some((prompt, buffer)) = &mut self.profile_prompt {
+            match key.code {
+                KeyCode::Enter => {
+                    let prompt = *prompt;
+                    let value = buffer.trim().to_string();
+                    self.profile_prompt = None;
+                    if !value.is_empty() {
+                        match prompt {
+                            ProfilePrompt::Export => self.export_profile(&value),
+                            ProfilePrompt::Import => self.import_profile(&value),
+                        }
+                    }
+                }
+                KeyCode::Esc => self.profile_prompt = None,
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.preview.is_some() {
+            return self.handle_preview_input(key);
+        }
+
         match key.code {
             KeyCode::Tab => {
                 self.mode = match self.mode {
                     BackupMode::Create => BackupMode::Restore,
-                    BackupMode::Restore => BackupMode::Create,
+                    BackupMode::Restore => BackupMode::Remote,
+                    BackupMode::Remote => BackupMode::Create,
                 };
+                if self.mode == BackupMode::Remote {
+                    self.load_remote_backups();
+                }
                 self.list_state.select(Some(0));
             }
             KeyCode::Up | KeyCode::Char('k') => {
-                let len = match self.mode {
-                    BackupMode::Create => self.config_files.len(),
-                    BackupMode::Restore => self.backups.len(),
-                };
+                let len = self.list_len();
                 if let Some(selected) = self.list_state.selected() {
                     if selected > 0 {
                         self.list_state.select(Some(selected - 1));
@@ -303,10 +1612,7 @@ impl Component for BackupComponent {
                 }
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                let len = match self.mode {
-                    BackupMode::Create => self.config_files.len(),
-                    BackupMode::Restore => self.backups.len(),
-                };
+                let len = self.list_len();
                 if let Some(selected) = self.list_state.selected() {
                     if selected < len.saturating_sub(1) {
                         self.list_state.select(Some(selected + 1));
@@ -331,31 +1637,86 @@ impl Component for BackupComponent {
             KeyCode::Enter => {
                 match self.mode {
                     BackupMode::Create => {
-                        self.pending_action = Some(BackupAction::CreateBackup);
-                        self.show_confirm = true;
+                        self.request(BackupAction::CreateBackup(BackupKind::Full));
                     }
                     BackupMode::Restore => {
                         if let Some(selected) = self.list_state.selected() {
                             if let Some(backup) = self.backups.get(selected) {
-                                self.pending_action =
-                                    Some(BackupAction::RestoreBackup(backup.path.clone()));
-                                self.show_confirm = true;
+                                let path = backup.path.clone();
+                                self.open_preview(&path);
+                            }
+                        }
+                    }
+                    BackupMode::Remote => {
+                        if let Some(selected) = self.list_state.selected() {
+                            if let Some(name) = self.remote_backups.get(selected) {
+                                self.request(BackupAction::RestoreRemote(name.clone()));
                             }
                         }
                     }
                 }
             }
+            KeyCode::Char('f') if self.mode == BackupMode::Create => {
+                self.request(BackupAction::CreateBackup(BackupKind::Snapshot));
+            }
+            KeyCode::Char('e') if self.mode == BackupMode::Create => {
+                self.profile_prompt = Some((ProfilePrompt::Export, String::new()));
+            }
+            KeyCode::Char('o') if self.mode == BackupMode::Create => {
+                self.profile_prompt = Some((ProfilePrompt::Import, String::new()));
+            }
+            KeyCode::Char('i') if self.mode == BackupMode::Create => {
+                self.request(BackupAction::CreateBackup(BackupKind::Incremental));
+            }
+            KeyCode::Char('p') if self.mode == BackupMode::Restore => {
+                if self.remote.is_none() {
+                    self.status_message = Some((
+                        tr("No remote target configured (Settings → Backup)").to_string(),
+                        true,
+                    ));
+                } else if let Some(selected) = self.list_state.selected() {
+                    if let Some(backup) = self.backups.get(selected) {
+                        self.request(BackupAction::PushRemote(backup.path.clone()));
+                    }
+                }
+            }
+            KeyCode::Char('i') if self.mode == BackupMode::Restore => {
+                if let Some(backup) = self.list_state.selected().and_then(|i| self.backups.get(i)) {
+                    if backup.packages.is_none() {
+                        self.status_message =
+                            Some((tr("This backup has no package list").to_string(), true));
+                    } else {
+                        let missing = Self::missing_packages(backup);
+                        if missing.is_empty() {
+                            self.status_message = Some((
+                                tr("All packages in this backup are installed").to_string(),
+                                false,
+                            ));
+                        } else {
+                            self.request(BackupAction::ReinstallPackages(missing));
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('v') if self.mode == BackupMode::Restore => {
+                if let Some(backup) = self.list_state.selected().and_then(|i| self.backups.get(i)) {
+                    let path = backup.path.clone();
+                    self.verify_backup(&path);
+                }
+            }
             KeyCode::Char('d') if self.mode == BackupMode::Restore => {
                 if let Some(selected) = self.list_state.selected() {
                     if let Some(backup) = self.backups.get(selected) {
-                        self.pending_action = Some(BackupAction::DeleteBackup(backup.path.clone()));
-                        self.show_confirm = true;
+                        self.request(BackupAction::DeleteBackup(backup.path.clone()));
                     }
                 }
             }
             KeyCode::F(5) => {
                 self.load_backups();
-                self.status_message = Some(("Backup list refreshed".to_string(), false));
+                self.status_message = Some((tr("Backup list refreshed").to_string(), false));
+                if self.mode == BackupMode::Remote {
+                    self.load_remote_backups();
+                }
             }
             _ => {}
         }
@@ -373,48 +1734,111 @@ impl Component for BackupComponent {
             .split(area);
 
         // Mode tabs
-        let mode_text = match self.mode {
-            BackupMode::Create => "[Create Backup]  Restore Backup",
-            BackupMode::Restore => " Create Backup  [Restore Backup]",
-        };
+        let mode_text = [
+            (BackupMode::Create, "Create Backup"),
+            (BackupMode::Restore, "Restore Backup"),
+            (BackupMode::Remote, "Remote"),
+        ]
+        .iter()
+        .map(|(mode, name)| {
+            if *mode == self.mode {
+                format!("[{}]", tr(name))
+            } else {
+                tr(name).to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
         let mode_bar = Paragraph::new(Line::from(vec![
-            Span::styled("Mode: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{} ", tr("Mode:")), Theme::label()),
             Span::raw(mode_text),
         ]))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Backup & Restore "),
+                .title(format!(" {} ", tr("Backup & Restore"))),
         );
         frame.render_widget(mode_bar, chunks[0]);
 
         // Content
-        match self.mode {
-            BackupMode::Create => self.render_create_mode(frame, chunks[1]),
-            BackupMode::Restore => self.render_restore_mode(frame, chunks[1]),
+        if let Some(preview) = &self.preview {
+            Self::render_preview(preview, frame, chunks[1]);
+        } else {
+            match self.mode {
+                BackupMode::Create => self.render_create_mode(frame, chunks[1]),
+                BackupMode::Restore => self.render_restore_mode(frame, chunks[1]),
+                BackupMode::Remote => self.render_remote_mode(frame, chunks[1]),
+            }
         }
 
         // Status bar
-        let status_content = if self.show_confirm {
+        let status_content = if let Some((prompt, buffer)) = &self.profile_prompt {
+            let label = match prompt {
+                ProfilePrompt::Export => tr("Export profile (name or path):").to_string(),
+                ProfilePrompt::Import => {
+                    let available = BackupProfile::available();
+                    if available.is_empty() {
+                        tr("Import profile (name or path):").to_string()
+                    } else {
+                        trf("Import profile [{}]:", &[&available.join(", ")])
+                    }
+                }
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", label), Theme::warning()),
+                Span::styled(format!("{}_", buffer), Theme::input_active()),
+            ])
+        } else if self.show_confirm {
             let action_desc = match &self.pending_action {
-                Some(BackupAction::CreateBackup) => "Create backup?".to_string(),
-                Some(BackupAction::RestoreBackup(_)) => "Restore this backup?".to_string(),
-                Some(BackupAction::DeleteBackup(_)) => "Delete this backup?".to_string(),
-                None => "Confirm action?".to_string(),
+                Some(BackupAction::CreateBackup(BackupKind::Full)) => {
+                    tr("Create backup?").to_string()
+                }
+                Some(BackupAction::CreateBackup(BackupKind::Incremental)) => {
+                    tr("Create incremental backup (changes since last full backup)?").to_string()
+                }
+                Some(BackupAction::CreateBackup(BackupKind::Snapshot)) => {
+                    tr("Snapshot the entire /etc directory?").to_string()
+                }
+                Some(BackupAction::RestoreFiles(files)) if files.len() == 1 => {
+                    trf("Restore {} from backup?", &[&files[0].path])
+                }
+                Some(BackupAction::RestoreFiles(files)) => {
+                    trf("Restore {} selected file(s)?", &[&files.len().to_string()])
+                }
+                Some(BackupAction::DeleteBackup(_)) => tr("Delete this backup?").to_string(),
+                Some(BackupAction::PushRemote(_)) => {
+                    tr("Push this backup to the remote target?").to_string()
+                }
+                Some(BackupAction::ReinstallPackages(packages)) => trf(
+                    "Reinstall {} missing package(s) via slackpkg?",
+                    &[&packages.len().to_string()],
+                ),
+                Some(BackupAction::RestoreRemote(_)) => {
+                    tr("Download this remote backup and review it for restore?").to_string()
+                }
+                None => tr("Confirm action?").to_string(),
             };
             Line::from(vec![
-                Span::styled(action_desc, Style::default().fg(Color::Yellow)),
-                Span::raw(" [Y]es / [N]o"),
+                Span::styled(action_desc, Theme::warning()),
+                Span::raw(format!(" {}", tr("[Y]es / [N]o"))),
             ])
         } else if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
+            ))
+        } else if self.mode == BackupMode::Remote {
+            Line::from(Span::styled(
+                match &self.remote {
+                    Some(remote) => trf("Remote target: {}", &[&remote.display().to_string()]),
+                    None => tr("Remote target: not configured").to_string(),
+                },
+                Theme::muted(),
             ))
         } else {
             Line::from(Span::styled(
-                format!("Backup directory: {}", BACKUP_DIR),
-                Style::default().fg(Color::DarkGray),
+                trf("Backup directory: {}", &[BACKUP_DIR]),
+                Theme::muted(),
             ))
         };
 
@@ -423,28 +1847,242 @@ impl Component for BackupComponent {
         frame.render_widget(status, chunks[2]);
     }
 
+    fn is_typing(&self) -> bool {
+        self.profile_prompt.is_some()
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        if self.preview.is_some() {
+            return vec![
+                ("Space", "Toggle"),
+                ("a", "All"),
+                ("PgUp/PgDn", "Scroll"),
+                ("v", "Diff/Content"),
+                ("Enter", "Restore Selected"),
+                ("r", "Restore This File"),
+                ("Esc", "Back"),
+            ];
+        }
+
         match self.mode {
             BackupMode::Create => vec![
                 ("Tab", "Switch Mode"),
                 ("Space", "Toggle"),
                 ("a", "Select All"),
-                ("Enter", "Backup"),
+                ("Enter", "Create Backup"),
+                ("i", "Incremental"),
+                ("f", "Full /etc"),
+                ("e/o", "Export/Import Profile"),
             ],
             BackupMode::Restore => vec![
                 ("Tab", "Switch Mode"),
                 ("Enter", "Restore"),
+                ("p", "Push"),
+                ("i", "Reinstall Pkgs"),
+                ("v", "Verify"),
                 ("d", "Delete"),
             ],
+            BackupMode::Remote => vec![
+                ("Tab", "Switch Mode"),
+                ("Enter", "Fetch & Restore"),
+                ("F5", "Refresh"),
+            ],
+        }
+    }
+
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        if self.preview.is_some() {
+            return vec!["Enter", "r"];
+        }
+        match self.mode {
+            BackupMode::Create => vec!["Enter", "i", "f"],
+            BackupMode::Restore => vec!["Enter", "p", "i", "d"],
+            BackupMode::Remote => vec!["Enter"],
         }
     }
 
+    fn on_deactivate(&mut self) {
+        self.preview = None;
+    }
+
     fn on_activate(&mut self) {
+        self.settings = AppSettings::load().backup;
+        self.remote = RemoteTarget::from_settings(&self.settings);
         self.load_backups();
     }
 }
 
 impl BackupComponent {
+    fn handle_preview_input(&mut self, key: KeyEvent) -> Option<Message> {
+        let preview = self.preview.as_mut()?;
+        let len = preview.files.len();
+
+        match key.code {
+            KeyCode::Esc => {
+                self.preview = None;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(selected) = preview.list_state.selected() {
+                    if selected > 0 {
+                        preview.list_state.select(Some(selected - 1));
+                        preview.update_diff();
+                    }
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(selected) = preview.list_state.selected() {
+                    if selected < len.saturating_sub(1) {
+                        preview.list_state.select(Some(selected + 1));
+                        preview.update_diff();
+                    }
+                }
+            }
+            KeyCode::PageUp => {
+                preview.diff_scroll = preview.diff_scroll.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                let max = preview.diff.len().saturating_sub(1) as u16;
+                preview.diff_scroll = (preview.diff_scroll + 10).min(max);
+            }
+            KeyCode::Char(' ') => {
+                if let Some(selected) = preview.list_state.selected() {
+                    if let Some(file) = preview.files.get_mut(selected) {
+                        file.1 = !file.1;
+                    }
+                }
+            }
+            KeyCode::Char('a') => {
+                let all_selected = preview.files.iter().all(|(_, s, _)| *s);
+                for file in &mut preview.files {
+                    file.1 = !all_selected;
+                }
+            }
+            KeyCode::Char('v') => {
+                preview.show_content = !preview.show_content;
+                preview.update_diff();
+            }
+            KeyCode::Char('r') => {
+                if let Some(file) = preview.selected_file() {
+                    let action = BackupAction::RestoreFiles(vec![file.clone()]);
+                    return self.request(action);
+                }
+            }
+            KeyCode::Enter => {
+                let files = preview.selected_files();
+                if files.is_empty() {
+                    self.status_message =
+                        Some((tr("No files selected for restore").to_string(), true));
+                } else {
+                    return self.request(BackupAction::RestoreFiles(files));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn render_preview(preview: &RestorePreview, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(area);
+
+        let items: Vec<ListItem> = preview
+            .files
+            .iter()
+            .map(|(file, selected, change)| {
+                let checkbox = if *selected { "[✓]" } else { "[ ]" };
+                let (label, color) = change.label();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        checkbox,
+                        Style::default().fg(if *selected {
+                            Theme::colors().success
+                        } else {
+                            Theme::colors().muted
+                        }),
+                    ),
+                    Span::raw(" "),
+                    Span::raw(file.path.clone()),
+                    Span::styled(format!(" ({})", tr(label)), Style::default().fg(color)),
+                ]))
+            })
+            .collect();
+
+        let title = format!(
+            " {} ",
+            trf(
+                "Restore from {}",
+                &[&preview
+                    .backup_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default()]
+            )
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+
+        let mut state = preview.list_state.clone();
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        if preview.show_content {
+            let lines: Vec<Line> = preview
+                .diff
+                .iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    Line::from(vec![
+                        Span::styled(format!("{:>4} ", i + 1), Theme::muted()),
+                        Span::raw(line.clone()),
+                    ])
+                })
+                .collect();
+            let title = format!(
+                " {} ",
+                trf(
+                    "{} (backup copy, read-only)",
+                    &[preview.selected_file().map(|f| f.path.as_str()).unwrap_or("")]
+                )
+            );
+            let content = Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .scroll((preview.diff_scroll, 0));
+            frame.render_widget(content, chunks[1]);
+            return;
+        }
+
+        let lines: Vec<Line> = preview
+            .diff
+            .iter()
+            .map(|line| {
+                let style = if line.starts_with("+++") || line.starts_with("---") {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else if line.starts_with('+') {
+                    Theme::success()
+                } else if line.starts_with('-') {
+                    Theme::error()
+                } else if line.starts_with("@@") {
+                    Theme::label()
+                } else {
+                    Style::default()
+                };
+                Line::from(Span::styled(line.clone(), style))
+            })
+            .collect();
+
+        let diff = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", tr("Diff (current → backup)"))),
+            )
+            .scroll((preview.diff_scroll, 0));
+        frame.render_widget(diff, chunks[1]);
+    }
+
     fn render_create_mode(&self, frame: &mut Frame, area: Rect) {
         let items: Vec<ListItem> = self
             .config_files
@@ -452,16 +2090,20 @@ impl BackupComponent {
             .map(|(path, desc, selected)| {
                 let checkbox = if *selected { "[✓]" } else { "[ ]" };
                 let exists = Path::new(path).exists();
-                let status = if exists { "" } else { " (not found)" };
+                let status = if exists {
+                    String::new()
+                } else {
+                    format!(" ({})", tr("not found"))
+                };
 
                 ListItem::new(vec![
                     Line::from(vec![
                         Span::styled(
                             checkbox,
                             Style::default().fg(if *selected {
-                                Color::Green
+                                Theme::colors().success
                             } else {
-                                Color::DarkGray
+                                Theme::colors().muted
                             }),
                         ),
                         Span::raw(" "),
@@ -473,11 +2115,11 @@ impl BackupComponent {
                                 Modifier::DIM
                             }),
                         ),
-                        Span::styled(status, Style::default().fg(Color::Red)),
+                        Span::styled(status, Theme::error()),
                     ]),
                     Line::from(Span::styled(
-                        format!("    {}", desc),
-                        Style::default().fg(Color::DarkGray),
+                        format!("    {}", tr(desc)),
+                        Theme::muted(),
                     )),
                 ])
             })
@@ -487,7 +2129,7 @@ impl BackupComponent {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Select files to backup "),
+                    .title(format!(" {} ", tr("Select files to backup"))),
             )
             .highlight_style(Theme::list_selected())
             .highlight_symbol("▶ ");
@@ -496,16 +2138,33 @@ impl BackupComponent {
         frame.render_stateful_widget(list, area, &mut state);
     }
 
+    /// Title for the backup list with a disk-usage summary
+    fn usage_summary(&self) -> String {
+        // Incremental backups reference files in their base, so summing
+        // per-directory sizes gives the real on-disk usage
+        let total: u64 = self.backups.iter().map(|b| b.size).sum();
+        let count = self.backups.len().to_string();
+        let used = Self::format_size(total);
+        let summary = match self.disk_free {
+            Some(free) => trf(
+                "Available Backups ({}, {} used, {} free)",
+                &[&count, &used, &Self::format_size(free)],
+            ),
+            None => trf("Available Backups ({}, {} used)", &[&count, &used]),
+        };
+        format!(" {} ", summary)
+    }
+
     fn render_restore_mode(&self, frame: &mut Frame, area: Rect) {
         if self.backups.is_empty() {
             let empty = Paragraph::new(Line::from(Span::styled(
-                "No backups found",
-                Style::default().fg(Color::DarkGray),
+                tr("No backups found"),
+                Theme::muted(),
             )))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Available Backups "),
+                    .title(format!(" {} ", tr("Available Backups"))),
             );
             frame.render_widget(empty, area);
             return;
@@ -515,20 +2174,46 @@ impl BackupComponent {
             .backups
             .iter()
             .map(|backup| {
+                let kind = match backup.kind() {
+                    BackupKind::Full => Span::styled(format!("  {}", tr("full")), Theme::success()),
+                    BackupKind::Incremental => {
+                        Span::styled(format!("  {}", tr("incremental")), Theme::warning())
+                    }
+                    BackupKind::Snapshot => {
+                        Span::styled(format!("  {}", tr("/etc snapshot")), Theme::label())
+                    }
+                };
                 ListItem::new(vec![
                     Line::from(vec![
                         Span::styled(
                             backup.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
                             Style::default()
-                                .fg(Color::Cyan)
+                                .fg(Theme::colors().primary)
                                 .add_modifier(Modifier::BOLD),
                         ),
+                        kind,
+                        if backup.is_encrypted() {
+                            Span::styled(
+                                format!("  {}", tr("encrypted")),
+                                Style::default().fg(Theme::colors().secondary),
+                            )
+                        } else {
+                            Span::raw("")
+                        },
                     ]),
                     Line::from(vec![
-                        Span::styled("    Files: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(format!("    {} ", tr("Files:")), Theme::muted()),
                         Span::raw(format!("{}", backup.file_count)),
-                        Span::styled("  Size: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(format!("  {} ", tr("Size:")), Theme::muted()),
                         Span::raw(Self::format_size(backup.size)),
+                        Span::styled(format!("  {} ", tr("Packages:")), Theme::muted()),
+                        Span::raw(
+                            backup
+                                .packages
+                                .as_ref()
+                                .map(|p| p.len().to_string())
+                                .unwrap_or_else(|| "-".to_string()),
+                        ),
                     ]),
                 ])
             })
@@ -538,7 +2223,7 @@ impl BackupComponent {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Available Backups "),
+                    .title(self.usage_summary()),
             )
             .highlight_style(Theme::list_selected())
             .highlight_symbol("▶ ");
@@ -546,4 +2231,100 @@ impl BackupComponent {
         let mut state = self.list_state.clone();
         frame.render_stateful_widget(list, area, &mut state);
     }
+
+    fn render_remote_mode(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", tr("Remote Backups")));
+
+        let message = if self.remote.is_none() {
+            Some(tr("No remote target configured. Set one in Settings → Backup."))
+        } else if self.remote_backups.is_empty() {
+            Some(tr("No remote backups found"))
+        } else {
+            None
+        };
+
+        if let Some(message) = message {
+            let empty = Paragraph::new(Line::from(Span::styled(
+                message,
+                Theme::muted(),
+            )))
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .remote_backups
+            .iter()
+            .map(|name| {
+                let label = name
+                    .strip_prefix("backup_")
+                    .and_then(|ts| {
+                        chrono::NaiveDateTime::parse_from_str(ts, "%Y%m%d_%H%M%S").ok()
+                    })
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| name.clone());
+                ListItem::new(Line::from(Span::styled(
+                    label,
+                    Style::default()
+                        .fg(Theme::colors().primary)
+                        .add_modifier(Modifier::BOLD),
+                )))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssh_command_quoting() {
+        let target = RemoteTarget {
+            host: "backup@nas".to_string(),
+            path: "/srv/it's here".to_string(),
+            ssh_key: Some("/home/me/my keys/id_ed25519".to_string()),
+        };
+        assert_eq!(
+            target.ssh_command(),
+            "ssh -o BatchMode=yes -i '/home/me/my keys/id_ed25519'"
+        );
+        assert_eq!(shell_quote(&target.path), "'/srv/it'\\''s here'");
+    }
+
+    #[test]
+    fn test_restore_keeps_mode() {
+        let dir = scratch::private_dir("backup-test").unwrap();
+        let live = dir.join("hosts");
+        fs::write(&live, "127.0.0.1 localhost\n").unwrap();
+        fs::set_permissions(&live, fs::Permissions::from_mode(0o644)).unwrap();
+        let mode = fs::metadata(&live).unwrap().permissions().mode();
+
+        let stored = store_content(&fs::read(&live).unwrap(), &dir, "hosts.bak", None).unwrap();
+        let source = dir.join(&stored);
+        assert_eq!(fs::metadata(&source).unwrap().permissions().mode() & 0o777, 0o600);
+
+        fs::remove_file(&live).unwrap();
+        let file = RestoreFile {
+            path: live.to_string_lossy().to_string(),
+            source,
+            mode: Some(mode),
+            encrypted: false,
+        };
+        file.restore().unwrap();
+        assert_eq!(fs::read(&live).unwrap(), b"127.0.0.1 localhost\n");
+        assert_eq!(fs::metadata(&live).unwrap().permissions().mode() & 0o777, 0o644);
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -6,12 +6,14 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
-use tokio::sync::mpsc;
 use tui_textarea::TextArea;
 
-use super::{AsyncComponent, Component};
+use super::{AsyncComponent, Component, SearchItem};
 use crate::app::Message;
+use crate::slackware::config::Transaction;
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::root;
 
 /// Available config files to edit
 const CONFIG_FILES: [(&str, &str); 3] = [
@@ -35,7 +37,6 @@ pub struct ConfigEditorComponent {
     is_modified: bool,
     is_saving: bool,
     status_message: Option<(String, bool)>,
-    progress_tx: Option<mpsc::UnboundedSender<String>>,
 }
 
 impl ConfigEditorComponent {
@@ -44,7 +45,7 @@ impl ConfigEditorComponent {
         textarea.set_block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Editor")
+                .title(tr("Editor"))
                 .border_style(Theme::border()),
         );
 
@@ -56,20 +57,18 @@ impl ConfigEditorComponent {
             is_modified: false,
             is_saving: false,
             status_message: None,
-            progress_tx: None,
         }
     }
 
     pub fn load_file(&mut self, path: &str) -> Result<(), String> {
-        use std::fs;
-
-        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let content = root::read_file(std::path::Path::new(path)).map_err(|e| e.to_string())?;
+        let content = String::from_utf8_lossy(&content);
 
         self.textarea = TextArea::from(content.lines());
         self.textarea.set_block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Editing: {}", path))
+                .title(trf("Editing: {}", &[path]))
                 .border_style(Theme::border_focused()),
         );
 
@@ -82,13 +81,13 @@ impl ConfigEditorComponent {
     }
 
     pub fn save_file(&mut self) -> Result<(), String> {
-        use std::fs;
-
         if let Some(ref path) = self.current_file {
             let content = self.textarea.lines().join("\n");
-            fs::write(path, content + "\n").map_err(|e| e.to_string())?;
+            Transaction::begin(format!("Edit {}", path))
+                .write(std::path::Path::new(path), content + "\n")
+                .map_err(|e| e.to_string())?;
             self.is_modified = false;
-            self.status_message = Some(("File saved successfully".to_string(), false));
+            self.status_message = Some((tr("File saved successfully").to_string(), false));
         }
 
         Ok(())
@@ -102,7 +101,7 @@ impl ConfigEditorComponent {
         self.textarea.set_block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Editor")
+                .title(tr("Editor"))
                 .border_style(Theme::border()),
         );
     }
@@ -113,11 +112,6 @@ impl ConfigEditorComponent {
             .and_then(|i| CONFIG_FILES.get(i))
             .map(|(path, _)| *path)
     }
-
-    pub fn set_status(&mut self, message: String, is_error: bool) {
-        self.status_message = Some((message, is_error));
-        self.is_saving = false;
-    }
 }
 
 impl Default for ConfigEditorComponent {
@@ -150,7 +144,7 @@ impl Component for ConfigEditorComponent {
                     if let Some(path) = self.get_selected_file() {
                         let path = path.to_string();
                         if let Err(e) = self.load_file(&path) {
-                            self.status_message = Some((format!("Error: {}", e), true));
+                            self.status_message = Some((trf("Error: {}", &[&e]), true));
                         }
                     }
                     None
@@ -163,14 +157,14 @@ impl Component for ConfigEditorComponent {
                     match key.code {
                         KeyCode::Char('s') => {
                             if let Err(e) = self.save_file() {
-                                self.status_message = Some((format!("Save error: {}", e), true));
+                                self.status_message = Some((trf("Save error: {}", &[&e]), true));
                             }
                             return None;
                         }
                         KeyCode::Char('q') => {
                             if self.is_modified {
                                 self.status_message =
-                                    Some(("Unsaved changes! Ctrl+S to save, Ctrl+X to discard".to_string(), true));
+                                    Some((tr("Unsaved changes! Ctrl+S to save, Ctrl+X to discard").to_string(), true));
                             } else {
                                 self.close_editor();
                             }
@@ -206,7 +200,7 @@ impl Component for ConfigEditorComponent {
 
         // Title
         let title = Paragraph::new(Line::from(vec![Span::styled(
-            "Configuration Editor",
+            tr("Configuration Editor"),
             Theme::title(),
         )]))
         .block(Block::default().borders(Borders::BOTTOM));
@@ -220,7 +214,7 @@ impl Component for ConfigEditorComponent {
                     .map(|(path, desc)| {
                         ListItem::new(Line::from(vec![
                             Span::styled(*path, Theme::default().add_modifier(Modifier::BOLD)),
-                            Span::styled(format!(" - {}", desc), Theme::muted()),
+                            Span::styled(format!(" - {}", tr(desc)), Theme::muted()),
                         ]))
                     })
                     .collect();
@@ -229,7 +223,7 @@ impl Component for ConfigEditorComponent {
                     .block(
                         Block::default()
                             .borders(Borders::ALL)
-                            .title("Select file to edit"),
+                            .title(tr("Select file to edit")),
                     )
                     .highlight_style(Theme::highlight().add_modifier(Modifier::BOLD))
                     .highlight_symbol("→ ");
@@ -252,12 +246,19 @@ impl Component for ConfigEditorComponent {
         } else {
             match self.mode {
                 EditorMode::FileSelect => {
-                    Paragraph::new("Press Enter to edit file").style(Theme::muted())
+                    Paragraph::new(tr("Press Enter to edit file")).style(Theme::muted())
                 }
                 EditorMode::Editing => {
-                    let modified = if self.is_modified { " [Modified]" } else { "" };
+                    let modified = if self.is_modified {
+                        format!(" [{}]", tr("Modified"))
+                    } else {
+                        String::new()
+                    };
                     Paragraph::new(format!(
-                        "Ctrl+S: Save  Ctrl+Q: Close  Ctrl+X: Discard{}",
+                        "Ctrl+S: {}  Ctrl+Q: {}  Ctrl+X: {}{}",
+                        tr("Save"),
+                        tr("Close"),
+                        tr("Discard"),
                         modified
                     ))
                     .style(Theme::muted())
@@ -270,6 +271,10 @@ impl Component for ConfigEditorComponent {
         );
     }
 
+    fn is_typing(&self) -> bool {
+        self.mode == EditorMode::Editing
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
         match self.mode {
             EditorMode::FileSelect => vec![("↑/↓", "Navigate"), ("Enter", "Edit")],
@@ -280,11 +285,38 @@ impl Component for ConfigEditorComponent {
             ],
         }
     }
+
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        match self.mode {
+            EditorMode::FileSelect => Vec::new(),
+            EditorMode::Editing => vec!["Ctrl+S"],
+        }
+    }
+
+    fn search_items(&self) -> Vec<SearchItem> {
+        CONFIG_FILES
+            .iter()
+            .map(|(path, description)| SearchItem::new(*path, format!("{} - {}", path, tr(description))))
+            .collect()
+    }
+
+    fn select_item(&mut self, id: &str) {
+        // Unsaved edits stay open rather than being dropped
+        if self.is_modified {
+            return;
+        }
+        self.close_editor();
+        if let Some(i) = CONFIG_FILES.iter().position(|(path, _)| *path == id) {
+            self.file_list_state.select(Some(i));
+        }
+    }
 }
 
 impl AsyncComponent for ConfigEditorComponent {
-    fn set_progress_channel(&mut self, tx: mpsc::UnboundedSender<String>) {
-        self.progress_tx = Some(tx);
+    fn add_progress(&mut self, line: String) {
+        if self.is_saving {
+            self.status_message = Some((line, false));
+        }
     }
 
     fn is_running(&self) -> bool {
@@ -6,12 +6,30 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
+use chrono::{Local, NaiveDateTime};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
 
 use crate::app::Message;
-use crate::components::Component;
+use crate::components::settings;
+use crate::components::{Component, SearchItem};
+use crate::slackware::at::{self, AtJob};
+use crate::slackware::cron::{self, CronSource, RunEvent, Target};
+use crate::slackware::cronexpr::{self, Schedule};
+use crate::slackware::cronlog::{self, History};
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+
+/// Fields of the new job form, in display order
+const FORM_FIELDS: [&str; 5] = ["Target", "User", "Name", "Schedule", "Command"];
+
+/// Where a new job can go, as cycled through in the Target field
+const TARGETS: [&str; 3] = ["User crontab", "/etc/cron.d file", "Periodic script"];
+
+/// Lines of output kept from a job run with 'r'
+const MAX_RUN_LINES: usize = 1000;
 
 /// Cron job entry
 #[derive(Debug, Clone)]
@@ -25,12 +43,8 @@ pub struct CronJob {
     pub source: CronSource,
     pub enabled: bool,
     pub raw_line: String,
-}
-
-#[derive(Debug, Clone)]
-pub enum CronSource {
-    System(String),    // Path to file in /etc/cron.*
-    User(String),      // Username
+    /// When crond next runs the job; `None` when disabled or at reboot
+    pub next_run: Option<NaiveDateTime>,
 }
 
 /// Cron Job Manager Component
@@ -42,12 +56,137 @@ pub struct CronComponent {
     status_message: Option<(String, bool)>,
     show_confirm: bool,
     pending_action: Option<CronAction>,
+    /// The job being added, the highlighted form field and, while a field
+    /// is being typed into, its text
+    draft: JobDraft,
+    form_field: usize,
+    edit_buffer: Option<String>,
+    editing_job: Option<usize>,
+    /// Backup taken by the last delete or toggle, restored by 'u'
+    last_undo: Option<(CronSource, PathBuf)>,
+    run: Option<JobRun>,
+    /// Pending at(1) jobs with their commands, shown in `CronMode::At`
+    at_jobs: Vec<(AtJob, String)>,
+    at_state: ListState,
+    atd_running: bool,
+    /// The prompt being typed into, and the command entered before the time
+    at_prompt: Option<(AtPrompt, String)>,
+    at_command: String,
+    /// The user picked with 'U'; only their crontab is shown
+    user_filter: Option<String>,
+    users: Vec<String>,
+    users_state: ListState,
+    /// Runs and failures from the cron log, by (user, command)
+    history: HashMap<(String, String), History>,
+    /// The crontab whose variables `CronMode::Env` edits, its variables
+    /// (unset ones of ENV_VARS included) and the value being typed
+    env_source: Option<CronSource>,
+    env_vars: Vec<(String, Option<String>)>,
+    env_state: ListState,
+    env_buffer: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AtPrompt {
+    Command,
+    Time,
+}
+
+/// A job started by hand, shown in the output pane
+struct JobRun {
+    title: String,
+    lines: Vec<String>,
+    /// Set once the job finished; the inner `None` means killed by a signal
+    exit: Option<Option<i32>>,
+    rx: Option<mpsc::UnboundedReceiver<RunEvent>>,
+}
+
+/// A job being filled in on the add form
+#[derive(Debug, Clone, Default)]
+struct JobDraft {
+    /// Index into TARGETS
+    target: usize,
+    user: String,
+    /// cron.d file or script name
+    name: String,
+    schedule: String,
+    command: String,
+}
+
+impl JobDraft {
+    fn value(&self, field: usize) -> String {
+        match field {
+            0 => TARGETS[self.target].to_string(),
+            1 => self.user.clone(),
+            2 => self.name.clone(),
+            3 => self.schedule.clone(),
+            _ => self.command.clone(),
+        }
+    }
+
+    fn set(&mut self, field: usize, value: String) {
+        match field {
+            1 => self.user = value,
+            2 => self.name = value,
+            3 => self.schedule = value,
+            4 => self.command = value,
+            _ => {}
+        }
+    }
+
+    /// First field that can be changed: only the schedule and command of
+    /// an existing job
+    fn first_field(mode: CronMode) -> usize {
+        if mode == CronMode::Edit {
+            3
+        } else {
+            0
+        }
+    }
+
+    /// Whether a field means anything for the chosen target
+    fn uses(&self, field: usize) -> bool {
+        match field {
+            1 => self.target != 2,
+            2 => self.target != 0,
+            _ => true,
+        }
+    }
+
+    fn to_target(&self) -> Result<Target, String> {
+        let user = self.user.trim().to_string();
+        let name = self.name.trim().to_string();
+        match self.target {
+            0 => Ok(Target::User(user)),
+            1 => Ok(Target::CronD { name, user }),
+            _ => {
+                // Periodic scripts run whenever run-parts runs their directory
+                let period = self.schedule.trim().trim_start_matches('@');
+                if !cron::PERIODS.contains(&period) {
+                    return Err("Periodic scripts need @hourly, @daily, @weekly or @monthly"
+                        .to_string());
+                }
+                Ok(Target::Script {
+                    name,
+                    period: period.to_string(),
+                })
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CronMode {
     View,
     Add,
+    /// Changing the schedule and command of the job in `editing_job`
+    Edit,
+    /// One-shot at(1) jobs
+    At,
+    /// Picking whose crontab to show
+    Users,
+    /// MAILTO, PATH, SHELL and other variables of one crontab
+    Env,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -65,6 +204,15 @@ pub enum CronFilter {
 pub enum CronAction {
     Delete(usize),
     Toggle(usize),
+    Run(usize),
+    /// Remove the at job with this number
+    RemoveAt(String),
+}
+
+impl CronAction {
+    fn always_confirm(&self) -> bool {
+        matches!(self, CronAction::Delete(_) | CronAction::RemoveAt(_))
+    }
 }
 
 impl CronComponent {
@@ -77,6 +225,25 @@ impl CronComponent {
             status_message: None,
             show_confirm: false,
             pending_action: None,
+            draft: JobDraft::default(),
+            form_field: 0,
+            edit_buffer: None,
+            editing_job: None,
+            last_undo: None,
+            run: None,
+            at_jobs: Vec::new(),
+            at_state: ListState::default(),
+            atd_running: false,
+            at_prompt: None,
+            at_command: String::new(),
+            user_filter: None,
+            users: Vec::new(),
+            users_state: ListState::default(),
+            history: HashMap::new(),
+            env_source: None,
+            env_vars: Vec::new(),
+            env_state: ListState::default(),
+            env_buffer: None,
         };
         component.load_cron_jobs();
         if !component.jobs.is_empty() {
@@ -109,6 +276,87 @@ impl CronComponent {
 
         // Load user crontabs
         self.load_user_crontabs();
+        self.refresh_next_runs();
+        self.history = cronlog::load();
+    }
+
+    /// Five-field schedule of a job. Periodic scripts run when the
+    /// run-parts line for their directory does, if there is one.
+    fn five_fields(&self, job: &CronJob) -> String {
+        let job = self.runner(job).unwrap_or(job);
+        format!("{} {} {} {} {}", job.minute, job.hour, job.day, job.month, job.weekday)
+    }
+
+    /// The run-parts line that runs a periodic script
+    fn runner(&self, job: &CronJob) -> Option<&CronJob> {
+        if !job.source.is_script() {
+            return None;
+        }
+        let path = job.source.path();
+        let dir = Path::new(&path).parent()?.to_string_lossy().to_string();
+        self.jobs.iter().find(|j| {
+            !j.source.is_script() && j.command.contains("run-parts") && j.command.contains(&dir)
+        })
+    }
+
+    /// What the cron log says about a job. Periodic scripts share the
+    /// history of their run-parts line.
+    fn job_history(&self, job: &CronJob) -> Option<&History> {
+        let job = self.runner(job).unwrap_or(job);
+        let (user, command) = Self::run_as(job);
+        self.history.get(&(user, command.trim().to_string()))
+    }
+
+    fn format_history(&self, job: &CronJob) -> Vec<Span<'static>> {
+        let Some(history) = self.job_history(job) else {
+            return Vec::new();
+        };
+        let mut spans = Vec::new();
+        if let Some(time) = history.last_run {
+            spans.push(Span::styled(
+                format!("  last run {}", time.format("%b %e %H:%M")),
+                Theme::muted(),
+            ));
+        }
+        if let Some((time, status)) = history.last_failure {
+            spans.push(Span::styled(
+                format!(
+                    "  {} failed, last {} (exit {})",
+                    history.failures,
+                    time.format("%b %e %H:%M"),
+                    status
+                ),
+                Theme::error(),
+            ));
+        }
+        spans
+    }
+
+    fn refresh_next_runs(&mut self) {
+        let now = Local::now().naive_local();
+        let next_runs: Vec<Option<NaiveDateTime>> = self
+            .jobs
+            .iter()
+            .map(|job| {
+                let schedule = Schedule::parse(&self.five_fields(job))?;
+                schedule.next_after(now).filter(|_| job.enabled)
+            })
+            .collect();
+        for (job, next_run) in self.jobs.iter_mut().zip(next_runs) {
+            job.next_run = next_run;
+        }
+    }
+
+    fn format_next_run(&self, job: &CronJob) -> String {
+        match job.next_run {
+            Some(time) => {
+                let seconds = (time - Local::now().naive_local()).num_seconds();
+                cronexpr::format_until((seconds + 59) / 60)
+            }
+            None if !job.enabled => "disabled".to_string(),
+            None if job.minute == "@reboot" => "at boot".to_string(),
+            None => "never".to_string(),
+        }
     }
 
     fn load_cron_dir(&mut self, dir: &str, period: &str) {
@@ -149,6 +397,7 @@ impl CronComponent {
                         source: CronSource::System(format!("{}/{}", dir, name)),
                         enabled: is_executable,
                         raw_line: format!("@{} {}", period, name),
+                        next_run: None,
                     });
                 }
             }
@@ -176,36 +425,265 @@ impl CronComponent {
         }
     }
 
+    /// Read user crontabs through `crontab -u`: the picked user's, or
+    /// everyone's that has one
     fn load_user_crontabs(&mut self) {
-        let crontab_dir = Path::new("/var/spool/cron/crontabs");
-        if !crontab_dir.exists() {
+        let users = match &self.user_filter {
+            Some(user) => vec![user.clone()],
+            None => fs::read_dir(cron::CRONTAB_DIR)
+                .map(|entries| {
+                    entries
+                        .filter_map(|e| e.ok())
+                        .map(|e| e.file_name().to_string_lossy().to_string())
+                        .filter(|name| cron::is_valid_name(name))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        for username in users {
+            let content = match cron::read_user_crontab(&username) {
+                Ok(content) => content,
+                Err(e) => {
+                    self.status_message = Some((e.to_string(), true));
+                    continue;
+                }
+            };
+            for line in content.lines() {
+                if let Some(job) = self.parse_cron_line(line, CronSource::User(username.clone())) {
+                    self.jobs.push(job);
+                }
+            }
+        }
+    }
+
+    /// Edit the variables of the selected job's crontab, or of the picked
+    /// user's when they have no jobs yet
+    fn open_env_editor(&mut self) {
+        let source = match (self.selected_job(), &self.user_filter) {
+            (Some((_, job)), _) => job.source.clone(),
+            (None, Some(user)) => CronSource::User(user.clone()),
+            (None, None) => return,
+        };
+        if source.is_script() {
+            self.status_message =
+                Some((tr("Periodic scripts have no crontab variables").to_string(), true));
             return;
         }
+        self.env_source = Some(source);
+        self.load_env_vars();
+        self.env_state.select(Some(0));
+        self.mode = CronMode::Env;
+        self.status_message = None;
+    }
 
-        if let Ok(entries) = fs::read_dir(crontab_dir) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let username = entry.file_name().to_string_lossy().to_string();
-                if let Ok(content) = fs::read_to_string(entry.path()) {
-                    for line in content.lines() {
-                        if let Some(job) = self.parse_cron_line(line, CronSource::User(username.clone())) {
-                            self.jobs.push(job);
-                        }
-                    }
+    fn load_env_vars(&mut self) {
+        let Some(source) = &self.env_source else {
+            return;
+        };
+        let set = match source.read() {
+            Ok(content) => cron::env_vars(&content),
+            Err(e) => {
+                self.status_message = Some((e.to_string(), true));
+                Vec::new()
+            }
+        };
+        let mut vars: Vec<(String, Option<String>)> = cron::ENV_VARS
+            .iter()
+            .map(|name| {
+                let value = set.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone());
+                (name.to_string(), value)
+            })
+            .collect();
+        for (name, value) in set {
+            if !vars.iter().any(|(n, _)| *n == name) {
+                vars.push((name, Some(value)));
+            }
+        }
+        self.env_vars = vars;
+    }
+
+    fn set_env_var(&mut self, value: Option<&str>) {
+        let (Some(source), Some(index)) = (&self.env_source, self.env_state.selected()) else {
+            return;
+        };
+        let Some((name, _)) = self.env_vars.get(index) else {
+            return;
+        };
+        let value = value.filter(|v| !v.trim().is_empty());
+        self.status_message = Some(match cron::update_env(source, name, value) {
+            Ok(backup) => (
+                trf(
+                    "{} {} in {} (backup: {})",
+                    &[
+                        tr(if value.is_some() { "Set" } else { "Removed" }),
+                        name,
+                        &source.path(),
+                        &backup.display().to_string(),
+                    ],
+                ),
+                false,
+            ),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_env_vars();
+    }
+
+    fn handle_env_input(&mut self, key: KeyEvent) {
+        if let Some(buffer) = &mut self.env_buffer {
+            match key.code {
+                KeyCode::Enter => {
+                    let value = buffer.clone();
+                    self.env_buffer = None;
+                    self.set_env_var(Some(&value));
+                }
+                KeyCode::Esc => self.env_buffer = None,
+                KeyCode::Backspace => {
+                    buffer.pop();
                 }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return;
+        }
+        let selected = self.env_state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.env_state.select(Some(selected.saturating_sub(1)));
             }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.env_vars.len().saturating_sub(1);
+                self.env_state.select(Some((selected + 1).min(last)));
+            }
+            KeyCode::Enter => {
+                let value = self.env_vars.get(selected).and_then(|(_, v)| v.clone());
+                self.env_buffer = Some(value.unwrap_or_default());
+            }
+            KeyCode::Char('x') | KeyCode::Delete => self.set_env_var(None),
+            KeyCode::Esc => {
+                self.mode = CronMode::View;
+                self.env_source = None;
+                self.load_cron_jobs();
+            }
+            _ => {}
         }
     }
 
+    fn render_env(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .env_vars
+            .iter()
+            .enumerate()
+            .map(|(i, (name, value))| {
+                let editing = self.env_buffer.is_some() && self.env_state.selected() == Some(i);
+                let value = match (&self.env_buffer, value) {
+                    (Some(buffer), _) if editing => {
+                        Span::styled(format!("{}_", buffer), Theme::input_active())
+                    }
+                    (_, Some(value)) => Span::raw(value.clone()),
+                    (_, None) => Span::styled("(not set)", Theme::muted()),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<10}", name), Theme::label()),
+                    value,
+                ]))
+            })
+            .collect();
+        let title = format!(
+            " Variables of {} ",
+            self.env_source.as_ref().map(|s| s.path()).unwrap_or_default()
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.env_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn open_user_picker(&mut self) {
+        self.users = cron::crontab_users();
+        let current = self
+            .user_filter
+            .as_ref()
+            .and_then(|u| self.users.iter().position(|name| name == u));
+        // Row 0 is "All users"
+        self.users_state.select(Some(current.map_or(0, |i| i + 1)));
+        self.mode = CronMode::Users;
+        self.status_message = None;
+    }
+
+    fn handle_users_input(&mut self, key: KeyEvent) {
+        let selected = self.users_state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.users_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.users_state.select(Some((selected + 1).min(self.users.len())));
+            }
+            KeyCode::Enter => {
+                self.user_filter = selected.checked_sub(1).and_then(|i| self.users.get(i)).cloned();
+                self.mode = CronMode::View;
+                self.load_cron_jobs();
+                let first = (!self.filtered_jobs().is_empty()).then_some(0);
+                self.list_state.select(first);
+                let shown = match &self.user_filter {
+                    Some(user) => trf("Showing the crontab of {}; 'a' adds to it", &[user]),
+                    None => tr("Showing all crontabs").to_string(),
+                };
+                self.status_message = Some((shown, false));
+            }
+            KeyCode::Esc => self.mode = CronMode::View,
+            _ => {}
+        }
+    }
+
+    fn render_users(&self, frame: &mut Frame, area: Rect) {
+        let count = |user: &str| {
+            self.jobs
+                .iter()
+                .filter(|j| matches!(&j.source, CronSource::User(u) if u == user))
+                .count()
+        };
+        let mut items = vec![ListItem::new(Line::from(Span::styled(
+            "All users",
+            Theme::label(),
+        )))];
+        items.extend(self.users.iter().map(|user| {
+            // Only counted when the crontab was loaded, i.e. with no user picked
+            let jobs = match count(user) {
+                0 if self.user_filter.is_none() => "no crontab".to_string(),
+                0 => String::new(),
+                n => format!("{} jobs", n),
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<20}", user)),
+                Span::styled(jobs, Theme::muted()),
+            ]))
+        }));
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Crontab of "))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.users_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
     fn parse_cron_line(&self, line: &str, source: CronSource) -> Option<CronJob> {
-        let line = line.trim();
+        let raw_line = line.trim();
 
-        // Skip empty lines and comments
+        // Commented-out jobs are listed as disabled, other comments skipped
+        let (line, enabled) = match cron::disabled_job(raw_line) {
+            Some(job) => (job, false),
+            None => (raw_line, true),
+        };
         if line.is_empty() || line.starts_with('#') {
             return None;
         }
 
-        // Skip variable assignments
-        if line.contains('=') && !line.contains(' ') {
+        // Variable assignments are edited with 'v'
+        if cron::env_assignment(line).is_some() {
             return None;
         }
 
@@ -232,8 +710,9 @@ impl CronComponent {
                 weekday,
                 command,
                 source,
-                enabled: true,
-                raw_line: line.to_string(),
+                enabled,
+                raw_line: raw_line.to_string(),
+                next_run: None,
             });
         }
 
@@ -255,8 +734,9 @@ impl CronComponent {
                     weekday: parts[4].to_string(),
                     command: parts[cmd_start..].join(" "),
                     source,
-                    enabled: true,
-                    raw_line: line.to_string(),
+                    enabled,
+                    raw_line: raw_line.to_string(),
+                    next_run: None,
                 });
             }
         }
@@ -264,10 +744,16 @@ impl CronComponent {
         None
     }
 
+    /// Jobs shown under the current filter, the soonest to run first
     fn filtered_jobs(&self) -> Vec<(usize, &CronJob)> {
-        self.jobs
+        let mut jobs: Vec<(usize, &CronJob)> = self
+            .jobs
             .iter()
             .enumerate()
+            .filter(|(_, job)| match &self.user_filter {
+                Some(user) => matches!(&job.source, CronSource::User(u) if u == user),
+                None => true,
+            })
             .filter(|(_, job)| match self.filter {
                 CronFilter::All => true,
                 CronFilter::System => matches!(job.source, CronSource::System(_)),
@@ -277,7 +763,15 @@ impl CronComponent {
                 CronFilter::Weekly => job.raw_line.contains("weekly") || job.weekday != "*",
                 CronFilter::Monthly => job.raw_line.contains("monthly") || (job.day == "1" && job.month == "*"),
             })
-            .collect()
+            .collect();
+        jobs.sort_by_key(|(_, job)| (job.next_run.is_none(), job.next_run));
+        jobs
+    }
+
+    /// What the global search finds `job` by: the same line may be in
+    /// several crontabs
+    fn search_id(job: &CronJob) -> String {
+        format!("{}\n{}", job.source.path(), job.raw_line)
     }
 
     fn selected_job(&self) -> Option<(usize, &CronJob)> {
@@ -285,65 +779,508 @@ impl CronComponent {
         self.list_state.selected().and_then(|i| filtered.get(i).copied())
     }
 
-    fn format_schedule(&self, job: &CronJob) -> String {
-        if job.minute == "@reboot" {
-            return "At reboot".to_string();
+    /// The schedule as written: an @keyword or five fields
+    fn schedule_text(&self, job: &CronJob) -> String {
+        let line = cron::disabled_job(&job.raw_line).unwrap_or(&job.raw_line);
+        if line.starts_with('@') && !job.source.is_script() {
+            return line.split_whitespace().next().unwrap_or_default().to_string();
         }
+        self.five_fields(job)
+    }
+
+    fn start_add(&mut self) {
+        self.draft = JobDraft {
+            user: self.user_filter.clone().unwrap_or_else(|| "root".to_string()),
+            schedule: "@daily".to_string(),
+            ..JobDraft::default()
+        };
+        self.form_field = 0;
+        self.edit_buffer = None;
+        self.mode = CronMode::Add;
+        self.status_message = None;
+    }
+
+    fn start_edit(&mut self) {
+        let Some((index, job)) = self.selected_job() else {
+            return;
+        };
+        if job.source.is_script() {
+            self.status_message = Some((
+                trf("{} is a script; edit it in the Config editor", &[&job.source.path()]),
+                true,
+            ));
+            return;
+        }
+        let schedule = self.schedule_text(job);
+        let (target, user) = match &job.source {
+            CronSource::User(user) => (0, user.clone()),
+            CronSource::System(_) => {
+                let user = cron::line_user(&job.raw_line).filter(|_| job.source.has_user_field());
+                (1, user.unwrap_or("root").to_string())
+            }
+        };
+        self.draft = JobDraft {
+            target,
+            user,
+            name: String::new(),
+            schedule,
+            command: job.command.clone(),
+        };
+        self.editing_job = Some(index);
+        self.form_field = 3;
+        self.edit_buffer = None;
+        self.mode = CronMode::Edit;
+        self.status_message = None;
+    }
 
-        // Try to create human-readable schedule
-        let mut parts = Vec::new();
+    fn save_edit(&mut self) {
+        let Some(job) = self.editing_job.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        if let Err(e) = cronexpr::validate(&self.draft.schedule) {
+            self.status_message = Some((e, true));
+            return;
+        }
+        if self.draft.command.trim().is_empty() {
+            self.status_message = Some((tr("The command is empty").to_string(), true));
+            return;
+        }
+        let line = cron::rebuild_line(
+            &job.raw_line,
+            job.source.has_user_field(),
+            &self.draft.schedule,
+            &self.draft.command,
+        );
+        match cron::update_job(&job.source, &job.raw_line, Some(&line)) {
+            Ok(backup) => {
+                self.status_message = Some((
+                    trf(
+                        "Updated {} (backup: {})",
+                        &[&job.source.path(), &backup.display().to_string()],
+                    ),
+                    false,
+                ));
+                self.mode = CronMode::View;
+                self.load_cron_jobs();
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+    }
 
-        if job.minute != "*" && job.minute != "0" {
-            parts.push(format!(":{}", job.minute));
+    fn confirm(&mut self, action: fn(usize) -> CronAction) {
+        if let Some((index, _)) = self.selected_job() {
+            self.request(action(index));
         }
+    }
 
-        if job.hour == "*" {
-            parts.push("Every hour".to_string());
+    /// Ask before running `action`, unless confirmations are off and it is low-risk
+    fn request(&mut self, action: CronAction) {
+        if settings::confirm_actions() || action.always_confirm() {
+            self.pending_action = Some(action);
+            self.show_confirm = true;
         } else {
-            parts.push(format!("{}:00", job.hour));
+            self.execute_action(action);
         }
+    }
 
-        if job.day != "*" {
-            parts.push(format!("day {}", job.day));
+    /// The user crond runs a job as and the command it runs
+    fn run_as(job: &CronJob) -> (String, String) {
+        if job.source.is_script() {
+            return ("root".to_string(), job.source.path());
         }
+        let user = match &job.source {
+            CronSource::User(user) => user.clone(),
+            CronSource::System(_) if job.source.has_user_field() => {
+                let line = cron::disabled_job(&job.raw_line).unwrap_or(&job.raw_line);
+                cron::line_user(line).unwrap_or("root").to_string()
+            }
+            CronSource::System(_) => "root".to_string(),
+        };
+        (user, job.command.clone())
+    }
 
-        if job.weekday != "*" {
-            let day_name = match job.weekday.as_str() {
-                "0" | "7" => "Sun",
-                "1" => "Mon",
-                "2" => "Tue",
-                "3" => "Wed",
-                "4" => "Thu",
-                "5" => "Fri",
-                "6" => "Sat",
-                _ => &job.weekday,
-            };
-            parts.push(day_name.to_string());
+    fn start_run(&mut self, index: usize) {
+        let Some(job) = self.jobs.get(index) else {
+            return;
+        };
+        let (user, command) = Self::run_as(job);
+        self.run = Some(JobRun {
+            title: format!("{} (as {})", command, user),
+            lines: Vec::new(),
+            exit: None,
+            rx: Some(cron::run_now(&user, &command)),
+        });
+        self.status_message = Some((trf("Running {} as {}", &[&command, &user]), false));
+    }
+
+    fn load_at_jobs(&mut self) {
+        self.atd_running = at::atd_running();
+        match at::list() {
+            Ok(jobs) => {
+                self.at_jobs = jobs
+                    .into_iter()
+                    .map(|job| {
+                        let command = at::command_of(&job.id).unwrap_or_default();
+                        (job, command)
+                    })
+                    .collect();
+            }
+            Err(e) => {
+                self.at_jobs.clear();
+                self.status_message = Some((e.to_string(), true));
+            }
         }
+        let selected = self.at_state.selected().unwrap_or(0);
+        self.at_state.select(if self.at_jobs.is_empty() {
+            None
+        } else {
+            Some(selected.min(self.at_jobs.len() - 1))
+        });
+    }
+
+    fn submit_at_prompt(&mut self, prompt: AtPrompt, input: String) {
+        match prompt {
+            AtPrompt::Command if input.trim().is_empty() => {}
+            AtPrompt::Command => {
+                self.at_command = input;
+                self.at_prompt = Some((AtPrompt::Time, "now + 1 hour".to_string()));
+            }
+            AtPrompt::Time => {
+                self.status_message = Some(match at::schedule(&input, &self.at_command) {
+                    Ok(job) => (job, false),
+                    Err(e) => (e.to_string(), true),
+                });
+                self.load_at_jobs();
+            }
+        }
+    }
 
-        if parts.is_empty() {
-            format!("{} {} {} {} {}", job.minute, job.hour, job.day, job.month, job.weekday)
+    fn handle_at_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                let selected = self.at_state.selected().unwrap_or(0);
+                self.at_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(selected) = self.at_state.selected() {
+                    if selected + 1 < self.at_jobs.len() {
+                        self.at_state.select(Some(selected + 1));
+                    }
+                }
+            }
+            KeyCode::Char('a') => {
+                self.at_prompt = Some((AtPrompt::Command, String::new()));
+                self.status_message = None;
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some((job, _)) = self.at_state.selected().and_then(|i| self.at_jobs.get(i)) {
+                    self.request(CronAction::RemoveAt(job.id.clone()));
+                }
+            }
+            KeyCode::F(5) => {
+                self.load_at_jobs();
+                self.status_message = Some((tr("At jobs refreshed").to_string(), false));
+            }
+            KeyCode::Esc => {
+                self.mode = CronMode::View;
+                self.status_message = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn render_at(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .at_jobs
+            .iter()
+            .map(|(job, command)| {
+                let running = job.queue == "=";
+                ListItem::new(vec![
+                    Line::from(vec![
+                        Span::styled(format!("#{:<6}", job.id), Theme::label()),
+                        Span::styled(
+                            format!("{:<26}", job.time),
+                            Theme::warning(),
+                        ),
+                        Span::raw(format!("{:<10}", job.user)),
+                        if running {
+                            Span::styled("running", Theme::success())
+                        } else {
+                            Span::styled(
+                                format!("queue {}", job.queue),
+                                Theme::muted(),
+                            )
+                        },
+                    ]),
+                    Line::from(format!("    {}", command.lines().next().unwrap_or_default())),
+                ])
+            })
+            .collect();
+        let title = if self.atd_running {
+            Line::from(" At Jobs ")
         } else {
-            parts.join(" ")
+            Line::from(vec![
+                Span::raw(" At Jobs "),
+                Span::styled(
+                    "(atd is not running, jobs won't fire) ",
+                    Theme::error(),
+                ),
+            ])
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.at_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn confirm_text(&self) -> String {
+        if let Some(CronAction::Run(index)) = self.pending_action {
+            if let Some(job) = self.jobs.get(index) {
+                let (user, command) = Self::run_as(job);
+                return format!("Run '{}' now as {}? ", command, user);
+            }
+        }
+        if let Some(CronAction::RemoveAt(id)) = &self.pending_action {
+            return format!("Remove at job {}? ", id);
+        }
+        let (verb, index) = match self.pending_action {
+            Some(CronAction::Delete(i)) => ("Delete", i),
+            Some(CronAction::Toggle(i)) => match self.jobs.get(i) {
+                Some(job) if job.enabled => ("Disable", i),
+                _ => ("Enable", i),
+            },
+            _ => return "Confirm action? ".to_string(),
+        };
+        match self.jobs.get(index) {
+            Some(job) => format!("{} '{}' in {}? ", verb, job.command, job.source.path()),
+            None => "Confirm action? ".to_string(),
         }
     }
 
+    /// Delete or toggle a job. Crontab lines are removed or commented out;
+    /// periodic scripts are moved to the backup directory or chmodded.
+    fn execute_action(&mut self, action: CronAction) {
+        let index = match action {
+            CronAction::Delete(index) | CronAction::Toggle(index) => index,
+            CronAction::Run(index) => return self.start_run(index),
+            CronAction::RemoveAt(id) => {
+                self.status_message = Some(match at::remove(&id) {
+                    Ok(()) => (trf("Removed at job {}", &[&id]), false),
+                    Err(e) => (e.to_string(), true),
+                });
+                return self.load_at_jobs();
+            }
+        };
+        let Some(job) = self.jobs.get(index).cloned() else {
+            return;
+        };
+        let path = job.source.path();
+        let (done, result) = match action {
+            CronAction::Delete(_) if job.source.is_script() => {
+                ("Removed", cron::remove_script(&path).map(Some))
+            }
+            CronAction::Delete(_) => (
+                "Deleted",
+                cron::update_job(&job.source, &job.raw_line, None).map(Some),
+            ),
+            CronAction::Toggle(_) if job.source.is_script() => (
+                if job.enabled { "Disabled" } else { "Enabled" },
+                cron::set_script_enabled(&path, !job.enabled).map(|_| None),
+            ),
+            CronAction::Toggle(_) => {
+                let line = cron::toggled_line(&job.raw_line, job.enabled);
+                (
+                    if job.enabled { "Disabled" } else { "Enabled" },
+                    cron::update_job(&job.source, &job.raw_line, Some(&line)).map(Some),
+                )
+            }
+            CronAction::Run(_) | CronAction::RemoveAt(_) => return,
+        };
+        match result {
+            Ok(Some(backup)) => {
+                self.status_message = Some((
+                    trf("{} '{}' in {}, 'u' undoes", &[tr(done), &job.command, &path]),
+                    false,
+                ));
+                self.last_undo = Some((job.source, backup));
+            }
+            Ok(None) => {
+                self.status_message = Some((format!("{} {}", tr(done), path), false));
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+        self.load_cron_jobs();
+        let len = self.filtered_jobs().len();
+        if self.list_state.selected().is_some_and(|s| s >= len) {
+            self.list_state.select(len.checked_sub(1));
+        }
+    }
+
+    fn undo(&mut self) {
+        let Some((source, backup)) = self.last_undo.take() else {
+            self.status_message = Some((tr("Nothing to undo").to_string(), true));
+            return;
+        };
+        self.status_message = Some(match cron::restore(&source, &backup) {
+            Ok(()) => (
+                trf(
+                    "Restored {} from {}",
+                    &[&source.path(), &backup.display().to_string()],
+                ),
+                false,
+            ),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_cron_jobs();
+    }
+
+    /// Cycle the target or the schedule preset of the highlighted field
+    fn cycle_field(&mut self, forward: bool) {
+        let step = |index: usize, len: usize| {
+            if forward {
+                (index + 1) % len
+            } else {
+                (index + len - 1) % len
+            }
+        };
+        match self.form_field {
+            0 => self.draft.target = step(self.draft.target, TARGETS.len()),
+            3 => {
+                let current = cron::PRESETS
+                    .iter()
+                    .position(|p| *p == self.draft.schedule.trim());
+                let next = match current {
+                    Some(i) => step(i, cron::PRESETS.len()),
+                    None => 0,
+                };
+                self.draft.schedule = cron::PRESETS[next].to_string();
+            }
+            _ => {}
+        }
+    }
+
+    fn save_draft(&mut self) {
+        let result = self
+            .draft
+            .to_target()
+            .and_then(|target| {
+                cron::add_job(&target, &self.draft.schedule, &self.draft.command)
+                    .map_err(|e| e.to_string())
+            });
+        match result {
+            Ok(written) => {
+                self.mode = CronMode::View;
+                self.load_cron_jobs();
+                self.status_message = Some((trf("Job added to {}", &[&written]), false));
+            }
+            Err(e) => self.status_message = Some((e, true)),
+        }
+    }
+
+    fn render_form(&self, frame: &mut Frame, area: Rect) {
+        let title = if self.mode == CronMode::Edit {
+            " Edit Cron Job "
+        } else {
+            " New Cron Job "
+        };
+        let items: Vec<ListItem> = FORM_FIELDS
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let editing = self.edit_buffer.is_some() && i == self.form_field;
+                let editing_source = self
+                    .editing_job
+                    .and_then(|j| self.jobs.get(j))
+                    .filter(|_| self.mode == CronMode::Edit && i == 0);
+                let value = match (&self.edit_buffer, editing_source) {
+                    (Some(buffer), _) if editing => format!("{}_", buffer),
+                    (_, Some(job)) => job.source.path(),
+                    _ => self.draft.value(i),
+                };
+                let value_style = if editing {
+                    Theme::input_active()
+                } else if !self.draft.uses(i) || i < JobDraft::first_field(self.mode) {
+                    Theme::muted()
+                } else {
+                    Style::default()
+                };
+                let hint = match i {
+                    0 if self.mode == CronMode::Add => "  ←/→ to change",
+                    3 => "  ←/→ for presets",
+                    _ => "",
+                };
+                let mut spans = vec![
+                    Span::styled(format!("{:<10}", label), Theme::label()),
+                    Span::styled(value, value_style),
+                    Span::styled(hint, Theme::muted()),
+                ];
+                if i == 3 {
+                    // Checks what is being typed, not just what was applied
+                    let schedule = match &self.edit_buffer {
+                        Some(buffer) if editing => buffer.clone(),
+                        _ => self.draft.schedule.clone(),
+                    };
+                    spans.push(match cronexpr::validate(&schedule) {
+                        Ok(()) => Span::styled(
+                            format!("  {}", cronexpr::describe(&schedule).unwrap_or_default()),
+                            Theme::success(),
+                        ),
+                        Err(e) => Span::styled(format!("  {}", e), Theme::error()),
+                    });
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = ListState::default();
+        state.select(Some(self.form_field));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_run(&self, run: &JobRun, frame: &mut Frame, area: Rect) {
+        let (state, color) = match run.exit {
+            None => ("running".to_string(), Theme::colors().warning),
+            Some(Some(0)) => ("exit 0".to_string(), Theme::colors().success),
+            Some(Some(code)) => (format!("exit {}", code), Theme::colors().error),
+            Some(None) => ("killed".to_string(), Theme::colors().error),
+        };
+        let height = area.height.saturating_sub(2) as usize;
+        let lines: Vec<Line> = run
+            .lines
+            .iter()
+            .skip(run.lines.len().saturating_sub(height))
+            .map(|l| Line::from(l.as_str()))
+            .collect();
+        let title = Line::from(vec![
+            Span::raw(format!(" Output: {} ", run.title)),
+            Span::styled(format!("[{}] ", state), Style::default().fg(color)),
+        ]);
+        let pane = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(pane, area);
+    }
+
     fn source_display(&self, source: &CronSource) -> (String, Color) {
         match source {
             CronSource::System(path) => {
                 if path.contains("hourly") {
-                    ("hourly".to_string(), Color::Cyan)
+                    ("hourly".to_string(), Theme::colors().primary)
                 } else if path.contains("daily") {
-                    ("daily".to_string(), Color::Green)
+                    ("daily".to_string(), Theme::colors().success)
                 } else if path.contains("weekly") {
-                    ("weekly".to_string(), Color::Yellow)
+                    ("weekly".to_string(), Theme::colors().warning)
                 } else if path.contains("monthly") {
-                    ("monthly".to_string(), Color::Magenta)
+                    ("monthly".to_string(), Theme::colors().secondary)
                 } else {
-                    ("system".to_string(), Color::Blue)
+                    ("system".to_string(), Theme::colors().primary)
                 }
             }
-            CronSource::User(name) => (name.clone(), Color::White),
+            CronSource::User(name) => (name.clone(), Theme::colors().foreground),
         }
     }
 }
@@ -354,8 +1291,9 @@ impl Component for CronComponent {
             match key.code {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
                     self.show_confirm = false;
-                    self.pending_action = None;
-                    self.status_message = Some(("Action not implemented for safety".to_string(), true));
+                    if let Some(action) = self.pending_action.take() {
+                        self.execute_action(action);
+                    }
                 }
                 KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                     self.show_confirm = false;
@@ -366,6 +1304,80 @@ impl Component for CronComponent {
             return None;
         }
 
+        if let Some(buffer) = &mut self.edit_buffer {
+            match key.code {
+                KeyCode::Enter => {
+                    let value = buffer.trim().to_string();
+                    self.draft.set(self.form_field, value);
+                    self.edit_buffer = None;
+                }
+                KeyCode::Esc => self.edit_buffer = None,
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some((prompt, buffer)) = &mut self.at_prompt {
+            let prompt = *prompt;
+            match key.code {
+                KeyCode::Enter => {
+                    let input = buffer.trim().to_string();
+                    self.at_prompt = None;
+                    self.submit_at_prompt(prompt, input);
+                }
+                KeyCode::Esc => self.at_prompt = None,
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.mode == CronMode::At {
+            self.handle_at_input(key);
+            return None;
+        }
+        if self.mode == CronMode::Users {
+            self.handle_users_input(key);
+            return None;
+        }
+        if self.mode == CronMode::Env {
+            self.handle_env_input(key);
+            return None;
+        }
+
+        if self.mode == CronMode::Add || self.mode == CronMode::Edit {
+            let first = JobDraft::first_field(self.mode);
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.form_field = self.form_field.saturating_sub(1).max(first);
+                }
+                KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => {
+                    self.form_field = (self.form_field + 1).min(FORM_FIELDS.len() - 1);
+                }
+                KeyCode::Left => self.cycle_field(false),
+                KeyCode::Right => self.cycle_field(true),
+                KeyCode::Enter if self.form_field == 0 => self.cycle_field(true),
+                KeyCode::Enter => {
+                    self.edit_buffer = Some(self.draft.value(self.form_field));
+                }
+                KeyCode::Char('s') if self.mode == CronMode::Edit => self.save_edit(),
+                KeyCode::Char('s') => self.save_draft(),
+                KeyCode::Esc => {
+                    self.mode = CronMode::View;
+                    self.status_message = Some((tr("Cancelled").to_string(), false));
+                }
+                _ => {}
+            }
+            return None;
+        }
+
         let filtered_len = self.filtered_jobs().len();
 
         match key.code {
@@ -397,9 +1409,26 @@ impl Component for CronComponent {
                 };
                 self.list_state.select(Some(0));
             }
+            KeyCode::Char('a') => self.start_add(),
+            KeyCode::Char('e') | KeyCode::Enter => self.start_edit(),
+            KeyCode::Char('d') | KeyCode::Delete => self.confirm(CronAction::Delete),
+            KeyCode::Char(' ') | KeyCode::Char('t') => self.confirm(CronAction::Toggle),
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('r') => self.confirm(CronAction::Run),
+            KeyCode::Char('U') => self.open_user_picker(),
+            KeyCode::Char('v') => self.open_env_editor(),
+            KeyCode::Char('A') => {
+                self.mode = CronMode::At;
+                self.status_message = None;
+                self.load_at_jobs();
+            }
+            KeyCode::Char('c') | KeyCode::Esc if self.run.is_some() => {
+                // Dropping the receiver kills a job still running
+                self.run = None;
+            }
             KeyCode::F(5) => {
                 self.load_cron_jobs();
-                self.status_message = Some(("Cron jobs refreshed".to_string(), false));
+                self.status_message = Some((tr("Cron jobs refreshed").to_string(), false));
             }
             _ => {}
         }
@@ -428,14 +1457,21 @@ impl Component for CronComponent {
         };
 
         let filtered_jobs = self.filtered_jobs();
-        let filter_bar = Paragraph::new(Line::from(vec![
-            Span::styled("Filter: ", Style::default().fg(Color::Cyan)),
+        let mut filter_spans = vec![
+            Span::styled("Filter: ", Theme::label()),
             Span::raw(filter_text),
-            Span::styled(
-                format!("  ({} jobs)", filtered_jobs.len()),
-                Style::default().fg(Color::DarkGray),
-            ),
-        ]))
+        ];
+        if let Some(user) = &self.user_filter {
+            filter_spans.push(Span::styled(
+                format!("  Crontab of {}", user),
+                Theme::warning(),
+            ));
+        }
+        filter_spans.push(Span::styled(
+            format!("  ({} jobs)", filtered_jobs.len()),
+            Theme::muted(),
+        ));
+        let filter_bar = Paragraph::new(Line::from(filter_spans))
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -448,6 +1484,7 @@ impl Component for CronComponent {
             .iter()
             .map(|(_, job)| {
                 let (source_name, source_color) = self.source_display(&job.source);
+                let schedule = self.schedule_text(job);
 
                 ListItem::new(vec![
                     Line::from(vec![
@@ -458,25 +1495,39 @@ impl Component for CronComponent {
                         Span::styled(
                             if job.enabled { "●" } else { "○" },
                             Style::default().fg(if job.enabled {
-                                Color::Green
+                                Theme::colors().success
                             } else {
-                                Color::Red
+                                Theme::colors().error
                             }),
                         ),
                         Span::raw(" "),
                         Span::styled(
-                            format!("{:<20}", self.format_schedule(job)),
-                            Style::default().fg(Color::Yellow),
+                            format!("{:<16}", schedule),
+                            Theme::warning(),
+                        ),
+                        Span::styled(
+                            format!("{:<36}", cronexpr::describe(&schedule).unwrap_or_default()),
+                            Theme::muted(),
+                        ),
+                        Span::styled(
+                            self.format_next_run(job),
+                            Theme::label(),
                         ),
                     ]),
-                    Line::from(vec![
-                        Span::styled("    ", Style::default()),
-                        Span::raw(if job.command.len() > 60 {
-                            format!("{}...", &job.command[..57])
-                        } else {
-                            job.command.clone()
-                        }),
-                    ]),
+                    Line::from(
+                        [
+                            vec![
+                                Span::styled("    ", Style::default()),
+                                Span::raw(if job.command.len() > 60 {
+                                    format!("{}...", &job.command[..57])
+                                } else {
+                                    job.command.clone()
+                                }),
+                            ],
+                            self.format_history(job),
+                        ]
+                        .concat(),
+                    ),
                 ])
             })
             .collect();
@@ -486,19 +1537,56 @@ impl Component for CronComponent {
             .highlight_style(Theme::list_selected())
             .highlight_symbol("▶ ");
 
-        let mut state = self.list_state.clone();
-        frame.render_stateful_widget(list, chunks[1], &mut state);
+        if self.mode == CronMode::At {
+            self.render_at(frame, chunks[1]);
+        } else if self.mode == CronMode::Users {
+            self.render_users(frame, chunks[1]);
+        } else if self.mode == CronMode::Env {
+            self.render_env(frame, chunks[1]);
+        } else if self.mode == CronMode::Add || self.mode == CronMode::Edit {
+            self.render_form(frame, chunks[1]);
+        } else if let Some(run) = &self.run {
+            let panes = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(6), Constraint::Length(12)])
+                .split(chunks[1]);
+            let mut state = self.list_state.clone();
+            frame.render_stateful_widget(list, panes[0], &mut state);
+            self.render_run(run, frame, panes[1]);
+        } else {
+            let mut state = self.list_state.clone();
+            frame.render_stateful_widget(list, chunks[1], &mut state);
+        }
 
         // Status bar
         let status_content = if self.show_confirm {
             Line::from(vec![
-                Span::styled("Confirm action? ", Style::default().fg(Color::Yellow)),
-                Span::raw("[Y]es / [N]o"),
+                Span::styled(self.confirm_text(), Theme::warning()),
+                Span::raw(tr("[Y]es / [N]o")),
+            ])
+        } else if let Some((prompt, buffer)) = &self.at_prompt {
+            let label = match prompt {
+                AtPrompt::Command => "Command: ",
+                AtPrompt::Time => "Run at (e.g. now + 1 hour, 03:00 tomorrow): ",
+            };
+            Line::from(vec![
+                Span::styled(label, Theme::label()),
+                Span::styled(format!("{}_", buffer), Theme::input_active()),
             ])
         } else if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
+            ))
+        } else if self.mode == CronMode::Add {
+            Line::from(Span::styled(
+                "Enter edits a field, 's' adds the job, Esc cancels",
+                Theme::muted(),
+            ))
+        } else if self.mode == CronMode::Edit {
+            Line::from(Span::styled(
+                "Enter edits a field, 's' saves (the previous crontab is backed up), Esc cancels",
+                Theme::muted(),
             ))
         } else if let Some((_, job)) = self.selected_job() {
             let source_path = match &job.source {
@@ -506,7 +1594,7 @@ impl Component for CronComponent {
                 CronSource::User(u) => format!("/var/spool/cron/crontabs/{}", u),
             };
             Line::from(vec![
-                Span::styled("Source: ", Style::default().fg(Color::Cyan)),
+                Span::styled("Source: ", Theme::label()),
                 Span::raw(source_path),
             ])
         } else {
@@ -518,17 +1606,152 @@ impl Component for CronComponent {
         frame.render_widget(status, chunks[2]);
     }
 
+    fn is_typing(&self) -> bool {
+        self.edit_buffer.is_some() || self.at_prompt.is_some() || self.env_buffer.is_some()
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
-        vec![
+        if self.edit_buffer.is_some() || self.at_prompt.is_some() || self.env_buffer.is_some() {
+            return vec![("Enter", "Apply"), ("Esc", "Cancel")];
+        }
+        if self.mode == CronMode::Env {
+            return vec![
+                ("↑/↓", "Navigate"),
+                ("Enter", "Edit"),
+                ("x", "Unset"),
+                ("Esc", "Back"),
+            ];
+        }
+        if self.mode == CronMode::Users {
+            return vec![("↑/↓", "Navigate"), ("Enter", "Select"), ("Esc", "Back")];
+        }
+        if self.mode == CronMode::At {
+            return vec![
+                ("↑/↓", "Navigate"),
+                ("a", "New Job"),
+                ("d", "Remove"),
+                ("F5", "Refresh"),
+                ("Esc", "Back"),
+            ];
+        }
+        if self.mode == CronMode::Add {
+            return vec![
+                ("↑/↓", "Field"),
+                ("Enter", "Edit"),
+                ("←/→", "Change"),
+                ("s", "Add Job"),
+                ("Esc", "Cancel"),
+            ];
+        }
+        if self.mode == CronMode::Edit {
+            return vec![
+                ("↑/↓", "Field"),
+                ("Enter", "Edit"),
+                ("←/→", "Presets"),
+                ("s", "Save"),
+                ("Esc", "Cancel"),
+            ];
+        }
+        let mut help = vec![
             ("Tab", "Filter"),
             ("↑/↓", "Navigate"),
+            ("a", "Add"),
+            ("e", "Edit"),
+            ("d", "Delete"),
+            ("Space", "Enable/Disable"),
+            ("u", "Undo"),
+            ("r", "Run Now"),
+            ("U", "User"),
+            ("v", "Variables"),
+            ("A", "At Jobs"),
             ("F5", "Refresh"),
-        ]
+        ];
+        if self.run.is_some() {
+            help.push(("c", "Close Output"));
+        }
+        help
+    }
+
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        if self.is_typing() {
+            return Vec::new();
+        }
+        match self.mode {
+            CronMode::Env => vec!["Enter", "x"],
+            CronMode::Users => Vec::new(),
+            CronMode::At => vec!["a", "d"],
+            CronMode::Add | CronMode::Edit => vec!["s"],
+            CronMode::View => vec!["d", "Space", "u", "r"],
+        }
     }
 
     fn on_activate(&mut self) {
         self.load_cron_jobs();
     }
+
+    fn search_items(&self) -> Vec<SearchItem> {
+        self.jobs
+            .iter()
+            .map(|job| {
+                SearchItem::new(
+                    Self::search_id(job),
+                    format!("{} ({})", job.command, job.source.path()),
+                )
+            })
+            .collect()
+    }
+
+    fn select_item(&mut self, id: &str) {
+        // A job being added or edited is not thrown away
+        if matches!(self.mode, CronMode::Add | CronMode::Edit) {
+            return;
+        }
+        self.mode = CronMode::View;
+        self.filter = CronFilter::All;
+        self.user_filter = None;
+        let position = self
+            .filtered_jobs()
+            .iter()
+            .position(|(_, job)| Self::search_id(job) == id);
+        if let Some(i) = position {
+            self.list_state.select(Some(i));
+        }
+    }
+
+    fn is_busy(&self) -> bool {
+        self.run.as_ref().is_some_and(|run| run.rx.is_some())
+    }
+
+    fn on_tick(&mut self) {
+        let now = Local::now().naive_local();
+        if self.jobs.iter().any(|j| j.next_run.is_some_and(|t| t <= now)) {
+            self.refresh_next_runs();
+        }
+
+        let Some(run) = &mut self.run else {
+            return;
+        };
+        let Some(rx) = &mut run.rx else {
+            return;
+        };
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                RunEvent::Line(line) => run.lines.push(line),
+                RunEvent::Exited(code) => run.exit = Some(code),
+            }
+        }
+        if run.lines.len() > MAX_RUN_LINES {
+            run.lines.drain(..run.lines.len() - MAX_RUN_LINES);
+        }
+        if let Some(code) = run.exit {
+            run.rx = None;
+            self.status_message = Some(match code {
+                Some(0) => (tr("Job finished with exit status 0").to_string(), false),
+                Some(code) => (trf("Job failed with exit status {}", &[&code.to_string()]), true),
+                None => (tr("Job was killed by a signal").to_string(), true),
+            });
+        }
+    }
 }
 
 use std::os::unix::fs::PermissionsExt;
@@ -0,0 +1,332 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokio::sync::mpsc;
+
+use crate::app::Message;
+use crate::components::settings;
+use crate::components::Component;
+use crate::slackware::commands;
+use crate::slackware::cron::RunEvent;
+use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::keymap::Key;
+
+/// Directory under each config directory holding one TOML file per tab
+const TABS_DIR: &str = "tabs";
+
+/// How much asking a command needs before it runs
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmLevel {
+    /// Runs straight away
+    None,
+    /// Asks [Y]/[N] unless confirmations are off in Settings
+    #[default]
+    Ask,
+    /// Always asks, like the destructive actions of the built-in tabs
+    Always,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomCommand {
+    pub label: String,
+    /// Run with /bin/sh -c
+    pub command: String,
+    #[serde(default)]
+    pub confirm: ConfirmLevel,
+}
+
+/// A tab defined in tabs/<id>.toml, e.g.
+///
+/// ```toml
+/// name = "Web"
+/// key = "ctrl+w"
+/// [[commands]]
+/// label = "Restart Apache"
+/// command = "/etc/rc.d/rc.httpd restart"
+/// confirm = "always"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct TabDefinition {
+    pub name: String,
+    /// Key switching to the tab, written like in the [keys] table
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub commands: Vec<CustomCommand>,
+    /// Name in the default_tab setting, from the file name
+    #[serde(skip)]
+    pub id: String,
+    /// Name of the action switching to the tab in the [keys] table
+    #[serde(skip)]
+    pub action: String,
+}
+
+static DEFINITIONS: OnceLock<(Vec<TabDefinition>, Vec<String>)> = OnceLock::new();
+
+/// The custom tabs, read once at startup
+pub fn definitions() -> &'static [TabDefinition] {
+    &DEFINITIONS.get_or_init(load).0
+}
+
+/// Tab files that could not be read, as "path: error"
+pub fn load_errors() -> &'static [String] {
+    &DEFINITIONS.get_or_init(load).1
+}
+
+/// Read the tab files of the system and user config directories. A user
+/// file replaces the system file of the same name.
+fn load() -> (Vec<TabDefinition>, Vec<String>) {
+    let mut files: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for dir in settings::config_dirs() {
+        let Ok(entries) = fs::read_dir(dir.join(TABS_DIR)) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().is_some_and(|ext| ext == "toml") {
+                if let Some(stem) = path.file_stem() {
+                    files.insert(stem.to_string_lossy().to_string(), path);
+                }
+            }
+        }
+    }
+
+    let mut definitions = Vec::new();
+    let mut errors = Vec::new();
+    for (stem, path) in files {
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                toml::from_str::<TabDefinition>(&content).map_err(|e| e.message().to_string())
+            });
+        match parsed {
+            Ok(mut definition) => {
+                if let Some(Err(e)) = definition.key.as_deref().map(Key::parse_list) {
+                    errors.push(format!("{}: {}", path.display(), e));
+                    definition.key = None;
+                }
+                definition.id = format!("custom-{}", stem);
+                definition.action = format!("tab-custom-{}", stem);
+                definitions.push(definition);
+            }
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    (definitions, errors)
+}
+
+/// A command started from the tab and its output so far
+struct CommandRun {
+    label: String,
+    lines: Vec<String>,
+    /// Exit status once it has finished; `None` inside when killed
+    exit: Option<Option<i32>>,
+    rx: Option<mpsc::UnboundedReceiver<RunEvent>>,
+}
+
+/// Generic runner for a custom tab: a list of labelled commands, run
+/// one at a time with their output shown below
+pub struct CustomTabComponent {
+    definition: &'static TabDefinition,
+    list_state: ListState,
+    show_confirm: bool,
+    run: Option<CommandRun>,
+}
+
+impl CustomTabComponent {
+    pub fn new(definition: &'static TabDefinition) -> Self {
+        let selected = (!definition.commands.is_empty()).then_some(0);
+        Self {
+            definition,
+            list_state: ListState::default().with_selected(selected),
+            show_confirm: false,
+            run: None,
+        }
+    }
+
+    fn selected_command(&self) -> Option<&'static CustomCommand> {
+        self.definition.commands.get(self.list_state.selected()?)
+    }
+
+    fn is_running(&self) -> bool {
+        self.run.as_ref().is_some_and(|run| run.exit.is_none())
+    }
+
+    fn run_selected(&mut self) {
+        if let Some(command) = self.selected_command() {
+            let args = vec!["-c".to_string(), command.command.clone()];
+            self.run = Some(CommandRun {
+                label: command.label.clone(),
+                lines: Vec::new(),
+                exit: None,
+                rx: Some(commands::stream("/bin/sh", &args)),
+            });
+        }
+    }
+}
+
+impl Component for CustomTabComponent {
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        if self.show_confirm {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.show_confirm = false;
+                    self.run_selected();
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.show_confirm = false;
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        let count = self.definition.commands.len();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(selected) = self.list_state.selected() {
+                    self.list_state.select(Some(selected.saturating_sub(1)));
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(selected) = self.list_state.selected() {
+                    if selected + 1 < count {
+                        self.list_state.select(Some(selected + 1));
+                    }
+                }
+            }
+            KeyCode::Enter if !self.is_running() => {
+                let confirm = match self.selected_command().map(|c| c.confirm) {
+                    Some(ConfirmLevel::None) | None => false,
+                    Some(ConfirmLevel::Ask) => settings::confirm_actions(),
+                    Some(ConfirmLevel::Always) => true,
+                };
+                if confirm {
+                    self.show_confirm = true;
+                } else {
+                    self.run_selected();
+                }
+            }
+            KeyCode::Esc if !self.is_running() => self.run = None,
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(5),
+                Constraint::Percentage(50),
+                Constraint::Length(3),
+            ])
+            .split(area);
+
+        let items: Vec<ListItem> = self
+            .definition
+            .commands
+            .iter()
+            .map(|c| ListItem::new(c.label.as_str()))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(format!(" {} ", self.definition.name))
+                    .borders(Borders::ALL)
+                    .border_style(Theme::border_focused()),
+            )
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let (title, lines) = match &self.run {
+            Some(run) => {
+                let state = match run.exit {
+                    None => tr("running").to_string(),
+                    Some(Some(0)) => tr("done").to_string(),
+                    Some(Some(code)) => trf("exit {}", &[&code.to_string()]),
+                    Some(None) => tr("killed").to_string(),
+                };
+                let height = chunks[1].height.saturating_sub(2) as usize;
+                let lines: Vec<Line> = run
+                    .lines
+                    .iter()
+                    .skip(run.lines.len().saturating_sub(height))
+                    .map(|l| Line::from(l.as_str()))
+                    .collect();
+                (format!(" {} ({}) ", run.label, state), lines)
+            }
+            None => (format!(" {} ", tr("Output")), Vec::new()),
+        };
+        let output =
+            Paragraph::new(lines).block(Block::default().title(title).borders(Borders::ALL));
+        frame.render_widget(output, chunks[1]);
+
+        let status_content = if self.show_confirm {
+            Line::from(vec![
+                Span::styled(
+                    trf(
+                        "Run {}?",
+                        &[self.selected_command().map_or("", |c| c.command.as_str())],
+                    ),
+                    Theme::warning(),
+                ),
+                Span::raw(format!(" {}", tr("[Y]es / [N]o"))),
+            ])
+        } else if self.definition.commands.is_empty() {
+            Line::from(Span::styled(
+                tr("No [[commands]] in this tab's file"),
+                Theme::muted(),
+            ))
+        } else if let Some(command) = self.selected_command() {
+            Line::from(vec![
+                Span::styled(format!("{} ", tr("Command:")), Theme::label()),
+                Span::raw(command.command.as_str()),
+            ])
+        } else {
+            Line::from("")
+        };
+        let status = Paragraph::new(status_content).block(Block::default().borders(Borders::ALL));
+        frame.render_widget(status, chunks[2]);
+    }
+
+    fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("↑↓", "Select"), ("Enter", "Run"), ("Esc", "Clear Output")]
+    }
+
+    fn is_busy(&self) -> bool {
+        self.run.as_ref().is_some_and(|run| run.rx.is_some())
+    }
+
+    fn on_tick(&mut self) {
+        let Some(run) = &mut self.run else {
+            return;
+        };
+        let Some(rx) = &mut run.rx else {
+            return;
+        };
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                RunEvent::Line(line) => run.lines.push(line),
+                RunEvent::Exited(code) => run.exit = Some(code),
+            }
+        }
+        if run.exit.is_some() {
+            run.rx = None;
+        }
+    }
+}
@@ -3,15 +3,32 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph},
     Frame,
 };
 use std::fs;
 use std::process::Command;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 
 use crate::app::Message;
+use crate::components::settings;
 use crate::components::Component;
+use crate::slackware::audit;
+use crate::slackware::blkid;
+use crate::slackware::cron::RunEvent;
+use crate::slackware::diskstats::{self, Counters};
+use crate::slackware::fsck;
+use crate::slackware::lvm::{self, LvmOp};
+use crate::slackware::mdraid;
+use crate::slackware::mkfs::{self, Filesystem};
+use crate::slackware::partitions::{self, PartitionOp, PartitionTable, Slot};
+use crate::slackware::removable;
+use crate::slackware::smart::{self, SmartReport};
+use crate::ui::clipboard;
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::root;
 
 /// Disk/partition information
 #[derive(Debug, Clone)]
@@ -25,6 +42,56 @@ pub struct DiskInfo {
     pub use_percent: u8,
     pub is_mounted: bool,
     pub device_path: String,
+    /// Options it is mounted with, from /proc/mounts
+    pub options: String,
+    /// Filesystem label and UUID, from blkid
+    pub label: String,
+    pub uuid: String,
+    /// On removable media or a USB drive
+    pub removable: bool,
+}
+
+/// How often to look for devices being plugged in or pulled out
+const HOTPLUG_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time between /proc/diskstats samples in the I/O view
+const IO_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Options offered by the mount dialog, with what they do
+const MOUNT_OPTIONS: [(&str, &str); 4] = [
+    ("ro", "read-only"),
+    ("noexec", "don't allow running programs from it"),
+    ("nosuid", "ignore setuid and setgid bits"),
+    ("relatime", "only update access times when stale"),
+];
+
+/// Picking options before mounting a device
+struct MountDialog {
+    device: String,
+    mount_point: String,
+    chosen: [bool; MOUNT_OPTIONS.len()],
+    /// Row under the cursor; the last one is the free-form options
+    selected: usize,
+    custom: String,
+}
+
+impl MountDialog {
+    /// The -o argument, empty for the defaults
+    fn options(&self) -> String {
+        MOUNT_OPTIONS
+            .iter()
+            .zip(self.chosen)
+            .filter(|(_, on)| *on)
+            .map(|((name, _), _)| name.to_string())
+            .chain(
+                self.custom
+                    .split(',')
+                    .map(|o| o.trim().to_string())
+                    .filter(|o| !o.is_empty()),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
 }
 
 /// Disk Management Component
@@ -35,19 +102,286 @@ pub struct DiskComponent {
     status_message: Option<(String, bool)>,
     show_confirm: bool,
     pending_action: Option<DiskAction>,
+    /// Physical drives and what smartctl reported for each
+    smart_drives: Vec<(String, Result<SmartReport, String>)>,
+    smart_state: ListState,
+    format: Option<FormatWizard>,
+    /// Partition tables of the physical drives
+    tables: Vec<(String, Result<PartitionTable, String>)>,
+    table_index: usize,
+    slot_state: ListState,
+    part_edit: Option<PartitionEdit>,
+    lvm: lvm::Layout,
+    lvm_state: ListState,
+    lvm_prompt: Option<(LvmPrompt, String)>,
+    arrays: Vec<mdraid::Array>,
+    mount_dialog: Option<MountDialog>,
+    /// Unmounted device waiting for a report-only or repair choice
+    fsck_prompt: Option<(String, String)>,
+    fsck: Option<FsckRun>,
+    /// New label being typed for the selected filesystem
+    label_prompt: Option<String>,
+    /// Entries of /sys/class/block at the last refresh
+    block_devices: Vec<String>,
+    hotplug_checked: Instant,
+    /// Last /proc/diskstats sample and when it was taken
+    io_sample: Option<(Instant, Vec<(String, Counters)>)>,
+    io_rates: Vec<diskstats::Rate>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DiskMode {
     Overview,
     Details,
+    /// SMART health of the physical drives
+    Smart,
+    /// The format wizard
+    Format,
+    /// Partition tables, one disk at a time
+    Partitions,
+    /// Volume groups with their logical and physical volumes
+    Lvm,
+    /// Software RAID arrays from /proc/mdstat
+    Raid,
+    /// Output of a filesystem check
+    Fsck,
+    /// Throughput and utilization per device
+    Io,
 }
 
 #[derive(Debug, Clone)]
 pub enum DiskAction {
-    Mount(String),
+    /// Device, mount point and -o options
+    Mount(String, String, String),
     Unmount(String),
-    CheckFilesystem(String),
+    /// Device, filesystem type and whether to repair
+    CheckFilesystem(String, String, bool),
+    /// Device, filesystem type and the new label
+    SetLabel(String, String, String),
+    /// Check every filesystem on the next boot, or stop that
+    BootCheck(bool),
+    /// Start a self-test on a drive; `true` for the extended one
+    SelfTest(String, bool),
+    Lvm(LvmOp),
+    /// Unmount, sync and eject a whole removable disk
+    Eject(String),
+}
+
+impl DiskAction {
+    /// Repairs and LVM changes rewrite data on disk
+    fn always_confirm(&self) -> bool {
+        matches!(self, DiskAction::CheckFilesystem(_, _, true) | DiskAction::Lvm(_))
+    }
+}
+
+/// What the LVM view is asking for
+#[derive(Debug, Clone, PartialEq)]
+enum LvmPrompt {
+    /// Name of a new LV in the group
+    Name(String),
+    /// Size of the new LV
+    Size { vg: String, name: String },
+    /// How much to grow an LV by
+    Grow(String),
+}
+
+/// A line of the LVM view
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LvmRow {
+    Vg(usize),
+    Lv(usize),
+    Pv(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FormatStep {
+    Filesystem,
+    Label,
+    /// Type the device name to go ahead
+    Confirm,
+    Running,
+}
+
+/// State of a guarded mkfs run on an unmounted partition
+struct FormatWizard {
+    device: String,
+    /// The filesystem it holds now, if any
+    current: String,
+    step: FormatStep,
+    fs_index: usize,
+    label: String,
+    typed: String,
+    error: Option<String>,
+    run: Option<CommandRun>,
+}
+
+/// Output of mkfs or fsck running in the background
+struct CommandRun {
+    lines: Vec<String>,
+    /// Exit status once it has finished; `None` inside when killed
+    exit: Option<Option<i32>>,
+    rx: Option<mpsc::UnboundedReceiver<RunEvent>>,
+}
+
+impl CommandRun {
+    fn new(rx: mpsc::UnboundedReceiver<RunEvent>) -> Self {
+        Self {
+            lines: Vec::new(),
+            exit: None,
+            rx: Some(rx),
+        }
+    }
+
+    /// Collect new output, returning the exit status when it just ended
+    fn drain(&mut self) -> Option<Option<i32>> {
+        let rx = self.rx.as_mut()?;
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                RunEvent::Line(line) => self.lines.push(line),
+                RunEvent::Exited(code) => self.exit = Some(code),
+            }
+        }
+        if self.exit.is_some() {
+            self.rx = None;
+        }
+        self.exit
+    }
+
+    fn finished(&self) -> bool {
+        self.exit.is_some()
+    }
+
+    /// A state line and the last lines of output that fit `height`
+    fn render_lines(&self, height: usize) -> Vec<Line<'_>> {
+        let (state, color) = match self.exit {
+            None => (tr("running").to_string(), Theme::colors().warning),
+            Some(Some(0)) => (
+                tr("done, Enter to close").to_string(),
+                Theme::colors().success,
+            ),
+            Some(Some(code)) => (
+                trf("exit {}, Enter to close", &[&code.to_string()]),
+                Theme::colors().error,
+            ),
+            Some(None) => (
+                tr("killed, Enter to close").to_string(),
+                Theme::colors().error,
+            ),
+        };
+        let mut lines = vec![Line::from(Span::styled(state, Style::default().fg(color)))];
+        lines.extend(
+            self.lines
+                .iter()
+                .skip(self.lines.len().saturating_sub(height.saturating_sub(1)))
+                .map(|l| Line::from(l.as_str())),
+        );
+        lines
+    }
+}
+
+/// A filesystem check, run or about to be
+struct FsckRun {
+    device: String,
+    fstype: String,
+    repair: bool,
+    run: CommandRun,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EditStep {
+    Size,
+    Kind,
+    /// Show the sfdisk command before anything happens
+    Review,
+    /// Type the disk name to run it
+    TypeDisk,
+}
+
+#[derive(Debug, Clone)]
+enum EditGoal {
+    Create { start: u64, size: u64 },
+    Delete(partitions::Partition),
+    SetType(partitions::Partition),
+}
+
+/// A partition table change being put together and confirmed
+struct PartitionEdit {
+    disk: String,
+    goal: EditGoal,
+    step: EditStep,
+    size: String,
+    kind: usize,
+    typed: String,
+    op: Option<PartitionOp>,
+    error: Option<String>,
+}
+
+impl PartitionEdit {
+    fn new(disk: &str, goal: EditGoal) -> Self {
+        let step = match goal {
+            EditGoal::Create { .. } => EditStep::Size,
+            EditGoal::SetType(_) => EditStep::Kind,
+            EditGoal::Delete(_) => EditStep::Review,
+        };
+        let mut edit = Self {
+            disk: disk.to_string(),
+            goal,
+            step,
+            size: String::new(),
+            kind: 0,
+            typed: String::new(),
+            op: None,
+            error: None,
+        };
+        if step == EditStep::Review {
+            edit.op = edit.build_op(512).ok();
+        }
+        edit
+    }
+
+    fn build_op(&self, sector_size: u64) -> std::result::Result<PartitionOp, String> {
+        let kind = partitions::KINDS[self.kind].0;
+        let number = |p: &partitions::Partition| {
+            p.number()
+                .ok_or_else(|| trf("Can't tell the number of {}", &[&p.node]))
+        };
+        Ok(match &self.goal {
+            EditGoal::Create { start, size } => {
+                let sectors = if self.size.trim().is_empty() {
+                    *size
+                } else {
+                    partitions::parse_size(&self.size, sector_size)
+                        .ok_or_else(|| trf("Not a size: {} (try 512M or 20G)", &[&self.size]))?
+                };
+                if sectors > *size {
+                    return Err(tr("That is more than the free space").to_string());
+                }
+                PartitionOp::Create {
+                    disk: self.disk.clone(),
+                    start: *start,
+                    size: sectors,
+                    kind,
+                }
+            }
+            EditGoal::Delete(p) => PartitionOp::Delete {
+                disk: self.disk.clone(),
+                partition: p.node.clone(),
+                number: number(p)?,
+            },
+            EditGoal::SetType(p) => PartitionOp::SetType {
+                disk: self.disk.clone(),
+                partition: p.node.clone(),
+                number: number(p)?,
+                kind,
+            },
+        })
+    }
+}
+
+impl FormatWizard {
+    fn filesystem(&self) -> Filesystem {
+        Filesystem::ALL[self.fs_index]
+    }
 }
 
 impl DiskComponent {
@@ -59,6 +393,25 @@ impl DiskComponent {
             status_message: None,
             show_confirm: false,
             pending_action: None,
+            smart_drives: Vec::new(),
+            smart_state: ListState::default(),
+            format: None,
+            tables: Vec::new(),
+            table_index: 0,
+            slot_state: ListState::default(),
+            part_edit: None,
+            lvm: lvm::Layout::default(),
+            lvm_state: ListState::default(),
+            lvm_prompt: None,
+            arrays: Vec::new(),
+            mount_dialog: None,
+            fsck_prompt: None,
+            fsck: None,
+            label_prompt: None,
+            block_devices: Vec::new(),
+            hotplug_checked: Instant::now(),
+            io_sample: None,
+            io_rates: Vec::new(),
         };
         component.load_disk_info();
         if !component.disks.is_empty() {
@@ -69,6 +422,19 @@ impl DiskComponent {
 
     fn load_disk_info(&mut self) {
         self.disks.clear();
+        self.block_devices = removable::block_devices();
+        self.lvm = lvm::load();
+        let mounts = fs::read_to_string("/proc/mounts").unwrap_or_default();
+        // The last mount on a target is the one in effect
+        let options_of = |target: &str| {
+            mounts
+                .lines()
+                .rev()
+                .map(|l| l.split_whitespace().collect::<Vec<_>>())
+                .find(|f| f.len() >= 4 && f[1] == target)
+                .map(|f| f[3].to_string())
+                .unwrap_or_default()
+        };
 
         // Use df to get mounted filesystems
         if let Ok(output) = Command::new("df")
@@ -86,12 +452,14 @@ impl DiskComponent {
                         continue;
                     }
 
-                    let name = source
-                        .trim_start_matches("/dev/")
-                        .to_string();
+                    // LVs show up as /dev/mapper nodes; call them vg/lv
+                    let name = self.lvm.lv_label(source).unwrap_or_else(|| {
+                        source.trim_start_matches("/dev/").to_string()
+                    });
 
                     let mount_point = parts[1].to_string();
                     let filesystem = parts[2].to_string();
+                    let options = options_of(&mount_point);
 
                     let size: u64 = parts[3].parse().unwrap_or(0);
                     let used: u64 = parts[4].parse().unwrap_or(0);
@@ -111,6 +479,10 @@ impl DiskComponent {
                         use_percent,
                         is_mounted: true,
                         device_path: source.to_string(),
+                        options,
+                        label: String::new(),
+                        uuid: String::new(),
+                        removable: false,
                     });
                 }
             }
@@ -118,24 +490,43 @@ impl DiskComponent {
 
         // Also scan for unmounted block devices
         self.scan_block_devices();
+
+        let ids = blkid::load();
+        for disk in &mut self.disks {
+            disk.removable = removable::is_removable(&disk.name);
+            if let Some(id) = ids.get(&disk.device_path) {
+                disk.label = id.label.clone();
+                disk.uuid = id.uuid.clone();
+                if disk.filesystem.is_empty() {
+                    disk.filesystem = id.fstype.clone();
+                }
+            }
+        }
     }
 
     fn scan_block_devices(&mut self) {
         // Use lsblk for additional info
         if let Ok(output) = Command::new("lsblk")
-            .args(["-b", "-n", "-o", "NAME,SIZE,TYPE,FSTYPE,MOUNTPOINT"])
+            .args(["-b", "-n", "-l", "-o", "NAME,SIZE,TYPE,FSTYPE,MOUNTPOINT"])
             .output()
         {
             let stdout = String::from_utf8_lossy(&output.stdout);
             for line in stdout.lines() {
                 let parts: Vec<&str> = line.split_whitespace().collect();
                 if parts.len() >= 3 {
-                    let name = parts[0].trim_start_matches("├─").trim_start_matches("└─");
+                    let mut name = parts[0].to_string();
                     let size: u64 = parts[1].parse().unwrap_or(0);
                     let device_type = parts[2];
+                    let mut device_path = format!("/dev/{}", name);
 
-                    // Only interested in partitions and disks
-                    if device_type != "part" && device_type != "disk" {
+                    // Only interested in partitions, disks and LVs
+                    if device_type == "lvm" {
+                        let Some(label) = self.lvm.lv_label(&name) else {
+                            continue;
+                        };
+                        device_path = format!("/dev/mapper/{}", name);
+                        name = label;
+                    } else if device_type != "part" && device_type != "disk" {
                         continue;
                     }
 
@@ -150,7 +541,7 @@ impl DiskComponent {
                     let exists = self.disks.iter().any(|d| d.name == name);
                     if !exists && !name.starts_with("loop") {
                         self.disks.push(DiskInfo {
-                            name: name.to_string(),
+                            name,
                             mount_point,
                             filesystem: filesystem.to_string(),
                             size,
@@ -158,7 +549,11 @@ impl DiskComponent {
                             available: size,
                             use_percent: 0,
                             is_mounted: false,
-                            device_path: format!("/dev/{}", name),
+                            device_path,
+                            options: String::new(),
+                            label: String::new(),
+                            uuid: String::new(),
+                            removable: false,
                         });
                     }
                 }
@@ -173,57 +568,706 @@ impl DiskComponent {
         self.list_state.selected().and_then(|i| self.disks.get(i))
     }
 
-    fn mount_disk(&mut self, device: &str) -> Option<Message> {
-        // Find mount point from fstab or create one
-        let mount_point = self.find_mount_point(device);
-
-        match Command::new("mount").arg(device).arg(&mount_point).output() {
+    fn mount_disk(&mut self, device: &str, mount_point: &str, options: &str) -> Option<Message> {
+        let mut mount = root::command("mount");
+        if !options.is_empty() {
+            mount.args(["-o", options]);
+        }
+        match audit::output(mount.arg(device).arg(mount_point)) {
             Ok(output) => {
                 if output.status.success() {
-                    self.status_message = Some((
-                        format!("Mounted {} at {}", device, mount_point),
-                        false,
-                    ));
+                    self.status_message =
+                        Some((trf("Mounted {} at {}", &[device, mount_point]), false));
                     self.load_disk_info();
                 } else {
                     let stderr = String::from_utf8_lossy(&output.stderr);
-                    self.status_message = Some((format!("Mount failed: {}", stderr), true));
+                    self.status_message = Some((trf("Mount failed: {}", &[&stderr]), true));
                 }
             }
             Err(e) => {
-                self.status_message = Some((format!("Error: {}", e), true));
+                self.status_message = Some((trf("Error: {}", &[&e.to_string()]), true));
             }
         }
         None
     }
 
+    fn handle_mount_dialog(&mut self, key: KeyEvent) {
+        let Some(dialog) = &mut self.mount_dialog else {
+            return;
+        };
+        let custom_row = MOUNT_OPTIONS.len();
+        match key.code {
+            KeyCode::Up => dialog.selected = dialog.selected.saturating_sub(1),
+            KeyCode::Down => dialog.selected = (dialog.selected + 1).min(custom_row),
+            KeyCode::Char(' ') if dialog.selected < custom_row => {
+                dialog.chosen[dialog.selected] = !dialog.chosen[dialog.selected];
+            }
+            KeyCode::Char(c) if dialog.selected == custom_row && c != ' ' => {
+                dialog.custom.push(c);
+            }
+            KeyCode::Backspace if dialog.selected == custom_row => {
+                dialog.custom.pop();
+            }
+            KeyCode::Enter => {
+                let options = dialog.options();
+                let has = |name: &str| options.split(',').any(|o| o == name);
+                if has("ro") && has("rw") {
+                    self.status_message = Some((tr("Pick either ro or rw").to_string(), true));
+                    return;
+                }
+                let action =
+                    DiskAction::Mount(dialog.device.clone(), dialog.mount_point.clone(), options);
+                self.mount_dialog = None;
+                self.status_message = None;
+                self.request(action);
+            }
+            KeyCode::Esc => self.mount_dialog = None,
+            _ => {}
+        }
+    }
+
     fn unmount_disk(&mut self, mount_point: &str) -> Option<Message> {
-        match Command::new("umount").arg(mount_point).output() {
+        match audit::output(root::command("umount").arg(mount_point)) {
             Ok(output) => {
                 if output.status.success() {
-                    self.status_message = Some((format!("Unmounted {}", mount_point), false));
+                    self.status_message = Some((trf("Unmounted {}", &[mount_point]), false));
                     self.load_disk_info();
                 } else {
                     let stderr = String::from_utf8_lossy(&output.stderr);
-                    self.status_message = Some((format!("Unmount failed: {}", stderr), true));
+                    self.status_message = Some((trf("Unmount failed: {}", &[&stderr]), true));
                 }
             }
             Err(e) => {
-                self.status_message = Some((format!("Error: {}", e), true));
+                self.status_message = Some((trf("Error: {}", &[&e.to_string()]), true));
+            }
+        }
+        None
+    }
+
+    /// Ask before running `action`, unless confirmations are off and it is low-risk
+    fn request(&mut self, action: DiskAction) -> Option<Message> {
+        if settings::confirm_actions() || action.always_confirm() {
+            self.pending_action = Some(action);
+            self.show_confirm = true;
+            None
+        } else {
+            self.execute_action(action)
+        }
+    }
+
+    fn execute_action(&mut self, action: DiskAction) -> Option<Message> {
+        match action {
+            DiskAction::Mount(dev, mp, options) => self.mount_disk(&dev, &mp, &options),
+            DiskAction::Unmount(mp) => self.unmount_disk(&mp),
+            DiskAction::CheckFilesystem(dev, fstype, repair) => {
+                self.check_filesystem(&dev, &fstype, repair)
+            }
+            DiskAction::BootCheck(enabled) => self.set_boot_check(enabled),
+            DiskAction::SetLabel(dev, fstype, label) => self.set_label(&dev, &fstype, &label),
+            DiskAction::SelfTest(drive, long) => self.start_self_test(&drive, long),
+            DiskAction::Lvm(op) => self.apply_lvm_op(op),
+            DiskAction::Eject(disk) => self.eject_disk(&disk),
+        }
+    }
+
+    fn start_eject(&mut self) {
+        let Some(disk) = self.selected_disk() else {
+            return;
+        };
+        match removable::parent_disk(&disk.name) {
+            Some(parent) if disk.removable => {
+                self.request(DiskAction::Eject(parent));
+            }
+            _ => {
+                self.status_message = Some((trf("{} is not removable", &[&disk.name]), true));
+            }
+        }
+    }
+
+    fn eject_disk(&mut self, disk: &str) -> Option<Message> {
+        self.status_message = Some(match removable::eject(disk) {
+            Ok(()) => (trf("{} can be removed now", &[disk]), false),
+            Err(e) => (trf("Eject failed: {}", &[&e.to_string()]), true),
+        });
+        self.load_disk_info();
+        self.clamp_selection();
+        None
+    }
+
+    fn clamp_selection(&mut self) {
+        let selected = match self.list_state.selected() {
+            _ if self.disks.is_empty() => None,
+            Some(i) => Some(i.min(self.disks.len() - 1)),
+            None => Some(0),
+        };
+        self.list_state.select(selected);
+    }
+
+    /// Take a new diskstats sample once the interval is up
+    fn sample_io(&mut self) {
+        let Some((taken, before)) = &self.io_sample else {
+            return;
+        };
+        let elapsed = taken.elapsed();
+        if elapsed < IO_INTERVAL {
+            return;
+        }
+        let after = diskstats::sample();
+        self.io_rates = diskstats::rates(before, &after, elapsed);
+        self.io_sample = Some((Instant::now(), after));
+    }
+
+    /// Reload when something was plugged in or pulled out
+    fn check_hotplug(&mut self) {
+        if self.hotplug_checked.elapsed() < HOTPLUG_INTERVAL {
+            return;
+        }
+        self.hotplug_checked = Instant::now();
+        let devices = removable::block_devices();
+        if devices == self.block_devices {
+            return;
+        }
+        if let Some(added) = devices.iter().find(|d| !self.block_devices.contains(d)) {
+            self.status_message = Some((trf("New device: {}", &[added]), false));
+        } else if let Some(gone) = self.block_devices.iter().find(|d| !devices.contains(d)) {
+            self.status_message = Some((trf("{} was removed", &[gone]), false));
+        }
+        self.load_disk_info();
+        self.clamp_selection();
+    }
+
+    fn start_check(&mut self) {
+        let Some(disk) = self.selected_disk() else {
+            return;
+        };
+        if disk.is_mounted {
+            // A mounted filesystem can only be checked by rc.S at boot
+            let scheduled = fsck::boot_check_scheduled();
+            self.request(DiskAction::BootCheck(!scheduled));
+            return;
+        }
+        if let Err(e) = fsck::command(&disk.filesystem, &disk.device_path, false) {
+            self.status_message = Some((e.to_string(), true));
+            return;
+        }
+        self.fsck_prompt = Some((disk.device_path.clone(), disk.filesystem.clone()));
+        self.status_message = None;
+    }
+
+    fn check_filesystem(&mut self, device: &str, fstype: &str, repair: bool) -> Option<Message> {
+        // It may have been mounted since it was picked
+        if let Err(e) = mkfs::check_unused(device) {
+            self.status_message = Some((e.to_string(), true));
+            return None;
+        }
+        match fsck::run(fstype, device, repair) {
+            Ok(rx) => {
+                self.fsck = Some(FsckRun {
+                    device: device.to_string(),
+                    fstype: fstype.to_string(),
+                    repair,
+                    run: CommandRun::new(rx),
+                });
+                self.mode = DiskMode::Fsck;
+                self.status_message = None;
             }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
         }
         None
     }
 
-    fn check_filesystem(&mut self, device: &str) -> Option<Message> {
-        // Note: filesystem check usually requires unmounted partition
-        self.status_message = Some((
-            "Filesystem check requires unmounted partition. Use 'fsck' manually.".to_string(),
-            true,
-        ));
+    fn set_label(&mut self, device: &str, fstype: &str, label: &str) -> Option<Message> {
+        self.status_message = Some(match blkid::set_label(fstype, device, label) {
+            Ok(()) => (trf("Labelled {} \"{}\"", &[device, label]), false),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_disk_info();
+        None
+    }
+
+    fn copy_fstab_line(&mut self) {
+        let Some(disk) = self.selected_disk() else {
+            return;
+        };
+        if disk.uuid.is_empty() {
+            self.status_message = Some((trf("{} has no filesystem UUID", &[&disk.name]), true));
+            return;
+        }
+        let mount_point = match &disk.mount_point {
+            Some(mp) if disk.is_mounted => mp.clone(),
+            _ => self.find_mount_point(&disk.device_path),
+        };
+        let line = blkid::fstab_line(&disk.uuid, &mount_point, &disk.filesystem);
+        self.status_message = Some(match clipboard::copy(&line) {
+            Ok(()) => (trf("Copied fstab line for {}", &[&disk.device_path]), false),
+            Err(e) => (e.to_string(), true),
+        });
+    }
+
+    fn set_boot_check(&mut self, enabled: bool) -> Option<Message> {
+        self.status_message = Some(match fsck::schedule_boot_check(enabled) {
+            Ok(()) if enabled => (
+                tr("All filesystems in fstab will be checked on the next boot").to_string(),
+                false,
+            ),
+            Ok(()) => (tr("The boot-time check was cancelled").to_string(), false),
+            Err(e) => (e.to_string(), true),
+        });
+        None
+    }
+
+    fn load_smart(&mut self) {
+        self.smart_drives = smart::physical_drives()
+            .into_iter()
+            .map(|drive| {
+                let report = smart::report(&drive).map_err(|e| e.to_string());
+                (drive, report)
+            })
+            .collect();
+        let selected = self.smart_state.selected().unwrap_or(0);
+        self.smart_state.select(if self.smart_drives.is_empty() {
+            None
+        } else {
+            Some(selected.min(self.smart_drives.len() - 1))
+        });
+    }
+
+    fn selected_drive(&self) -> Option<&(String, Result<SmartReport, String>)> {
+        self.smart_state.selected().and_then(|i| self.smart_drives.get(i))
+    }
+
+    fn start_self_test(&mut self, drive: &str, long: bool) -> Option<Message> {
+        self.status_message = Some(match smart::start_self_test(drive, long) {
+            Ok(eta) => (format!("{}: {}", drive, eta), false),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_smart();
+        None
+    }
+
+    fn handle_smart_input(&mut self, key: KeyEvent) {
+        let selected = self.smart_state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.smart_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.smart_drives.len().saturating_sub(1);
+                self.smart_state.select(Some((selected + 1).min(last)));
+            }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                let long = key.code == KeyCode::Char('T');
+                if let Some((drive, Ok(_))) = self.selected_drive() {
+                    self.request(DiskAction::SelfTest(drive.clone(), long));
+                }
+            }
+            KeyCode::F(5) => {
+                self.load_smart();
+                self.status_message = Some((tr("SMART data refreshed").to_string(), false));
+            }
+            KeyCode::Esc => self.mode = DiskMode::Overview,
+            _ => {}
+        }
+    }
+
+    fn start_format(&mut self) {
+        let Some(disk) = self.selected_disk() else {
+            return;
+        };
+        let device = disk.device_path.clone();
+        let current = disk.filesystem.clone();
+        if disk.is_mounted {
+            self.status_message = Some((trf("Unmount {} before formatting it", &[&device]), true));
+            return;
+        }
+        if let Err(e) = mkfs::check_unused(&device) {
+            self.status_message = Some((e.to_string(), true));
+            return;
+        }
+        self.format = Some(FormatWizard {
+            device,
+            current,
+            step: FormatStep::Filesystem,
+            fs_index: 0,
+            label: String::new(),
+            typed: String::new(),
+            error: None,
+            run: None,
+        });
+        self.mode = DiskMode::Format;
+        self.status_message = None;
+    }
+
+    fn handle_format_input(&mut self, key: KeyEvent) {
+        let Some(wizard) = &mut self.format else {
+            self.mode = DiskMode::Overview;
+            return;
+        };
+        wizard.error = None;
+        match (wizard.step, key.code) {
+            (FormatStep::Filesystem, KeyCode::Up | KeyCode::Char('k')) => {
+                wizard.fs_index = wizard.fs_index.saturating_sub(1);
+            }
+            (FormatStep::Filesystem, KeyCode::Down | KeyCode::Char('j')) => {
+                wizard.fs_index = (wizard.fs_index + 1).min(Filesystem::ALL.len() - 1);
+            }
+            (FormatStep::Filesystem, KeyCode::Enter) => wizard.step = FormatStep::Label,
+            (FormatStep::Label, KeyCode::Enter) => {
+                match mkfs::validate_label(wizard.filesystem(), &wizard.label) {
+                    Ok(()) => wizard.step = FormatStep::Confirm,
+                    Err(e) => wizard.error = Some(e),
+                }
+            }
+            (FormatStep::Confirm, KeyCode::Enter) => {
+                if wizard.typed != wizard.device {
+                    wizard.error = Some(trf("Type {} exactly to go ahead", &[&wizard.device]));
+                    return;
+                }
+                // Guard against it having been mounted while the wizard was open
+                if let Err(e) = mkfs::check_unused(&wizard.device) {
+                    wizard.error = Some(e.to_string());
+                    return;
+                }
+                wizard.run = Some(CommandRun::new(mkfs::format(
+                    wizard.filesystem(),
+                    &wizard.device,
+                    &wizard.label,
+                )));
+                wizard.step = FormatStep::Running;
+            }
+            (FormatStep::Label, KeyCode::Backspace) => {
+                wizard.label.pop();
+            }
+            (FormatStep::Confirm, KeyCode::Backspace) => {
+                wizard.typed.pop();
+            }
+            (FormatStep::Label, KeyCode::Char(c)) => wizard.label.push(c),
+            (FormatStep::Confirm, KeyCode::Char(c)) => wizard.typed.push(c),
+            (FormatStep::Label, KeyCode::Esc) => wizard.step = FormatStep::Filesystem,
+            (FormatStep::Confirm, KeyCode::Esc) => {
+                wizard.typed.clear();
+                wizard.step = FormatStep::Label;
+            }
+            (FormatStep::Running, KeyCode::Esc | KeyCode::Enter)
+                if !wizard.run.as_ref().is_some_and(CommandRun::finished) => {}
+            (FormatStep::Filesystem | FormatStep::Running, KeyCode::Esc | KeyCode::Enter) => {
+                self.format = None;
+                self.mode = DiskMode::Overview;
+                self.load_disk_info();
+            }
+            _ => {}
+        }
+    }
+
+    fn load_tables(&mut self) {
+        self.tables = smart::physical_drives()
+            .into_iter()
+            .map(|disk| {
+                let table = partitions::read(&disk).map_err(|e| e.to_string());
+                (disk, table)
+            })
+            .collect();
+        self.table_index = self.table_index.min(self.tables.len().saturating_sub(1));
+        let slots = self.slots().len();
+        let selected = self.slot_state.selected().unwrap_or(0);
+        self.slot_state
+            .select((slots > 0).then(|| selected.min(slots - 1)));
+    }
+
+    fn current_table(&self) -> Option<&(String, Result<PartitionTable, String>)> {
+        self.tables.get(self.table_index)
+    }
+
+    fn slots(&self) -> Vec<Slot> {
+        match self.current_table() {
+            Some((_, Ok(table))) => table.layout(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn selected_slot(&self) -> Option<Slot> {
+        self.slot_state
+            .selected()
+            .and_then(|i| self.slots().get(i).cloned())
+    }
+
+    fn apply_partition_op(&mut self, op: PartitionOp) {
+        self.status_message = Some(match partitions::apply(&op) {
+            Ok(backup) => (
+                trf(
+                    "Done; the old table is saved in {}",
+                    &[&backup.display().to_string()],
+                ),
+                false,
+            ),
+            Err(e) => (e.to_string(), true),
+        });
+        self.part_edit = None;
+        self.load_tables();
+        self.load_disk_info();
+    }
+
+    fn handle_partition_input(&mut self, key: KeyEvent) {
+        if let Some(edit) = &mut self.part_edit {
+            let sector_size = match self.tables.get(self.table_index) {
+                Some((_, Ok(table))) => table.sectorsize,
+                _ => 512,
+            };
+            edit.error = None;
+            match (edit.step, key.code) {
+                (EditStep::Size, KeyCode::Char(c)) => edit.size.push(c),
+                (EditStep::Size, KeyCode::Backspace) => {
+                    edit.size.pop();
+                }
+                (EditStep::Size, KeyCode::Enter) => match edit.build_op(sector_size) {
+                    Ok(_) => edit.step = EditStep::Kind,
+                    Err(e) => edit.error = Some(e),
+                },
+                (EditStep::Kind, KeyCode::Up | KeyCode::Char('k')) => {
+                    edit.kind = edit.kind.saturating_sub(1);
+                }
+                (EditStep::Kind, KeyCode::Down | KeyCode::Char('j')) => {
+                    edit.kind = (edit.kind + 1).min(partitions::KINDS.len() - 1);
+                }
+                (EditStep::Kind, KeyCode::Enter) => match edit.build_op(sector_size) {
+                    Ok(op) => {
+                        edit.op = Some(op);
+                        edit.step = EditStep::Review;
+                    }
+                    Err(e) => edit.error = Some(e),
+                },
+                (EditStep::Review, KeyCode::Char('y') | KeyCode::Char('Y')) => {
+                    edit.step = EditStep::TypeDisk;
+                }
+                (EditStep::TypeDisk, KeyCode::Char(c)) => edit.typed.push(c),
+                (EditStep::TypeDisk, KeyCode::Backspace) => {
+                    edit.typed.pop();
+                }
+                (EditStep::TypeDisk, KeyCode::Enter) => {
+                    if edit.typed != edit.disk {
+                        edit.error = Some(trf("Type {} exactly to go ahead", &[&edit.disk]));
+                    } else if let Some(op) = edit.op.take() {
+                        self.apply_partition_op(op);
+                    }
+                }
+                (_, KeyCode::Esc) | (EditStep::Review, KeyCode::Char('n')) => {
+                    self.part_edit = None;
+                    self.status_message = Some((tr("Nothing was changed").to_string(), false));
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        let selected = self.slot_state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.slot_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.slots().len().saturating_sub(1);
+                self.slot_state.select(Some((selected + 1).min(last)));
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') => {
+                if self.tables.is_empty() {
+                    return;
+                }
+                let count = self.tables.len();
+                self.table_index = match key.code {
+                    KeyCode::Left | KeyCode::Char('h') => (self.table_index + count - 1) % count,
+                    _ => (self.table_index + 1) % count,
+                };
+                self.slot_state
+                    .select((!self.slots().is_empty()).then_some(0));
+            }
+            KeyCode::Char('n') | KeyCode::Char('d') | KeyCode::Delete | KeyCode::Char('t') => {
+                let Some(disk) = self.current_table().map(|(disk, _)| disk.clone()) else {
+                    return;
+                };
+                let goal = match (key.code, self.selected_slot()) {
+                    (KeyCode::Char('n'), Some(Slot::Free { start, size })) => {
+                        EditGoal::Create { start, size }
+                    }
+                    (KeyCode::Char('t'), Some(Slot::Used(p))) => EditGoal::SetType(p),
+                    (KeyCode::Char('d') | KeyCode::Delete, Some(Slot::Used(p))) => {
+                        if let Err(e) = mkfs::check_unused(&p.node) {
+                            self.status_message = Some((e.to_string(), true));
+                            return;
+                        }
+                        EditGoal::Delete(p)
+                    }
+                    (KeyCode::Char('n'), _) => {
+                        self.status_message = Some((
+                            tr("Select free space to create a partition").to_string(),
+                            true,
+                        ));
+                        return;
+                    }
+                    _ => return,
+                };
+                self.status_message = None;
+                self.part_edit = Some(PartitionEdit::new(&disk, goal));
+            }
+            KeyCode::F(5) => {
+                self.load_tables();
+                self.status_message = Some((tr("Partition tables refreshed").to_string(), false));
+            }
+            KeyCode::Esc => self.mode = DiskMode::Overview,
+            _ => {}
+        }
+    }
+
+    /// Each VG followed by its LVs and PVs, then PVs outside any VG
+    fn lvm_rows(&self) -> Vec<LvmRow> {
+        let mut rows = Vec::new();
+        for (i, vg) in self.lvm.vgs.iter().enumerate() {
+            rows.push(LvmRow::Vg(i));
+            rows.extend(
+                (0..self.lvm.lvs.len())
+                    .filter(|&l| self.lvm.lvs[l].vg == vg.name)
+                    .map(LvmRow::Lv),
+            );
+            rows.extend(
+                (0..self.lvm.pvs.len())
+                    .filter(|&p| self.lvm.pvs[p].vg == vg.name)
+                    .map(LvmRow::Pv),
+            );
+        }
+        rows.extend(
+            (0..self.lvm.pvs.len())
+                .filter(|&p| self.lvm.pvs[p].vg.is_empty())
+                .map(LvmRow::Pv),
+        );
+        rows
+    }
+
+    fn selected_lvm_row(&self) -> Option<LvmRow> {
+        self.lvm_state
+            .selected()
+            .and_then(|i| self.lvm_rows().get(i).copied())
+    }
+
+    fn load_lvm(&mut self) {
+        self.lvm = lvm::load();
+        let rows = self.lvm_rows().len();
+        let selected = self.lvm_state.selected().unwrap_or(0);
+        self.lvm_state
+            .select((rows > 0).then(|| selected.min(rows - 1)));
+    }
+
+    fn apply_lvm_op(&mut self, op: LvmOp) -> Option<Message> {
+        self.status_message = Some(match lvm::apply(&op) {
+            Ok(()) => (trf("Done: {}", &[&op.describe()]), false),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_disk_info();
+        self.load_lvm();
         None
     }
 
+    fn handle_lvm_prompt(&mut self, key: KeyEvent) {
+        self.status_message = None;
+        let Some((prompt, buffer)) = &mut self.lvm_prompt else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) => buffer.push(c),
+            KeyCode::Backspace => {
+                buffer.pop();
+            }
+            KeyCode::Esc => self.lvm_prompt = None,
+            KeyCode::Enter => {
+                let value = buffer.trim().to_string();
+                match prompt.clone() {
+                    LvmPrompt::Name(vg) => {
+                        if !lvm::is_valid_name(&value) {
+                            self.status_message =
+                                Some((trf("Not a valid LV name: {}", &[&value]), true));
+                            return;
+                        }
+                        if self.lvm.lvs.iter().any(|lv| lv.vg == vg && lv.name == value) {
+                            self.status_message =
+                                Some((trf("{}/{} already exists", &[&vg, &value]), true));
+                            return;
+                        }
+                        self.status_message = None;
+                        let prompt = LvmPrompt::Size { vg, name: value };
+                        self.lvm_prompt = Some((prompt, String::new()));
+                    }
+                    LvmPrompt::Size { vg, name } => {
+                        if !lvm::is_valid_size(&value) {
+                            self.status_message = Some((
+                                tr("Sizes look like 20G, 512M or 100%FREE").to_string(),
+                                true,
+                            ));
+                            return;
+                        }
+                        self.lvm_prompt = None;
+                        self.status_message = None;
+                        let op = LvmOp::Create { vg, name, size: value };
+                        self.request(DiskAction::Lvm(op));
+                    }
+                    LvmPrompt::Grow(lv) => {
+                        if !lvm::is_valid_size(value.trim_start_matches('+')) {
+                            self.status_message =
+                                Some((tr("Sizes look like +5G or +100%FREE").to_string(), true));
+                            return;
+                        }
+                        self.lvm_prompt = None;
+                        self.status_message = None;
+                        let op = LvmOp::Extend { lv, size: value };
+                        self.request(DiskAction::Lvm(op));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_lvm_input(&mut self, key: KeyEvent) {
+        if self.lvm_prompt.is_some() {
+            self.handle_lvm_prompt(key);
+            return;
+        }
+        let selected = self.lvm_state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.lvm_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.lvm_rows().len().saturating_sub(1);
+                self.lvm_state.select(Some((selected + 1).min(last)));
+            }
+            KeyCode::Char('c') => {
+                let vg = match self.selected_lvm_row() {
+                    Some(LvmRow::Vg(i)) => self.lvm.vgs[i].name.clone(),
+                    Some(LvmRow::Lv(i)) => self.lvm.lvs[i].vg.clone(),
+                    Some(LvmRow::Pv(i)) if !self.lvm.pvs[i].vg.is_empty() => {
+                        self.lvm.pvs[i].vg.clone()
+                    }
+                    _ => return,
+                };
+                if self.lvm.vgs.iter().any(|g| g.name == vg && g.free_extents == 0) {
+                    self.status_message = Some((trf("{} has no free extents", &[&vg]), true));
+                    return;
+                }
+                self.lvm_prompt = Some((LvmPrompt::Name(vg), String::new()));
+            }
+            KeyCode::Char('e') => {
+                if let Some(LvmRow::Lv(i)) = self.selected_lvm_row() {
+                    let lv = self.lvm.lvs[i].path();
+                    self.lvm_prompt = Some((LvmPrompt::Grow(lv), "+".to_string()));
+                }
+            }
+            KeyCode::F(5) => {
+                self.load_lvm();
+                self.status_message = Some((tr("LVM info refreshed").to_string(), false));
+            }
+            KeyCode::Esc => self.mode = DiskMode::Overview,
+            _ => {}
+        }
+    }
+
     fn find_mount_point(&self, device: &str) -> String {
         // Check fstab for configured mount point
         if let Ok(content) = fs::read_to_string("/etc/fstab") {
@@ -265,37 +1309,119 @@ impl DiskComponent {
 
     fn usage_color(percent: u8) -> Color {
         if percent >= 90 {
-            Color::Red
+            Theme::colors().error
         } else if percent >= 75 {
-            Color::Yellow
+            Theme::colors().warning
         } else {
-            Color::Green
+            Theme::colors().success
+        }
+    }
+}
+
+impl Component for DiskComponent {
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        if self.show_confirm {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.show_confirm = false;
+                    if let Some(action) = self.pending_action.take() {
+                        return self.execute_action(action);
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.show_confirm = false;
+                    self.pending_action = None;
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.mount_dialog.is_some() {
+            self.handle_mount_dialog(key);
+            return None;
+        }
+        if let Some(buffer) = &mut self.label_prompt {
+            match key.code {
+                KeyCode::Char(c) => buffer.push(c),
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Enter => {
+                    let label = buffer.trim().to_string();
+                    self.label_prompt = None;
+                    if let Some(disk) = self.selected_disk() {
+                        let action = DiskAction::SetLabel(
+                            disk.device_path.clone(),
+                            disk.filesystem.clone(),
+                            label,
+                        );
+                        return self.request(action);
+                    }
+                }
+                KeyCode::Esc => self.label_prompt = None,
+                _ => {}
+            }
+            return None;
+        }
+        if let Some((device, fstype)) = self.fsck_prompt.clone() {
+            let repair = match key.code {
+                KeyCode::Char('n') => false,
+                KeyCode::Char('y') => true,
+                KeyCode::Esc => {
+                    self.fsck_prompt = None;
+                    return None;
+                }
+                _ => return None,
+            };
+            self.fsck_prompt = None;
+            if let Err(e) = fsck::command(&fstype, &device, repair) {
+                self.status_message = Some((e.to_string(), true));
+                return None;
+            }
+            self.request(DiskAction::CheckFilesystem(device, fstype, repair));
+            return None;
+        }
+        if self.mode == DiskMode::Fsck {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter)
+                && self.fsck.as_ref().map_or(true, |check| check.run.finished())
+            {
+                self.fsck = None;
+                self.mode = DiskMode::Overview;
+            }
+            return None;
+        }
+        if self.mode == DiskMode::Smart {
+            self.handle_smart_input(key);
+            return None;
+        }
+        if self.mode == DiskMode::Format {
+            self.handle_format_input(key);
+            return None;
+        }
+        if self.mode == DiskMode::Partitions {
+            self.handle_partition_input(key);
+            return None;
         }
-    }
-}
-
-impl Component for DiskComponent {
-    fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
-        if self.show_confirm {
+        if self.mode == DiskMode::Lvm {
+            self.handle_lvm_input(key);
+            return None;
+        }
+        if self.mode == DiskMode::Raid {
             match key.code {
-                KeyCode::Char('y') | KeyCode::Char('Y') => {
-                    self.show_confirm = false;
-                    if let Some(action) = self.pending_action.take() {
-                        return match action {
-                            DiskAction::Mount(dev) => self.mount_disk(&dev),
-                            DiskAction::Unmount(mp) => self.unmount_disk(&mp),
-                            DiskAction::CheckFilesystem(dev) => self.check_filesystem(&dev),
-                        };
-                    }
-                }
-                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                    self.show_confirm = false;
-                    self.pending_action = None;
-                }
+                KeyCode::F(5) => self.arrays = mdraid::arrays(),
+                KeyCode::Esc => self.mode = DiskMode::Overview,
                 _ => {}
             }
             return None;
         }
+        if self.mode == DiskMode::Io {
+            if key.code == KeyCode::Esc {
+                self.mode = DiskMode::Overview;
+                self.io_sample = None;
+            }
+            return None;
+        }
 
         match key.code {
             KeyCode::Up | KeyCode::Char('k') => {
@@ -315,8 +1441,15 @@ impl Component for DiskComponent {
             KeyCode::Char('m') => {
                 if let Some(disk) = self.selected_disk() {
                     if !disk.is_mounted {
-                        self.pending_action = Some(DiskAction::Mount(disk.device_path.clone()));
-                        self.show_confirm = true;
+                        let device = disk.device_path.clone();
+                        self.mount_dialog = Some(MountDialog {
+                            mount_point: self.find_mount_point(&device),
+                            device,
+                            chosen: [false; MOUNT_OPTIONS.len()],
+                            selected: 0,
+                            custom: String::new(),
+                        });
+                        self.status_message = None;
                     }
                 }
             }
@@ -324,8 +1457,7 @@ impl Component for DiskComponent {
                 if let Some(disk) = self.selected_disk() {
                     if disk.is_mounted {
                         if let Some(mp) = &disk.mount_point {
-                            self.pending_action = Some(DiskAction::Unmount(mp.clone()));
-                            self.show_confirm = true;
+                            self.request(DiskAction::Unmount(mp.clone()));
                         }
                     }
                 }
@@ -333,12 +1465,53 @@ impl Component for DiskComponent {
             KeyCode::Enter => {
                 self.mode = match self.mode {
                     DiskMode::Overview => DiskMode::Details,
-                    DiskMode::Details => DiskMode::Overview,
+                    _ => DiskMode::Overview,
                 };
             }
+            KeyCode::Char('f') => self.start_format(),
+            KeyCode::Char('c') => self.start_check(),
+            KeyCode::Char('e') => self.start_eject(),
+            KeyCode::Char('L') => {
+                if let Some(disk) = self.selected_disk() {
+                    if disk.filesystem.is_empty() {
+                        self.status_message =
+                            Some((trf("{} has no filesystem to label", &[&disk.name]), true));
+                    } else {
+                        self.label_prompt = Some(disk.label.clone());
+                        self.status_message = None;
+                    }
+                }
+            }
+            KeyCode::Char('y') => self.copy_fstab_line(),
+            KeyCode::Char('l') => {
+                self.mode = DiskMode::Lvm;
+                self.status_message = None;
+                self.load_lvm();
+            }
+            KeyCode::Char('r') => {
+                self.mode = DiskMode::Raid;
+                self.status_message = None;
+                self.arrays = mdraid::arrays();
+            }
+            KeyCode::Char('p') => {
+                self.mode = DiskMode::Partitions;
+                self.status_message = None;
+                self.load_tables();
+            }
+            KeyCode::Char('i') => {
+                self.mode = DiskMode::Io;
+                self.status_message = None;
+                self.io_rates.clear();
+                self.io_sample = Some((Instant::now(), diskstats::sample()));
+            }
+            KeyCode::Char('s') => {
+                self.mode = DiskMode::Smart;
+                self.status_message = None;
+                self.load_smart();
+            }
             KeyCode::F(5) => {
                 self.load_disk_info();
-                self.status_message = Some(("Disk info refreshed".to_string(), false));
+                self.status_message = Some((tr("Disk info refreshed").to_string(), false));
             }
             _ => {}
         }
@@ -361,6 +1534,26 @@ impl Component for DiskComponent {
         // Disk list or details
         match self.mode {
             DiskMode::Overview => self.render_list(frame, chunks[1]),
+            DiskMode::Smart => self.render_smart(frame, chunks[1]),
+            DiskMode::Format => {
+                if let Some(wizard) = &self.format {
+                    self.render_format(wizard, frame, chunks[1]);
+                }
+            }
+            DiskMode::Partitions => self.render_partitions(frame, chunks[1]),
+            DiskMode::Lvm => self.render_lvm(frame, chunks[1]),
+            DiskMode::Raid => self.render_raid(frame, chunks[1]),
+            DiskMode::Io => self.render_io(frame, chunks[1]),
+            DiskMode::Fsck => {
+                if let Some(check) = &self.fsck {
+                    let verb = if check.repair { "Repairing" } else { "Checking" };
+                    let title = format!(" {} {} ({}) ", tr(verb), check.device, check.fstype);
+                    let lines = check.run.render_lines(chunks[1].height.saturating_sub(2) as usize);
+                    let pane = Paragraph::new(lines)
+                        .block(Block::default().borders(Borders::ALL).title(title));
+                    frame.render_widget(pane, chunks[1]);
+                }
+            }
             DiskMode::Details => {
                 if let Some(disk) = self.selected_disk() {
                     self.render_details(frame, chunks[1], disk);
@@ -370,30 +1563,135 @@ impl Component for DiskComponent {
             }
         }
 
+        if let Some(dialog) = &self.mount_dialog {
+            self.render_mount_dialog(dialog, frame, area);
+        }
+
         // Status bar
         let status_content = if self.show_confirm {
             let action_desc = match &self.pending_action {
-                Some(DiskAction::Mount(d)) => format!("Mount {}?", d),
-                Some(DiskAction::Unmount(m)) => format!("Unmount {}?", m),
-                Some(DiskAction::CheckFilesystem(d)) => format!("Check {}?", d),
-                None => "Confirm action?".to_string(),
+                Some(DiskAction::Mount(d, mp, options)) if options.is_empty() => {
+                    trf("Mount {} at {}?", &[d, mp])
+                }
+                Some(DiskAction::Mount(d, mp, options)) => {
+                    trf("Mount {} at {} with {}?", &[d, mp, options])
+                }
+                Some(DiskAction::Unmount(m)) => trf("Unmount {}?", &[m]),
+                Some(DiskAction::CheckFilesystem(d, fstype, false)) => {
+                    trf("Check {} ({}) without changing anything?", &[d, fstype])
+                }
+                Some(DiskAction::CheckFilesystem(d, fstype, true)) => {
+                    trf("Check {} ({}) and repair every error found?", &[d, fstype])
+                }
+                Some(DiskAction::SetLabel(d, _, label)) if label.is_empty() => {
+                    trf("Clear the label of {}?", &[d])
+                }
+                Some(DiskAction::SetLabel(d, _, label)) => trf("Label {} \"{}\"?", &[d, label]),
+                Some(DiskAction::BootCheck(true)) => {
+                    tr("It is mounted. Check all fstab filesystems on the next boot?").to_string()
+                }
+                Some(DiskAction::BootCheck(false)) => {
+                    tr("A boot-time check is already scheduled. Cancel it?").to_string()
+                }
+                Some(DiskAction::Lvm(op)) => trf("Run {}?", &[&op.describe()]),
+                Some(DiskAction::Eject(d)) => {
+                    trf("Unmount everything on {}, sync and eject it?", &[d])
+                }
+                Some(DiskAction::SelfTest(d, true)) => trf("Start a long self-test on {}?", &[d]),
+                Some(DiskAction::SelfTest(d, false)) => trf("Start a short self-test on {}?", &[d]),
+                None => tr("Confirm action?").to_string(),
+            };
+            Line::from(vec![
+                Span::styled(action_desc, Theme::warning()),
+                Span::raw(format!(" {}", tr("[Y]es / [N]o"))),
+            ])
+        } else if let Some((prompt, buffer)) = self.lvm_prompt.as_ref().filter(|_| {
+            !self.status_message.as_ref().is_some_and(|(_, is_error)| *is_error)
+        }) {
+            let label = match prompt {
+                LvmPrompt::Name(vg) => trf("New LV in {}, name: ", &[vg]),
+                LvmPrompt::Size { name, .. } => trf("Size of {} (20G, 100%FREE): ", &[name]),
+                LvmPrompt::Grow(lv) => trf("Grow {} by (+5G, +100%FREE): ", &[lv]),
             };
             Line::from(vec![
-                Span::styled(action_desc, Style::default().fg(Color::Yellow)),
-                Span::raw(" [Y]es / [N]o"),
+                Span::styled(label, Theme::label()),
+                Span::styled(format!("{}_", buffer), Theme::input_active()),
+            ])
+        } else if let Some(buffer) = &self.label_prompt {
+            Line::from(vec![
+                Span::styled(tr("New label: "), Theme::label()),
+                Span::styled(format!("{}_", buffer), Theme::input_active()),
+            ])
+        } else if let Some((device, _)) = &self.fsck_prompt {
+            Line::from(vec![
+                Span::styled(trf("Check {}: ", &[device]), Theme::label()),
+                Span::styled("[N]", Theme::key_hint()),
+                Span::raw(format!(" {}  ", tr("report only"))),
+                Span::styled("[Y]", Theme::key_hint()),
+                Span::raw(format!(" {}  ", tr("repair"))),
+                Span::styled("[Esc]", Theme::key_hint()),
+                Span::raw(format!(" {}", tr("cancel"))),
             ])
+        } else if let Some(edit) = &self.part_edit {
+            match (&edit.error, edit.step) {
+                (Some(e), _) => {
+                    Line::from(Span::styled(e.clone(), Theme::error()))
+                }
+                (None, EditStep::Size) => Line::from(vec![
+                    Span::styled(
+                        tr("Size (e.g. 512M, 20G; empty for all free space): "),
+                        Theme::label(),
+                    ),
+                    Span::styled(format!("{}_", edit.size), Theme::input_active()),
+                ]),
+                (None, EditStep::Kind) => Line::from(Span::raw(tr("Choose the partition type"))),
+                (None, EditStep::Review) => Line::from(vec![
+                    Span::styled(
+                        tr("Review the change above."),
+                        Theme::warning(),
+                    ),
+                    Span::raw(format!(" {}", tr("[Y] continue / [N] cancel"))),
+                ]),
+                (None, EditStep::TypeDisk) => Line::from(vec![
+                    Span::styled(
+                        trf("Type {} to write its partition table: ", &[&edit.disk]),
+                        Theme::error(),
+                    ),
+                    Span::styled(format!("{}_", edit.typed), Theme::input_active()),
+                ]),
+            }
+        } else if let Some(wizard) = self.format.as_ref().filter(|w| w.error.is_some()) {
+            Line::from(Span::styled(
+                wizard.error.clone().unwrap_or_default(),
+                Theme::error(),
+            ))
+        } else if let Some(wizard) = &self.format {
+            match wizard.step {
+                FormatStep::Label => Line::from(vec![
+                    Span::styled(tr("Label (optional): "), Theme::label()),
+                    Span::styled(format!("{}_", wizard.label), Theme::input_active()),
+                ]),
+                FormatStep::Confirm => Line::from(vec![
+                    Span::styled(
+                        trf("Type {} to erase it: ", &[&wizard.device]),
+                        Theme::error(),
+                    ),
+                    Span::styled(format!("{}_", wizard.typed), Theme::input_active()),
+                ]),
+                _ => Line::from(Span::raw(trf("Formatting {}", &[&wizard.device]))),
+            }
         } else if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
             ))
         } else if let Some(disk) = self.selected_disk() {
             Line::from(vec![
-                Span::styled("Device: ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{} ", tr("Device:")), Theme::label()),
                 Span::raw(&disk.device_path),
             ])
         } else {
-            Line::from(Span::raw("Select a disk"))
+            Line::from(Span::raw(tr("Select a disk")))
         };
 
         let status = Paragraph::new(status_content)
@@ -401,25 +1699,178 @@ impl Component for DiskComponent {
         frame.render_widget(status, chunks[2]);
     }
 
+    fn is_typing(&self) -> bool {
+        self.format.is_some()
+            || self.part_edit.is_some()
+            || self.lvm_prompt.is_some()
+            || self.mount_dialog.is_some()
+            || self.fsck_prompt.is_some()
+            || self.label_prompt.is_some()
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        if self.mount_dialog.is_some() {
+            return vec![
+                ("↑/↓", "Navigate"),
+                ("Space", "Toggle"),
+                ("Enter", "Mount"),
+                ("Esc", "Cancel"),
+            ];
+        }
+        if self.mode == DiskMode::Fsck {
+            return vec![("Esc", "Close")];
+        }
+        if self.mode == DiskMode::Raid {
+            return vec![("F5", "Refresh"), ("Esc", "Back")];
+        }
+        if self.mode == DiskMode::Io {
+            return vec![("Esc", "Back")];
+        }
+        if self.mode == DiskMode::Lvm {
+            if self.lvm_prompt.is_some() {
+                return vec![("Enter", "Next"), ("Esc", "Cancel")];
+            }
+            return vec![
+                ("↑/↓", "Navigate"),
+                ("c", "Create LV"),
+                ("e", "Extend LV"),
+                ("F5", "Refresh"),
+                ("Esc", "Back"),
+            ];
+        }
+        if self.mode == DiskMode::Partitions {
+            if self.part_edit.is_some() {
+                return vec![("Enter", "Next"), ("Esc", "Cancel")];
+            }
+            return vec![
+                ("←/→", "Disk"),
+                ("↑/↓", "Navigate"),
+                ("n", "New"),
+                ("d", "Delete"),
+                ("t", "Type"),
+                ("Esc", "Back"),
+            ];
+        }
+        if self.mode == DiskMode::Format {
+            return vec![("↑/↓", "Filesystem"), ("Enter", "Next"), ("Esc", "Back")];
+        }
+        if self.mode == DiskMode::Smart {
+            return vec![
+                ("↑/↓", "Drive"),
+                ("t", "Short Test"),
+                ("T", "Long Test"),
+                ("F5", "Refresh"),
+                ("Esc", "Back"),
+            ];
+        }
         vec![
             ("m", "Mount"),
             ("u", "Unmount"),
             ("Enter", "Details"),
+            ("c", "Check"),
+            ("L", "Label"),
+            ("y", "Copy fstab"),
+            ("e", "Eject"),
+            ("f", "Format"),
+            ("p", "Partitions"),
+            ("l", "LVM"),
+            ("r", "RAID"),
+            ("i", "I/O"),
+            ("s", "SMART"),
             ("F5", "Refresh"),
         ]
     }
 
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        if self.mount_dialog.is_some() {
+            return Vec::new();
+        }
+        match self.mode {
+            DiskMode::Lvm if self.lvm_prompt.is_none() => vec!["c", "e"],
+            DiskMode::Partitions if self.part_edit.is_none() => vec!["n", "d", "t"],
+            DiskMode::Smart => vec!["t", "T"],
+            DiskMode::Overview | DiskMode::Details => vec!["m", "u", "c", "L", "e", "f"],
+            _ => Vec::new(),
+        }
+    }
+
     fn on_activate(&mut self) {
         self.load_disk_info();
     }
+
+    fn on_refresh(&mut self) {
+        // Leave dialogs and prompts alone
+        if self.show_confirm
+            || self.mount_dialog.is_some()
+            || self.label_prompt.is_some()
+            || self.fsck_prompt.is_some()
+        {
+            return;
+        }
+        match self.mode {
+            DiskMode::Overview | DiskMode::Details => {
+                self.load_disk_info();
+                self.clamp_selection();
+            }
+            DiskMode::Raid => self.arrays = mdraid::arrays(),
+            DiskMode::Lvm if self.lvm_prompt.is_none() => self.load_lvm(),
+            _ => {}
+        }
+    }
+
+    fn is_busy(&self) -> bool {
+        self.mode == DiskMode::Io
+            || self.fsck.as_ref().is_some_and(|check| !check.run.finished())
+            || self
+                .format
+                .as_ref()
+                .and_then(|wizard| wizard.run.as_ref())
+                .is_some_and(|run| !run.finished())
+    }
+
+    fn on_tick(&mut self) {
+        if matches!(self.mode, DiskMode::Overview | DiskMode::Details) {
+            self.check_hotplug();
+        }
+        if self.mode == DiskMode::Io {
+            self.sample_io();
+        }
+        // Keep sync progress moving while it is on screen
+        if self.mode == DiskMode::Raid && self.arrays.iter().any(|a| a.sync.is_some()) {
+            self.arrays = mdraid::arrays();
+        }
+        if let Some(check) = &mut self.fsck {
+            if let Some(code) = check.run.drain() {
+                self.status_message = Some(match code {
+                    Some(code) => (
+                        format!("{}: {}", check.device, fsck::describe_exit(code, check.repair)),
+                        code != 0,
+                    ),
+                    None => (tr("fsck was killed by a signal").to_string(), true),
+                });
+            }
+        }
+        let Some(wizard) = &mut self.format else {
+            return;
+        };
+        if let Some(code) = wizard.run.as_mut().and_then(CommandRun::drain) {
+            self.status_message = Some(match code {
+                Some(0) => (trf("Formatted {}", &[&wizard.device]), false),
+                Some(code) => (
+                    trf("mkfs failed with exit status {}", &[&code.to_string()]),
+                    true,
+                ),
+                None => (tr("mkfs was killed by a signal").to_string(), true),
+            });
+        }
+    }
 }
 
 impl DiskComponent {
     fn render_summary(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .borders(Borders::ALL)
-            .title(" Disk Management ");
+            .title(format!(" {} ", tr("Disk Management")));
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
@@ -444,19 +1895,19 @@ impl DiskComponent {
         // Stats
         let stats = Paragraph::new(vec![
             Line::from(vec![
-                Span::styled("Mounted:   ", Style::default().fg(Color::Cyan)),
-                Span::raw(format!("{} partitions", mounted_count)),
+                Span::styled(format!("{:<10} ", tr("Mounted:")), Theme::label()),
+                Span::raw(trf("{} partitions", &[&mounted_count.to_string()])),
             ]),
             Line::from(vec![
-                Span::styled("Unmounted: ", Style::default().fg(Color::Cyan)),
-                Span::raw(format!("{} partitions", unmounted_count)),
+                Span::styled(format!("{:<10} ", tr("Unmounted:")), Theme::label()),
+                Span::raw(trf("{} partitions", &[&unmounted_count.to_string()])),
             ]),
         ]);
         frame.render_widget(stats, chunks[0]);
 
         // Overall usage gauge
         let gauge = Gauge::default()
-            .block(Block::default().title("Total Usage"))
+            .block(Block::default().title(tr("Total Usage")))
             .gauge_style(Style::default().fg(Self::usage_color(overall_percent as u8)))
             .percent(overall_percent)
             .label(format!(
@@ -473,16 +1924,12 @@ impl DiskComponent {
             .disks
             .iter()
             .map(|disk| {
-                let mount_str = disk
-                    .mount_point
-                    .as_ref()
-                    .map(|m| m.as_str())
-                    .unwrap_or("-");
+                let mount_str = disk.mount_point.as_deref().unwrap_or("-");
 
                 let status = if disk.is_mounted {
-                    Span::styled("●", Style::default().fg(Color::Green))
+                    Span::styled("●", Theme::success())
                 } else {
-                    Span::styled("○", Style::default().fg(Color::DarkGray))
+                    Span::styled("○", Theme::muted())
                 };
 
                 ListItem::new(vec![
@@ -495,11 +1942,11 @@ impl DiskComponent {
                         ),
                         Span::styled(
                             format!("{:<10}", disk.filesystem),
-                            Style::default().fg(Color::Cyan),
+                            Theme::label(),
                         ),
                         Span::styled(
                             format!("{:>10}", Self::format_size(disk.size)),
-                            Style::default().fg(Color::Yellow),
+                            Theme::warning(),
                         ),
                         if disk.is_mounted {
                             Span::styled(
@@ -509,10 +1956,31 @@ impl DiskComponent {
                         } else {
                             Span::raw("     ")
                         },
+                        if disk.removable {
+                            Span::styled(format!("  ⏏ {}", tr("removable")), Theme::label())
+                        } else {
+                            Span::raw("")
+                        },
                     ]),
                     Line::from(vec![
-                        Span::styled("  Mount: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(format!("  {} ", tr("Mount:")), Theme::muted()),
                         Span::raw(mount_str),
+                        Span::styled(
+                            if disk.label.is_empty() {
+                                String::new()
+                            } else {
+                                format!("  [{}]", disk.label)
+                            },
+                            Style::default().fg(Theme::colors().secondary),
+                        ),
+                        Span::styled(
+                            if disk.options.is_empty() {
+                                String::new()
+                            } else {
+                                format!("  ({})", disk.options)
+                            },
+                            Theme::muted(),
+                        ),
                     ]),
                 ])
             })
@@ -522,7 +1990,7 @@ impl DiskComponent {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(format!(" Partitions ({}) ", self.disks.len())),
+                    .title(format!(" {} ({}) ", tr("Partitions"), self.disks.len())),
             )
             .highlight_style(Theme::list_selected())
             .highlight_symbol("▶ ");
@@ -534,45 +2002,54 @@ impl DiskComponent {
     fn render_details(&self, frame: &mut Frame, area: Rect, disk: &DiskInfo) {
         let block = Block::default()
             .borders(Borders::ALL)
-            .title(format!(" {} Details ", disk.name));
+            .title(format!(" {} ", trf("{} Details", &[&disk.name])));
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(8), Constraint::Min(3)])
+            .constraints([Constraint::Length(11), Constraint::Min(3)])
             .split(inner);
 
         // Info
         let info = vec![
             Line::from(vec![
-                Span::styled("Device:     ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<11} ", tr("Device:")), Theme::label()),
                 Span::raw(&disk.device_path),
             ]),
             Line::from(vec![
-                Span::styled("Filesystem: ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<11} ", tr("Filesystem:")), Theme::label()),
                 Span::raw(&disk.filesystem),
             ]),
             Line::from(vec![
-                Span::styled("Mount:      ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<11} ", tr("Mount:")), Theme::label()),
                 Span::raw(
-                    disk.mount_point
-                        .as_ref()
-                        .map(|s| s.as_str())
-                        .unwrap_or("Not mounted"),
+                    disk.mount_point.as_deref().unwrap_or(tr("Not mounted")),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Size:       ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<11} ", tr("Label:")), Theme::label()),
+                Span::raw(if disk.label.is_empty() { "-" } else { &disk.label }),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("{:<11} ", tr("UUID:")), Theme::label()),
+                Span::raw(if disk.uuid.is_empty() { "-" } else { &disk.uuid }),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("{:<11} ", tr("Options:")), Theme::label()),
+                Span::raw(if disk.options.is_empty() { "-" } else { &disk.options }),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("{:<11} ", tr("Size:")), Theme::label()),
                 Span::raw(Self::format_size(disk.size)),
             ]),
             Line::from(vec![
-                Span::styled("Used:       ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<11} ", tr("Used:")), Theme::label()),
                 Span::raw(Self::format_size(disk.used)),
             ]),
             Line::from(vec![
-                Span::styled("Available:  ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<11} ", tr("Available:")), Theme::label()),
                 Span::raw(Self::format_size(disk.available)),
             ]),
         ];
@@ -583,11 +2060,600 @@ impl DiskComponent {
         // Usage gauge
         if disk.is_mounted {
             let gauge = Gauge::default()
-                .block(Block::default().title("Usage"))
+                .block(Block::default().title(tr("Usage")))
                 .gauge_style(Style::default().fg(Self::usage_color(disk.use_percent)))
                 .percent(disk.use_percent as u16)
                 .label(format!("{}%", disk.use_percent));
             frame.render_widget(gauge, chunks[1]);
         }
     }
+
+    fn render_smart(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(24), Constraint::Min(40)])
+            .split(area);
+
+        let items: Vec<ListItem> = self
+            .smart_drives
+            .iter()
+            .map(|(drive, report)| {
+                let (mark, color) = match report {
+                    Ok(r) if r.healthy == Some(false) => ("✗", Theme::colors().error),
+                    Ok(r) if r.highlights().iter().any(|h| h.warn) => ("!", Theme::colors().warning),
+                    Ok(r) if r.healthy == Some(true) => ("●", Theme::colors().success),
+                    _ => ("?", Theme::colors().muted),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(mark, Style::default().fg(color)),
+                    Span::raw(format!(" {}", drive.trim_start_matches("/dev/"))),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", tr("Drives"))))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.smart_state.clone();
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let label =
+            |text: &str| Span::styled(format!("{:<22}", tr(text)), Theme::label());
+        let mut lines = Vec::new();
+        let title = match self.selected_drive() {
+            None => {
+                lines.push(Line::from(tr("No drives found")));
+                " SMART ".to_string()
+            }
+            Some((drive, Err(e))) => {
+                lines.push(Line::from(Span::styled(e.clone(), Theme::error())));
+                format!(" {} ", drive)
+            }
+            Some((drive, Ok(report))) => {
+                lines.push(Line::from(vec![label("Model"), Span::raw(report.model.clone())]));
+                lines.push(Line::from(vec![label("Serial"), Span::raw(report.serial.clone())]));
+                let (health, color) = match report.healthy {
+                    Some(true) => ("PASSED", Theme::colors().success),
+                    Some(false) => ("FAILING", Theme::colors().error),
+                    None => ("unknown (SMART unavailable?)", Theme::colors().muted),
+                };
+                lines.push(Line::from(vec![
+                    label("Health"),
+                    Span::styled(tr(health), Style::default().fg(color)),
+                ]));
+                for highlight in report.highlights() {
+                    let color = if highlight.warn { Theme::colors().warning } else { Theme::colors().foreground };
+                    lines.push(Line::from(vec![
+                        label(highlight.label),
+                        Span::styled(highlight.value, Style::default().fg(color)),
+                    ]));
+                }
+
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    tr("Recent self-tests"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )));
+                if let Some(progress) = &report.test_in_progress {
+                    lines.push(Line::from(Span::styled(
+                        progress.clone(),
+                        Theme::warning(),
+                    )));
+                }
+                if report.self_tests.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        tr("None logged"),
+                        Theme::muted(),
+                    )));
+                }
+                for test in report.self_tests.iter().take(5) {
+                    let color = if test.passed() { Theme::colors().success } else { Theme::colors().error };
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("{:<20}", test.description)),
+                        Span::styled(format!("{:<32}", test.status), Style::default().fg(color)),
+                        Span::styled(
+                            trf("at {} h", &[&test.lifetime_hours.to_string()]),
+                            Theme::muted(),
+                        ),
+                    ]));
+                }
+                format!(" {} ", drive)
+            }
+        };
+        let details =
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(details, chunks[1]);
+    }
+
+    fn render_format(&self, wizard: &FormatWizard, frame: &mut Frame, area: Rect) {
+        let title = format!(" {} ", trf("Format {}", &[&wizard.device]));
+        let mut lines = Vec::new();
+        if let Some(run) = &wizard.run {
+            lines = run.render_lines(area.height.saturating_sub(2) as usize);
+        } else {
+            let current = if wizard.current.is_empty() {
+                tr("no filesystem")
+            } else {
+                &wizard.current
+            };
+            lines.push(Line::from(vec![
+                Span::styled(tr("Now holds: "), Theme::label()),
+                Span::raw(current.to_string()),
+            ]));
+            lines.push(Line::from(""));
+            for (i, fs) in Filesystem::ALL.iter().enumerate() {
+                let selected = i == wizard.fs_index;
+                let style = if selected && wizard.step == FormatStep::Filesystem {
+                    Theme::list_selected()
+                } else if selected {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Theme::muted()
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{} {:<8}", if selected { "▶" } else { " " }, fs.name()),
+                        style,
+                    ),
+                    Span::styled(tr(fs.description()), Theme::muted()),
+                ]));
+            }
+            if wizard.step != FormatStep::Filesystem {
+                lines.push(Line::from(""));
+                let label = if wizard.label.is_empty() { tr("(none)") } else { &wizard.label };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:<10} ", tr("Label:")), Theme::label()),
+                    Span::raw(label.to_string()),
+                ]));
+            }
+            if wizard.step == FormatStep::Confirm {
+                let (program, args) = wizard.filesystem().command(&wizard.device, &wizard.label);
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:<10} ", tr("Command:")), Theme::label()),
+                    Span::raw(format!("{} {}", program, args.join(" "))),
+                ]));
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    trf("Everything on {} will be erased.", &[&wizard.device]),
+                    Theme::error().add_modifier(Modifier::BOLD),
+                )));
+            }
+        }
+        let pane = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(pane, area);
+    }
+
+    fn render_partitions(&self, frame: &mut Frame, area: Rect) {
+        let Some((disk, table)) = self.current_table() else {
+            let empty = Paragraph::new(tr("No disks found"))
+                .block(Block::default().borders(Borders::ALL).title(format!(" {} ", tr("Partitions"))));
+            frame.render_widget(empty, area);
+            return;
+        };
+        let table = match table {
+            Ok(table) => table,
+            Err(e) => {
+                let error = Paragraph::new(Span::styled(e.clone(), Theme::error()))
+                    .block(Block::default().borders(Borders::ALL).title(format!(" {} ", disk)));
+                frame.render_widget(error, area);
+                return;
+            }
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(if self.part_edit.is_some() {
+                [Constraint::Min(6), Constraint::Length(9)]
+            } else {
+                [Constraint::Min(6), Constraint::Length(0)]
+            })
+            .split(area);
+
+        let size = |sectors: u64| Self::format_size(table.bytes(sectors));
+        let items: Vec<ListItem> = table
+            .layout()
+            .iter()
+            .map(|slot| match slot {
+                Slot::Used(p) => {
+                    let mut spans = vec![
+                        Span::styled(
+                            format!("{:<16}", p.node.trim_start_matches("/dev/")),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!("{:>10}", size(p.size)),
+                            Theme::warning(),
+                        ),
+                        Span::styled(
+                            format!("  {:>12}-{:<12}", p.start, p.start + p.size - 1),
+                            Theme::muted(),
+                        ),
+                        Span::styled(
+                            format!("{:<22}", p.kind_name()),
+                            Theme::label(),
+                        ),
+                    ];
+                    if let Some(name) = p.name.as_deref().filter(|n| !n.is_empty()) {
+                        spans.push(Span::raw(format!("\"{}\" ", name)));
+                    }
+                    if p.bootable {
+                        spans.push(Span::styled(tr("boot"), Theme::success()));
+                    }
+                    ListItem::new(Line::from(spans))
+                }
+                Slot::Free { start, size: free } => ListItem::new(Line::from(Span::styled(
+                    format!(
+                        "{:<16}{:>10}  {:>12}-{:<12}",
+                        tr("free space"),
+                        size(*free),
+                        start,
+                        start + free - 1
+                    ),
+                    Theme::muted(),
+                ))),
+            })
+            .collect();
+        let title = format!(
+            " {} ({}, {}/{}) ",
+            disk,
+            table.label,
+            self.table_index + 1,
+            self.tables.len()
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.slot_state.clone();
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        if let Some(edit) = &self.part_edit {
+            self.render_partition_edit(edit, table, frame, chunks[1]);
+        }
+    }
+
+    fn render_partition_edit(
+        &self,
+        edit: &PartitionEdit,
+        table: &PartitionTable,
+        frame: &mut Frame,
+        area: Rect,
+    ) {
+        let (title, target) = match &edit.goal {
+            EditGoal::Create { size, .. } => (
+                "New partition",
+                trf("{} of free space", &[&Self::format_size(table.bytes(*size))]),
+            ),
+            EditGoal::Delete(p) => ("Delete partition", p.node.clone()),
+            EditGoal::SetType(p) => ("Change partition type", p.node.clone()),
+        };
+        let title = format!(" {} ", tr(title));
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("{:<8} ", tr("Target:")), Theme::label()),
+            Span::raw(target),
+        ])];
+        if edit.step == EditStep::Kind {
+            for (i, (_, name)) in partitions::KINDS.iter().enumerate() {
+                let style = if i == edit.kind {
+                    Theme::list_selected()
+                } else {
+                    Style::default()
+                };
+                let marker = if i == edit.kind { "▶ " } else { "  " };
+                lines.push(Line::from(Span::styled(format!("{}{}", marker, name), style)));
+            }
+        }
+        if let Some(op) = &edit.op {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<8} ", tr("Command:")), Theme::label()),
+                Span::raw(op.describe()),
+            ]));
+            lines.push(Line::from(""));
+            let warning = match op {
+                PartitionOp::Delete { partition, .. } => {
+                    trf("Everything on {} will be lost.", &[partition])
+                }
+                _ => trf("The partition table of {} will be rewritten.", &[&edit.disk]),
+            };
+            lines.push(Line::from(Span::styled(
+                warning,
+                Theme::error().add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(Span::styled(
+                tr("A backup of the current table is saved first."),
+                Theme::muted(),
+            )));
+        }
+        let pane = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(pane, area);
+    }
+
+    fn render_lvm(&self, frame: &mut Frame, area: Rect) {
+        let dim = Theme::muted();
+        let items: Vec<ListItem> = self
+            .lvm_rows()
+            .into_iter()
+            .map(|row| match row {
+                LvmRow::Vg(i) => {
+                    let vg = &self.lvm.vgs[i];
+                    let free_color = if vg.free_extents == 0 {
+                        Theme::colors().muted
+                    } else {
+                        Theme::colors().success
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<20}", vg.name),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!("{:>10}", Self::format_size(vg.size)),
+                            Theme::warning(),
+                        ),
+                        Span::styled(
+                            format!(
+                                "  {}",
+                                trf(
+                                    "{} free ({} extents of {})",
+                                    &[
+                                        &Self::format_size(vg.free),
+                                        &vg.free_extents.to_string(),
+                                        &Self::format_size(vg.extent_size),
+                                    ]
+                                )
+                            ),
+                            Style::default().fg(free_color),
+                        ),
+                    ]))
+                }
+                LvmRow::Lv(i) => {
+                    let lv = &self.lvm.lvs[i];
+                    let mount = self
+                        .disks
+                        .iter()
+                        .find(|d| d.device_path == lv.mapper_path() && d.is_mounted)
+                        .and_then(|d| d.mount_point.clone());
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("  LV {:<15}", lv.name)),
+                        Span::styled(
+                            format!("{:>10}", Self::format_size(lv.size)),
+                            Theme::warning(),
+                        ),
+                        Span::styled(
+                            match (mount, lv.is_open()) {
+                                (Some(mount), _) => format!("  {}", trf("on {}", &[&mount])),
+                                (None, true) => format!("  {}", tr("in use")),
+                                (None, false) => String::new(),
+                            },
+                            Theme::label(),
+                        ),
+                    ]))
+                }
+                LvmRow::Pv(i) => {
+                    let pv = &self.lvm.pvs[i];
+                    let note = if pv.vg.is_empty() {
+                        format!("  {}", tr("not in a volume group"))
+                    } else {
+                        format!("  {}", trf("{} free", &[&Self::format_size(pv.free)]))
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("  PV {:<15}", pv.name), dim),
+                        Span::styled(format!("{:>10}", Self::format_size(pv.size)), dim),
+                        Span::styled(note, dim),
+                    ]))
+                }
+            })
+            .collect();
+
+        let title = format!(
+            " {} ",
+            trf(
+                "LVM ({} VGs, {} LVs, {} PVs)",
+                &[
+                    &self.lvm.vgs.len().to_string(),
+                    &self.lvm.lvs.len().to_string(),
+                    &self.lvm.pvs.len().to_string(),
+                ]
+            )
+        );
+        if items.is_empty() {
+            let empty = Paragraph::new(Span::styled(tr("No LVM volumes found"), dim))
+                .block(Block::default().borders(Borders::ALL).title(title));
+            frame.render_widget(empty, area);
+            return;
+        }
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.lvm_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_raid(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ({}) ", tr("Software RAID"), self.arrays.len()));
+        if self.arrays.is_empty() {
+            let empty = Paragraph::new(Span::styled(
+                tr("No md arrays in /proc/mdstat"),
+                Theme::muted(),
+            ))
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let mut lines = Vec::new();
+        for array in &self.arrays {
+            let (health, color) = if array.is_degraded() {
+                ("DEGRADED", Theme::colors().error)
+            } else if array.sync.is_some() {
+                ("syncing", Theme::colors().warning)
+            } else {
+                ("clean", Theme::colors().success)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<8}", array.name),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{:<8}", array.level), Theme::label()),
+                Span::styled(format!("{:<10}", tr(health)), Style::default().fg(color)),
+                Span::raw(format!(
+                    "{} [{}/{}] {}",
+                    array.state, array.wanted, array.working, array.map
+                )),
+            ]));
+            if let Some(sync) = &array.sync {
+                let width = 30;
+                let done = (sync.percent / 100.0 * width as f32) as usize;
+                lines.push(Line::from(vec![
+                    Span::raw("        "),
+                    Span::styled(
+                        format!("[{}{}]", "=".repeat(done), ".".repeat(width - done.min(width))),
+                        Theme::warning(),
+                    ),
+                    Span::raw(format!(
+                        " {} {:.1}%, {}",
+                        sync.action,
+                        sync.percent,
+                        trf("{} left at {}", &[&sync.finish, &sync.speed])
+                    )),
+                ]));
+            }
+            for member in &array.members {
+                let (state, color) = if member.faulty {
+                    ("faulty", Theme::colors().error)
+                } else if member.spare {
+                    ("spare", Theme::colors().muted)
+                } else if array.member_up(member) {
+                    ("in sync", Theme::colors().success)
+                } else {
+                    ("rebuilding", Theme::colors().warning)
+                };
+                lines.push(Line::from(vec![
+                    Span::raw(format!(
+                        "        {:<12}{} {:<3}",
+                        member.device,
+                        tr("slot"),
+                        member.slot
+                    )),
+                    Span::styled(tr(state), Style::default().fg(color)),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_mount_dialog(&self, dialog: &MountDialog, frame: &mut Frame, area: Rect) {
+        let popup = crate::ui::centered_rect(60, 50, area);
+        frame.render_widget(Clear, popup);
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(tr("Mount point: "), Theme::label()),
+                Span::raw(dialog.mount_point.as_str()),
+            ]),
+            Line::from(""),
+        ];
+        for (i, ((name, help), on)) in MOUNT_OPTIONS.iter().zip(dialog.chosen).enumerate() {
+            let style = if i == dialog.selected {
+                Theme::list_selected()
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("[{}] {:<10}", if on { "x" } else { " " }, name), style),
+                Span::styled(tr(help), Theme::muted()),
+            ]));
+        }
+        let editing = dialog.selected == MOUNT_OPTIONS.len();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("    {:<10} ", tr("Other:")),
+                if editing {
+                    Theme::list_selected()
+                } else {
+                    Style::default()
+                },
+            ),
+            if editing {
+                Span::styled(format!("{}_", dialog.custom), Theme::input_active())
+            } else {
+                Span::raw(dialog.custom.as_str())
+            },
+        ]));
+        lines.push(Line::from(""));
+        let options = dialog.options();
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", tr("Command:")), Theme::label()),
+            Span::raw(if options.is_empty() {
+                format!("mount {} {}", dialog.device, dialog.mount_point)
+            } else {
+                format!("mount -o {} {} {}", options, dialog.device, dialog.mount_point)
+            }),
+        ]));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", trf("Mount {}", &[&dialog.device])));
+        frame.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+
+    fn render_io(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", trf("I/O per device (every {}s)", &[&IO_INTERVAL.as_secs().to_string()])));
+        if self.io_rates.is_empty() {
+            let waiting = Paragraph::new(Span::styled(
+                tr("Sampling /proc/diskstats..."),
+                Theme::muted(),
+            ))
+            .block(block);
+            frame.render_widget(waiting, area);
+            return;
+        }
+
+        // The busiest device is the likely bottleneck
+        let busiest = self
+            .io_rates
+            .iter()
+            .filter(|r| r.util >= 1.0)
+            .max_by(|a, b| a.util.total_cmp(&b.util))
+            .map(|r| r.device.as_str());
+        let per_sec = |bytes: f64| format!("{}/s", Self::format_size(bytes as u64));
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{:<12}{:>12}{:>12}{:>9}{:>9}  {}",
+                tr("Device"), tr("Read"), tr("Write"), "r/s", "w/s", "%util"
+            ),
+            Theme::title(),
+        ))];
+        for rate in &self.io_rates {
+            let width = 20;
+            let filled = (rate.util / 100.0 * width as f64).round() as usize;
+            let color = Self::usage_color(rate.util as u8);
+            let mut name_style = Style::default().add_modifier(Modifier::BOLD);
+            if busiest == Some(rate.device.as_str()) {
+                name_style = name_style.fg(Theme::colors().error);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<12}", rate.device), name_style),
+                Span::styled(
+                    format!("{:>12}{:>12}", per_sec(rate.read_bytes), per_sec(rate.write_bytes)),
+                    Theme::warning(),
+                ),
+                Span::raw(format!("{:>9.1}{:>9.1}  ", rate.reads, rate.writes)),
+                Span::styled(
+                    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled.min(width))),
+                    Style::default().fg(color),
+                ),
+                Span::styled(format!(" {:>5.1}%", rate.util), Style::default().fg(color)),
+            ]));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
 }
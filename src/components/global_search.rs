@@ -0,0 +1,167 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::{Component, SearchItem, Tab};
+use crate::app::Message;
+use crate::ui::centered_rect;
+use crate::ui::theme::Theme;
+use crate::utils::fuzzy;
+use crate::utils::i18n::tr;
+
+/// Matches shown at once; more letters narrow them down
+const MAX_MATCHES: usize = 200;
+
+/// Ctrl+F overlay that searches the packages, services, config files,
+/// cron jobs and logs of every tab at once
+pub struct GlobalSearch {
+    items: Vec<(Tab, SearchItem)>,
+    query: String,
+    selected: usize,
+}
+
+impl GlobalSearch {
+    pub fn new(items: Vec<(Tab, SearchItem)>) -> Self {
+        Self {
+            items,
+            query: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Items matching the query, best first; none before something is typed
+    fn matches(&self) -> Vec<&(Tab, SearchItem)> {
+        if self.query.trim().is_empty() {
+            return Vec::new();
+        }
+        let mut scored: Vec<(u32, &(Tab, SearchItem))> = self
+            .items
+            .iter()
+            .filter_map(|entry| fuzzy::score(&self.query, &entry.1.label).map(|s| (s, entry)))
+            .collect();
+        // Stable, so equal scores keep the tab order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.truncate(MAX_MATCHES);
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
+
+impl Component for GlobalSearch {
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        let count = self.matches().len();
+        match key.code {
+            KeyCode::Esc => return Some(Message::SearchClose),
+            KeyCode::Enter => {
+                return self
+                    .matches()
+                    .get(self.selected)
+                    .map(|(tab, item)| Message::SearchJump(*tab, item.id.clone()));
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < count => self.selected += 1,
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let dialog_area = centered_rect(70, 80, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let dialog = Block::default()
+            .title(format!(" {} ", tr("Search Everything")))
+            .borders(Borders::ALL)
+            .border_style(Theme::title());
+        let inner = dialog.inner(dialog_area);
+        frame.render_widget(dialog, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)])
+            .split(inner);
+
+        let input = Paragraph::new(Line::from(vec![
+            Span::styled("> ", Theme::key_hint()),
+            Span::raw(self.query.as_str()),
+            Span::styled("_", Theme::muted()),
+        ]))
+        .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(input, chunks[0]);
+
+        let matches = self.matches();
+        let items: Vec<ListItem> = if matches.is_empty() {
+            let hint = if self.query.trim().is_empty() {
+                tr("Packages, services, config files, cron jobs and logs")
+            } else {
+                tr("Nothing found")
+            };
+            vec![ListItem::new(Span::styled(hint, Theme::muted()))]
+        } else {
+            matches
+                .iter()
+                .map(|(tab, item)| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{:<10} ", tr(tab.title())), Theme::muted()),
+                        Span::raw(item.label.as_str()),
+                    ]))
+                })
+                .collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let selected = (!matches.is_empty()).then_some(self.selected);
+        let mut state = ListState::default().with_selected(selected);
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+    }
+
+    fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("↑/↓", "Select"), ("Enter", "Go to"), ("Esc", "Close")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(search: &mut GlobalSearch, code: KeyCode) -> Option<Message> {
+        search.handle_input(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_jump_to_match() {
+        let mut search = GlobalSearch::new(vec![
+            (Tab::Services, SearchItem::new("rc.sshd", "rc.sshd - OpenSSH daemon")),
+            (Tab::Logs, SearchItem::new("/var/log/messages", "/var/log/messages")),
+            (Tab::PackageBrowser, SearchItem::new("openssh-9.8p1-x86_64-1", "openssh 9.8p1")),
+        ]);
+        assert!(search.matches().is_empty());
+        assert!(press(&mut search, KeyCode::Enter).is_none());
+
+        for c in "ssh".chars() {
+            press(&mut search, KeyCode::Char(c));
+        }
+        assert_eq!(search.matches().len(), 2);
+        press(&mut search, KeyCode::Down);
+        press(&mut search, KeyCode::Down);
+        assert_eq!(search.selected, 1);
+        assert!(matches!(
+            press(&mut search, KeyCode::Enter),
+            Some(Message::SearchJump(_, id)) if id == search.matches()[1].1.id
+        ));
+        assert!(matches!(press(&mut search, KeyCode::Esc), Some(Message::SearchClose)));
+    }
+}
@@ -1,9 +1,9 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 use std::fs;
@@ -11,7 +11,18 @@ use std::path::Path;
 
 use crate::app::Message;
 use crate::components::Component;
+use crate::slackware::audit;
+use crate::slackware::changelog::{self, KernelChange};
+use crate::slackware::config::Transaction;
+use crate::slackware::efi::{self, BootEntry, BootManager, EntryPlan};
+use crate::slackware::grub::{self, MenuEntry};
+use crate::slackware::initrd::InitrdPlan;
+use crate::slackware::kconfig::{self, OptionChange};
+use crate::slackware::kernels::{self, MirrorPackage, ModulePackage};
+use crate::slackware::lilo::{self, LiloConf};
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::root;
 
 /// Kernel information
 #[derive(Debug, Clone)]
@@ -33,6 +44,72 @@ pub struct KernelComponent {
     status_message: Option<(String, bool)>,
     show_confirm: bool,
     pending_action: Option<KernelAction>,
+    view: KernelView,
+    /// Entries of grub.cfg and the one booted by default
+    grub_entries: Vec<MenuEntry>,
+    grub_default: Option<usize>,
+    grub_state: ListState,
+    /// initrd wizard: the kernel's image version and what will be built
+    initrd_plan: Option<(String, InitrdPlan)>,
+    /// Kernel packages on the active mirror, one row per kernel version
+    /// (or firmware release)
+    mirror_rows: Vec<(String, Vec<MirrorPackage>)>,
+    mirror_url: String,
+    mirror_state: ListState,
+    /// A kernel changed since lilo last ran or grub.cfg was regenerated
+    bootloader_stale: bool,
+    /// lilo.conf being edited, with changes not yet written
+    lilo_edit: LiloConf,
+    lilo_dirty: bool,
+    lilo_state: ListState,
+    lilo_prompt: Option<(LiloPrompt, String)>,
+    /// Config options differing between the running and selected kernel,
+    /// and the files they were read from
+    config_diff: Vec<OptionChange>,
+    config_sources: (String, String),
+    config_state: ListState,
+    /// Firmware boot entries, the numbers of those whose loader is gone,
+    /// and whether the boot order was changed but not written
+    efi: BootManager,
+    efi_stale: Vec<String>,
+    efi_state: ListState,
+    efi_order_dirty: bool,
+    /// ChangeLog.txt entries for kernels newer than the running one, once
+    /// fetched
+    changelog: Option<Vec<KernelChange>>,
+    changelog_scroll: u16,
+    /// Out-of-tree module packages once scanned, and the releases of the
+    /// installed kernels they should be built for
+    module_packages: Option<Vec<ModulePackage>>,
+    module_releases: Vec<String>,
+    modules_state: ListState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LiloPrompt {
+    Add,
+    Label,
+    Append,
+    Timeout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KernelView {
+    Kernels,
+    /// The generated GRUB menu
+    GrubMenu,
+    /// Kernel packages offered by the mirror
+    Mirror,
+    /// The image stanzas of lilo.conf
+    LiloEditor,
+    /// Config differences between the running and the selected kernel
+    ConfigDiff,
+    /// efibootmgr entries and boot order
+    Efi,
+    /// ChangeLog.txt excerpts for newer kernels
+    ChangeLog,
+    /// Third-party module packages and what they still need building for
+    Modules,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -47,6 +124,25 @@ pub enum KernelAction {
     SetDefault(String),
     RemoveKernel(String),
     RunLilo,
+    /// Make a grub.cfg entry (by index) the default
+    SetGrubDefault(usize),
+    RegenerateGrub,
+    /// Drop a removed kernel from the bootloader configuration
+    UpdateBootloader(String),
+    /// Install the packages of a mirror row (by index)
+    InstallFromMirror(usize),
+    /// Add boot entries for newly installed kernels (by image version)
+    AddBootEntries(Vec<String>),
+    /// Drop a lilo.conf stanza in the editor (by index)
+    RemoveStanza(usize),
+    /// Leave the lilo.conf editor without saving
+    DiscardLiloChanges,
+    CreateEfiEntry(EntryPlan),
+    /// Delete a Boot#### variable (by number)
+    DeleteEfiEntry(String),
+    WriteBootOrder,
+    /// Rebuild an SBo module package (by index) for a kernel release
+    RebuildModule(usize, String),
 }
 
 impl KernelComponent {
@@ -59,6 +155,31 @@ impl KernelComponent {
             status_message: None,
             show_confirm: false,
             pending_action: None,
+            view: KernelView::Kernels,
+            grub_entries: Vec::new(),
+            grub_default: None,
+            grub_state: ListState::default(),
+            initrd_plan: None,
+            mirror_rows: Vec::new(),
+            mirror_url: String::new(),
+            mirror_state: ListState::default(),
+            bootloader_stale: false,
+            lilo_edit: LiloConf::default(),
+            lilo_dirty: false,
+            lilo_state: ListState::default(),
+            lilo_prompt: None,
+            config_diff: Vec::new(),
+            config_sources: (String::new(), String::new()),
+            config_state: ListState::default(),
+            efi: BootManager::default(),
+            efi_stale: Vec::new(),
+            efi_state: ListState::default(),
+            efi_order_dirty: false,
+            changelog: None,
+            changelog_scroll: 0,
+            module_packages: None,
+            module_releases: Vec::new(),
+            modules_state: ListState::default(),
         };
         component.load_kernel_info();
         if !component.kernels.is_empty() {
@@ -77,6 +198,13 @@ impl KernelComponent {
 
         // Detect bootloader
         self.bootloader = Self::detect_bootloader();
+        if self.bootloader == BootloaderType::Grub {
+            self.grub_entries = grub::menu_entries();
+            self.grub_default = grub::default_entry(&self.grub_entries);
+            if self.grub_state.selected().is_none() && !self.grub_entries.is_empty() {
+                self.grub_state.select(Some(0));
+            }
+        }
 
         // Scan for installed kernels
         self.scan_kernels();
@@ -167,7 +295,12 @@ impl KernelComponent {
                 }
                 false
             }
-            _ => false,
+            BootloaderType::Grub => self
+                .grub_default
+                .and_then(|i| self.grub_entries.get(i))
+                .and_then(|e| e.kernel.as_deref())
+                .is_some_and(|k| Path::new(k).file_name() == Some(kernel_name.as_ref())),
+            BootloaderType::Unknown => false,
         }
     }
 
@@ -201,7 +334,8 @@ impl KernelComponent {
                     }
 
                     if found_label.is_empty() {
-                        self.status_message = Some(("Kernel not found in lilo.conf".to_string(), true));
+                        self.status_message =
+                            Some((tr("Kernel not found in lilo.conf").to_string(), true));
                         return None;
                     }
 
@@ -222,22 +356,40 @@ impl KernelComponent {
                         new_content = format!("default = {}\n{}", found_label, new_content);
                     }
 
-                    if let Err(e) = fs::write("/etc/lilo.conf", new_content) {
-                        self.status_message = Some((format!("Failed to update lilo.conf: {}", e), true));
+                    let mut transaction =
+                        Transaction::begin(format!("Set the default kernel to {}", found_label));
+                    if let Err(e) = transaction.write(Path::new("/etc/lilo.conf"), new_content) {
+                        self.status_message =
+                            Some((trf("Failed to update lilo.conf: {}", &[&e.to_string()]), true));
                         return None;
                     }
 
+                    self.bootloader_stale = true;
                     self.status_message = Some((
-                        format!("Default set to {}. Run lilo to apply!", found_label),
+                        trf("Default set to {}. Run lilo to apply!", &[&found_label]),
                         false,
                     ));
                 }
             }
             BootloaderType::Grub => {
-                self.status_message = Some(("GRUB configuration editing not yet supported".to_string(), true));
+                let image = format!("vmlinuz-{}", version);
+                let entry = self.grub_entries.iter().position(|e| {
+                    e.kernel
+                        .as_deref()
+                        .is_some_and(|k| Path::new(k).file_name() == Some(image.as_ref()))
+                });
+                match entry {
+                    Some(index) => return self.set_grub_default(index),
+                    None => {
+                        self.status_message = Some((
+                            trf("No grub.cfg entry boots {}; regenerate grub.cfg first", &[&image]),
+                            true,
+                        ));
+                    }
+                }
             }
             BootloaderType::Unknown => {
-                self.status_message = Some(("No known bootloader detected".to_string(), true));
+                self.status_message = Some((tr("No known bootloader detected").to_string(), true));
             }
         }
 
@@ -245,18 +397,511 @@ impl KernelComponent {
         None
     }
 
+    fn set_grub_default(&mut self, index: usize) -> Option<Message> {
+        let entry = self.grub_entries.get(index).cloned()?;
+        self.status_message = Some(match grub::set_default(&entry) {
+            Ok(true) => (
+                trf(
+                    "Default set to '{}' (GRUB_DEFAULT=saved, grub.cfg regenerated)",
+                    &[&entry.title],
+                ),
+                false,
+            ),
+            Ok(false) => (trf("Default set to '{}'", &[&entry.title]), false),
+            Err(e) => (trf("Failed to set GRUB default: {}", &[&e.to_string()]), true),
+        });
+        self.load_kernel_info();
+        None
+    }
+
+    fn regenerate_grub(&mut self) -> Option<Message> {
+        self.status_message = Some(match grub::regenerate() {
+            Ok(()) => {
+                self.bootloader_stale = false;
+                (trf("Regenerated {}", &[grub::GRUB_CFG]), false)
+            }
+            Err(e) => (trf("grub-mkconfig failed: {}", &[&e.to_string()]), true),
+        });
+        self.load_kernel_info();
+        None
+    }
+
+    fn remove_kernel(&mut self, version: &str) -> Option<Message> {
+        let installed: Vec<String> = self.kernels.iter().map(|k| k.version.clone()).collect();
+        match kernels::remove(version, &installed) {
+            Ok(removal) => {
+                self.status_message = Some((
+                    trf(
+                        "Removed {} package(s) and {} boot file(s)",
+                        &[&removal.packages.len().to_string(), &removal.files.len().to_string()],
+                    ),
+                    false,
+                ));
+                if self.bootloader != BootloaderType::Unknown {
+                    self.bootloader_stale = true;
+                    self.pending_action = Some(KernelAction::UpdateBootloader(version.to_string()));
+                    self.show_confirm = true;
+                }
+            }
+            Err(e) => self.status_message = Some((trf("Not removed: {}", &[&e.to_string()]), true)),
+        }
+        self.load_kernel_info();
+        None
+    }
+
+    fn update_bootloader(&mut self, version: &str) -> Option<Message> {
+        match self.bootloader {
+            BootloaderType::Lilo => {
+                let image = format!("vmlinuz-{}", version);
+                let removed = LiloConf::load().and_then(|mut conf| {
+                    let removed = conf.remove_stanzas(&image);
+                    if removed > 0 {
+                        conf.save()?;
+                    }
+                    Ok(removed)
+                });
+                if let Err(e) = removed {
+                    self.status_message =
+                        Some((trf("Failed to update lilo.conf: {}", &[&e.to_string()]), true));
+                    return None;
+                }
+                self.run_lilo()
+            }
+            BootloaderType::Grub => self.regenerate_grub(),
+            BootloaderType::Unknown => None,
+        }
+    }
+
+    fn load_mirror(&mut self) {
+        let (url, packages) = match kernels::available_from_mirror() {
+            Ok(found) => found,
+            Err(e) => {
+                self.status_message = Some((e.to_string(), true));
+                return;
+            }
+        };
+        self.mirror_url = url;
+        self.mirror_rows.clear();
+        for package in packages {
+            // Firmware is versioned by date and installed on its own
+            let key = if package.name == "kernel-firmware" {
+                format!("firmware {}", package.version)
+            } else {
+                package.version.clone()
+            };
+            match self.mirror_rows.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.push(package),
+                None => self.mirror_rows.push((key, vec![package])),
+            }
+        }
+        self.mirror_rows.reverse();
+        self.mirror_state.select(if self.mirror_rows.is_empty() { None } else { Some(0) });
+        self.view = KernelView::Mirror;
+        self.status_message = None;
+    }
+
+    fn install_from_mirror(&self, row: usize) -> Option<Message> {
+        let (_, packages) = self.mirror_rows.get(row)?;
+        let urls: Vec<String> = packages
+            .iter()
+            .filter(|p| !p.is_installed())
+            .map(|p| format!("{}{}", self.mirror_url, p.path))
+            .collect();
+        (!urls.is_empty()).then_some(Message::InstallKernelPackages(urls))
+    }
+
+    /// Called by the app once mirror packages were downloaded and installed
+    pub fn packages_installed(&mut self, result: std::result::Result<Vec<String>, String>) {
+        self.view = KernelView::Kernels;
+        self.load_kernel_info();
+        let installed = match result {
+            Ok(installed) => installed,
+            Err(e) => {
+                self.status_message =
+                    Some((trf("Installation failed: {}", &[&e.to_string()]), true));
+                return;
+            }
+        };
+        self.status_message = Some((trf("Installed {}", &[&installed.join(", ")]), false));
+        self.check_modules();
+
+        // kernel-generic-5.15.19-x86_64-2 installs /boot/vmlinuz-generic-5.15.19
+        let images: Vec<String> = installed
+            .iter()
+            .filter_map(|stem| {
+                let rest = stem.strip_prefix("kernel-")?;
+                let (variant, rest) = rest.split_once('-')?;
+                let version = rest.split('-').next()?;
+                matches!(variant, "generic" | "huge").then(|| format!("{}-{}", variant, version))
+            })
+            .collect();
+        if !images.is_empty() && self.bootloader != BootloaderType::Unknown {
+            self.bootloader_stale = true;
+            self.pending_action = Some(KernelAction::AddBootEntries(images));
+            self.show_confirm = true;
+        }
+    }
+
+    /// Give new kernels a boot entry next to the existing ones
+    fn add_boot_entries(&mut self, images: &[String]) -> Option<Message> {
+        if self.bootloader == BootloaderType::Grub {
+            return self.regenerate_grub();
+        }
+        let updated = LiloConf::load().and_then(|mut conf| {
+            let stanzas = conf.stanzas();
+            let template = stanzas.iter().find(|s| s.kind == "image").cloned();
+            let mut added = 0;
+            for version in images {
+                let image = format!("/boot/vmlinuz-{}", version);
+                let file = format!("vmlinuz-{}", version);
+                if Path::new(&image).exists() && !stanzas.iter().any(|s| s.image_file() == file) {
+                    conf.add_image(&image, &lilo::label_for(version), template.as_ref());
+                    added += 1;
+                }
+            }
+            if added > 0 {
+                conf.save()?;
+            }
+            Ok(added)
+        });
+        if let Err(e) = updated {
+            self.status_message =
+                Some((trf("Failed to update lilo.conf: {}", &[&e.to_string()]), true));
+            return None;
+        }
+        self.run_lilo()
+    }
+
+    /// Whether quitting now could leave the system unbootable
+    pub fn needs_bootloader_update(&self) -> bool {
+        self.bootloader_stale
+    }
+
+    /// Ask to run lilo or regenerate grub.cfg
+    pub fn prompt_bootloader_update(&mut self) {
+        self.pending_action = Some(match self.bootloader {
+            BootloaderType::Grub => KernelAction::RegenerateGrub,
+            _ => KernelAction::RunLilo,
+        });
+        self.show_confirm = true;
+    }
+
+    /// Compare the selected kernel's config against the running kernel's
+    fn open_config_diff(&mut self) {
+        let Some(kernel) = self.selected_kernel() else {
+            return;
+        };
+        let running = self
+            .kernels
+            .iter()
+            .find(|k| k.is_current)
+            .map(|k| k.version.clone());
+        let loaded = kconfig::load_running(running.as_deref())
+            .and_then(|old| kconfig::load(&kernel.version).map(|new| (old, new)));
+        match loaded {
+            Ok(((old_source, old), (new_source, new))) => {
+                self.config_diff = kconfig::diff(&old, &new);
+                self.config_sources = (old_source, new_source);
+                self.config_state
+                    .select(if self.config_diff.is_empty() { None } else { Some(0) });
+                self.view = KernelView::ConfigDiff;
+                self.status_message = None;
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+    }
+
+    /// Fetch the mirror's ChangeLog.txt entries for kernels newer than the
+    /// running one. Called by the app when an update brings a new kernel.
+    pub fn load_changelog(&mut self) {
+        match changelog::kernel_changes(&self.current_kernel) {
+            Ok(changes) => {
+                self.changelog = Some(changes);
+                self.changelog_scroll = 0;
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+    }
+
+    /// Look for module packages not built for every installed kernel.
+    /// Called by the app after an update changes the kernel.
+    pub fn check_modules(&mut self) {
+        let mut releases = kernels::installed_releases();
+        if !releases.contains(&self.current_kernel) && !self.current_kernel.is_empty() {
+            releases.push(self.current_kernel.clone());
+        }
+        self.module_releases = releases;
+        let packages = kernels::module_packages();
+        self.modules_state
+            .select(if packages.is_empty() { None } else { Some(0) });
+        self.module_packages = Some(packages);
+    }
+
+    /// Installed kernel releases a module package has no build for
+    fn missing_releases(&self, package: &ModulePackage) -> Vec<String> {
+        self.module_releases
+            .iter()
+            .filter(|r| !package.built_for.contains(r))
+            .cloned()
+            .collect()
+    }
+
+    fn modules_needing_rebuild(&self) -> usize {
+        self.module_packages
+            .iter()
+            .flatten()
+            .filter(|p| !self.missing_releases(p).is_empty())
+            .count()
+    }
+
+    /// Called by the app once sboupgrade finished
+    pub fn module_rebuilt(&mut self, name: &str, result: std::result::Result<(), String>) {
+        self.status_message = Some(match result {
+            Ok(()) => (trf("Rebuilt {}", &[name]), false),
+            Err(e) => (trf("Rebuilding {} failed: {}", &[name, &e.to_string()]), true),
+        });
+        self.check_modules();
+    }
+
+    fn load_efi(&mut self) {
+        match efi::list() {
+            Ok(manager) => {
+                self.efi_stale = manager
+                    .entries
+                    .iter()
+                    .filter(|e| efi::is_stale(e))
+                    .map(|e| e.number.clone())
+                    .collect();
+                self.efi = manager;
+                self.efi_order_dirty = false;
+                let count = self.efi.entries.len();
+                let selected = self.efi_state.selected().unwrap_or(0);
+                self.efi_state
+                    .select(count.checked_sub(1).map(|last| selected.min(last)));
+                self.view = KernelView::Efi;
+            }
+            Err(e) => {
+                self.status_message = Some((trf("efibootmgr failed: {}", &[&e.to_string()]), true))
+            }
+        }
+    }
+
+    /// Entries in boot order, followed by those not in BootOrder
+    fn efi_rows(&self) -> Vec<&BootEntry> {
+        let mut rows: Vec<&BootEntry> =
+            self.efi.order.iter().filter_map(|n| self.efi.entry(n)).collect();
+        rows.extend(self.efi.entries.iter().filter(|e| !self.efi.order.contains(&e.number)));
+        rows
+    }
+
+    /// Move the selected entry up (-1) or down (+1) in the boot order
+    fn move_efi_entry(&mut self, delta: isize) {
+        let Some(i) = self.efi_state.selected() else {
+            return;
+        };
+        let Some(target) = i.checked_add_signed(delta).filter(|t| *t < self.efi.order.len())
+        else {
+            return;
+        };
+        if i < self.efi.order.len() {
+            self.efi.order.swap(i, target);
+            self.efi_state.select(Some(target));
+            self.efi_order_dirty = true;
+        }
+    }
+
+    fn run_efi_action(&mut self, action: KernelAction) {
+        let (result, done) = match &action {
+            KernelAction::CreateEfiEntry(plan) => {
+                (plan.create(), trf("Created boot entry '{}'", &[&plan.label]))
+            }
+            KernelAction::DeleteEfiEntry(number) => {
+                (efi::delete(number), trf("Deleted Boot{}", &[&number.to_string()]))
+            }
+            _ => (efi::set_order(&self.efi.order), tr("Boot order written").to_string()),
+        };
+        self.status_message = Some(match result {
+            Ok(()) => (done, false),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_efi();
+    }
+
+    fn open_lilo_editor(&mut self) {
+        match LiloConf::load() {
+            Ok(conf) => {
+                self.lilo_state
+                    .select(if conf.stanzas().is_empty() { None } else { Some(0) });
+                self.lilo_edit = conf;
+                self.lilo_dirty = false;
+                self.view = KernelView::LiloEditor;
+                self.status_message = None;
+            }
+            Err(e) => {
+                self.status_message =
+                    Some((trf("Failed to read {}: {}", &[lilo::LILO_CONF, &e.to_string()]), true));
+            }
+        }
+    }
+
+    fn submit_lilo_prompt(&mut self, prompt: LiloPrompt, input: &str) {
+        let input = input.trim();
+        let selected = self.lilo_state.selected();
+        let result = match (prompt, selected) {
+            (LiloPrompt::Add, _) => self.add_stanza(input),
+            (LiloPrompt::Label, Some(i)) => {
+                self.lilo_edit.set_label(i, input).map_err(|e| e.to_string())
+            }
+            (LiloPrompt::Append, Some(i)) => {
+                self.lilo_edit.set_option(i, "append", Some(input).filter(|v| !v.is_empty()));
+                Ok(())
+            }
+            (LiloPrompt::Timeout, _) => match input {
+                "" => {
+                    self.lilo_edit.set_global("timeout", None);
+                    Ok(())
+                }
+                _ if input.parse::<u32>().is_ok() => {
+                    self.lilo_edit.set_global("timeout", Some(input));
+                    Ok(())
+                }
+                _ => Err(tr("Timeout is in tenths of a second, e.g. 50").to_string()),
+            },
+            _ => Ok(()),
+        };
+        match result {
+            Ok(()) => {
+                self.lilo_dirty = true;
+                self.status_message = None;
+            }
+            Err(e) => self.status_message = Some((e, true)),
+        }
+    }
+
+    /// Add an image stanza booting `image`, modelled on the first one
+    fn add_stanza(&mut self, image: &str) -> std::result::Result<(), String> {
+        if !Path::new(image).is_file() {
+            return Err(trf("{} does not exist", &[image]));
+        }
+        let stanzas = self.lilo_edit.stanzas();
+        let file = Path::new(image)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(image);
+        let label = lilo::label_for(file.strip_prefix("vmlinuz-").unwrap_or(file));
+        if stanzas.iter().any(|s| s.label.as_deref() == Some(label.as_str())) {
+            return Err(trf("Label '{}' is already used; rename that entry first", &[&label]));
+        }
+        let template = stanzas.iter().find(|s| s.kind == "image");
+        self.lilo_edit.add_image(image, &label, template);
+        self.lilo_state.select(Some(stanzas.len()));
+        Ok(())
+    }
+
+    /// Run `lilo -t` on the edited configuration and save it only if lilo
+    /// accepts it, then offer to install it for real
+    fn test_and_save_lilo(&mut self) {
+        if let Err(e) = self.lilo_edit.test() {
+            self.status_message = Some((trf("Not saved: {}", &[&e.to_string()]), true));
+            return;
+        }
+        match self.lilo_edit.save() {
+            Ok(_) => {
+                self.lilo_dirty = false;
+                self.bootloader_stale = true;
+                self.status_message =
+                    Some((tr("lilo -t passed, lilo.conf saved").to_string(), false));
+                self.pending_action = Some(KernelAction::RunLilo);
+                self.show_confirm = true;
+            }
+            Err(e) => {
+                self.status_message =
+                    Some((trf("Failed to write {}: {}", &[lilo::LILO_CONF, &e.to_string()]), true));
+            }
+        }
+    }
+
+    fn plan_initrd(&mut self) {
+        let Some(version) = self.selected_kernel().map(|k| k.version.clone()) else {
+            return;
+        };
+        match InitrdPlan::detect(&version) {
+            Ok(plan) => self.initrd_plan = Some((version, plan)),
+            Err(e) => {
+                self.status_message = Some((trf("Cannot plan initrd: {}", &[&e.to_string()]), true))
+            }
+        }
+    }
+
+    /// Build the planned initrd and point the kernel's boot entry at it
+    fn build_initrd(&mut self) {
+        let Some((version, plan)) = self.initrd_plan.take() else {
+            return;
+        };
+        if let Err(e) = plan.build() {
+            self.status_message = Some((e.to_string(), true));
+            return;
+        }
+        let image = format!("vmlinuz-{}", version);
+        self.status_message = Some(match self.bootloader {
+            BootloaderType::Lilo => {
+                let updated = LiloConf::load().and_then(|mut conf| {
+                    let found = conf.set_image_option(&image, "initrd", Some(&plan.output));
+                    if found {
+                        conf.save()?;
+                    }
+                    Ok(found)
+                });
+                match updated {
+                    Ok(true) => {
+                        self.bootloader_stale = true;
+                        (
+                            trf(
+                                "Built {} and added it to lilo.conf. Run lilo to apply!",
+                                &[&plan.output],
+                            ),
+                            false,
+                        )
+                    }
+                    Ok(false) => (
+                        trf("Built {}; lilo.conf has no entry for {}", &[&plan.output, &image]),
+                        true,
+                    ),
+                    Err(e) => (
+                        trf("Built {} but lilo.conf: {}", &[&plan.output, &e.to_string()]),
+                        true,
+                    ),
+                }
+            }
+            // grub-mkconfig picks up /boot/initrd-<version>.gz on its own
+            BootloaderType::Grub => match grub::regenerate() {
+                Ok(()) => (trf("Built {} and regenerated grub.cfg", &[&plan.output]), false),
+                Err(e) => (trf("Built {} but {}", &[&plan.output, &e.to_string()]), true),
+            },
+            BootloaderType::Unknown => (
+                trf("Built {}; add it to your bootloader by hand", &[&plan.output]),
+                false,
+            ),
+        });
+        self.load_kernel_info();
+    }
+
     fn run_lilo(&mut self) -> Option<Message> {
-        match std::process::Command::new("lilo").output() {
+        match audit::output(&mut root::command("lilo")) {
             Ok(output) => {
                 if output.status.success() {
-                    self.status_message = Some(("LILO updated successfully".to_string(), false));
+                    self.bootloader_stale = false;
+                    self.status_message =
+                        Some((tr("LILO updated successfully").to_string(), false));
                 } else {
                     let stderr = String::from_utf8_lossy(&output.stderr);
-                    self.status_message = Some((format!("LILO failed: {}", stderr), true));
+                    self.status_message =
+                        Some((trf("LILO failed: {}", &[stderr.as_ref()]), true));
                 }
             }
             Err(e) => {
-                self.status_message = Some((format!("Failed to run lilo: {}", e), true));
+                self.status_message =
+                    Some((trf("Failed to run lilo: {}", &[&e.to_string()]), true));
             }
         }
         None
@@ -277,11 +922,47 @@ impl Component for KernelComponent {
                     if let Some(action) = self.pending_action.take() {
                         return match action {
                             KernelAction::SetDefault(version) => self.set_default_kernel(&version),
-                            KernelAction::RemoveKernel(_) => {
-                                self.status_message = Some(("Kernel removal not implemented for safety".to_string(), true));
+                            KernelAction::RemoveKernel(version) => self.remove_kernel(&version),
+                            KernelAction::UpdateBootloader(version) => {
+                                self.update_bootloader(&version)
+                            }
+                            KernelAction::InstallFromMirror(row) => {
+                                self.status_message =
+                                    Some((tr("Downloading kernel packages...").to_string(), false));
+                                self.install_from_mirror(row)
+                            }
+                            KernelAction::AddBootEntries(images) => self.add_boot_entries(&images),
+                            KernelAction::RemoveStanza(index) => {
+                                if self.lilo_edit.remove_stanza(index) {
+                                    self.lilo_dirty = true;
+                                    let last = self.lilo_edit.stanzas().len().checked_sub(1);
+                                    self.lilo_state.select(last.map(|last| index.min(last)));
+                                }
+                                None
+                            }
+                            KernelAction::RebuildModule(index, release) => {
+                                let package = self.module_packages.as_ref()?.get(index)?;
+                                let name = package.name().to_string();
+                                self.status_message = Some((
+                                    trf("Rebuilding {} for {}...", &[&name, &release]),
+                                    false,
+                                ));
+                                Some(Message::RebuildKernelModule(name, release))
+                            }
+                            action @ (KernelAction::CreateEfiEntry(_)
+                            | KernelAction::DeleteEfiEntry(_)
+                            | KernelAction::WriteBootOrder) => {
+                                self.run_efi_action(action);
+                                None
+                            }
+                            KernelAction::DiscardLiloChanges => {
+                                self.lilo_dirty = false;
+                                self.view = KernelView::Kernels;
                                 None
                             }
                             KernelAction::RunLilo => self.run_lilo(),
+                            KernelAction::SetGrubDefault(index) => self.set_grub_default(index),
+                            KernelAction::RegenerateGrub => self.regenerate_grub(),
                         };
                     }
                 }
@@ -294,6 +975,296 @@ impl Component for KernelComponent {
             return None;
         }
 
+        if self.initrd_plan.is_some() {
+            match key.code {
+                KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => self.build_initrd(),
+                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => self.initrd_plan = None,
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some((prompt, input)) = &mut self.lilo_prompt {
+            match key.code {
+                KeyCode::Enter => {
+                    let (prompt, input) = (*prompt, input.clone());
+                    self.lilo_prompt = None;
+                    self.submit_lilo_prompt(prompt, &input);
+                }
+                KeyCode::Esc => self.lilo_prompt = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.view == KernelView::Modules {
+            let selected = self.modules_state.selected();
+            let count = self.module_packages.as_ref().map_or(0, Vec::len);
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('o') => self.view = KernelView::Kernels,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    if let Some(i) = selected.filter(|i| *i > 0) {
+                        self.modules_state.select(Some(i - 1));
+                    }
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if let Some(i) = selected.filter(|i| i + 1 < count) {
+                        self.modules_state.select(Some(i + 1));
+                    }
+                }
+                KeyCode::Enter | KeyCode::Char('r') => {
+                    let package = selected
+                        .and_then(|i| self.module_packages.as_ref()?.get(i).map(|p| (i, p)));
+                    if let Some((i, package)) = package {
+                        let missing = self.missing_releases(package);
+                        if !package.is_sbo() {
+                            self.status_message = Some((
+                                trf("{} is not from SBo; rebuild it by hand", &[package.name()]),
+                                true,
+                            ));
+                        } else if let Some(release) = missing.into_iter().next() {
+                            self.pending_action = Some(KernelAction::RebuildModule(i, release));
+                            self.show_confirm = true;
+                        } else {
+                            self.status_message =
+                                Some((tr("Already built for every kernel").to_string(), false));
+                        }
+                    }
+                }
+                KeyCode::F(5) => self.check_modules(),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.view == KernelView::ChangeLog {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('n') => self.view = KernelView::Kernels,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.changelog_scroll = self.changelog_scroll.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.changelog_scroll = self.changelog_scroll.saturating_add(1);
+                }
+                KeyCode::PageUp => self.changelog_scroll = self.changelog_scroll.saturating_sub(20),
+                KeyCode::PageDown => {
+                    self.changelog_scroll = self.changelog_scroll.saturating_add(20);
+                }
+                KeyCode::F(5) => self.load_changelog(),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.view == KernelView::Efi {
+            let selected = self.efi_state.selected();
+            let entry = selected.and_then(|i| self.efi_rows().get(i).map(|e| e.number.clone()));
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('b') => {
+                    if self.efi_order_dirty {
+                        self.status_message =
+                            Some((tr("Boot order change discarded").to_string(), false));
+                    }
+                    self.efi_order_dirty = false;
+                    self.view = KernelView::Kernels;
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    if let Some(i) = selected.filter(|i| *i > 0) {
+                        self.efi_state.select(Some(i - 1));
+                    }
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if let Some(i) = selected.filter(|i| i + 1 < self.efi.entries.len()) {
+                        self.efi_state.select(Some(i + 1));
+                    }
+                }
+                KeyCode::Char('K') | KeyCode::Char('-') => self.move_efi_entry(-1),
+                KeyCode::Char('J') | KeyCode::Char('+') => self.move_efi_entry(1),
+                KeyCode::Char('w') if self.efi_order_dirty => {
+                    self.pending_action = Some(KernelAction::WriteBootOrder);
+                    self.show_confirm = true;
+                }
+                KeyCode::Char('a') | KeyCode::Char('l') => {
+                    let plan = if key.code == KeyCode::Char('l') {
+                        EntryPlan::for_elilo()
+                    } else {
+                        match self.selected_kernel() {
+                            Some(kernel) => EntryPlan::for_kernel(&kernel.version),
+                            None => return None,
+                        }
+                    };
+                    match plan {
+                        Ok(plan) => {
+                            self.pending_action = Some(KernelAction::CreateEfiEntry(plan));
+                            self.show_confirm = true;
+                        }
+                        Err(e) => self.status_message = Some((e.to_string(), true)),
+                    }
+                }
+                KeyCode::Char('x') | KeyCode::Delete => {
+                    if let Some(number) = entry {
+                        if self.efi.current.as_deref() == Some(number.as_str()) {
+                            self.status_message = Some((
+                                tr("That entry booted the running system").to_string(),
+                                true,
+                            ));
+                        } else {
+                            self.pending_action = Some(KernelAction::DeleteEfiEntry(number));
+                            self.show_confirm = true;
+                        }
+                    }
+                }
+                KeyCode::F(5) => self.load_efi(),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.view == KernelView::ConfigDiff {
+            let selected = self.config_state.selected();
+            let last = self.config_diff.len().saturating_sub(1);
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('c') => self.view = KernelView::Kernels,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.config_state.select(selected.map(|i| i.saturating_sub(1)));
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.config_state.select(selected.map(|i| (i + 1).min(last)));
+                }
+                KeyCode::PageUp => {
+                    self.config_state.select(selected.map(|i| i.saturating_sub(20)));
+                }
+                KeyCode::PageDown => {
+                    self.config_state.select(selected.map(|i| (i + 20).min(last)));
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.view == KernelView::LiloEditor {
+            let selected = self.lilo_state.selected();
+            let stanza = selected.and_then(|i| self.lilo_edit.stanzas().into_iter().nth(i));
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('e') => {
+                    if self.lilo_dirty {
+                        self.pending_action = Some(KernelAction::DiscardLiloChanges);
+                        self.show_confirm = true;
+                    } else {
+                        self.view = KernelView::Kernels;
+                    }
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    if let Some(i) = selected.filter(|i| *i > 0) {
+                        self.lilo_state.select(Some(i - 1));
+                    }
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    let count = self.lilo_edit.stanzas().len();
+                    if let Some(i) = selected.filter(|i| i + 1 < count) {
+                        self.lilo_state.select(Some(i + 1));
+                    }
+                }
+                KeyCode::Char('a') => {
+                    self.lilo_prompt = Some((LiloPrompt::Add, "/boot/vmlinuz-".to_string()));
+                }
+                KeyCode::Char('r') => {
+                    if let Some(stanza) = stanza {
+                        self.lilo_prompt =
+                            Some((LiloPrompt::Label, stanza.label.unwrap_or_default()));
+                    }
+                }
+                KeyCode::Char('p') => {
+                    if let Some(stanza) = stanza {
+                        let current = stanza.option("append").unwrap_or_default().to_string();
+                        self.lilo_prompt = Some((LiloPrompt::Append, current));
+                    }
+                }
+                KeyCode::Char('t') => {
+                    let current = self.lilo_edit.global("timeout").unwrap_or_default();
+                    self.lilo_prompt = Some((LiloPrompt::Timeout, current));
+                }
+                KeyCode::Char('x') | KeyCode::Delete => {
+                    if let Some(i) = selected {
+                        self.pending_action = Some(KernelAction::RemoveStanza(i));
+                        self.show_confirm = true;
+                    }
+                }
+                KeyCode::Char('w') => self.test_and_save_lilo(),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.view == KernelView::Mirror {
+            let selected = self.mirror_state.selected();
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('m') => self.view = KernelView::Kernels,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    if let Some(i) = selected.filter(|i| *i > 0) {
+                        self.mirror_state.select(Some(i - 1));
+                    }
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if let Some(i) = selected.filter(|i| i + 1 < self.mirror_rows.len()) {
+                        self.mirror_state.select(Some(i + 1));
+                    }
+                }
+                KeyCode::Enter => {
+                    let row = selected.and_then(|i| self.mirror_rows.get(i).map(|r| (i, r)));
+                    if let Some((i, (_, packages))) = row {
+                        if packages.iter().all(|p| p.is_installed()) {
+                            self.status_message =
+                                Some((tr("Already installed").to_string(), false));
+                        } else {
+                            self.pending_action = Some(KernelAction::InstallFromMirror(i));
+                            self.show_confirm = true;
+                        }
+                    }
+                }
+                KeyCode::F(5) => self.load_mirror(),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.view == KernelView::GrubMenu {
+            let selected = self.grub_state.selected();
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('g') => self.view = KernelView::Kernels,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    if let Some(i) = selected.filter(|i| *i > 0) {
+                        self.grub_state.select(Some(i - 1));
+                    }
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if let Some(i) = selected.filter(|i| i + 1 < self.grub_entries.len()) {
+                        self.grub_state.select(Some(i + 1));
+                    }
+                }
+                KeyCode::Enter | KeyCode::Char('d') => {
+                    if let Some(i) = selected {
+                        self.pending_action = Some(KernelAction::SetGrubDefault(i));
+                        self.show_confirm = true;
+                    }
+                }
+                KeyCode::Char('G') => {
+                    self.pending_action = Some(KernelAction::RegenerateGrub);
+                    self.show_confirm = true;
+                }
+                KeyCode::F(5) => {
+                    self.load_kernel_info();
+                    self.status_message = Some((tr("GRUB menu reloaded").to_string(), false));
+                }
+                _ => {}
+            }
+            return None;
+        }
+
         match key.code {
             KeyCode::Up | KeyCode::Char('k') => {
                 if let Some(selected) = self.list_state.selected() {
@@ -315,15 +1286,46 @@ impl Component for KernelComponent {
                     self.show_confirm = true;
                 }
             }
-            KeyCode::Char('l') => {
-                if self.bootloader == BootloaderType::Lilo {
-                    self.pending_action = Some(KernelAction::RunLilo);
+            KeyCode::Char('l') if self.bootloader == BootloaderType::Lilo => {
+                self.pending_action = Some(KernelAction::RunLilo);
+                self.show_confirm = true;
+            }
+            KeyCode::Char('e') if self.bootloader == BootloaderType::Lilo => {
+                self.open_lilo_editor();
+            }
+            KeyCode::Char('i') => self.plan_initrd(),
+            KeyCode::Char('c') => self.open_config_diff(),
+            KeyCode::Char('o') => {
+                self.check_modules();
+                self.view = KernelView::Modules;
+                self.status_message = None;
+            }
+            KeyCode::Char('n') => {
+                if self.changelog.is_none() {
+                    self.load_changelog();
+                }
+                if self.changelog.is_some() {
+                    self.view = KernelView::ChangeLog;
+                }
+            }
+            KeyCode::Char('b') if efi::is_efi() => {
+                self.status_message = None;
+                self.load_efi();
+            }
+            KeyCode::Char('m') => self.load_mirror(),
+            KeyCode::Char('x') => {
+                if let Some(kernel) = self.selected_kernel() {
+                    self.pending_action = Some(KernelAction::RemoveKernel(kernel.version.clone()));
                     self.show_confirm = true;
                 }
             }
+            KeyCode::Char('g') if self.bootloader == BootloaderType::Grub => {
+                self.view = KernelView::GrubMenu;
+                self.status_message = None;
+            }
             KeyCode::F(5) => {
                 self.load_kernel_info();
-                self.status_message = Some(("Kernel list refreshed".to_string(), false));
+                self.status_message = Some((tr("Kernel list refreshed").to_string(), false));
             }
             _ => {}
         }
@@ -334,7 +1336,7 @@ impl Component for KernelComponent {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(5),
+                Constraint::Length(6),
                 Constraint::Min(10),
                 Constraint::Length(3),
             ])
@@ -344,28 +1346,30 @@ impl Component for KernelComponent {
         let bootloader_str = match self.bootloader {
             BootloaderType::Lilo => "LILO",
             BootloaderType::Grub => "GRUB",
-            BootloaderType::Unknown => "Unknown",
+            BootloaderType::Unknown => tr("Unknown"),
         };
 
         let info = Paragraph::new(vec![
             Line::from(vec![
-                Span::styled("Running Kernel: ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<15} ", tr("Running Kernel:")), Theme::label()),
                 Span::styled(
                     &self.current_kernel,
                     Style::default()
-                        .fg(Color::Green)
+                        .fg(Theme::colors().success)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Bootloader:     ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:<15} ", tr("Bootloader:")), Theme::label()),
                 Span::raw(bootloader_str),
             ]),
+            self.changelog_summary(),
+            self.modules_summary(),
         ])
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Kernel Manager "),
+                .title(format!(" {} ", tr("Kernel Manager"))),
         );
         frame.render_widget(info, chunks[0]);
 
@@ -378,14 +1382,14 @@ impl Component for KernelComponent {
 
                 if kernel.is_current {
                     status_parts.push(Span::styled(
-                        " [RUNNING]",
-                        Style::default().fg(Color::Green),
+                        format!(" [{}]", tr("RUNNING")),
+                        Theme::success(),
                     ));
                 }
                 if kernel.is_default {
                     status_parts.push(Span::styled(
-                        " [DEFAULT]",
-                        Style::default().fg(Color::Yellow),
+                        format!(" [{}]", tr("DEFAULT")),
+                        Theme::warning(),
                     ));
                 }
 
@@ -397,72 +1401,666 @@ impl Component for KernelComponent {
                         ),
                         Span::styled(
                             format!("{:<10}", kernel.variant),
-                            Style::default().fg(Color::Cyan),
+                            Theme::label(),
                         ),
                     ]
                     .into_iter()
                     .chain(status_parts)
                     .collect::<Vec<_>>()),
                     Line::from(vec![
-                        Span::styled("    Path: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(format!("    {} ", tr("Path:")), Theme::muted()),
                         Span::raw(&kernel.path),
-                        Span::styled("  Size: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(format!("  {} ", tr("Size:")), Theme::muted()),
                         Span::raw(Self::format_size(kernel.size)),
                     ]),
                 ])
             })
             .collect();
 
+        let title = trf("Installed Kernels ({})", &[&self.kernels.len().to_string()]);
         let list = List::new(items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(format!(" Installed Kernels ({}) ", self.kernels.len())),
-            )
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", title)))
             .highlight_style(Theme::list_selected())
             .highlight_symbol("▶ ");
 
-        let mut state = self.list_state.clone();
-        frame.render_stateful_widget(list, chunks[1], &mut state);
+        if self.view == KernelView::GrubMenu {
+            self.render_grub_menu(frame, chunks[1]);
+        } else if self.view == KernelView::Mirror {
+            self.render_mirror(frame, chunks[1]);
+        } else if self.view == KernelView::LiloEditor {
+            self.render_lilo_editor(frame, chunks[1]);
+        } else if self.view == KernelView::ConfigDiff {
+            self.render_config_diff(frame, chunks[1]);
+        } else if self.view == KernelView::Efi {
+            self.render_efi(frame, chunks[1]);
+        } else if self.view == KernelView::ChangeLog {
+            self.render_changelog(frame, chunks[1]);
+        } else if self.view == KernelView::Modules {
+            self.render_modules(frame, chunks[1]);
+        } else {
+            let mut state = self.list_state.clone();
+            frame.render_stateful_widget(list, chunks[1], &mut state);
+        }
 
         // Status bar
         let status_content = if self.show_confirm {
             let action_desc = match &self.pending_action {
-                Some(KernelAction::SetDefault(v)) => format!("Set {} as default?", v),
-                Some(KernelAction::RemoveKernel(v)) => format!("Remove kernel {}?", v),
-                Some(KernelAction::RunLilo) => "Run lilo to update bootloader?".to_string(),
-                None => "Confirm action?".to_string(),
+                Some(KernelAction::SetDefault(v)) => trf("Set {} as default?", &[v]),
+                Some(KernelAction::RemoveKernel(v)) => {
+                    trf("Remove kernel {} with its packages and boot files?", &[v])
+                }
+                Some(KernelAction::InstallFromMirror(i)) => trf(
+                    "Download and install {} alongside the current kernel?",
+                    &[self.mirror_rows.get(*i).map(|(k, _)| k.as_str()).unwrap_or("?")],
+                ),
+                Some(KernelAction::AddBootEntries(v)) => match self.bootloader {
+                    BootloaderType::Grub => {
+                        tr("Regenerate grub.cfg for the new kernel?").to_string()
+                    }
+                    _ => trf("Add {} to lilo.conf and run lilo?", &[&v.join(", ")]),
+                },
+                Some(KernelAction::UpdateBootloader(v)) => match self.bootloader {
+                    BootloaderType::Grub => trf("Regenerate grub.cfg without {}?", &[v]),
+                    _ => trf("Remove {} from lilo.conf and run lilo?", &[v]),
+                },
+                Some(KernelAction::RemoveStanza(i)) => trf(
+                    "Remove the '{}' entry?",
+                    &[
+                        &self.lilo_edit
+                            .stanzas()
+                            .get(*i)
+                            .and_then(|s| s.label.clone())
+                            .unwrap_or_else(|| "?".to_string()),
+                    ],
+                ),
+                Some(KernelAction::DiscardLiloChanges) => {
+                    tr("Discard unsaved lilo.conf changes?").to_string()
+                }
+                Some(KernelAction::CreateEfiEntry(plan)) => {
+                    trf("Run '{}'?", &[&plan.command().join(" ")])
+                }
+                Some(KernelAction::DeleteEfiEntry(number)) => trf(
+                    "Delete Boot{} ({})?",
+                    &[
+                        &number.to_string(),
+                        self.efi.entry(number).map(|e| e.label.as_str()).unwrap_or("?"),
+                    ],
+                ),
+                Some(KernelAction::WriteBootOrder) => {
+                    trf("Set the boot order to {}?", &[&self.efi.order.join(",")])
+                }
+                Some(KernelAction::RebuildModule(i, release)) => trf(
+                    "Rebuild {} for {} with sboupgrade?",
+                    &[
+                        self.module_packages
+                            .as_ref()
+                            .and_then(|p| p.get(*i))
+                            .map(|p| p.name())
+                            .unwrap_or("?"),
+                        release,
+                    ],
+                ),
+                Some(KernelAction::RunLilo) => tr("Run lilo to update bootloader?").to_string(),
+                Some(KernelAction::SetGrubDefault(i)) => trf(
+                    "Boot '{}' by default?",
+                    &[self.grub_entries.get(*i).map(|e| e.title.as_str()).unwrap_or("?")],
+                ),
+                Some(KernelAction::RegenerateGrub) => {
+                    trf("Regenerate {} with grub-mkconfig?", &[grub::GRUB_CFG])
+                }
+                None => tr("Confirm action?").to_string(),
+            };
+            Line::from(vec![
+                Span::styled(action_desc, Theme::warning()),
+                Span::raw(format!(" {}", tr("[Y]es / [N]o"))),
+            ])
+        } else if let Some((prompt, input)) = &self.lilo_prompt {
+            let label = match prompt {
+                LiloPrompt::Add => tr("Kernel image:"),
+                LiloPrompt::Label => tr("Label:"),
+                LiloPrompt::Append => tr("Kernel parameters (empty to remove):"),
+                LiloPrompt::Timeout => tr("Timeout in tenths of a second (empty to remove):"),
             };
             Line::from(vec![
-                Span::styled(action_desc, Style::default().fg(Color::Yellow)),
-                Span::raw(" [Y]es / [N]o"),
+                Span::styled(format!("{} ", label), Theme::warning()),
+                Span::raw(format!("{}_", input)),
             ])
         } else if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
             ))
-        } else {
+        } else if self.view == KernelView::LiloEditor {
             Line::from(Span::styled(
-                "Press 'd' to set default, 'l' to run lilo",
-                Style::default().fg(Color::DarkGray),
+                tr("Changes are checked with 'lilo -t' when saved with 'w'"),
+                Theme::muted(),
             ))
+        } else if self.view == KernelView::Efi {
+            let hint = if self.efi_order_dirty {
+                "Boot order changed; press 'w' to write it"
+            } else {
+                "'a' adds an EFI stub entry for the kernel selected in the kernel list"
+            };
+            Line::from(Span::styled(tr(hint), Theme::muted()))
+        } else if self.view == KernelView::ConfigDiff {
+            Line::from(vec![
+                Span::styled(format!("+ {}  ", tr("enabled")), Theme::success()),
+                Span::styled(format!("- {}  ", tr("dropped")), Theme::error()),
+                Span::styled(format!("~ {}", tr("changed")), Theme::warning()),
+            ])
+        } else {
+            let hint = match self.bootloader {
+                BootloaderType::Grub => "Press 'd' to set default, 'g' for the GRUB menu",
+                _ => "Press 'd' to set default, 'e' to edit lilo.conf, 'l' to run lilo",
+            };
+            Line::from(Span::styled(tr(hint), Theme::muted()))
         };
 
         let status = Paragraph::new(status_content)
             .block(Block::default().borders(Borders::ALL));
         frame.render_widget(status, chunks[2]);
+
+        if let Some((version, plan)) = &self.initrd_plan {
+            self.render_initrd_plan(frame, area, version, plan);
+        }
+    }
+
+    fn is_typing(&self) -> bool {
+        self.lilo_prompt.is_some()
     }
 
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        if self.lilo_prompt.is_some() {
+            return vec![("Enter", "Apply"), ("Esc", "Cancel")];
+        }
+        if self.view == KernelView::ConfigDiff {
+            return vec![("↑/↓", "Scroll"), ("PgUp/PgDn", "Page"), ("c/Esc", "Back")];
+        }
+        if self.view == KernelView::Modules {
+            return vec![("r/Enter", "Rebuild (SBo)"), ("o/Esc", "Back"), ("F5", "Rescan")];
+        }
+        if self.view == KernelView::ChangeLog {
+            return vec![
+                ("↑/↓", "Scroll"),
+                ("PgUp/PgDn", "Page"),
+                ("n/Esc", "Back"),
+                ("F5", "Refetch"),
+            ];
+        }
+        if self.view == KernelView::Efi {
+            return vec![
+                ("K/J", "Move"),
+                ("w", "Write Order"),
+                ("a", "Add Kernel"),
+                ("l", "Add elilo"),
+                ("x", "Delete"),
+                ("b/Esc", "Back"),
+                ("F5", "Reload"),
+            ];
+        }
+        if self.view == KernelView::LiloEditor {
+            return vec![
+                ("a", "Add"),
+                ("x", "Remove"),
+                ("r", "Rename"),
+                ("p", "Parameters"),
+                ("t", "Timeout"),
+                ("w", "Test & Save"),
+                ("e/Esc", "Back"),
+            ];
+        }
+        if self.view == KernelView::Mirror {
+            return vec![
+                ("Enter", "Install"),
+                ("m/Esc", "Back"),
+                ("F5", "Reload"),
+            ];
+        }
+        if self.view == KernelView::GrubMenu {
+            return vec![
+                ("d/Enter", "Set Default"),
+                ("G", "Regenerate grub.cfg"),
+                ("g/Esc", "Back"),
+                ("F5", "Reload"),
+            ];
+        }
+        if self.initrd_plan.is_some() {
+            return vec![("Enter", "Build initrd"), ("Esc", "Cancel")];
+        }
         vec![
             ("d/Enter", "Set Default"),
+            ("i", "Build initrd"),
+            ("c", "Config Diff"),
+            ("n", "ChangeLog"),
+            ("o", "Modules"),
+            ("x", "Remove"),
+            ("m", "From Mirror"),
             ("l", "Run LILO"),
+            ("e", "Edit lilo.conf"),
+            ("g", "GRUB Menu"),
+            ("b", "EFI Boot"),
             ("F5", "Refresh"),
         ]
     }
 
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        if self.lilo_prompt.is_some() || self.initrd_plan.is_some() {
+            return vec!["Enter"];
+        }
+        match self.view {
+            KernelView::Modules => vec!["r/Enter"],
+            KernelView::Efi => vec!["w", "a", "l", "x"],
+            KernelView::LiloEditor => vec!["w"],
+            KernelView::Mirror => vec!["Enter"],
+            KernelView::GrubMenu => vec!["d/Enter", "G"],
+            KernelView::Kernels => vec!["d/Enter", "i", "x", "l"],
+            KernelView::ConfigDiff | KernelView::ChangeLog => Vec::new(),
+        }
+    }
+
     fn on_activate(&mut self) {
         self.load_kernel_info();
     }
 }
+
+impl KernelComponent {
+    fn render_initrd_plan(&self, frame: &mut Frame, area: Rect, version: &str, plan: &InitrdPlan) {
+        let popup = crate::ui::centered_rect(70, 60, area);
+        frame.render_widget(Clear, popup);
+
+        let label = |text| Span::styled(format!("{:<12} ", tr(text)), Theme::label());
+        let yes_no = |on: bool| tr(if on { "yes" } else { "no" });
+        let lines = vec![
+            Line::from(vec![label("Kernel:"), Span::raw(version.to_string())]),
+            Line::from(vec![
+                label("Modules dir:"),
+                Span::raw(format!("/lib/modules/{}", plan.kernel)),
+            ]),
+            Line::from(vec![
+                label("Root:"),
+                Span::raw(format!("{} ({})", plan.root_device, plan.root_fs)),
+            ]),
+            Line::from(vec![
+                label("LUKS:"),
+                Span::raw(plan.luks_device.clone().unwrap_or_else(|| tr("no").to_string())),
+            ]),
+            Line::from(vec![label("LVM:"), Span::raw(yes_no(plan.lvm))]),
+            Line::from(vec![label("Modules:"), Span::raw(plan.modules.join(" "))]),
+            Line::from(vec![label("Output:"), Span::raw(plan.output.clone())]),
+            Line::from(""),
+            Line::from(Span::styled(plan.command().join(" "), Theme::warning())),
+            Line::from(""),
+            Line::from(Span::styled(
+                tr("Enter builds the initrd and updates the boot entry, Esc cancels"),
+                Theme::muted(),
+            )),
+        ];
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", tr("Build initrd")))
+                .border_style(Theme::warning()),
+        );
+        frame.render_widget(dialog, popup);
+    }
+
+    fn render_mirror(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .mirror_rows
+            .iter()
+            .map(|(key, packages)| {
+                let installed = packages.iter().all(|p| p.is_installed());
+                let names: Vec<&str> = packages
+                    .iter()
+                    .map(|p| p.name.trim_start_matches("kernel-"))
+                    .collect();
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{:<28}", key),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:<30}", names.join(" ")),
+                        Theme::label(),
+                    ),
+                ];
+                if installed {
+                    spans.push(Span::styled(format!("[{}]", tr("INSTALLED")), Theme::success()));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", trf("Kernels on {}", &[&self.mirror_url]))),
+            )
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.mirror_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// Header line announcing newer kernels found in the ChangeLog
+    fn changelog_summary(&self) -> Line<'static> {
+        let Some(changes) = self.changelog.as_ref().filter(|c| !c.is_empty()) else {
+            return Line::from("");
+        };
+        let newest = changes[0].versions.join(", ");
+        let mut spans = vec![
+            Span::styled(format!("{:<15} ", tr("Newer kernel:")), Theme::label()),
+            Span::raw(newest),
+        ];
+        if changes.iter().any(|c| c.is_security_fix()) {
+            spans.push(Span::styled(
+                format!(" ({})", tr("security fix")),
+                Theme::error().add_modifier(Modifier::BOLD),
+            ));
+        }
+        spans.push(Span::styled(
+            format!("  - {}", tr("press 'n' for the ChangeLog")),
+            Theme::muted(),
+        ));
+        Line::from(spans)
+    }
+
+    /// Header line reminding of module packages to rebuild
+    fn modules_summary(&self) -> Line<'static> {
+        match self.modules_needing_rebuild() {
+            0 => Line::from(""),
+            count => Line::from(vec![
+                Span::styled(format!("{:<15} ", tr("Modules:")), Theme::label()),
+                Span::styled(
+                    trf("{} out-of-tree module package(s) need rebuilding", &[&count.to_string()]),
+                    Theme::warning(),
+                ),
+                Span::styled(format!("  - {}", tr("press 'o'")), Theme::muted()),
+            ]),
+        }
+    }
+
+    fn render_modules(&self, frame: &mut Frame, area: Rect) {
+        let packages = self.module_packages.as_deref().unwrap_or_default();
+        let items: Vec<ListItem> = packages
+            .iter()
+            .map(|package| {
+                let missing = self.missing_releases(package);
+                let (check, color) = if missing.is_empty() {
+                    ("[x]", Theme::colors().success)
+                } else {
+                    ("[ ]", Theme::colors().warning)
+                };
+                let mut spans = vec![
+                    Span::styled(format!("{} ", check), Style::default().fg(color)),
+                    Span::styled(
+                        format!("{:<24}", package.name()),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:<30}",
+                            trf("built for {}", &[&package.built_for.join(", ")])
+                        ),
+                        Theme::muted(),
+                    ),
+                ];
+                if !missing.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" {}", trf("needs {}", &[&missing.join(", ")])),
+                        Theme::warning(),
+                    ));
+                }
+                if package.is_sbo() {
+                    spans.push(Span::styled(" [SBo]", Theme::label()));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let title = trf("Out-of-tree Modules ({})", &[&packages.len().to_string()]);
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", title)))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.modules_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_changelog(&self, frame: &mut Frame, area: Rect) {
+        let changes = self.changelog.as_deref().unwrap_or_default();
+        let mut lines = Vec::new();
+        if changes.is_empty() {
+            lines.push(Line::from(Span::styled(
+                trf("The mirror has no kernel newer than {}", &[&self.current_kernel]),
+                Theme::muted(),
+            )));
+        }
+        for change in changes {
+            lines.push(Line::from(Span::styled(
+                change.date.clone(),
+                Theme::title(),
+            )));
+            for line in &change.lines {
+                let style = if line.contains("Security fix") {
+                    Theme::error().add_modifier(Modifier::BOLD)
+                } else if line.contains("CVE-") {
+                    Theme::warning()
+                } else {
+                    Style::default()
+                };
+                lines.push(Line::from(Span::styled(line.clone(), style)));
+            }
+            lines.push(Line::from(""));
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(
+                        " {} ",
+                        trf("Kernel ChangeLog since {}", &[&self.current_kernel])
+                    )),
+            )
+            .scroll((self.changelog_scroll, 0));
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_efi(&self, frame: &mut Frame, area: Rect) {
+        let order_len = self.efi.order.len();
+        let items: Vec<ListItem> = self
+            .efi_rows()
+            .into_iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let position = if i < order_len {
+                    format!("{:>2}.", i + 1)
+                } else {
+                    "   ".to_string()
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{} Boot{} ", position, entry.number),
+                        Theme::muted(),
+                    ),
+                    Span::styled(
+                        format!("{:<30}", entry.label),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                ];
+                if let Some(loader) = &entry.loader {
+                    spans.push(Span::styled(loader.clone(), Theme::label()));
+                }
+                if self.efi.current.as_deref() == Some(entry.number.as_str()) {
+                    spans.push(Span::styled(format!(" [{}]", tr("CURRENT")), Theme::success()));
+                }
+                if !entry.active {
+                    spans.push(Span::styled(format!(" [{}]", tr("INACTIVE")), Theme::muted()));
+                }
+                if self.efi_stale.contains(&entry.number) {
+                    spans.push(Span::styled(format!(" [{}]", tr("STALE")), Theme::error()));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let title = trf("EFI Boot Entries ({})", &[&self.efi.entries.len().to_string()]);
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", title)))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.efi_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_config_diff(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .config_diff
+            .iter()
+            .map(|change| {
+                let option = |value: &str| format!("CONFIG_{}={}", change.name, value);
+                let (marker, color, text) = match (&change.old, &change.new) {
+                    (None, Some(new)) => ("+", Theme::colors().success, option(new)),
+                    (Some(old), None) => ("-", Theme::colors().error, option(old)),
+                    (old, new) => (
+                        "~",
+                        Theme::colors().warning,
+                        format!(
+                            "CONFIG_{}: {} -> {}",
+                            change.name,
+                            old.as_deref().unwrap_or(""),
+                            new.as_deref().unwrap_or("")
+                        ),
+                    ),
+                };
+                ListItem::new(Line::from(Span::styled(
+                    format!("{} {}", marker, text),
+                    Style::default().fg(color),
+                )))
+            })
+            .collect();
+
+        let title = if self.config_diff.is_empty() {
+            trf(
+                "{} and {} are identical",
+                &[&self.config_sources.0, &self.config_sources.1],
+            )
+        } else {
+            trf(
+                "{} -> {} ({} changes)",
+                &[
+                    &self.config_sources.0,
+                    &self.config_sources.1,
+                    &self.config_diff.len().to_string(),
+                ],
+            )
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", title)))
+            .highlight_style(Theme::list_selected());
+        let mut state = self.config_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_lilo_editor(&self, frame: &mut Frame, area: Rect) {
+        let stanzas = self.lilo_edit.stanzas();
+        // Without `default =`, lilo boots the first entry
+        let default = self
+            .lilo_edit
+            .global("default")
+            .or_else(|| stanzas.first().and_then(|s| s.label.clone()));
+        let items: Vec<ListItem> = stanzas
+            .iter()
+            .map(|stanza| {
+                let label = stanza.label.clone().unwrap_or_else(|| tr("(no label)").to_string());
+                let mut first = vec![
+                    Span::styled(
+                        format!("{:<16}", label),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{} = {}", stanza.kind, stanza.image),
+                        Theme::label(),
+                    ),
+                ];
+                if stanza.label.is_some() && stanza.label == default {
+                    first.push(Span::styled(format!(" [{}]", tr("DEFAULT")), Theme::warning()));
+                }
+                let details: Vec<String> = ["root", "initrd", "append"]
+                    .iter()
+                    .filter_map(|key| stanza.option(key).map(|v| format!("{}={}", key, v)))
+                    .collect();
+                ListItem::new(vec![
+                    Line::from(first),
+                    Line::from(Span::styled(
+                        format!("    {}", details.join("  ")),
+                        Theme::muted(),
+                    )),
+                ])
+            })
+            .collect();
+
+        let timeout = match self.lilo_edit.global("timeout") {
+            Some(t) => t
+                .parse::<u32>()
+                .map(|t| format!("{:.1}s", t as f64 / 10.0))
+                .unwrap_or(t),
+            None => tr("none").to_string(),
+        };
+        let modified = if self.lilo_dirty {
+            format!(" [{}]", tr("modified"))
+        } else {
+            String::new()
+        };
+        let title = format!(
+            " {}{} - {} ",
+            lilo::LILO_CONF,
+            modified,
+            trf("timeout {}", &[&timeout])
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.lilo_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_grub_menu(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .grub_entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let indent = "  ".repeat(entry.submenus.len());
+                let mut spans = vec![Span::styled(
+                    format!("{}{}", indent, entry.title),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )];
+                if Some(i) == self.grub_default {
+                    spans.push(Span::styled(format!(" [{}]", tr("DEFAULT")), Theme::warning()));
+                }
+                if let Some(kernel) = &entry.kernel {
+                    spans.push(Span::styled(
+                        format!("  {}", kernel),
+                        Theme::muted(),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", trf("GRUB Menu ({})", &[grub::GRUB_CFG]))),
+            )
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.grub_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+}
@@ -6,13 +6,23 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
+use regex::{Regex, RegexBuilder};
+use std::collections::BTreeSet;
+use std::fs;
 use std::path::{Path, PathBuf};
 
+use tokio::sync::mpsc;
+
 use crate::app::Message;
-use crate::components::Component;
+use crate::components::settings::{AppSettings, HighlightRule};
+use crate::components::{Component, SearchItem, ViewState};
+use crate::slackware::audit::{self, AUDIT_LOG};
+use crate::slackware::logrotate::{self, Directives, RotationPolicy, Setting};
+use crate::ui::clipboard;
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::logfile;
+use crate::utils::root;
 
 /// Log file information
 #[derive(Debug, Clone)]
@@ -21,6 +31,19 @@ pub struct LogFile {
     pub path: PathBuf,
     pub size: u64,
     pub modified: String,
+    pub kind: LogKind,
+}
+
+/// Where a log's lines come from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogKind {
+    File,
+    /// The kernel ring buffer, read with dmesg
+    KernelRing,
+    /// Binary wtmp/btmp/lastlog records, read with last, lastb or lastlog
+    LoginRecords,
+    /// Commands this program ran, from the audit log
+    Audit,
 }
 
 /// Log Viewer Component
@@ -29,19 +52,118 @@ pub struct LogViewerComponent {
     file_list_state: ListState,
     mode: LogViewMode,
     log_content: Vec<String>,
+    /// Indices into log_content of the lines passing every filter
+    visible_lines: Vec<usize>,
+    /// Position within visible_lines of the top line shown
     content_scroll: usize,
     search_query: String,
     is_searching: bool,
+    /// Positions within visible_lines of the lines matching the search
     search_results: Vec<usize>,
     current_search_idx: usize,
+    /// Compiled search query; a literal pattern when it isn't valid regex
+    search_regex: Option<Regex>,
+    case_sensitive: bool,
     follow_mode: bool,
     status_message: Option<(String, bool)>,
+    /// Active filters; a line is shown only if it passes all of them
+    filters: Vec<LogFilter>,
+    is_filtering: bool,
+    filter_input: String,
+    /// Destination being typed for an export, while prompting
+    export_path: Option<String>,
+    /// Bookmarked lines, as indices into log_content
+    bookmarks: BTreeSet<usize>,
+    /// Show the log's rotated copies in front of the live file
+    merge_rotations: bool,
+    /// Number of rotated files merged into the current view
+    merged_rotations: usize,
+    /// Logs marked in the file list for the merged timeline
+    marked: Vec<PathBuf>,
+    /// Logs shown interleaved in the current view, if it is a timeline
+    timeline: Vec<PathBuf>,
+    /// Lines appended to the open log, while following it
+    follow_rx: Option<mpsc::UnboundedReceiver<String>>,
+    /// Global logrotate defaults and the per-log policies
+    rotation_defaults: Directives,
+    rotation: Vec<RotationPolicy>,
+    rotation_state: ListState,
+    /// Setting being edited in the rotation view, with its input so far
+    rotation_prompt: Option<(RotationPrompt, String)>,
+    /// Lines read per page, from settings.log_lines
+    buffer_lines: usize,
+    /// The file shown and the offset where its loaded part starts, when only
+    /// the end of a plain log was read
+    window: Option<(PathBuf, u64)>,
+    /// User highlight rules from settings, compiled
+    highlights: Vec<(Regex, Color)>,
+    /// Rule being typed as "pattern=color", while prompting
+    highlight_input: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RotationPrompt {
+    Rotate,
+    Size,
+}
+
+/// A condition lines must meet to stay visible
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogFilter {
+    /// Only lines that look like errors
+    Errors,
+    /// Only lines that look like warnings or errors
+    Warnings,
+    /// Lines containing the text (case-insensitive)
+    Include(String),
+    /// Lines not containing the text
+    Exclude(String),
+}
+
+impl LogFilter {
+    /// Parse filter input: "!text" excludes, anything else includes
+    fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        match input.strip_prefix('!') {
+            Some(text) if !text.trim().is_empty() => {
+                Some(LogFilter::Exclude(text.trim().to_lowercase()))
+            }
+            Some(_) => None,
+            None if input.is_empty() => None,
+            None => Some(LogFilter::Include(input.to_lowercase())),
+        }
+    }
+
+    fn matches(&self, line: &str) -> bool {
+        let lower = line.to_lowercase();
+        match self {
+            LogFilter::Errors => LogViewerComponent::get_log_level_color(line) == Theme::colors().error,
+            LogFilter::Warnings => {
+                let colors = Theme::colors();
+                let color = LogViewerComponent::get_log_level_color(line);
+                color == colors.error || color == colors.warning
+            }
+            LogFilter::Include(text) => lower.contains(text),
+            LogFilter::Exclude(text) => !lower.contains(text),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            LogFilter::Errors => tr("errors").to_string(),
+            LogFilter::Warnings => tr("warnings+").to_string(),
+            LogFilter::Include(text) => format!("\"{}\"", text),
+            LogFilter::Exclude(text) => format!("!\"{}\"", text),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LogViewMode {
     FileList,
     ViewLog,
+    /// logrotate policies of the system's logs
+    Rotation,
 }
 
 const LOG_DIRS: &[&str] = &["/var/log"];
@@ -68,14 +190,36 @@ impl LogViewerComponent {
             file_list_state: ListState::default(),
             mode: LogViewMode::FileList,
             log_content: Vec::new(),
+            visible_lines: Vec::new(),
             content_scroll: 0,
             search_query: String::new(),
             is_searching: false,
             search_results: Vec::new(),
             current_search_idx: 0,
+            search_regex: None,
+            case_sensitive: false,
             follow_mode: false,
             status_message: None,
+            filters: Vec::new(),
+            is_filtering: false,
+            filter_input: String::new(),
+            export_path: None,
+            bookmarks: BTreeSet::new(),
+            merge_rotations: false,
+            merged_rotations: 0,
+            marked: Vec::new(),
+            timeline: Vec::new(),
+            follow_rx: None,
+            rotation_defaults: Directives::default(),
+            rotation: Vec::new(),
+            rotation_state: ListState::default(),
+            rotation_prompt: None,
+            buffer_lines: 0,
+            window: None,
+            highlights: Vec::new(),
+            highlight_input: None,
         };
+        component.load_settings();
         component.load_log_files();
         if !component.log_files.is_empty() {
             component.file_list_state.select(Some(0));
@@ -86,8 +230,9 @@ impl LogViewerComponent {
     fn load_log_files(&mut self) {
         self.log_files.clear();
 
+        let escalate = root::can_escalate();
         for log_dir in LOG_DIRS {
-            self.scan_directory(Path::new(log_dir), 0);
+            self.scan_directory(Path::new(log_dir), 0, escalate);
         }
 
         // Sort by importance and name
@@ -101,9 +246,30 @@ impl LogViewerComponent {
                 _ => a.name.cmp(&b.name),
             }
         });
+
+        // /var/log/dmesg only holds the boot messages; the live buffer goes first
+        self.log_files.insert(
+            0,
+            LogFile {
+                name: "dmesg (kernel ring buffer)".to_string(),
+                path: PathBuf::from("/dev/kmsg"),
+                size: 0,
+                modified: "live".to_string(),
+                kind: LogKind::KernelRing,
+            },
+        );
+        // The audit log is listed once, read as commands rather than raw lines
+        if let Some(i) = self.log_files.iter().position(|l| l.path == Path::new(AUDIT_LOG)) {
+            let mut log = self.log_files.remove(i);
+            log.name = tr("Audit log (commands run)").to_string();
+            log.kind = LogKind::Audit;
+            self.log_files.insert(1, log);
+        }
     }
 
-    fn scan_directory(&mut self, path: &Path, depth: usize) {
+    /// Add the logs under `path`. Without `escalate`, only those this user
+    /// can read themselves.
+    fn scan_directory(&mut self, path: &Path, depth: usize, escalate: bool) {
         if depth > 2 {
             return;
         }
@@ -118,16 +284,17 @@ impl LogViewerComponent {
                     if name.starts_with('.') || name == "journal" {
                         continue;
                     }
-                    self.scan_directory(&entry_path, depth + 1);
+                    self.scan_directory(&entry_path, depth + 1, escalate);
                 } else if entry_path.is_file() {
                     let name = entry.file_name().to_string_lossy().to_string();
 
-                    // Skip compressed logs and certain files
-                    if name.ends_with(".gz")
-                        || name.ends_with(".xz")
-                        || name.ends_with(".old")
-                        || name.starts_with('.')
-                    {
+                    // Skip backups and hidden files
+                    if name.ends_with(".old") || name.starts_with('.') {
+                        continue;
+                    }
+                    // Root-only logs are read through sudo or doas, so they
+                    // are left out when that cannot run
+                    if !escalate && fs::File::open(&entry_path).is_err() {
                         continue;
                     }
 
@@ -135,11 +302,11 @@ impl LogViewerComponent {
                         let modified = metadata
                             .modified()
                             .ok()
-                            .and_then(|t| {
+                            .map(|t| {
                                 let datetime: chrono::DateTime<chrono::Local> = t.into();
-                                Some(datetime.format("%Y-%m-%d %H:%M").to_string())
+                                datetime.format("%Y-%m-%d %H:%M").to_string()
                             })
-                            .unwrap_or_else(|| "Unknown".to_string());
+                            .unwrap_or_else(|| tr("Unknown").to_string());
 
                         // Create display name with relative path
                         let display_name = entry_path
@@ -149,8 +316,12 @@ impl LogViewerComponent {
 
                         self.log_files.push(LogFile {
                             name: display_name,
-                            path: entry_path,
                             size: metadata.len(),
+                            kind: match logfile::login_records_command(&entry_path) {
+                                Some(_) => LogKind::LoginRecords,
+                                None => LogKind::File,
+                            },
+                            path: entry_path,
                             modified,
                         });
                     }
@@ -159,47 +330,416 @@ impl LogViewerComponent {
         }
     }
 
+    fn open_log(&mut self, log: &LogFile) {
+        self.window = None;
+        match log.kind {
+            LogKind::File => self.load_log_content(&log.path),
+            LogKind::KernelRing => {
+                self.merged_rotations = 0;
+                self.set_content(logfile::command_lines("dmesg", &["-x"]));
+            }
+            LogKind::LoginRecords => {
+                self.merged_rotations = 0;
+                let Some((cmd, args)) = logfile::login_records_command(&log.path) else {
+                    return;
+                };
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                self.set_content(logfile::command_lines(cmd, &args));
+            }
+            LogKind::Audit => {
+                self.merged_rotations = 0;
+                self.set_content(audit::read_display_lines());
+            }
+        }
+    }
+
     fn load_log_content(&mut self, path: &Path) {
+        // Rotated files come first so the merged view reads oldest to newest
+        let mut sources = if self.merge_rotations {
+            logfile::rotations(path)
+        } else {
+            Vec::new()
+        };
+        self.merged_rotations = sources.len();
+        if sources.is_empty() && !logfile::is_compressed(path) {
+            // Only the end of the file is read; earlier pages load on demand
+            let read = logfile::tail_lines(path, self.buffer_lines, u64::MAX);
+            let read = read.map(|(lines, start)| {
+                self.window = Some((path.to_path_buf(), start));
+                lines
+            });
+            self.set_content(read);
+            return;
+        }
+        sources.push(path.to_path_buf());
+
+        let read = sources
+            .iter()
+            .try_fold(Vec::new(), |mut acc: Vec<String>, p| {
+                acc.extend(logfile::read_lines(p)?);
+                Ok::<_, std::io::Error>(acc)
+            });
+        self.set_content(read);
+    }
+
+    fn load_settings(&mut self) {
+        let settings = AppSettings::load();
+        self.buffer_lines = settings.log_lines;
+        // Rules that no longer compile are skipped rather than rejected
+        self.highlights = settings
+            .log_highlights
+            .iter()
+            .filter_map(|rule| Some((Regex::new(&rule.pattern).ok()?, rule.color.parse().ok()?)))
+            .collect();
+    }
+
+    /// Add, replace or (with an empty color) remove a highlight rule given
+    /// as "pattern=color", and save it to the settings
+    fn submit_highlight(&mut self, input: &str) {
+        let Some((pattern, color)) = input.rsplit_once('=') else {
+            self.status_message = Some((tr("Expected 'pattern=color'").to_string(), true));
+            return;
+        };
+        let (pattern, color) = (pattern.trim(), color.trim());
+        if let Err(e) = Regex::new(pattern) {
+            self.status_message = Some((trf("Invalid pattern: {}", &[&e.to_string()]), true));
+            return;
+        }
+        if !color.is_empty() && color.parse::<Color>().is_err() {
+            self.status_message = Some((trf("Unknown color '{}'", &[color]), true));
+            return;
+        }
+
+        let mut settings = AppSettings::load();
+        settings.log_highlights.retain(|r| r.pattern != pattern);
+        if !color.is_empty() {
+            settings.log_highlights.push(HighlightRule {
+                pattern: pattern.to_string(),
+                color: color.to_string(),
+            });
+        }
+        self.status_message = Some(match settings.save() {
+            Ok(_) if color.is_empty() => (trf("Removed highlight for {}", &[pattern]), false),
+            Ok(_) => (trf("Highlighting {} in {}", &[pattern, color]), false),
+            Err(e) => (trf("Failed to save settings: {}", &[&e.to_string()]), true),
+        });
+        self.load_settings();
+    }
+
+    /// Prepend the page of lines before the loaded part of the log
+    fn load_more(&mut self) {
+        let Some((path, start)) = self.window.clone() else {
+            self.status_message = Some((tr("The whole log is loaded").to_string(), false));
+            return;
+        };
+        if start == 0 {
+            self.status_message = Some((tr("Already at the start of the log").to_string(), false));
+            return;
+        }
+        let (lines, start) = match logfile::tail_lines(&path, self.buffer_lines, start) {
+            Ok(page) => page,
+            Err(e) => {
+                self.status_message =
+                    Some((trf("Error reading file: {}", &[&e.to_string()]), true));
+                return;
+            }
+        };
+        self.window = Some((path, start));
+
+        // Keep the line at the top of the screen in place
+        let added = lines.len();
+        let top = self.visible_lines.get(self.content_scroll).map(|i| i + added);
+        self.log_content.splice(0..0, lines);
+        self.bookmarks = self.bookmarks.iter().map(|b| b + added).collect();
+        self.apply_filters();
+        if let Some(top) = top {
+            self.content_scroll = self.visible_lines.partition_point(|&i| i < top);
+        }
+        self.status_message =
+            Some((trf("Loaded {} earlier line(s)", &[&added.to_string()]), false));
+    }
+
+    /// Read the marked logs and interleave them by timestamp
+    fn load_timeline(&mut self) {
+        self.window = None;
+        let sources: std::io::Result<Vec<(String, Vec<String>)>> = self
+            .timeline
+            .iter()
+            .map(|path| {
+                let tag = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                Ok((tag, logfile::read_lines(path)?))
+            })
+            .collect();
+        self.set_content(sources.map(|s| logfile::merge_timeline(&s)));
+    }
+
+    fn set_content(&mut self, read: std::io::Result<Vec<String>>) {
         self.log_content.clear();
+        self.bookmarks.clear();
+        self.visible_lines.clear();
         self.content_scroll = 0;
         self.search_results.clear();
 
-        // Read last N lines (tail behavior)
-        const MAX_LINES: usize = 1000;
-
-        match File::open(path) {
-            Ok(file) => {
-                let reader = BufReader::new(file);
-                let mut lines: Vec<String> = reader
-                    .lines()
-                    .filter_map(|l| l.ok())
-                    .collect();
-
-                // Keep only last MAX_LINES
-                if lines.len() > MAX_LINES {
-                    lines = lines.split_off(lines.len() - MAX_LINES);
+        match read {
+            Ok(mut lines) => {
+                // Keep only the last page of lines
+                if lines.len() > self.buffer_lines {
+                    lines = lines.split_off(lines.len() - self.buffer_lines);
                 }
 
                 self.log_content = lines;
-
-                // Scroll to end if in follow mode
-                if self.follow_mode && !self.log_content.is_empty() {
-                    self.content_scroll = self.log_content.len().saturating_sub(1);
-                }
             }
             Err(e) => {
-                self.log_content = vec![format!("Error reading file: {}", e)];
+                self.log_content = vec![trf("Error reading file: {}", &[&e.to_string()])];
             }
         }
+        self.apply_filters();
+
+        // Scroll to end if in follow mode
+        if self.follow_mode {
+            self.content_scroll = self.visible_lines.len().saturating_sub(1);
+        }
+    }
+
+    /// Recompute which lines pass the filters, keeping the top line in
+    /// view when it is still visible
+    fn apply_filters(&mut self) {
+        let top = self.visible_lines.get(self.content_scroll).copied();
+        self.visible_lines = (0..self.log_content.len())
+            .filter(|&i| self.filters.iter().all(|f| f.matches(&self.log_content[i])))
+            .collect();
+        self.content_scroll = top
+            .map(|top| self.visible_lines.partition_point(|&i| i < top))
+            .unwrap_or(0)
+            .min(self.visible_lines.len().saturating_sub(1));
+        self.find_matches();
+    }
+
+    /// Lines an export covers: the search hits when searching, otherwise
+    /// everything the filters let through
+    fn export_lines(&self) -> Vec<&str> {
+        let indices: Vec<usize> = if self.search_regex.is_some() {
+            self.search_results
+                .iter()
+                .map(|&pos| self.visible_lines[pos])
+                .collect()
+        } else {
+            self.visible_lines.clone()
+        };
+        indices
+            .into_iter()
+            .map(|i| self.log_content[i].as_str())
+            .collect()
+    }
+
+    fn default_export_path(&self) -> String {
+        let name = if self.timeline.is_empty() {
+            self.selected_log()
+                .and_then(|l| l.path.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "log".to_string())
+        } else {
+            "timeline".to_string()
+        };
+        format!(
+            "/tmp/{}-{}.txt",
+            name,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        )
+    }
+
+    fn export_to_file(&mut self, path: &str) {
+        let lines = self.export_lines();
+        let count = lines.len();
+        let content = lines.join("\n") + "\n";
+        self.status_message = Some(match fs::write(path, content) {
+            Ok(()) => (trf("Wrote {} line(s) to {}", &[&count.to_string(), path]), false),
+            Err(e) => (trf("Failed to write {}: {}", &[path, &e.to_string()]), true),
+        });
+    }
+
+    fn copy_to_clipboard(&mut self) {
+        let lines = self.export_lines();
+        let count = lines.len();
+        self.status_message = Some(match clipboard::copy(&lines.join("\n")) {
+            Ok(()) => (trf("Copied {} line(s) to the clipboard", &[&count.to_string()]), false),
+            Err(e) => (trf("{} - export to a file with 'w' instead", &[&e.to_string()]), true),
+        });
+    }
+
+    /// Bookmark the top line in view, or remove its bookmark
+    fn toggle_bookmark(&mut self) {
+        let Some(&line) = self.visible_lines.get(self.content_scroll) else {
+            return;
+        };
+        if !self.bookmarks.remove(&line) {
+            self.bookmarks.insert(line);
+        }
+    }
+
+    /// Scroll to the next (or previous) bookmark, wrapping around
+    fn jump_bookmark(&mut self, forward: bool) {
+        let current = self.visible_lines.get(self.content_scroll).copied().unwrap_or(0);
+        let target = if forward {
+            self.bookmarks
+                .range(current + 1..)
+                .next()
+                .or_else(|| self.bookmarks.iter().next())
+        } else {
+            self.bookmarks
+                .range(..current)
+                .next_back()
+                .or_else(|| self.bookmarks.iter().next_back())
+        };
+        let Some(&target) = target else {
+            self.status_message =
+                Some((tr("No bookmarks - press 'm' to add one").to_string(), false));
+            return;
+        };
+        // A bookmark hidden by a filter lands on the next visible line
+        self.content_scroll = self
+            .visible_lines
+            .partition_point(|&i| i < target)
+            .min(self.visible_lines.len().saturating_sub(1));
+        self.set_follow(false);
+    }
+
+    fn hidden_lines(&self) -> usize {
+        self.log_content.len() - self.visible_lines.len()
+    }
+
+    /// Cycle the level filter: none, errors only, warnings and errors
+    fn cycle_level_filter(&mut self) {
+        let current = self
+            .filters
+            .iter()
+            .position(|f| matches!(f, LogFilter::Errors | LogFilter::Warnings));
+        let next = match current.map(|i| self.filters.remove(i)) {
+            None => Some(LogFilter::Errors),
+            Some(LogFilter::Errors) => Some(LogFilter::Warnings),
+            _ => None,
+        };
+        self.filters.extend(next);
+        self.apply_filters();
     }
 
     fn refresh_log(&mut self) {
-        if let Some(selected) = self.file_list_state.selected() {
-            if let Some(log) = self.log_files.get(selected) {
-                let path = log.path.clone();
-                self.load_log_content(&path);
+        if !self.timeline.is_empty() {
+            self.load_timeline();
+        } else if let Some(log) = self.selected_log().cloned() {
+            self.open_log(&log);
+        }
+    }
+
+    /// Start or stop tailing the open log in the background
+    fn set_follow(&mut self, follow: bool) {
+        self.follow_mode = follow;
+        self.follow_rx = None;
+        if !follow {
+            return;
+        }
+        if !self.timeline.is_empty() {
+            self.follow_mode = false;
+            self.status_message =
+                Some((tr("Follow is not available in the timeline").to_string(), true));
+            return;
+        }
+        self.refresh_log();
+        let Some(log) = self.selected_log().cloned() else {
+            return;
+        };
+        match log.kind {
+            LogKind::KernelRing => {
+                self.follow_rx = Some(logfile::follow_command("dmesg", &["-x", "-W"]));
+                return;
             }
+            LogKind::LoginRecords => {
+                self.follow_mode = false;
+                self.status_message = Some((
+                    tr("Login records are binary; press F5 to re-read them").to_string(),
+                    true,
+                ));
+                return;
+            }
+            LogKind::Audit => {
+                self.follow_mode = false;
+                self.status_message =
+                    Some((tr("Press F5 to re-read the audit log").to_string(), true));
+                return;
+            }
+            LogKind::File => {}
+        }
+        let path = log.path;
+        if logfile::is_compressed(&path) {
+            self.follow_mode = false;
+            self.status_message = Some((tr("Rotated logs do not change").to_string(), true));
+            return;
         }
+        let offset = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        self.follow_rx = Some(logfile::follow(&path, offset));
+    }
+
+    fn load_rotation(&mut self) {
+        let (defaults, policies) = logrotate::load();
+        self.rotation_defaults = defaults;
+        self.rotation = policies;
+        let selected = self.rotation_state.selected().unwrap_or(0);
+        self.rotation_state.select(if self.rotation.is_empty() {
+            None
+        } else {
+            Some(selected.min(self.rotation.len() - 1))
+        });
+    }
+
+    fn apply_rotation(&mut self, setting: Setting) {
+        let Some(policy) = self.rotation_state.selected().and_then(|i| self.rotation.get(i)) else {
+            return;
+        };
+        self.status_message = Some(match logrotate::apply(policy, &setting) {
+            Ok(backup) => (
+                trf(
+                    "Updated {} (backup: {})",
+                    &[&policy.source.display().to_string(), &backup.display().to_string()],
+                ),
+                false,
+            ),
+            Err(e) => (
+                trf(
+                    "Failed to update {}: {}",
+                    &[&policy.source.display().to_string(), &e.to_string()],
+                ),
+                true,
+            ),
+        });
+        self.load_rotation();
+    }
+
+    fn submit_rotation_prompt(&mut self, prompt: RotationPrompt, input: &str) {
+        let input = input.trim();
+        let setting = match prompt {
+            RotationPrompt::Rotate => match input.parse() {
+                Ok(count) => Setting::Rotate(count),
+                Err(_) => {
+                    self.status_message =
+                        Some((tr("Rotate count must be a whole number").to_string(), true));
+                    return;
+                }
+            },
+            RotationPrompt::Size if input.is_empty() => Setting::Size(None),
+            RotationPrompt::Size => {
+                let digits = input.trim_end_matches(['k', 'K', 'M', 'G']);
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                    self.status_message =
+                        Some((tr("Size must look like 100k, 10M or 1G").to_string(), true));
+                    return;
+                }
+                Setting::Size(Some(input.to_string()))
+            }
+        };
+        self.apply_rotation(setting);
     }
 
     fn selected_log(&self) -> Option<&LogFile> {
@@ -208,20 +748,50 @@ impl LogViewerComponent {
             .and_then(|i| self.log_files.get(i))
     }
 
-    fn perform_search(&mut self) {
-        self.search_results.clear();
-        self.current_search_idx = 0;
-
+    /// Compile the search query as a regex, falling back to a literal
+    /// match when it doesn't parse (so "[error" still finds something)
+    fn compile_search(&mut self) {
+        self.search_regex = None;
         if self.search_query.is_empty() {
             return;
         }
+        let build = |pattern: &str| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(!self.case_sensitive)
+                .build()
+        };
+        self.search_regex = match build(&self.search_query) {
+            Ok(regex) => Some(regex),
+            Err(_) => {
+                self.status_message = Some((
+                    tr("Not a valid regex, searching for the literal text").to_string(),
+                    false,
+                ));
+                build(&regex::escape(&self.search_query)).ok()
+            }
+        };
+    }
 
-        let query = self.search_query.to_lowercase();
-        for (i, line) in self.log_content.iter().enumerate() {
-            if line.to_lowercase().contains(&query) {
-                self.search_results.push(i);
+    /// Recompute the lines matching the search query
+    fn find_matches(&mut self) {
+        self.search_results.clear();
+        let Some(regex) = &self.search_regex else {
+            return;
+        };
+        for (pos, &i) in self.visible_lines.iter().enumerate() {
+            if regex.is_match(&self.log_content[i]) {
+                self.search_results.push(pos);
             }
         }
+        self.current_search_idx = self
+            .current_search_idx
+            .min(self.search_results.len().saturating_sub(1));
+    }
+
+    fn perform_search(&mut self) {
+        self.current_search_idx = 0;
+        self.compile_search();
+        self.find_matches();
 
         // Jump to first result
         if !self.search_results.is_empty() {
@@ -264,24 +834,159 @@ impl LogViewerComponent {
         }
     }
 
+    /// Split a line into spans, marking the substrings the search matched
+    fn highlight_matches<'a>(&self, line: &'a str) -> Vec<Span<'a>> {
+        let base = Style::default().fg(Self::get_log_level_color(line));
+        let matched = Style::default().bg(Theme::colors().warning).fg(Color::Black);
+
+        // Earlier rules win over later ones, and search hits over both
+        let mut ranges: Vec<(usize, usize, Style)> = Vec::new();
+        if let Some(regex) = &self.search_regex {
+            ranges.extend(regex.find_iter(line).map(|m| (m.start(), m.end(), matched)));
+        }
+        for (regex, color) in &self.highlights {
+            let style = base.fg(*color).add_modifier(Modifier::BOLD);
+            ranges.extend(regex.find_iter(line).map(|m| (m.start(), m.end(), style)));
+        }
+        ranges.retain(|(start, end, _)| start < end);
+        if ranges.is_empty() {
+            return vec![Span::styled(line, base)];
+        }
+
+        let mut bounds: Vec<usize> = ranges.iter().flat_map(|(s, e, _)| [*s, *e]).collect();
+        bounds.extend([0, line.len()]);
+        bounds.sort_unstable();
+        bounds.dedup();
+        let mut spans: Vec<Span> = Vec::new();
+        for pair in bounds.windows(2) {
+            let style = ranges
+                .iter()
+                .find(|(s, e, _)| *s <= pair[0] && pair[0] < *e)
+                .map(|(_, _, style)| *style)
+                .unwrap_or(base);
+            match spans.last_mut() {
+                // Merge neighbours so unhighlighted text stays one span
+                Some(last) if last.style == style => {
+                    let start = pair[0] - last.content.len();
+                    last.content = line[start..pair[1]].into();
+                }
+                _ => spans.push(Span::styled(&line[pair[0]..pair[1]], style)),
+            }
+        }
+        spans
+    }
+
     fn get_log_level_color(line: &str) -> Color {
+        // `dmesg -x` prefixes lines with "facility:level :"
+        let mut fields = line.splitn(3, ':');
+        let facility = fields.next().unwrap_or("").trim();
+        if let Some(level) = fields
+            .next()
+            .filter(|_| !facility.is_empty() && facility.chars().all(|c| c.is_ascii_lowercase()))
+        {
+            match level.trim() {
+                "emerg" | "alert" | "crit" | "err" => return Theme::colors().error,
+                "warn" => return Theme::colors().warning,
+                "notice" | "info" => return Theme::colors().primary,
+                "debug" => return Theme::colors().muted,
+                _ => {}
+            }
+        }
         let lower = line.to_lowercase();
         if lower.contains("error") || lower.contains("fail") || lower.contains("crit") {
-            Color::Red
+            Theme::colors().error
         } else if lower.contains("warn") {
-            Color::Yellow
+            Theme::colors().warning
         } else if lower.contains("info") {
-            Color::Cyan
+            Theme::colors().primary
         } else if lower.contains("debug") {
-            Color::DarkGray
+            Theme::colors().muted
         } else {
-            Color::White
+            Theme::colors().foreground
         }
     }
 }
 
 impl Component for LogViewerComponent {
     fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        if let Some(path) = &mut self.export_path {
+            match key.code {
+                KeyCode::Enter => {
+                    let path = path.trim().to_string();
+                    self.export_path = None;
+                    if !path.is_empty() {
+                        self.export_to_file(&path);
+                    }
+                }
+                KeyCode::Esc => self.export_path = None,
+                KeyCode::Backspace => {
+                    path.pop();
+                }
+                KeyCode::Char(c) => path.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some((prompt, input)) = &mut self.rotation_prompt {
+            match key.code {
+                KeyCode::Enter => {
+                    let (prompt, input) = (*prompt, input.clone());
+                    self.rotation_prompt = None;
+                    self.submit_rotation_prompt(prompt, &input);
+                }
+                KeyCode::Esc => self.rotation_prompt = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some(input) = &mut self.highlight_input {
+            match key.code {
+                KeyCode::Enter => {
+                    let input = input.clone();
+                    self.highlight_input = None;
+                    self.submit_highlight(&input);
+                }
+                KeyCode::Esc => self.highlight_input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.is_filtering {
+            match key.code {
+                KeyCode::Enter => {
+                    self.is_filtering = false;
+                    if let Some(filter) = LogFilter::parse(&self.filter_input) {
+                        if !self.filters.contains(&filter) {
+                            self.filters.push(filter);
+                        }
+                        self.apply_filters();
+                    }
+                    self.filter_input.clear();
+                }
+                KeyCode::Esc => {
+                    self.is_filtering = false;
+                    self.filter_input.clear();
+                }
+                KeyCode::Backspace => {
+                    self.filter_input.pop();
+                }
+                KeyCode::Char(c) => self.filter_input.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
         if self.is_searching {
             match key.code {
                 KeyCode::Enter => {
@@ -291,6 +996,7 @@ impl Component for LogViewerComponent {
                 KeyCode::Esc => {
                     self.is_searching = false;
                     self.search_query.clear();
+                    self.search_regex = None;
                     self.search_results.clear();
                 }
                 KeyCode::Backspace => {
@@ -320,59 +1026,191 @@ impl Component for LogViewerComponent {
                         }
                     }
                 }
-                KeyCode::Enter => {
-                    if let Some(log) = self.selected_log() {
-                        let path = log.path.clone();
-                        self.load_log_content(&path);
+                KeyCode::Enter | KeyCode::Char('a') => {
+                    if let Some(log) = self.selected_log().cloned() {
+                        // 'a' opens the log together with all of its rotations
+                        self.merge_rotations =
+                            key.code == KeyCode::Char('a') && log.kind == LogKind::File;
+                        self.open_log(&log);
+                        self.mode = LogViewMode::ViewLog;
+                        if self.merge_rotations {
+                            self.status_message = Some(if self.merged_rotations == 0 {
+                                (tr("No rotated copies found").to_string(), false)
+                            } else {
+                                (
+                                    trf(
+                                        "Merged {} rotated file(s)",
+                                        &[&self.merged_rotations.to_string()],
+                                    ),
+                                    false,
+                                )
+                            });
+                        }
+                    }
+                }
+                KeyCode::Char(' ')
+                    if self.selected_log().map(|l| l.kind) == Some(LogKind::KernelRing) =>
+                {
+                    self.status_message = Some((
+                        tr("The kernel ring buffer has no wall-clock timestamps to merge by")
+                            .to_string(),
+                        true,
+                    ));
+                }
+                KeyCode::Char(' ')
+                    if self.selected_log().map(|l| l.kind) == Some(LogKind::LoginRecords) =>
+                {
+                    self.status_message = Some((
+                        tr("Login records cannot be merged into a timeline").to_string(),
+                        true,
+                    ));
+                }
+                KeyCode::Char(' ') => {
+                    if let Some(path) = self.selected_log().map(|l| l.path.clone()) {
+                        match self.marked.iter().position(|p| *p == path) {
+                            Some(i) => {
+                                self.marked.remove(i);
+                            }
+                            None => self.marked.push(path),
+                        }
+                    }
+                }
+                KeyCode::Char('m') => {
+                    if self.marked.len() < 2 {
+                        self.status_message = Some((
+                            tr("Mark at least two logs with Space to merge them").to_string(),
+                            true,
+                        ));
+                    } else {
+                        self.timeline = self.marked.clone();
+                        self.load_timeline();
                         self.mode = LogViewMode::ViewLog;
+                        self.status_message = None;
                     }
                 }
                 KeyCode::Home => {
                     self.file_list_state.select(Some(0));
                 }
-                KeyCode::End => {
-                    if !self.log_files.is_empty() {
-                        self.file_list_state.select(Some(self.log_files.len() - 1));
-                    }
+                KeyCode::End if !self.log_files.is_empty() => {
+                    self.file_list_state.select(Some(self.log_files.len() - 1));
+                }
+                KeyCode::Char('R') => {
+                    self.load_rotation();
+                    self.mode = LogViewMode::Rotation;
+                    self.status_message = None;
                 }
                 KeyCode::F(5) => {
                     self.load_log_files();
-                    self.status_message = Some(("Log list refreshed".to_string(), false));
+                    self.status_message = Some((tr("Log list refreshed").to_string(), false));
                 }
                 _ => {}
             },
+            LogViewMode::Rotation => {
+                let selected = self.rotation_state.selected();
+                let policy = selected.and_then(|i| self.rotation.get(i));
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        self.mode = LogViewMode::FileList;
+                        self.status_message = None;
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        if let Some(i) = selected.filter(|i| *i > 0) {
+                            self.rotation_state.select(Some(i - 1));
+                        }
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        if let Some(i) = selected.filter(|i| i + 1 < self.rotation.len()) {
+                            self.rotation_state.select(Some(i + 1));
+                        }
+                    }
+                    KeyCode::Char('c') if policy.is_some() => {
+                        let current = policy
+                            .and_then(|p| p.directives.rotate)
+                            .map(|n| n.to_string())
+                            .unwrap_or_default();
+                        self.rotation_prompt = Some((RotationPrompt::Rotate, current));
+                    }
+                    KeyCode::Char('s') if policy.is_some() => {
+                        let current = policy.and_then(|p| p.directives.size.clone());
+                        self.rotation_prompt =
+                            Some((RotationPrompt::Size, current.unwrap_or_default()));
+                    }
+                    KeyCode::Char('z') => {
+                        if let Some(policy) = policy {
+                            let compress = policy.directives.or(&self.rotation_defaults).compress;
+                            self.apply_rotation(Setting::Compress(compress != Some(true)));
+                        }
+                    }
+                    KeyCode::Char('x') => {
+                        self.status_message = Some(match logrotate::run_now() {
+                            Ok(output) if output.is_empty() => {
+                                (tr("logrotate finished").to_string(), false)
+                            }
+                            Ok(output) => (format!("logrotate: {}", output), false),
+                            Err(e) => (e.to_string(), true),
+                        });
+                        self.load_log_files();
+                    }
+                    KeyCode::F(5) => {
+                        self.load_rotation();
+                        self.status_message =
+                            Some((tr("Rotation policies reloaded").to_string(), false));
+                    }
+                    _ => {}
+                }
+            }
             LogViewMode::ViewLog => match key.code {
                 KeyCode::Esc | KeyCode::Char('q') => {
+                    self.set_follow(false);
+                    self.timeline.clear();
                     self.mode = LogViewMode::FileList;
                     self.log_content.clear();
+                    self.visible_lines.clear();
+                    self.filters.clear();
                     self.search_query.clear();
+                    self.search_regex = None;
                     self.search_results.clear();
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if self.content_scroll > 0 {
-                        self.content_scroll -= 1;
-                        self.follow_mode = false;
-                    }
+                KeyCode::Up | KeyCode::Char('k') if self.content_scroll > 0 => {
+                    self.content_scroll -= 1;
+                    self.set_follow(false);
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if self.content_scroll < self.log_content.len().saturating_sub(1) {
-                        self.content_scroll += 1;
-                    }
+                KeyCode::Down | KeyCode::Char('j')
+                    if self.content_scroll < self.visible_lines.len().saturating_sub(1) =>
+                {
+                    self.content_scroll += 1;
                 }
                 KeyCode::PageUp => {
                     self.content_scroll = self.content_scroll.saturating_sub(20);
-                    self.follow_mode = false;
+                    self.set_follow(false);
                 }
                 KeyCode::PageDown => {
                     self.content_scroll = (self.content_scroll + 20)
-                        .min(self.log_content.len().saturating_sub(1));
+                        .min(self.visible_lines.len().saturating_sub(1));
                 }
                 KeyCode::Home | KeyCode::Char('g') => {
                     self.content_scroll = 0;
-                    self.follow_mode = false;
+                    self.set_follow(false);
                 }
                 KeyCode::End | KeyCode::Char('G') => {
-                    self.content_scroll = self.log_content.len().saturating_sub(1);
+                    self.content_scroll = self.visible_lines.len().saturating_sub(1);
+                }
+                KeyCode::Char('l') => self.cycle_level_filter(),
+                KeyCode::Char('L') => self.load_more(),
+                KeyCode::Char('H') => self.highlight_input = Some(String::new()),
+                KeyCode::Char('m') => self.toggle_bookmark(),
+                KeyCode::Char(']') => self.jump_bookmark(true),
+                KeyCode::Char('[') => self.jump_bookmark(false),
+                KeyCode::Char('w') => self.export_path = Some(self.default_export_path()),
+                KeyCode::Char('y') => self.copy_to_clipboard(),
+                KeyCode::Char('F') => {
+                    self.is_filtering = true;
+                    self.filter_input.clear();
+                }
+                KeyCode::Char('X') if !self.filters.is_empty() => {
+                    self.filters.clear();
+                    self.apply_filters();
+                    self.status_message = Some((tr("Filters cleared").to_string(), false));
                 }
                 KeyCode::Char('/') => {
                     self.is_searching = true;
@@ -384,18 +1222,34 @@ impl Component for LogViewerComponent {
                 KeyCode::Char('N') => {
                     self.prev_search_result();
                 }
+                KeyCode::Char('c') => {
+                    self.case_sensitive = !self.case_sensitive;
+                    let idx = self.current_search_idx;
+                    self.compile_search();
+                    self.find_matches();
+                    self.current_search_idx = idx.min(self.search_results.len().saturating_sub(1));
+                    self.status_message = Some((
+                        tr(if self.case_sensitive {
+                            "Case-sensitive search"
+                        } else {
+                            "Case-insensitive search"
+                        })
+                        .to_string(),
+                        false,
+                    ));
+                }
                 KeyCode::Char('f') => {
-                    self.follow_mode = !self.follow_mode;
+                    self.status_message = None;
+                    self.set_follow(!self.follow_mode);
                     if self.follow_mode {
-                        self.refresh_log();
-                        self.status_message = Some(("Follow mode enabled".to_string(), false));
-                    } else {
-                        self.status_message = Some(("Follow mode disabled".to_string(), false));
+                        self.status_message = Some((tr("Follow mode enabled").to_string(), false));
+                    } else if self.status_message.is_none() {
+                        self.status_message = Some((tr("Follow mode disabled").to_string(), false));
                     }
                 }
                 KeyCode::F(5) => {
                     self.refresh_log();
-                    self.status_message = Some(("Log refreshed".to_string(), false));
+                    self.status_message = Some((tr("Log refreshed").to_string(), false));
                 }
                 _ => {}
             },
@@ -407,24 +1261,154 @@ impl Component for LogViewerComponent {
         match self.mode {
             LogViewMode::FileList => self.render_file_list(frame, area),
             LogViewMode::ViewLog => self.render_log_view(frame, area),
+            LogViewMode::Rotation => self.render_rotation(frame, area),
         }
     }
 
+    fn is_typing(&self) -> bool {
+        self.is_searching
+            || self.is_filtering
+            || self.export_path.is_some()
+            || self.rotation_prompt.is_some()
+            || self.highlight_input.is_some()
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
         match self.mode {
-            LogViewMode::FileList => vec![("Enter", "Open"), ("↑/↓", "Navigate"), ("F5", "Refresh")],
+            LogViewMode::FileList => vec![
+                ("Enter", "Open"),
+                ("a", "Open with Rotated"),
+                ("Space/m", "Mark/Merge"),
+                ("R", "Rotation"),
+                ("↑/↓", "Navigate"),
+                ("F5", "Refresh"),
+            ],
+            LogViewMode::Rotation => vec![
+                ("q/Esc", "Back"),
+                ("c", "Rotate Count"),
+                ("s", "Size"),
+                ("z", "Compression"),
+                ("x", "Run Now"),
+                ("F5", "Reload"),
+            ],
             LogViewMode::ViewLog => vec![
                 ("q/Esc", "Back"),
-                ("/", "Search"),
+                ("/", "Search (regex)"),
                 ("n/N", "Next/Prev"),
+                ("c", "Case"),
+                ("l/F/X", "Level/Filter/Clear"),
+                ("w/y", "Export/Copy"),
+                ("m/[/]", "Bookmark/Prev/Next"),
+                ("L/H", "Load Earlier/Highlight"),
                 ("f", "Follow"),
             ],
         }
     }
 
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        match self.mode {
+            LogViewMode::Rotation => vec!["c", "s", "z", "x"],
+            _ => Vec::new(),
+        }
+    }
+
     fn on_activate(&mut self) {
+        self.load_settings();
         self.load_log_files();
     }
+
+    fn on_refresh(&mut self) {
+        // An open log updates live in follow mode instead; reloading it here
+        // would lose the scroll position, search and bookmarks
+        if self.mode == LogViewMode::FileList {
+            self.load_log_files();
+        }
+    }
+
+    fn is_busy(&self) -> bool {
+        self.follow_rx.is_some()
+    }
+
+    /// The log open in the viewer, its scroll position and search. A
+    /// timeline is not kept, the file list selection is.
+    fn view_state(&self) -> ViewState {
+        let viewing = self.mode == LogViewMode::ViewLog && self.timeline.is_empty();
+        ViewState {
+            selected: self.file_list_state.selected().filter(|&i| i > 0),
+            query: if viewing { self.search_query.clone() } else { String::new() },
+            open: viewing
+                .then(|| self.selected_log().map(|log| log.path.display().to_string()))
+                .flatten(),
+            scroll: if viewing { self.content_scroll } else { 0 },
+        }
+    }
+
+    fn restore_view_state(&mut self, state: &ViewState) {
+        if let Some(selected) = state.selected.filter(|&i| i < self.log_files.len()) {
+            self.file_list_state.select(Some(selected));
+        }
+        let Some(open) = &state.open else {
+            return;
+        };
+        // The list may have changed since; the path is what counts
+        let Some(i) = self.log_files.iter().position(|log| log.path == Path::new(open)) else {
+            return;
+        };
+        self.file_list_state.select(Some(i));
+        let log = self.log_files[i].clone();
+        self.open_log(&log);
+        self.mode = LogViewMode::ViewLog;
+        self.search_query = state.query.clone();
+        self.compile_search();
+        self.find_matches();
+        self.content_scroll = state.scroll.min(self.visible_lines.len().saturating_sub(1));
+    }
+
+    fn search_items(&self) -> Vec<SearchItem> {
+        self.log_files
+            .iter()
+            .map(|log| SearchItem::new(log.path.display().to_string(), log.path.display().to_string()))
+            .collect()
+    }
+
+    fn select_item(&mut self, id: &str) {
+        let Some(i) = self.log_files.iter().position(|log| log.path == Path::new(id)) else {
+            return;
+        };
+        self.set_follow(false);
+        self.timeline.clear();
+        self.mode = LogViewMode::FileList;
+        self.file_list_state.select(Some(i));
+    }
+
+    fn on_tick(&mut self) {
+        let Some(rx) = &mut self.follow_rx else {
+            return;
+        };
+        let mut appended = false;
+        while let Ok(line) = rx.try_recv() {
+            self.log_content.push(line);
+            appended = true;
+        }
+        if !appended {
+            return;
+        }
+        if self.log_content.len() > self.buffer_lines {
+            let excess = self.log_content.len() - self.buffer_lines;
+            let dropped: usize = self.log_content.drain(..excess).map(|l| l.len() + 1).sum();
+            if let Some((_, start)) = &mut self.window {
+                *start += dropped as u64;
+            }
+            self.bookmarks = self
+                .bookmarks
+                .iter()
+                .filter_map(|b| b.checked_sub(excess))
+                .collect();
+        }
+        // Line numbers may have shifted, so filter and search hits are redone
+        self.apply_filters();
+        self.content_scroll = self.visible_lines.len().saturating_sub(1);
+    }
 }
 
 impl LogViewerComponent {
@@ -438,29 +1422,36 @@ impl LogViewerComponent {
             .log_files
             .iter()
             .map(|log| {
+                // Rotated copies are dimmed so the live logs stand out
+                let name_style = if logfile::is_compressed(&log.path) {
+                    Theme::muted()
+                } else {
+                    Style::default().add_modifier(Modifier::BOLD)
+                };
+                let mark = if self.marked.contains(&log.path) { "* " } else { "  " };
                 ListItem::new(Line::from(vec![
+                    Span::styled(mark, Theme::success()),
+                    Span::styled(format!("{:<40}", log.name), name_style),
                     Span::styled(
-                        format!("{:<40}", log.name),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        format!("{:>8}", Self::format_size(log.size)),
-                        Style::default().fg(Color::Cyan),
+                        match log.kind {
+                            LogKind::File | LogKind::LoginRecords | LogKind::Audit => {
+                                format!("{:>8}", Self::format_size(log.size))
+                            }
+                            LogKind::KernelRing => format!("{:>8}", "-"),
+                        },
+                        Theme::label(),
                     ),
                     Span::styled(
                         format!("  {}", log.modified),
-                        Style::default().fg(Color::DarkGray),
+                        Theme::muted(),
                     ),
                 ]))
             })
             .collect();
 
+        let title = trf("Log Files ({})", &[&self.log_files.len().to_string()]);
         let list = List::new(items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(format!(" Log Files ({}) ", self.log_files.len())),
-            )
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", title)))
             .highlight_style(Theme::list_selected())
             .highlight_symbol("▶ ");
 
@@ -471,15 +1462,15 @@ impl LogViewerComponent {
         let status_content = if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
             ))
         } else if let Some(log) = self.selected_log() {
             Line::from(vec![
-                Span::styled("Path: ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{} ", tr("Path:")), Theme::label()),
                 Span::raw(log.path.to_string_lossy().to_string()),
             ])
         } else {
-            Line::from(Span::raw("Select a log file"))
+            Line::from(Span::raw(tr("Select a log file")))
         };
 
         let status = Paragraph::new(status_content)
@@ -487,6 +1478,119 @@ impl LogViewerComponent {
         frame.render_widget(status, chunks[1]);
     }
 
+    fn render_rotation(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(10), Constraint::Length(3)])
+            .split(area);
+
+        // Settings inherited from logrotate.conf are dimmed
+        let defaults = &self.rotation_defaults;
+        let cell = |own: Option<String>, inherited: Option<String>, width: usize| {
+            match (own, inherited) {
+                (Some(value), _) => Span::raw(format!("{:<width$}", value, width = width)),
+                (None, Some(value)) => Span::styled(
+                    format!("{:<width$}", value, width = width),
+                    Theme::muted(),
+                ),
+                (None, None) => Span::styled(
+                    format!("{:<width$}", "-", width = width),
+                    Theme::muted(),
+                ),
+            }
+        };
+        let compress = |c: Option<bool>| c.map(|c| tr(if c { "yes" } else { "no" }).to_string());
+        let items: Vec<ListItem> = self
+            .rotation
+            .iter()
+            .map(|policy| {
+                let own = &policy.directives;
+                let source = policy.source.file_name().map(|n| n.to_string_lossy().to_string());
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<40}", policy.logs.join(" ")),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    cell(own.frequency.clone(), defaults.frequency.clone(), 9),
+                    cell(
+                        own.rotate.map(|n| n.to_string()),
+                        defaults.rotate.map(|n| n.to_string()),
+                        7,
+                    ),
+                    cell(own.size.clone(), defaults.size.clone(), 7),
+                    cell(compress(own.compress), compress(defaults.compress), 6),
+                    Span::styled(source.unwrap_or_default(), Theme::label()),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                " {} ",
+                trf(
+                    "Log Rotation ({}) - log / frequency / rotate / size / compress / file",
+                    &[&self.rotation.len().to_string()]
+                )
+            )))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.rotation_state.clone();
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let status = if let Some((prompt, input)) = &self.rotation_prompt {
+            let label = match prompt {
+                RotationPrompt::Rotate => tr("Rotations to keep:"),
+                RotationPrompt::Size => tr("Rotate at size (empty to remove):"),
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", label), Theme::warning()),
+                Span::raw(format!("{}_", input)),
+            ])
+        } else if let Some((msg, is_error)) = &self.status_message {
+            Line::from(Span::styled(
+                msg.clone(),
+                if *is_error { Theme::error() } else { Theme::success() },
+            ))
+        } else {
+            Line::from(Span::styled(
+                trf(
+                    "Policies from {} and {}/",
+                    &[logrotate::LOGROTATE_CONF, logrotate::LOGROTATE_DIR],
+                ),
+                Theme::muted(),
+            ))
+        };
+        frame.render_widget(
+            Paragraph::new(status).block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+    }
+
+    fn render_bookmarks(&self, frame: &mut Frame, area: Rect) {
+        let top = self.visible_lines.get(self.content_scroll).copied();
+        let preview_width = area.width.saturating_sub(11) as usize;
+        let items: Vec<ListItem> = self
+            .bookmarks
+            .iter()
+            .map(|&line| {
+                let style = if Some(line) == top {
+                    Style::default().fg(Theme::colors().secondary).add_modifier(Modifier::BOLD)
+                } else {
+                    Theme::muted()
+                };
+                let preview: String = self.log_content[line].chars().take(preview_width).collect();
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:>6} ", line + 1), style),
+                    Span::raw(preview),
+                ]))
+            })
+            .collect();
+        let title = trf("Bookmarks ({})", &[&self.bookmarks.len().to_string()]);
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", title)));
+        frame.render_widget(list, area);
+    }
+
     fn render_log_view(&self, frame: &mut Frame, area: Rect) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -494,17 +1598,44 @@ impl LogViewerComponent {
             .split(area);
 
         // Header with search
-        let title = self
-            .selected_log()
-            .map(|l| l.name.clone())
-            .unwrap_or_else(|| "Log".to_string());
+        let mut title = if self.timeline.is_empty() {
+            self.selected_log()
+                .map(|l| l.name.clone())
+                .unwrap_or_else(|| tr("Log").to_string())
+        } else {
+            let names: Vec<String> = self
+                .timeline
+                .iter()
+                .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .collect();
+            trf("Timeline: {}", &[&names.join(" + ")])
+        };
+        if self.merged_rotations > 0 {
+            title.push_str(&format!(
+                " ({})",
+                trf("+{} rotated", &[&self.merged_rotations.to_string()])
+            ));
+        }
+        if self.window.as_ref().is_some_and(|(_, start)| *start > 0) {
+            title.push_str(&format!(" ({})", tr("L loads earlier lines")));
+        }
 
-        let search_display = if self.is_searching {
-            format!("Search: {}█", self.search_query)
+        let case = if self.case_sensitive { "Aa" } else { "aa" };
+        let search_display = if let Some(path) = &self.export_path {
+            trf(
+                "Export {} line(s) to: {}█",
+                &[&self.export_lines().len().to_string(), path],
+            )
+        } else if let Some(input) = &self.highlight_input {
+            trf("Highlight (pattern=color, pattern= removes): {}█", &[input])
+        } else if self.is_filtering {
+            trf("Filter (!text excludes): {}█", &[&self.filter_input])
+        } else if self.is_searching {
+            trf("Search [{}]: {}█", &[case, &self.search_query])
         } else if !self.search_query.is_empty() {
             format!(
-                "Search: {} ({}/{})",
-                self.search_query,
+                "{} ({}/{})",
+                trf("Search [{}]: {}", &[case, &self.search_query]),
                 if self.search_results.is_empty() {
                     0
                 } else {
@@ -521,17 +1652,36 @@ impl LogViewerComponent {
             Span::raw("  "),
             Span::styled(
                 search_display,
-                Style::default().fg(if self.is_searching {
-                    Color::Yellow
+                Style::default().fg(if self.is_searching
+                    || self.is_filtering
+                    || self.export_path.is_some()
+                    || self.highlight_input.is_some()
+                {
+                    Theme::colors().warning
                 } else {
-                    Color::Cyan
+                    Theme::colors().primary
                 }),
             ),
             if self.follow_mode {
-                Span::styled(" [FOLLOW]", Style::default().fg(Color::Green))
+                Span::styled(format!(" [{}]", tr("FOLLOW")), Theme::success())
             } else {
                 Span::raw("")
             },
+            if self.filters.is_empty() {
+                Span::raw("")
+            } else {
+                let labels: Vec<String> = self.filters.iter().map(|f| f.label()).collect();
+                Span::styled(
+                    format!(
+                        " [{}]",
+                        trf(
+                            "filter: {}, {} hidden",
+                            &[&labels.join(" + "), &self.hidden_lines().to_string()]
+                        )
+                    ),
+                    Style::default().fg(Theme::colors().secondary),
+                )
+            },
         ]))
         .block(Block::default().borders(Borders::ALL));
         frame.render_widget(header, chunks[0]);
@@ -539,50 +1689,65 @@ impl LogViewerComponent {
         // Log content
         let visible_height = chunks[1].height.saturating_sub(2) as usize;
         let start = self.content_scroll;
-        let end = (start + visible_height).min(self.log_content.len());
+        let end = (start + visible_height).min(self.visible_lines.len());
 
-        let lines: Vec<Line> = self.log_content[start..end]
+        let lines: Vec<Line> = self.visible_lines[start..end]
             .iter()
             .enumerate()
-            .map(|(i, line)| {
-                let line_num = start + i;
-                let is_search_match = self.search_results.contains(&line_num);
-
-                let style = if is_search_match {
-                    Style::default()
-                        .bg(Color::Yellow)
-                        .fg(Color::Black)
+            .map(|(i, &line_num)| {
+                let line = &self.log_content[line_num];
+                let is_current_match = !self.search_results.is_empty()
+                    && self.search_results[self.current_search_idx] == start + i;
+                let number_style = if is_current_match {
+                    Theme::warning().add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Self::get_log_level_color(line))
+                    Theme::muted()
                 };
 
-                Line::from(vec![
+                let mut spans = vec![
                     Span::styled(
-                        format!("{:>6} ", line_num + 1),
-                        Style::default().fg(Color::DarkGray),
+                        if self.bookmarks.contains(&line_num) { "●" } else { " " },
+                        Style::default().fg(Theme::colors().secondary),
                     ),
-                    Span::styled(line.clone(), style),
-                ])
+                    Span::styled(format!("{:>6} ", line_num + 1), number_style),
+                ];
+                spans.extend(self.highlight_matches(line));
+                Line::from(spans)
             })
             .collect();
 
+        let (content_area, bookmark_area) = if self.bookmarks.is_empty() {
+            (chunks[1], None)
+        } else {
+            let split = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(40), Constraint::Length(36)])
+                .split(chunks[1]);
+            (split[0], Some(split[1]))
+        };
         let content = Paragraph::new(lines)
             .block(Block::default().borders(Borders::ALL))
             .wrap(Wrap { trim: false });
-        frame.render_widget(content, chunks[1]);
+        frame.render_widget(content, content_area);
+        if let Some(area) = bookmark_area {
+            self.render_bookmarks(frame, area);
+        }
 
         // Status bar
         let status = Paragraph::new(Line::from(vec![
-            Span::styled("Line: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{} ", tr("Line:")), Theme::label()),
             Span::raw(format!(
                 "{}/{}",
-                self.content_scroll + 1,
+                self.visible_lines
+                    .get(self.content_scroll)
+                    .map(|i| i + 1)
+                    .unwrap_or(0),
                 self.log_content.len()
             )),
             if let Some((msg, is_error)) = &self.status_message {
                 Span::styled(
                     format!("  {}", msg),
-                    Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                    if *is_error { Theme::error() } else { Theme::success() },
                 )
             } else {
                 Span::raw("")
@@ -6,13 +6,13 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
-use tokio::sync::mpsc;
 
 use super::{AsyncComponent, Component};
 use crate::app::Message;
 use crate::slackware::config::MirrorEntry;
 use crate::slackware::SlackwareVersion;
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
 
 /// Mirror management component
 pub struct MirrorComponent {
@@ -21,7 +21,6 @@ pub struct MirrorComponent {
     version: SlackwareVersion,
     is_running: bool,
     status_message: Option<(String, bool)>, // (message, is_error)
-    progress_tx: Option<mpsc::UnboundedSender<String>>,
 }
 
 impl MirrorComponent {
@@ -32,15 +31,9 @@ impl MirrorComponent {
             version,
             is_running: false,
             status_message: None,
-            progress_tx: None,
         }
     }
 
-    pub fn set_version(&mut self, version: SlackwareVersion) {
-        self.version = version;
-        self.load_mirrors();
-    }
-
     pub fn load_mirrors(&mut self) {
         use crate::slackware::config::SlackwareConfig;
 
@@ -54,7 +47,7 @@ impl MirrorComponent {
                 self.status_message = None;
             }
             Err(e) => {
-                self.status_message = Some((format!("Failed to load mirrors: {}", e), true));
+                self.status_message = Some((trf("Failed to load mirrors: {}", &[&e.to_string()]), true));
             }
         }
     }
@@ -128,7 +121,7 @@ impl Component for MirrorComponent {
 
         // Title
         let title = Paragraph::new(Line::from(vec![Span::styled(
-            "Mirror Configuration",
+            tr("Mirror Configuration"),
             Theme::title(),
         )]))
         .block(Block::default().borders(Borders::BOTTOM));
@@ -136,7 +129,7 @@ impl Component for MirrorComponent {
 
         // Version info
         let version_info = Paragraph::new(Line::from(vec![
-            Span::styled("Detected version: ", Theme::muted()),
+            Span::styled(format!("{} ", tr("Detected version:")), Theme::muted()),
             Span::styled(self.version.display_name(), Theme::default()),
         ]))
         .block(Block::default().borders(Borders::NONE));
@@ -166,7 +159,7 @@ impl Component for MirrorComponent {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(format!("Mirrors ({})", self.mirrors.len())),
+                    .title(trf("Mirrors ({})", &[&self.mirrors.len().to_string()])),
             )
             .highlight_style(Theme::highlight().add_modifier(Modifier::BOLD))
             .highlight_symbol("→ ");
@@ -181,9 +174,9 @@ impl Component for MirrorComponent {
                 Theme::success()
             })
         } else if self.is_running {
-            Paragraph::new("Updating mirror configuration...").style(Theme::warning())
+            Paragraph::new(tr("Updating mirror configuration...")).style(Theme::warning())
         } else {
-            Paragraph::new("Press Enter to select mirror, R to refresh list").style(Theme::muted())
+            Paragraph::new(tr("Press Enter to select mirror, R to refresh list")).style(Theme::muted())
         };
         frame.render_widget(
             status.block(Block::default().borders(Borders::TOP)),
@@ -205,8 +198,10 @@ impl Component for MirrorComponent {
 }
 
 impl AsyncComponent for MirrorComponent {
-    fn set_progress_channel(&mut self, tx: mpsc::UnboundedSender<String>) {
-        self.progress_tx = Some(tx);
+    fn add_progress(&mut self, line: String) {
+        if self.is_running {
+            self.status_message = Some((line, false));
+        }
     }
 
     fn is_running(&self) -> bool {
@@ -8,21 +8,60 @@ pub mod user_setup;
 // New components
 pub mod backup;
 pub mod cron;
+pub mod custom_tab;
 pub mod disks;
+pub mod global_search;
 pub mod kernel;
 pub mod logs;
 pub mod network;
 pub mod package_browser;
+pub mod palette;
 pub mod services;
 pub mod settings;
 pub mod sysinfo;
+pub mod tasks;
+pub mod wizard;
 
 use crossterm::event::KeyEvent;
 use ratatui::Frame;
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use crate::app::Message;
 
+/// Where a component stands, kept between sessions so it opens again
+/// where it was left
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewState {
+    /// Row selected in the main list
+    pub selected: Option<usize>,
+    /// Search or filter text
+    pub query: String,
+    /// What was open, e.g. the path of the log being viewed
+    pub open: Option<String>,
+    /// How far that was scrolled
+    pub scroll: usize,
+}
+
+/// Something the global search finds in a tab
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchItem {
+    /// What the tab selects it by, e.g. a path
+    pub id: String,
+    /// What the query is matched against and what is shown
+    pub label: String,
+}
+
+impl SearchItem {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+        }
+    }
+}
+
 /// Trait for TUI components
 pub trait Component {
     /// Handle keyboard input
@@ -34,17 +73,77 @@ pub trait Component {
     /// Get help text for status bar
     fn help_text(&self) -> Vec<(&'static str, &'static str)>;
 
+    /// Whether keys are going into a text field, so printable global
+    /// keys like '?' are left to the component
+    fn is_typing(&self) -> bool {
+        false
+    }
+
     /// Called when component becomes active
     fn on_activate(&mut self) {}
 
     /// Called when component becomes inactive
     fn on_deactivate(&mut self) {}
+
+    /// Called on every pass of the event loop while the component is active
+    fn on_tick(&mut self) {}
+
+    /// Called every refresh_interval while the component is active and
+    /// auto refresh is on in Settings
+    fn on_refresh(&mut self) {}
+
+    /// Whether output is coming in or the screen moves on its own, so the
+    /// event loop keeps drawing at the frame interval instead of idling
+    fn is_busy(&self) -> bool {
+        false
+    }
+
+    /// Keys of `help_text` that change the system, refused and greyed out
+    /// in read-only mode
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Where the component stands, saved on exit
+    fn view_state(&self) -> ViewState {
+        ViewState::default()
+    }
+
+    /// Go back to where a previous session left the component
+    fn restore_view_state(&mut self, _state: &ViewState) {}
+
+    /// What the global search finds in the component
+    fn search_items(&self) -> Vec<SearchItem> {
+        Vec::new()
+    }
+
+    /// Select the item with `id` picked in the global search, clearing
+    /// filters that would hide it
+    fn select_item(&mut self, _id: &str) {}
+}
+
+/// Sends progress lines tagged with the tab whose work they come from,
+/// so they reach that tab whichever one is on screen
+#[derive(Debug, Clone)]
+pub struct ProgressSender {
+    tab: Tab,
+    tx: mpsc::UnboundedSender<(Tab, String)>,
+}
+
+impl ProgressSender {
+    pub fn new(tab: Tab, tx: mpsc::UnboundedSender<(Tab, String)>) -> Self {
+        Self { tab, tx }
+    }
+
+    pub fn send(&self, line: String) {
+        let _ = self.tx.send((self.tab, line));
+    }
 }
 
 /// Async component trait for components that execute commands
 pub trait AsyncComponent: Component {
-    /// Set the progress channel for async updates
-    fn set_progress_channel(&mut self, tx: mpsc::UnboundedSender<String>);
+    /// Take a line of output from a job started for this tab
+    fn add_progress(&mut self, line: String);
 
     /// Check if a task is currently running
     fn is_running(&self) -> bool;
@@ -72,6 +171,10 @@ pub enum Tab {
     Cron,
     Disks,
     Settings,
+    Tasks,
+    /// A tab from the config directory, by its index in
+    /// `custom_tab::definitions()`
+    Custom(usize),
 }
 
 impl Tab {
@@ -93,7 +196,16 @@ impl Tab {
             Tab::Cron,
             Tab::Disks,
             Tab::Settings,
+            Tab::Tasks,
         ]
+        .into_iter()
+        .chain(Self::custom_tabs())
+        .collect()
+    }
+
+    /// Tabs defined in the config directory
+    pub fn custom_tabs() -> Vec<Tab> {
+        (0..custom_tab::definitions().len()).map(Tab::Custom).collect()
     }
 
     /// Get tabs shown in the primary tab bar (F1-F6)
@@ -120,9 +232,41 @@ impl Tab {
         ]
     }
 
-    /// Get additional tabs (Ctrl+shortcuts)
+    /// Whether the tab is open in read-only mode, without root and without
+    /// a password: it is mostly for looking at the system
+    pub fn browsable(&self) -> bool {
+        matches!(
+            self,
+            Tab::SysInfo
+                | Tab::Logs
+                | Tab::PackageBrowser
+                | Tab::Services
+                | Tab::Disks
+                | Tab::Settings
+        )
+    }
+
+    /// Whether the tab works on a remote host. The others read this
+    /// machine's files directly, so they stay closed while one is picked.
+    pub fn remote_capable(&self) -> bool {
+        matches!(
+            self,
+            Tab::Updater
+                | Tab::Sbotools
+                | Tab::UserSetup
+                | Tab::Packages
+                | Tab::Config
+                | Tab::Settings
+                | Tab::Tasks
+                | Tab::Custom(_)
+        )
+    }
+
+    /// Get additional tabs (Ctrl+shortcuts), then the custom ones
     pub fn additional_tabs() -> Vec<Tab> {
-        vec![Tab::Kernel, Tab::Cron, Tab::Disks, Tab::Settings]
+        let mut tabs = vec![Tab::Kernel, Tab::Cron, Tab::Disks, Tab::Settings, Tab::Tasks];
+        tabs.extend(Self::custom_tabs());
+        tabs
     }
 
     pub fn title(&self) -> &'static str {
@@ -143,69 +287,98 @@ impl Tab {
             Tab::Cron => "Cron",
             Tab::Disks => "Disks",
             Tab::Settings => "Settings",
+            Tab::Tasks => "Tasks",
+            Tab::Custom(i) => custom_tab::definitions()[*i].name.as_str(),
         }
     }
 
-    pub fn shortcut(&self) -> &'static str {
+    /// What the tab is for, shown in the '?' help
+    pub fn description(&self) -> &'static str {
         match self {
-            Tab::Updater => "F1",
-            Tab::Sbotools => "F2",
-            Tab::UserSetup => "F3",
-            Tab::Mirror => "F4",
-            Tab::Packages => "F5",
-            Tab::Config => "F6",
-            Tab::SysInfo => "F7",
-            Tab::Services => "F8",
-            Tab::PackageBrowser => "F9",
-            Tab::Backup => "F10",
-            Tab::Network => "F11",
-            Tab::Logs => "F12",
-            Tab::Kernel => "^K",
-            Tab::Cron => "^J",
-            Tab::Disks => "^D",
-            Tab::Settings => "^S",
+            Tab::Updater => {
+                "Runs the slackpkg update cycle: update, install-new, upgrade-all and \
+                 clean-system, then offers to update the bootloader when the kernel changed."
+            }
+            Tab::Sbotools => {
+                "Installs sbopkg and sbotools and fetches the SlackBuilds.org snapshot."
+            }
+            Tab::UserSetup => {
+                "Creates a user with a password and groups, and can switch the default \
+                 runlevel to the graphical login."
+            }
+            Tab::Mirror => "Picks the slackpkg mirror in /etc/slackpkg/mirrors.",
+            Tab::Packages => "Searches SlackBuilds.org with sbofind and installs with sboinstall.",
+            Tab::Config => "Edits the slackpkg, mirror and sbotools configuration files.",
+            Tab::SysInfo => "Shows the system, CPU, memory, network rates and top processes.",
+            Tab::Services => "Starts, stops and enables the rc.d service scripts.",
+            Tab::PackageBrowser => "Browses, searches and removes installed packages.",
+            Tab::Backup => {
+                "Backs up configuration files, full or incremental, and restores, verifies \
+                 or pushes backups."
+            }
+            Tab::Network => {
+                "Configures interfaces, DNS, hosts, routes, the firewall, wireless and the \
+                 proxy, and restarts the network."
+            }
+            Tab::Logs => "Views, searches, filters and exports the system logs.",
+            Tab::Kernel => {
+                "Lists installed kernels, installs new ones, builds the initrd and edits \
+                 the bootloader."
+            }
+            Tab::Cron => "Edits crontab jobs, at jobs and the cron environment.",
+            Tab::Disks => {
+                "Shows disks, partitions, RAID and LVM; mounts, formats, checks and \
+                 partitions."
+            }
+            Tab::Settings => "Theme, behavior, display, backup, key and system settings.",
+            Tab::Tasks => {
+                "Lists the jobs running in the background and those finished, with their \
+                 output, and cancels them."
+            }
+            Tab::Custom(i) => custom_tab::definitions()[*i]
+                .description
+                .as_deref()
+                .unwrap_or("Site-specific commands from the config directory."),
         }
     }
 
-    pub fn next(&self) -> Tab {
+    /// Name of the tab in the default_tab setting
+    pub fn id(&self) -> &'static str {
         match self {
-            Tab::Updater => Tab::Sbotools,
-            Tab::Sbotools => Tab::UserSetup,
-            Tab::UserSetup => Tab::Mirror,
-            Tab::Mirror => Tab::Packages,
-            Tab::Packages => Tab::Config,
-            Tab::Config => Tab::SysInfo,
-            Tab::SysInfo => Tab::Services,
-            Tab::Services => Tab::PackageBrowser,
-            Tab::PackageBrowser => Tab::Backup,
-            Tab::Backup => Tab::Network,
-            Tab::Network => Tab::Logs,
-            Tab::Logs => Tab::Kernel,
-            Tab::Kernel => Tab::Cron,
-            Tab::Cron => Tab::Disks,
-            Tab::Disks => Tab::Settings,
-            Tab::Settings => Tab::Updater,
+            Tab::Updater => "updater",
+            Tab::Sbotools => "sbotools",
+            Tab::UserSetup => "user_setup",
+            Tab::Mirror => "mirror",
+            Tab::Packages => "packages",
+            Tab::Config => "config",
+            Tab::SysInfo => "sysinfo",
+            Tab::Services => "services",
+            Tab::PackageBrowser => "package_browser",
+            Tab::Backup => "backup",
+            Tab::Network => "network",
+            Tab::Logs => "logs",
+            Tab::Kernel => "kernel",
+            Tab::Cron => "cron",
+            Tab::Disks => "disks",
+            Tab::Settings => "settings",
+            Tab::Tasks => "tasks",
+            Tab::Custom(i) => custom_tab::definitions()[*i].id.as_str(),
         }
     }
 
+    pub fn from_id(id: &str) -> Option<Tab> {
+        Tab::all().into_iter().find(|t| t.id() == id)
+    }
+
+    pub fn next(&self) -> Tab {
+        let tabs = Tab::all();
+        let index = tabs.iter().position(|t| t == self).unwrap_or(0);
+        tabs[(index + 1) % tabs.len()]
+    }
+
     pub fn prev(&self) -> Tab {
-        match self {
-            Tab::Updater => Tab::Settings,
-            Tab::Sbotools => Tab::Updater,
-            Tab::UserSetup => Tab::Sbotools,
-            Tab::Mirror => Tab::UserSetup,
-            Tab::Packages => Tab::Mirror,
-            Tab::Config => Tab::Packages,
-            Tab::SysInfo => Tab::Config,
-            Tab::Services => Tab::SysInfo,
-            Tab::PackageBrowser => Tab::Services,
-            Tab::Backup => Tab::PackageBrowser,
-            Tab::Network => Tab::Backup,
-            Tab::Logs => Tab::Network,
-            Tab::Kernel => Tab::Logs,
-            Tab::Cron => Tab::Kernel,
-            Tab::Disks => Tab::Cron,
-            Tab::Settings => Tab::Disks,
-        }
+        let tabs = Tab::all();
+        let index = tabs.iter().position(|t| t == self).unwrap_or(0);
+        tabs[(index + tabs.len() - 1) % tabs.len()]
     }
 }
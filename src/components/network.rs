@@ -1,16 +1,43 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Sparkline},
     Frame,
 };
+use std::collections::HashMap;
 use std::fs;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
 
 use crate::app::Message;
 use crate::components::Component;
+use crate::slackware::audit;
+use crate::slackware::dhcp::{self, Lease};
+use crate::slackware::firewall::{self, OpenPort};
+use crate::slackware::hosts::{self, HostsFile};
+use crate::slackware::inet1::{self, Inet1Config};
+use crate::slackware::listeners::{self, Exposure, Listener};
+use crate::slackware::netdev::{self, LinkStats};
+use crate::slackware::proxy::{self, ProxySettings};
+use crate::slackware::resolv::{self, ResolvConf};
+use crate::slackware::routes::{self, Route};
+use crate::slackware::wireless::{self, WirelessLink, WirelessNetwork};
 use crate::ui::theme::Theme;
+use crate::utils::bandwidth::{format_rate, BandwidthMonitor};
+use crate::utils::i18n::{tr, trf};
+use crate::utils::root;
+
+/// Fields of the interface editor, in display order
+const EDIT_FIELDS: [&str; 6] = [
+    "IP Address",
+    "Netmask",
+    "Gateway",
+    "Use DHCP",
+    "MTU",
+    "MAC Address",
+];
 
 /// Network interface information
 #[derive(Debug, Clone)]
@@ -22,6 +49,9 @@ pub struct NetworkInterface {
     pub use_dhcp: bool,
     pub is_up: bool,
     pub mac_address: String,
+    pub mtu: String,
+    /// HWADDR override from rc.inet1.conf; empty keeps the hardware address
+    pub mac_override: String,
 }
 
 /// Network Configuration Component
@@ -31,20 +61,102 @@ pub struct NetworkComponent {
     mode: NetworkMode,
     edit_field: usize,
     edit_buffer: String,
-    is_editing: bool,
-    dns_servers: Vec<String>,
+    /// Text prompt currently capturing input into edit_buffer
+    prompt: Option<InputPrompt>,
+    resolv: ResolvConf,
+    /// resolv.conf has edits that have not been written yet
+    dns_dirty: bool,
+    /// DHCP interfaces that will replace resolv.conf on their next lease
+    dns_overwriters: Vec<(usize, String)>,
     hostname: String,
     status_message: Option<(String, bool)>,
     show_confirm: bool,
+    pending_action: Option<NetworkAction>,
+    /// Interface settings being edited in EditInterface mode
+    draft: Option<NetworkInterface>,
+    wireless_iface: Option<String>,
+    wireless_networks: Vec<WirelessNetwork>,
+    wireless_link: Option<WirelessLink>,
+    firewall_enabled: bool,
+    firewall_ports: Vec<OpenPort>,
+    firewall_backend: &'static str,
+    firewall_rules: Vec<String>,
+    firewall_scroll: u16,
+    bandwidth: BandwidthMonitor,
+    /// Routing table with whether each route is restored at boot
+    routes: Vec<(Route, bool)>,
+    hosts: HostsFile,
+    listeners: Vec<Listener>,
+    /// dhcpcd leases of DHCP interfaces that are up, by interface name
+    leases: HashMap<String, Lease>,
+    /// /etc/hosts has edits that have not been written yet
+    hosts_dirty: bool,
+    proxy: ProxySettings,
+    /// Proxy settings have edits that have not been written yet
+    proxy_dirty: bool,
+    /// Interface shown in the statistics popup
+    link_stats: Option<(String, LinkStats)>,
+}
+
+/// Actions that wait for confirmation
+#[derive(Debug, Clone, PartialEq)]
+enum NetworkAction {
+    Restart,
+    LinkDown(String),
+    DeleteRoute(Route),
+}
+
+/// What the text in edit_buffer is being entered for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InputPrompt {
+    /// A field of the interface editor
+    Field,
+    Passphrase,
+    Nameserver,
+    SearchDomain,
+    Hostname,
+    /// A custom port to open in the firewall
+    Port,
+    /// `ip route add` arguments
+    Route,
+    /// "address name [alias...]" for /etc/hosts
+    Host,
+    /// "parent.id" of a VLAN to create
+    Vlan,
+    /// "bridge member..." of a bridge to create
+    Bridge,
+    /// The selected proxy setting
+    Proxy,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum NetworkMode {
     Overview,
     EditInterface,
-    DNS,
+    Dns,
+    Wireless,
+    Firewall,
+    Ports,
+    Routes,
+    Hosts,
+    Proxy,
 }
 
+/// Views reachable with Tab, in order
+const VIEWS: [(NetworkMode, &str); 8] = [
+    (NetworkMode::Overview, "Interfaces"),
+    (NetworkMode::Dns, "DNS"),
+    (NetworkMode::Wireless, "Wireless"),
+    (NetworkMode::Firewall, "Firewall"),
+    (NetworkMode::Ports, "Ports"),
+    (NetworkMode::Routes, "Routes"),
+    (NetworkMode::Hosts, "Hosts"),
+    (NetworkMode::Proxy, "Proxy"),
+];
+
+/// Fields of the proxy view
+const PROXY_FIELDS: [&str; 3] = ["HTTP Proxy", "HTTPS Proxy", "No Proxy For"];
+
 impl NetworkComponent {
     pub fn new() -> Self {
         let mut component = Self {
@@ -53,11 +165,32 @@ impl NetworkComponent {
             mode: NetworkMode::Overview,
             edit_field: 0,
             edit_buffer: String::new(),
-            is_editing: false,
-            dns_servers: Vec::new(),
+            prompt: None,
+            resolv: ResolvConf::default(),
+            dns_dirty: false,
+            dns_overwriters: Vec::new(),
             hostname: String::new(),
             status_message: None,
             show_confirm: false,
+            pending_action: None,
+            draft: None,
+            wireless_iface: None,
+            wireless_networks: Vec::new(),
+            wireless_link: None,
+            firewall_enabled: false,
+            firewall_ports: Vec::new(),
+            firewall_backend: "none",
+            firewall_rules: Vec::new(),
+            firewall_scroll: 0,
+            bandwidth: BandwidthMonitor::new(Duration::from_secs(1)),
+            routes: Vec::new(),
+            hosts: HostsFile::parse(""),
+            listeners: Vec::new(),
+            leases: HashMap::new(),
+            proxy: ProxySettings::default(),
+            proxy_dirty: false,
+            link_stats: None,
+            hosts_dirty: false,
         };
         component.load_network_info();
         if !component.interfaces.is_empty() {
@@ -71,9 +204,13 @@ impl NetworkComponent {
         self.load_interfaces();
         self.load_dns();
         self.load_hostname();
+        self.wireless_iface = wireless::wireless_interfaces().into_iter().next();
+        self.wireless_link = self.wireless_iface.as_deref().and_then(wireless::link_status);
     }
 
     fn load_interfaces(&mut self) {
+        let config = Inet1Config::load().ok();
+
         // Read from /sys/class/net for interface list
         if let Ok(entries) = fs::read_dir("/sys/class/net") {
             for entry in entries.filter_map(|e| e.ok()) {
@@ -92,6 +229,8 @@ impl NetworkComponent {
                     use_dhcp: true,
                     is_up: false,
                     mac_address: String::new(),
+                    mtu: String::new(),
+                    mac_override: String::new(),
                 };
 
                 // Read MAC address
@@ -99,6 +238,9 @@ impl NetworkComponent {
                 if let Ok(mac) = fs::read_to_string(&mac_path) {
                     iface.mac_address = mac.trim().to_string();
                 }
+                if let Ok(mtu) = fs::read_to_string(format!("/sys/class/net/{}/mtu", name)) {
+                    iface.mtu = mtu.trim().to_string();
+                }
 
                 // Check if interface is up
                 let flags_path = format!("/sys/class/net/{}/flags", name);
@@ -130,10 +272,14 @@ impl NetworkComponent {
                 }
 
                 // Read from rc.inet1.conf for static config
-                if let Ok(config) = fs::read_to_string("/etc/rc.d/rc.inet1.conf") {
-                    iface.use_dhcp = Self::is_dhcp_enabled(&config, &name);
+                if let Some(config) = &config {
+                    let index = config.index_for(&name);
+                    iface.use_dhcp = index
+                        .and_then(|i| config.get("USE_DHCP", Some(i)))
+                        .map(|v| v.eq_ignore_ascii_case("yes"))
+                        .unwrap_or(true);
                     if !iface.use_dhcp {
-                        if let Some(gw) = Self::get_config_value(&config, &format!("GATEWAY")) {
+                        if let Some(gw) = config.get("GATEWAY", None) {
                             iface.gateway = gw;
                         }
                     }
@@ -144,6 +290,12 @@ impl NetworkComponent {
         }
 
         self.interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        self.leases = self
+            .interfaces
+            .iter()
+            .filter(|i| i.use_dhcp && i.is_up)
+            .filter_map(|i| Some((i.name.clone(), dhcp::lease(&i.name)?)))
+            .collect();
     }
 
     fn cidr_to_netmask(cidr: &str) -> String {
@@ -162,47 +314,130 @@ impl NetworkComponent {
         )
     }
 
-    fn is_dhcp_enabled(config: &str, iface: &str) -> bool {
-        // Check for USE_DHCP[n]="yes" where n is interface number
-        let iface_num = if iface.starts_with("eth") {
-            iface.trim_start_matches("eth")
-        } else if iface.starts_with("enp") {
-            // For enp* interfaces, try to find the index
-            "0"
+
+    fn load_dns(&mut self) {
+        self.dns_overwriters = Inet1Config::load()
+            .map(|config| resolv::dhcp_overwriters(&config))
+            .unwrap_or_default();
+        // Keep unsaved edits when the tab is re-entered
+        if !self.dns_dirty {
+            self.resolv = ResolvConf::load();
+        }
+    }
+
+    fn dns_len(&self) -> usize {
+        self.resolv.nameservers.len() + self.resolv.search.len()
+    }
+
+    /// The list and index within it backing the selected DNS row
+    fn selected_dns_entry(&mut self) -> Option<(&mut Vec<String>, usize)> {
+        let selected = self.list_state.selected()?;
+        let servers = self.resolv.nameservers.len();
+        if selected < servers {
+            Some((&mut self.resolv.nameservers, selected))
+        } else if selected - servers < self.resolv.search.len() {
+            Some((&mut self.resolv.search, selected - servers))
         } else {
-            "0"
-        };
+            None
+        }
+    }
 
-        for line in config.lines() {
-            let line = line.trim();
-            if line.starts_with(&format!("USE_DHCP[{}]", iface_num)) {
-                return line.contains("yes") || line.contains("YES");
+    fn add_dns_entry(&mut self, search_domain: bool) {
+        let value = self.edit_buffer.trim().to_string();
+        if value.is_empty() {
+            return;
+        }
+        if search_domain {
+            if value.contains(char::is_whitespace) || value.starts_with('.') {
+                self.status_message = Some((trf("Invalid domain: '{}'", &[&value]), true));
+                return;
             }
+            self.resolv.search.push(value);
+            self.list_state.select(Some(self.dns_len() - 1));
+        } else {
+            if value.parse::<IpAddr>().is_err() {
+                self.status_message = Some((trf("Invalid nameserver: '{}'", &[&value]), true));
+                return;
+            }
+            self.resolv.nameservers.push(value);
+            self.list_state.select(Some(self.resolv.nameservers.len() - 1));
         }
-        true // Default to DHCP
+        self.dns_dirty = true;
     }
 
-    fn get_config_value(config: &str, key: &str) -> Option<String> {
-        for line in config.lines() {
-            let line = line.trim();
-            if line.starts_with(key) && line.contains('=') {
-                let value = line.split('=').nth(1)?;
-                return Some(value.trim().trim_matches('"').to_string());
+    fn remove_dns_entry(&mut self) {
+        if let Some((list, i)) = self.selected_dns_entry() {
+            list.remove(i);
+            self.dns_dirty = true;
+            let len = self.dns_len();
+            if len == 0 {
+                self.list_state.select(None);
+            } else if self.list_state.selected().is_some_and(|s| s >= len) {
+                self.list_state.select(Some(len - 1));
             }
         }
-        None
     }
 
-    fn load_dns(&mut self) {
-        self.dns_servers.clear();
-        if let Ok(content) = fs::read_to_string("/etc/resolv.conf") {
-            for line in content.lines() {
-                let line = line.trim();
-                if line.starts_with("nameserver ") {
-                    let server = line.trim_start_matches("nameserver ").trim();
-                    self.dns_servers.push(server.to_string());
+    /// Move the selected entry up or down within its own list
+    fn move_dns_entry(&mut self, up: bool) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        let Some((list, i)) = self.selected_dns_entry() else {
+            return;
+        };
+        let target = if up { i.checked_sub(1) } else { Some(i + 1) };
+        if let Some(target) = target.filter(|t| *t < list.len()) {
+            list.swap(i, target);
+            self.dns_dirty = true;
+            self.list_state.select(Some(if up { selected - 1 } else { selected + 1 }));
+        }
+    }
+
+    fn save_dns(&mut self) {
+        match self.resolv.save() {
+            Ok(backup) => {
+                self.dns_dirty = false;
+                let mut message = trf(
+                    "Wrote {} (previous version in {})",
+                    &[resolv::RESOLV_CONF, &backup.display().to_string()],
+                );
+                if !self.dns_overwriters.is_empty() {
+                    message.push_str(" - ");
+                    message.push_str(tr("DHCP may overwrite it, press 'p' to prevent that"));
                 }
+                self.status_message = Some((message, !self.dns_overwriters.is_empty()));
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+    }
+
+    /// Tell rc.inet1 to keep resolv.conf for all DHCP interfaces
+    fn keep_resolv_conf(&mut self) {
+        if self.dns_overwriters.is_empty() {
+            self.status_message = Some((
+                tr("No DHCP interface overwrites resolv.conf").to_string(),
+                false,
+            ));
+            return;
+        }
+        let result = Inet1Config::load().and_then(|mut config| {
+            for (index, _) in &self.dns_overwriters {
+                config.set("DHCP_KEEPRESOLV", Some(*index), "yes");
             }
+            config.save()
+        });
+        match result {
+            Ok(_) => {
+                let names: Vec<&str> =
+                    self.dns_overwriters.iter().map(|(_, n)| n.as_str()).collect();
+                self.status_message = Some((
+                    trf("Set DHCP_KEEPRESOLV for {} in rc.inet1.conf", &[&names.join(", ")]),
+                    false,
+                ));
+                self.load_dns();
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
         }
     }
 
@@ -214,26 +449,679 @@ impl NetworkComponent {
         }
     }
 
+    fn list_len(&self) -> usize {
+        match self.mode {
+            NetworkMode::Overview => self.interfaces.len(),
+            NetworkMode::Dns => self.dns_len(),
+            NetworkMode::Wireless => self.wireless_networks.len(),
+            NetworkMode::Firewall => self.firewall_entries().len(),
+            NetworkMode::Routes => self.routes.len(),
+            NetworkMode::Ports => self.listeners.len(),
+            NetworkMode::Proxy => PROXY_FIELDS.len(),
+            NetworkMode::Hosts => self.hosts.entries().len(),
+            NetworkMode::EditInterface => EDIT_FIELDS.len(),
+        }
+    }
+
     fn selected_interface(&self) -> Option<&NetworkInterface> {
         self.list_state.selected().and_then(|i| self.interfaces.get(i))
     }
 
-    fn restart_network(&mut self) {
-        self.status_message = Some(("Restarting network...".to_string(), false));
+    /// Start editing the selected interface, seeded from rc.inet1.conf
+    fn start_edit(&mut self) {
+        let Some(iface) = self.selected_interface().cloned() else {
+            return;
+        };
+        let mut draft = iface;
+        // Empty unless rc.inet1.conf sets one, so saving never pins the
+        // live MTU into the file
+        draft.mtu = String::new();
+        if let Ok(config) = Inet1Config::load() {
+            if let Some(i) = config.index_for(&draft.name) {
+                draft.mtu = config.get("MTU", Some(i)).unwrap_or_default();
+                if let Some(ip) = config.get("IPADDR", Some(i)).filter(|v| !v.is_empty()) {
+                    draft.ip_address = ip;
+                }
+                if let Some(mask) = config.get("NETMASK", Some(i)).filter(|v| !v.is_empty()) {
+                    draft.netmask = mask;
+                }
+                draft.mac_override = config.get("HWADDR", Some(i)).unwrap_or_default();
+            }
+            draft.gateway = config.get("GATEWAY", None).unwrap_or_default();
+        }
+        self.draft = Some(draft);
+        self.edit_field = 0;
+        self.mode = NetworkMode::EditInterface;
+        self.status_message = None;
+    }
 
-        match std::process::Command::new("/etc/rc.d/rc.inet1")
-            .arg("restart")
-            .output()
+    fn draft_value(draft: &NetworkInterface, field: usize) -> String {
+        match field {
+            0 => draft.ip_address.clone(),
+            1 => draft.netmask.clone(),
+            2 => draft.gateway.clone(),
+            3 => if draft.use_dhcp { "yes" } else { "no" }.to_string(),
+            4 => draft.mtu.clone(),
+            _ => draft.mac_override.clone(),
+        }
+    }
+
+    fn apply_edit_buffer(&mut self) {
+        let value = self.edit_buffer.trim().to_string();
+        if let Some(draft) = &mut self.draft {
+            match self.edit_field {
+                0 => draft.ip_address = value,
+                1 => draft.netmask = value,
+                2 => draft.gateway = value,
+                4 => draft.mtu = value,
+                5 => draft.mac_override = value.to_lowercase(),
+                _ => {}
+            }
+        }
+    }
+
+    fn validate_draft(draft: &NetworkInterface) -> Result<(), String> {
+        if !draft.mtu.is_empty()
+            && !draft
+                .mtu
+                .parse::<u32>()
+                .is_ok_and(|mtu| (68..=65535).contains(&mtu))
+        {
+            return Err(trf("Invalid MTU: '{}' (68-65535)", &[&draft.mtu]));
+        }
+        if !draft.mac_override.is_empty() && !netdev::is_valid_mac(&draft.mac_override) {
+            return Err(trf("Invalid MAC address: '{}'", &[&draft.mac_override]));
+        }
+        if draft.use_dhcp {
+            return Ok(());
+        }
+        if draft.ip_address.parse::<Ipv4Addr>().is_err() {
+            return Err(trf("Invalid IP address: '{}'", &[&draft.ip_address]));
+        }
+        if !inet1::is_valid_netmask(&draft.netmask) {
+            return Err(trf("Invalid netmask: '{}'", &[&draft.netmask]));
+        }
+        if !draft.gateway.is_empty() && draft.gateway.parse::<Ipv4Addr>().is_err() {
+            return Err(trf("Invalid gateway: '{}'", &[&draft.gateway]));
+        }
+        Ok(())
+    }
+
+    /// Write the edited interface into rc.inet1.conf
+    fn save_draft(&mut self) {
+        let Some(draft) = self.draft.clone() else {
+            return;
+        };
+        if let Err(e) = Self::validate_draft(&draft) {
+            self.status_message = Some((e, true));
+            return;
+        }
+
+        let mut config = match Inet1Config::load() {
+            Ok(config) => config,
+            Err(e) => {
+                self.status_message = Some((e.to_string(), true));
+                return;
+            }
+        };
+        let index = config.index_or_insert(&draft.name);
+        for (key, value) in [("MTU", &draft.mtu), ("HWADDR", &draft.mac_override)] {
+            // Don't add empty overrides to blocks that never had them
+            if !value.is_empty() || config.get(key, Some(index)).is_some() {
+                config.set(key, Some(index), value);
+            }
+        }
+        if draft.use_dhcp {
+            config.set("USE_DHCP", Some(index), "yes");
+        } else {
+            config.set("IPADDR", Some(index), &draft.ip_address);
+            config.set("NETMASK", Some(index), &draft.netmask);
+            config.set("USE_DHCP", Some(index), "");
+            config.set("GATEWAY", None, &draft.gateway);
+        }
+
+        // Link settings take effect right away; addressing waits for a restart
+        let current = self.interfaces.iter().find(|i| i.name == draft.name);
+        let mut applied = Vec::new();
+        let mtu_changed = current.is_some_and(|c| c.mtu != draft.mtu);
+        if let Some(mtu) = draft.mtu.parse().ok().filter(|_| mtu_changed) {
+            match netdev::set_mtu(&draft.name, mtu) {
+                Ok(()) => applied.push(tr("MTU")),
+                Err(e) => {
+                    self.status_message = Some((e.to_string(), true));
+                    return;
+                }
+            }
+        }
+        if !draft.mac_override.is_empty()
+            && current.is_some_and(|c| !c.mac_address.eq_ignore_ascii_case(&draft.mac_override))
         {
+            match netdev::set_mac(&draft.name, &draft.mac_override) {
+                Ok(()) => applied.push(tr("MAC address")),
+                Err(e) => {
+                    self.status_message = Some((e.to_string(), true));
+                    return;
+                }
+            }
+        }
+
+        match config.save() {
+            Ok(backup) => {
+                let applied = if applied.is_empty() {
+                    String::new()
+                } else {
+                    let and = format!(" {} ", tr("and"));
+                    format!(", {}", trf("applied {}", &[&applied.join(&and)]))
+                };
+                self.status_message = Some((
+                    trf(
+                        "Saved {} config{} (previous version in {})",
+                        &[&draft.name, &applied, &backup.display().to_string()],
+                    ),
+                    false,
+                ));
+                self.draft = None;
+                self.mode = NetworkMode::Overview;
+                self.load_network_info();
+                self.pending_action = Some(NetworkAction::Restart);
+                self.show_confirm = true;
+            }
+            Err(e) => {
+                self.status_message = Some((e.to_string(), true));
+            }
+        }
+    }
+
+    fn scan_wireless(&mut self) {
+        let Some(iface) = self.wireless_iface.clone() else {
+            self.status_message = Some((tr("No wireless interface found").to_string(), true));
+            return;
+        };
+        match wireless::scan(&iface) {
+            Ok(networks) => {
+                self.status_message = Some((
+                    trf("Found {} network(s) on {}", &[&networks.len().to_string(), &iface]),
+                    false,
+                ));
+                self.wireless_networks = networks;
+                self.list_state.select(if self.wireless_networks.is_empty() {
+                    None
+                } else {
+                    Some(0)
+                });
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+    }
+
+    fn selected_wireless(&self) -> Option<&WirelessNetwork> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.wireless_networks.get(i))
+    }
+
+    /// Save credentials for the selected network and associate with it
+    fn connect_wireless(&mut self, passphrase: Option<&str>) {
+        let (Some(iface), Some(network)) = (self.wireless_iface.clone(), self.selected_wireless())
+        else {
+            return;
+        };
+        let ssid = network.ssid.clone();
+
+        let result = wireless::configure_network(&ssid, passphrase)
+            .and_then(|_| wireless::enable_at_boot(&iface))
+            .and_then(|()| wireless::associate(&iface));
+        self.status_message = Some(match result {
+            Ok(()) => (trf("Associating {} with {}...", &[&iface, &ssid]), false),
+            Err(e) => (trf("Failed to connect to {}: {}", &[&ssid, &e.to_string()]), true),
+        });
+        self.wireless_link = wireless::link_status(&iface);
+    }
+
+    fn change_hostname(&mut self) {
+        let new = self.edit_buffer.trim().to_string();
+        if new.is_empty() || new == self.hostname {
+            return;
+        }
+        match hosts::set_hostname(&self.hostname, &new) {
+            Ok(()) => {
+                self.status_message = Some((trf("Hostname changed to {}", &[&new]), false));
+                self.load_hostname();
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+    }
+
+    fn load_firewall(&mut self) {
+        self.firewall_enabled = firewall::is_enabled();
+        self.firewall_ports = if firewall::is_preset() {
+            firewall::open_ports()
+        } else {
+            // Keep SSH reachable when the preset is first enabled
+            vec![OpenPort::new(22, "tcp")]
+        };
+        let (backend, rules) = firewall::current_rules();
+        self.firewall_backend = backend;
+        self.firewall_rules = rules;
+        self.firewall_scroll = 0;
+    }
+
+    /// Rows of the port list: common services plus any custom open ports
+    fn firewall_entries(&self) -> Vec<(String, OpenPort)> {
+        let mut entries: Vec<(String, OpenPort)> = firewall::COMMON_PORTS
+            .iter()
+            .map(|(name, port, proto)| (name.to_string(), OpenPort::new(*port, proto)))
+            .collect();
+        for port in &self.firewall_ports {
+            if !entries.iter().any(|(_, p)| p == port) {
+                entries.push((tr("Custom").to_string(), port.clone()));
+            }
+        }
+        entries
+    }
+
+    /// Regenerate and load rc.firewall when the preset is active
+    fn apply_firewall(&mut self, message: String) {
+        if !self.firewall_enabled {
+            self.status_message = Some((
+                format!("{} - {}", message, tr("press 'f' to enable the firewall")),
+                false,
+            ));
+            return;
+        }
+        match firewall::install_preset(&self.firewall_ports) {
+            Ok(_) => self.status_message = Some((message, false)),
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+        let selected = self.list_state.selected();
+        self.load_firewall();
+        self.list_state.select(selected);
+    }
+
+    fn toggle_firewall_port(&mut self) {
+        let Some((name, port)) = self
+            .list_state
+            .selected()
+            .and_then(|i| self.firewall_entries().into_iter().nth(i))
+        else {
+            return;
+        };
+        let message = if let Some(pos) = self.firewall_ports.iter().position(|p| *p == port) {
+            self.firewall_ports.remove(pos);
+            trf("Closed {} ({})", &[&port.display(), &name])
+        } else {
+            self.firewall_ports.push(port.clone());
+            trf("Opened {} ({})", &[&port.display(), &name])
+        };
+        self.apply_firewall(message);
+    }
+
+    fn open_custom_port(&mut self) {
+        match OpenPort::parse(&self.edit_buffer) {
+            Some(port) if self.firewall_ports.contains(&port) => {
+                self.status_message =
+                    Some((trf("{} is already open", &[&port.display()]), false));
+            }
+            Some(port) => {
+                let message = trf("Opened {}", &[&port.display()]);
+                self.firewall_ports.push(port);
+                self.apply_firewall(message);
+            }
+            None => {
+                self.status_message = Some((
+                    trf("Invalid port '{}', expected e.g. 8080/tcp", &[self.edit_buffer.trim()]),
+                    true,
+                ));
+            }
+        }
+    }
+
+    fn toggle_firewall(&mut self) {
+        let result = if self.firewall_enabled {
+            firewall::disable().map(|()| tr("Firewall disabled").to_string())
+        } else {
+            firewall::install_preset(&self.firewall_ports).map(|backup| match backup {
+                Some(backup) => trf(
+                    "Firewall enabled ({} previous script in {})",
+                    &[firewall::RC_FIREWALL, &backup.display().to_string()],
+                ),
+                None => trf("Firewall enabled ({})", &[firewall::RC_FIREWALL]),
+            })
+        };
+        self.status_message = Some(match result {
+            Ok(message) => (message, false),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_firewall();
+    }
+
+    /// Bring an interface up or down, falling back to ifconfig
+    fn set_link(&mut self, iface: &str, up: bool) {
+        let state = if up { "up" } else { "down" };
+        let result = match audit::output(root::command("ip").args(["link", "set", iface, state])) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                audit::output(root::command("ifconfig").args([iface, state]))
+            }
+            result => result,
+        };
+        self.status_message = Some(match result {
+            Ok(output) if output.status.success() => (trf("{} is now {}", &[iface, state]), false),
+            Ok(output) => (
+                trf(
+                    "Failed to bring {} {}: {}",
+                    &[iface, state, String::from_utf8_lossy(&output.stderr).trim()],
+                ),
+                true,
+            ),
+            Err(e) => (trf("Error: {}", &[&e.to_string()]), true),
+        });
+        self.reload_interfaces();
+    }
+
+    /// Request a DHCP lease for an interface now
+    fn run_dhcpcd(&mut self, iface: &str) {
+        // -n rebinds a running dhcpcd, or starts one if there is none
+        let result = audit::output(root::command("dhcpcd").args(["-n", "-t", "15", iface]));
+        self.status_message = Some(match result {
+            Ok(output) if output.status.success() => {
+                (trf("dhcpcd requested a lease for {}", &[iface]), false)
+            }
+            Ok(output) => (
+                trf(
+                    "dhcpcd failed on {}: {}",
+                    &[iface, String::from_utf8_lossy(&output.stderr).trim()],
+                ),
+                true,
+            ),
+            Err(e) => (trf("Failed to run dhcpcd: {}", &[&e.to_string()]), true),
+        });
+        self.reload_interfaces();
+    }
+
+    fn create_vlan(&mut self) {
+        let spec = std::mem::take(&mut self.edit_buffer);
+        if spec.trim().is_empty() {
+            return;
+        }
+        self.status_message = Some(match netdev::create_vlan(&spec) {
+            Ok((vlan, persisted_in)) => (
+                trf(
+                    "Created {} (persisted in {}) - press Enter to set its address",
+                    &[&vlan.name(), persisted_in],
+                ),
+                false,
+            ),
+            Err(e) => (e.to_string(), true),
+        });
+        self.reload_interfaces();
+    }
+
+    fn create_bridge(&mut self) {
+        let spec = std::mem::take(&mut self.edit_buffer);
+        if spec.trim().is_empty() {
+            return;
+        }
+        self.status_message = Some(match netdev::create_bridge(&spec) {
+            Ok(bridge) => (
+                trf(
+                    "Created bridge {} with {} - press Enter to set its address",
+                    &[&bridge.name, &bridge.members.join(", ")],
+                ),
+                false,
+            ),
+            Err(e) => (e.to_string(), true),
+        });
+        self.reload_interfaces();
+    }
+
+    fn renew_lease(&mut self, iface: &str) {
+        if !self.leases.contains_key(iface) {
+            self.status_message = Some((
+                trf("{} has no DHCP lease to renew; press 'c' to request one", &[iface]),
+                true,
+            ));
+            return;
+        }
+        self.status_message = Some(match dhcp::renew(iface) {
+            Ok(()) => (trf("Asked dhcpcd to renew the lease on {}", &[iface]), false),
+            Err(e) => (e.to_string(), true),
+        });
+        self.reload_interfaces();
+    }
+
+    /// Re-read interface state, keeping the same interface selected
+    fn reload_interfaces(&mut self) {
+        let selected = self.selected_interface().map(|i| i.name.clone());
+        self.interfaces.clear();
+        self.load_interfaces();
+        let index = selected.and_then(|name| self.interfaces.iter().position(|i| i.name == name));
+        self.list_state.select(index.or(if self.interfaces.is_empty() { None } else { Some(0) }));
+    }
+
+    fn load_routes(&mut self) {
+        match routes::list() {
+            Ok(list) => {
+                self.routes = list
+                    .into_iter()
+                    .map(|r| {
+                        let persisted = routes::is_persisted(&r);
+                        (r, persisted)
+                    })
+                    .collect();
+            }
+            Err(e) => {
+                self.routes.clear();
+                self.status_message = Some((trf("Failed to read routes: {}", &[&e.to_string()]), true));
+            }
+        }
+        let len = self.routes.len();
+        if self.list_state.selected().is_some_and(|s| s >= len) {
+            self.list_state.select(len.checked_sub(1));
+        }
+    }
+
+    fn load_listeners(&mut self) {
+        match listeners::list() {
+            Ok(list) => self.listeners = list,
+            Err(e) => {
+                self.listeners.clear();
+                self.status_message =
+                    Some((trf("Failed to list listening sockets: {}", &[&e.to_string()]), true));
+            }
+        }
+        let len = self.listeners.len();
+        if self.list_state.selected().is_some_and(|s| s >= len) {
+            self.list_state.select(len.checked_sub(1));
+        }
+    }
+
+    fn selected_route(&self) -> Option<&(Route, bool)> {
+        self.list_state.selected().and_then(|i| self.routes.get(i))
+    }
+
+    fn add_route(&mut self) {
+        let spec = self.edit_buffer.trim().to_string();
+        if spec.is_empty() {
+            return;
+        }
+        self.status_message = Some(match routes::add(&spec) {
+            Ok(route) => (
+                trf(
+                    "Added route {} - press 'p' to keep it across reboots",
+                    &[&route.spec().join(" ")],
+                ),
+                false,
+            ),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_routes();
+    }
+
+    fn delete_route(&mut self, route: &Route) {
+        let mut result = routes::delete(route);
+        // Don't bring a deleted route back at the next boot
+        if result.is_ok() && routes::is_persisted(route) {
+            result = routes::set_persisted(route, false);
+        }
+        self.status_message = Some(match result {
+            Ok(()) => (trf("Deleted route {}", &[&route.destination]), false),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_routes();
+    }
+
+    fn toggle_route_persisted(&mut self) {
+        let Some((route, persisted)) = self.selected_route().cloned() else {
+            return;
+        };
+        if !persisted && route.is_kernel() {
+            self.status_message = Some((
+                tr("Connected-subnet routes are created by the kernel automatically").to_string(),
+                true,
+            ));
+            return;
+        }
+        let target = if route.is_default() {
+            "rc.inet1.conf GATEWAY"
+        } else {
+            "rc.local"
+        };
+        self.status_message = Some(match routes::set_persisted(&route, !persisted) {
+            Ok(()) if persisted => (
+                trf("Route {} no longer persisted in {}", &[&route.destination, target]),
+                false,
+            ),
+            Ok(()) => (
+                trf("Route {} persisted in {}", &[&route.destination, target]),
+                false,
+            ),
+            Err(e) => (e.to_string(), true),
+        });
+        self.load_routes();
+    }
+
+    fn load_hosts(&mut self) {
+        // Keep unsaved edits when the view is re-entered
+        if !self.hosts_dirty {
+            self.hosts = HostsFile::load();
+        }
+    }
+
+    fn add_host(&mut self) {
+        let spec = self.edit_buffer.trim().to_string();
+        if spec.is_empty() {
+            return;
+        }
+        match self.hosts.add(&spec) {
+            Ok(entry) => {
+                self.hosts_dirty = true;
+                let duplicates = self.hosts.duplicates();
+                let clashes: Vec<&str> = entry
+                    .names
+                    .iter()
+                    .filter(|n| duplicates.contains_key(&n.to_lowercase()))
+                    .map(|n| n.as_str())
+                    .collect();
+                self.status_message = Some(if clashes.is_empty() {
+                    (trf("Added {} - press 'w' to write", &[&entry.address]), false)
+                } else {
+                    (
+                        trf(
+                            "Added {}, but {} already listed",
+                            &[&entry.address, &clashes.join(", ")],
+                        ),
+                        true,
+                    )
+                });
+                self.list_state.select(Some(self.hosts.entries().len() - 1));
+            }
+            Err(e) => self.status_message = Some((e.to_string(), true)),
+        }
+    }
+
+    fn remove_host(&mut self) {
+        let entries = self.hosts.entries();
+        let Some(entry) = self.list_state.selected().and_then(|i| entries.get(i)) else {
+            return;
+        };
+        self.hosts.remove(entry.line);
+        self.hosts_dirty = true;
+        let len = entries.len() - 1;
+        if self.list_state.selected().is_some_and(|s| s >= len) {
+            self.list_state.select(len.checked_sub(1));
+        }
+    }
+
+    fn save_hosts(&mut self) {
+        self.status_message = Some(match self.hosts.save() {
+            Ok(()) => {
+                self.hosts_dirty = false;
+                (trf("Wrote {}", &[hosts::HOSTS_FILE]), false)
+            }
+            Err(e) => (e.to_string(), true),
+        });
+    }
+
+    fn load_proxy(&mut self) {
+        if !self.proxy_dirty {
+            self.proxy = ProxySettings::load();
+        }
+    }
+
+    fn selected_proxy_field(&mut self) -> Option<&mut String> {
+        match self.list_state.selected()? {
+            0 => Some(&mut self.proxy.http),
+            1 => Some(&mut self.proxy.https),
+            2 => Some(&mut self.proxy.no_proxy),
+            _ => None,
+        }
+    }
+
+    fn apply_proxy_field(&mut self) {
+        let value = self.edit_buffer.trim().to_string();
+        let is_url_field = self.list_state.selected() != Some(2);
+        if is_url_field && !value.is_empty() && !value.contains("://") {
+            self.status_message = Some((
+                trf("'{}' should be a URL such as http://proxy:3128/", &[&value]),
+                true,
+            ));
+            return;
+        }
+        if let Some(field) = self.selected_proxy_field() {
+            *field = value;
+            self.proxy_dirty = true;
+        }
+    }
+
+    fn save_proxy(&mut self) {
+        self.status_message = Some(match self.proxy.save() {
+            Ok(()) => {
+                self.proxy_dirty = false;
+                let message = if self.proxy.is_empty() {
+                    tr("Proxy disabled").to_string()
+                } else {
+                    trf("Wrote {} and {}", &[proxy::PROFILE_SH, proxy::WGETRC])
+                };
+                (message, false)
+            }
+            Err(e) => (e.to_string(), true),
+        });
+    }
+
+    fn restart_network(&mut self) {
+        self.status_message = Some((tr("Restarting network...").to_string(), false));
+
+        match audit::output(root::command("/etc/rc.d/rc.inet1").arg("restart")) {
             Ok(output) => {
                 if output.status.success() {
-                    self.status_message = Some(("Network restarted successfully".to_string(), false));
+                    self.status_message =
+                        Some((tr("Network restarted successfully").to_string(), false));
                 } else {
-                    self.status_message = Some(("Failed to restart network".to_string(), true));
+                    self.status_message = Some((tr("Failed to restart network").to_string(), true));
                 }
             }
             Err(e) => {
-                self.status_message = Some((format!("Error: {}", e), true));
+                self.status_message = Some((trf("Error: {}", &[&e.to_string()]), true));
             }
         }
 
@@ -247,20 +1135,59 @@ impl Component for NetworkComponent {
             match key.code {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
                     self.show_confirm = false;
-                    self.restart_network();
+                    match self.pending_action.take() {
+                        Some(NetworkAction::Restart) => self.restart_network(),
+                        Some(NetworkAction::LinkDown(iface)) => self.set_link(&iface, false),
+                        Some(NetworkAction::DeleteRoute(route)) => self.delete_route(&route),
+                        None => {}
+                    }
                 }
                 KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                     self.show_confirm = false;
+                    self.pending_action = None;
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some((iface, _)) = &self.link_stats {
+            match key.code {
+                KeyCode::F(5) => {
+                    let iface = iface.clone();
+                    let stats = LinkStats::load(&iface);
+                    self.link_stats = Some((iface, stats));
                 }
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('i') => self.link_stats = None,
                 _ => {}
             }
             return None;
         }
 
-        if self.is_editing {
+        if let Some(prompt) = self.prompt {
             match key.code {
-                KeyCode::Enter | KeyCode::Esc => {
-                    self.is_editing = false;
+                KeyCode::Enter => {
+                    self.prompt = None;
+                    match prompt {
+                        InputPrompt::Field => self.apply_edit_buffer(),
+                        InputPrompt::Passphrase => {
+                            let passphrase = std::mem::take(&mut self.edit_buffer);
+                            self.connect_wireless(Some(&passphrase));
+                        }
+                        InputPrompt::Nameserver => self.add_dns_entry(false),
+                        InputPrompt::SearchDomain => self.add_dns_entry(true),
+                        InputPrompt::Hostname => self.change_hostname(),
+                        InputPrompt::Port => self.open_custom_port(),
+                        InputPrompt::Route => self.add_route(),
+                        InputPrompt::Host => self.add_host(),
+                        InputPrompt::Vlan => self.create_vlan(),
+                        InputPrompt::Bridge => self.create_bridge(),
+                        InputPrompt::Proxy => self.apply_proxy_field(),
+                    }
+                }
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.edit_buffer.clear();
                 }
                 KeyCode::Backspace => {
                     self.edit_buffer.pop();
@@ -273,34 +1200,193 @@ impl Component for NetworkComponent {
             return None;
         }
 
-        match key.code {
-            KeyCode::Tab => {
-                self.mode = match self.mode {
-                    NetworkMode::Overview => NetworkMode::DNS,
-                    NetworkMode::DNS => NetworkMode::Overview,
-                    NetworkMode::EditInterface => NetworkMode::Overview,
-                };
-            }
-            KeyCode::Up | KeyCode::Char('k') => {
-                let len = match self.mode {
-                    NetworkMode::Overview => self.interfaces.len(),
-                    NetworkMode::DNS => self.dns_servers.len(),
-                    NetworkMode::EditInterface => 4, // IP, Netmask, Gateway, DHCP
-                };
-                if let Some(selected) = self.list_state.selected() {
-                    if selected > 0 {
-                        self.list_state.select(Some(selected - 1));
+        if self.mode == NetworkMode::EditInterface {
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.edit_field = self.edit_field.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => {
+                    self.edit_field = (self.edit_field + 1).min(EDIT_FIELDS.len() - 1);
+                }
+                KeyCode::Char(' ') if self.edit_field == 3 => {
+                    if let Some(draft) = &mut self.draft {
+                        draft.use_dhcp = !draft.use_dhcp;
+                    }
+                }
+                KeyCode::Enter => {
+                    if let Some(draft) = &mut self.draft {
+                        if self.edit_field == 3 {
+                            draft.use_dhcp = !draft.use_dhcp;
+                        } else {
+                            self.edit_buffer = Self::draft_value(draft, self.edit_field);
+                            self.prompt = Some(InputPrompt::Field);
+                        }
+                    }
+                }
+                KeyCode::Char('s') => self.save_draft(),
+                KeyCode::Esc => {
+                    self.draft = None;
+                    self.mode = NetworkMode::Overview;
+                    self.status_message = Some((tr("Edit cancelled").to_string(), false));
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('e') if self.mode == NetworkMode::Overview => {
+                self.start_edit();
+            }
+            KeyCode::Char('u') if self.mode == NetworkMode::Overview => {
+                if let Some(name) = self.selected_interface().map(|i| i.name.clone()) {
+                    self.set_link(&name, true);
+                }
+            }
+            KeyCode::Char('d') if self.mode == NetworkMode::Overview => {
+                if let Some(name) = self.selected_interface().map(|i| i.name.clone()) {
+                    self.status_message = None;
+                    self.pending_action = Some(NetworkAction::LinkDown(name));
+                    self.show_confirm = true;
+                }
+            }
+            KeyCode::Char('c') if self.mode == NetworkMode::Overview => {
+                if let Some(name) = self.selected_interface().map(|i| i.name.clone()) {
+                    self.run_dhcpcd(&name);
+                }
+            }
+            KeyCode::Char('i') if self.mode == NetworkMode::Overview => {
+                if let Some(name) = self.selected_interface().map(|i| i.name.clone()) {
+                    let stats = LinkStats::load(&name);
+                    self.link_stats = Some((name, stats));
+                }
+            }
+            KeyCode::Char('n') if self.mode == NetworkMode::Overview => {
+                if let Some(name) = self.selected_interface().map(|i| i.name.clone()) {
+                    self.renew_lease(&name);
+                }
+            }
+            KeyCode::Char('v') if self.mode == NetworkMode::Overview => {
+                self.edit_buffer = self
+                    .selected_interface()
+                    .map(|i| format!("{}.", i.name))
+                    .unwrap_or_default();
+                self.prompt = Some(InputPrompt::Vlan);
+            }
+            KeyCode::Char('b') if self.mode == NetworkMode::Overview => {
+                self.edit_buffer = self
+                    .selected_interface()
+                    .map(|i| format!("br0 {}", i.name))
+                    .unwrap_or_default();
+                self.prompt = Some(InputPrompt::Bridge);
+            }
+            KeyCode::Char('h') => {
+                self.edit_buffer = self.hostname.clone();
+                self.prompt = Some(InputPrompt::Hostname);
+            }
+            KeyCode::Char('a') | KeyCode::Char('d') if self.mode == NetworkMode::Dns => {
+                self.prompt = Some(if key.code == KeyCode::Char('a') {
+                    InputPrompt::Nameserver
+                } else {
+                    InputPrompt::SearchDomain
+                });
+                self.edit_buffer.clear();
+            }
+            KeyCode::Char('x') | KeyCode::Delete if self.mode == NetworkMode::Dns => {
+                self.remove_dns_entry();
+            }
+            KeyCode::Char('K') if self.mode == NetworkMode::Dns => self.move_dns_entry(true),
+            KeyCode::Char('J') if self.mode == NetworkMode::Dns => self.move_dns_entry(false),
+            KeyCode::Char('w') if self.mode == NetworkMode::Dns => self.save_dns(),
+            KeyCode::Char('p') if self.mode == NetworkMode::Dns => self.keep_resolv_conf(),
+            KeyCode::Char('a') if self.mode == NetworkMode::Hosts => {
+                self.edit_buffer.clear();
+                self.prompt = Some(InputPrompt::Host);
+            }
+            KeyCode::Char('x') | KeyCode::Delete if self.mode == NetworkMode::Hosts => {
+                self.remove_host();
+            }
+            KeyCode::Char('w') if self.mode == NetworkMode::Hosts => self.save_hosts(),
+            KeyCode::Enter if self.mode == NetworkMode::Proxy => {
+                if let Some(field) = self.selected_proxy_field() {
+                    self.edit_buffer = field.clone();
+                    self.prompt = Some(InputPrompt::Proxy);
+                }
+            }
+            KeyCode::Char('x') | KeyCode::Delete if self.mode == NetworkMode::Proxy => {
+                if let Some(field) = self.selected_proxy_field() {
+                    field.clear();
+                    self.proxy_dirty = true;
+                }
+            }
+            KeyCode::Char('w') if self.mode == NetworkMode::Proxy => self.save_proxy(),
+            KeyCode::Char('a') if self.mode == NetworkMode::Routes => {
+                self.edit_buffer.clear();
+                self.prompt = Some(InputPrompt::Route);
+            }
+            KeyCode::Char('x') | KeyCode::Delete if self.mode == NetworkMode::Routes => {
+                if let Some((route, _)) = self.selected_route().cloned() {
+                    self.status_message = None;
+                    self.pending_action = Some(NetworkAction::DeleteRoute(route));
+                    self.show_confirm = true;
+                }
+            }
+            KeyCode::Char('p') if self.mode == NetworkMode::Routes => self.toggle_route_persisted(),
+            KeyCode::Char(' ') if self.mode == NetworkMode::Firewall => {
+                self.toggle_firewall_port();
+            }
+            KeyCode::Char('o') if self.mode == NetworkMode::Firewall => {
+                self.edit_buffer.clear();
+                self.prompt = Some(InputPrompt::Port);
+            }
+            KeyCode::Char('f') if self.mode == NetworkMode::Firewall => self.toggle_firewall(),
+            KeyCode::PageUp if self.mode == NetworkMode::Firewall => {
+                self.firewall_scroll = self.firewall_scroll.saturating_sub(10);
+            }
+            KeyCode::PageDown if self.mode == NetworkMode::Firewall => {
+                self.firewall_scroll = self.firewall_scroll.saturating_add(10);
+            }
+            KeyCode::Char('s') if self.mode == NetworkMode::Wireless => {
+                self.scan_wireless();
+            }
+            KeyCode::Enter if self.mode == NetworkMode::Wireless => {
+                if let Some(network) = self.selected_wireless() {
+                    if network.secured {
+                        self.edit_buffer.clear();
+                        self.prompt = Some(InputPrompt::Passphrase);
+                    } else {
+                        self.connect_wireless(None);
+                    }
+                }
+            }
+            KeyCode::Tab => {
+                let current = VIEWS.iter().position(|(m, _)| *m == self.mode);
+                self.mode = current
+                    .map(|i| VIEWS[(i + 1) % VIEWS.len()].0)
+                    .unwrap_or(NetworkMode::Overview);
+                match self.mode {
+                    NetworkMode::Firewall => self.load_firewall(),
+                    NetworkMode::Routes => self.load_routes(),
+                    NetworkMode::Ports => self.load_listeners(),
+                    NetworkMode::Hosts => self.load_hosts(),
+                    NetworkMode::Proxy => self.load_proxy(),
+                    _ => {}
+                }
+                let len = self.list_len();
+                self.list_state.select(if len == 0 { None } else { Some(0) });
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                let len = self.list_len();
+                if let Some(selected) = self.list_state.selected() {
+                    if selected > 0 {
+                        self.list_state.select(Some(selected - 1));
                     }
                 } else if len > 0 {
                     self.list_state.select(Some(0));
                 }
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                let len = match self.mode {
-                    NetworkMode::Overview => self.interfaces.len(),
-                    NetworkMode::DNS => self.dns_servers.len(),
-                    NetworkMode::EditInterface => 4,
-                };
+                let len = self.list_len();
                 if let Some(selected) = self.list_state.selected() {
                     if selected < len.saturating_sub(1) {
                         self.list_state.select(Some(selected + 1));
@@ -310,11 +1396,24 @@ impl Component for NetworkComponent {
                 }
             }
             KeyCode::Char('r') => {
+                self.status_message = None;
+                self.pending_action = Some(NetworkAction::Restart);
                 self.show_confirm = true;
             }
             KeyCode::F(5) => {
+                self.dns_dirty = false;
+                self.hosts_dirty = false;
+                self.proxy_dirty = false;
                 self.load_network_info();
-                self.status_message = Some(("Network info refreshed".to_string(), false));
+                match self.mode {
+                    NetworkMode::Firewall => self.load_firewall(),
+                    NetworkMode::Routes => self.load_routes(),
+                    NetworkMode::Ports => self.load_listeners(),
+                    NetworkMode::Hosts => self.load_hosts(),
+                    NetworkMode::Proxy => self.load_proxy(),
+                    _ => {}
+                }
+                self.status_message = Some((tr("Network info refreshed").to_string(), false));
             }
             _ => {}
         }
@@ -333,17 +1432,27 @@ impl Component for NetworkComponent {
             .split(area);
 
         // Mode bar
-        let mode_text = match self.mode {
-            NetworkMode::Overview => "[Interfaces]  DNS",
-            NetworkMode::DNS => " Interfaces  [DNS]",
-            NetworkMode::EditInterface => " Edit Interface ",
+        let mode_text = if self.mode == NetworkMode::EditInterface {
+            " Edit Interface ".to_string()
+        } else {
+            VIEWS
+                .iter()
+                .map(|(mode, name)| {
+                    if *mode == self.mode {
+                        format!("[{}]", name)
+                    } else {
+                        format!(" {} ", name)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
         };
         let mode_bar = Paragraph::new(Line::from(vec![
-            Span::styled("View: ", Style::default().fg(Color::Cyan)),
+            Span::styled("View: ", Theme::label()),
             Span::raw(mode_text),
             Span::styled(
                 format!("  Hostname: {}", self.hostname),
-                Style::default().fg(Color::DarkGray),
+                Theme::muted(),
             ),
         ]))
         .block(
@@ -356,46 +1465,222 @@ impl Component for NetworkComponent {
         // Main content
         match self.mode {
             NetworkMode::Overview => self.render_interfaces(frame, chunks[1]),
-            NetworkMode::DNS => self.render_dns(frame, chunks[1]),
+            NetworkMode::Dns => self.render_dns(frame, chunks[1]),
             NetworkMode::EditInterface => self.render_edit(frame, chunks[1]),
+            NetworkMode::Wireless => self.render_wireless(frame, chunks[1]),
+            NetworkMode::Firewall => self.render_firewall(frame, chunks[1]),
+            NetworkMode::Routes => self.render_routes(frame, chunks[1]),
+            NetworkMode::Ports => self.render_listeners(frame, chunks[1]),
+            NetworkMode::Hosts => self.render_hosts(frame, chunks[1]),
+            NetworkMode::Proxy => self.render_proxy(frame, chunks[1]),
         }
 
         // Info panel
-        self.render_info(frame, chunks[2]);
+        let info_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[2]);
+        self.render_info(frame, info_chunks[0]);
+        self.render_bandwidth(frame, info_chunks[1]);
 
         // Status bar
         let status_content = if self.show_confirm {
+            let question = match &self.pending_action {
+                Some(NetworkAction::LinkDown(iface)) => format!("Bring {} down? ", iface),
+                Some(NetworkAction::DeleteRoute(route)) => {
+                    format!("Delete route {}? ", route.spec().join(" "))
+                }
+                _ => "Restart network? ".to_string(),
+            };
+            let mut spans = Vec::new();
+            if let Some((msg, _)) = &self.status_message {
+                spans.push(Span::styled(format!("{}  ", msg), Theme::success()));
+            }
+            spans.push(Span::styled(question, Theme::warning()));
+            spans.push(Span::raw(tr("[Y]es / [N]o")));
+            Line::from(spans)
+        } else if let Some(prompt) = self.prompt.filter(|p| *p != InputPrompt::Field) {
+            let (label, value) = match prompt {
+                InputPrompt::Passphrase => ("Passphrase: ", "*".repeat(self.edit_buffer.len())),
+                InputPrompt::Nameserver => ("Nameserver: ", self.edit_buffer.clone()),
+                InputPrompt::SearchDomain => ("Search domain: ", self.edit_buffer.clone()),
+                InputPrompt::Hostname => ("New hostname: ", self.edit_buffer.clone()),
+                InputPrompt::Port => ("Open port (e.g. 8080/tcp): ", self.edit_buffer.clone()),
+                InputPrompt::Route => (
+                    "Route (e.g. 10.0.0.0/8 via 192.168.1.254): ",
+                    self.edit_buffer.clone(),
+                ),
+                InputPrompt::Host => ("Host (address name [alias...]): ", self.edit_buffer.clone()),
+                InputPrompt::Vlan => ("VLAN (e.g. eth0.10): ", self.edit_buffer.clone()),
+                InputPrompt::Bridge => ("Bridge (e.g. br0 eth0): ", self.edit_buffer.clone()),
+                InputPrompt::Proxy => (
+                    if self.list_state.selected() == Some(2) {
+                        "No proxy for (e.g. localhost,.lan): "
+                    } else {
+                        "Proxy URL (e.g. http://proxy:3128/): "
+                    },
+                    self.edit_buffer.clone(),
+                ),
+                InputPrompt::Field => ("", String::new()),
+            };
             Line::from(vec![
-                Span::styled("Restart network? ", Style::default().fg(Color::Yellow)),
-                Span::raw("[Y]es / [N]o"),
+                Span::styled(label, Theme::warning()),
+                Span::styled(format!("{}_", value), Theme::input_active()),
+                Span::styled(
+                    "  Enter to apply, Esc to cancel",
+                    Theme::muted(),
+                ),
             ])
         } else if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
+            ))
+        } else if self.mode == NetworkMode::EditInterface {
+            Line::from(Span::styled(
+                "Enter to edit field, Space toggles DHCP, 's' to save, Esc to cancel",
+                Theme::muted(),
             ))
         } else {
             Line::from(Span::styled(
                 "Press 'r' to restart network",
-                Style::default().fg(Color::DarkGray),
+                Theme::muted(),
             ))
         };
 
         let status = Paragraph::new(status_content)
             .block(Block::default().borders(Borders::ALL));
         frame.render_widget(status, chunks[3]);
+
+        if let Some((iface, stats)) = &self.link_stats {
+            self.render_link_stats(frame, area, iface, stats);
+        }
+    }
+
+    fn is_typing(&self) -> bool {
+        self.prompt.is_some()
     }
 
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        if self.mode == NetworkMode::EditInterface {
+            return vec![
+                ("↑↓", "Field"),
+                ("Enter", "Edit"),
+                ("Space", "DHCP"),
+                ("s", "Save"),
+                ("Esc", "Cancel"),
+            ];
+        }
+        if self.mode == NetworkMode::Dns {
+            return vec![
+                ("a/d", "Add Server/Domain"),
+                ("x", "Remove"),
+                ("J/K", "Reorder"),
+                ("w", "Write"),
+                ("p", "Keep on DHCP"),
+            ];
+        }
+        if self.mode == NetworkMode::Hosts {
+            return vec![
+                ("Tab", "Switch View"),
+                ("a", "Add Host"),
+                ("x", "Remove"),
+                ("w", "Write"),
+            ];
+        }
+        if self.mode == NetworkMode::Proxy {
+            return vec![
+                ("Tab", "Switch View"),
+                ("Enter", "Edit"),
+                ("x", "Clear"),
+                ("w", "Write"),
+            ];
+        }
+        if self.mode == NetworkMode::Ports {
+            return vec![("Tab", "Switch View"), ("F5", "Refresh")];
+        }
+        if self.mode == NetworkMode::Routes {
+            return vec![
+                ("Tab", "Switch View"),
+                ("a", "Add Route"),
+                ("x", "Delete"),
+                ("p", "Persist"),
+            ];
+        }
+        if self.mode == NetworkMode::Firewall {
+            return vec![
+                ("Tab", "Switch View"),
+                ("Space", "Open/Close Port"),
+                ("o", "Open Custom Port"),
+                ("f", "Enable/Disable"),
+                ("PgUp/PgDn", "Scroll Rules"),
+            ];
+        }
+        if self.mode == NetworkMode::Wireless {
+            return vec![
+                ("Tab", "Switch View"),
+                ("s", "Scan"),
+                ("Enter", "Connect"),
+                ("r", "Restart Network"),
+            ];
+        }
         vec![
             ("Tab", "Switch View"),
+            ("Enter", "Edit Interface"),
+            ("u/d", "Up/Down"),
+            ("c/n", "DHCP/Renew"),
+            ("i", "Statistics"),
+            ("v/b", "VLAN/Bridge"),
+            ("h", "Hostname"),
             ("r", "Restart Network"),
             ("F5", "Refresh"),
         ]
     }
 
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        match self.mode {
+            NetworkMode::EditInterface => vec!["s"],
+            NetworkMode::Dns => vec!["w", "p"],
+            NetworkMode::Hosts | NetworkMode::Proxy => vec!["w"],
+            NetworkMode::Ports => Vec::new(),
+            NetworkMode::Routes => vec!["a", "x", "p"],
+            NetworkMode::Firewall => vec!["Space", "o", "f"],
+            NetworkMode::Wireless => vec!["Enter", "r"],
+            NetworkMode::Overview => vec!["u/d", "c/n", "v/b", "h", "r"],
+        }
+    }
+
     fn on_activate(&mut self) {
         self.load_network_info();
+        self.bandwidth.sample();
+    }
+
+    fn on_tick(&mut self) {
+        self.bandwidth.sample_if_due();
+    }
+
+    fn on_refresh(&mut self) {
+        // Reloading would throw away whatever is being edited
+        if self.show_confirm
+            || self.prompt.is_some()
+            || self.draft.is_some()
+            || self.dns_dirty
+            || self.hosts_dirty
+            || self.proxy_dirty
+        {
+            return;
+        }
+        self.load_network_info();
+        match self.mode {
+            NetworkMode::Firewall => self.load_firewall(),
+            NetworkMode::Routes => self.load_routes(),
+            NetworkMode::Ports => self.load_listeners(),
+            _ => {}
+        }
+        if let Some((iface, _)) = self.link_stats.take() {
+            let stats = LinkStats::load(&iface);
+            self.link_stats = Some((iface, stats));
+        }
     }
 }
 
@@ -405,10 +1690,15 @@ impl NetworkComponent {
             .interfaces
             .iter()
             .map(|iface| {
+                let (rx, tx) = self
+                    .bandwidth
+                    .rates(&iface.name)
+                    .map(|r| r.current())
+                    .unwrap_or((0, 0));
                 let status = if iface.is_up {
-                    Span::styled("UP  ", Style::default().fg(Color::Green))
+                    Span::styled("UP  ", Theme::success())
                 } else {
-                    Span::styled("DOWN", Style::default().fg(Color::Red))
+                    Span::styled("DOWN", Theme::error())
                 };
 
                 let dhcp = if iface.use_dhcp { "DHCP" } else { "Static" };
@@ -420,16 +1710,20 @@ impl NetworkComponent {
                             Style::default().add_modifier(Modifier::BOLD),
                         ),
                         status,
-                        Span::styled(format!(" {:<6}", dhcp), Style::default().fg(Color::Cyan)),
+                        Span::styled(format!(" {:<6}", dhcp), Theme::label()),
                         Span::raw(format!(" {}", iface.mac_address)),
                     ]),
                     Line::from(vec![
-                        Span::styled("    IP: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled("    IP: ", Theme::muted()),
                         Span::raw(if iface.ip_address.is_empty() {
-                            "Not assigned".to_string()
+                            format!("{:<24}", "Not assigned")
                         } else {
-                            format!("{}/{}", iface.ip_address, iface.netmask)
+                            format!("{:<24}", format!("{}/{}", iface.ip_address, iface.netmask))
                         }),
+                        Span::styled(" ↓ ", Theme::success()),
+                        Span::raw(format!("{:<12}", format_rate(rx))),
+                        Span::styled("↑ ", Theme::error()),
+                        Span::raw(format_rate(tx)),
                     ]),
                 ])
             })
@@ -449,34 +1743,366 @@ impl NetworkComponent {
     }
 
     fn render_dns(&self, frame: &mut Frame, area: Rect) {
+        let (list_area, warning_area) = if self.dns_overwriters.is_empty() {
+            (area, None)
+        } else {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(3), Constraint::Length(1)])
+                .split(area);
+            (chunks[0], Some(chunks[1]))
+        };
+
+        let servers = self.resolv.nameservers.iter().enumerate().map(|(i, server)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("DNS {}: ", i + 1), Theme::label()),
+                Span::raw(server.as_str()),
+            ]))
+        });
+        let domains = self.resolv.search.iter().map(|domain| {
+            ListItem::new(Line::from(vec![
+                Span::styled("Search: ", Style::default().fg(Theme::colors().secondary)),
+                Span::raw(domain.as_str()),
+            ]))
+        });
+        let items: Vec<ListItem> = servers.chain(domains).collect();
+
+        let title = if self.dns_dirty {
+            " DNS Servers (/etc/resolv.conf) [modified - 'w' to write] "
+        } else {
+            " DNS Servers (/etc/resolv.conf) "
+        };
+        let list = if items.is_empty() {
+            List::new(vec![ListItem::new(Span::styled(
+                "No DNS servers configured",
+                Theme::muted(),
+            ))])
+        } else {
+            List::new(items)
+        }
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Theme::list_selected())
+        .highlight_symbol("▶ ");
+
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, list_area, &mut state);
+
+        if let Some(warning_area) = warning_area {
+            let names: Vec<&str> = self.dns_overwriters.iter().map(|(_, n)| n.as_str()).collect();
+            let warning = Paragraph::new(Span::styled(
+                format!(
+                    " ⚠ dhcpcd on {} will overwrite resolv.conf \
+                     - 'p' sets DHCP_KEEPRESOLV in rc.inet1.conf",
+                    names.join(", ")
+                ),
+                Theme::warning(),
+            ));
+            frame.render_widget(warning, warning_area);
+        }
+    }
+
+    fn render_proxy(&self, frame: &mut Frame, area: Rect) {
+        let mut title = " System Proxy ".to_string();
+        if self.proxy_dirty {
+            title.push_str("[modified - 'w' to write] ");
+        }
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(PROXY_FIELDS.len() as u16), Constraint::Min(0)])
+            .split(inner);
+
+        let values = [&self.proxy.http, &self.proxy.https, &self.proxy.no_proxy];
+        let items: Vec<ListItem> = PROXY_FIELDS
+            .iter()
+            .zip(values)
+            .map(|(label, value)| {
+                let (text, style) = if value.is_empty() {
+                    ("(none)", Theme::muted())
+                } else {
+                    (value.as_str(), Style::default())
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<14}", label), Theme::label()),
+                    Span::styled(text, style),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let notes = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "Written to {}, {} and {}.",
+                    proxy::PROFILE_SH,
+                    proxy::PROFILE_CSH,
+                    proxy::WGETRC
+                ),
+                Theme::muted(),
+            )),
+            Line::from(Span::styled(
+                "Commands run from this tool use the proxy immediately; \
+                 shells pick it up at the next login.",
+                Theme::muted(),
+            )),
+        ]);
+        frame.render_widget(notes, chunks[1]);
+    }
+
+    fn render_hosts(&self, frame: &mut Frame, area: Rect) {
+        let duplicates = self.hosts.duplicates();
         let items: Vec<ListItem> = self
-            .dns_servers
+            .hosts
+            .entries()
+            .into_iter()
+            .map(|entry| {
+                let mut spans = vec![Span::styled(
+                    format!("{:<24}", entry.address),
+                    Theme::label(),
+                )];
+                for name in &entry.names {
+                    let style = if duplicates.contains_key(&name.to_lowercase()) {
+                        Theme::warning()
+                    } else {
+                        Style::default()
+                    };
+                    spans.push(Span::styled(format!("{} ", name), style));
+                }
+                if let Some(comment) = &entry.comment {
+                    spans.push(Span::styled(
+                        format!(" # {}", comment),
+                        Theme::muted(),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let mut title = format!(" Hosts ({}) ", hosts::HOSTS_FILE);
+        if !duplicates.is_empty() {
+            let mut names: Vec<&String> = duplicates.keys().collect();
+            names.sort();
+            let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+            title.push_str(&format!("[duplicates: {}] ", names.join(", ")));
+        }
+        if self.hosts_dirty {
+            title.push_str("[modified - 'w' to write] ");
+        }
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_listeners(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .listeners
             .iter()
-            .enumerate()
-            .map(|(i, server)| {
+            .map(|listener| {
+                let exposure_style = match listener.exposure {
+                    Exposure::AllInterfaces => Theme::error(),
+                    Exposure::Interfaces(_) => Theme::warning(),
+                    Exposure::Loopback => Theme::success(),
+                };
+                let process = if listener.processes.is_empty() {
+                    "?".to_string()
+                } else {
+                    listener.processes.join(", ")
+                };
                 ListItem::new(Line::from(vec![
                     Span::styled(
-                        format!("DNS {}: ", i + 1),
-                        Style::default().fg(Color::Cyan),
+                        format!("{:>5}/{:<4}", listener.port, listener.proto),
+                        Style::default().add_modifier(Modifier::BOLD),
                     ),
-                    Span::raw(server),
+                    Span::raw(format!("{:<24}", listener.address)),
+                    Span::styled(format!("{:<20}", listener.exposure.describe()), exposure_style),
+                    Span::styled(process, Theme::label()),
                 ]))
             })
             .collect();
 
+        let public = self.listeners.iter().filter(|l| l.exposure.is_public()).count();
+        let title = format!(
+            " Listening Ports ({} reachable from the network, {} local only) ",
+            public,
+            self.listeners.len() - public
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_routes(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .routes
+            .iter()
+            .map(|(route, persisted)| {
+                let dest_style = if route.is_default() {
+                    Theme::warning().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().add_modifier(Modifier::BOLD)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        if *persisted { "P " } else { "  " },
+                        Theme::success(),
+                    ),
+                    Span::styled(format!("{:<20}", route.destination), dest_style),
+                    Span::raw(format!("{:<17}", route.gateway.as_deref().unwrap_or("-"))),
+                    Span::styled(
+                        format!("{:<10}", route.dev.as_deref().unwrap_or("-")),
+                        Theme::label(),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:<8} {}",
+                            route.proto.as_deref().unwrap_or(""),
+                            route.metric.map(|m| format!("metric {}", m)).unwrap_or_default()
+                        ),
+                        Theme::muted(),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Routing Table (P = restored at boot) "),
+            )
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_firewall(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(area);
+
+        let items: Vec<ListItem> = self
+            .firewall_entries()
+            .into_iter()
+            .map(|(name, port)| {
+                let open = self.firewall_ports.contains(&port);
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        if open { "[x] " } else { "[ ] " },
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("{:<10}", name), Theme::label()),
+                    Span::raw(port.display()),
+                ]))
+            })
+            .collect();
+
+        let (state_text, state_color) = if self.firewall_enabled {
+            ("enabled", Theme::colors().success)
+        } else {
+            ("disabled", Theme::colors().error)
+        };
+        let title = Line::from(vec![
+            Span::raw(" Open Ports - rc.firewall "),
+            Span::styled(state_text, Style::default().fg(state_color)),
+            Span::raw(" "),
+        ]);
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let rules: Vec<Line> = self
+            .firewall_rules
+            .iter()
+            .map(|rule| Line::from(rule.as_str()))
+            .collect();
+        let rules = Paragraph::new(rules)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Active Rules ({}) ", self.firewall_backend)),
+            )
+            .scroll((self.firewall_scroll, 0));
+        frame.render_widget(rules, chunks[1]);
+    }
+
+    fn render_wireless(&self, frame: &mut Frame, area: Rect) {
+        let title = match (&self.wireless_iface, &self.wireless_link) {
+            (None, _) => " Wireless (no wireless interface) ".to_string(),
+            (Some(iface), Some(link)) => format!(
+                " Wireless {}: connected to {} ({} dBm, {}%) ",
+                iface,
+                link.ssid,
+                link.signal_dbm,
+                wireless::signal_quality(link.signal_dbm)
+            ),
+            (Some(iface), None) => format!(" Wireless {}: not associated ", iface),
+        };
+
+        let connected = self.wireless_link.as_ref().map(|l| l.ssid.as_str());
+        let items: Vec<ListItem> = self
+            .wireless_networks
+            .iter()
+            .map(|network| {
+                let quality = network.quality();
+                let bars = "▮".repeat((quality as usize + 24) / 25);
+                let signal_color = match quality {
+                    60..=100 => Theme::colors().success,
+                    30..=59 => Theme::colors().warning,
+                    _ => Theme::colors().error,
+                };
+                let mut spans = vec![
+                    Span::styled(format!("{:<4}", bars), Style::default().fg(signal_color)),
+                    Span::raw(format!(" {:>4} dBm  ", network.signal_dbm)),
+                    Span::styled(
+                        format!("{:<32}", network.ssid),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        if network.secured { " secured " } else { " open    " },
+                        Style::default().fg(if network.secured {
+                            Theme::colors().primary
+                        } else {
+                            Theme::colors().warning
+                        }),
+                    ),
+                    Span::styled(network.frequency.clone(), Theme::muted()),
+                ];
+                if connected == Some(network.ssid.as_str()) {
+                    spans.push(Span::styled("  (connected)", Theme::success()));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
         let list = if items.is_empty() {
             List::new(vec![ListItem::new(Span::styled(
-                "No DNS servers configured",
-                Style::default().fg(Color::DarkGray),
+                "No scan results - press 's' to scan",
+                Theme::muted(),
             ))])
         } else {
             List::new(items)
         }
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" DNS Servers (/etc/resolv.conf) "),
-        )
+        .block(Block::default().borders(Borders::ALL).title(title))
         .highlight_style(Theme::list_selected())
         .highlight_symbol("▶ ");
 
@@ -485,10 +2111,257 @@ impl NetworkComponent {
     }
 
     fn render_edit(&self, frame: &mut Frame, area: Rect) {
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .title(" Edit Interface ");
+        let title = match &self.draft {
+            Some(draft) => format!(" Edit Interface: {} ", draft.name),
+            None => " Edit Interface ".to_string(),
+        };
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let Some(draft) = &self.draft else {
+            frame.render_widget(block, area);
+            return;
+        };
+
+        let items: Vec<ListItem> = EDIT_FIELDS
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let editing = self.prompt == Some(InputPrompt::Field) && i == self.edit_field;
+                // An unset MTU shows the live one, which it leaves alone
+                let live_mtu = (i == 4 && !editing && draft.mtu.is_empty())
+                    .then(|| self.interfaces.iter().find(|iface| iface.name == draft.name))
+                    .flatten()
+                    .filter(|iface| !iface.mtu.is_empty());
+                let value = if editing {
+                    format!("{}_", self.edit_buffer)
+                } else if i == 3 {
+                    if draft.use_dhcp { "[x]" } else { "[ ]" }.to_string()
+                } else if let Some(iface) = live_mtu {
+                    trf("{} (current)", &[&iface.mtu])
+                } else {
+                    Self::draft_value(draft, i)
+                };
+                // Static settings are ignored by rc.inet1 while DHCP is on
+                let value_style = if editing {
+                    Theme::input_active()
+                } else if live_mtu.is_some() || (draft.use_dhcp && i < 3) {
+                    Theme::muted()
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<12}", label), Theme::label()),
+                    Span::styled(value, value_style),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+
+        let mut state = ListState::default();
+        state.select(Some(self.edit_field));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// RX/TX sparklines for the highlighted interface
+    fn render_bandwidth(&self, frame: &mut Frame, area: Rect) {
+        let iface = match self.mode {
+            NetworkMode::Overview => self.selected_interface(),
+            _ => None,
+        }
+        .or_else(|| self.interfaces.iter().find(|i| i.is_up))
+        .map(|i| i.name.clone());
+
+        let title = match &iface {
+            Some(name) => format!(" Bandwidth: {} ", name),
+            None => " Bandwidth ".to_string(),
+        };
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let inner = block.inner(area);
         frame.render_widget(block, area);
+
+        let Some(rates) = iface.as_deref().and_then(|name| self.bandwidth.rates(name)) else {
+            frame.render_widget(
+                Paragraph::new(Span::styled("Collecting samples...", Theme::muted())),
+                inner,
+            );
+            return;
+        };
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .split(inner);
+        let (rx_now, tx_now) = rates.current();
+        for (row, label, samples, now, color) in [
+            (rows[0], "↓ RX", &rates.rx, rx_now, Theme::colors().success),
+            (rows[1], "↑ TX", &rates.tx, tx_now, Theme::colors().error),
+        ] {
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(16), Constraint::Min(1)])
+                .split(row);
+            frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(Span::styled(label, Style::default().fg(color))),
+                    Line::from(format_rate(now)),
+                ]),
+                cols[0],
+            );
+            // Show the most recent samples that fit the available width
+            let width = cols[1].width as usize;
+            let data: Vec<u64> = samples
+                .iter()
+                .skip(samples.len().saturating_sub(width))
+                .copied()
+                .collect();
+            frame.render_widget(
+                Sparkline::default()
+                    .data(&data)
+                    .style(Style::default().fg(color)),
+                cols[1],
+            );
+        }
+    }
+
+    fn lease_lines<'a>(iface: &'a str, lease: &'a Lease) -> Vec<Line<'a>> {
+        let label = |text| Span::styled(text, Theme::label());
+        let or_unknown = |value: Option<&String>| value.cloned().unwrap_or_else(|| "?".into());
+        let timing = match (lease.lease_time, lease.remaining()) {
+            (Some(total), Some(left)) => format!(
+                "{} ({} left)",
+                dhcp::format_duration(total),
+                dhcp::format_duration(left)
+            ),
+            (Some(total), None) => dhcp::format_duration(total),
+            _ => "infinite".to_string(),
+        };
+        vec![
+            Line::from(vec![
+                label("DHCP Lease:      "),
+                Span::raw(format!("{} on {}", or_unknown(lease.address.as_ref()), iface)),
+            ]),
+            Line::from(vec![
+                label("DHCP Server:     "),
+                Span::raw(or_unknown(lease.server.as_ref())),
+            ]),
+            Line::from(vec![label("Lease Time:      "), Span::raw(timing)]),
+            Line::from(vec![label("Routers:         "), Span::raw(lease.routers.join(", "))]),
+            Line::from(vec![label("Obtained DNS:    "), Span::raw(lease.dns.join(", "))]),
+            Line::from(vec![
+                label("Domain:          "),
+                Span::raw(lease.domain.as_deref().unwrap_or("-")),
+            ]),
+        ]
+    }
+
+    fn render_link_stats(&self, frame: &mut Frame, area: Rect, iface: &str, stats: &LinkStats) {
+        let popup = crate::ui::centered_rect(60, 60, area);
+        frame.render_widget(Clear, popup);
+
+        let label = |text| Span::styled(text, Theme::label());
+        let unknown = || "unknown".to_string();
+        // Any error counter above zero deserves a look
+        let counter = |value: u64| {
+            let style = if value > 0 {
+                Theme::warning()
+            } else {
+                Style::default()
+            };
+            Span::styled(format!("{:<14}", value), style)
+        };
+        let link = match stats.link_detected {
+            Some(true) => Span::styled("yes", Theme::success()),
+            Some(false) => Span::styled("no", Theme::error()),
+            None => Span::raw(unknown()),
+        };
+        let half_duplex = stats
+            .duplex
+            .as_deref()
+            .is_some_and(|d| d.eq_ignore_ascii_case("half"));
+
+        let mut lines = vec![
+            Line::from(vec![label("Link detected:  "), link]),
+            Line::from(vec![
+                label("Speed:          "),
+                Span::raw(stats.speed.clone().unwrap_or_else(unknown)),
+            ]),
+            Line::from(vec![
+                label("Duplex:         "),
+                Span::styled(
+                    stats.duplex.clone().unwrap_or_else(unknown),
+                    if half_duplex {
+                        Theme::warning()
+                    } else {
+                        Style::default()
+                    },
+                ),
+            ]),
+            Line::from(vec![
+                label("Driver:         "),
+                Span::raw(stats.driver.clone().unwrap_or_else(unknown)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("                "),
+                Span::styled(
+                    format!("{:<14}", "RX"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("TX", Style::default().add_modifier(Modifier::BOLD)),
+            ]),
+            Line::from(vec![
+                label("Packets:        "),
+                Span::raw(format!("{:<14}", stats.rx_packets)),
+                Span::raw(stats.tx_packets.to_string()),
+            ]),
+            Line::from(vec![
+                label("Errors:         "),
+                counter(stats.rx_errors),
+                counter(stats.tx_errors),
+            ]),
+            Line::from(vec![
+                label("Dropped:        "),
+                counter(stats.rx_dropped),
+                counter(stats.tx_dropped),
+            ]),
+            Line::from(vec![
+                label("CRC / Carrier:  "),
+                counter(stats.rx_crc_errors),
+                counter(stats.tx_carrier_errors),
+            ]),
+            Line::from(vec![label("Missed (RX):    "), counter(stats.rx_missed_errors)]),
+            Line::from(vec![label("Collisions:     "), counter(stats.collisions)]),
+            Line::from(""),
+            Line::from(vec![
+                label("Error rate:     "),
+                Span::raw(format!("{:.3}% of packets", stats.error_rate())),
+            ]),
+        ];
+        if half_duplex || stats.collisions > 0 {
+            lines.push(Line::from(Span::styled(
+                "Half duplex or collisions usually mean a duplex mismatch with the switch",
+                Theme::warning(),
+            )));
+        } else if stats.rx_crc_errors > 0 {
+            lines.push(Line::from(Span::styled(
+                "CRC errors usually point at a bad cable or port",
+                Theme::warning(),
+            )));
+        }
+        lines.push(Line::from(Span::styled(
+            "F5 refresh, Esc close",
+            Theme::muted(),
+        )));
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} Statistics ", iface)),
+        );
+        frame.render_widget(paragraph, popup);
     }
 
     fn render_info(&self, frame: &mut Frame, area: Rect) {
@@ -518,21 +2391,31 @@ impl NetworkComponent {
             })
             .unwrap_or_else(|| "Not set".to_string());
 
+        let lease = self
+            .selected_interface()
+            .filter(|_| self.mode == NetworkMode::Overview)
+            .and_then(|i| Some((i.name.as_str(), self.leases.get(&i.name)?)));
+        if let Some((iface, lease)) = lease {
+            let paragraph = Paragraph::new(Self::lease_lines(iface, lease));
+            frame.render_widget(paragraph, inner);
+            return;
+        }
+
         let info = vec![
             Line::from(vec![
-                Span::styled("Default Gateway: ", Style::default().fg(Color::Cyan)),
+                Span::styled("Default Gateway: ", Theme::label()),
                 Span::raw(&gateway),
             ]),
             Line::from(vec![
-                Span::styled("DNS Servers:     ", Style::default().fg(Color::Cyan)),
-                Span::raw(if self.dns_servers.is_empty() {
+                Span::styled("DNS Servers:     ", Theme::label()),
+                Span::raw(if self.resolv.nameservers.is_empty() {
                     "None".to_string()
                 } else {
-                    self.dns_servers.join(", ")
+                    self.resolv.nameservers.join(", ")
                 }),
             ]),
             Line::from(vec![
-                Span::styled("Config File:     ", Style::default().fg(Color::Cyan)),
+                Span::styled("Config File:     ", Theme::label()),
                 Span::raw("/etc/rc.d/rc.inet1.conf"),
             ]),
         ];
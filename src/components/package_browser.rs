@@ -1,7 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
@@ -10,8 +10,11 @@ use std::fs;
 use std::path::Path;
 
 use crate::app::Message;
-use crate::components::Component;
+use crate::components::{Component, SearchItem, ViewState};
+use crate::slackware::audit;
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::root;
 
 /// Installed package information
 #[derive(Debug, Clone)]
@@ -47,8 +50,13 @@ pub enum ViewMode {
 
 impl PackageBrowserComponent {
     pub fn new() -> Self {
+        Self::with_packages(Self::installed_packages())
+    }
+
+    /// A browser over `packages` rather than those in /var/log/packages
+    pub fn with_packages(packages: Vec<InstalledPackage>) -> Self {
         let mut component = Self {
-            packages: Vec::new(),
+            packages,
             filtered_packages: Vec::new(),
             list_state: ListState::default(),
             search_query: String::new(),
@@ -58,7 +66,6 @@ impl PackageBrowserComponent {
             show_confirm: false,
             view_mode: ViewMode::List,
         };
-        component.load_packages();
         component.apply_filter();
         if !component.filtered_packages.is_empty() {
             component.list_state.select(Some(0));
@@ -67,6 +74,11 @@ impl PackageBrowserComponent {
     }
 
     pub fn load_packages(&mut self) {
+        self.packages = Self::installed_packages();
+    }
+
+    /// The packages in /var/log/packages, sorted by name
+    fn installed_packages() -> Vec<InstalledPackage> {
         let packages_dir = Path::new("/var/log/packages");
         let mut packages = Vec::new();
 
@@ -89,11 +101,11 @@ impl PackageBrowserComponent {
             }
         }
 
-        packages.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-        self.packages = packages;
+        packages.sort_by_key(|a| a.name.to_lowercase());
+        packages
     }
 
-    fn parse_package_name(filename: &str) -> Option<InstalledPackage> {
+    pub fn parse_package_name(filename: &str) -> Option<InstalledPackage> {
         // Slackware package naming: name-version-arch-build
         // Examples: bash-5.1.008-x86_64-1, kernel-generic-5.15.19-x86_64-1
         let parts: Vec<&str> = filename.rsplitn(4, '-').collect();
@@ -160,7 +172,7 @@ impl PackageBrowserComponent {
                 return line.trim_start_matches(prefix).trim().to_string();
             }
         }
-        "Unknown".to_string()
+        tr("Unknown").to_string()
     }
 
     fn apply_filter(&mut self) {
@@ -187,6 +199,13 @@ impl PackageBrowserComponent {
         }
     }
 
+    /// Select row `selected`, or the last one when there are fewer
+    fn select_clamped(&mut self, selected: Option<usize>) {
+        let last = self.filtered_packages.len().checked_sub(1);
+        self.list_state
+            .select(last.map(|last| selected.unwrap_or(0).min(last)));
+    }
+
     fn selected_package(&self) -> Option<&InstalledPackage> {
         self.list_state
             .selected()
@@ -195,14 +214,11 @@ impl PackageBrowserComponent {
     }
 
     fn remove_package(&mut self, name: &str) -> Option<Message> {
-        match std::process::Command::new("removepkg")
-            .arg(name)
-            .output()
-        {
+        match audit::output(root::command("removepkg").arg(name)) {
             Ok(output) => {
                 if output.status.success() {
                     self.status_message = Some((
-                        format!("Package '{}' removed successfully", name),
+                        trf("Package '{}' removed successfully", &[name]),
                         false,
                     ));
                     self.load_packages();
@@ -210,13 +226,14 @@ impl PackageBrowserComponent {
                 } else {
                     let stderr = String::from_utf8_lossy(&output.stderr);
                     self.status_message = Some((
-                        format!("Failed to remove package: {}", stderr),
+                        trf("Failed to remove package: {}", &[&stderr]),
                         true,
                     ));
                 }
             }
             Err(e) => {
-                self.status_message = Some((format!("Error executing removepkg: {}", e), true));
+                self.status_message =
+                    Some((trf("Error executing removepkg: {}", &[&e.to_string()]), true));
             }
         }
         None
@@ -277,15 +294,11 @@ impl Component for PackageBrowserComponent {
                     self.list_state.select(Some(0));
                 }
             }
-            KeyCode::Home => {
-                if !self.filtered_packages.is_empty() {
-                    self.list_state.select(Some(0));
-                }
+            KeyCode::Home if !self.filtered_packages.is_empty() => {
+                self.list_state.select(Some(0));
             }
-            KeyCode::End => {
-                if !self.filtered_packages.is_empty() {
-                    self.list_state.select(Some(self.filtered_packages.len() - 1));
-                }
+            KeyCode::End if !self.filtered_packages.is_empty() => {
+                self.list_state.select(Some(self.filtered_packages.len() - 1));
             }
             KeyCode::PageUp => {
                 if let Some(selected) = self.list_state.selected() {
@@ -320,7 +333,7 @@ impl Component for PackageBrowserComponent {
             KeyCode::F(5) => {
                 self.load_packages();
                 self.apply_filter();
-                self.status_message = Some(("Package list refreshed".to_string(), false));
+                self.status_message = Some((tr("Package list refreshed").to_string(), false));
             }
             _ => {}
         }
@@ -339,31 +352,36 @@ impl Component for PackageBrowserComponent {
 
         // Search bar
         let search_style = if self.is_searching {
-            Style::default().fg(Color::Yellow)
+            Theme::warning()
         } else {
             Style::default()
         };
         let search_bar = Paragraph::new(Line::from(vec![
-            Span::styled("Search: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{} ", tr("Search:")), Theme::label()),
             Span::styled(&self.search_query, search_style),
             if self.is_searching {
-                Span::styled("_", Style::default().fg(Color::Yellow))
+                Span::styled("_", Theme::warning())
             } else {
                 Span::raw("")
             },
             Span::styled(
                 format!(
-                    "  ({}/{} packages)",
-                    self.filtered_packages.len(),
-                    self.packages.len()
+                    "  ({})",
+                    trf(
+                        "{}/{} packages",
+                        &[
+                            &self.filtered_packages.len().to_string(),
+                            &self.packages.len().to_string(),
+                        ],
+                    )
                 ),
-                Style::default().fg(Color::DarkGray),
+                Theme::muted(),
             ),
         ]))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Installed Packages "),
+                .title(format!(" {} ", tr("Installed Packages"))),
         );
         frame.render_widget(search_bar, chunks[0]);
 
@@ -381,31 +399,35 @@ impl Component for PackageBrowserComponent {
             Line::from(vec![
                 Span::styled(
                     format!(
-                        "Remove package '{}'? ",
-                        self.selected_package
-                            .as_ref()
-                            .map(|p| p.name.as_str())
-                            .unwrap_or("?")
+                        "{} ",
+                        trf(
+                            "Remove package '{}'?",
+                            &[self
+                                .selected_package
+                                .as_ref()
+                                .map(|p| p.name.as_str())
+                                .unwrap_or("?")]
+                        )
                     ),
-                    Style::default().fg(Color::Yellow),
+                    Theme::warning(),
                 ),
-                Span::raw("[Y]es / [N]o"),
+                Span::raw(tr("[Y]es / [N]o")),
             ])
         } else if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
             ))
         } else if let Some(pkg) = self.selected_package() {
             Line::from(vec![
-                Span::styled("Size: ", Style::default().fg(Color::Cyan)),
-                Span::raw(format!(
+                Span::styled(format!("{} ", tr("Size:")), Theme::label()),
+                Span::raw(trf(
                     "{} compressed, {} installed",
-                    pkg.size_compressed, pkg.size_uncompressed
+                    &[&pkg.size_compressed, &pkg.size_uncompressed],
                 )),
             ])
         } else {
-            Line::from(Span::raw("No package selected"))
+            Line::from(Span::raw(tr("No package selected")))
         };
 
         let status = Paragraph::new(status_content)
@@ -413,6 +435,10 @@ impl Component for PackageBrowserComponent {
         frame.render_widget(status, chunks[2]);
     }
 
+    fn is_typing(&self) -> bool {
+        self.is_searching
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
         if self.is_searching {
             vec![("Enter/Esc", "Done"), ("Type", "Search")]
@@ -426,9 +452,50 @@ impl Component for PackageBrowserComponent {
         }
     }
 
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        if self.is_searching {
+            Vec::new()
+        } else {
+            vec!["d"]
+        }
+    }
+
     fn on_activate(&mut self) {
+        let selected = self.list_state.selected();
         self.load_packages();
         self.apply_filter();
+        self.select_clamped(selected);
+    }
+
+    fn view_state(&self) -> ViewState {
+        ViewState {
+            selected: self.list_state.selected().filter(|&i| i > 0),
+            query: self.search_query.clone(),
+            ..ViewState::default()
+        }
+    }
+
+    fn restore_view_state(&mut self, state: &ViewState) {
+        self.search_query = state.query.clone();
+        self.apply_filter();
+        self.select_clamped(state.selected);
+    }
+
+    fn search_items(&self) -> Vec<SearchItem> {
+        self.packages
+            .iter()
+            .map(|pkg| SearchItem::new(&pkg.full_name, format!("{} {}", pkg.name, pkg.version)))
+            .collect()
+    }
+
+    fn select_item(&mut self, id: &str) {
+        self.search_query.clear();
+        self.is_searching = false;
+        self.view_mode = ViewMode::List;
+        self.apply_filter();
+        if let Some(i) = self.packages.iter().position(|pkg| pkg.full_name == id) {
+            self.list_state.select(Some(i));
+        }
     }
 }
 
@@ -447,11 +514,11 @@ impl PackageBrowserComponent {
                         ),
                         Span::styled(
                             format!(" {:<15}", pkg.version),
-                            Style::default().fg(Color::Green),
+                            Theme::success(),
                         ),
                         Span::styled(
                             format!(" {:<10}", pkg.arch),
-                            Style::default().fg(Color::Cyan),
+                            Theme::label(),
                         ),
                     ]),
                     Line::from(Span::styled(
@@ -463,7 +530,7 @@ impl PackageBrowserComponent {
                                 pkg.description.clone()
                             }
                         ),
-                        Style::default().fg(Color::DarkGray),
+                        Theme::muted(),
                     )),
                 ])
             })
@@ -481,43 +548,44 @@ impl PackageBrowserComponent {
     fn render_details(&self, frame: &mut Frame, area: Rect, pkg: &InstalledPackage) {
         let block = Block::default()
             .borders(Borders::ALL)
-            .title(format!(" Package: {} ", pkg.name));
+            .title(format!(" {} ", trf("Package: {}", &[&pkg.name])));
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
+        let label = |text| Span::styled(format!("{:<13} ", tr(text)), Theme::label());
         let details = vec![
             Line::from(vec![
-                Span::styled("Name:         ", Style::default().fg(Color::Cyan)),
+                label("Name:"),
                 Span::raw(&pkg.name),
             ]),
             Line::from(vec![
-                Span::styled("Version:      ", Style::default().fg(Color::Cyan)),
+                label("Version:"),
                 Span::raw(&pkg.version),
             ]),
             Line::from(vec![
-                Span::styled("Architecture: ", Style::default().fg(Color::Cyan)),
+                label("Architecture:"),
                 Span::raw(&pkg.arch),
             ]),
             Line::from(vec![
-                Span::styled("Build:        ", Style::default().fg(Color::Cyan)),
+                label("Build:"),
                 Span::raw(&pkg.build),
             ]),
             Line::from(vec![
-                Span::styled("Full Name:    ", Style::default().fg(Color::Cyan)),
+                label("Full Name:"),
                 Span::raw(&pkg.full_name),
             ]),
             Line::from(""),
             Line::from(vec![
-                Span::styled("Compressed:   ", Style::default().fg(Color::Cyan)),
+                label("Compressed:"),
                 Span::raw(&pkg.size_compressed),
             ]),
             Line::from(vec![
-                Span::styled("Uncompressed: ", Style::default().fg(Color::Cyan)),
+                label("Uncompressed:"),
                 Span::raw(&pkg.size_uncompressed),
             ]),
             Line::from(""),
-            Line::from(Span::styled("Description:", Style::default().fg(Color::Cyan))),
+            Line::from(Span::styled(tr("Description:"), Theme::label())),
             Line::from(""),
         ];
 
@@ -531,3 +599,59 @@ impl PackageBrowserComponent {
         frame.render_widget(paragraph, inner);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::{assert_snapshot, render};
+
+    fn browser() -> PackageBrowserComponent {
+        let packages = [
+            ("bash-5.2.037-x86_64-1", "The GNU Bourne-Again SHell", "2.1M", "8.4M"),
+            ("openssh-9.9p1-x86_64-1", "Secure Shell daemon and clients", "1.3M", "5.6M"),
+            ("vim-9.1.1000-x86_64-1", "Vi IMproved", "9.2M", "42M"),
+        ]
+        .into_iter()
+        .filter_map(|(file, description, compressed, uncompressed)| {
+            let mut pkg = PackageBrowserComponent::parse_package_name(file)?;
+            pkg.description = description.to_string();
+            pkg.size_compressed = compressed.to_string();
+            pkg.size_uncompressed = uncompressed.to_string();
+            Some(pkg)
+        })
+        .collect();
+        PackageBrowserComponent::with_packages(packages)
+    }
+
+    fn draw(browser: &PackageBrowserComponent) -> ratatui::buffer::Buffer {
+        render(80, 20, |frame| browser.render(frame, frame.area()))
+    }
+
+    #[test]
+    fn test_list() {
+        let mut browser = browser();
+        browser.handle_input(KeyEvent::from(KeyCode::Down));
+        assert_snapshot("package_browser_list", &draw(&browser));
+    }
+
+    #[test]
+    fn test_search() {
+        let mut browser = browser();
+        browser.handle_input(KeyEvent::from(KeyCode::Char('/')));
+        for c in "shell".chars() {
+            browser.handle_input(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert!(browser.is_typing());
+        assert_snapshot("package_browser_search", &draw(&browser));
+    }
+
+    #[test]
+    fn test_details_and_remove_confirm() {
+        let mut browser = browser();
+        browser.handle_input(KeyEvent::from(KeyCode::Enter));
+        assert_snapshot("package_browser_details", &draw(&browser));
+        browser.handle_input(KeyEvent::from(KeyCode::Enter));
+        browser.handle_input(KeyEvent::from(KeyCode::Char('d')));
+        assert_snapshot("package_browser_remove_confirm", &draw(&browser));
+    }
+}
@@ -6,9 +6,8 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
-use tokio::sync::mpsc;
 
-use super::{AsyncComponent, Component};
+use super::{AsyncComponent, Component, ViewState};
 use crate::app::Message;
 use crate::slackware::packages::PackageInfo;
 use crate::ui::theme::Theme;
@@ -21,7 +20,6 @@ pub struct PackageSearchComponent {
     is_searching: bool,
     is_installing: bool,
     status_message: Option<(String, bool)>,
-    progress_tx: Option<mpsc::UnboundedSender<String>>,
 }
 
 impl PackageSearchComponent {
@@ -33,7 +31,6 @@ impl PackageSearchComponent {
             is_searching: false,
             is_installing: false,
             status_message: None,
-            progress_tx: None,
         }
     }
 
@@ -51,10 +48,6 @@ impl PackageSearchComponent {
         self.list_state.selected().and_then(|i| self.results.get(i))
     }
 
-    pub fn get_query(&self) -> &str {
-        &self.search_query
-    }
-
     pub fn start_search(&mut self) {
         self.is_searching = true;
         self.status_message = None;
@@ -223,18 +216,37 @@ impl Component for PackageSearchComponent {
         );
     }
 
+    fn is_typing(&self) -> bool {
+        // The query field takes every key
+        true
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
         vec![
-            ("Enter", "Search"),
+            ("Enter", "Search Packages"),
             ("Tab", "Next result"),
             ("Ctrl+I", "Install"),
         ]
     }
+
+    /// Only the query: the results are from SlackBuilds.org as it was then
+    fn view_state(&self) -> ViewState {
+        ViewState {
+            query: self.search_query.clone(),
+            ..ViewState::default()
+        }
+    }
+
+    fn restore_view_state(&mut self, state: &ViewState) {
+        self.search_query = state.query.clone();
+    }
 }
 
 impl AsyncComponent for PackageSearchComponent {
-    fn set_progress_channel(&mut self, tx: mpsc::UnboundedSender<String>) {
-        self.progress_tx = Some(tx);
+    fn add_progress(&mut self, line: String) {
+        if self.is_running() {
+            self.status_message = Some((line, false));
+        }
     }
 
     fn is_running(&self) -> bool {
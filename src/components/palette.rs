@@ -0,0 +1,127 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::{Component, Tab};
+use crate::app::Message;
+use crate::ui::centered_rect;
+use crate::ui::theme::Theme;
+use crate::utils::fuzzy;
+use crate::utils::i18n::tr;
+
+/// Something the palette can do: switch to `tab`, then press `key` there
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub tab: Tab,
+    pub key: Option<KeyEvent>,
+}
+
+/// Ctrl+P overlay that fuzzy-searches the actions of every tab
+pub struct CommandPalette {
+    entries: Vec<PaletteEntry>,
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn new(entries: Vec<PaletteEntry>) -> Self {
+        Self {
+            entries,
+            query: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Entries matching the query, best first
+    fn matches(&self) -> Vec<&PaletteEntry> {
+        let mut scored: Vec<(u32, &PaletteEntry)> = self
+            .entries
+            .iter()
+            .filter_map(|e| fuzzy::score(&self.query, &e.label).map(|s| (s, e)))
+            .collect();
+        // Stable, so equal scores keep the tab order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, e)| e).collect()
+    }
+}
+
+impl Component for CommandPalette {
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        let count = self.matches().len();
+        match key.code {
+            KeyCode::Esc => return Some(Message::PaletteClose),
+            KeyCode::Enter => {
+                return self
+                    .matches()
+                    .get(self.selected)
+                    .map(|e| Message::PaletteRun(e.tab, e.key));
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < count => self.selected += 1,
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let dialog_area = centered_rect(60, 70, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let dialog = Block::default()
+            .title(format!(" {} ", tr("Command Palette")))
+            .borders(Borders::ALL)
+            .border_style(Theme::title());
+        let inner = dialog.inner(dialog_area);
+        frame.render_widget(dialog, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)])
+            .split(inner);
+
+        let input = Paragraph::new(Line::from(vec![
+            Span::styled("> ", Theme::key_hint()),
+            Span::raw(self.query.as_str()),
+            Span::styled("_", Theme::muted()),
+        ]))
+        .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(input, chunks[0]);
+
+        let matches = self.matches();
+        let items: Vec<ListItem> = if matches.is_empty() {
+            vec![ListItem::new(Span::styled(
+                tr("No matching actions"),
+                Theme::muted(),
+            ))]
+        } else {
+            matches
+                .iter()
+                .map(|e| ListItem::new(e.label.as_str()))
+                .collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let selected = (!matches.is_empty()).then_some(self.selected);
+        let mut state = ListState::default().with_selected(selected);
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+    }
+
+    fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("↑/↓", "Select"), ("Enter", "Run"), ("Esc", "Close")]
+    }
+}
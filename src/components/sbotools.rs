@@ -5,12 +5,12 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use tokio::sync::mpsc;
 
 use super::{AsyncComponent, Component};
 use crate::app::Message;
 use crate::ui::theme::Theme;
 use crate::ui::widgets::{ProgressList, ProgressStep, StepStatus};
+use crate::utils::i18n::tr;
 
 const SBOPKG_URL: &str = "https://github.com/sbopkg/sbopkg/releases/download/0.38.2/sbopkg-0.38.2-noarch-1_wsr.tgz";
 const SBOPKG_FILENAME: &str = "sbopkg-0.38.2-noarch-1_wsr.tgz";
@@ -22,7 +22,6 @@ pub struct SbotoolsComponent {
     current_step: usize,
     output_lines: Vec<String>,
     is_running: bool,
-    progress_tx: Option<mpsc::UnboundedSender<String>>,
 }
 
 impl SbotoolsComponent {
@@ -39,7 +38,6 @@ impl SbotoolsComponent {
             current_step: 0,
             output_lines: Vec::new(),
             is_running: false,
-            progress_tx: None,
         }
     }
 
@@ -63,6 +61,27 @@ impl SbotoolsComponent {
         self.steps[0].status = StepStatus::Running;
     }
 
+    pub fn is_running(&self) -> bool {
+        self.is_running
+    }
+
+    /// The running step, counting from 1, and the number of steps
+    pub fn progress(&self) -> (usize, usize) {
+        (self.current_step + 1, self.steps.len())
+    }
+
+    /// Steps still to run after the current one
+    pub fn queued_steps(&self) -> Vec<String> {
+        if !self.is_running {
+            return Vec::new();
+        }
+        self.steps
+            .iter()
+            .skip(self.current_step + 1)
+            .map(|step| step.name.clone())
+            .collect()
+    }
+
     pub fn add_output(&mut self, line: String) {
         self.output_lines.push(line);
     }
@@ -86,7 +105,7 @@ impl SbotoolsComponent {
                 self.steps[self.current_step].status = StepStatus::Running;
             } else {
                 self.is_running = false;
-                self.add_output("All steps completed successfully!".to_string());
+                self.add_output(tr("All steps completed successfully!").to_string());
             }
         }
     }
@@ -160,7 +179,7 @@ impl Component for SbotoolsComponent {
 
         // Title
         let title = Paragraph::new(Line::from(vec![
-            ratatui::text::Span::styled("sbotools Installer", Theme::title()),
+            ratatui::text::Span::styled(tr("sbotools Installer"), Theme::title()),
         ]))
         .block(Block::default().borders(Borders::BOTTOM));
         frame.render_widget(title, chunks[0]);
@@ -168,7 +187,7 @@ impl Component for SbotoolsComponent {
         // Description
         let desc = Paragraph::new(vec![
             Line::from(""),
-            Line::from("This will install sbopkg and sbotools for SlackBuilds.org packages."),
+            Line::from(tr("This will install sbopkg and sbotools for SlackBuilds.org packages.")),
             Line::from(""),
         ])
         .style(Theme::muted());
@@ -182,11 +201,11 @@ impl Component for SbotoolsComponent {
 
         // Progress steps
         let progress = ProgressList::new(&self.steps)
-            .block(Block::default().borders(Borders::ALL).title("Installation Steps"));
+            .block(Block::default().borders(Borders::ALL).title(tr("Installation Steps")));
         frame.render_widget(progress, progress_chunks[1]);
 
         // Output
-        let output_block = Block::default().borders(Borders::ALL).title("Output");
+        let output_block = Block::default().borders(Borders::ALL).title(tr("Output"));
         let inner = output_block.inner(chunks[2]);
         frame.render_widget(output_block, chunks[2]);
 
@@ -210,8 +229,8 @@ impl Component for SbotoolsComponent {
 }
 
 impl AsyncComponent for SbotoolsComponent {
-    fn set_progress_channel(&mut self, tx: mpsc::UnboundedSender<String>) {
-        self.progress_tx = Some(tx);
+    fn add_progress(&mut self, line: String) {
+        self.add_output(line);
     }
 
     fn is_running(&self) -> bool {
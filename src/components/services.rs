@@ -11,8 +11,12 @@ use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
 use crate::app::Message;
-use crate::components::Component;
+use crate::components::settings;
+use crate::components::{Component, SearchItem, ViewState};
+use crate::slackware::audit;
 use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+use crate::utils::root;
 
 /// Service information
 #[derive(Debug, Clone)]
@@ -27,10 +31,10 @@ pub struct ServiceInfo {
 impl ServiceInfo {
     pub fn status_display(&self) -> (&'static str, Color) {
         match (self.is_running, self.is_enabled) {
-            (true, true) => ("● Running", Color::Green),
-            (true, false) => ("● Running (disabled)", Color::Yellow),
-            (false, true) => ("○ Stopped", Color::Red),
-            (false, false) => ("○ Stopped (disabled)", Color::DarkGray),
+            (true, true) => ("● Running", Theme::colors().success),
+            (true, false) => ("● Running (disabled)", Theme::colors().warning),
+            (false, true) => ("○ Stopped", Theme::colors().error),
+            (false, false) => ("○ Stopped (disabled)", Theme::colors().muted),
         }
     }
 }
@@ -61,6 +65,13 @@ pub enum ServiceAction {
     Toggle(String),
 }
 
+impl ServiceAction {
+    /// Stopping a service can cut off whoever relies on it
+    fn always_confirm(&self) -> bool {
+        matches!(self, ServiceAction::Stop(_))
+    }
+}
+
 impl ServiceComponent {
     pub fn new() -> Self {
         let mut component = Self {
@@ -174,7 +185,7 @@ impl ServiceComponent {
                 }
             }
         }
-        "No description available".to_string()
+        tr("No description available").to_string()
     }
 
     fn filtered_services(&self) -> Vec<&ServiceInfo> {
@@ -194,6 +205,17 @@ impl ServiceComponent {
         self.list_state.selected().and_then(|i| filtered.get(i).copied())
     }
 
+    /// Ask before running `action`, unless confirmations are off and it is low-risk
+    fn request(&mut self, action: ServiceAction) -> Option<Message> {
+        if settings::confirm_actions() || action.always_confirm() {
+            self.pending_action = Some(action);
+            self.show_confirm = true;
+            None
+        } else {
+            self.execute_action(action)
+        }
+    }
+
     fn execute_action(&mut self, action: ServiceAction) -> Option<Message> {
         let (script_path, action_str) = match &action {
             ServiceAction::Start(name) => {
@@ -209,17 +231,18 @@ impl ServiceComponent {
                 // Toggle executable bit
                 let path = format!("/etc/rc.d/{}", name);
                 if let Ok(metadata) = fs::metadata(&path) {
-                    let mut perms = metadata.permissions();
-                    let mode = perms.mode();
-                    if mode & 0o111 != 0 {
-                        perms.set_mode(mode & !0o111);
+                    let mode = metadata.permissions().mode();
+                    let mode = if mode & 0o111 != 0 {
+                        mode & !0o111
                     } else {
-                        perms.set_mode(mode | 0o755);
-                    }
-                    if let Err(e) = fs::set_permissions(&path, perms) {
-                        self.status_message = Some((format!("Failed to toggle: {}", e), true));
+                        mode | 0o755
+                    };
+                    if let Err(e) = root::set_mode(Path::new(&path), mode) {
+                        self.status_message =
+                            Some((trf("Failed to toggle: {}", &[&e.to_string()]), true));
                     } else {
-                        self.status_message = Some((format!("Toggled {} executable bit", name), false));
+                        self.status_message =
+                            Some((trf("Toggled {} executable bit", &[name]), false));
                         self.load_services();
                     }
                 }
@@ -227,26 +250,23 @@ impl ServiceComponent {
             }
         };
 
-        match std::process::Command::new(&script_path)
-            .arg(action_str)
-            .output()
-        {
+        let (done, failed) = match action_str {
+            "start" => ("Service {} started successfully", "Failed to start service: {}"),
+            "stop" => ("Service {} stopped successfully", "Failed to stop service: {}"),
+            _ => ("Service {} restarted successfully", "Failed to restart service: {}"),
+        };
+        match audit::output(root::command(&script_path).arg(action_str)) {
             Ok(output) => {
                 if output.status.success() {
-                    self.status_message = Some((
-                        format!("Service {} {}ed successfully", script_path, action_str),
-                        false,
-                    ));
+                    self.status_message = Some((trf(done, &[&script_path]), false));
                 } else {
                     let stderr = String::from_utf8_lossy(&output.stderr);
-                    self.status_message = Some((
-                        format!("Failed to {} service: {}", action_str, stderr),
-                        true,
-                    ));
+                    self.status_message = Some((trf(failed, &[&stderr]), true));
                 }
             }
             Err(e) => {
-                self.status_message = Some((format!("Failed to execute: {}", e), true));
+                self.status_message =
+                    Some((trf("Failed to execute: {}", &[&e.to_string()]), true));
             }
         }
 
@@ -293,32 +313,25 @@ impl Component for ServiceComponent {
                     self.list_state.select(Some(0));
                 }
             }
-            KeyCode::Home => {
-                if filtered_len > 0 {
-                    self.list_state.select(Some(0));
-                }
+            KeyCode::Home if filtered_len > 0 => {
+                self.list_state.select(Some(0));
             }
-            KeyCode::End => {
-                if filtered_len > 0 {
-                    self.list_state.select(Some(filtered_len - 1));
-                }
+            KeyCode::End if filtered_len > 0 => {
+                self.list_state.select(Some(filtered_len - 1));
             }
             KeyCode::Char('s') => {
                 if let Some(service) = self.selected_service() {
-                    self.pending_action = Some(ServiceAction::Start(service.name.clone()));
-                    self.show_confirm = true;
+                    return self.request(ServiceAction::Start(service.name.clone()));
                 }
             }
             KeyCode::Char('x') => {
                 if let Some(service) = self.selected_service() {
-                    self.pending_action = Some(ServiceAction::Stop(service.name.clone()));
-                    self.show_confirm = true;
+                    return self.request(ServiceAction::Stop(service.name.clone()));
                 }
             }
             KeyCode::Char('r') => {
                 if let Some(service) = self.selected_service() {
-                    self.pending_action = Some(ServiceAction::Restart(service.name.clone()));
-                    self.show_confirm = true;
+                    return self.request(ServiceAction::Restart(service.name.clone()));
                 }
             }
             KeyCode::Char('e') => {
@@ -337,7 +350,7 @@ impl Component for ServiceComponent {
             }
             KeyCode::F(5) => {
                 self.load_services();
-                self.status_message = Some(("Services refreshed".to_string(), false));
+                self.status_message = Some((tr("Services refreshed").to_string(), false));
             }
             _ => {}
         }
@@ -355,21 +368,38 @@ impl Component for ServiceComponent {
             .split(area);
 
         // Filter bar
-        let filter_text = match self.filter {
-            ServiceFilter::All => "[All]  Running  Stopped  Enabled",
-            ServiceFilter::Running => " All  [Running]  Stopped  Enabled",
-            ServiceFilter::Stopped => " All   Running  [Stopped]  Enabled",
-            ServiceFilter::Enabled => " All   Running   Stopped  [Enabled]",
-        };
+        let filter_text = [
+            (ServiceFilter::All, "All"),
+            (ServiceFilter::Running, "Running"),
+            (ServiceFilter::Stopped, "Stopped"),
+            (ServiceFilter::Enabled, "Enabled"),
+        ]
+        .iter()
+        .map(|(filter, name)| {
+            if *filter == self.filter {
+                format!("[{}]", tr(name))
+            } else {
+                tr(name).to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
         let filter_bar = Paragraph::new(Line::from(vec![
-            Span::styled("Filter: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{} ", tr("Filter:")), Theme::label()),
             Span::raw(filter_text),
             Span::styled(
-                format!("  ({} services)", self.filtered_services().len()),
-                Style::default().fg(Color::DarkGray),
+                format!(
+                    "  ({})",
+                    trf("{} services", &[&self.filtered_services().len().to_string()])
+                ),
+                Theme::muted(),
             ),
         ]))
-        .block(Block::default().borders(Borders::ALL).title(" Services "));
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", tr("Services"))),
+        );
         frame.render_widget(filter_bar, chunks[0]);
 
         // Service list
@@ -384,11 +414,11 @@ impl Component for ServiceComponent {
                             format!("{:<20}", service.name),
                             Style::default().add_modifier(Modifier::BOLD),
                         ),
-                        Span::styled(format!(" {:<20}", status), Style::default().fg(color)),
+                        Span::styled(format!(" {:<20}", tr(status)), Style::default().fg(color)),
                     ]),
                     Line::from(vec![Span::styled(
                         format!("  {}", service.description),
-                        Style::default().fg(Color::DarkGray),
+                        Theme::muted(),
                     )]),
                 ])
             })
@@ -405,28 +435,28 @@ impl Component for ServiceComponent {
         // Status bar
         let status_content = if self.show_confirm {
             let action_desc = match &self.pending_action {
-                Some(ServiceAction::Start(n)) => format!("Start {}?", n),
-                Some(ServiceAction::Stop(n)) => format!("Stop {}?", n),
-                Some(ServiceAction::Restart(n)) => format!("Restart {}?", n),
-                Some(ServiceAction::Toggle(n)) => format!("Toggle {}?", n),
-                None => "Confirm action?".to_string(),
+                Some(ServiceAction::Start(n)) => trf("Start {}?", &[n]),
+                Some(ServiceAction::Stop(n)) => trf("Stop {}?", &[n]),
+                Some(ServiceAction::Restart(n)) => trf("Restart {}?", &[n]),
+                Some(ServiceAction::Toggle(n)) => trf("Toggle {}?", &[n]),
+                None => tr("Confirm action?").to_string(),
             };
             Line::from(vec![
-                Span::styled(action_desc, Style::default().fg(Color::Yellow)),
-                Span::raw(" [Y]es / [N]o"),
+                Span::styled(action_desc, Theme::warning()),
+                Span::raw(format!(" {}", tr("[Y]es / [N]o"))),
             ])
         } else if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
             ))
         } else if let Some(service) = self.selected_service() {
             Line::from(vec![
-                Span::styled("Path: ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{} ", tr("Path:")), Theme::label()),
                 Span::raw(&service.path),
             ])
         } else {
-            Line::from(Span::raw("Select a service"))
+            Line::from(Span::raw(tr("Select a service")))
         };
 
         let status = Paragraph::new(status_content)
@@ -444,7 +474,57 @@ impl Component for ServiceComponent {
         ]
     }
 
+    fn mutating_keys(&self) -> Vec<&'static str> {
+        vec!["s", "x", "r", "e"]
+    }
+
     fn on_activate(&mut self) {
         self.load_services();
     }
+
+    fn view_state(&self) -> ViewState {
+        let filter = match self.filter {
+            ServiceFilter::All => "",
+            ServiceFilter::Running => "running",
+            ServiceFilter::Stopped => "stopped",
+            ServiceFilter::Enabled => "enabled",
+        };
+        ViewState {
+            selected: self.list_state.selected().filter(|&i| i > 0),
+            query: filter.to_string(),
+            ..ViewState::default()
+        }
+    }
+
+    fn restore_view_state(&mut self, state: &ViewState) {
+        self.filter = match state.query.as_str() {
+            "running" => ServiceFilter::Running,
+            "stopped" => ServiceFilter::Stopped,
+            "enabled" => ServiceFilter::Enabled,
+            _ => ServiceFilter::All,
+        };
+        let len = self.filtered_services().len();
+        self.list_state
+            .select(len.checked_sub(1).map(|last| state.selected.unwrap_or(0).min(last)));
+    }
+
+    fn search_items(&self) -> Vec<SearchItem> {
+        self.services
+            .iter()
+            .map(|s| SearchItem::new(&s.name, format!("{} - {}", s.name, s.description)))
+            .collect()
+    }
+
+    fn select_item(&mut self, id: &str) {
+        self.filter = ServiceFilter::All;
+        if let Some(i) = self.services.iter().position(|s| s.name == id) {
+            self.list_state.select(Some(i));
+        }
+    }
+
+    fn on_refresh(&mut self) {
+        if !self.show_confirm {
+            self.load_services();
+        }
+    }
 }
@@ -1,157 +1,256 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use crate::app::Message;
-use crate::components::Component;
-use crate::ui::theme::Theme;
+use crate::components::backup::BackupProfile;
+use crate::components::custom_tab;
+use crate::components::{Component, Tab};
+use crate::slackware::remote::RemoteHost;
+use crate::slackware::timeconfig::{self, HardwareClock};
+use crate::ui::theme::{Theme, ThemeChoice};
+use crate::utils::i18n::{self, tr};
+use crate::utils::keymap::{Action, Keymap};
+use crate::utils::root::{self, Escalation};
 
 const CONFIG_DIR: &str = "/etc/slackware-cli-manager";
 const CONFIG_FILE: &str = "config.toml";
 
+/// Suggested file name for settings exports
+const BUNDLE_FILE: &str = "slackware-cli-manager-settings.toml";
+
+/// `confirm_actions` of the loaded settings, read by every component
+static CONFIRM_ACTIONS: AtomicBool = AtomicBool::new(true);
+
+/// Whether low-risk actions ask [Y]/[N] first. Destructive ones always do.
+pub fn confirm_actions() -> bool {
+    CONFIRM_ACTIONS.load(Ordering::Relaxed)
+}
+
+fn set_confirm_actions(enabled: bool) {
+    CONFIRM_ACTIONS.store(enabled, Ordering::Relaxed);
+}
+
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppSettings {
     pub theme: ThemeChoice,
+    /// Interface language, a code from `i18n::LANGUAGES`
+    pub language: String,
     pub confirm_actions: bool,
     pub show_hidden_files: bool,
     pub auto_refresh: bool,
     pub refresh_interval: u32,
+    /// Clicks and the scroll wheel; off leaves the mouse to the terminal
+    pub mouse: bool,
+    /// Milliseconds between frames while something moves on screen
+    pub frame_interval: u32,
+    /// Milliseconds between redraws while nothing happens
+    pub idle_interval: u32,
+    /// Runs privileged commands when started as a normal user
+    pub escalation: Escalation,
     pub default_tab: String,
+    /// Open where the last session left off: its tab, selections,
+    /// searches and log position, instead of `default_tab`
+    pub restore_session: bool,
     pub log_lines: usize,
+    pub backup: BackupSettings,
+    /// Extra coloring applied in the log viewer
+    pub log_highlights: Vec<HighlightRule>,
+    /// Machines offered by the host list (Ctrl+O)
+    pub remote_hosts: Vec<RemoteHost>,
+    /// Keys for actions, e.g. `next-tab = "ctrl+n"`; unlisted ones keep
+    /// their defaults
+    pub keys: BTreeMap<String, String>,
+}
+
+/// Backup-related settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupSettings {
+    /// Remote destination in `user@host:/path` form (empty = disabled)
+    pub remote_target: String,
+    /// SSH private key used for the remote destination
+    pub ssh_key: String,
+    /// GPG key ID or email to encrypt backups to (empty = unencrypted)
+    pub gpg_recipient: String,
+    /// Number of most recent backups to keep (0 = unlimited)
+    pub keep_last: usize,
+    /// Delete backups older than this many days (0 = never)
+    pub keep_days: u32,
+    /// Extra comma-separated tar exclude patterns for /etc snapshots
+    pub snapshot_excludes: String,
+}
+
+/// A log viewer rule coloring the text a regex matches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightRule {
+    pub pattern: String,
+    /// A color name ("magenta", "lightblue"), palette index or "#rrggbb"
+    pub color: String,
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             theme: ThemeChoice::Default,
+            language: "en".to_string(),
             confirm_actions: true,
             show_hidden_files: false,
             auto_refresh: false,
             refresh_interval: 5,
+            mouse: true,
+            frame_interval: 100,
+            idle_interval: 1000,
+            escalation: Escalation::default(),
             default_tab: "updater".to_string(),
+            restore_session: true,
             log_lines: 1000,
+            backup: BackupSettings::default(),
+            log_highlights: Vec::new(),
+            remote_hosts: Vec::new(),
+            keys: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum ThemeChoice {
-    Default,
-    Dark,
-    Light,
-    Solarized,
-    Nord,
-    Dracula,
+/// The settings, key bindings included, and the backup profiles of one
+/// machine in a single file, to set up others the same way
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub settings: AppSettings,
+    #[serde(default)]
+    pub profiles: Vec<BackupProfile>,
 }
 
-impl ThemeChoice {
-    pub fn all() -> Vec<ThemeChoice> {
-        vec![
-            ThemeChoice::Default,
-            ThemeChoice::Dark,
-            ThemeChoice::Light,
-            ThemeChoice::Solarized,
-            ThemeChoice::Nord,
-            ThemeChoice::Dracula,
-        ]
-    }
+/// Text prompt for settings export/import
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BundlePrompt {
+    Export,
+    Import,
+}
 
-    pub fn name(&self) -> &'static str {
-        match self {
-            ThemeChoice::Default => "Default",
-            ThemeChoice::Dark => "Dark",
-            ThemeChoice::Light => "Light",
-            ThemeChoice::Solarized => "Solarized",
-            ThemeChoice::Nord => "Nord",
-            ThemeChoice::Dracula => "Dracula",
+/// Per-user settings, which override the system-wide ones:
+/// $XDG_CONFIG_HOME/slackware-cli-manager/config.toml, or under ~/.config
+pub fn user_config_path() -> Option<PathBuf> {
+    let var = |name| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let base = var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))?;
+    Some(base.join("slackware-cli-manager").join(CONFIG_FILE))
+}
+
+/// Directories holding config, the system one first and then the user's
+pub fn config_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![PathBuf::from(CONFIG_DIR)];
+    dirs.extend(user_config_path().and_then(|p| p.parent().map(Path::to_path_buf)));
+    dirs
+}
+
+/// A config file as a TOML table; empty when missing or unreadable
+fn read_table(path: &Path) -> toml::Table {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Lay `overrides` over `base`, merging nested tables key by key
+fn merge(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(inner)), toml::Value::Table(value)) => merge(inner, value),
+            (_, value) => {
+                base.insert(key, value);
+            }
         }
     }
+}
 
-    pub fn colors(&self) -> ThemeColors {
-        match self {
-            ThemeChoice::Default => ThemeColors {
-                primary: Color::Cyan,
-                secondary: Color::Yellow,
-                success: Color::Green,
-                error: Color::Red,
-                warning: Color::Yellow,
-                muted: Color::DarkGray,
-                background: Color::Reset,
-                foreground: Color::White,
-            },
-            ThemeChoice::Dark => ThemeColors {
-                primary: Color::Blue,
-                secondary: Color::Magenta,
-                success: Color::Green,
-                error: Color::Red,
-                warning: Color::Yellow,
-                muted: Color::DarkGray,
-                background: Color::Rgb(30, 30, 30),
-                foreground: Color::White,
-            },
-            ThemeChoice::Light => ThemeColors {
-                primary: Color::Blue,
-                secondary: Color::Magenta,
-                success: Color::Green,
-                error: Color::Red,
-                warning: Color::Rgb(200, 150, 0),
-                muted: Color::Gray,
-                background: Color::White,
-                foreground: Color::Black,
-            },
-            ThemeChoice::Solarized => ThemeColors {
-                primary: Color::Rgb(38, 139, 210),   // Blue
-                secondary: Color::Rgb(211, 54, 130), // Magenta
-                success: Color::Rgb(133, 153, 0),    // Green
-                error: Color::Rgb(220, 50, 47),      // Red
-                warning: Color::Rgb(181, 137, 0),    // Yellow
-                muted: Color::Rgb(88, 110, 117),     // Base01
-                background: Color::Rgb(0, 43, 54),   // Base03
-                foreground: Color::Rgb(131, 148, 150), // Base0
-            },
-            ThemeChoice::Nord => ThemeColors {
-                primary: Color::Rgb(136, 192, 208),  // Nord8
-                secondary: Color::Rgb(180, 142, 173), // Nord15
-                success: Color::Rgb(163, 190, 140),  // Nord14
-                error: Color::Rgb(191, 97, 106),     // Nord11
-                warning: Color::Rgb(235, 203, 139),  // Nord13
-                muted: Color::Rgb(76, 86, 106),      // Nord3
-                background: Color::Rgb(46, 52, 64),  // Nord0
-                foreground: Color::Rgb(236, 239, 244), // Nord6
-            },
-            ThemeChoice::Dracula => ThemeColors {
-                primary: Color::Rgb(139, 233, 253),  // Cyan
-                secondary: Color::Rgb(255, 121, 198), // Pink
-                success: Color::Rgb(80, 250, 123),   // Green
-                error: Color::Rgb(255, 85, 85),      // Red
-                warning: Color::Rgb(241, 250, 140),  // Yellow
-                muted: Color::Rgb(98, 114, 164),     // Comment
-                background: Color::Rgb(40, 42, 54),  // Background
-                foreground: Color::Rgb(248, 248, 242), // Foreground
-            },
+/// The entries of `table` that differ from `base`
+fn difference(table: &toml::Table, base: &toml::Table) -> toml::Table {
+    let mut changed = toml::Table::new();
+    for (key, value) in table {
+        match (value, base.get(key)) {
+            (toml::Value::Table(inner), Some(toml::Value::Table(base))) => {
+                let inner = difference(inner, base);
+                if !inner.is_empty() {
+                    changed.insert(key.clone(), toml::Value::Table(inner));
+                }
+            }
+            (value, Some(base)) if value == base => {}
+            (value, _) => {
+                changed.insert(key.clone(), value.clone());
+            }
         }
     }
+    changed
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct ThemeColors {
-    pub primary: Color,
-    pub secondary: Color,
-    pub success: Color,
-    pub error: Color,
-    pub warning: Color,
-    pub muted: Color,
-    pub background: Color,
-    pub foreground: Color,
+fn write_table(path: &Path, table: &toml::Table) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = toml::to_string_pretty(table)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    fs::write(path, content)
+}
+
+impl AppSettings {
+    /// Load the system settings with the user's on top, falling back to
+    /// defaults for anything neither sets
+    pub fn load() -> Self {
+        let mut table = read_table(&SettingsComponent::config_path());
+        if let Some(user) = user_config_path() {
+            merge(&mut table, read_table(&user));
+        }
+        toml::Value::Table(table).try_into().unwrap_or_default()
+    }
+
+    /// Write the settings, returning the file they went to. Once a user
+    /// has a config of their own, or the system one can't be written (not
+    /// root, read-only /etc), changes go to the user config, holding only
+    /// what differs from the system settings.
+    pub fn save(&self) -> std::io::Result<PathBuf> {
+        let table = self.to_table()?;
+        let system = SettingsComponent::config_path();
+        let user = user_config_path();
+        if !user.as_ref().is_some_and(|u| u.exists()) {
+            match write_table(&system, &table) {
+                Ok(()) => return Ok(system),
+                Err(e) if user.is_none() => return Err(e),
+                Err(_) => {}
+            }
+        }
+        let user = user.ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "no home directory")
+        })?;
+        let defaults: Self = toml::Value::Table(read_table(&system))
+            .try_into()
+            .unwrap_or_default();
+        write_table(&user, &difference(&table, &defaults.to_table()?))?;
+        Ok(user)
+    }
+
+    fn to_table(&self) -> std::io::Result<toml::Table> {
+        match toml::Value::try_from(self) {
+            Ok(toml::Value::Table(table)) => Ok(table),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "settings did not serialize to a table",
+            )),
+        }
+    }
 }
 
 /// Settings section
@@ -160,6 +259,11 @@ pub enum SettingsSection {
     Theme,
     Behavior,
     Display,
+    Backup,
+    Keys,
+    /// Timezone, hardware clock and language of the machine itself, which
+    /// apply right away rather than on save
+    System,
 }
 
 /// Settings Component
@@ -168,20 +272,137 @@ pub struct SettingsComponent {
     list_state: ListState,
     section: SettingsSection,
     editing: bool,
+    edit_buffer: String,
     status_message: Option<(String, bool)>,
     unsaved_changes: bool,
+    /// Built from `settings.keys`, used by the whole app
+    keymap: Keymap,
+    bundle_prompt: Option<(BundlePrompt, String)>,
+    timezone: String,
+    hardware_clock: HardwareClock,
+    lang: String,
 }
 
 impl SettingsComponent {
     pub fn new() -> Self {
-        let settings = Self::load_settings();
+        let settings = AppSettings::load();
+        Theme::set(settings.theme);
+        i18n::set_language(&settings.language);
+        set_confirm_actions(settings.confirm_actions);
+        root::set_escalation(settings.escalation);
+        let (keymap, errors) = Keymap::load(&settings.keys);
         Self {
             settings,
             list_state: ListState::default().with_selected(Some(0)),
             section: SettingsSection::Theme,
             editing: false,
-            status_message: None,
+            edit_buffer: String::new(),
+            status_message: errors
+                .first()
+                .map(|e| (format!("Key bindings: {}", e), true))
+                .or_else(|| {
+                    custom_tab::load_errors()
+                        .first()
+                        .map(|e| (format!("Custom tabs: {}", e), true))
+                }),
             unsaved_changes: false,
+            keymap,
+            bundle_prompt: None,
+            timezone: timeconfig::timezone().unwrap_or_default(),
+            hardware_clock: HardwareClock::load(),
+            lang: timeconfig::lang().unwrap_or_default(),
+        }
+    }
+
+    /// How often tabs reload their data, when auto refresh is on
+    pub fn auto_refresh_interval(&self) -> Option<Duration> {
+        self.settings
+            .auto_refresh
+            .then(|| Duration::from_secs(self.settings.refresh_interval.max(1) as u64))
+    }
+
+    /// How often the screen is drawn while jobs run or output comes in
+    pub fn frame_interval(&self) -> Duration {
+        Duration::from_millis(self.settings.frame_interval.clamp(20, 1000) as u64)
+    }
+
+    /// How often the screen is drawn when nothing happens, for clocks and
+    /// rates that change on their own; input is drawn right away
+    pub fn idle_interval(&self) -> Duration {
+        let idle = self.settings.idle_interval.clamp(250, 10000);
+        Duration::from_millis(idle.max(self.settings.frame_interval) as u64)
+    }
+
+    /// Whether neither a system nor a user config exists yet
+    pub fn first_run() -> bool {
+        !Self::config_path().exists() && !user_config_path().is_some_and(|p| p.exists())
+    }
+
+    /// Take the choices of the first-run wizard and save them
+    pub fn apply_setup(&mut self, theme: ThemeChoice, default_tab: Tab, confirm_actions: bool) {
+        self.settings.theme = theme;
+        self.settings.default_tab = default_tab.id().to_string();
+        self.settings.confirm_actions = confirm_actions;
+        Theme::set(theme);
+        set_confirm_actions(confirm_actions);
+        self.save_settings();
+    }
+
+    pub fn remote_hosts(&self) -> &[RemoteHost] {
+        &self.settings.remote_hosts
+    }
+
+    pub fn mouse_enabled(&self) -> bool {
+        self.settings.mouse
+    }
+
+    pub fn restore_session(&self) -> bool {
+        self.settings.restore_session
+    }
+
+    /// The tab to open at startup
+    pub fn default_tab(&self) -> Tab {
+        Tab::from_id(&self.settings.default_tab).unwrap_or(Tab::Updater)
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Rebuild the keymap after the bindings changed, reporting what is wrong
+    fn reload_keymap(&mut self) {
+        // Only keep bindings that differ from the defaults
+        self.settings.keys.retain(|name, keys| {
+            Action::from_name(name).map_or(true, |a| a.default_keys() != keys.as_str())
+        });
+        let (keymap, errors) = Keymap::load(&self.settings.keys);
+        self.keymap = keymap;
+        if let Some(error) = errors.first() {
+            self.status_message = Some((error.clone(), true));
+        } else if let Some((key, first, second)) = self.keymap.conflicts().first() {
+            self.status_message = Some((
+                format!("{} is bound to both {} and {}", key, first.name(), second.name()),
+                true,
+            ));
+        }
+    }
+
+    fn key_value(&self, action: Action) -> String {
+        let keys = self
+            .settings
+            .keys
+            .get(action.name())
+            .map(String::as_str)
+            .unwrap_or(action.default_keys());
+        let conflicted = self
+            .keymap
+            .conflicts()
+            .iter()
+            .any(|(_, a, b)| *a == action || *b == action);
+        match (keys.is_empty(), conflicted) {
+            (true, _) => "(unbound)".to_string(),
+            (false, true) => format!("{}  (conflict)", keys),
+            (false, false) => keys.to_string(),
         }
     }
 
@@ -189,41 +410,108 @@ impl SettingsComponent {
         PathBuf::from(CONFIG_DIR).join(CONFIG_FILE)
     }
 
-    fn load_settings() -> AppSettings {
-        let path = Self::config_path();
-        if path.exists() {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(settings) = toml::from_str(&content) {
-                    return settings;
+    fn save_settings(&mut self) -> bool {
+        // Highlight rules are edited from the log viewer, which saves them itself
+        self.settings.log_highlights = AppSettings::load().log_highlights;
+        match self.settings.save() {
+            Ok(path) => {
+                self.unsaved_changes = false;
+                self.status_message =
+                    Some((format!("Settings saved to {}", path.display()), false));
+                true
+            }
+            Err(e) => {
+                self.status_message = Some((format!("Failed to save: {}", e), true));
+                false
+            }
+        }
+    }
+
+    /// Write the settings and every backup profile to one file
+    fn export_bundle(&mut self, path: &str) {
+        let mut settings = self.settings.clone();
+        settings.log_highlights = AppSettings::load().log_highlights;
+        let mut profiles = Vec::new();
+        for name in BackupProfile::available() {
+            match BackupProfile::load(&BackupProfile::resolve_path(&name)) {
+                Ok(profile) => profiles.push(profile),
+                Err(e) => {
+                    self.status_message =
+                        Some((format!("Failed to read profile {}: {}", name, e), true));
+                    return;
                 }
             }
         }
-        AppSettings::default()
+        let bundle = SettingsBundle { settings, profiles };
+        let result = toml::to_string_pretty(&bundle)
+            .map_err(|e| e.to_string())
+            .and_then(|content| fs::write(path, content).map_err(|e| e.to_string()));
+        self.status_message = Some(match result {
+            Ok(()) => (
+                format!(
+                    "Exported settings and {} backup profiles to {}",
+                    bundle.profiles.len(),
+                    path
+                ),
+                false,
+            ),
+            Err(e) => (format!("Failed to export: {}", e), true),
+        });
     }
 
-    fn save_settings(&mut self) -> bool {
-        // Ensure config directory exists
-        if let Err(e) = fs::create_dir_all(CONFIG_DIR) {
-            self.status_message = Some((format!("Failed to create config dir: {}", e), true));
-            return false;
+    /// Apply and save the settings from an exported file, and add its
+    /// backup profiles to this machine's
+    fn import_bundle(&mut self, path: &str) {
+        let bundle: SettingsBundle = match fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| toml::from_str(&content).map_err(|e| e.to_string()))
+        {
+            Ok(bundle) => bundle,
+            Err(e) => {
+                self.status_message = Some((format!("Failed to import: {}", e), true));
+                return;
+            }
+        };
+        for profile in &bundle.profiles {
+            // A name with a path in it would be written outside the profile directory
+            if profile.name.is_empty() || profile.name.contains('/') {
+                self.status_message =
+                    Some((format!("Invalid profile name '{}' in {}", profile.name, path), true));
+                return;
+            }
+        }
+        for profile in &bundle.profiles {
+            if let Err(e) = profile.save(&BackupProfile::resolve_path(&profile.name)) {
+                self.status_message =
+                    Some((format!("Failed to save profile {}: {}", profile.name, e), true));
+                return;
+            }
         }
 
-        let path = Self::config_path();
-        match toml::to_string_pretty(&self.settings) {
-            Ok(content) => {
-                if let Err(e) = fs::write(&path, content) {
-                    self.status_message = Some((format!("Failed to save: {}", e), true));
-                    return false;
-                }
+        self.settings = bundle.settings;
+        Theme::set(self.settings.theme);
+        i18n::set_language(&self.settings.language);
+        set_confirm_actions(self.settings.confirm_actions);
+        root::set_escalation(self.settings.escalation);
+        match self.settings.save() {
+            Ok(saved) => {
                 self.unsaved_changes = false;
-                self.status_message = Some(("Settings saved".to_string(), false));
-                true
+                self.status_message = Some((
+                    format!(
+                        "Imported settings and {} backup profiles, saved to {}",
+                        bundle.profiles.len(),
+                        saved.display()
+                    ),
+                    false,
+                ));
             }
             Err(e) => {
-                self.status_message = Some((format!("Serialization error: {}", e), true));
-                false
+                self.unsaved_changes = true;
+                self.status_message = Some((format!("Imported but failed to save: {}", e), true));
             }
         }
+        // Bad bindings from the file replace the message above
+        self.reload_keymap();
     }
 
     fn get_section_items(&self) -> Vec<(&'static str, String, bool)> {
@@ -231,8 +519,12 @@ impl SettingsComponent {
             SettingsSection::Theme => {
                 vec![(
                     "Color Theme",
-                    self.settings.theme.name().to_string(),
-                    true,
+                    if Theme::no_color() {
+                        format!("{} (NO_COLOR)", ThemeChoice::Monochrome.name())
+                    } else {
+                        self.settings.theme.name().to_string()
+                    },
+                    !Theme::no_color(),
                 )]
             }
             SettingsSection::Behavior => {
@@ -262,10 +554,40 @@ impl SettingsComponent {
                         format!("{} seconds", self.settings.refresh_interval),
                         self.settings.auto_refresh,
                     ),
+                    (
+                        "Mouse Support",
+                        if self.settings.mouse {
+                            "Yes"
+                        } else {
+                            "No"
+                        }
+                        .to_string(),
+                        true,
+                    ),
+                    (
+                        "Frame Interval",
+                        format!("{} ms", self.settings.frame_interval),
+                        true,
+                    ),
+                    (
+                        "Idle Redraw",
+                        format!("{} ms", self.settings.idle_interval),
+                        true,
+                    ),
+                    (
+                        "Privilege Tool",
+                        self.settings.escalation.program().to_string(),
+                        true,
+                    ),
                 ]
             }
             SettingsSection::Display => {
                 vec![
+                    (
+                        "Interface Language",
+                        i18n::language_name(&self.settings.language).to_string(),
+                        true,
+                    ),
                     (
                         "Show Hidden Files",
                         if self.settings.show_hidden_files {
@@ -281,9 +603,172 @@ impl SettingsComponent {
                         format!("{} lines", self.settings.log_lines),
                         true,
                     ),
-                    ("Default Tab", self.settings.default_tab.clone(), true),
+                    (
+                        "Default Tab",
+                        match Tab::from_id(&self.settings.default_tab) {
+                            Some(tab) => tr(tab.title()).to_string(),
+                            None => format!("{} (unknown)", self.settings.default_tab),
+                        },
+                        true,
+                    ),
+                    (
+                        "Restore Session",
+                        if self.settings.restore_session {
+                            "Yes"
+                        } else {
+                            "No"
+                        }
+                        .to_string(),
+                        true,
+                    ),
+                    (
+                        "Log Highlights",
+                        format!(
+                            "{} rule(s) - press H in the log viewer",
+                            self.settings.log_highlights.len()
+                        ),
+                        false,
+                    ),
                 ]
             }
+            SettingsSection::Backup => {
+                vec![
+                    (
+                        "Remote Target",
+                        Self::text_value(&self.settings.backup.remote_target),
+                        true,
+                    ),
+                    (
+                        "SSH Key",
+                        Self::text_value(&self.settings.backup.ssh_key),
+                        true,
+                    ),
+                    (
+                        "GPG Recipient",
+                        Self::text_value(&self.settings.backup.gpg_recipient),
+                        true,
+                    ),
+                    (
+                        "Snapshot Excludes",
+                        Self::text_value(&self.settings.backup.snapshot_excludes),
+                        true,
+                    ),
+                    (
+                        "Keep Last",
+                        match self.settings.backup.keep_last {
+                            0 => "Unlimited".to_string(),
+                            n => format!("{} backups", n),
+                        },
+                        true,
+                    ),
+                    (
+                        "Keep Days",
+                        match self.settings.backup.keep_days {
+                            0 => "Forever".to_string(),
+                            n => format!("{} days", n),
+                        },
+                        true,
+                    ),
+                ]
+            }
+            SettingsSection::Keys => Action::all()
+                .into_iter()
+                .map(|action| (action.name(), self.key_value(action), true))
+                .collect(),
+            SettingsSection::System => vec![
+                ("Timezone", Self::text_value(&self.timezone), true),
+                ("Hardware Clock", self.hardware_clock.label().to_string(), true),
+                ("Language", Self::text_value(&self.lang), true),
+            ],
+        }
+    }
+
+    /// Re-read the system settings, which other tools may have changed
+    fn load_system(&mut self) {
+        self.timezone = timeconfig::timezone().unwrap_or_default();
+        self.hardware_clock = HardwareClock::load();
+        self.lang = timeconfig::lang().unwrap_or_default();
+    }
+
+    /// Write a system setting as soon as it is changed
+    fn apply_system(&mut self, name: &str) {
+        let (result, done) = match name {
+            "Timezone" => (
+                timeconfig::set_timezone(&self.timezone),
+                format!("Timezone set to {}", self.timezone),
+            ),
+            "Hardware Clock" => (
+                self.hardware_clock.save(),
+                format!("Hardware clock set to {}", self.hardware_clock.label()),
+            ),
+            "Language" => (
+                timeconfig::set_lang(&self.lang),
+                format!("LANG set to {} for new logins", self.lang),
+            ),
+            _ => return,
+        };
+        self.status_message = Some(match result {
+            Ok(()) => (done, false),
+            Err(e) => (format!("Failed to set {}: {}", name.to_lowercase(), e), true),
+        });
+        self.load_system();
+    }
+
+    fn text_value(value: &str) -> String {
+        if value.is_empty() {
+            "(not set)".to_string()
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// Get a mutable reference to the text setting with the given name
+    fn text_field_mut(&mut self, name: &str) -> Option<&mut String> {
+        match name {
+            "Remote Target" => Some(&mut self.settings.backup.remote_target),
+            "SSH Key" => Some(&mut self.settings.backup.ssh_key),
+            "GPG Recipient" => Some(&mut self.settings.backup.gpg_recipient),
+            "Snapshot Excludes" => Some(&mut self.settings.backup.snapshot_excludes),
+            "Timezone" => Some(&mut self.timezone),
+            "Language" => Some(&mut self.lang),
+            name => {
+                let action = Action::from_name(name)?;
+                Some(
+                    self.settings
+                        .keys
+                        .entry(name.to_string())
+                        .or_insert_with(|| action.default_keys().to_string()),
+                )
+            }
+        }
+    }
+
+    fn start_editing(&mut self, name: &str) {
+        if let Some(value) = self.text_field_mut(name) {
+            let value = value.clone();
+            self.edit_buffer = value;
+            self.editing = true;
+        }
+    }
+
+    fn finish_editing(&mut self) {
+        let items = self.get_section_items();
+        let selected = self.list_state.selected().unwrap_or(0);
+        if let Some((name, _, _)) = items.get(selected) {
+            let value = self.edit_buffer.trim().to_string();
+            if let Some(field) = self.text_field_mut(name) {
+                *field = value;
+                if self.section == SettingsSection::System {
+                    self.apply_system(name);
+                } else {
+                    self.unsaved_changes = true;
+                }
+            }
+        }
+        self.editing = false;
+        self.edit_buffer.clear();
+        if self.section == SettingsSection::Keys {
+            self.reload_keymap();
         }
     }
 
@@ -313,11 +798,13 @@ impl SettingsComponent {
                     (current_idx + themes.len() - 1) % themes.len()
                 };
                 self.settings.theme = themes[new_idx];
+                Theme::set(self.settings.theme);
             }
             SettingsSection::Behavior => {
                 match *name {
                     "Confirm Actions" => {
                         self.settings.confirm_actions = !self.settings.confirm_actions;
+                        set_confirm_actions(self.settings.confirm_actions);
                     }
                     "Auto Refresh" => {
                         self.settings.auto_refresh = !self.settings.auto_refresh;
@@ -331,6 +818,31 @@ impl SettingsComponent {
                                 self.settings.refresh_interval.saturating_sub(1).max(1);
                         }
                     }
+                    "Mouse Support" => {
+                        self.settings.mouse = !self.settings.mouse;
+                    }
+                    "Frame Interval" => {
+                        if forward {
+                            self.settings.frame_interval =
+                                (self.settings.frame_interval + 10).min(1000);
+                        } else {
+                            self.settings.frame_interval =
+                                self.settings.frame_interval.saturating_sub(10).max(20);
+                        }
+                    }
+                    "Idle Redraw" => {
+                        if forward {
+                            self.settings.idle_interval =
+                                (self.settings.idle_interval + 250).min(10000);
+                        } else {
+                            self.settings.idle_interval =
+                                self.settings.idle_interval.saturating_sub(250).max(250);
+                        }
+                    }
+                    "Privilege Tool" => {
+                        self.settings.escalation = self.settings.escalation.next();
+                        root::set_escalation(self.settings.escalation);
+                    }
                     _ => {}
                 }
             }
@@ -347,41 +859,129 @@ impl SettingsComponent {
                                 self.settings.log_lines.saturating_sub(100).max(100);
                         }
                     }
+                    "Interface Language" => {
+                        let codes: Vec<&str> = i18n::LANGUAGES.iter().map(|(c, _)| *c).collect();
+                        let current_idx = codes
+                            .iter()
+                            .position(|c| *c == self.settings.language)
+                            .unwrap_or(0);
+                        let new_idx = if forward {
+                            (current_idx + 1) % codes.len()
+                        } else {
+                            (current_idx + codes.len() - 1) % codes.len()
+                        };
+                        self.settings.language = codes[new_idx].to_string();
+                        i18n::set_language(&self.settings.language);
+                    }
                     "Default Tab" => {
-                        let tabs = [
-                            "updater",
-                            "sbotools",
-                            "user_setup",
-                            "mirror",
-                            "packages",
-                            "config",
-                        ];
+                        let tabs = Tab::all();
                         let current_idx = tabs
                             .iter()
-                            .position(|&t| t == self.settings.default_tab)
+                            .position(|t| t.id() == self.settings.default_tab)
                             .unwrap_or(0);
                         let new_idx = if forward {
                             (current_idx + 1) % tabs.len()
                         } else {
                             (current_idx + tabs.len() - 1) % tabs.len()
                         };
-                        self.settings.default_tab = tabs[new_idx].to_string();
+                        self.settings.default_tab = tabs[new_idx].id().to_string();
+                    }
+                    "Restore Session" => {
+                        self.settings.restore_session = !self.settings.restore_session;
                     }
                     _ => {}
                 }
             }
+            SettingsSection::Backup => {
+                let backup = &mut self.settings.backup;
+                match *name {
+                    "Keep Last" => {
+                        backup.keep_last = if forward {
+                            (backup.keep_last + 1).min(100)
+                        } else {
+                            backup.keep_last.saturating_sub(1)
+                        };
+                    }
+                    "Keep Days" => {
+                        backup.keep_days = if forward {
+                            (backup.keep_days + 1).min(3650)
+                        } else {
+                            backup.keep_days.saturating_sub(1)
+                        };
+                    }
+                    name => {
+                        self.start_editing(name);
+                        return;
+                    }
+                }
+            }
+            SettingsSection::Keys => {
+                self.start_editing(name);
+                return;
+            }
+            SettingsSection::System => {
+                if *name == "Hardware Clock" {
+                    self.hardware_clock = match self.hardware_clock {
+                        HardwareClock::Utc => HardwareClock::Localtime,
+                        HardwareClock::Localtime => HardwareClock::Utc,
+                    };
+                    self.apply_system(name);
+                } else {
+                    self.start_editing(name);
+                }
+                return;
+            }
         }
 
         self.unsaved_changes = true;
     }
-
-    pub fn get_theme(&self) -> ThemeChoice {
-        self.settings.theme
-    }
 }
 
 impl Component for SettingsComponent {
     fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        if let Some((prompt, buffer)) = &mut self.bundle_prompt {
+            match key.code {
+                KeyCode::Enter => {
+                    let prompt = *prompt;
+                    let value = buffer.trim().to_string();
+                    self.bundle_prompt = None;
+                    if !value.is_empty() {
+                        match prompt {
+                            BundlePrompt::Export => self.export_bundle(&value),
+                            BundlePrompt::Import => self.import_bundle(&value),
+                        }
+                    }
+                }
+                KeyCode::Esc => self.bundle_prompt = None,
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.editing {
+            match key.code {
+                KeyCode::Enter => self.finish_editing(),
+                KeyCode::Esc => {
+                    self.editing = false;
+                    self.edit_buffer.clear();
+                    if self.section == SettingsSection::Keys {
+                        // Drop the default that editing filled in
+                        self.reload_keymap();
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.edit_buffer.pop();
+                }
+                KeyCode::Char(c) => self.edit_buffer.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
         let items_len = self.get_section_items().len();
 
         match key.code {
@@ -389,7 +989,10 @@ impl Component for SettingsComponent {
                 self.section = match self.section {
                     SettingsSection::Theme => SettingsSection::Behavior,
                     SettingsSection::Behavior => SettingsSection::Display,
-                    SettingsSection::Display => SettingsSection::Theme,
+                    SettingsSection::Display => SettingsSection::Backup,
+                    SettingsSection::Backup => SettingsSection::Keys,
+                    SettingsSection::Keys => SettingsSection::System,
+                    SettingsSection::System => SettingsSection::Theme,
                 };
                 self.list_state.select(Some(0));
             }
@@ -416,8 +1019,19 @@ impl Component for SettingsComponent {
             KeyCode::Char('s') => {
                 self.save_settings();
             }
+            KeyCode::Char('x') => {
+                self.bundle_prompt = Some((BundlePrompt::Export, BUNDLE_FILE.to_string()));
+            }
+            KeyCode::Char('i') => {
+                self.bundle_prompt = Some((BundlePrompt::Import, BUNDLE_FILE.to_string()));
+            }
             KeyCode::Char('r') => {
                 self.settings = AppSettings::default();
+                Theme::set(self.settings.theme);
+                i18n::set_language(&self.settings.language);
+                set_confirm_actions(self.settings.confirm_actions);
+                root::set_escalation(self.settings.escalation);
+                self.reload_keymap();
                 self.unsaved_changes = true;
                 self.status_message = Some(("Settings reset to defaults".to_string(), false));
             }
@@ -438,17 +1052,29 @@ impl Component for SettingsComponent {
             .split(area);
 
         // Section tabs
-        let section_text = match self.section {
-            SettingsSection::Theme => "[Theme]  Behavior  Display",
-            SettingsSection::Behavior => " Theme  [Behavior]  Display",
-            SettingsSection::Display => " Theme   Behavior  [Display]",
-        };
+        let section_text: String = [
+            (SettingsSection::Theme, "Theme"),
+            (SettingsSection::Behavior, "Behavior"),
+            (SettingsSection::Display, "Display"),
+            (SettingsSection::Backup, "Backup"),
+            (SettingsSection::Keys, "Keys"),
+            (SettingsSection::System, "System"),
+        ]
+        .iter()
+        .map(|(section, name)| {
+            if *section == self.section {
+                format!("[{}] ", tr(name))
+            } else {
+                format!(" {}  ", tr(name))
+            }
+        })
+        .collect();
 
         let section_bar = Paragraph::new(Line::from(vec![
-            Span::styled("Section: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{}: ", tr("Section")), Theme::label()),
             Span::raw(section_text),
             if self.unsaved_changes {
-                Span::styled(" (unsaved)", Style::default().fg(Color::Yellow))
+                Span::styled(format!(" ({})", tr("unsaved")), Theme::warning())
             } else {
                 Span::raw("")
             },
@@ -456,27 +1082,36 @@ impl Component for SettingsComponent {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Settings "),
+                .title(format!(" {} ", tr("Settings"))),
         );
         frame.render_widget(section_bar, chunks[0]);
 
         // Settings list
+        let selected = self.list_state.selected().unwrap_or(0);
         let items: Vec<ListItem> = self
             .get_section_items()
             .iter()
-            .map(|(name, value, enabled)| {
+            .enumerate()
+            .map(|(i, (name, value, enabled))| {
                 let style = if *enabled {
                     Style::default()
                 } else {
-                    Style::default().fg(Color::DarkGray)
+                    Theme::muted()
                 };
 
+                if self.editing && i == selected {
+                    return ListItem::new(Line::from(vec![
+                        Span::styled(format!("{:<20}", tr(name)), style),
+                        Span::styled(format!("{}_", self.edit_buffer), Theme::input_active()),
+                    ]));
+                }
+
                 ListItem::new(Line::from(vec![
-                    Span::styled(format!("{:<20}", name), style),
+                    Span::styled(format!("{:<20}", tr(name)), style),
                     Span::styled(
-                        format!("< {} >", value),
+                        format!("< {} >", tr(value)),
                         if *enabled {
-                            Style::default().fg(Color::Cyan)
+                            Theme::label()
                         } else {
                             style
                         },
@@ -497,15 +1132,39 @@ impl Component for SettingsComponent {
         self.render_theme_preview(frame, chunks[2]);
 
         // Status bar
-        let status_content = if let Some((msg, is_error)) = &self.status_message {
+        let status_content = if let Some((prompt, buffer)) = &self.bundle_prompt {
+            let label = match prompt {
+                BundlePrompt::Export => "Export settings to: ",
+                BundlePrompt::Import => "Import settings from: ",
+            };
+            Line::from(vec![
+                Span::styled(label, Theme::warning()),
+                Span::styled(format!("{}_", buffer), Theme::input_active()),
+            ])
+        } else if self.editing && self.section == SettingsSection::Keys {
+            Line::from(Span::styled(
+                "Keys like ctrl+n, alt+left or f5, comma-separated; empty to unbind",
+                Theme::warning(),
+            ))
+        } else if self.editing && self.section == SettingsSection::System {
+            Line::from(Span::styled(
+                "A zone under /usr/share/zoneinfo (Europe/Berlin) or a locale from `locale -a`",
+                Theme::warning(),
+            ))
+        } else if self.editing {
+            Line::from(Span::styled(
+                "Type a value, Enter to apply, Esc to cancel",
+                Theme::warning(),
+            ))
+        } else if let Some((msg, is_error)) = &self.status_message {
             Line::from(Span::styled(
                 msg.clone(),
-                Style::default().fg(if *is_error { Color::Red } else { Color::Green }),
+                if *is_error { Theme::error() } else { Theme::success() },
             ))
         } else {
             Line::from(Span::styled(
-                "Use ←/→ to change values, 's' to save, 'r' to reset",
-                Style::default().fg(Color::DarkGray),
+                "Use ←/→ to change values, 's' to save, 'r' to reset, 'x'/'i' to export/import",
+                Theme::muted(),
             ))
         };
 
@@ -514,14 +1173,26 @@ impl Component for SettingsComponent {
         frame.render_widget(status, chunks[3]);
     }
 
+    fn is_typing(&self) -> bool {
+        self.editing || self.bundle_prompt.is_some()
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
         vec![
             ("Tab", "Section"),
             ("←/→", "Change"),
             ("s", "Save"),
             ("r", "Reset"),
+            ("x", "Export"),
+            ("i", "Import"),
         ]
     }
+
+    fn on_activate(&mut self) {
+        // The log viewer may have changed these since the tab was opened
+        self.settings.log_highlights = AppSettings::load().log_highlights;
+        self.load_system();
+    }
 }
 
 impl SettingsComponent {
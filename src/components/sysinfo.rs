@@ -6,18 +6,20 @@ use ratatui::{
     widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
     Frame,
 };
-use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, Networks, RefreshKind, System};
-use std::time::Instant;
+use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, RefreshKind, System};
+use std::time::{Duration, Instant};
 
 use crate::app::Message;
 use crate::components::Component;
 use crate::ui::theme::Theme;
+use crate::utils::bandwidth::{format_rate, BandwidthMonitor};
+use crate::utils::i18n::tr;
 
 /// System Information Dashboard Component
 pub struct SysInfoComponent {
     system: System,
     disks: Disks,
-    networks: Networks,
+    bandwidth: BandwidthMonitor,
     last_refresh: Instant,
     selected_section: usize,
     scroll_offset: usize,
@@ -31,12 +33,11 @@ impl SysInfoComponent {
                 .with_memory(MemoryRefreshKind::everything()),
         );
         let disks = Disks::new_with_refreshed_list();
-        let networks = Networks::new_with_refreshed_list();
 
         Self {
             system,
             disks,
-            networks,
+            bandwidth: BandwidthMonitor::new(Duration::from_secs(1)),
             last_refresh: Instant::now(),
             selected_section: 0,
             scroll_offset: 0,
@@ -47,7 +48,6 @@ impl SysInfoComponent {
         self.system.refresh_cpu_all();
         self.system.refresh_memory();
         self.disks.refresh();
-        self.networks.refresh();
         self.last_refresh = Instant::now();
     }
 
@@ -72,7 +72,7 @@ impl SysInfoComponent {
 
     fn render_system_info(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
-            .title(" System Information ")
+            .title(format!(" {} ", tr("System Information")))
             .borders(Borders::ALL)
             .border_style(if self.selected_section == 0 {
                 Theme::highlight()
@@ -85,28 +85,28 @@ impl SysInfoComponent {
 
         let info_lines = vec![
             Line::from(vec![
-                Span::styled("Hostname: ", Style::default().fg(Color::Cyan)),
-                Span::raw(System::host_name().unwrap_or_else(|| "Unknown".to_string())),
+                Span::styled(format!("{} ", tr("Hostname:")), Theme::label()),
+                Span::raw(System::host_name().unwrap_or_else(|| tr("Unknown").to_string())),
             ]),
             Line::from(vec![
-                Span::styled("OS: ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{} ", tr("OS:")), Theme::label()),
                 Span::raw(format!(
                     "{} {}",
-                    System::name().unwrap_or_else(|| "Unknown".to_string()),
-                    System::os_version().unwrap_or_else(|| "".to_string())
+                    System::name().unwrap_or_else(|| tr("Unknown").to_string()),
+                    System::os_version().unwrap_or_default()
                 )),
             ]),
             Line::from(vec![
-                Span::styled("Kernel: ", Style::default().fg(Color::Cyan)),
-                Span::raw(System::kernel_version().unwrap_or_else(|| "Unknown".to_string())),
+                Span::styled(format!("{} ", tr("Kernel:")), Theme::label()),
+                Span::raw(System::kernel_version().unwrap_or_else(|| tr("Unknown").to_string())),
             ]),
             Line::from(vec![
-                Span::styled("Uptime: ", Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{} ", tr("Uptime:")), Theme::label()),
                 Span::raw(Self::format_uptime(System::uptime())),
             ]),
             Line::from(vec![
-                Span::styled("Architecture: ", Style::default().fg(Color::Cyan)),
-                Span::raw(System::cpu_arch().unwrap_or_else(|| "Unknown".to_string())),
+                Span::styled(format!("{} ", tr("Architecture:")), Theme::label()),
+                Span::raw(System::cpu_arch().unwrap_or_else(|| tr("Unknown").to_string())),
             ]),
         ];
 
@@ -130,7 +130,7 @@ impl SysInfoComponent {
 
     fn render_cpu(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
-            .title(" CPU ")
+            .title(format!(" {} ", tr("CPU")))
             .borders(Borders::ALL)
             .border_style(if self.selected_section == 1 {
                 Theme::highlight()
@@ -158,7 +158,7 @@ impl SysInfoComponent {
             .block(Block::default())
             .gauge_style(Style::default().fg(Self::usage_color(overall_usage as u16)))
             .percent(overall_usage as u16)
-            .label(format!("Overall: {:.1}%", overall_usage));
+            .label(format!("{} {:.1}%", tr("Overall:"), overall_usage));
         frame.render_widget(gauge, chunks[0]);
 
         // Per-core display
@@ -177,7 +177,7 @@ impl SysInfoComponent {
                     "░".repeat(bar_width - filled)
                 );
                 ListItem::new(Line::from(vec![
-                    Span::styled(format!("CPU{:2}: ", i), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("CPU{:2}: ", i), Theme::label()),
                     Span::styled(bar, Style::default().fg(Self::usage_color(usage as u16))),
                     Span::raw(format!(" {:5.1}%", usage)),
                 ]))
@@ -190,17 +190,17 @@ impl SysInfoComponent {
 
     fn usage_color(percent: u16) -> Color {
         if percent >= 90 {
-            Color::Red
+            Theme::colors().error
         } else if percent >= 70 {
-            Color::Yellow
+            Theme::colors().warning
         } else {
-            Color::Green
+            Theme::colors().success
         }
     }
 
     fn render_memory(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
-            .title(" Memory ")
+            .title(format!(" {} ", tr("Memory")))
             .borders(Borders::ALL)
             .border_style(if self.selected_section == 2 {
                 Theme::highlight()
@@ -235,7 +235,7 @@ impl SysInfoComponent {
 
         // RAM gauge
         let ram_gauge = Gauge::default()
-            .block(Block::default().title("RAM"))
+            .block(Block::default().title(tr("RAM")))
             .gauge_style(Style::default().fg(Self::usage_color(mem_percent)))
             .percent(mem_percent)
             .label(format!(
@@ -248,7 +248,7 @@ impl SysInfoComponent {
 
         // Swap gauge
         let swap_gauge = Gauge::default()
-            .block(Block::default().title("Swap"))
+            .block(Block::default().title(tr("Swap")))
             .gauge_style(Style::default().fg(Self::usage_color(swap_percent)))
             .percent(swap_percent)
             .label(format!(
@@ -262,7 +262,7 @@ impl SysInfoComponent {
 
     fn render_network(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
-            .title(" Network ")
+            .title(format!(" {} ", tr("Network")))
             .borders(Borders::ALL)
             .border_style(if self.selected_section == 3 {
                 Theme::highlight()
@@ -274,29 +274,25 @@ impl SysInfoComponent {
         frame.render_widget(block, area);
 
         let items: Vec<ListItem> = self
-            .networks
-            .iter()
-            .map(|(name, data)| {
+            .bandwidth
+            .all_rates()
+            .filter(|(name, _)| name.as_str() != "lo")
+            .map(|(name, rates)| {
+                let (rx, tx) = rates.current();
                 ListItem::new(vec![
                     Line::from(vec![
                         Span::styled(
                             format!("{}: ", name),
                             Style::default()
-                                .fg(Color::Cyan)
+                                .fg(Theme::colors().primary)
                                 .add_modifier(Modifier::BOLD),
                         ),
                     ]),
                     Line::from(vec![
-                        Span::styled("  ↓ ", Style::default().fg(Color::Green)),
-                        Span::raw(format!(
-                            "{}/s  ",
-                            Self::format_bytes(data.received())
-                        )),
-                        Span::styled("↑ ", Style::default().fg(Color::Red)),
-                        Span::raw(format!(
-                            "{}/s",
-                            Self::format_bytes(data.transmitted())
-                        )),
+                        Span::styled("  ↓ ", Theme::success()),
+                        Span::raw(format!("{}  ", format_rate(rx))),
+                        Span::styled("↑ ", Theme::error()),
+                        Span::raw(format_rate(tx)),
                     ]),
                 ])
             })
@@ -308,7 +304,7 @@ impl SysInfoComponent {
 
     fn render_processes(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
-            .title(" Top Processes ")
+            .title(format!(" {} ", tr("Top Processes")))
             .borders(Borders::ALL)
             .border_style(if self.selected_section == 4 {
                 Theme::highlight()
@@ -337,7 +333,7 @@ impl SysInfoComponent {
                 ListItem::new(Line::from(vec![
                     Span::styled(
                         format!("{:>6} ", p.pid()),
-                        Style::default().fg(Color::DarkGray),
+                        Theme::muted(),
                     ),
                     Span::styled(
                         format!("{:>5.1}% ", p.cpu_usage()),
@@ -345,7 +341,7 @@ impl SysInfoComponent {
                     ),
                     Span::styled(
                         format!("{:>8} ", Self::format_bytes(p.memory())),
-                        Style::default().fg(Color::Yellow),
+                        Theme::warning(),
                     ),
                     Span::raw(p.name().to_string_lossy().chars().take(30).collect::<String>()),
                 ]))
@@ -373,9 +369,7 @@ impl Component for SysInfoComponent {
                 self.scroll_offset = 0;
             }
             KeyCode::Up | KeyCode::Char('k') => {
-                if self.scroll_offset > 0 {
-                    self.scroll_offset -= 1;
-                }
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 self.scroll_offset += 1;
@@ -427,5 +421,14 @@ impl Component for SysInfoComponent {
 
     fn on_activate(&mut self) {
         self.refresh();
+        self.bandwidth.sample();
+    }
+
+    fn on_tick(&mut self) {
+        self.bandwidth.sample_if_due();
+    }
+
+    fn on_refresh(&mut self) {
+        self.refresh();
     }
 }
@@ -0,0 +1,293 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::time::Duration;
+
+use super::{settings, Component, Tab};
+use crate::app::Message;
+use crate::ui::theme::Theme;
+use crate::utils::i18n::{tr, trf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskState {
+    /// A later step of a running sequence, such as the update cycle
+    Queued,
+    Running,
+    Finished,
+    Cancelled,
+}
+
+impl TaskState {
+    fn label(&self) -> &'static str {
+        tr(match self {
+            TaskState::Queued => "queued",
+            TaskState::Running => "running",
+            TaskState::Finished => "done",
+            TaskState::Cancelled => "cancelled",
+        })
+    }
+
+    fn style(&self) -> Style {
+        match self {
+            TaskState::Queued => Theme::muted(),
+            TaskState::Running => Theme::warning(),
+            TaskState::Finished => Theme::success(),
+            TaskState::Cancelled => Theme::error(),
+        }
+    }
+}
+
+/// A background job as the Tasks tab shows it
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    /// `None` for queued steps, which are no job yet
+    pub id: Option<u64>,
+    pub label: String,
+    /// Tab the job was started from
+    pub tab: Tab,
+    pub state: TaskState,
+    pub elapsed: Duration,
+    /// Such as "step 2/5" for jobs that are one of a sequence
+    pub progress: Option<String>,
+    pub output: Vec<String>,
+}
+
+/// Lists the jobs running in the background, with their output, and
+/// cancels them
+pub struct TasksComponent {
+    tasks: Vec<TaskInfo>,
+    list_state: ListState,
+    /// Showing the whole output of the selected task, scrolled this far
+    output_scroll: Option<usize>,
+    show_confirm: bool,
+}
+
+impl TasksComponent {
+    pub fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+            list_state: ListState::default(),
+            output_scroll: None,
+            show_confirm: false,
+        }
+    }
+
+    /// Take the current jobs, keeping the same one selected
+    pub fn set_tasks(&mut self, tasks: Vec<TaskInfo>) {
+        let selected_id = self.selected().and_then(|t| t.id);
+        let index = selected_id
+            .and_then(|id| tasks.iter().position(|t| t.id == Some(id)))
+            .or_else(|| self.list_state.selected())
+            .map(|i| i.min(tasks.len().saturating_sub(1)));
+        self.tasks = tasks;
+        self.list_state.select(if self.tasks.is_empty() {
+            None
+        } else {
+            index.or(Some(0))
+        });
+    }
+
+    fn selected(&self) -> Option<&TaskInfo> {
+        self.tasks.get(self.list_state.selected()?)
+    }
+
+    fn render_output(&self, frame: &mut Frame, area: Rect, task: Option<&TaskInfo>, scroll: usize) {
+        let lines = task.map_or(&[][..], |t| t.output.as_slice());
+        let height = area.height.saturating_sub(2) as usize;
+        // Scrolled back from the end, so new lines show up while following
+        let end = lines.len().saturating_sub(scroll);
+        let shown: Vec<Line> = lines[end.saturating_sub(height)..end]
+            .iter()
+            .map(|l| Line::from(l.as_str()))
+            .collect();
+        let title = match task {
+            Some(task) => format!(" {} ", trf("Output: {}", &[&task.label])),
+            None => format!(" {} ", tr("Output")),
+        };
+        let output =
+            Paragraph::new(shown).block(Block::default().title(title).borders(Borders::ALL));
+        frame.render_widget(output, area);
+    }
+}
+
+/// Elapsed time such as "45s" or "3m 07s"
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        s if s >= 3600 => format!("{}h {:02}m", s / 3600, s % 3600 / 60),
+        s if s >= 60 => format!("{}m {:02}s", s / 60, s % 60),
+        s => format!("{}s", s),
+    }
+}
+
+impl Component for TasksComponent {
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        if self.show_confirm {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.show_confirm = false;
+                    return self.selected().and_then(|t| t.id).map(Message::CancelJob);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.show_confirm = false;
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some(scroll) = self.output_scroll {
+            let count = self.selected().map_or(0, |t| t.output.len());
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.output_scroll = Some((scroll + 1).min(count.saturating_sub(1)))
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.output_scroll = Some(scroll.saturating_sub(1))
+                }
+                KeyCode::PageUp => {
+                    self.output_scroll = Some((scroll + 10).min(count.saturating_sub(1)))
+                }
+                KeyCode::PageDown => self.output_scroll = Some(scroll.saturating_sub(10)),
+                KeyCode::End => self.output_scroll = Some(0),
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => self.output_scroll = None,
+                _ => {}
+            }
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(selected) = self.list_state.selected() {
+                    self.list_state.select(Some(selected.saturating_sub(1)));
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(selected) = self.list_state.selected() {
+                    if selected + 1 < self.tasks.len() {
+                        self.list_state.select(Some(selected + 1));
+                    }
+                }
+            }
+            KeyCode::Enter if self.selected().is_some_and(|t| t.id.is_some()) => {
+                self.output_scroll = Some(0);
+            }
+            KeyCode::Char('c')
+                if self
+                    .selected()
+                    .is_some_and(|t| t.state == TaskState::Running) =>
+            {
+                if settings::confirm_actions() {
+                    self.show_confirm = true;
+                } else {
+                    return self.selected().and_then(|t| t.id).map(Message::CancelJob);
+                }
+            }
+            KeyCode::Char('g') => return self.selected().map(|t| Message::GotoTab(t.tab)),
+            KeyCode::Char('x') => return Some(Message::ClearFinishedJobs),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if let Some(scroll) = self.output_scroll {
+            self.render_output(frame, area, self.selected(), scroll);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(5),
+                Constraint::Percentage(40),
+                Constraint::Length(3),
+            ])
+            .split(area);
+
+        let items: Vec<ListItem> = if self.tasks.is_empty() {
+            vec![ListItem::new(Span::styled(
+                tr("No background tasks yet"),
+                Theme::muted(),
+            ))]
+        } else {
+            self.tasks
+                .iter()
+                .map(|task| {
+                    let elapsed = match task.state {
+                        TaskState::Queued => String::new(),
+                        _ => format_elapsed(task.elapsed),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{:<10}", task.state.label()), task.state.style()),
+                        Span::raw(format!("{:>8}  ", elapsed)),
+                        Span::styled(format!("{:<10}", tr(task.tab.title())), Theme::muted()),
+                        Span::raw(task.label.as_str()),
+                        Span::styled(
+                            task.progress
+                                .as_ref()
+                                .map_or(String::new(), |p| format!("  ({})", p)),
+                            Theme::muted(),
+                        ),
+                    ]))
+                })
+                .collect()
+        };
+        let running = self
+            .tasks
+            .iter()
+            .filter(|t| t.state == TaskState::Running)
+            .count();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(format!(" {} ", trf("Tasks ({} running)", &[&running.to_string()])))
+                    .borders(Borders::ALL)
+                    .border_style(Theme::border_focused()),
+            )
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = self.list_state.clone();
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        self.render_output(frame, chunks[1], self.selected(), 0);
+
+        let status_content = if self.show_confirm {
+            Line::from(vec![
+                Span::styled(
+                    trf(
+                        "Cancel {}?",
+                        &[self.selected().map_or("", |t| t.label.as_str())],
+                    ),
+                    Theme::warning(),
+                ),
+                Span::raw(format!(" {}", tr("[Y]es / [N]o"))),
+            ])
+        } else {
+            Line::from(Span::styled(
+                tr("Jobs started from any tab; finished ones stay until cleared"),
+                Theme::muted(),
+            ))
+        };
+        let status = Paragraph::new(status_content).block(Block::default().borders(Borders::ALL));
+        frame.render_widget(status, chunks[2]);
+    }
+
+    fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        if self.output_scroll.is_some() {
+            return vec![("↑↓", "Scroll"), ("End", "Follow"), ("Esc", "Back")];
+        }
+        vec![
+            ("↑↓", "Select"),
+            ("Enter", "Show Output"),
+            ("c", "Cancel Task"),
+            ("g", "Go to Tab"),
+            ("x", "Clear Finished"),
+        ]
+    }
+}
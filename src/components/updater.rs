@@ -5,13 +5,13 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use tokio::sync::mpsc;
 
 use super::{AsyncComponent, Component};
 use crate::app::Message;
 use crate::slackware::Bootloader;
 use crate::ui::theme::Theme;
 use crate::ui::widgets::{ProgressList, ProgressStep, StepStatus};
+use crate::utils::i18n::{tr, trf};
 
 /// System updater component - runs slackpkg update sequence
 pub struct UpdaterComponent {
@@ -21,7 +21,6 @@ pub struct UpdaterComponent {
     is_running: bool,
     show_lilo_confirm: bool,
     lilo_confirmed: bool,
-    progress_tx: Option<mpsc::UnboundedSender<String>>,
 
     // Safety features
     bootloader: Bootloader,
@@ -46,7 +45,6 @@ impl UpdaterComponent {
             is_running: false,
             show_lilo_confirm: false,
             lilo_confirmed: false,
-            progress_tx: None,
 
             bootloader: Bootloader::detect(),
             kernel_updated: false,
@@ -104,7 +102,7 @@ impl UpdaterComponent {
     pub fn set_kernel_updated(&mut self, updated: bool) {
         self.kernel_updated = updated;
         if updated {
-            self.add_output("*** KERNEL PACKAGES DETECTED - Bootloader update will be required ***".to_string());
+            self.add_output(tr("*** KERNEL PACKAGES DETECTED - Bootloader update will be required ***").to_string());
         }
     }
 
@@ -118,11 +116,6 @@ impl UpdaterComponent {
         self.lilo_skipped
     }
 
-    /// Get detected bootloader
-    pub fn get_bootloader(&self) -> Bootloader {
-        self.bootloader
-    }
-
     pub fn step_complete(&mut self, success: bool, error: Option<String>) {
         if self.current_step < self.steps.len() {
             self.steps[self.current_step].status = if success {
@@ -141,7 +134,7 @@ impl UpdaterComponent {
                         Bootloader::Grub => {
                             // GRUB detected - skip LILO step with informative message
                             self.steps[self.current_step].status = StepStatus::Complete;
-                            self.add_output("GRUB detected - skipping LILO. Run 'grub-mkconfig -o /boot/grub/grub.cfg' if kernel was updated.".to_string());
+                            self.add_output(tr("GRUB detected - skipping LILO. Run 'grub-mkconfig -o /boot/grub/grub.cfg' if kernel was updated.").to_string());
                             self.current_step += 1;
                             self.is_running = false;
                             self.show_summary = true;
@@ -150,7 +143,7 @@ impl UpdaterComponent {
                         Bootloader::Unknown => {
                             // Unknown bootloader - skip with warning
                             self.steps[self.current_step].status = StepStatus::Failed("No bootloader detected".to_string());
-                            self.add_output("WARNING: No bootloader configuration found. Update your bootloader manually if needed.".to_string());
+                            self.add_output(tr("WARNING: No bootloader configuration found. Update your bootloader manually if needed.").to_string());
                             self.current_step += 1;
                             self.is_running = false;
                             self.show_summary = true;
@@ -207,6 +200,35 @@ impl UpdaterComponent {
         self.is_running
     }
 
+    /// Stop after the running step, which was cancelled
+    pub fn cancel(&mut self) {
+        if let Some(step) = self.steps.get_mut(self.current_step) {
+            step.status = StepStatus::Failed("Cancelled".to_string());
+        }
+        self.add_output(tr("Update cancelled").to_string());
+        self.show_lilo_confirm = false;
+        self.is_running = false;
+        self.show_summary = true;
+    }
+
+    /// The running step, counting from 1, and the number of steps
+    pub fn progress(&self) -> (usize, usize) {
+        (self.current_step + 1, self.steps.len())
+    }
+
+    /// Steps still to run after the current one
+    pub fn queued_steps(&self) -> Vec<String> {
+        if !self.is_running {
+            return Vec::new();
+        }
+        self.steps
+            .iter()
+            .skip(self.current_step + 1)
+            .filter(|step| step.status == StepStatus::Pending)
+            .map(|step| step.name.clone())
+            .collect()
+    }
+
     pub fn get_current_command(&self) -> Option<(&str, Vec<&str>)> {
         if !self.is_running {
             return None;
@@ -233,7 +255,7 @@ impl UpdaterComponent {
         if self.kernel_updated {
             // Mandatory LILO - kernel was updated
             let dialog = Block::default()
-                .title(" !! KERNEL UPDATED - BOOTLOADER REQUIRED !! ")
+                .title(format!(" {} ", tr("!! KERNEL UPDATED - BOOTLOADER REQUIRED !!")))
                 .borders(Borders::ALL)
                 .border_style(Theme::error());
             let inner = dialog.inner(dialog_area);
@@ -246,25 +268,25 @@ impl UpdaterComponent {
             let text = Paragraph::new(vec![
                 Line::from(""),
                 Line::from(ratatui::text::Span::styled(
-                    "Your kernel was updated.",
+                    tr("Your kernel was updated."),
                     Theme::warning(),
                 )),
                 Line::from(""),
-                Line::from("You MUST update the bootloader or your"),
-                Line::from("system will NOT boot after reboot!"),
+                Line::from(tr("You MUST update the bootloader or your")),
+                Line::from(tr("system will NOT boot after reboot!")),
                 Line::from(""),
                 Line::from(vec![
                     ratatui::text::Span::styled("[Y]", Theme::key_hint()),
-                    ratatui::text::Span::raw(" Update bootloader now (Recommended)"),
+                    ratatui::text::Span::raw(format!(" {}", tr("Update bootloader now (Recommended)"))),
                 ]),
                 Line::from(""),
                 Line::from(vec![
-                    ratatui::text::Span::styled("Type SKIP", Theme::error()),
-                    ratatui::text::Span::raw(" to bypass at your own risk"),
+                    ratatui::text::Span::styled(tr("Type SKIP"), Theme::error()),
+                    ratatui::text::Span::raw(format!(" {}", tr("to bypass at your own risk"))),
                 ]),
                 Line::from(""),
                 Line::from(vec![
-                    ratatui::text::Span::raw("Input: "),
+                    ratatui::text::Span::raw(format!("{} ", tr("Input:"))),
                     ratatui::text::Span::styled(
                         format!("{}{}", skip_display, underscores),
                         Theme::muted(),
@@ -272,7 +294,7 @@ impl UpdaterComponent {
                 ]),
                 Line::from(""),
                 Line::from(ratatui::text::Span::styled(
-                    "[Backspace] to correct",
+                    tr("[Backspace] to correct"),
                     Theme::muted(),
                 )),
             ])
@@ -281,7 +303,7 @@ impl UpdaterComponent {
         } else {
             // Optional LILO - no kernel update detected
             let dialog = Block::default()
-                .title(" Update Bootloader? ")
+                .title(format!(" {} ", tr("Update Bootloader?")))
                 .borders(Borders::ALL)
                 .border_style(Theme::warning());
             let inner = dialog.inner(dialog_area);
@@ -289,20 +311,20 @@ impl UpdaterComponent {
 
             let text = Paragraph::new(vec![
                 Line::from(""),
-                Line::from("Run 'lilo' to update the bootloader?"),
+                Line::from(tr("Run 'lilo' to update the bootloader?")),
                 Line::from(""),
                 Line::from(ratatui::text::Span::styled(
-                    "No kernel changes detected - safe to skip.",
+                    tr("No kernel changes detected - safe to skip."),
                     Theme::muted(),
                 )),
                 Line::from(""),
                 Line::from(vec![
                     ratatui::text::Span::styled("[Y]", Theme::key_hint()),
-                    ratatui::text::Span::raw(" Yes - Update bootloader"),
+                    ratatui::text::Span::raw(format!(" {}", tr("Yes - Update bootloader"))),
                 ]),
                 Line::from(vec![
                     ratatui::text::Span::styled("[N]", Theme::key_hint()),
-                    ratatui::text::Span::raw(" No - Skip this step"),
+                    ratatui::text::Span::raw(format!(" {}", tr("No - Skip this step"))),
                 ]),
             ])
             .style(Theme::default());
@@ -315,9 +337,9 @@ impl UpdaterComponent {
         frame.render_widget(ratatui::widgets::Clear, dialog_area);
 
         let title = if self.lilo_skipped && self.kernel_updated {
-            " !! UPDATE COMPLETE - WARNING !! "
+            tr("!! UPDATE COMPLETE - WARNING !!")
         } else {
-            " Update Complete "
+            tr("Update Complete")
         };
 
         let border_style = if self.lilo_skipped && self.kernel_updated {
@@ -327,7 +349,7 @@ impl UpdaterComponent {
         };
 
         let dialog = Block::default()
-            .title(title)
+            .title(format!(" {} ", title))
             .borders(Borders::ALL)
             .border_style(border_style);
         let inner = dialog.inner(dialog_area);
@@ -346,7 +368,7 @@ impl UpdaterComponent {
 
             lines.push(Line::from(vec![
                 ratatui::text::Span::styled(format!(" [{}] ", symbol), style),
-                ratatui::text::Span::raw(&step.name),
+                ratatui::text::Span::raw(tr(&step.name)),
             ]));
         }
 
@@ -355,22 +377,22 @@ impl UpdaterComponent {
         // Show warning if LILO was skipped after kernel update
         if self.lilo_skipped && self.kernel_updated {
             lines.push(Line::from(ratatui::text::Span::styled(
-                "  !! WARNING !!",
+                format!("  {}", tr("!! WARNING !!")),
                 Theme::error(),
             )));
             lines.push(Line::from(""));
             lines.push(Line::from(ratatui::text::Span::styled(
-                "  Bootloader was NOT updated after kernel change!",
+                format!("  {}", tr("Bootloader was NOT updated after kernel change!")),
                 Theme::error(),
             )));
             lines.push(Line::from(ratatui::text::Span::styled(
-                "  Run 'lilo' manually BEFORE rebooting!",
+                format!("  {}", tr("Run 'lilo' manually BEFORE rebooting!")),
                 Theme::error(),
             )));
             lines.push(Line::from(""));
         } else if self.lilo_skipped {
             lines.push(Line::from(ratatui::text::Span::styled(
-                "  Note: Bootloader was skipped.",
+                format!("  {}", tr("Note: Bootloader was skipped.")),
                 Theme::warning(),
             )));
             lines.push(Line::from(""));
@@ -378,7 +400,7 @@ impl UpdaterComponent {
 
         lines.push(Line::from(vec![
             ratatui::text::Span::styled("[Enter]", Theme::key_hint()),
-            ratatui::text::Span::raw(" Acknowledge"),
+            ratatui::text::Span::raw(format!(" {}", tr("Acknowledge"))),
         ]));
 
         let text = Paragraph::new(lines).style(Theme::default());
@@ -479,9 +501,10 @@ impl Component for UpdaterComponent {
             .split(area);
 
         // Title with bootloader info
-        let bootloader_info = format!(" [Bootloader: {}]", self.bootloader.name());
+        let bootloader_info =
+            format!(" [{}]", trf("Bootloader: {}", &[tr(self.bootloader.name())]));
         let title = Paragraph::new(Line::from(vec![
-            ratatui::text::Span::styled("Slackware System Updater", Theme::title()),
+            ratatui::text::Span::styled(tr("Slackware System Updater"), Theme::title()),
             ratatui::text::Span::styled(bootloader_info, Theme::muted()),
         ]))
         .block(Block::default().borders(Borders::BOTTOM));
@@ -489,11 +512,11 @@ impl Component for UpdaterComponent {
 
         // Progress steps
         let progress = ProgressList::new(&self.steps)
-            .block(Block::default().borders(Borders::ALL).title("Progress"));
+            .block(Block::default().borders(Borders::ALL).title(tr("Progress")));
         frame.render_widget(progress, chunks[1]);
 
         // Output
-        let output_block = Block::default().borders(Borders::ALL).title("Output");
+        let output_block = Block::default().borders(Borders::ALL).title(tr("Output"));
         let inner = output_block.inner(chunks[2]);
         frame.render_widget(output_block, chunks[2]);
 
@@ -539,11 +562,61 @@ impl Component for UpdaterComponent {
 }
 
 impl AsyncComponent for UpdaterComponent {
-    fn set_progress_channel(&mut self, tx: mpsc::UnboundedSender<String>) {
-        self.progress_tx = Some(tx);
+    fn add_progress(&mut self, line: String) {
+        self.add_output(line);
     }
 
     fn is_running(&self) -> bool {
         self.is_running
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::{assert_snapshot, render};
+
+    /// An update run up to the bootloader step with lilo installed
+    fn at_lilo_step(kernel_updated: bool) -> UpdaterComponent {
+        let mut updater = UpdaterComponent::new();
+        updater.start_update();
+        // Detected anew by start_update
+        updater.bootloader = Bootloader::Lilo;
+        updater.step_complete(true, None);
+        updater.step_complete(true, None);
+        updater.step_complete(true, None);
+        updater.set_kernel_updated(kernel_updated);
+        updater.step_complete(true, None);
+        updater
+    }
+
+    fn draw(updater: &UpdaterComponent) -> ratatui::buffer::Buffer {
+        render(80, 30, |frame| updater.render(frame, frame.area()))
+    }
+
+    #[test]
+    fn test_lilo_confirm() {
+        let updater = at_lilo_step(false);
+        assert!(updater.needs_lilo_confirm());
+        assert_snapshot("updater_lilo_confirm", &draw(&updater));
+    }
+
+    #[test]
+    fn test_lilo_required() {
+        let mut updater = at_lilo_step(true);
+        updater.handle_input(KeyEvent::from(KeyCode::Char('s')));
+        updater.handle_input(KeyEvent::from(KeyCode::Char('k')));
+        assert_snapshot("updater_lilo_required", &draw(&updater));
+    }
+
+    #[test]
+    fn test_summary_after_skip() {
+        let mut updater = at_lilo_step(true);
+        for c in "skip".chars() {
+            updater.handle_input(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert!(updater.is_showing_summary());
+        assert!(updater.was_lilo_skipped());
+        assert_snapshot("updater_summary_skipped", &draw(&updater));
+    }
+}
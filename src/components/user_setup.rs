@@ -6,11 +6,11 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use tokio::sync::mpsc;
 
 use super::{AsyncComponent, Component};
 use crate::app::Message;
 use crate::ui::theme::Theme;
+use crate::utils::i18n::tr;
 
 /// Default groups for new users
 const DEFAULT_GROUPS: [(&str, &str); 10] = [
@@ -37,7 +37,6 @@ pub struct UserSetupComponent {
     is_running: bool,
     error_message: Option<String>,
     success_message: Option<String>,
-    progress_tx: Option<mpsc::UnboundedSender<String>>,
 }
 
 impl UserSetupComponent {
@@ -57,7 +56,6 @@ impl UserSetupComponent {
             is_running: false,
             error_message: None,
             success_message: None,
-            progress_tx: None,
         }
     }
 
@@ -78,23 +76,23 @@ impl UserSetupComponent {
 
     pub fn validate(&self) -> Result<(), String> {
         if self.username.is_empty() {
-            return Err("Username cannot be empty".to_string());
+            return Err(tr("Username cannot be empty").to_string());
         }
 
         if self.username.contains(' ') {
-            return Err("Username cannot contain spaces".to_string());
+            return Err(tr("Username cannot contain spaces").to_string());
         }
 
         if self.password.is_empty() {
-            return Err("Password cannot be empty".to_string());
+            return Err(tr("Password cannot be empty").to_string());
         }
 
         if self.password != self.confirm_password {
-            return Err("Passwords do not match".to_string());
+            return Err(tr("Passwords do not match").to_string());
         }
 
         if self.password.len() < 4 {
-            return Err("Password must be at least 4 characters".to_string());
+            return Err(tr("Password must be at least 4 characters").to_string());
         }
 
         Ok(())
@@ -231,7 +229,7 @@ impl Component for UserSetupComponent {
 
         // Title
         let title = Paragraph::new(Line::from(vec![Span::styled(
-            "User Setup",
+            tr("User Setup"),
             Theme::title(),
         )]))
         .block(Block::default().borders(Borders::BOTTOM));
@@ -244,7 +242,7 @@ impl Component for UserSetupComponent {
             .split(chunks[1]);
 
         // Left side - text inputs
-        let input_block = Block::default().borders(Borders::ALL).title("User Info");
+        let input_block = Block::default().borders(Borders::ALL).title(tr("User Info"));
         let input_inner = input_block.inner(form_chunks[0]);
         frame.render_widget(input_block, form_chunks[0]);
 
@@ -265,7 +263,7 @@ impl Component for UserSetupComponent {
         };
         let username_block = Block::default()
             .borders(Borders::ALL)
-            .title("Username")
+            .title(tr("Username"))
             .border_style(if self.current_field == 0 {
                 Theme::border_focused()
             } else {
@@ -285,7 +283,7 @@ impl Component for UserSetupComponent {
         let password_display = "*".repeat(self.password.len());
         let password_block = Block::default()
             .borders(Borders::ALL)
-            .title("Password")
+            .title(tr("Password"))
             .border_style(if self.current_field == 1 {
                 Theme::border_focused()
             } else {
@@ -305,7 +303,7 @@ impl Component for UserSetupComponent {
         let confirm_display = "*".repeat(self.confirm_password.len());
         let confirm_block = Block::default()
             .borders(Borders::ALL)
-            .title("Confirm Password")
+            .title(tr("Confirm Password"))
             .border_style(if self.current_field == 2 {
                 Theme::border_focused()
             } else {
@@ -317,7 +315,7 @@ impl Component for UserSetupComponent {
         frame.render_widget(confirm, input_chunks[2]);
 
         // Right side - groups checkboxes
-        let groups_block = Block::default().borders(Borders::ALL).title("Groups");
+        let groups_block = Block::default().borders(Borders::ALL).title(tr("Groups"));
         let groups_inner = groups_block.inner(form_chunks[1]);
         frame.render_widget(groups_block, form_chunks[1]);
 
@@ -327,8 +325,7 @@ impl Component for UserSetupComponent {
             let desc = DEFAULT_GROUPS
                 .iter()
                 .find(|(n, _)| *n == name)
-                .map(|(_, d)| *d)
-                .unwrap_or("");
+                .map_or("", |(_, d)| tr(d));
 
             let field_idx = 3 + i;
             let style = if self.current_field == field_idx {
@@ -357,7 +354,7 @@ impl Component for UserSetupComponent {
                 format!("{} ", runlevel_checkbox),
                 runlevel_style.add_modifier(Modifier::BOLD),
             ),
-            Span::styled("Change runlevel 3→4 (GUI)", runlevel_style),
+            Span::styled(tr("Change runlevel 3→4 (GUI)"), runlevel_style),
         ]));
 
         let groups_para = Paragraph::new(lines);
@@ -373,17 +370,22 @@ impl Component for UserSetupComponent {
                 .style(Theme::success())
                 .block(Block::default().borders(Borders::TOP))
         } else if self.is_running {
-            Paragraph::new("Creating user...")
+            Paragraph::new(tr("Creating user..."))
                 .style(Theme::warning())
                 .block(Block::default().borders(Borders::TOP))
         } else {
-            Paragraph::new("Press Enter to create user, Ctrl+R to reset")
+            Paragraph::new(tr("Press Enter to create user, Ctrl+R to reset"))
                 .style(Theme::muted())
                 .block(Block::default().borders(Borders::TOP))
         };
         frame.render_widget(status, chunks[2]);
     }
 
+    fn is_typing(&self) -> bool {
+        // Every field of the form is typed into, or toggled with Space
+        true
+    }
+
     fn help_text(&self) -> Vec<(&'static str, &'static str)> {
         vec![
             ("Tab", "Next field"),
@@ -395,8 +397,11 @@ impl Component for UserSetupComponent {
 }
 
 impl AsyncComponent for UserSetupComponent {
-    fn set_progress_channel(&mut self, tx: mpsc::UnboundedSender<String>) {
-        self.progress_tx = Some(tx);
+    fn add_progress(&mut self, line: String) {
+        // Shown until the result replaces it
+        if self.is_running {
+            self.success_message = Some(line);
+        }
     }
 
     fn is_running(&self) -> bool {
@@ -0,0 +1,253 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{Component, Tab};
+use crate::app::Message;
+use crate::slackware::config::{MirrorEntry, SlackwareConfig};
+use crate::slackware::SlackwareVersion;
+use crate::ui::centered_rect;
+use crate::ui::theme::{Theme, ThemeChoice};
+use crate::utils::i18n::{tr, trf};
+
+/// Pages of the first-run wizard, in order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WizardStep {
+    Theme,
+    Mirror,
+    DefaultTab,
+    Confirmations,
+    Update,
+}
+
+impl WizardStep {
+    const ALL: [WizardStep; 5] = [
+        WizardStep::Theme,
+        WizardStep::Mirror,
+        WizardStep::DefaultTab,
+        WizardStep::Confirmations,
+        WizardStep::Update,
+    ];
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|s| s == self).unwrap_or(0)
+    }
+
+    fn question(&self) -> &'static str {
+        match self {
+            WizardStep::Theme => "Pick a color theme. It is applied as you move through the list.",
+            WizardStep::Mirror => "Pick the slackpkg mirror to download packages from.",
+            WizardStep::DefaultTab => "Which tab should open when the manager starts?",
+            WizardStep::Confirmations => {
+                "Ask [Y]/[N] before every action? Destructive ones always ask."
+            }
+            WizardStep::Update => "Fetch the package lists with `slackpkg update` now?",
+        }
+    }
+}
+
+/// What the wizard settled on, applied by the app when it finishes
+#[derive(Debug, Clone)]
+pub struct SetupChoices {
+    pub theme: ThemeChoice,
+    /// None keeps the mirror already active in /etc/slackpkg/mirrors
+    pub mirror: Option<String>,
+    pub default_tab: Tab,
+    pub confirm_actions: bool,
+    pub run_update: bool,
+}
+
+impl Default for SetupChoices {
+    fn default() -> Self {
+        Self {
+            theme: ThemeChoice::Default,
+            mirror: None,
+            default_tab: Tab::Updater,
+            confirm_actions: true,
+            run_update: false,
+        }
+    }
+}
+
+/// Short setup shown in front of everything on the first launch, when
+/// there is no config file yet
+pub struct SetupWizard {
+    step: WizardStep,
+    choices: SetupChoices,
+    mirrors: Vec<MirrorEntry>,
+}
+
+impl SetupWizard {
+    pub fn new(version: &SlackwareVersion) -> Self {
+        Self {
+            step: WizardStep::Theme,
+            choices: SetupChoices::default(),
+            mirrors: SlackwareConfig::parse_mirrors(Some(version.mirror_path()))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The options of the current step and which one is chosen
+    fn options(&self) -> (Vec<String>, usize) {
+        let yes_no = |yes: bool| (vec![tr("Yes").to_string(), tr("No").to_string()], !yes as usize);
+        match self.step {
+            WizardStep::Theme => {
+                let themes = ThemeChoice::all();
+                let selected = themes
+                    .iter()
+                    .position(|t| *t == self.choices.theme)
+                    .unwrap_or(0);
+                (
+                    themes.iter().map(|t| t.name().to_string()).collect(),
+                    selected,
+                )
+            }
+            WizardStep::Mirror => {
+                let mut options = vec![tr("Keep the current mirror").to_string()];
+                options.extend(self.mirrors.iter().map(|m| m.display()));
+                let selected = self
+                    .choices
+                    .mirror
+                    .as_ref()
+                    .and_then(|url| self.mirrors.iter().position(|m| &m.url == url))
+                    .map_or(0, |i| i + 1);
+                (options, selected)
+            }
+            WizardStep::DefaultTab => {
+                let tabs = Tab::all();
+                let selected = tabs
+                    .iter()
+                    .position(|t| *t == self.choices.default_tab)
+                    .unwrap_or(0);
+                (
+                    tabs.iter().map(|t| tr(t.title()).to_string()).collect(),
+                    selected,
+                )
+            }
+            WizardStep::Confirmations => yes_no(self.choices.confirm_actions),
+            WizardStep::Update => yes_no(self.choices.run_update),
+        }
+    }
+
+    fn choose(&mut self, index: usize) {
+        match self.step {
+            WizardStep::Theme => {
+                if let Some(theme) = ThemeChoice::all().get(index) {
+                    self.choices.theme = *theme;
+                    Theme::set(*theme);
+                }
+            }
+            WizardStep::Mirror => {
+                self.choices.mirror = index
+                    .checked_sub(1)
+                    .and_then(|i| self.mirrors.get(i))
+                    .map(|m| m.url.clone());
+            }
+            WizardStep::DefaultTab => {
+                if let Some(tab) = Tab::all().get(index) {
+                    self.choices.default_tab = *tab;
+                }
+            }
+            WizardStep::Confirmations => self.choices.confirm_actions = index == 0,
+            WizardStep::Update => self.choices.run_update = index == 0,
+        }
+    }
+}
+
+impl Component for SetupWizard {
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Message> {
+        let (options, selected) = self.options();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.choose(selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.choose((selected + 1).min(options.len().saturating_sub(1)))
+            }
+            KeyCode::Enter | KeyCode::Tab => match WizardStep::ALL.get(self.step.index() + 1) {
+                Some(next) => self.step = *next,
+                None => return Some(Message::FinishSetup(self.choices.clone())),
+            },
+            KeyCode::Backspace | KeyCode::BackTab => {
+                self.step = WizardStep::ALL[self.step.index().saturating_sub(1)];
+            }
+            KeyCode::Esc => {
+                // Skipping still writes the defaults, so the wizard does not come back
+                Theme::set(ThemeChoice::Default);
+                return Some(Message::FinishSetup(SetupChoices::default()));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let dialog_area = centered_rect(60, 60, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let dialog = Block::default()
+            .title(format!(
+                " {} ",
+                trf(
+                    "Welcome - Setup {}/{}",
+                    &[
+                        &(self.step.index() + 1).to_string(),
+                        &WizardStep::ALL.len().to_string(),
+                    ]
+                )
+            ))
+            .borders(Borders::ALL)
+            .border_style(Theme::title());
+        let inner = dialog.inner(dialog_area);
+        frame.render_widget(dialog, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let question = Paragraph::new(Line::from(Span::styled(
+            tr(self.step.question()),
+            Theme::warning(),
+        )))
+        .wrap(Wrap { trim: true });
+        frame.render_widget(question, chunks[0]);
+
+        let (options, selected) = self.options();
+        let items: Vec<ListItem> = options.into_iter().map(ListItem::new).collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL))
+            .highlight_style(Theme::list_selected())
+            .highlight_symbol("▶ ");
+        let mut state = ListState::default().with_selected(Some(selected));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+
+        let last = self.step.index() + 1 == WizardStep::ALL.len();
+        let hints = Line::from(vec![
+            Span::styled("[↑/↓]", Theme::key_hint()),
+            Span::raw(format!(" {}  ", tr("Choose"))),
+            Span::styled("[Enter]", Theme::key_hint()),
+            Span::raw(format!(" {}  ", tr(if last { "Finish" } else { "Next" }))),
+            Span::styled("[Backspace]", Theme::key_hint()),
+            Span::raw(format!(" {}  ", tr("Back"))),
+            Span::styled("[Esc]", Theme::key_hint()),
+            Span::raw(format!(" {}", tr("Skip"))),
+        ]);
+        frame.render_widget(Paragraph::new(hints), chunks[2]);
+    }
+
+    fn help_text(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/↓", "Choose"),
+            ("Enter", "Next"),
+            ("Backspace", "Back"),
+            ("Esc", "Skip"),
+        ]
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Context};
+use serde_json::{json, Value};
+
+use crate::cli;
+use crate::utils::jsonrpc::{self, Error, Request, INVALID_PARAMS, METHOD_NOT_FOUND, SERVER_ERROR};
+
+pub const SOCKET: &str = "/run/slackware-cli-manager.sock";
+
+/// Output lines of the last update kept for update_status
+const UPDATE_OUTPUT_LINES: usize = 500;
+
+/// The update started with start_update, which runs on after the request
+/// that started it was answered
+#[derive(Debug, Default)]
+struct UpdateState {
+    running: bool,
+    /// The slackpkg command running, or the one that failed
+    step: Option<String>,
+    exit_code: Option<i32>,
+    kernel_changed: bool,
+    output: VecDeque<String>,
+}
+
+impl UpdateState {
+    fn to_json(&self) -> Value {
+        json!({
+            "running": self.running,
+            "step": self.step,
+            "exit_code": self.exit_code,
+            "kernel_changed": self.kernel_changed,
+            "output": self.output,
+        })
+    }
+
+    fn push_output(&mut self, text: &str) {
+        self.output.extend(text.lines().map(str::to_string));
+        while self.output.len() > UPDATE_OUTPUT_LINES {
+            self.output.pop_front();
+        }
+    }
+}
+
+/// Answer requests on `socket` until killed, one thread per connection.
+/// The socket is only open to root, like the operations behind it.
+pub fn serve(socket: &Path) -> anyhow::Result<i32> {
+    if UnixStream::connect(socket).is_ok() {
+        bail!("a daemon is already listening on {}", socket.display());
+    }
+    // Left behind by a daemon that was killed. Anything else at the path
+    // is not ours to remove.
+    match fs::symlink_metadata(socket) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            fs::remove_file(socket)
+                .with_context(|| format!("failed to remove {}", socket.display()))?;
+        }
+        Ok(_) => bail!("{} exists and is not a socket", socket.display()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e).with_context(|| format!("failed to check {}", socket.display())),
+    }
+    let listener = UnixListener::bind(socket)
+        .with_context(|| format!("failed to listen on {}", socket.display()))?;
+    fs::set_permissions(socket, fs::Permissions::from_mode(0o600))?;
+    println!("Listening on {}", socket.display());
+
+    let update = Arc::new(Mutex::new(UpdateState::default()));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("warning: failed to accept a connection: {}", e);
+                continue;
+            }
+        };
+        let update = Arc::clone(&update);
+        std::thread::spawn(move || {
+            if let Err(e) = handle(stream, &update) {
+                eprintln!("warning: connection closed: {}", e);
+            }
+        });
+    }
+    Ok(0)
+}
+
+/// Answer each line of a connection until the client hangs up
+fn handle(stream: UnixStream, update: &Arc<Mutex<UpdateState>>) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match jsonrpc::parse(&line) {
+            Ok(request) => {
+                let result = dispatch(&request, update);
+                match &request.id {
+                    Some(id) => jsonrpc::response(id, result),
+                    None => continue,
+                }
+            }
+            Err(response) => response,
+        };
+        writeln!(writer, "{}", response)?;
+    }
+    Ok(())
+}
+
+fn server_error(e: impl std::fmt::Display) -> Error {
+    Error::new(SERVER_ERROR, e.to_string())
+}
+
+fn dispatch(request: &Request, update: &Arc<Mutex<UpdateState>>) -> Result<Value, Error> {
+    match request.method.as_str() {
+        "list_packages" => {
+            let packages = cli::installed_packages().map_err(server_error)?;
+            Ok(packages.iter().map(cli::package_json).collect())
+        }
+        "start_update" => start_update(update),
+        "update_status" => Ok(update.lock().map_err(server_error)?.to_json()),
+        "service" => {
+            let (Some(action), Some(name)) =
+                (request.str_param("action", 0), request.str_param("name", 1))
+            else {
+                return Err(Error::new(
+                    INVALID_PARAMS,
+                    "service takes an action and a name",
+                ));
+            };
+            let script = cli::service_script(action, name).map_err(server_error)?;
+            let output = Command::new(&script).arg(action).output();
+            let program = script.to_string_lossy();
+            cli::record(
+                &program,
+                &[action],
+                output.as_ref().ok().and_then(|o| o.status.code()),
+            );
+            let output = output.map_err(server_error)?;
+            Ok(json!({
+                "exit_code": output.status.code(),
+                "stdout": String::from_utf8_lossy(&output.stdout),
+                "stderr": String::from_utf8_lossy(&output.stderr),
+            }))
+        }
+        method => Err(Error::new(
+            METHOD_NOT_FOUND,
+            format!("no method {}", method),
+        )),
+    }
+}
+
+/// Start slackpkg's update steps in batch mode in the background;
+/// update_status follows them
+fn start_update(update: &Arc<Mutex<UpdateState>>) -> Result<Value, Error> {
+    {
+        let mut state = update.lock().map_err(server_error)?;
+        if state.running {
+            return Err(server_error("an update is already running"));
+        }
+        *state = UpdateState {
+            running: true,
+            ..UpdateState::default()
+        };
+    }
+    let update = Arc::clone(update);
+    std::thread::spawn(move || {
+        let kernels = cli::kernel_packages();
+        let mut exit_code = 0;
+        for step in cli::UPDATE_STEPS {
+            if let Ok(mut state) = update.lock() {
+                state.step = Some(format!("slackpkg {}", step));
+                state.push_output(&format!("==> slackpkg {}", step));
+            }
+            let args = ["-batch=on", "-default_answer=y", step];
+            let output = Command::new("slackpkg").args(args).output();
+            cli::record(
+                "slackpkg",
+                &args,
+                output.as_ref().ok().and_then(|o| o.status.code()),
+            );
+            let Ok(mut state) = update.lock() else { return };
+            match output {
+                Ok(output) => {
+                    state.push_output(&String::from_utf8_lossy(&output.stdout));
+                    state.push_output(&String::from_utf8_lossy(&output.stderr));
+                    match output.status.code() {
+                        Some(0) | Some(cli::SLACKPKG_NOTHING_TO_DO) => {}
+                        code => {
+                            exit_code = code.unwrap_or(1);
+                            break;
+                        }
+                    }
+                }
+                Err(e) => {
+                    state.push_output(&format!("failed to run slackpkg: {}", e));
+                    exit_code = 1;
+                    break;
+                }
+            }
+        }
+        if let Ok(mut state) = update.lock() {
+            state.running = false;
+            state.exit_code = Some(exit_code);
+            state.kernel_changed = cli::kernel_packages() != kernels;
+            if exit_code == 0 {
+                state.step = None;
+            }
+        }
+    });
+    Ok(json!({ "started": true }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serve_keeps_other_files() {
+        let dir = crate::utils::scratch::private_dir("daemon-test").unwrap();
+        let file = dir.join("shadow");
+        fs::write(&file, "root:x:\n").unwrap();
+        assert!(serve(&file).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "root:x:\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,34 +1,54 @@
+
 mod app;
+mod cli;
 mod components;
+mod daemon;
 mod slackware;
 mod ui;
 mod utils;
 
 use std::io;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    cursor,
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, MouseEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, buffer::Buffer, Terminal};
 
 use app::App;
-use slackware::detect_version;
+use clap::Parser;
+use cli::Cli;
+use slackware::{detect_version, remote};
 use utils::check_root;
+use utils::root::is_root;
 
 const APP_NAME: &str = "Slackware CLI Manager";
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Check for root privileges
-    if let Err(e) = check_root() {
-        eprintln!("{}", e);
-        eprintln!("\nThis application requires root privileges to manage Slackware packages.");
-        eprintln!("Please run with: sudo {}", std::env::args().next().unwrap_or_default());
-        std::process::exit(1);
+    // A command for a remote host on its way to ssh, see RemoteHost::command
+    if std::env::args_os().nth(1).is_some_and(|arg| arg == remote::EXEC_ARG) {
+        let args: Vec<String> = std::env::args().skip(2).collect();
+        let e = remote::exec(&args);
+        eprintln!("ssh: {}", e);
+        std::process::exit(255);
+    }
+
+    let cli = Cli::parse();
+    if cli.has_action() {
+        if cli.needs_root() {
+            check_root()?;
+        }
+        std::process::exit(cli.run()?);
+    }
+
+    // Commands that need root go through sudo or doas otherwise
+    if !is_root() {
+        println!("Not running as root: privileged commands will go through sudo or doas");
     }
 
     // Detect Slackware version
@@ -49,10 +69,9 @@ async fn main() -> anyhow::Result<()> {
     std::thread::sleep(Duration::from_millis(500));
 
     // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    install_panic_hook();
+    let guard = TerminalGuard::new()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
@@ -62,13 +81,11 @@ async fn main() -> anyhow::Result<()> {
     let result = run_app(&mut terminal, &mut app).await;
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    drop(guard);
+
+    if let Err(e) = app.save_session() {
+        eprintln!("Warning: could not save the session: {}", e);
+    }
 
     if let Err(e) = result {
         eprintln!("Error: {}", e);
@@ -79,28 +96,122 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Raw mode and the alternate screen for as long as it lives, so an early
+/// return leaves the terminal as it was found
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> io::Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    // Best effort: there is nothing left to report a failure to
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        cursor::Show
+    );
+}
+
+/// Put the terminal back before the panic message is printed, so it shows
+/// on the normal screen with proper line breaks. A panic in a background job
+/// ends the program too; the screen it would keep drawing on is gone.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+        std::process::exit(101);
+    }));
+}
+
+/// Draws only when something changed or, in case something changed on its
+/// own, once per interval: the frame interval while jobs run or output comes
+/// in, the longer idle interval otherwise. Waiting for input in between
+/// keeps an idle session near 0% CPU.
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
 ) -> anyhow::Result<()> {
+    let mut mouse_captured = true;
+    let mut dirty = true;
+    let mut drawn = Instant::now();
+    let mut frame = Buffer::default();
     loop {
-        // Draw UI
-        terminal.draw(|frame| app.render(frame))?;
+        // Let go of the mouse when it is turned off, so the terminal can
+        // select text again
+        if app.settings.mouse_enabled() != mouse_captured {
+            mouse_captured = !mouse_captured;
+            if mouse_captured {
+                execute!(terminal.backend_mut(), EnableMouseCapture)?;
+            } else {
+                execute!(terminal.backend_mut(), DisableMouseCapture)?;
+            }
+        }
+
+        let interval = if app.is_busy() {
+            app.settings.frame_interval()
+        } else {
+            app.settings.idle_interval()
+        };
+
+        // Draw UI, keeping the frame to find what a click lands on
+        if dirty || drawn.elapsed() >= interval {
+            frame = terminal.draw(|frame| app.render(frame))?.buffer.clone();
+            dirty = false;
+            drawn = Instant::now();
+        }
 
         // Handle events
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if let Some(msg) = app.handle_input(key) {
-                    app.update(msg).await;
+        if event::poll(interval.saturating_sub(drawn.elapsed()))? {
+            let event = event::read()?;
+            // The pointer moving over the screen changes nothing on it
+            dirty |= !matches!(
+                event,
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Moved
+            );
+            let msg = match event {
+                Event::Key(key) => app.handle_input(key),
+                Event::Mouse(mouse) => app.handle_mouse(mouse, &frame),
+                Event::Resize(..) => {
+                    // Redraw from scratch, so nothing of the old size is left
+                    terminal.autoresize()?;
+                    terminal.clear()?;
+                    None
                 }
+                _ => None,
+            };
+            if let Some(msg) = msg {
+                app.update(msg).await;
             }
         }
 
         // Check for progress updates
-        while let Ok(line) = app.progress_rx.try_recv() {
-            app.update(app::Message::ProgressUpdate(line)).await;
+        while let Ok((tab, line)) = app.progress_rx.try_recv() {
+            app.update(app::Message::ProgressUpdate(tab, line)).await;
+            dirty = true;
         }
 
+        // Results of commands running in the background
+        while let Ok(msg) = app.message_rx.try_recv() {
+            app.update(msg).await;
+            dirty = true;
+        }
+
+        app.on_tick();
+
         // Exit if not running
         if !app.running {
             break;
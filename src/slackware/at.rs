@@ -0,0 +1,173 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use crate::slackware::audit;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// A pending job as listed by `atq`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtJob {
+    pub id: String,
+    /// When it runs, as atq prints it: "Wed Oct 14 15:00:00 2026"
+    pub time: String,
+    /// Queue letter; '=' while the job is running
+    pub queue: String,
+    pub user: String,
+}
+
+/// Parse `atq` output: "12\tWed Oct 14 15:00:00 2026 a root"
+pub fn parse_atq(output: &str) -> Vec<AtJob> {
+    let mut jobs: Vec<AtJob> = output
+        .lines()
+        .filter_map(|line| {
+            let (id, rest) = line.split_once('\t')?;
+            let words: Vec<&str> = rest.split_whitespace().collect();
+            let [date @ .., queue, user] = &words[..] else {
+                return None;
+            };
+            Some(AtJob {
+                id: id.trim().to_string(),
+                time: date.join(" "),
+                queue: queue.to_string(),
+                user: user.to_string(),
+            })
+        })
+        .collect();
+    jobs.sort_by_key(|j| j.id.parse::<u64>().unwrap_or(u64::MAX));
+    jobs
+}
+
+/// The commands of a job from the script `at -c` prints, without the
+/// environment and `cd` preamble at adds
+pub fn job_command(script: &str) -> String {
+    let user_lines: Vec<&str> = script
+        .lines()
+        .skip_while(|l| !l.starts_with("cd "))
+        .skip_while(|l| l.trim() != "}")
+        .skip(1)
+        .collect();
+    // at 3.1.14 and later wrap the commands in a here document
+    if let Some(first) = user_lines.first() {
+        if let Some(delimiter) = first.strip_prefix("${SHELL:-/bin/sh} << ") {
+            let delimiter = delimiter.trim_matches('\'');
+            return user_lines[1..]
+                .iter()
+                .take_while(|l| l.trim() != delimiter)
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+    user_lines.join("\n").trim().to_string()
+}
+
+fn run(cmd: &str, args: &[&str]) -> Result<String> {
+    check(cmd, root::command(cmd).args(args).output()?)
+}
+
+/// `run` for commands that change something, recorded in the audit log
+fn change(cmd: &str, args: &[&str]) -> Result<()> {
+    check(cmd, audit::output(root::command(cmd).args(args))?).map(|_| ())
+}
+
+fn check(cmd: &str, output: Output) -> Result<String> {
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Whether atd is running; without it queued jobs never fire
+pub fn atd_running() -> bool {
+    Command::new("pgrep")
+        .args(["-x", "atd"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Pending jobs of all users
+pub fn list() -> Result<Vec<AtJob>> {
+    Ok(parse_atq(&run("atq", &[])?))
+}
+
+/// What a job runs
+pub fn command_of(id: &str) -> Result<String> {
+    Ok(job_command(&run("at", &["-c", id])?))
+}
+
+/// Queue `command` for `time`, in any form at(1) accepts ("now + 1 hour",
+/// "03:00 tomorrow"), returning at's "job N at ..." line
+pub fn schedule(time: &str, command: &str) -> Result<String> {
+    let time = time.trim();
+    if time.is_empty() || command.trim().is_empty() {
+        return Err(AppError::Config(
+            "Both a time and a command are needed".to_string(),
+        ));
+    }
+    let mut at = root::command("at");
+    at.args(time.split_whitespace())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    let mut child = at.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(format!("{}\n", command.trim()).as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    audit::record_command(&at, output.status.code());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!("at: {}", stderr.trim())));
+    }
+    // at warns that commands run with /bin/sh before saying which job it made
+    Ok(stderr
+        .lines()
+        .find(|l| l.starts_with("job "))
+        .unwrap_or("Job queued")
+        .to_string())
+}
+
+/// Remove a pending job
+pub fn remove(id: &str) -> Result<()> {
+    change("atrm", &[id])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let jobs = parse_atq(
+            "14\tThu Oct 15 03:00:00 2026 a root\n\
+             9\tWed Oct 14 15:00:00 2026 = alice\n",
+        );
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].id, "9");
+        assert_eq!(jobs[0].time, "Wed Oct 14 15:00:00 2026");
+        assert_eq!(jobs[0].queue, "=");
+        assert_eq!(jobs[1].user, "root");
+
+        let preamble = "#!/bin/sh\n# atrun uid=0 gid=0\numask 22\n\
+                        PATH=/usr/bin:/bin; export PATH\ncd /root || {\n\
+                        \t echo 'Execution directory inaccessible' >&2\n\t exit 1\n}\n";
+        assert_eq!(
+            job_command(&format!("{}/sbin/reboot\n", preamble)),
+            "/sbin/reboot"
+        );
+        assert_eq!(
+            job_command(&format!(
+                "{}${{SHELL:-/bin/sh}} << 'marcinDELIMITER1a2b'\n\
+                 fstrim -av\nmarcinDELIMITER1a2b\n",
+                preamble
+            )),
+            "fstrim -av"
+        );
+    }
+}
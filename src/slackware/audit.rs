@@ -0,0 +1,190 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::Mutex;
+
+use crate::utils::error::Result;
+use crate::utils::root;
+
+/// Every command run on the system's behalf, one line each
+pub const AUDIT_LOG: &str = "/var/log/slackware-cli-manager/audit.log";
+
+/// The tab commands are started from when the caller doesn't say
+static CURRENT_TAB: Mutex<&str> = Mutex::new("");
+
+pub fn set_current_tab(tab: &'static str) {
+    if let Ok(mut current) = CURRENT_TAB.lock() {
+        *current = tab;
+    }
+}
+
+pub fn current_tab() -> &'static str {
+    CURRENT_TAB.lock().map(|tab| *tab).unwrap_or("")
+}
+
+/// One executed command
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    /// Local time, e.g. "2026-10-14T09:30:00+02:00"
+    pub timestamp: String,
+    pub tab: String,
+    pub argv: Vec<String>,
+    /// `None` when it could not start or was killed by a signal
+    pub exit_code: Option<i32>,
+}
+
+impl AuditEntry {
+    pub fn new(tab: &str, argv: Vec<String>, exit_code: Option<i32>) -> Self {
+        Self {
+            timestamp: chrono::Local::now()
+                .format("%Y-%m-%dT%H:%M:%S%:z")
+                .to_string(),
+            tab: tab.to_string(),
+            argv,
+            exit_code,
+        }
+    }
+
+    /// Tab-separated: timestamp, tab, exit code ("-" when none) and argv
+    /// as a JSON array, so arguments with spaces or tabs survive
+    pub fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.timestamp,
+            if self.tab.is_empty() { "-" } else { &self.tab },
+            self.exit_code.map_or("-".to_string(), |c| c.to_string()),
+            serde_json::to_string(&self.argv).unwrap_or_default()
+        )
+    }
+
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        let timestamp = fields.next()?.to_string();
+        let tab = match fields.next()? {
+            "-" => String::new(),
+            tab => tab.to_string(),
+        };
+        let exit_code = match fields.next()? {
+            "-" => None,
+            code => Some(code.parse().ok()?),
+        };
+        let argv = serde_json::from_str(fields.next()?).ok()?;
+        Some(Self {
+            timestamp,
+            tab,
+            argv,
+            exit_code,
+        })
+    }
+
+    /// The command as it could be typed into a shell
+    pub fn command_line(&self) -> String {
+        self.argv
+            .iter()
+            .map(|arg| shell_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// One line for the log viewer
+    pub fn display(&self) -> String {
+        let tab = if self.tab.is_empty() { "-" } else { &self.tab };
+        let exit = self
+            .exit_code
+            .map_or("exit -".to_string(), |c| format!("exit {}", c));
+        format!(
+            "{}  [{}]  {}  {}",
+            self.timestamp.replacen('T', " ", 1),
+            tab,
+            exit,
+            self.command_line()
+        )
+    }
+}
+
+/// Append a command to the audit log. The log is root's alone: commands
+/// can carry user names and paths worth keeping private.
+pub fn record(tab: &str, argv: Vec<String>, exit_code: Option<i32>) -> Result<()> {
+    let path = Path::new(AUDIT_LOG);
+    if let Some(dir) = path.parent() {
+        root::create_dir_all(dir)?;
+    }
+    let line = AuditEntry::new(tab, argv, exit_code).to_line() + "\n";
+    root::append_private(path, line.as_bytes())?;
+    Ok(())
+}
+
+/// Record a command made with `root::command` under the current tab
+pub fn record_command(command: &Command, exit_code: Option<i32>) {
+    // An unwritable audit log must not stop the work itself
+    let _ = record(current_tab(), root::unwrapped_argv(command), exit_code);
+}
+
+/// `Command::output` for commands that change the system, recorded in the
+/// audit log
+pub fn output(command: &mut Command) -> io::Result<Output> {
+    let output = command.output();
+    record_command(command, output.as_ref().ok().and_then(|o| o.status.code()));
+    output
+}
+
+/// Entries of the audit log, oldest first; lines that don't parse are kept
+/// as they are so nothing goes missing from the view
+pub fn read_display_lines() -> std::io::Result<Vec<String>> {
+    let content = fs::read_to_string(AUDIT_LOG)?;
+    Ok(content
+        .lines()
+        .map(|line| AuditEntry::parse(line).map_or(line.to_string(), |e| e.display()))
+        .collect())
+}
+
+/// `arg` as one word for a POSIX shell, quoted only when it needs to be
+pub fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_./=:,+@%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_line() {
+        let entry = AuditEntry {
+            timestamp: "2026-10-14T09:30:00+02:00".to_string(),
+            tab: "Users".to_string(),
+            argv: vec![
+                "useradd".into(),
+                "-G".into(),
+                "wheel,audio".into(),
+                "o'neil".into(),
+            ],
+            exit_code: Some(0),
+        };
+        assert_eq!(AuditEntry::parse(&entry.to_line()), Some(entry.clone()));
+        assert_eq!(entry.command_line(), "useradd -G wheel,audio 'o'\\''neil'");
+        assert_eq!(
+            entry.display(),
+            "2026-10-14 09:30:00+02:00  [Users]  exit 0  useradd -G wheel,audio 'o'\\''neil'"
+        );
+
+        let killed = AuditEntry {
+            tab: String::new(),
+            exit_code: None,
+            argv: vec!["mkfs.ext4".into(), "/dev/sdb1".into()],
+            ..entry
+        };
+        let line = killed.to_line();
+        assert!(line.contains("\t-\t-\t"));
+        assert_eq!(AuditEntry::parse(&line), Some(killed));
+        assert_eq!(AuditEntry::parse("not an audit line"), None);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::process::Command;
+
+use crate::slackware::mkfs::{self, Filesystem};
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// What blkid knows about a block device
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ids {
+    pub label: String,
+    pub uuid: String,
+    pub partuuid: String,
+    pub fstype: String,
+}
+
+/// `blkid -o export` output: a KEY=value block per device
+pub fn parse(output: &str) -> HashMap<String, Ids> {
+    let mut devices = HashMap::new();
+    for block in output.split("\n\n") {
+        let mut device = None;
+        let mut ids = Ids::default();
+        for line in block.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.to_string();
+            match key {
+                "DEVNAME" => device = Some(value),
+                "LABEL" => ids.label = value,
+                "UUID" => ids.uuid = value,
+                "PARTUUID" => ids.partuuid = value,
+                "TYPE" => ids.fstype = value,
+                _ => {}
+            }
+        }
+        if let Some(device) = device {
+            devices.insert(device, ids);
+        }
+    }
+    devices
+}
+
+/// Labels and UUIDs of every device blkid can probe
+pub fn load() -> HashMap<String, Ids> {
+    Command::new("blkid")
+        .args(["-o", "export"])
+        .output()
+        .map(|o| parse(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// An fstab entry that finds the filesystem by UUID, so it survives
+/// disks being renamed
+pub fn fstab_line(uuid: &str, mount_point: &str, fstype: &str) -> String {
+    let (mount_point, pass) = match (fstype, mount_point) {
+        ("swap", _) => ("swap", "0 0"),
+        (_, "/") => ("/", "1 1"),
+        (_, mount_point) => (mount_point, "1 2"),
+    };
+    format!(
+        "UUID={:<38} {:<16} {:<11} defaults         {}",
+        uuid, mount_point, fstype, pass
+    )
+}
+
+/// The labelling tool for a filesystem type
+fn label_command(fstype: &str, device: &str, label: &str) -> Result<(&'static str, Vec<String>)> {
+    let (device, label) = (device.to_string(), label.to_string());
+    Ok(match fstype {
+        "ext2" | "ext3" | "ext4" => ("e2label", vec![device, label]),
+        "xfs" => ("xfs_admin", vec!["-L".into(), label, device]),
+        "btrfs" => (
+            "btrfs",
+            vec!["filesystem".into(), "label".into(), device, label],
+        ),
+        "vfat" => ("fatlabel", vec![device, label]),
+        "exfat" => ("exfatlabel", vec![device, label]),
+        "swap" => ("swaplabel", vec!["-L".into(), label, device]),
+        other => {
+            return Err(AppError::Config(format!(
+                "Don't know how to label {}",
+                other
+            )))
+        }
+    })
+}
+
+/// Give the filesystem on `device` a new label
+pub fn set_label(fstype: &str, device: &str, label: &str) -> Result<()> {
+    let known = match fstype {
+        "ext2" | "ext3" | "ext4" => Some(Filesystem::Ext4),
+        "xfs" => Some(Filesystem::Xfs),
+        "btrfs" => Some(Filesystem::Btrfs),
+        "vfat" => Some(Filesystem::Fat),
+        _ => None,
+    };
+    if let Some(fs) = known {
+        mkfs::validate_label(fs, label).map_err(AppError::Config)?;
+    }
+    if fstype == "xfs" {
+        // xfs_admin refuses to touch a mounted filesystem
+        mkfs::check_unused(device)?;
+    }
+    let (program, args) = label_command(fstype, device, label)?;
+    let output = root::command(program).args(&args).output()?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let devices = parse(
+            "DEVNAME=/dev/sda1\nUUID=0A1B-2C3D\nBLOCK_SIZE=512\nTYPE=vfat\n\
+             PARTUUID=5e2c9d1a-01\n\n\
+             DEVNAME=/dev/sda2\nLABEL=root\nUUID=3f1e2d4c-5b6a-4789-90ab-cdef01234567\n\
+             TYPE=ext4\n",
+        );
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices["/dev/sda1"].fstype, "vfat");
+        assert_eq!(devices["/dev/sda1"].label, "");
+        let root = &devices["/dev/sda2"];
+        assert_eq!(root.label, "root");
+        assert!(fstab_line(&root.uuid, "/", "ext4").ends_with("defaults         1 1"));
+        assert!(fstab_line("abcd", "/mnt/swap", "swap").contains(" swap "));
+    }
+}
@@ -0,0 +1,123 @@
+use regex::Regex;
+
+use crate::slackware::kernels;
+use crate::utils::error::Result;
+
+/// Separates the entries of ChangeLog.txt
+const SEPARATOR: &str = "+--------------------------+";
+
+/// One dated entry of ChangeLog.txt, reduced to the package blocks that
+/// touch the kernel
+#[derive(Debug, Clone, PartialEq)]
+pub struct KernelChange {
+    pub date: String,
+    /// Kernel versions the entry ships
+    pub versions: Vec<String>,
+    /// The kernel package lines with their indented descriptions
+    pub lines: Vec<String>,
+}
+
+impl KernelChange {
+    pub fn is_security_fix(&self) -> bool {
+        self.lines.iter().any(|l| l.contains("(* Security fix *)"))
+    }
+}
+
+/// Compare dotted version numbers, e.g. 5.15.161 > 5.15.19
+fn is_newer(version: &str, than: &str) -> bool {
+    let parts = |v: &str| -> Vec<u64> {
+        v.split(|c: char| !c.is_ascii_digit())
+            .take_while(|p| !p.is_empty())
+            .filter_map(|p| p.parse().ok())
+            .collect()
+    };
+    parts(version) > parts(than)
+}
+
+/// Entries shipping a kernel newer than `running`, newest first. Reading
+/// stops at the entry that shipped the running kernel.
+pub fn parse(content: &str, running: &str) -> Vec<KernelChange> {
+    // a/kernel-generic-6.6.30-x86_64-1.txz and patches/packages/linux-5.15.161/*
+    let version_re =
+        Regex::new(r"kernel-(?:generic|huge|modules|source|headers)-(\d[^-]*)-|linux-(\d[^/]*)/")
+            .expect("valid regex");
+    let mut changes = Vec::new();
+    for entry in content.split(SEPARATOR) {
+        let mut lines = entry.lines().skip_while(|l| l.trim().is_empty());
+        let Some(date) = lines.next() else {
+            continue;
+        };
+
+        let mut versions: Vec<String> = Vec::new();
+        let mut kernel_lines = Vec::new();
+        let mut in_block = false;
+        for line in lines {
+            if !line.starts_with(char::is_whitespace) && !line.is_empty() {
+                let found: Vec<String> = version_re
+                    .captures_iter(line)
+                    .filter_map(|c| c.get(1).or(c.get(2)))
+                    .map(|m| m.as_str().to_string())
+                    .collect();
+                in_block = !found.is_empty();
+                for version in found {
+                    if !versions.contains(&version) {
+                        versions.push(version);
+                    }
+                }
+            }
+            if in_block {
+                kernel_lines.push(line.to_string());
+            }
+        }
+        if versions.iter().any(|v| v == running) {
+            break;
+        }
+        if versions.iter().any(|v| is_newer(v, running)) {
+            changes.push(KernelChange {
+                date: date.trim().to_string(),
+                versions,
+                lines: kernel_lines,
+            });
+        }
+    }
+    changes
+}
+
+/// Kernel entries of the active mirror's ChangeLog.txt newer than `running`
+pub fn kernel_changes(running: &str) -> Result<Vec<KernelChange>> {
+    let (_, content) = kernels::fetch_from_mirror("ChangeLog.txt")?;
+    Ok(parse(&content, running))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = "Mon Jun 17 19:12:39 UTC 2024\n\
+                       patches/packages/linux-5.15.161/*:  Upgraded.\n  \
+                       These updates fix various bugs and security issues.\n  \
+                       (* Security fix *)\n\
+                       patches/packages/openssl-1.1.1za-x86_64-1_slack15.0.txz:  Upgraded.\n  \
+                       (* Security fix *)\n\
+                       +--------------------------+\n\
+                       Fri May 31 20:01:02 UTC 2024\n\
+                       patches/packages/bind-9.18.27-x86_64-1_slack15.0.txz:  Upgraded.\n\
+                       +--------------------------+\n\
+                       Thu May 16 18:36:21 UTC 2024\n\
+                       patches/packages/linux-5.15.158/*:  Upgraded.\n\
+                       +--------------------------+\n\
+                       Mon Apr  8 19:12:28 UTC 2024\n\
+                       patches/packages/linux-5.15.153/*:  Upgraded.\n";
+        let changes = parse(content, "5.15.158");
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].date, "Mon Jun 17 19:12:39 UTC 2024");
+        assert_eq!(changes[0].versions, vec!["5.15.161"]);
+        assert_eq!(changes[0].lines.len(), 3);
+        assert!(changes[0].is_security_fix());
+
+        assert_eq!(parse(content, "5.15.161"), Vec::new());
+        assert!(is_newer("5.15.161", "5.15.19"));
+    }
+}
@@ -1,21 +1,22 @@
 use std::process::Stdio;
+use tokio::io::AsyncBufReadExt;
 use tokio::process::Command;
 use tokio::sync::mpsc;
 
+use crate::slackware::audit;
+use crate::slackware::cron::RunEvent;
+use crate::slackware::proxy::ProxySettings;
+use crate::utils::root;
+
 /// Result of a command execution
 #[derive(Debug, Clone)]
 pub struct CommandResult {
     pub success: bool,
     pub stdout: String,
     pub stderr: String,
-    pub exit_code: Option<i32>,
 }
 
 impl CommandResult {
-    pub fn is_success(&self) -> bool {
-        self.success
-    }
-
     pub fn output(&self) -> &str {
         if self.stdout.is_empty() {
             &self.stderr
@@ -23,42 +24,94 @@ impl CommandResult {
             &self.stdout
         }
     }
+
+    /// Stands in for the result of a command cancelled from the Tasks tab
+    pub fn cancelled() -> Self {
+        Self {
+            success: false,
+            stdout: String::new(),
+            stderr: "Cancelled".to_string(),
+        }
+    }
 }
 
 /// Async command executor for running shell commands
+#[derive(Clone)]
 pub struct CommandExecutor {
     /// Channel for sending command progress updates
     progress_tx: Option<mpsc::UnboundedSender<String>>,
+    /// Tab the commands are recorded under in the audit log; the current
+    /// tab when unset
+    tab: Option<&'static str>,
 }
 
 impl CommandExecutor {
     pub fn new() -> Self {
-        Self { progress_tx: None }
+        Self {
+            progress_tx: None,
+            tab: None,
+        }
     }
 
     /// Create executor with progress channel
     pub fn with_progress(tx: mpsc::UnboundedSender<String>) -> Self {
         Self {
             progress_tx: Some(tx),
+            tab: None,
         }
     }
 
+    /// A copy recording its commands under `tab`, for work that may go on
+    /// after the user has moved to another tab
+    pub fn with_tab(&self, tab: &'static str) -> Self {
+        Self {
+            tab: Some(tab),
+            ..self.clone()
+        }
+    }
+
+    fn audit(&self, tab: &str, cmd: &str, args: &[&str], exit_code: Option<i32>) {
+        let argv = std::iter::once(cmd)
+            .chain(args.iter().copied())
+            .map(str::to_string)
+            .collect();
+        // An unwritable audit log must not stop the work itself
+        let _ = audit::record(tab, argv, exit_code);
+    }
+
     /// Execute a command and return the result
+    ///
+    /// The system proxy settings are exported so downloads made by
+    /// slackpkg, wget and friends work behind a proxy even when sudo
+    /// stripped them from our environment. Run as a normal user, the
+    /// command goes through sudo or doas.
     pub async fn execute(&self, cmd: &str, args: &[&str]) -> CommandResult {
         self.send_progress(format!("Running: {} {}", cmd, args.join(" ")));
+        let tab = self.tab.unwrap_or_else(audit::current_tab);
 
-        let output = Command::new(cmd)
+        let output = Command::from(root::command_with_env(cmd, &ProxySettings::load().env_vars()))
             .args(args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            // Cancelling the job drops this future, and must stop the command too
+            .kill_on_drop(true)
             .output()
             .await;
+        self.audit(
+            tab,
+            cmd,
+            args,
+            output.as_ref().ok().and_then(|o| o.status.code()),
+        );
 
         match output {
             Ok(output) => {
                 let stdout = String::from_utf8_lossy(&output.stdout).to_string();
                 let stderr = String::from_utf8_lossy(&output.stderr).to_string();
                 let success = output.status.success();
+                for line in stdout.lines() {
+                    self.send_progress(line.to_string());
+                }
 
                 if success {
                     self.send_progress("Command completed successfully".to_string());
@@ -70,7 +123,6 @@ impl CommandExecutor {
                     success,
                     stdout,
                     stderr,
-                    exit_code: output.status.code(),
                 }
             }
             Err(e) => {
@@ -79,17 +131,11 @@ impl CommandExecutor {
                     success: false,
                     stdout: String::new(),
                     stderr: e.to_string(),
-                    exit_code: None,
                 }
             }
         }
     }
 
-    /// Execute a shell command (via /bin/sh -c)
-    pub async fn execute_shell(&self, command: &str) -> CommandResult {
-        self.execute("sh", &["-c", command]).await
-    }
-
     /// Download a file using wget
     pub async fn download_file(&self, url: &str, output_path: &str) -> CommandResult {
         self.send_progress(format!("Downloading: {}", url));
@@ -102,12 +148,6 @@ impl CommandExecutor {
         self.execute("installpkg", &[package_path]).await
     }
 
-    /// Remove a Slackware package
-    pub async fn removepkg(&self, package_name: &str) -> CommandResult {
-        self.send_progress(format!("Removing package: {}", package_name));
-        self.execute("removepkg", &[package_name]).await
-    }
-
     /// Run slackpkg command
     pub async fn slackpkg(&self, args: &[&str]) -> CommandResult {
         self.send_progress(format!("Running slackpkg {}", args.join(" ")));
@@ -170,8 +210,9 @@ impl CommandExecutor {
     pub async fn set_password(&self, username: &str, password: &str) -> CommandResult {
         self.send_progress(format!("Setting password for: {}", username));
         let input = format!("{}:{}", username, password);
+        let tab = self.tab.unwrap_or_else(audit::current_tab);
 
-        let output = Command::new("chpasswd")
+        let output = Command::from(root::command("chpasswd"))
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -185,36 +226,38 @@ impl CommandExecutor {
                     let _ = stdin.shutdown().await;
                 }
 
-                match child.wait_with_output().await {
+                let output = child.wait_with_output().await;
+                // The password goes in on stdin and stays out of the log
+                self.audit(
+                    tab,
+                    "chpasswd",
+                    &[],
+                    output.as_ref().ok().and_then(|o| o.status.code()),
+                );
+                match output {
                     Ok(output) => CommandResult {
                         success: output.status.success(),
                         stdout: String::from_utf8_lossy(&output.stdout).to_string(),
                         stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-                        exit_code: output.status.code(),
                     },
                     Err(e) => CommandResult {
                         success: false,
                         stdout: String::new(),
                         stderr: e.to_string(),
-                        exit_code: None,
                     },
                 }
             }
-            Err(e) => CommandResult {
-                success: false,
-                stdout: String::new(),
-                stderr: e.to_string(),
-                exit_code: None,
-            },
+            Err(e) => {
+                self.audit(tab, "chpasswd", &[], None);
+                CommandResult {
+                    success: false,
+                    stdout: String::new(),
+                    stderr: e.to_string(),
+                }
+            }
         }
     }
 
-    /// Run lilo bootloader
-    pub async fn lilo(&self) -> CommandResult {
-        self.send_progress("Running lilo bootloader update".to_string());
-        self.execute("lilo", &[]).await
-    }
-
     fn send_progress(&self, message: String) {
         if let Some(tx) = &self.progress_tx {
             let _ = tx.send(message);
@@ -227,3 +270,44 @@ impl Default for CommandExecutor {
         Self::new()
     }
 }
+
+/// Run `program` to completion in the background, streaming its output
+/// with stderr merged in. Unlike a cron job run, it is never killed
+/// halfway when nobody is listening any more: mkfs or fsck must finish.
+pub fn stream(program: &str, args: &[String]) -> mpsc::UnboundedReceiver<RunEvent> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut process = Command::from(root::command("/bin/sh"));
+    process
+        .args(["-c", "exec 2>&1; exec \"$0\" \"$@\"", program])
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    let _ = tx.send(RunEvent::Line(format!("# {} {}", program, args.join(" "))));
+    let tab = audit::current_tab();
+    let argv: Vec<String> = std::iter::once(program.to_string())
+        .chain(args.iter().cloned())
+        .collect();
+    tokio::spawn(async move {
+        let mut child = match process.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = audit::record(tab, argv, None);
+                let _ = tx.send(RunEvent::Line(format!("Failed to start: {}", e)));
+                let _ = tx.send(RunEvent::Exited(None));
+                return;
+            }
+        };
+        let Some(stdout) = child.stdout.take() else {
+            return;
+        };
+        let mut lines = tokio::io::BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = tx.send(RunEvent::Line(line));
+        }
+        let code = child.wait().await.ok().and_then(|s| s.code());
+        let _ = audit::record(tab, argv, code);
+        let _ = tx.send(RunEvent::Exited(code));
+    });
+    rx
+}
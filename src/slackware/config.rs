@@ -1,9 +1,14 @@
 use std::fs;
-use std::path::Path;
+use std::io::ErrorKind;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
 
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
+use super::remote;
 use crate::utils::error::{AppError, Result};
+use crate::utils::root;
 
 /// Detected bootloader type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,13 +22,13 @@ impl Bootloader {
     /// Detect which bootloader is installed on the system
     pub fn detect() -> Self {
         // Check for LILO first (Slackware default)
-        if Path::new("/etc/lilo.conf").exists() {
+        if root::exists(Path::new("/etc/lilo.conf")) {
             return Bootloader::Lilo;
         }
 
         // Check for GRUB
-        if Path::new("/boot/grub/grub.cfg").exists()
-            || Path::new("/etc/default/grub").exists()
+        if root::exists(Path::new("/boot/grub/grub.cfg"))
+            || root::exists(Path::new("/etc/default/grub"))
         {
             return Bootloader::Grub;
         }
@@ -169,35 +174,25 @@ impl SlackwareConfig {
             }
         }
 
-        fs::write(mirrors_path, new_lines.join("\n") + "\n")?;
+        Transaction::begin("Change the slackpkg mirror")
+            .write(mirrors_path, new_lines.join("\n") + "\n")?;
         Ok(())
     }
 
-    /// Read a config file
-    pub fn read_config(path: &str) -> Result<String> {
-        fs::read_to_string(path).map_err(|e| {
-            AppError::FileOperation(format!("Failed to read {}: {}", path, e))
-        })
-    }
-
-    /// Write a config file
-    pub fn write_config(path: &str, content: &str) -> Result<()> {
-        fs::write(path, content).map_err(|e| {
-            AppError::FileOperation(format!("Failed to write {}: {}", path, e))
-        })
-    }
-
     /// Modify /etc/inittab to change default runlevel
     pub fn set_default_runlevel(runlevel: u8) -> Result<()> {
         let inittab_path = "/etc/inittab";
 
-        if !Path::new(inittab_path).exists() {
-            return Err(AppError::FileOperation(
-                "/etc/inittab not found".to_string(),
-            ));
-        }
-
-        let content = fs::read_to_string(inittab_path)?;
+        // Read from the managed host, which the change is written to
+        let content = match root::read_file(Path::new(inittab_path)) {
+            Ok(content) => String::from_utf8_lossy(&content).to_string(),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(AppError::FileOperation(
+                    "/etc/inittab not found".to_string(),
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        };
         let re = Regex::new(r"id:\d:initdefault:").map_err(|e| {
             AppError::Config(format!("Regex error: {}", e))
         })?;
@@ -206,11 +201,209 @@ impl SlackwareConfig {
             .replace(&content, format!("id:{}:initdefault:", runlevel).as_str())
             .to_string();
 
-        fs::write(inittab_path, new_content)?;
+        Transaction::begin(format!("Set the default runlevel to {}", runlevel))
+            .write(Path::new(inittab_path), new_content)?;
         Ok(())
     }
 }
 
+/// Previous contents of changed config files, one directory per change
+const JOURNAL_DIR: &str = "/var/lib/slackware-cli-manager/journal";
+const JOURNAL_ENTRY: &str = "entry.json";
+/// Changes kept for undo; older ones are dropped
+const JOURNAL_LIMIT: usize = 50;
+
+/// A file as it was before a change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournaledFile {
+    pub path: PathBuf,
+    /// False when the change created the file, so undo removes it
+    pub existed: bool,
+    mode: Option<u32>,
+}
+
+/// One change to the system's configuration, which may span files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub description: String,
+    pub time: String,
+    pub files: Vec<JournaledFile>,
+}
+
+/// A change to config files that can be undone. Each file's previous
+/// content is put in the journal before it is first touched, so even a
+/// change that fails halfway can be rolled back.
+pub struct Transaction {
+    journal: PathBuf,
+    entry: JournalEntry,
+    /// Directory of this change in the journal, once a file was recorded
+    dir: Option<PathBuf>,
+}
+
+impl Transaction {
+    pub fn begin(description: impl Into<String>) -> Self {
+        Self::begin_in(Path::new(JOURNAL_DIR), description)
+    }
+
+    fn begin_in(journal: &Path, description: impl Into<String>) -> Self {
+        Self {
+            journal: journal.to_path_buf(),
+            entry: JournalEntry {
+                description: description.into(),
+                time: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                files: Vec::new(),
+            },
+            dir: None,
+        }
+    }
+
+    /// Keep the current content of `path` before something changes it
+    pub fn record(&mut self, path: &Path) -> Result<()> {
+        // The journal is kept on this machine, so changes made on a remote
+        // host cannot be undone
+        if remote::current().is_some() || self.entry.files.iter().any(|f| f.path == path) {
+            return Ok(());
+        }
+        let journal_error = |e: std::io::Error| {
+            AppError::FileOperation(format!("Failed to journal {}: {}", path.display(), e))
+        };
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => {
+                let dir = self.new_entry_dir().map_err(journal_error)?;
+                self.dir = Some(dir.clone());
+                dir
+            }
+        };
+        let existed = match root::read_file(path) {
+            Ok(content) => {
+                root::write_private(&dir.join(self.entry.files.len().to_string()), &content)
+                    .map_err(journal_error)?;
+                true
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => false,
+            Err(e) => return Err(journal_error(e)),
+        };
+        self.entry.files.push(JournaledFile {
+            path: path.to_path_buf(),
+            existed,
+            mode: fs::metadata(path).ok().map(|m| m.permissions().mode()),
+        });
+        let entry = serde_json::to_string_pretty(&self.entry).unwrap_or_default();
+        root::write_file(&dir.join(JOURNAL_ENTRY), entry.as_bytes()).map_err(journal_error)?;
+        Ok(())
+    }
+
+    /// Record `path`, then replace its content
+    pub fn write(&mut self, path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+        self.record(path)?;
+        root::write_file(path, content.as_ref())?;
+        Ok(())
+    }
+
+    /// A directory numbered after all earlier changes, dropping the oldest
+    /// ones past the limit
+    fn new_entry_dir(&self) -> std::io::Result<PathBuf> {
+        // The copies include files like /etc/shadow
+        root::create_private_dir(&self.journal)?;
+        let mut names = entry_names(&self.journal);
+        let next = names.last().and_then(|n| n.parse::<u64>().ok()).map_or(1, |n| n + 1);
+        let name = format!("{:010}", next);
+        let dir = self.journal.join(&name);
+        root::create_dir_all(&dir)?;
+        names.push(name);
+        while names.len() > JOURNAL_LIMIT {
+            let _ = root::remove(&self.journal.join(names.remove(0)));
+        }
+        Ok(dir)
+    }
+}
+
+/// Replace a config file atomically, saving the old contents as `.bak`.
+/// Returns the path of the backup copy.
+pub fn write_with_backup(path: &Path, content: &str) -> Result<PathBuf> {
+    let backup = path.with_extension(
+        path.extension()
+            .map(|e| format!("{}.bak", e.to_string_lossy()))
+            .unwrap_or_else(|| "bak".to_string()),
+    );
+    write_with_backup_to(path, content, backup)
+}
+
+/// Like [`write_with_backup`], for directories where a `.bak` file would
+/// itself be picked up as configuration
+pub fn write_with_backup_to(path: &Path, content: &str, backup: PathBuf) -> Result<PathBuf> {
+    Transaction::begin(format!("Edit {}", path.display())).record(path)?;
+    if path.exists() {
+        root::copy(path, &backup).map_err(|e| {
+            AppError::FileOperation(format!("Failed to back up {}: {}", path.display(), e))
+        })?;
+    }
+
+    let tmp = path.with_extension("tmp");
+    root::write_file(&tmp, content.as_bytes())?;
+    let mode = fs::metadata(path).map_or(0o644, |m| m.permissions().mode());
+    let _ = root::set_mode(&tmp, mode);
+    root::rename(&tmp, path).map_err(|e| {
+        let _ = root::remove(&tmp);
+        AppError::FileOperation(format!("Failed to write {}: {}", path.display(), e))
+    })?;
+    Ok(backup)
+}
+
+/// Journal directories, oldest first; the zero-padded numbers sort as text
+fn entry_names(journal: &Path) -> Vec<String> {
+    let mut names = root::read_dir_names(journal).unwrap_or_default();
+    names.sort();
+    names
+}
+
+fn read_entry(dir: &Path) -> Option<JournalEntry> {
+    serde_json::from_slice(&root::read_file(&dir.join(JOURNAL_ENTRY)).ok()?).ok()
+}
+
+/// The change "Undo last change" would revert
+pub fn last_change() -> Option<JournalEntry> {
+    last_change_in(Path::new(JOURNAL_DIR))
+}
+
+fn last_change_in(journal: &Path) -> Option<JournalEntry> {
+    let name = entry_names(journal).pop()?;
+    read_entry(&journal.join(name))
+}
+
+/// Put back the files of the latest change and drop it from the journal
+pub fn undo_last_change() -> Result<JournalEntry> {
+    undo_last_change_in(Path::new(JOURNAL_DIR))
+}
+
+fn undo_last_change_in(journal: &Path) -> Result<JournalEntry> {
+    let name = entry_names(journal)
+        .pop()
+        .ok_or_else(|| AppError::Config("Nothing to undo".to_string()))?;
+    let dir = journal.join(name);
+    let Some(entry) = read_entry(&dir) else {
+        // A change that never got as far as recording a file
+        let _ = root::remove(&dir);
+        return Err(AppError::Config("Nothing to undo".to_string()));
+    };
+    for (i, file) in entry.files.iter().enumerate().rev() {
+        if file.existed {
+            root::write_file(&file.path, &root::read_file(&dir.join(i.to_string()))?)?;
+            if let Some(mode) = file.mode {
+                root::set_mode(&file.path, mode)?;
+            }
+        } else {
+            match root::remove(&file.path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+    }
+    root::remove(&dir)?;
+    Ok(entry)
+}
+
 /// Represents a mirror entry
 #[derive(Debug, Clone)]
 pub struct MirrorEntry {
@@ -225,3 +418,40 @@ impl MirrorEntry {
         format!("[{}] {} ({})", status, self.url, self.region)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_last_change() {
+        let root = std::env::temp_dir().join(format!("journal-test-{}", std::process::id()));
+        let journal = root.join("journal");
+        let existing = root.join("lilo.conf");
+        let created = root.join("new.conf");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(&existing, "timeout = 50\n").unwrap();
+
+        let mut first = Transaction::begin_in(&journal, "Set timeout");
+        first.write(&existing, "timeout = 10\n").unwrap();
+        let private = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(private(&journal), 0o700);
+        let entry = journal.join(entry_names(&journal).pop().unwrap());
+        assert_eq!(private(&entry.join("0")), 0o600);
+        let mut second = Transaction::begin_in(&journal, "Add a file");
+        second.write(&existing, "timeout = 20\n").unwrap();
+        second.write(&created, "new\n").unwrap();
+        assert_eq!(last_change_in(&journal).unwrap().description, "Add a file");
+
+        let undone = undo_last_change_in(&journal).unwrap();
+        assert_eq!(undone.files.len(), 2);
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "timeout = 10\n");
+        assert!(!created.exists());
+
+        undo_last_change_in(&journal).unwrap();
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "timeout = 50\n");
+        assert!(last_change_in(&journal).is_none());
+        assert!(undo_last_change_in(&journal).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
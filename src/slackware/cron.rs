@@ -0,0 +1,596 @@
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+use crate::slackware::audit;
+use crate::slackware::config::{self, Transaction};
+use crate::slackware::cronexpr;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// Where dcron keeps user crontabs
+pub const CRONTAB_DIR: &str = "/var/spool/cron/crontabs";
+pub const CRON_D: &str = "/etc/cron.d";
+/// Periods with an /etc/cron.<period> directory run by run-parts
+pub const PERIODS: [&str; 4] = ["hourly", "daily", "weekly", "monthly"];
+
+/// Previous versions of edited crontabs. Kept out of the cron directories
+/// so crond never runs a backup.
+const BACKUP_DIR: &str = "/var/backups/slackware-cli-manager/cron";
+
+/// Environment variables a crontab commonly sets for its jobs
+pub const ENV_VARS: [&str; 3] = ["MAILTO", "PATH", "SHELL"];
+
+/// Schedules offered when adding a job
+pub const PRESETS: [&str; 8] = [
+    "@hourly",
+    "@daily",
+    "@weekly",
+    "@monthly",
+    "@reboot",
+    "*/15 * * * *",
+    "0 3 * * *",
+    "30 4 * * 0",
+];
+
+/// Where a cron job is defined
+#[derive(Debug, Clone, PartialEq)]
+pub enum CronSource {
+    System(String), // Path to file in /etc/cron.*
+    User(String),   // Username
+}
+
+impl CronSource {
+    /// The file crond reads the job from
+    pub fn path(&self) -> String {
+        match self {
+            CronSource::System(path) => path.clone(),
+            CronSource::User(user) => format!("{}/{}", CRONTAB_DIR, user),
+        }
+    }
+
+    /// System crontabs name the user to run as after the schedule
+    pub fn has_user_field(&self) -> bool {
+        matches!(self, CronSource::System(p) if p == "/etc/crontab" || p.starts_with(CRON_D))
+    }
+
+    /// A script in one of the /etc/cron.<period> directories rather than
+    /// a crontab line
+    pub fn is_script(&self) -> bool {
+        matches!(
+            self,
+            CronSource::System(p) if p.starts_with("/etc/cron.") && !p.starts_with(CRON_D)
+        )
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            CronSource::System(path) => Ok(fs::read_to_string(path)?),
+            CronSource::User(user) => read_user_crontab(user),
+        }
+    }
+
+    /// Write the crontab back, returning the backup of the previous content
+    pub fn write(&self, content: &str) -> Result<PathBuf> {
+        match self {
+            CronSource::System(path) => write_system_file(Path::new(path), content),
+            CronSource::User(user) => write_user_crontab(user, content),
+        }
+    }
+}
+
+/// Replace the first line of `content` reading `old` (ignoring surrounding
+/// whitespace) with `new`, or drop it when `new` is `None`. Returns `None`
+/// when no line matches.
+pub fn replace_line(content: &str, old: &str, new: Option<&str>) -> Option<String> {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let index = lines.iter().position(|l| l.trim() == old.trim())?;
+    match new {
+        Some(line) => lines[index] = line,
+        None => {
+            lines.remove(index);
+        }
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+/// A `NAME=value` line as (name, value). Spaces around '=' are allowed and
+/// the value is kept as written, quotes included.
+pub fn env_assignment(line: &str) -> Option<(&str, &str)> {
+    let (name, value) = line.trim().split_once('=')?;
+    let name = name.trim();
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some((name, value.trim()))
+}
+
+/// Variables a crontab sets, in file order
+pub fn env_vars(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(env_assignment)
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Set `name` in a crontab, replacing its first assignment, or remove it
+/// when `value` is `None`. A new assignment goes before the first job so it
+/// applies to all of them.
+pub fn set_env_var(content: &str, name: &str, value: Option<&str>) -> String {
+    let assignment = value.map(|v| format!("{}={}", name, v.trim()));
+    let mut lines: Vec<String> = Vec::new();
+    let mut done = false;
+    for line in content.lines() {
+        if env_assignment(line).is_some_and(|(n, _)| n == name) {
+            if let (false, Some(assignment)) = (done, &assignment) {
+                lines.push(assignment.clone());
+            }
+            done = true;
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    if let (false, Some(assignment)) = (done, assignment) {
+        let first_job = lines
+            .iter()
+            .position(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+            .unwrap_or(lines.len());
+        lines.insert(first_job, assignment);
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Set or remove a variable in a crontab, returning the backup
+pub fn update_env(source: &CronSource, name: &str, value: Option<&str>) -> Result<PathBuf> {
+    let content = source.read()?;
+    source.write(&set_env_var(&content, name, value))
+}
+
+/// The user a system crontab line runs as
+pub fn line_user(raw: &str) -> Option<&str> {
+    let schedule_words = if raw.trim_start().starts_with('@') {
+        1
+    } else {
+        5
+    };
+    raw.split_whitespace().nth(schedule_words)
+}
+
+/// `raw` with its schedule and command replaced, keeping the user field of
+/// system crontabs
+pub fn rebuild_line(raw: &str, has_user: bool, schedule: &str, command: &str) -> String {
+    let user = if has_user { line_user(raw) } else { None };
+    match user {
+        Some(user) => format!("{} {} {}", schedule.trim(), user, command.trim()),
+        None => format!("{} {}", schedule.trim(), command.trim()),
+    }
+}
+
+/// Replace a job's line in its crontab, returning the backup
+pub fn update_job(source: &CronSource, raw: &str, new: Option<&str>) -> Result<PathBuf> {
+    let content = source.read()?;
+    let updated = replace_line(&content, raw, new)
+        .ok_or_else(|| AppError::Config(format!("The job is no longer in {}", source.path())))?;
+    source.write(&updated)
+}
+
+/// The job of a commented-out crontab line. A comment counts as a disabled
+/// job when a schedule follows the `#` straight away, which tells
+/// "#0 3 * * * job" from "# m h dom mon dow command".
+pub fn disabled_job(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix('#')?.trim_start();
+    let schedule = rest.split_whitespace().next()?;
+    let starts_like_schedule = schedule.starts_with(|c: char| c.is_ascii_digit() || c == '*');
+    (starts_like_schedule || is_valid_schedule(schedule)).then_some(rest)
+}
+
+/// Comment a crontab line out, or back in
+pub fn toggled_line(raw: &str, enabled: bool) -> String {
+    if enabled {
+        format!("#{}", raw.trim())
+    } else {
+        raw.trim().trim_start_matches('#').trim_start().to_string()
+    }
+}
+
+/// Run or stop running a periodic script by flipping its execute bits
+pub fn set_script_enabled(path: &str, enabled: bool) -> Result<()> {
+    let mode = if enabled { 0o755 } else { 0o644 };
+    let verb = if enabled { "Enable" } else { "Disable" };
+    Transaction::begin(format!("{} {}", verb, path)).record(Path::new(path))?;
+    root::set_mode(Path::new(path), mode)?;
+    Ok(())
+}
+
+/// Move a periodic script to the backup directory, returning where it went
+pub fn remove_script(path: &str) -> Result<PathBuf> {
+    let backup = backup_path(&path.trim_start_matches('/').replace('/', "_"))?;
+    root::copy(Path::new(path), &backup)?;
+    Transaction::begin(format!("Remove {}", path)).record(Path::new(path))?;
+    root::remove(Path::new(path))?;
+    Ok(backup)
+}
+
+/// Put back what `backup` holds for `source`, undoing the last change
+pub fn restore(source: &CronSource, backup: &Path) -> Result<()> {
+    if source.is_script() {
+        let path = source.path();
+        Transaction::begin(format!("Restore {}", path)).record(Path::new(&path))?;
+        // The copy keeps the script's permissions
+        root::copy(backup, Path::new(&path))?;
+        return Ok(());
+    }
+    let content = root::read_file(backup)?;
+    source.write(&String::from_utf8_lossy(&content)).map(|_| ())
+}
+
+/// Output of a job started with `run_now`
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunEvent {
+    Line(String),
+    /// The exit code, `None` when the job was killed by a signal
+    Exited(Option<i32>),
+}
+
+/// Split a crontab command the way crond does: text after the first
+/// unescaped '%' is fed to the command on stdin, with further '%'s as
+/// newlines. "\%" is a literal '%'.
+pub fn split_command(command: &str) -> (String, Option<String>) {
+    let mut parts = vec![String::new()];
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'%') => {
+                chars.next();
+                parts.last_mut().unwrap().push('%');
+            }
+            '%' => parts.push(String::new()),
+            c => parts.last_mut().unwrap().push(c),
+        }
+    }
+    let command = parts.remove(0);
+    let stdin = (!parts.is_empty()).then(|| parts.join("\n") + "\n");
+    (command, stdin)
+}
+
+/// Run `command` now as `user` through /bin/sh, as crond would, streaming
+/// stdout and stderr line by line and finally the exit status. The job is
+/// killed once the receiver is dropped.
+pub fn run_now(user: &str, command: &str) -> mpsc::UnboundedReceiver<RunEvent> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let (command, stdin) = split_command(command);
+    // Merge stderr into stdout so the two stay in order
+    let script = format!("exec 2>&1\n{}", command);
+    let argv: Vec<String> = if user == "root" {
+        vec!["/bin/sh".into(), "-c".into(), command.clone()]
+    } else {
+        let argv = ["su", "-s", "/bin/sh", "-c", &command, user];
+        argv.iter().map(|a| a.to_string()).collect()
+    };
+    let tab = audit::current_tab();
+    let mut process = if user == "root" {
+        let mut process = tokio::process::Command::from(root::command("/bin/sh"));
+        process.args(["-c", &script]);
+        process
+    } else {
+        let mut process = tokio::process::Command::from(root::command("su"));
+        process.args(["-s", "/bin/sh", "-c", &script, user]);
+        process
+    };
+    process
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    tokio::spawn(async move {
+        let mut child = match process.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = audit::record(tab, argv, None);
+                let _ = tx.send(RunEvent::Line(format!("Failed to start: {}", e)));
+                let _ = tx.send(RunEvent::Exited(None));
+                return;
+            }
+        };
+        if let Some(mut pipe) = child.stdin.take() {
+            let _ = pipe.write_all(stdin.unwrap_or_default().as_bytes()).await;
+        }
+        let Some(stdout) = child.stdout.take() else {
+            return;
+        };
+        let mut lines = tokio::io::BufReader::new(stdout).lines();
+        loop {
+            tokio::select! {
+                _ = tx.closed() => {
+                    let _ = audit::record(tab, argv, None);
+                    return;
+                }
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => {
+                        let _ = tx.send(RunEvent::Line(line));
+                    }
+                    _ => break,
+                },
+            }
+        }
+        let code = child.wait().await.ok().and_then(|status| status.code());
+        let _ = audit::record(tab, argv, code);
+        let _ = tx.send(RunEvent::Exited(code));
+    });
+    rx
+}
+
+/// Where a new job is written to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// A user's crontab, installed with `crontab -u <user> -`
+    User(String),
+    /// A file in /etc/cron.d, run as the given user
+    CronD { name: String, user: String },
+    /// An executable script in /etc/cron.<period>
+    Script { name: String, period: String },
+}
+
+fn backup_path(name: &str) -> Result<PathBuf> {
+    root::create_private_dir(Path::new(BACKUP_DIR))?;
+    Ok(Path::new(BACKUP_DIR).join(name))
+}
+
+/// File names run-parts and crond accept: no dots, no backup suffixes
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The schedule is a known @ keyword or five fields with values in range
+pub fn is_valid_schedule(schedule: &str) -> bool {
+    cronexpr::validate(schedule).is_ok()
+}
+
+/// Accounts that can own a crontab: root and regular users with a login
+/// shell, from /etc/passwd content
+pub fn login_users(passwd: &str) -> Vec<String> {
+    passwd
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(':').collect();
+            let [name, _, uid, _, _, _, shell] = fields[..] else {
+                return None;
+            };
+            let uid: u32 = uid.parse().ok()?;
+            let can_login = !shell.ends_with("nologin") && !shell.ends_with("false");
+            (uid == 0 || (1000..65534).contains(&uid) && can_login).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Users offered by the crontab picker: login users and anyone who
+/// already has a crontab, root first
+pub fn crontab_users() -> Vec<String> {
+    let mut users = login_users(&fs::read_to_string("/etc/passwd").unwrap_or_default());
+    if let Ok(entries) = fs::read_dir(CRONTAB_DIR) {
+        users.extend(entries.filter_map(|e| e.ok()).filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            // dcron keeps cron.update next to the crontabs
+            is_valid_name(&name).then_some(name)
+        }));
+    }
+    users.sort_by(|a, b| (a != "root", a).cmp(&(b != "root", b)));
+    users.dedup();
+    users
+}
+
+/// A user's crontab, empty if they have none
+pub fn read_user_crontab(user: &str) -> Result<String> {
+    let output = root::command("crontab").args(["-u", user, "-l"]).output()?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("no crontab") {
+        return Ok(String::new());
+    }
+    Err(AppError::CommandFailed(format!(
+        "crontab -l: {}",
+        stderr.trim()
+    )))
+}
+
+/// Install a user's crontab through `crontab`, so crond notices the change,
+/// returning the backup of the previous one
+pub fn write_user_crontab(user: &str, content: &str) -> Result<PathBuf> {
+    let backup = backup_path(&format!("{}.crontab", user))?;
+    let previous = read_user_crontab(user)?;
+    // Written anew, readable by root only like the crontab itself
+    match root::remove(&backup) {
+        Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    root::write_private(&backup, previous.as_bytes())?;
+
+    let mut command = root::command("crontab");
+    command
+        .args(["-u", user, "-"])
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    audit::record_command(&command, output.status.code());
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "crontab -u {}: {}",
+            user,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(backup)
+}
+
+/// Write a system crontab such as /etc/crontab or a cron.d file,
+/// returning the backup of the previous content
+pub fn write_system_file(path: &Path, content: &str) -> Result<PathBuf> {
+    let name = path
+        .to_string_lossy()
+        .trim_start_matches('/')
+        .replace('/', "_");
+    config::write_with_backup_to(path, content, backup_path(&name)?)
+}
+
+/// Add `command` on `schedule` to `target`, returning the file written
+pub fn add_job(target: &Target, schedule: &str, command: &str) -> Result<String> {
+    let schedule = schedule.trim();
+    let command = command.trim();
+    if command.is_empty() {
+        return Err(AppError::Config("The command is empty".to_string()));
+    }
+    cronexpr::validate(schedule).map_err(AppError::Config)?;
+
+    match target {
+        Target::User(user) => {
+            let mut content = read_user_crontab(user)?;
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content.push_str(&format!("{} {}\n", schedule, command));
+            write_user_crontab(user, &content)?;
+            Ok(format!("{}/{}", CRONTAB_DIR, user))
+        }
+        Target::CronD { name, user } => {
+            if !is_valid_name(name) {
+                return Err(AppError::Config(format!("Invalid file name '{}'", name)));
+            }
+            let path = Path::new(CRON_D).join(name);
+            let mut content = fs::read_to_string(&path).unwrap_or_default();
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content.push_str(&format!("{} {} {}\n", schedule, user, command));
+            write_system_file(&path, &content)?;
+            Ok(path.display().to_string())
+        }
+        Target::Script { name, period } => {
+            if !is_valid_name(name) {
+                return Err(AppError::Config(format!("Invalid script name '{}'", name)));
+            }
+            let path = Path::new("/etc")
+                .join(format!("cron.{}", period))
+                .join(name);
+            if path.exists() {
+                return Err(AppError::Config(format!(
+                    "{} already exists",
+                    path.display()
+                )));
+            }
+            Transaction::begin(format!("Add {}", path.display()))
+                .write(&path, format!("#!/bin/sh\n{}\n", command))?;
+            root::set_mode(&path, 0o755)?;
+            Ok(path.display().to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        assert!(is_valid_schedule("@daily"));
+        assert!(is_valid_schedule("*/15 * * * *"));
+        assert!(!is_valid_schedule("@fortnightly"));
+        assert!(!is_valid_schedule("0 3 * *"));
+        assert!(!is_valid_schedule("0 25 * * *"));
+        assert!(is_valid_name("backup-db"));
+        assert!(!is_valid_name("backup.sh"));
+        assert!(!is_valid_name(""));
+    }
+
+    #[test]
+    fn test_login_users() {
+        let passwd = "root:x:0:0::/root:/bin/bash\n\
+                      daemon:x:2:2:daemon:/sbin:/bin/false\n\
+                      alice:x:1000:100::/home/alice:/bin/bash\n\
+                      svc:x:1001:100::/var/lib/svc:/sbin/nologin\n\
+                      nobody:x:65534:65534:nobody:/:/bin/false\n";
+        assert_eq!(login_users(passwd), vec!["root", "alice"]);
+    }
+
+    #[test]
+    fn test_env_vars() {
+        let content = "# root crontab\nSHELL=/bin/sh\n0 3 * * * /bin/true\nPATH = /usr/bin:/bin\n";
+        assert_eq!(
+            env_vars(content),
+            vec![
+                ("SHELL".to_string(), "/bin/sh".to_string()),
+                ("PATH".to_string(), "/usr/bin:/bin".to_string()),
+            ]
+        );
+        assert_eq!(env_assignment("0 3 * * * FOO=1 /bin/true"), None);
+
+        assert_eq!(
+            set_env_var(content, "MAILTO", Some("admin@example.com")),
+            "# root crontab\nMAILTO=admin@example.com\nSHELL=/bin/sh\n\
+             0 3 * * * /bin/true\nPATH = /usr/bin:/bin\n"
+        );
+        assert_eq!(
+            set_env_var(content, "PATH", None),
+            "# root crontab\nSHELL=/bin/sh\n0 3 * * * /bin/true\n"
+        );
+        assert_eq!(
+            set_env_var(content, "SHELL", Some("/bin/bash")),
+            "# root crontab\nSHELL=/bin/bash\n0 3 * * * /bin/true\nPATH = /usr/bin:/bin\n"
+        );
+    }
+
+    #[test]
+    fn test_edit_line() {
+        let raw = "0 3 * * * root /usr/sbin/logrotate /etc/logrotate.conf";
+        assert_eq!(
+            rebuild_line(
+                raw,
+                true,
+                "@daily",
+                "/usr/sbin/logrotate -v /etc/logrotate.conf"
+            ),
+            "@daily root /usr/sbin/logrotate -v /etc/logrotate.conf"
+        );
+        assert_eq!(
+            rebuild_line("@hourly /bin/true", false, "*/5 * * * *", "/bin/false"),
+            "*/5 * * * * /bin/false"
+        );
+
+        let content = "SHELL=/bin/sh\n  0 3 * * * /bin/true\n";
+        assert_eq!(
+            replace_line(content, "0 3 * * * /bin/true", Some("0 4 * * * /bin/true")).unwrap(),
+            "SHELL=/bin/sh\n0 4 * * * /bin/true\n"
+        );
+        assert_eq!(
+            replace_line(content, "0 3 * * * /bin/true", None).unwrap(),
+            "SHELL=/bin/sh\n"
+        );
+        assert_eq!(replace_line(content, "@daily /bin/true", None), None);
+
+        assert_eq!(toggled_line("0 3 * * * /bin/true", true), "#0 3 * * * /bin/true");
+        assert_eq!(toggled_line("# 0 3 * * * /bin/true", false), "0 3 * * * /bin/true");
+        assert_eq!(disabled_job("#@daily /bin/true"), Some("@daily /bin/true"));
+        assert_eq!(disabled_job("# m h dom mon dow command"), None);
+
+        assert_eq!(
+            split_command("date +\\%F >> /tmp/log"),
+            ("date +%F >> /tmp/log".to_string(), None)
+        );
+        assert_eq!(
+            split_command("mail -s hi root%line one%line two"),
+            ("mail -s hi root".to_string(), Some("line one\nline two\n".to_string()))
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike};
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAY_NAMES: [&str; 8] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// How far ahead to look for a matching day. Covers "0 0 29 2 *" across
+/// a leap year gap.
+const SEARCH_DAYS: i64 = 366 * 8;
+
+/// A parsed five-field cron schedule, each field a bit set of the values
+/// it matches
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// A field starting with '*' doesn't restrict the day; when both the
+    /// day of month and the weekday are restricted either one matching is
+    /// enough
+    any_day: bool,
+    any_weekday: bool,
+}
+
+/// The five fields an @keyword stands for. @reboot has none.
+pub fn expand_keyword(keyword: &str) -> Option<&'static str> {
+    match keyword {
+        "@yearly" | "@annually" => Some("0 0 1 1 *"),
+        "@monthly" => Some("0 0 1 * *"),
+        "@weekly" => Some("0 0 * * 0"),
+        "@daily" | "@midnight" => Some("0 0 * * *"),
+        "@hourly" => Some("0 * * * *"),
+        _ => None,
+    }
+}
+
+fn parse_value(value: &str, min: u32, names: &[&str]) -> Result<u32, String> {
+    if let Ok(number) = value.parse() {
+        return Ok(number);
+    }
+    let lower = value.to_lowercase();
+    names
+        .iter()
+        .position(|n| *n == lower)
+        .map(|i| i as u32 + min)
+        .ok_or_else(|| format!("'{}' is not a number", value))
+}
+
+/// The bit set of values a field such as "1-5", "*/15" or "mon,wed" matches
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut bits = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(format!("step '{}' is not a positive number", step)),
+            },
+            None => (item, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, min, names)?,
+                parse_value(end, min, names)?,
+            )
+        } else {
+            let start = parse_value(range, min, names)?;
+            // "5/10" means from 5 to the end in steps of 10
+            (start, if item.contains('/') { max } else { start })
+        };
+        if start < min || end > max {
+            return Err(format!("'{}' is outside {}-{}", item, min, max));
+        }
+        if start > end {
+            return Err(format!("range '{}' runs backwards", range));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+/// Check a schedule, explaining what is wrong with it
+pub fn validate(expr: &str) -> Result<(), String> {
+    if expr.trim() == "@reboot" {
+        return Ok(());
+    }
+    Schedule::build(expr).map(|_| ())
+}
+
+impl Schedule {
+    /// Parse five fields or an @keyword
+    pub fn parse(expr: &str) -> Option<Self> {
+        Self::build(expr).ok()
+    }
+
+    fn build(expr: &str) -> Result<Self, String> {
+        let expr = expr.trim();
+        let expr = if expr.starts_with('@') {
+            expand_keyword(expr).ok_or_else(|| format!("Unknown keyword {}", expr))?
+        } else {
+            expr
+        };
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "Expected five fields or an @keyword, got {} fields",
+                fields.len()
+            ));
+        };
+        let field = |name: &str, value: &str, min: u32, max: u32, names: &[&str]| {
+            parse_field(value, min, max, names).map_err(|e| format!("{}: {}", name, e))
+        };
+        let mut weekdays = field("weekday", weekday, 0, 7, &WEEKDAYS)?;
+        // 7 is another name for Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(Self {
+            minutes: field("minute", minute, 0, 59, &[])?,
+            hours: field("hour", hour, 0, 23, &[])?,
+            days: field("day of month", day, 1, 31, &[])?,
+            months: field("month", month, 1, 12, &MONTHS)?,
+            weekdays,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    fn matches_day(&self, time: &NaiveDateTime) -> bool {
+        if self.months & (1 << time.month()) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << time.day()) != 0;
+        let weekday = self.weekdays & (1 << time.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+
+    /// The first minute after `after` the schedule fires in
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let midnight = start.date().and_hms_opt(0, 0, 0)?;
+        for offset in 0..SEARCH_DAYS {
+            let day = midnight + Duration::days(offset);
+            if !self.matches_day(&day) {
+                continue;
+            }
+            for hour in (0..24).filter(|h| self.hours & (1 << h) != 0) {
+                for minute in (0..60).filter(|m| self.minutes & (1 << m) != 0) {
+                    let time = day.with_hour(hour)?.with_minute(minute)?;
+                    if time >= start {
+                        return Some(time);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// "a, b and c"
+fn join_and(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [one] => one.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// A day, month or weekday field in words: "Monday to Friday", "1 and 15"
+fn describe_list(field: &str, min: u32, names: &[&str], full_names: &[&str]) -> String {
+    let name = |value: &str| match parse_value(value, min, names) {
+        Ok(n) if !full_names.is_empty() => full_names
+            .get((n - min) as usize)
+            .map_or(value.to_string(), |s| s.to_string()),
+        _ => value.to_string(),
+    };
+    let items: Vec<String> = field
+        .split(',')
+        .map(|item| {
+            if item.contains('/') {
+                return item.to_string();
+            }
+            match item.split_once('-') {
+                Some((start, end)) => format!("{} to {}", name(start), name(end)),
+                None => name(item),
+            }
+        })
+        .collect();
+    join_and(&items)
+}
+
+fn is_number(field: &str) -> bool {
+    !field.is_empty() && field.chars().all(|c| c.is_ascii_digit())
+}
+
+/// The time of day part: "at 03:00", "every 15 minutes", "every hour at :47".
+/// The flag tells whether it names specific times rather than a repeat.
+fn describe_time(minute: &str, hour: &str) -> (String, bool) {
+    let hours: Vec<&str> = hour.split(',').collect();
+    if is_number(minute) && hours.iter().all(|h| is_number(h)) {
+        let m: u32 = minute.parse().unwrap_or(0);
+        let times: Vec<String> = hours
+            .iter()
+            .map(|h| format!("{:02}:{:02}", h.parse::<u32>().unwrap_or(0), m))
+            .collect();
+        return (format!("at {}", join_and(&times)), true);
+    }
+    let text = match (minute, hour) {
+        ("*", "*") => "every minute".to_string(),
+        (m, "*") if m.starts_with("*/") => format!("every {} minutes", &m[2..]),
+        (m, "*") if is_number(m) => format!("every hour at :{:0>2}", m),
+        (m, h) if is_number(m) && h.starts_with("*/") => {
+            format!("every {} hours at :{:0>2}", &h[2..], m)
+        }
+        (m, h)
+            if is_number(m)
+                && h.split_once('-')
+                    .is_some_and(|(a, b)| is_number(a) && is_number(b)) =>
+        {
+            let (start, end) = h.split_once('-').unwrap_or_default();
+            format!(
+                "every hour from {:0>2}:{:0>2} to {:0>2}:{:0>2}",
+                start, m, end, m
+            )
+        }
+        (m, h) => format!("at minute {} past hour {}", m, h),
+    };
+    (text, false)
+}
+
+/// A schedule in plain English, e.g. "every Monday at 03:00"
+pub fn describe(expr: &str) -> Option<String> {
+    let expr = expr.trim();
+    match expr {
+        "@reboot" => return Some("at boot".to_string()),
+        "@hourly" => return Some("every hour".to_string()),
+        _ => {}
+    }
+    let five = if expr.starts_with('@') {
+        expand_keyword(expr)?
+    } else {
+        expr
+    };
+    validate(five).ok()?;
+    let fields: Vec<&str> = five.split_whitespace().collect();
+    let [minute, hour, day, month, weekday] = fields[..] else {
+        return None;
+    };
+
+    let (time, specific) = describe_time(minute, hour);
+    let days = (day != "*").then(|| format!("day {}", describe_list(day, 1, &[], &[])));
+    let weekdays = (weekday != "*").then(|| describe_list(weekday, 0, &WEEKDAYS, &WEEKDAY_NAMES));
+    let months = (month != "*").then(|| describe_list(month, 1, &MONTHS, &MONTH_NAMES));
+
+    let on = match (days, weekdays) {
+        (Some(d), Some(w)) => Some(format!("on {} or {}", d, w)),
+        (Some(d), None) => Some(format!("on {}", d)),
+        (None, Some(w)) if specific && months.is_none() => Some(format!("every {}", w)),
+        (None, Some(w)) => Some(format!("on {}", w)),
+        (None, None) => None,
+    };
+    let mut date = match (on, &months) {
+        (Some(on), Some(m)) => format!("{} in {}", on, m),
+        (Some(on), None) if on.starts_with("on day") => format!("{} of every month", on),
+        (Some(on), None) => on,
+        (None, Some(m)) => format!("every day in {}", m),
+        (None, None) if specific => "every day".to_string(),
+        (None, None) => return Some(time),
+    };
+    if specific {
+        date = format!("{} {}", date, time);
+        return Some(date);
+    }
+    Some(format!("{} {}", time, date))
+}
+
+/// A wait in whole minutes as "in 2h 14m"
+pub fn format_until(minutes: i64) -> String {
+    let (days, hours, minutes) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+    if days > 0 {
+        format!("in {}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("in {}h {}m", hours, minutes)
+    } else {
+        format!("in {}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_describe() {
+        let text = |expr: &str| describe(expr).unwrap();
+        assert_eq!(text("0 3 * * 1"), "every Monday at 03:00");
+        assert_eq!(text("40 4 * * *"), "every day at 04:40");
+        assert_eq!(text("*/15 * * * *"), "every 15 minutes");
+        assert_eq!(text("47 * * * *"), "every hour at :47");
+        assert_eq!(
+            text("0 9-17 * * mon-fri"),
+            "every hour from 09:00 to 17:00 on Monday to Friday"
+        );
+        assert_eq!(text("20 4 1 * *"), "on day 1 of every month at 04:20");
+        assert_eq!(
+            text("0 0,12 1,15 jan *"),
+            "on day 1 and 15 in January at 00:00 and 12:00"
+        );
+        assert_eq!(text("@daily"), "every day at 00:00");
+        assert_eq!(text("@reboot"), "at boot");
+        assert_eq!(describe("0 3 * *"), None);
+    }
+
+    #[test]
+    fn test_next_after() {
+        let now = at("2024-06-14 10:30:15"); // a Friday
+        let next = |expr: &str| Schedule::parse(expr).and_then(|s| s.next_after(now));
+
+        assert_eq!(next("*/15 * * * *"), Some(at("2024-06-14 10:45:00")));
+        assert_eq!(next("40 4 * * *"), Some(at("2024-06-15 04:40:00")));
+        assert_eq!(next("30 4 * * 0"), Some(at("2024-06-16 04:30:00")));
+        assert_eq!(next("0 9 * * mon-fri"), Some(at("2024-06-17 09:00:00")));
+        assert_eq!(next("@monthly"), Some(at("2024-07-01 00:00:00")));
+        assert_eq!(next("0 0 29 2 *"), Some(at("2028-02-29 00:00:00")));
+        // Day of month or weekday: the 20th, or any Saturday
+        assert_eq!(next("0 12 20 * 6"), Some(at("2024-06-15 12:00:00")));
+
+        assert_eq!(next("@reboot"), None);
+        assert_eq!(next("61 * * * *"), None);
+        assert_eq!(next("*/0 * * * *"), None);
+        assert_eq!(next("* * *"), None);
+
+        assert_eq!(validate("@reboot"), Ok(()));
+        assert_eq!(
+            validate("61 * * * *"),
+            Err("minute: '61' is outside 0-59".to_string())
+        );
+        assert_eq!(
+            validate("0 3 * * 5-1"),
+            Err("weekday: range '5-1' runs backwards".to_string())
+        );
+
+        assert_eq!(format_until(134), "in 2h 14m");
+        assert_eq!(format_until(3 * 1440 + 65), "in 3d 1h");
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::NaiveDateTime;
+use regex::Regex;
+
+use crate::utils::logfile;
+
+/// Where crond logs, in order of preference: dcron writes /var/log/cron on
+/// Slackware, other crons go through syslog
+const LOG_FILES: [&str; 3] = ["/var/log/cron", "/var/log/syslog", "/var/log/messages"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogEvent {
+    Started,
+    /// A non-zero exit status
+    Failed(i32),
+}
+
+/// A line of the cron log about a job
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub time: NaiveDateTime,
+    pub user: String,
+    pub command: String,
+    pub event: LogEvent,
+}
+
+/// What the log says about one job
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    pub last_run: Option<NaiveDateTime>,
+    pub failures: usize,
+    /// Time and exit status of the latest failure
+    pub last_failure: Option<(NaiveDateTime, i32)>,
+}
+
+/// Job lines of a cron log. Understands dcron ("USER root pid 12 cmd ...",
+/// "exit status 1 from user root ...") and Vixie cron / cronie
+/// ("(root) CMD (...)").
+pub fn parse(lines: &[String]) -> Vec<LogEntry> {
+    let started = Regex::new(r"USER (\S+) pid \d+ cmd (.+)$").expect("valid regex");
+    let failed = Regex::new(r"exit status (\d+) from user (\S+) (.+)$").expect("valid regex");
+    let vixie = Regex::new(r"\((\S+)\) CMD \((.*)\)$").expect("valid regex");
+    lines
+        .iter()
+        .filter(|line| line.contains("crond") || line.contains("CRON"))
+        .filter_map(|line| {
+            let time = logfile::parse_timestamp(line)?;
+            let (user, command, event) = if let Some(c) = started.captures(line) {
+                (c[1].to_string(), c[2].to_string(), LogEvent::Started)
+            } else if let Some(c) = failed.captures(line) {
+                let status = c[1].parse().ok()?;
+                (c[2].to_string(), c[3].to_string(), LogEvent::Failed(status))
+            } else if let Some(c) = vixie.captures(line) {
+                (c[1].to_string(), c[2].to_string(), LogEvent::Started)
+            } else {
+                return None;
+            };
+            Some(LogEntry {
+                time,
+                user,
+                command: command.trim().to_string(),
+                event,
+            })
+        })
+        .collect()
+}
+
+/// Per (user, command) history, from entries in log order
+pub fn summarize(entries: &[LogEntry]) -> HashMap<(String, String), History> {
+    let mut history: HashMap<(String, String), History> = HashMap::new();
+    for entry in entries {
+        let job = history
+            .entry((entry.user.clone(), entry.command.clone()))
+            .or_default();
+        match entry.event {
+            LogEvent::Started => job.last_run = Some(entry.time),
+            LogEvent::Failed(status) => {
+                job.failures += 1;
+                job.last_failure = Some((entry.time, status));
+            }
+        }
+    }
+    history
+}
+
+/// History of every job in the cron log and its latest rotation
+pub fn load() -> HashMap<(String, String), History> {
+    let Some(path) = LOG_FILES.iter().map(Path::new).find(|p| p.exists()) else {
+        return HashMap::new();
+    };
+    let mut lines = Vec::new();
+    if let Some(rotated) = logfile::rotations(path).pop() {
+        lines.extend(logfile::read_lines(&rotated).unwrap_or_default());
+    }
+    lines.extend(logfile::read_lines(path).unwrap_or_default());
+    summarize(&parse(&lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let lines: Vec<String> = [
+            "Oct 13 04:40:01 darkstar crond[1042]: USER root pid 8812 cmd \
+             /usr/bin/run-parts /etc/cron.daily 1> /dev/null",
+            "Oct 14 03:00:01 darkstar crond[1042]: USER alice pid 9001 cmd /home/alice/backup.sh",
+            "Oct 14 03:00:09 darkstar crond[1042]: exit status 2 from user alice \
+             /home/alice/backup.sh",
+            "Oct 14 04:40:01 darkstar crond[1042]: USER root pid 9120 cmd \
+             /usr/bin/run-parts /etc/cron.daily 1> /dev/null",
+            "Oct 14 05:00:01 otherhost CROND[311]: (bob) CMD (/usr/bin/fetchmail -s)",
+            "Oct 14 05:01:00 darkstar sshd[12]: Accepted publickey for root",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let entries = parse(&lines);
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[2].event, LogEvent::Failed(2));
+        assert_eq!(entries[4].user, "bob");
+
+        let history = summarize(&entries);
+        let daily = &history[&(
+            "root".to_string(),
+            "/usr/bin/run-parts /etc/cron.daily 1> /dev/null".to_string(),
+        )];
+        assert_eq!(daily.failures, 0);
+        assert_eq!(daily.last_run, Some(entries[3].time));
+        let backup = &history[&("alice".to_string(), "/home/alice/backup.sh".to_string())];
+        assert_eq!(backup.failures, 1);
+        assert_eq!(backup.last_failure, Some((entries[2].time, 2)));
+    }
+}
@@ -0,0 +1,95 @@
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use crate::slackware::audit;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+const LEASE_DIR: &str = "/var/lib/dhcpcd";
+
+/// The DHCP lease dhcpcd holds for an interface
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Lease {
+    pub address: Option<String>,
+    pub server: Option<String>,
+    pub routers: Vec<String>,
+    pub dns: Vec<String>,
+    pub domain: Option<String>,
+    pub lease_time: Option<Duration>,
+    /// When the lease was last written, from the lease file's mtime
+    pub obtained: Option<SystemTime>,
+}
+
+impl Lease {
+    /// Parse the shell variables printed by `dhcpcd -U <iface>`
+    pub fn parse(output: &str) -> Self {
+        let mut lease = Lease::default();
+        for line in output.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('\'').trim_matches('"');
+            let list = || value.split_whitespace().map(String::from).collect();
+            match key.trim() {
+                "ip_address" => lease.address = Some(value.to_string()),
+                "dhcp_server_identifier" => lease.server = Some(value.to_string()),
+                "routers" => lease.routers = list(),
+                "domain_name_servers" => lease.dns = list(),
+                "domain_name" => lease.domain = Some(value.to_string()),
+                "dhcp_lease_time" => {
+                    lease.lease_time = value.parse().ok().map(Duration::from_secs);
+                }
+                _ => {}
+            }
+        }
+        lease
+    }
+
+    /// Time left before the lease runs out
+    pub fn remaining(&self) -> Option<Duration> {
+        let elapsed = self.obtained?.elapsed().ok()?;
+        Some(self.lease_time?.saturating_sub(elapsed))
+    }
+}
+
+/// Lease dhcpcd currently holds for `iface`, if any
+pub fn lease(iface: &str) -> Option<Lease> {
+    let output = root::command("dhcpcd").args(["-U", iface]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut lease = Lease::parse(&String::from_utf8_lossy(&output.stdout));
+    lease.address.as_ref()?;
+    // dhcpcd 9 names the file after the interface, older versions prefix it
+    lease.obtained = [
+        format!("{}/{}.lease", LEASE_DIR, iface),
+        format!("{}/dhcpcd-{}.lease", LEASE_DIR, iface),
+    ]
+    .iter()
+    .find_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok());
+    Some(lease)
+}
+
+/// Ask the running dhcpcd to renew the lease of `iface`
+pub fn renew(iface: &str) -> Result<()> {
+    let output = audit::output(root::command("dhcpcd").args(["-N", iface]))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "dhcpcd -N {}: {}",
+            iface,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Compact duration such as "3d 4h" or "12m"
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        s if s >= 86400 => format!("{}d {}h", s / 86400, s % 86400 / 3600),
+        s if s >= 3600 => format!("{}h {}m", s / 3600, s % 3600 / 60),
+        s if s >= 60 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const DISKSTATS: &str = "/proc/diskstats";
+
+/// /proc/diskstats counts in 512-byte sectors whatever the real sector size
+const SECTOR_BYTES: u64 = 512;
+
+/// Cumulative I/O counters of one device since boot
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Counters {
+    pub reads: u64,
+    pub sectors_read: u64,
+    pub writes: u64,
+    pub sectors_written: u64,
+    /// Milliseconds the device had I/O in flight
+    pub busy_ms: u64,
+}
+
+/// Throughput of a device between two samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rate {
+    pub device: String,
+    pub read_bytes: f64,
+    pub write_bytes: f64,
+    pub reads: f64,
+    pub writes: f64,
+    /// Share of the interval the device was busy, like iostat's %util
+    pub util: f64,
+}
+
+/// Counters per device in /proc/diskstats content, in file order
+pub fn parse(content: &str) -> Vec<(String, Counters)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 13 {
+                return None;
+            }
+            let n = |i: usize| fields[i].parse::<u64>().unwrap_or(0);
+            Some((
+                fields[2].to_string(),
+                Counters {
+                    reads: n(3),
+                    sectors_read: n(5),
+                    writes: n(7),
+                    sectors_written: n(9),
+                    busy_ms: n(12),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Counters of the whole disks, md arrays and device-mapper volumes;
+/// partitions, loop devices and ramdisks are left out
+pub fn sample() -> Vec<(String, Counters)> {
+    let content = fs::read_to_string(DISKSTATS).unwrap_or_default();
+    parse(&content)
+        .into_iter()
+        .filter(|(name, _)| {
+            !name.starts_with("loop")
+                && !name.starts_with("ram")
+                && Path::new("/sys/block").join(name).exists()
+        })
+        .collect()
+}
+
+/// Per-second rates between two samples taken `elapsed` apart
+pub fn rates(
+    before: &[(String, Counters)],
+    after: &[(String, Counters)],
+    elapsed: Duration,
+) -> Vec<Rate> {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return Vec::new();
+    }
+    after
+        .iter()
+        .filter_map(|(device, now)| {
+            let (_, then) = before.iter().find(|(d, _)| d == device)?;
+            // Counters go backwards when a device is replaced between samples
+            let delta = |a: u64, b: u64| a.saturating_sub(b) as f64;
+            Some(Rate {
+                device: device.clone(),
+                read_bytes: delta(now.sectors_read, then.sectors_read) * SECTOR_BYTES as f64 / secs,
+                write_bytes: delta(now.sectors_written, then.sectors_written) * SECTOR_BYTES as f64
+                    / secs,
+                reads: delta(now.reads, then.reads) / secs,
+                writes: delta(now.writes, then.writes) / secs,
+                util: (delta(now.busy_ms, then.busy_ms) / 10.0 / secs).min(100.0),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates() {
+        let before = parse(
+            "   8       0 sda 1000 10 80000 500 2000 20 160000 900 0 1500 1400 0 0 0 0\n\
+             \x20  8       1 sda1 900 10 70000 450 1900 20 150000 850 0 1400 1300\n\
+             \x20  9       0 md0 50 0 400 0 0 0 0 0 0 0 0\n",
+        );
+        assert_eq!(before.len(), 3);
+        assert_eq!(before[1].0, "sda1");
+        let after = parse(
+            "   8       0 sda 1100 10 82048 600 2400 20 180480 1200 2 2000 1900 0 0 0 0\n\
+             \x20  8      16 sdb 10 0 80 5 0 0 0 0 0 5 5\n",
+        );
+        let rates = rates(&before, &after, Duration::from_secs(2));
+        assert_eq!(rates.len(), 1);
+        let sda = &rates[0];
+        assert_eq!(sda.read_bytes, 2048.0 * 512.0 / 2.0);
+        assert_eq!(sda.writes, 200.0);
+        assert_eq!(sda.util, 25.0);
+    }
+}
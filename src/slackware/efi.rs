@@ -0,0 +1,316 @@
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use crate::slackware::audit;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// Where Slackware mounts the EFI system partition
+pub const ESP_MOUNT: &str = "/boot/efi";
+
+/// Directory on the ESP eliloconfig installs to
+const SLACKWARE_DIR: &str = "EFI/Slackware";
+
+/// A Boot#### variable as shown by `efibootmgr -v`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootEntry {
+    /// Hex number, e.g. "0001"
+    pub number: String,
+    pub label: String,
+    pub active: bool,
+    /// Partition GUID of the `HD(...)` device path
+    pub partition: Option<String>,
+    /// Loader path on that partition, e.g. "\EFI\Slackware\elilo.efi"
+    pub loader: Option<String>,
+}
+
+/// The firmware boot manager state
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BootManager {
+    pub current: Option<String>,
+    pub order: Vec<String>,
+    pub entries: Vec<BootEntry>,
+}
+
+impl BootManager {
+    pub fn parse(output: &str) -> Self {
+        let mut manager = Self::default();
+        for line in output.lines() {
+            if let Some(current) = line.strip_prefix("BootCurrent:") {
+                manager.current = Some(current.trim().to_string());
+            } else if let Some(order) = line.strip_prefix("BootOrder:") {
+                manager.order = order
+                    .trim()
+                    .split(',')
+                    .map(|n| n.trim().to_string())
+                    .collect();
+            } else if let Some(entry) = parse_entry(line) {
+                manager.entries.push(entry);
+            }
+        }
+        manager
+    }
+
+    pub fn entry(&self, number: &str) -> Option<&BootEntry> {
+        self.entries.iter().find(|e| e.number == number)
+    }
+}
+
+fn parse_entry(line: &str) -> Option<BootEntry> {
+    let rest = line.strip_prefix("Boot")?;
+    let number = rest
+        .get(..4)
+        .filter(|n| n.chars().all(|c| c.is_ascii_hexdigit()))?;
+    let rest = &rest[4..];
+    let (active, rest) = match rest.strip_prefix('*') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    // The label and device path are separated by a tab
+    let (label, path) = rest.split_once('\t').unwrap_or((rest, ""));
+    let inner = |prefix: &str| {
+        let start = path.find(prefix)? + prefix.len();
+        let end = path[start..].find(')')? + start;
+        Some(&path[start..end])
+    };
+    Some(BootEntry {
+        number: number.to_string(),
+        label: label.trim().to_string(),
+        active,
+        partition: inner("HD(")
+            .and_then(|hd| hd.split(',').nth(2))
+            .map(|guid| guid.to_lowercase()),
+        loader: inner("File(").map(String::from),
+    })
+}
+
+pub fn is_efi() -> bool {
+    Path::new("/sys/firmware/efi").is_dir()
+}
+
+fn run(cmd: &str, args: &[&str]) -> Result<String> {
+    check(cmd, root::command(cmd).args(args).output()?)
+}
+
+/// `run` for commands that change something, recorded in the audit log
+fn change(cmd: &str, args: &[&str]) -> Result<()> {
+    check(cmd, audit::output(root::command(cmd).args(args))?).map(|_| ())
+}
+
+fn check(cmd: &str, output: Output) -> Result<String> {
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub fn list() -> Result<BootManager> {
+    Ok(BootManager::parse(&run("efibootmgr", &["-v"])?))
+}
+
+/// Write BootOrder
+pub fn set_order(order: &[String]) -> Result<()> {
+    change("efibootmgr", &["-o", &order.join(",")])
+}
+
+/// Delete a Boot#### variable
+pub fn delete(number: &str) -> Result<()> {
+    change("efibootmgr", &["-b", number, "-B"])
+}
+
+/// The mounted ESP as (device, partition GUID)
+fn esp() -> Option<(String, String)> {
+    let out = run("findmnt", &["-n", "-o", "SOURCE,PARTUUID", ESP_MOUNT]).ok()?;
+    let mut words = out.split_whitespace();
+    Some((words.next()?.to_string(), words.next()?.to_lowercase()))
+}
+
+/// Whether an entry points at a loader missing from the mounted ESP.
+/// Entries for other partitions (another disk, another OS) can't be
+/// checked and are never stale.
+pub fn is_stale(entry: &BootEntry) -> bool {
+    let (Some(partition), Some(loader)) = (&entry.partition, &entry.loader) else {
+        return false;
+    };
+    match esp() {
+        Some((_, guid)) if guid == *partition => {
+            let relative = loader.trim_start_matches('\\').replace('\\', "/");
+            !Path::new(ESP_MOUNT).join(relative).exists()
+        }
+        _ => false,
+    }
+}
+
+/// Split a partition device into its disk and partition number:
+/// /dev/sda1 -> (/dev/sda, 1), /dev/nvme0n1p2 -> (/dev/nvme0n1, 2)
+pub fn split_partition(device: &str) -> Option<(String, String)> {
+    let disk = device.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = &device[disk.len()..];
+    if number.is_empty() || disk.is_empty() {
+        return None;
+    }
+    let disk = match disk.strip_suffix('p') {
+        Some(base) if base.ends_with(|c: char| c.is_ascii_digit()) => base,
+        _ => disk,
+    };
+    Some((disk.to_string(), number.to_string()))
+}
+
+/// A boot entry to create, and the files to put on the ESP first
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryPlan {
+    pub label: String,
+    pub disk: String,
+    pub partition: String,
+    /// Loader path on the ESP, in EFI notation
+    pub loader: String,
+    /// Kernel command line passed to an EFI stub kernel
+    pub args: Option<String>,
+    pub copies: Vec<(PathBuf, PathBuf)>,
+}
+
+impl EntryPlan {
+    fn on_esp() -> Result<(String, String)> {
+        let (device, _) = esp().ok_or_else(|| {
+            AppError::FileOperation(format!("No EFI system partition mounted on {}", ESP_MOUNT))
+        })?;
+        split_partition(&device)
+            .ok_or_else(|| AppError::FileOperation(format!("Can't tell the disk of {}", device)))
+    }
+
+    /// Boot /boot/vmlinuz-`version` directly through its EFI stub, copying
+    /// it (and its initrd) to the ESP
+    pub fn for_kernel(version: &str) -> Result<Self> {
+        let (disk, partition) = Self::on_esp()?;
+        let kernel = PathBuf::from(format!("/boot/vmlinuz-{}", version));
+        if !kernel.is_file() {
+            return Err(AppError::FileOperation(format!(
+                "{} not found",
+                kernel.display()
+            )));
+        }
+        let root = run("findmnt", &["-n", "-o", "SOURCE", "/"])?
+            .trim()
+            .to_string();
+        let esp_dir = Path::new(ESP_MOUNT).join(SLACKWARE_DIR);
+        let efi_path = |file: &str| format!("\\{}\\{}", SLACKWARE_DIR.replace('/', "\\"), file);
+
+        let image = format!("vmlinuz-{}.efi", version);
+        let mut copies = vec![(kernel, esp_dir.join(&image))];
+        let mut args = format!("root={} ro", root);
+        let initrd = PathBuf::from(format!("/boot/initrd-{}.gz", version));
+        if initrd.is_file() {
+            let file = format!("initrd-{}.gz", version);
+            args.push_str(&format!(" initrd={}", efi_path(&file)));
+            copies.push((initrd, esp_dir.join(file)));
+        }
+        Ok(Self {
+            label: format!("Slackware {}", version),
+            disk,
+            partition,
+            loader: efi_path(&image),
+            args: Some(args),
+            copies,
+        })
+    }
+
+    /// Boot elilo as installed by eliloconfig
+    pub fn for_elilo() -> Result<Self> {
+        let (disk, partition) = Self::on_esp()?;
+        let elilo = Path::new(ESP_MOUNT).join(SLACKWARE_DIR).join("elilo.efi");
+        if !elilo.is_file() {
+            return Err(AppError::FileOperation(format!(
+                "{} not found; run eliloconfig first",
+                elilo.display()
+            )));
+        }
+        Ok(Self {
+            label: "Slackware".to_string(),
+            disk,
+            partition,
+            loader: "\\EFI\\Slackware\\elilo.efi".to_string(),
+            args: None,
+            copies: Vec::new(),
+        })
+    }
+
+    pub fn command(&self) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "efibootmgr",
+            "-c",
+            "-d",
+            &self.disk,
+            "-p",
+            &self.partition,
+            "-L",
+            &self.label,
+            "-l",
+            &self.loader,
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        if let Some(kernel_args) = &self.args {
+            args.extend(["-u".to_string(), kernel_args.clone()]);
+        }
+        args
+    }
+
+    /// Copy the files and create the entry. efibootmgr puts new entries
+    /// first in BootOrder.
+    pub fn create(&self) -> Result<()> {
+        for (from, to) in &self.copies {
+            if let Some(dir) = to.parent() {
+                root::create_dir_all(dir)?;
+            }
+            root::copy(from, to)?;
+        }
+        let command = self.command();
+        let args: Vec<&str> = command[1..].iter().map(String::as_str).collect();
+        change(&command[0], &args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let manager = BootManager::parse(
+            "BootCurrent: 0001\nTimeout: 1 seconds\nBootOrder: 0001,0000,0003\n\
+             Boot0000* Windows Boot Manager\tHD(1,GPT,1F3E-AB,0x800,0x32000)/\
+             File(\\EFI\\Microsoft\\Boot\\bootmgfw.efi)\n\
+             Boot0001* Slackware\tHD(1,GPT,1F3E-AB,0x800,0x32000)/\
+             File(\\EFI\\Slackware\\elilo.efi)\n\
+             Boot0003  UEFI: PXE IPv4\tPciRoot(0x0)/Pci(0x1c,0x0)/MAC(001122334455,0)\n",
+        );
+        assert_eq!(manager.current.as_deref(), Some("0001"));
+        assert_eq!(manager.order, vec!["0001", "0000", "0003"]);
+        assert_eq!(manager.entries.len(), 3);
+        let slackware = manager.entry("0001").unwrap();
+        assert_eq!(slackware.label, "Slackware");
+        assert_eq!(slackware.partition.as_deref(), Some("1f3e-ab"));
+        assert_eq!(
+            slackware.loader.as_deref(),
+            Some("\\EFI\\Slackware\\elilo.efi")
+        );
+        let pxe = manager.entry("0003").unwrap();
+        assert!(!pxe.active);
+        assert_eq!(pxe.loader, None);
+
+        assert_eq!(
+            split_partition("/dev/nvme0n1p2"),
+            Some(("/dev/nvme0n1".to_string(), "2".to_string()))
+        );
+        assert_eq!(
+            split_partition("/dev/sda1"),
+            Some(("/dev/sda".to_string(), "1".to_string()))
+        );
+        assert_eq!(split_partition("/dev/sda"), None);
+    }
+}
@@ -0,0 +1,211 @@
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use crate::slackware::audit;
+use crate::slackware::config;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// Started by rc.inet2 at boot when executable
+pub const RC_FIREWALL: &str = "/etc/rc.d/rc.firewall";
+
+/// Marks an rc.firewall written by this tool so it can be regenerated
+const PRESET_MARKER: &str = "# Generated by slackware-cli-manager";
+
+/// Services offered in the port list: (name, port, protocol)
+pub const COMMON_PORTS: &[(&str, u16, &str)] = &[
+    ("SSH", 22, "tcp"),
+    ("HTTP", 80, "tcp"),
+    ("HTTPS", 443, "tcp"),
+    ("DNS", 53, "udp"),
+    ("DNS (TCP)", 53, "tcp"),
+    ("SMTP", 25, "tcp"),
+    ("IMAPS", 993, "tcp"),
+    ("Samba", 445, "tcp"),
+    ("NFS", 2049, "tcp"),
+    ("CUPS", 631, "tcp"),
+    ("rsync", 873, "tcp"),
+    ("NTP", 123, "udp"),
+];
+
+/// A port accepted by the preset firewall
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenPort {
+    pub port: u16,
+    pub proto: String,
+}
+
+impl OpenPort {
+    pub fn new(port: u16, proto: &str) -> Self {
+        Self {
+            port,
+            proto: proto.to_string(),
+        }
+    }
+
+    /// Parse "8080/tcp" (protocol defaults to tcp)
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (port, proto) = spec.trim().split_once('/').unwrap_or((spec.trim(), "tcp"));
+        let proto = proto.to_lowercase();
+        if proto != "tcp" && proto != "udp" {
+            return None;
+        }
+        Some(Self::new(port.parse().ok().filter(|p| *p > 0)?, &proto))
+    }
+
+    pub fn display(&self) -> String {
+        format!("{}/{}", self.port, self.proto)
+    }
+}
+
+/// Dump the active ruleset, returning the backend name and its lines
+pub fn current_rules() -> (&'static str, Vec<String>) {
+    let run = |cmd: &str, args: &[&str]| {
+        root::command(cmd)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+    };
+
+    let iptables = run("iptables", &["-S"]);
+    let nft = run("nft", &["list", "ruleset"]).filter(|out| !out.trim().is_empty());
+    match (iptables, nft) {
+        // iptables-nft shows the same rules through both tools
+        (Some(rules), None) => ("iptables", rules.lines().map(String::from).collect()),
+        (_, Some(rules)) => ("nftables", rules.lines().map(String::from).collect()),
+        (None, None) => (
+            "none",
+            vec!["Neither iptables nor nft is available".to_string()],
+        ),
+    }
+}
+
+/// Whether rc.firewall is present and will run at boot
+pub fn is_enabled() -> bool {
+    fs::metadata(RC_FIREWALL)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Whether rc.firewall was generated by us (and is safe to overwrite)
+pub fn is_preset() -> bool {
+    fs::read_to_string(RC_FIREWALL)
+        .map(|c| c.contains(PRESET_MARKER))
+        .unwrap_or(false)
+}
+
+/// Ports opened by the generated rc.firewall
+pub fn open_ports() -> Vec<OpenPort> {
+    let content = fs::read_to_string(RC_FIREWALL).unwrap_or_default();
+    let mut ports = Vec::new();
+    for (var, proto) in [("TCP_PORTS", "tcp"), ("UDP_PORTS", "udp")] {
+        let prefix = format!("{}=", var);
+        if let Some(line) = content.lines().find(|l| l.starts_with(&prefix)) {
+            ports.extend(
+                line[prefix.len()..]
+                    .trim_matches('"')
+                    .split_whitespace()
+                    .filter_map(|p| p.parse().ok())
+                    .map(|p| OpenPort::new(p, proto)),
+            );
+        }
+    }
+    ports
+}
+
+fn preset_script(ports: &[OpenPort]) -> String {
+    let list = |proto: &str| {
+        ports
+            .iter()
+            .filter(|p| p.proto == proto)
+            .map(|p| p.port.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    format!(
+        r#"#!/bin/sh
+{marker}
+# Basic stateful firewall: drop inbound traffic except the ports below.
+# Edit the port lists from the Network tab; manual changes are overwritten.
+
+TCP_PORTS="{tcp}"
+UDP_PORTS="{udp}"
+
+firewall_start() {{
+  iptables -F INPUT
+  iptables -P INPUT DROP
+  iptables -P FORWARD DROP
+  iptables -P OUTPUT ACCEPT
+  iptables -A INPUT -i lo -j ACCEPT
+  iptables -A INPUT -m conntrack --ctstate ESTABLISHED,RELATED -j ACCEPT
+  iptables -A INPUT -m conntrack --ctstate INVALID -j DROP
+  iptables -A INPUT -p icmp -j ACCEPT
+  for port in $TCP_PORTS; do
+    iptables -A INPUT -p tcp --dport $port -j ACCEPT
+  done
+  for port in $UDP_PORTS; do
+    iptables -A INPUT -p udp --dport $port -j ACCEPT
+  done
+}}
+
+firewall_stop() {{
+  iptables -P INPUT ACCEPT
+  iptables -P FORWARD ACCEPT
+  iptables -P OUTPUT ACCEPT
+  iptables -F INPUT
+}}
+
+case "$1" in
+  start) firewall_start ;;
+  stop) firewall_stop ;;
+  restart) firewall_stop; firewall_start ;;
+  status) iptables -L INPUT -n -v ;;
+  *) echo "usage: $0 start|stop|restart|status" ;;
+esac
+"#,
+        marker = PRESET_MARKER,
+        tcp = list("tcp"),
+        udp = list("udp"),
+    )
+}
+
+fn run_firewall(action: &str) -> Result<()> {
+    let output = audit::output(root::command("sh").args([RC_FIREWALL, action]))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "rc.firewall {}: {}",
+            action,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Write the preset rc.firewall with the given ports, enable it and load
+/// the rules. Refuses to replace a hand-written rc.firewall.
+///
+/// Returns the backup of the previous script, if there was one.
+pub fn install_preset(ports: &[OpenPort]) -> Result<Option<PathBuf>> {
+    let path = Path::new(RC_FIREWALL);
+    if path.exists() && !is_preset() {
+        return Err(AppError::Config(format!(
+            "{} was not generated by this tool; edit it by hand or remove it first",
+            RC_FIREWALL
+        )));
+    }
+    let existed = path.exists();
+    let backup = config::write_with_backup(path, &preset_script(ports))?;
+    root::set_mode(path, 0o755)?;
+    run_firewall("restart")?;
+    Ok(existed.then_some(backup))
+}
+
+/// Unload the rules and stop rc.inet2 from starting the firewall at boot
+pub fn disable() -> Result<()> {
+    run_firewall("stop")?;
+    root::set_mode(Path::new(RC_FIREWALL), 0o644)?;
+    Ok(())
+}
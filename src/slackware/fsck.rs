@@ -0,0 +1,95 @@
+use std::path::Path;
+
+use tokio::sync::mpsc;
+
+use crate::slackware::commands;
+use crate::slackware::cron::RunEvent;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// rc.S passes -f to fsck for every filesystem in fstab while this exists,
+/// and removes it afterwards
+const FORCEFSCK: &str = "/etc/forcefsck";
+
+/// The checker for a filesystem type and its arguments: with `repair`
+/// it fixes what it finds without asking, otherwise it only reports
+pub fn command(fstype: &str, device: &str, repair: bool) -> Result<(&'static str, Vec<String>)> {
+    let device = device.to_string();
+    let (program, args): (&str, Vec<&str>) = match fstype {
+        "ext2" | "ext3" | "ext4" => ("e2fsck", vec!["-f", if repair { "-y" } else { "-n" }]),
+        // fsck.xfs does nothing; xfs_repair is the real checker
+        "xfs" => ("xfs_repair", if repair { vec![] } else { vec!["-n"] }),
+        "vfat" | "fat" | "msdos" => ("fsck.fat", vec![if repair { "-a" } else { "-n" }]),
+        "exfat" => ("fsck.exfat", vec![if repair { "-y" } else { "-n" }]),
+        "btrfs" if repair => {
+            return Err(AppError::Config(
+                "btrfs check --repair can make things worse; run it by hand if you must"
+                    .to_string(),
+            ))
+        }
+        "btrfs" => ("btrfs", vec!["check", "--readonly"]),
+        "" => return Err(AppError::Config("No filesystem to check".to_string())),
+        other => {
+            return Err(AppError::Config(format!(
+                "Don't know how to check {}",
+                other
+            )))
+        }
+    };
+    let mut args: Vec<String> = args.into_iter().map(String::from).collect();
+    args.push(device);
+    Ok((program, args))
+}
+
+/// What an fsck exit status means; the bits are shared by e2fsck,
+/// fsck.fat and fsck.exfat. Report-only runs exit non-zero for any error.
+pub fn describe_exit(code: i32, repair: bool) -> &'static str {
+    match code {
+        0 => "no errors found",
+        c if c & 8 != 0 => "the check itself failed",
+        _ if !repair => "errors found; nothing was changed",
+        1 => "errors were corrected",
+        2 | 3 => "errors were corrected, reboot now",
+        _ => "errors were left uncorrected",
+    }
+}
+
+/// Check an unmounted filesystem, streaming the checker's output
+pub fn run(fstype: &str, device: &str, repair: bool) -> Result<mpsc::UnboundedReceiver<RunEvent>> {
+    let (program, args) = command(fstype, device, repair)?;
+    Ok(commands::stream(program, &args))
+}
+
+pub fn boot_check_scheduled() -> bool {
+    Path::new(FORCEFSCK).exists()
+}
+
+/// Have rc.S check all filesystems on the next boot, or stop it from
+pub fn schedule_boot_check(enabled: bool) -> Result<()> {
+    if enabled {
+        root::write_file(Path::new(FORCEFSCK), b"")?;
+    } else if boot_check_scheduled() {
+        root::remove(Path::new(FORCEFSCK))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command() {
+        let (program, args) = command("ext4", "/dev/sdb1", false).unwrap();
+        assert_eq!(program, "e2fsck");
+        assert_eq!(args, ["-f", "-n", "/dev/sdb1"]);
+        let (program, args) = command("xfs", "/dev/sdb2", true).unwrap();
+        assert_eq!(program, "xfs_repair");
+        assert_eq!(args, ["/dev/sdb2"]);
+        assert!(command("btrfs", "/dev/sdc1", true).is_err());
+        assert!(command("swap", "/dev/sda2", false).is_err());
+        assert_eq!(describe_exit(1, true), "errors were corrected");
+        assert_eq!(describe_exit(4, false), "errors found; nothing was changed");
+        assert_eq!(describe_exit(12, true), "the check itself failed");
+    }
+}
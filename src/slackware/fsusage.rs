@@ -0,0 +1,68 @@
+use std::process::Command;
+
+/// How full a mounted filesystem is
+#[derive(Debug, Clone, PartialEq)]
+pub struct Usage {
+    pub mount_point: String,
+    pub percent: u8,
+}
+
+/// Usage of the device-backed filesystems in `df -P` output, skipping
+/// tmpfs and the like, which fill and empty on their own
+pub fn parse(output: &str) -> Vec<Usage> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 || !fields[0].starts_with("/dev/") {
+                return None;
+            }
+            Some(Usage {
+                // Mount points may hold spaces
+                mount_point: fields[5..].join(" "),
+                percent: fields[4].trim_end_matches('%').parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Filesystems at least `threshold` percent full
+pub fn nearly_full(threshold: u8) -> Vec<Usage> {
+    Command::new("df")
+        .arg("-P")
+        .output()
+        .map(|output| parse(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|usage| usage.percent >= threshold)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let output = "\
+Filesystem     1024-blocks     Used Available Capacity Mounted on
+/dev/sda1         40000000 38400000   1600000      96% /
+tmpfs              8000000        0   8000000       0% /dev/shm
+/dev/sdb1        100000000 10000000  90000000      10% /mnt/my disk
+";
+        assert_eq!(
+            parse(output),
+            vec![
+                Usage {
+                    mount_point: "/".to_string(),
+                    percent: 96
+                },
+                Usage {
+                    mount_point: "/mnt/my disk".to_string(),
+                    percent: 10
+                },
+            ]
+        );
+    }
+}
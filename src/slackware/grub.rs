@@ -0,0 +1,235 @@
+use std::fs;
+use std::path::Path;
+
+use crate::slackware::audit;
+use crate::slackware::config;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+pub const GRUB_CFG: &str = "/boot/grub/grub.cfg";
+pub const GRUB_DEFAULTS: &str = "/etc/default/grub";
+const GRUB_ENV: &str = "/boot/grub/grubenv";
+
+/// A bootable entry of the generated GRUB menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuEntry {
+    pub title: String,
+    /// Titles of the submenus the entry is nested in, outermost first
+    pub submenus: Vec<String>,
+    /// Kernel image loaded by the entry's `linux` command
+    pub kernel: Option<String>,
+}
+
+impl MenuEntry {
+    /// The "Submenu>Entry" form grub-set-default and GRUB_DEFAULT accept
+    pub fn path(&self) -> String {
+        let mut parts = self.submenus.clone();
+        parts.push(self.title.clone());
+        parts.join(">")
+    }
+}
+
+/// The first quoted argument of a menuentry/submenu line
+fn quoted_title(rest: &str) -> Option<String> {
+    let rest = rest.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let rest = &rest[1..];
+    Some(rest[..rest.find(quote)?].to_string())
+}
+
+/// Parse the menu entries of a grub.cfg
+pub fn parse_menu(content: &str) -> Vec<MenuEntry> {
+    let mut entries: Vec<MenuEntry> = Vec::new();
+    // Open submenus with the brace depth they were opened at
+    let mut submenus: Vec<(String, usize)> = Vec::new();
+    // Index of the entry whose body is being read, with its depth
+    let mut current: Option<(usize, usize)> = None;
+    let mut depth = 0;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("submenu ") {
+            submenus.extend(quoted_title(rest).map(|title| (title, depth)));
+        } else if let Some(rest) = line.strip_prefix("menuentry ") {
+            if let Some(title) = quoted_title(rest) {
+                entries.push(MenuEntry {
+                    title,
+                    submenus: submenus.iter().map(|(t, _)| t.clone()).collect(),
+                    kernel: None,
+                });
+                current = Some((entries.len() - 1, depth));
+            }
+        } else if let Some((index, _)) = current {
+            let mut words = line.split_whitespace();
+            if matches!(words.next(), Some("linux" | "linux16" | "linuxefi")) {
+                entries[index].kernel = words.next().map(String::from);
+            }
+        }
+
+        depth += line.matches('{').count();
+        depth = depth.saturating_sub(line.matches('}').count());
+        if current.is_some_and(|(_, d)| depth <= d) {
+            current = None;
+        }
+        while submenus.last().is_some_and(|(_, d)| depth <= *d) {
+            submenus.pop();
+        }
+    }
+    entries
+}
+
+pub fn menu_entries() -> Vec<MenuEntry> {
+    parse_menu(&fs::read_to_string(GRUB_CFG).unwrap_or_default())
+}
+
+/// A variable of /etc/default/grub, unquoted
+fn default_setting(key: &str) -> Option<String> {
+    let content = fs::read_to_string(GRUB_DEFAULTS).ok()?;
+    let prefix = format!("{}=", key);
+    content
+        .lines()
+        .rev()
+        .find_map(|l| l.trim().strip_prefix(&prefix))
+        .map(|v| v.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+}
+
+fn saved_entry() -> Option<String> {
+    let content = fs::read_to_string(GRUB_ENV).ok()?;
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix("saved_entry="))
+        .map(String::from)
+}
+
+/// Index into `entries` of the entry GRUB boots by default
+pub fn default_entry(entries: &[MenuEntry]) -> Option<usize> {
+    let setting = match default_setting("GRUB_DEFAULT").unwrap_or_else(|| "0".to_string()) {
+        saved if saved == "saved" => saved_entry().unwrap_or_else(|| "0".to_string()),
+        other => other,
+    };
+    resolve(entries, &setting)
+}
+
+/// Find the entry a GRUB_DEFAULT/saved_entry value refers to: a title path
+/// or a ">"-separated list of positions
+fn resolve(entries: &[MenuEntry], setting: &str) -> Option<usize> {
+    if let Some(i) = entries
+        .iter()
+        .position(|e| e.path() == setting || e.title == setting)
+    {
+        return Some(i);
+    }
+    let positions: Vec<usize> = setting
+        .split('>')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    // Walk the menu tree level by level, counting submenus as items
+    let mut prefix: Vec<String> = Vec::new();
+    for (level, &pos) in positions.iter().enumerate() {
+        let mut items: Vec<(Option<usize>, Option<&String>)> = Vec::new();
+        for (i, entry) in entries.iter().enumerate() {
+            if entry.submenus.len() < prefix.len() || entry.submenus[..prefix.len()] != prefix[..] {
+                continue;
+            }
+            match entry.submenus.get(prefix.len()) {
+                None => items.push((Some(i), None)),
+                Some(sub) if items.last().map(|(_, s)| *s) != Some(Some(sub)) => {
+                    items.push((None, Some(sub)))
+                }
+                Some(_) => {}
+            }
+        }
+        match items.get(pos)? {
+            (Some(i), _) if level + 1 == positions.len() => return Some(*i),
+            (None, Some(sub)) => prefix.push((*sub).clone()),
+            _ => return None,
+        }
+    }
+    None
+}
+
+fn run(cmd: &str, args: &[&str]) -> Result<()> {
+    let output = audit::output(root::command(cmd).args(args))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Regenerate grub.cfg from /etc/default/grub and /etc/grub.d
+pub fn regenerate() -> Result<()> {
+    run("grub-mkconfig", &["-o", GRUB_CFG])
+}
+
+/// Make `entry` the default. GRUB only remembers a chosen entry when
+/// GRUB_DEFAULT=saved, so that is set first (regenerating grub.cfg) when
+/// needed. Returns whether grub.cfg was regenerated.
+pub fn set_default(entry: &MenuEntry) -> Result<bool> {
+    let regenerate_cfg = default_setting("GRUB_DEFAULT").as_deref() != Some("saved");
+    if regenerate_cfg {
+        let content = fs::read_to_string(GRUB_DEFAULTS).unwrap_or_default();
+        let mut lines: Vec<String> = content
+            .lines()
+            .filter(|l| !l.trim().starts_with("GRUB_DEFAULT="))
+            .map(String::from)
+            .collect();
+        lines.insert(0, "GRUB_DEFAULT=saved".to_string());
+        config::write_with_backup(Path::new(GRUB_DEFAULTS), &(lines.join("\n") + "\n"))?;
+        regenerate()?;
+    }
+
+    let path = entry.path();
+    match run("grub-set-default", &[&path]) {
+        Err(AppError::Io(_)) => run(
+            "grub-editenv",
+            &[GRUB_ENV, "set", &format!("saved_entry={}", path)],
+        )?,
+        other => other?,
+    }
+    Ok(regenerate_cfg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_menu() {
+        let cfg = "\
+menuentry 'Slackware 15.0' --class slackware $menuentry_id_option 'gnulinux-simple' {
+\tlinux\t/boot/vmlinuz-generic-5.15.19 root=/dev/sda1 ro
+}
+submenu 'Advanced options for Slackware 15.0' {
+\tmenuentry 'Slackware 15.0, with Linux 5.15.19' {
+\t\tlinux\t/boot/vmlinuz-5.15.19 root=/dev/sda1 ro
+\t}
+\tmenuentry 'Slackware 15.0, with Linux huge-5.15.19' {
+\t\tlinux\t/boot/vmlinuz-huge-5.15.19 root=/dev/sda1 ro
+\t}
+}
+menuentry \"UEFI Firmware Settings\" {
+\tfwsetup
+}
+";
+        let entries = parse_menu(cfg);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(
+            entries[0].kernel.as_deref(),
+            Some("/boot/vmlinuz-generic-5.15.19")
+        );
+        assert_eq!(
+            entries[2].path(),
+            "Advanced options for Slackware 15.0>Slackware 15.0, with Linux huge-5.15.19"
+        );
+        assert!(entries[3].submenus.is_empty());
+
+        assert_eq!(resolve(&entries, "0"), Some(0));
+        assert_eq!(resolve(&entries, "1>1"), Some(2));
+        assert_eq!(resolve(&entries, "2"), Some(3));
+        assert_eq!(resolve(&entries, "UEFI Firmware Settings"), Some(3));
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+
+use crate::slackware::audit;
+use crate::slackware::config;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+pub const HOSTS_FILE: &str = "/etc/hosts";
+/// Slackware keeps the fully qualified name here; rc.M sets the short form
+pub const HOSTNAME_FILE: &str = "/etc/HOSTNAME";
+
+/// A mapping line of /etc/hosts
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostEntry {
+    /// Index of the line in the file
+    pub line: usize,
+    pub address: String,
+    pub names: Vec<String>,
+    pub comment: Option<String>,
+}
+
+/// /etc/hosts kept as raw lines so comments survive edits
+#[derive(Debug, Clone)]
+pub struct HostsFile {
+    lines: Vec<String>,
+}
+
+impl HostsFile {
+    pub fn load() -> Self {
+        Self::parse(&fs::read_to_string(HOSTS_FILE).unwrap_or_default())
+    }
+
+    pub fn parse(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(String::from).collect(),
+        }
+    }
+
+    pub fn to_content(&self) -> String {
+        self.lines.join("\n") + "\n"
+    }
+
+    pub fn save(&self) -> Result<()> {
+        config::write_with_backup(Path::new(HOSTS_FILE), &self.to_content()).map(|_| ())
+    }
+
+    pub fn entries(&self) -> Vec<HostEntry> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let (entry, comment) = match line.split_once('#') {
+                    Some((entry, comment)) => (entry, Some(comment.trim().to_string())),
+                    None => (line.as_str(), None),
+                };
+                let mut fields = entry.split_whitespace();
+                let address = fields.next()?.to_string();
+                Some(HostEntry {
+                    line: i,
+                    address,
+                    names: fields.map(String::from).collect(),
+                    comment: comment.filter(|c| !c.is_empty()),
+                })
+            })
+            .collect()
+    }
+
+    /// Host names listed more than once, mapped to the addresses they resolve to
+    pub fn duplicates(&self) -> HashMap<String, Vec<String>> {
+        let mut seen: HashMap<String, Vec<String>> = HashMap::new();
+        for entry in self.entries() {
+            for name in &entry.names {
+                seen.entry(name.to_lowercase())
+                    .or_default()
+                    .push(entry.address.clone());
+            }
+        }
+        seen.retain(|_, addresses| addresses.len() > 1);
+        seen
+    }
+
+    /// Add a mapping parsed from "address name [alias...]"
+    pub fn add(&mut self, spec: &str) -> Result<HostEntry> {
+        let mut fields = spec.split_whitespace();
+        let address = fields.next().unwrap_or_default();
+        if address.parse::<IpAddr>().is_err() {
+            return Err(AppError::Config(format!("Invalid address: '{}'", address)));
+        }
+        let names: Vec<String> = fields.map(String::from).collect();
+        if names.is_empty() {
+            return Err(AppError::Config("Expected 'address name [alias...]'".to_string()));
+        }
+        if let Some(bad) = names.iter().find(|n| !is_valid_hostname(n)) {
+            return Err(AppError::Config(format!("Invalid host name: '{}'", bad)));
+        }
+
+        self.lines.push(format!("{}\t{}", address, names.join(" ")));
+        Ok(HostEntry {
+            line: self.lines.len() - 1,
+            address: address.to_string(),
+            names,
+            comment: None,
+        })
+    }
+
+    pub fn remove(&mut self, line: usize) {
+        if line < self.lines.len() {
+            self.lines.remove(line);
+        }
+    }
+
+    /// Replace the old host names on 127.0.0.1 lines with the new ones,
+    /// adding a loopback entry if the old name was not listed
+    pub fn rename_loopback(&mut self, old: &[&str], new: &[&str]) {
+        let mut replaced = false;
+        for line in self.lines.iter_mut() {
+            let (entry, comment) = match line.split_once('#') {
+                Some((entry, comment)) => (entry, Some(comment)),
+                None => (line.as_str(), None),
+            };
+            let mut fields = entry.split_whitespace();
+            if fields.next() != Some("127.0.0.1") {
+                continue;
+            }
+            let names: Vec<&str> = fields.collect();
+            if !names.iter().any(|n| old.contains(n)) {
+                continue;
+            }
+
+            let mut updated: Vec<&str> = Vec::new();
+            for name in names {
+                if old.contains(&name) {
+                    if !replaced {
+                        for n in new {
+                            if !updated.contains(n) {
+                                updated.push(n);
+                            }
+                        }
+                    }
+                    replaced = true;
+                } else if !updated.contains(&name) {
+                    updated.push(name);
+                }
+            }
+            let mut rebuilt = format!("127.0.0.1\t{}", updated.join(" "));
+            if let Some(comment) = comment {
+                rebuilt.push_str(&format!(" #{}", comment));
+            }
+            *line = rebuilt;
+        }
+
+        if !replaced {
+            let position = self
+                .lines
+                .iter()
+                .rposition(|l| l.trim_start().starts_with("127.0.0.1"))
+                .map(|i| i + 1)
+                .unwrap_or(self.lines.len());
+            self.lines
+                .insert(position, format!("127.0.0.1\t{}", new.join(" ")));
+        }
+    }
+}
+
+/// Check a host name against RFC 1123
+pub fn is_valid_hostname(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Fully qualified and short names, e.g. ("darkstar.example.net", "darkstar")
+fn name_forms(name: &str) -> (String, String) {
+    let short = name.split('.').next().unwrap_or(name).to_string();
+    (name.to_string(), short)
+}
+
+/// Change the system host name in /etc/HOSTNAME, /etc/hosts and the
+/// running kernel
+pub fn set_hostname(current: &str, new: &str) -> Result<()> {
+    if !is_valid_hostname(new) {
+        return Err(AppError::Config(format!("Invalid hostname: '{}'", new)));
+    }
+    let (old_full, old_short) = name_forms(current);
+    let (new_full, new_short) = name_forms(new);
+
+    let mut hosts = HostsFile::load();
+    let old_names: Vec<&str> = [old_full.as_str(), old_short.as_str()]
+        .into_iter()
+        .filter(|n| !n.is_empty() && *n != "localhost")
+        .collect();
+    let mut new_names = vec![new_full.as_str()];
+    if new_short != new_full {
+        new_names.push(new_short.as_str());
+    }
+    hosts.rename_loopback(&old_names, &new_names);
+    hosts.save()?;
+
+    config::write_with_backup(Path::new(HOSTNAME_FILE), &format!("{}\n", new_full))?;
+
+    let output = audit::output(root::command("hostname").arg(&new_short))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_loopback() {
+        let mut hosts = HostsFile::parse(
+            "127.0.0.1\tlocalhost\n127.0.0.1\tdarkstar.example.net darkstar\n::1 localhost\n",
+        );
+        hosts.rename_loopback(&["darkstar.example.net", "darkstar"], &["box.lan", "box"]);
+        assert_eq!(
+            hosts.to_content(),
+            "127.0.0.1\tlocalhost\n127.0.0.1\tbox.lan box\n::1 localhost\n"
+        );
+
+        let mut hosts = HostsFile::parse("127.0.0.1\tlocalhost\n");
+        hosts.rename_loopback(&["darkstar"], &["box"]);
+        assert_eq!(hosts.to_content(), "127.0.0.1\tlocalhost\n127.0.0.1\tbox\n");
+    }
+
+    #[test]
+    fn test_hostname_validation() {
+        assert!(is_valid_hostname("darkstar.example.net"));
+        assert!(!is_valid_hostname("-bad"));
+        assert!(!is_valid_hostname("has space"));
+        assert!(!is_valid_hostname(""));
+    }
+}
@@ -0,0 +1,253 @@
+use std::fs;
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+
+use crate::slackware::config;
+use crate::utils::error::{AppError, Result};
+
+/// Slackware network configuration file read by rc.inet1
+pub const INET1_CONF: &str = "/etc/rc.d/rc.inet1.conf";
+
+/// Editable view of /etc/rc.d/rc.inet1.conf
+///
+/// The file is kept as raw lines so comments and unrelated settings survive
+/// a round trip; only the variables that are changed get rewritten.
+#[derive(Debug, Clone)]
+pub struct Inet1Config {
+    lines: Vec<String>,
+}
+
+impl Inet1Config {
+    /// Load the system rc.inet1.conf
+    pub fn load() -> Result<Self> {
+        let content = fs::read_to_string(INET1_CONF).map_err(|e| {
+            AppError::FileOperation(format!("Failed to read {}: {}", INET1_CONF, e))
+        })?;
+        Ok(Self::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    pub fn to_content(&self) -> String {
+        self.lines.join("\n") + "\n"
+    }
+
+    fn var_name(key: &str, index: Option<usize>) -> String {
+        match index {
+            Some(i) => format!("{}[{}]", key, i),
+            None => key.to_string(),
+        }
+    }
+
+    /// Split an assignment line into (name, value, commented)
+    fn assignment(line: &str) -> Option<(&str, String, bool)> {
+        let trimmed = line.trim();
+        let (body, commented) = match trimmed.strip_prefix('#') {
+            Some(rest) => (rest.trim_start(), true),
+            None => (trimmed, false),
+        };
+        let (name, value) = body.split_once('=')?;
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return None;
+        }
+        let value = value
+            .split(" #")
+            .next()
+            .unwrap_or("")
+            .trim()
+            .trim_matches('"');
+        Some((name, value.to_string(), commented))
+    }
+
+    /// Value of an active (uncommented) variable, e.g. `IPADDR[0]`
+    pub fn get(&self, key: &str, index: Option<usize>) -> Option<String> {
+        let name = Self::var_name(key, index);
+        self.lines
+            .iter()
+            .rev()
+            .filter_map(|l| Self::assignment(l))
+            .find(|(n, _, commented)| *n == name && !commented)
+            .map(|(_, v, _)| v)
+    }
+
+    /// Set a variable, reusing an existing (possibly commented-out) line
+    /// when there is one and otherwise appending it to its interface block
+    pub fn set(&mut self, key: &str, index: Option<usize>, value: &str) {
+        let name = Self::var_name(key, index);
+        let line = format!("{}=\"{}\"", name, value);
+
+        let position = |want_commented: bool| {
+            self.lines.iter().rposition(|l| {
+                Self::assignment(l)
+                    .is_some_and(|(n, _, commented)| n == name && commented == want_commented)
+            })
+        };
+        if let Some(i) = position(false).or_else(|| position(true)) {
+            self.lines[i] = line;
+            return;
+        }
+
+        let block_end = index.and_then(|idx| {
+            let suffix = format!("[{}]", idx);
+            self.lines
+                .iter()
+                .rposition(|l| Self::assignment(l).is_some_and(|(n, _, _)| n.ends_with(&suffix)))
+        });
+        match block_end {
+            Some(i) => self.lines.insert(i + 1, line),
+            None => {
+                if self.lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                    self.lines.push(String::new());
+                }
+                self.lines.push(line);
+            }
+        }
+    }
+
+    /// Index of the rc.inet1.conf block configuring an interface: the one
+    /// naming it in IFNAME[n], else block n for ethN when that block names
+    /// no other interface
+    pub fn index_for(&self, iface: &str) -> Option<usize> {
+        let named = self
+            .lines
+            .iter()
+            .filter_map(|l| Self::assignment(l))
+            .find_map(|(n, v, c)| {
+                if c || v != iface {
+                    return None;
+                }
+                n.strip_prefix("IFNAME[")?.strip_suffix(']')?.parse().ok()
+            });
+        named.or_else(|| {
+            let idx = iface.strip_prefix("eth")?.parse().ok()?;
+            let other = self.get("IFNAME", Some(idx)).filter(|name| !name.is_empty());
+            other.is_none().then_some(idx)
+        })
+    }
+
+    /// Interface block indices mentioned in the file, in ascending order
+    pub fn indices(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = self
+            .lines
+            .iter()
+            .filter_map(|l| Self::assignment(l))
+            .filter_map(|(n, _, _)| {
+                let (_, rest) = n.split_once('[')?;
+                rest.strip_suffix(']')?.parse().ok()
+            })
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+
+    /// Indices of interfaces configured for DHCP
+    pub fn dhcp_indices(&self) -> Vec<usize> {
+        self.indices()
+            .into_iter()
+            .filter(|i| {
+                self.get("USE_DHCP", Some(*i))
+                    .is_some_and(|v| v.eq_ignore_ascii_case("yes"))
+            })
+            .collect()
+    }
+
+    /// Index to use for an interface, allocating a new IFNAME[n] block if it
+    /// is not configured yet
+    pub fn index_or_insert(&mut self, iface: &str) -> usize {
+        if let Some(idx) = self.index_for(iface) {
+            return idx;
+        }
+        let used = self.indices();
+        let idx = (0..).find(|i| !used.contains(i)).unwrap_or(0);
+        if self.lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            self.lines.push(String::new());
+        }
+        self.lines
+            .push(format!("# Config information for {}:", iface));
+        self.lines.push(format!("IFNAME[{}]=\"{}\"", idx, iface));
+        idx
+    }
+
+    /// Write the file back, keeping a copy of the previous version
+    ///
+    /// Returns the path of the backup copy.
+    pub fn save(&self) -> Result<PathBuf> {
+        config::write_with_backup(Path::new(INET1_CONF), &self.to_content())
+    }
+}
+
+/// Check that a string is a dotted-quad netmask with contiguous bits
+pub fn is_valid_netmask(mask: &str) -> bool {
+    mask.parse::<Ipv4Addr>()
+        .map(|m| {
+            let bits = u32::from(m);
+            bits.leading_ones() + bits.trailing_zeros() == 32
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# Config information for eth0:\n\
+IPADDR[0]=\"\"\n\
+NETMASK[0]=\"\"\n\
+USE_DHCP[0]=\"yes\"\n\
+\n\
+# Config information for eth1:\n\
+#IPADDR[1]=\"\"\n\
+USE_DHCP[1]=\"\"\n\
+\n\
+GATEWAY=\"\"\n";
+
+    #[test]
+    fn test_set_static_address() {
+        let mut config = Inet1Config::parse(SAMPLE);
+        config.set("IPADDR", Some(1), "192.168.1.10");
+        config.set("NETMASK", Some(1), "255.255.255.0");
+        config.set("GATEWAY", None, "192.168.1.1");
+
+        assert_eq!(
+            config.get("IPADDR", Some(1)).as_deref(),
+            Some("192.168.1.10")
+        );
+        assert_eq!(
+            config.get("NETMASK", Some(1)).as_deref(),
+            Some("255.255.255.0")
+        );
+        assert_eq!(config.get("GATEWAY", None).as_deref(), Some("192.168.1.1"));
+        assert_eq!(config.get("USE_DHCP", Some(0)).as_deref(), Some("yes"));
+        assert!(config
+            .to_content()
+            .starts_with("# Config information for eth0:\n"));
+    }
+
+    #[test]
+    fn test_interface_index() {
+        let mut config = Inet1Config::parse(SAMPLE);
+        assert_eq!(config.index_for("eth1"), Some(1));
+        assert_eq!(config.index_for("wlan0"), None);
+        assert_eq!(config.index_or_insert("wlan0"), 2);
+        assert_eq!(config.index_for("wlan0"), Some(2));
+
+        // Block 0 belongs to wlan0, so eth0 gets a block of its own
+        let mut config = Inet1Config::parse("IFNAME[0]=\"wlan0\"\nUSE_DHCP[0]=\"yes\"\n");
+        assert_eq!(config.index_for("eth0"), None);
+        assert_eq!(config.index_or_insert("eth0"), 1);
+        assert_eq!(config.index_for("wlan0"), Some(0));
+    }
+
+    #[test]
+    fn test_netmask_validation() {
+        assert!(is_valid_netmask("255.255.255.0"));
+        assert!(is_valid_netmask("255.255.240.0"));
+        assert!(!is_valid_netmask("255.0.255.0"));
+        assert!(!is_valid_netmask("not-a-mask"));
+    }
+}
@@ -0,0 +1,184 @@
+use std::path::Path;
+
+use crate::slackware::audit;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// Ships with mkinitrd and prints the command line this system needs
+const COMMAND_GENERATOR: &str = "/usr/share/mkinitrd/mkinitrd_command_generator.sh";
+
+/// Everything mkinitrd needs to know to boot a kernel on this system
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitrdPlan {
+    /// Version of the kernel's /lib/modules directory
+    pub kernel: String,
+    pub root_device: String,
+    pub root_fs: String,
+    pub modules: Vec<String>,
+    /// Encrypted partition holding the root filesystem
+    pub luks_device: Option<String>,
+    pub lvm: bool,
+    pub output: String,
+}
+
+impl InitrdPlan {
+    /// Inspect the running system to plan an initrd for the kernel installed
+    /// as /boot/vmlinuz-`image_version`
+    pub fn detect(image_version: &str) -> Result<Self> {
+        let kernel = modules_version(image_version).ok_or_else(|| {
+            AppError::FileOperation(format!("No /lib/modules directory for {}", image_version))
+        })?;
+        let root = run_output("findmnt", &["-n", "-o", "SOURCE,FSTYPE", "/"])?;
+        let mut words = root.split_whitespace();
+        let (Some(root_device), Some(root_fs)) = (words.next(), words.next()) else {
+            return Err(AppError::CommandFailed(format!(
+                "Unexpected findmnt output: {}",
+                root
+            )));
+        };
+
+        // The root device and everything below it, e.g. lvm <- crypt <- part
+        let stack = run_output(
+            "lsblk",
+            &["-l", "-n", "-s", "-p", "-o", "NAME,TYPE", root_device],
+        )
+        .unwrap_or_default();
+        let devices: Vec<(String, String)> = stack
+            .lines()
+            .filter_map(|l| {
+                let mut words = l.split_whitespace();
+                Some((words.next()?.to_string(), words.next()?.to_string()))
+            })
+            .collect();
+        let lvm = devices.iter().any(|(_, kind)| kind == "lvm");
+        let luks_device = devices
+            .iter()
+            .position(|(_, kind)| kind == "crypt")
+            .and_then(|i| devices.get(i + 1))
+            .map(|(name, _)| name.clone());
+
+        Ok(Self {
+            modules: generator_modules(&kernel).unwrap_or_else(|| vec![root_fs.to_string()]),
+            kernel,
+            root_device: root_device.to_string(),
+            root_fs: root_fs.to_string(),
+            luks_device,
+            lvm,
+            output: format!("/boot/initrd-{}.gz", image_version),
+        })
+    }
+
+    pub fn command(&self) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "mkinitrd",
+            "-c",
+            "-k",
+            &self.kernel,
+            "-f",
+            &self.root_fs,
+            "-r",
+            &self.root_device,
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        if !self.modules.is_empty() {
+            args.extend(["-m".to_string(), self.modules.join(":")]);
+        }
+        if let Some(luks) = &self.luks_device {
+            args.extend(["-C".to_string(), luks.clone()]);
+        }
+        if self.lvm {
+            args.push("-L".to_string());
+        }
+        args.extend(["-u".to_string(), "-o".to_string(), self.output.clone()]);
+        args
+    }
+
+    pub fn build(&self) -> Result<()> {
+        let args = self.command();
+        let output = audit::output(root::command(&args[0]).args(&args[1..]))?;
+        if !output.status.success() {
+            return Err(AppError::CommandFailed(format!(
+                "mkinitrd: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn run_output(cmd: &str, args: &[&str]) -> Result<String> {
+    let output = root::command(cmd).args(args).output()?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The /lib/modules version of a kernel image: Slackware names images
+/// "vmlinuz-generic-5.15.19" while the modules live in /lib/modules/5.15.19
+pub fn modules_version(image_version: &str) -> Option<String> {
+    let mut candidates = vec![image_version.to_string()];
+    for variant in ["generic", "huge"] {
+        if let Some(v) = image_version.strip_prefix(&format!("{}-", variant)) {
+            candidates.push(v.to_string());
+        }
+        if let Some(v) = image_version.strip_suffix(&format!("-{}", variant)) {
+            candidates.push(v.to_string());
+        }
+    }
+    candidates
+        .into_iter()
+        .find(|v| Path::new("/lib/modules").join(v).is_dir())
+}
+
+/// Modules the command generator says the root filesystem needs
+fn generator_modules(kernel: &str) -> Option<Vec<String>> {
+    let command = run_output("sh", &[COMMAND_GENERATOR, "-r", "-k", kernel]).ok()?;
+    parse_generator_modules(&command)
+}
+
+fn parse_generator_modules(command: &str) -> Option<Vec<String>> {
+    let line = command
+        .lines()
+        .find(|l| l.trim_start().starts_with("mkinitrd"))?;
+    let mut words = line.split_whitespace();
+    words.find(|w| *w == "-m")?;
+    Some(words.next()?.split(':').map(String::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator_modules() {
+        let output = "#\n# mkinitrd_command_generator.sh revision 1.45\n#\n\
+            mkinitrd -c -k 5.15.19 -f ext4 -r /dev/sda2 \
+            -m xhci-pci:ohci-pci:ehci-pci:usbhid:jbd2:mbcache:crc32c_intel:ext4 \
+            -u -o /boot/initrd.gz\n";
+        let modules = parse_generator_modules(output).unwrap();
+        assert_eq!(modules.first().map(String::as_str), Some("xhci-pci"));
+        assert_eq!(modules.last().map(String::as_str), Some("ext4"));
+
+        let plan = InitrdPlan {
+            kernel: "5.15.19".to_string(),
+            root_device: "/dev/cryptvg/root".to_string(),
+            root_fs: "ext4".to_string(),
+            modules: vec!["ext4".to_string()],
+            luks_device: Some("/dev/sda2".to_string()),
+            lvm: true,
+            output: "/boot/initrd-generic-5.15.19.gz".to_string(),
+        };
+        assert_eq!(
+            plan.command().join(" "),
+            "mkinitrd -c -k 5.15.19 -f ext4 -r /dev/cryptvg/root -m ext4 -C /dev/sda2 -L -u \
+             -o /boot/initrd-generic-5.15.19.gz"
+        );
+    }
+}
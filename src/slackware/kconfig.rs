@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::utils::error::{AppError, Result};
+
+/// Exposed by kernels built with CONFIG_IKCONFIG_PROC
+const PROC_CONFIG: &str = "/proc/config.gz";
+
+/// An option whose value differs between two kernel configs. `None` means
+/// the option is unset ("# CONFIG_X is not set" or missing altogether).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionChange {
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// The options set by a kernel .config, without their CONFIG_ prefix
+pub fn parse(content: &str) -> BTreeMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.trim().strip_prefix("CONFIG_")?.split_once('=')?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Options added, dropped or changed going from `old` to `new`
+pub fn diff(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Vec<OptionChange> {
+    let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter(|name| old.get(*name) != new.get(*name))
+        .map(|name| OptionChange {
+            name: name.clone(),
+            old: old.get(name).cloned(),
+            new: new.get(name).cloned(),
+        })
+        .collect()
+}
+
+/// The config Slackware installs next to vmlinuz-`version`, e.g.
+/// /boot/config-generic-5.15.19.x64 for vmlinuz-generic-5.15.19
+pub fn config_for(version: &str) -> Option<PathBuf> {
+    let prefix = format!("config-{}", version);
+    let mut matches: Vec<PathBuf> = fs::read_dir("/boot")
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.strip_prefix(&prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+        .map(|e| e.path())
+        .collect();
+    matches.sort();
+    matches.into_iter().next()
+}
+
+/// Read the config of an installed kernel, returning where it came from
+pub fn load(version: &str) -> Result<(String, BTreeMap<String, String>)> {
+    let path = config_for(version).ok_or_else(|| {
+        AppError::FileOperation(format!("No /boot/config-{}* for this kernel", version))
+    })?;
+    let content = fs::read_to_string(&path)?;
+    Ok((path.display().to_string(), parse(&content)))
+}
+
+/// Read the config of the running kernel: its /boot/config file when we
+/// know which image was booted, /proc/config.gz otherwise
+pub fn load_running(version: Option<&str>) -> Result<(String, BTreeMap<String, String>)> {
+    if let Some(found) = version.and_then(|v| load(v).ok()) {
+        return Ok(found);
+    }
+    if !Path::new(PROC_CONFIG).exists() {
+        return Err(AppError::FileOperation(format!(
+            "No config for the running kernel in /boot and no {}",
+            PROC_CONFIG
+        )));
+    }
+    let output = Command::new("zcat").arg(PROC_CONFIG).output()?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "zcat {}: {}",
+            PROC_CONFIG,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok((
+        PROC_CONFIG.to_string(),
+        parse(&String::from_utf8_lossy(&output.stdout)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let old = parse(
+            "# Linux/x86 5.15.19 Kernel Configuration\nCONFIG_EXT4_FS=y\n\
+             CONFIG_BTRFS_FS=m\n# CONFIG_ZSWAP is not set\nCONFIG_HZ=250\n",
+        );
+        let new = parse("CONFIG_EXT4_FS=y\nCONFIG_ZSWAP=y\nCONFIG_HZ=1000\n");
+        assert_eq!(old.get("BTRFS_FS").map(String::as_str), Some("m"));
+        assert!(!old.contains_key("ZSWAP"));
+
+        let changes = diff(&old, &new);
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.old.as_deref(), c.new.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("BTRFS_FS", Some("m"), None),
+                ("HZ", Some("250"), Some("1000")),
+                ("ZSWAP", None, Some("y")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::slackware::audit;
+use crate::slackware::config::SlackwareConfig;
+use crate::slackware::initrd;
+use crate::slackware::proxy::ProxySettings;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+const PACKAGES_DIR: &str = "/var/log/packages";
+
+/// Packages making up a bootable kernel, as shipped in the a/ series
+pub const KERNEL_PACKAGES: &[&str] = &[
+    "kernel-generic",
+    "kernel-huge",
+    "kernel-modules",
+    "kernel-firmware",
+];
+
+/// A kernel package offered by the mirror
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirrorPackage {
+    pub name: String,
+    pub version: String,
+    /// Path of the package below the mirror's root
+    pub path: String,
+}
+
+impl MirrorPackage {
+    /// Package file name without its extension, as listed in /var/log/packages
+    pub fn stem(&self) -> &str {
+        let file = self.path.rsplit('/').next().unwrap_or(&self.path);
+        file.strip_suffix(".txz").unwrap_or(file)
+    }
+
+    pub fn is_installed(&self) -> bool {
+        Path::new(PACKAGES_DIR).join(self.stem()).exists()
+    }
+}
+
+/// Kernel packages listed in a tree's FILELIST.TXT
+pub fn parse_filelist(content: &str) -> Vec<MirrorPackage> {
+    let mut packages: Vec<MirrorPackage> = content
+        .lines()
+        .filter_map(|line| {
+            let path = line.split_whitespace().last()?.trim_start_matches("./");
+            let stem = path.rsplit('/').next()?.strip_suffix(".txz")?;
+            // name-version-arch-build, where only the name may contain dashes
+            let mut fields = stem.rsplitn(4, '-');
+            let (_build, _arch) = (fields.next()?, fields.next()?);
+            let version = fields.next()?;
+            let name = fields.next()?;
+            KERNEL_PACKAGES.contains(&name).then(|| MirrorPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+                path: path.to_string(),
+            })
+        })
+        .collect();
+    packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    packages.dedup();
+    packages
+}
+
+/// URL of the active mirror in /etc/slackpkg/mirrors, ending in a slash
+pub fn active_mirror() -> Result<String> {
+    let mirror = SlackwareConfig::parse_mirrors(None)?
+        .into_iter()
+        .find(|m| m.is_active)
+        .ok_or_else(|| AppError::Config("No mirror is enabled in /etc/slackpkg/mirrors".into()))?;
+    let mut url = mirror.url;
+    if !url.ends_with('/') {
+        url.push('/');
+    }
+    Ok(url)
+}
+
+/// Download a file from the root of the active mirror, returning the
+/// mirror URL and the file's content
+pub fn fetch_from_mirror(file: &str) -> Result<(String, String)> {
+    let mirror = active_mirror()?;
+    // Reading the mirror needs no privileges, and sudo would drop the proxy
+    let output = Command::new("wget")
+        .args(["-q", "-O", "-", &format!("{}{}", mirror, file)])
+        .envs(ProxySettings::load().env_vars())
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "Could not fetch {}{}",
+            mirror, file
+        )));
+    }
+    let content = String::from_utf8_lossy(&output.stdout).to_string();
+    Ok((mirror, content))
+}
+
+/// Kernel packages available from the active mirror, with the mirror URL
+pub fn available_from_mirror() -> Result<(String, Vec<MirrorPackage>)> {
+    let (mirror, content) = fetch_from_mirror("FILELIST.TXT")?;
+    Ok((mirror, parse_filelist(&content)))
+}
+
+/// What removing a kernel took off the system
+#[derive(Debug, Clone, Default)]
+pub struct Removal {
+    pub packages: Vec<String>,
+    pub files: Vec<String>,
+}
+
+/// Files in /boot belonging to the kernel installed as vmlinuz-`version`
+pub fn boot_files(version: &str) -> Vec<String> {
+    ["vmlinuz", "System.map", "config"]
+        .iter()
+        .map(|prefix| format!("/boot/{}-{}", prefix, version))
+        .chain([format!("/boot/initrd-{}.gz", version)])
+        .filter(|path| Path::new(path).exists())
+        .collect()
+}
+
+fn is_link(version: &str) -> bool {
+    fs::symlink_metadata(format!("/boot/vmlinuz-{}", version))
+        .is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// Whether vmlinuz-`version` is the booted image. GRUB passes its path as
+/// BOOT_IMAGE; otherwise any image built for the running release counts.
+fn is_running(version: &str, release: &str) -> bool {
+    let cmdline = fs::read_to_string("/proc/cmdline").unwrap_or_default();
+    let boot_image = cmdline
+        .split_whitespace()
+        .find_map(|arg| arg.strip_prefix("BOOT_IMAGE="))
+        .filter(|image| image.contains("vmlinuz-"));
+    match boot_image {
+        Some(image) => image.ends_with(&format!("vmlinuz-{}", version)),
+        None => initrd::modules_version(version).as_deref() == Some(release),
+    }
+}
+
+/// Installed packages shipping the kernel image or its modules. Modules
+/// used by the running kernel (a huge and a generic kernel of the same
+/// version share them) are left alone.
+fn owning_packages(version: &str, running: &str) -> Vec<String> {
+    let image = format!("boot/vmlinuz-{}", version);
+    let modules = initrd::modules_version(version)
+        .filter(|m| m != running)
+        .map(|m| format!("lib/modules/{}/", m));
+    let Ok(entries) = fs::read_dir(PACKAGES_DIR) else {
+        return Vec::new();
+    };
+    let mut packages: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            fs::read_to_string(e.path()).is_ok_and(|content| {
+                content
+                    .lines()
+                    .any(|l| l == image || modules.as_deref().is_some_and(|m| l.starts_with(m)))
+            })
+        })
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    packages.sort();
+    packages
+}
+
+/// Remove the kernel installed as /boot/vmlinuz-`version`: its packages,
+/// then whatever boot files were not packaged (initrds, custom builds).
+/// Refuses to remove the running kernel or the last one installed.
+pub fn remove(version: &str, installed: &[String]) -> Result<Removal> {
+    let running = Command::new("uname")
+        .arg("-r")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())?;
+    let image = format!("/boot/vmlinuz-{}", version);
+    if is_link(version) {
+        let target = fs::read_link(&image)?;
+        return Err(AppError::Config(format!(
+            "{} is a link to {}; remove that kernel instead",
+            image,
+            target.display()
+        )));
+    }
+    if is_running(version, &running) {
+        return Err(AppError::Config(format!(
+            "{} is the running kernel",
+            version
+        )));
+    }
+    // The vmlinuz-generic style links are not kernels of their own
+    if installed.iter().all(|v| v == version || is_link(v)) {
+        return Err(AppError::Config(format!(
+            "{} is the only kernel installed",
+            version
+        )));
+    }
+
+    let mut removal = Removal::default();
+    for package in owning_packages(version, &running) {
+        let output = audit::output(root::command("removepkg").arg(&package))?;
+        if !output.status.success() {
+            return Err(AppError::CommandFailed(format!(
+                "removepkg {}: {}",
+                package,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        removal.packages.push(package);
+    }
+    for file in boot_files(version) {
+        root::remove(Path::new(&file))?;
+        removal.files.push(file);
+    }
+    Ok(removal)
+}
+
+/// A package outside the kernel-* series installing kernel modules, such
+/// as nvidia-kernel or virtualbox-kernel from SlackBuilds.org
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModulePackage {
+    /// Package name as in /var/log/packages, e.g. "nvidia-kernel-470.86_5.15.19-x86_64-1_SBo"
+    pub package: String,
+    /// Releases of the /lib/modules directories it installs into
+    pub built_for: Vec<String>,
+}
+
+impl ModulePackage {
+    /// Name without version, arch and build, e.g. "nvidia-kernel"
+    pub fn name(&self) -> &str {
+        let mut end = self.package.len();
+        for _ in 0..3 {
+            end = self.package[..end].rfind('-').unwrap_or(0);
+        }
+        &self.package[..end]
+    }
+
+    /// Built by a SlackBuilds.org script, so sbotools can rebuild it
+    pub fn is_sbo(&self) -> bool {
+        self.package.ends_with("_SBo")
+    }
+}
+
+/// /lib/modules releases listed in a package's file list
+fn module_releases_in(content: &str) -> Vec<String> {
+    let mut releases: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let Some(release) = line
+            .strip_prefix("lib/modules/")
+            .and_then(|rest| rest.split('/').next())
+            .filter(|r| !r.is_empty())
+        else {
+            continue;
+        };
+        if !releases.iter().any(|r| r == release) {
+            releases.push(release.to_string());
+        }
+    }
+    releases
+}
+
+/// Releases of the installed kernels, from the kernel-modules packages
+pub fn installed_releases() -> Vec<String> {
+    let mut releases: Vec<String> = fs::read_dir(PACKAGES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name().to_string_lossy().starts_with("kernel-modules-"))
+                .filter_map(|e| fs::read_to_string(e.path()).ok())
+                .flat_map(|content| module_releases_in(&content))
+                .collect()
+        })
+        .unwrap_or_default();
+    releases.sort();
+    releases.dedup();
+    releases
+}
+
+/// Installed packages shipping out-of-tree kernel modules
+pub fn module_packages() -> Vec<ModulePackage> {
+    let Ok(entries) = fs::read_dir(PACKAGES_DIR) else {
+        return Vec::new();
+    };
+    let mut packages: Vec<ModulePackage> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_name().to_string_lossy().starts_with("kernel-"))
+        .filter_map(|e| {
+            let content = fs::read_to_string(e.path()).ok()?;
+            let built_for = module_releases_in(&content);
+            (!built_for.is_empty()).then(|| ModulePackage {
+                package: e.file_name().to_string_lossy().to_string(),
+                built_for,
+            })
+        })
+        .collect();
+    packages.sort_by(|a, b| a.package.cmp(&b.package));
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filelist() {
+        let filelist = "\
+-rw-r--r-- 1 root root 1234 2022-02-02 20:51 ./slackware64/a/kernel-generic-5.15.19-x86_64-2.txz
+-rw-r--r-- 1 root root  195 2022-02-02 20:51 ./slackware64/a/kernel-generic-5.15.19-x86_64-2.txz.asc
+-rw-r--r-- 1 root root 1234 2022-02-02 20:51 ./slackware64/a/kernel-modules-5.15.19-x86_64-2.txz
+-rw-r--r-- 1 root root 1234 2024-06-17 19:12 ./patches/packages/linux-5.15.161/kernel-huge-5.15.161-x86_64-1.txz
+-rw-r--r-- 1 root root 1234 2022-02-02 20:51 ./slackware64/d/kernel-headers-5.15.19-x86-2.txz
+";
+        let packages = parse_filelist(filelist);
+        assert_eq!(packages.len(), 3);
+        assert_eq!(packages[0].name, "kernel-generic");
+        assert_eq!(packages[0].stem(), "kernel-generic-5.15.19-x86_64-2");
+        assert_eq!(packages[1].version, "5.15.161");
+        assert_eq!(
+            packages[1].path,
+            "patches/packages/linux-5.15.161/kernel-huge-5.15.161-x86_64-1.txz"
+        );
+    }
+
+    #[test]
+    fn test_module_package() {
+        let content = "PACKAGE NAME:     nvidia-kernel-470.86_5.15.19-x86_64-1_SBo\n\
+                       FILE LIST:\n./\nlib/\nlib/modules/\nlib/modules/5.15.19/\n\
+                       lib/modules/5.15.19/kernel/drivers/video/nvidia.ko\n";
+        let package = ModulePackage {
+            package: "nvidia-kernel-470.86_5.15.19-x86_64-1_SBo".to_string(),
+            built_for: module_releases_in(content),
+        };
+        assert_eq!(package.built_for, vec!["5.15.19"]);
+        assert_eq!(package.name(), "nvidia-kernel");
+        assert!(package.is_sbo());
+    }
+}
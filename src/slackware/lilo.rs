@@ -0,0 +1,358 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::slackware::config;
+use crate::utils::error::{AppError, Result};
+use crate::utils::{root, scratch};
+
+pub const LILO_CONF: &str = "/etc/lilo.conf";
+
+/// LILO rejects labels longer than this
+pub const MAX_LABEL_LEN: usize = 15;
+
+/// A label for the kernel installed as vmlinuz-`version` that fits LILO
+pub fn label_for(version: &str) -> String {
+    let label = version.replace("generic-", "gen-");
+    label.chars().take(MAX_LABEL_LEN).collect()
+}
+
+/// An `image =` or `other =` stanza of lilo.conf
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stanza {
+    /// "image" or "other"
+    pub kind: String,
+    pub image: String,
+    pub label: Option<String>,
+    /// Every `key = value` (or bare flag) line of the stanza, in order
+    pub options: Vec<(String, Option<String>)>,
+    /// Lines of the stanza, from its image line to before the next stanza
+    start: usize,
+    end: usize,
+}
+
+impl Stanza {
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// File name of the image, e.g. "vmlinuz-generic-5.15.19"
+    pub fn image_file(&self) -> &str {
+        Path::new(&self.image)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&self.image)
+    }
+}
+
+/// Split a lilo.conf line into its key and value, without comments
+fn key_value(line: &str) -> Option<(String, Option<String>)> {
+    let line = line.split('#').next()?.trim();
+    if line.is_empty() {
+        return None;
+    }
+    Some(match line.split_once('=') {
+        Some((key, value)) => (
+            key.trim().to_string(),
+            Some(value.trim().trim_matches('"').to_string()),
+        ),
+        None => (line.to_string(), None),
+    })
+}
+
+/// Quote values lilo would otherwise split, e.g. `append = "quiet vt.default_utf8=1"`
+fn format_value(value: &str) -> String {
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// lilo.conf kept as lines so edits leave the rest of the file untouched
+#[derive(Debug, Clone, Default)]
+pub struct LiloConf {
+    lines: Vec<String>,
+}
+
+impl LiloConf {
+    pub fn load() -> Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(LILO_CONF)?))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(String::from).collect(),
+        }
+    }
+
+    pub fn to_content(&self) -> String {
+        self.lines.join("\n") + "\n"
+    }
+
+    pub fn stanzas(&self) -> Vec<Stanza> {
+        let mut stanzas: Vec<Stanza> = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            let Some((key, value)) = key_value(line) else {
+                continue;
+            };
+            if key == "image" || key == "other" {
+                if let Some(last) = stanzas.last_mut() {
+                    last.end = i;
+                }
+                stanzas.push(Stanza {
+                    kind: key,
+                    image: value.unwrap_or_default(),
+                    label: None,
+                    options: Vec::new(),
+                    start: i,
+                    end: self.lines.len(),
+                });
+            } else if let Some(stanza) = stanzas.last_mut() {
+                if key == "label" {
+                    stanza.label = value.clone();
+                }
+                stanza.options.push((key, value));
+            }
+        }
+        stanzas
+    }
+
+    /// Set or remove `key` among `lines[start..end]`, inserting new lines
+    /// at `insert_at` with the indentation already used there
+    fn set_in_range(
+        &mut self,
+        (start, end): (usize, usize),
+        insert_at: usize,
+        key: &str,
+        value: Option<&str>,
+    ) {
+        let existing =
+            (start..end).find(|&i| key_value(&self.lines[i]).is_some_and(|(k, _)| k == key));
+        let indent = self.lines[start..end]
+            .iter()
+            .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+            .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+            .unwrap_or_else(|| if start == 0 { String::new() } else { "  ".to_string() });
+        let line = value.map(|v| format!("{}{} = {}", indent, key, format_value(v)));
+        match (existing, line) {
+            (Some(i), Some(line)) => self.lines[i] = line,
+            (Some(i), None) => {
+                self.lines.remove(i);
+            }
+            (None, Some(line)) => self.lines.insert(insert_at, line),
+            (None, None) => {}
+        }
+    }
+
+    /// Index of the first stanza line; everything before it is global
+    fn globals_end(&self) -> usize {
+        self.stanzas().first().map_or(self.lines.len(), |s| s.start)
+    }
+
+    /// A global option such as `timeout` or `default`
+    pub fn global(&self, key: &str) -> Option<String> {
+        self.lines[..self.globals_end()]
+            .iter()
+            .filter_map(|l| key_value(l))
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v)
+    }
+
+    /// Set (or, with `None`, remove) a global option
+    pub fn set_global(&mut self, key: &str, value: Option<&str>) {
+        let end = self.globals_end();
+        // New globals go after the last option, not after the stanza's comments
+        let insert_at = (0..end)
+            .rev()
+            .find(|&i| key_value(&self.lines[i]).is_some())
+            .map_or(0, |i| i + 1);
+        self.set_in_range((0, end), insert_at, key, value);
+    }
+
+    /// Set (or, with `None`, remove) an option of the `index`th stanza.
+    /// Returns false when there is no such stanza.
+    pub fn set_option(&mut self, index: usize, key: &str, value: Option<&str>) -> bool {
+        let Some(stanza) = self.stanzas().into_iter().nth(index) else {
+            return false;
+        };
+        self.set_in_range((stanza.start + 1, stanza.end), stanza.start + 1, key, value);
+        true
+    }
+
+    /// Set (or, with `None`, remove) an option of the stanza booting
+    /// `image_file`. Returns false when no stanza boots it.
+    pub fn set_image_option(&mut self, image_file: &str, key: &str, value: Option<&str>) -> bool {
+        match self.stanzas().iter().position(|s| s.image_file() == image_file) {
+            Some(index) => self.set_option(index, key, value),
+            None => false,
+        }
+    }
+
+    /// Rename the `index`th stanza, keeping `default =` pointing at it
+    pub fn set_label(&mut self, index: usize, label: &str) -> Result<()> {
+        let stanzas = self.stanzas();
+        let Some(stanza) = stanzas.get(index) else {
+            return Err(AppError::Config("No such boot entry".to_string()));
+        };
+        if label.is_empty() || label.len() > MAX_LABEL_LEN || label.contains(char::is_whitespace)
+        {
+            return Err(AppError::Config(format!(
+                "Labels must be 1-{} characters without spaces",
+                MAX_LABEL_LEN
+            )));
+        }
+        if stanzas
+            .iter()
+            .enumerate()
+            .any(|(i, s)| i != index && s.label.as_deref() == Some(label))
+        {
+            return Err(AppError::Config(format!("Label '{}' is already used", label)));
+        }
+        if stanza.label.is_some() && self.global("default") == stanza.label {
+            self.set_global("default", Some(label));
+        }
+        self.set_option(index, "label", Some(label));
+        Ok(())
+    }
+
+    /// Drop the `index`th stanza. Returns false when there is no such stanza.
+    pub fn remove_stanza(&mut self, index: usize) -> bool {
+        let Some(stanza) = self.stanzas().into_iter().nth(index) else {
+            return false;
+        };
+        self.lines.drain(stanza.start..stanza.end);
+        true
+    }
+
+    /// Append an image stanza, copying the root device and other options of
+    /// `template` so the new kernel boots the same system
+    pub fn add_image(&mut self, image: &str, label: &str, template: Option<&Stanza>) {
+        let mut lines = vec![format!("image = {}", image)];
+        lines.push(format!("  label = {}", label));
+        for (key, value) in template.map(|t| t.options.as_slice()).unwrap_or_default() {
+            if key == "label" || key == "initrd" {
+                continue;
+            }
+            lines.push(match value {
+                Some(value) => format!("  {} = {}", key, value),
+                None => format!("  {}", key),
+            });
+        }
+        if template.is_none() {
+            lines.push("  read-only".to_string());
+        }
+        self.lines.extend(lines);
+    }
+
+    /// Drop every stanza booting `image_file`, returning how many there were
+    pub fn remove_stanzas(&mut self, image_file: &str) -> usize {
+        let stanzas: Vec<Stanza> = self
+            .stanzas()
+            .into_iter()
+            .filter(|s| s.image_file() == image_file)
+            .collect();
+        for stanza in stanzas.iter().rev() {
+            self.lines.drain(stanza.start..stanza.end);
+        }
+        stanzas.len()
+    }
+
+    /// Write lilo.conf, returning the path of the backup copy
+    pub fn save(&self) -> Result<PathBuf> {
+        config::write_with_backup(Path::new(LILO_CONF), &self.to_content())
+    }
+
+    /// Check this configuration with `lilo -t`, which reports what lilo
+    /// would do without touching the boot sector or map file. Returns
+    /// lilo's output.
+    pub fn test(&self) -> Result<String> {
+        // In a directory of its own, so no other user can put a symlink
+        // where root is about to write
+        let dir = scratch::private_dir("lilo-test")?;
+        let path = dir.join("lilo.conf");
+        let output = fs::write(&path, self.to_content()).and_then(|()| {
+            root::command("lilo")
+                .arg("-t")
+                .arg("-C")
+                .arg(&path)
+                .output()
+        });
+        let _ = fs::remove_dir_all(&dir);
+        let output = output?;
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        if !output.status.success() {
+            return Err(AppError::CommandFailed(format!("lilo -t: {}", text.trim())));
+        }
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stanzas() {
+        let mut conf = LiloConf::parse(
+            "boot = /dev/sda\ntimeout = 50\n\
+             image = /boot/vmlinuz-generic-5.15.19\n  root = /dev/sda1\n  label = Linux\n  read-only\n\
+             other = /dev/sda3\n  label = Windows\n",
+        );
+        let stanzas = conf.stanzas();
+        assert_eq!(stanzas.len(), 2);
+        assert_eq!(stanzas[0].label.as_deref(), Some("Linux"));
+        assert_eq!(stanzas[0].option("root"), Some("/dev/sda1"));
+        assert_eq!(stanzas[1].kind, "other");
+
+        assert!(conf.set_image_option(
+            "vmlinuz-generic-5.15.19",
+            "initrd",
+            Some("/boot/initrd-generic-5.15.19.gz")
+        ));
+        assert_eq!(
+            conf.stanzas()[0].option("initrd"),
+            Some("/boot/initrd-generic-5.15.19.gz")
+        );
+        assert!(conf.set_image_option("vmlinuz-generic-5.15.19", "root", None));
+        assert_eq!(conf.stanzas()[0].option("root"), None);
+        assert!(!conf.set_image_option("vmlinuz-huge", "initrd", None));
+    }
+
+    #[test]
+    fn test_edit() {
+        let mut conf = LiloConf::parse(
+            "boot = /dev/sda
+default = Linux
+# Linux bootable partition config begins
+             image = /boot/vmlinuz
+  root = /dev/sda1
+  label = Linux
+             image = /boot/vmlinuz-huge
+  label = Huge
+",
+        );
+        conf.set_global("timeout", Some("50"));
+        assert_eq!(conf.global("timeout").as_deref(), Some("50"));
+        assert_eq!(conf.to_content().lines().nth(2), Some("timeout = 50"));
+
+        assert!(conf.set_label(0, "Huge").is_err());
+        assert!(conf.set_label(0, "a-label-far-too-long").is_err());
+        conf.set_label(0, "Slackware").unwrap();
+        assert_eq!(conf.global("default").as_deref(), Some("Slackware"));
+
+        conf.set_option(0, "append", Some("quiet vt.default_utf8=1"));
+        assert!(conf.to_content().contains("  append = \"quiet vt.default_utf8=1\"\n"));
+        assert_eq!(conf.stanzas()[0].option("append"), Some("quiet vt.default_utf8=1"));
+
+        assert!(conf.remove_stanza(1));
+        assert!(!conf.remove_stanza(1));
+        assert_eq!(conf.stanzas().len(), 1);
+    }
+}
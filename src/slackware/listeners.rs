@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::process::Command;
+
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// How reachable a listening socket is from the network
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Exposure {
+    /// Bound to the wildcard address, reachable on every interface
+    AllInterfaces,
+    /// Bound to the address of specific interfaces
+    Interfaces(Vec<String>),
+    /// Only reachable from this machine
+    Loopback,
+}
+
+impl Exposure {
+    pub fn is_public(&self) -> bool {
+        *self != Exposure::Loopback
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Exposure::AllInterfaces => "all interfaces".to_string(),
+            Exposure::Interfaces(names) if names.is_empty() => "unknown interface".to_string(),
+            Exposure::Interfaces(names) => names.join(", "),
+            Exposure::Loopback => "local only".to_string(),
+        }
+    }
+}
+
+/// A TCP or UDP socket waiting for connections
+#[derive(Debug, Clone, PartialEq)]
+pub struct Listener {
+    pub proto: String,
+    pub address: String,
+    pub port: u16,
+    /// Owning process names; empty when ss could not tell (not root)
+    pub processes: Vec<String>,
+    pub exposure: Exposure,
+}
+
+/// Parse a line of `ss -H -tulnp`:
+/// `tcp LISTEN 0 128 0.0.0.0:22 0.0.0.0:* users:(("sshd",pid=1,fd=3))`
+fn parse_ss_line(line: &str, addresses: &HashMap<IpAddr, String>) -> Option<Listener> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let proto = fields.first()?.to_string();
+    let local = fields.get(4)?;
+    let (address, port) = local.rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    // Link-local and interface-bound sockets carry a "%iface" scope
+    let (address, scope) = match address.split_once('%') {
+        Some((address, scope)) => (address, Some(scope)),
+        None => (address, None),
+    };
+    let address = address.trim_start_matches('[').trim_end_matches(']');
+
+    let exposure = match address {
+        "*" | "0.0.0.0" | "::" => match scope {
+            Some(iface) => Exposure::Interfaces(vec![iface.to_string()]),
+            None => Exposure::AllInterfaces,
+        },
+        _ => match address.parse::<IpAddr>() {
+            Ok(ip) if ip.is_loopback() => Exposure::Loopback,
+            Ok(ip) => Exposure::Interfaces(
+                scope
+                    .map(String::from)
+                    .or_else(|| addresses.get(&ip).cloned())
+                    .into_iter()
+                    .collect(),
+            ),
+            Err(_) => Exposure::Interfaces(scope.map(String::from).into_iter().collect()),
+        },
+    };
+
+    let mut processes: Vec<String> = fields[5..]
+        .iter()
+        .filter_map(|f| f.strip_prefix("users:"))
+        .flat_map(|users| users.split("(\"").skip(1))
+        .filter_map(|u| u.split('"').next())
+        .map(String::from)
+        .collect();
+    processes.sort();
+    processes.dedup();
+
+    Some(Listener {
+        proto,
+        address: address.to_string(),
+        port,
+        processes,
+        exposure,
+    })
+}
+
+/// Map of configured addresses to the interface holding them
+fn interface_addresses() -> HashMap<IpAddr, String> {
+    let output = Command::new("ip").args(["-o", "addr", "show"]).output();
+    let Ok(output) = output else {
+        return HashMap::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            // "2: eth0    inet 192.168.1.5/24 brd ... scope global eth0"
+            let mut words = line.split_whitespace().skip(1);
+            let iface = words.next()?;
+            let address = words.nth(1)?.split('/').next()?.parse().ok()?;
+            Some((address, iface.to_string()))
+        })
+        .collect()
+}
+
+/// Listening TCP and UDP sockets, most exposed first
+pub fn list() -> Result<Vec<Listener>> {
+    let output = root::command("ss").args(["-H", "-tulnp"]).output()?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "ss: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let addresses = interface_addresses();
+    let mut listeners: Vec<Listener> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|l| parse_ss_line(l, &addresses))
+        .collect();
+    listeners.sort_by(|a, b| (&a.exposure, a.port, &a.proto).cmp(&(&b.exposure, b.port, &b.proto)));
+    listeners.dedup_by(|a, b| a.port == b.port && a.proto == b.proto && a.exposure == b.exposure);
+    Ok(listeners)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ss_line() {
+        let mut addresses = HashMap::new();
+        addresses.insert("192.168.1.5".parse().unwrap(), "eth0".to_string());
+
+        let sshd = parse_ss_line(
+            "tcp LISTEN 0 128 0.0.0.0:22 0.0.0.0:* users:((\"sshd\",pid=1,fd=3))",
+            &addresses,
+        )
+        .unwrap();
+        assert_eq!(sshd.port, 22);
+        assert_eq!(sshd.exposure, Exposure::AllInterfaces);
+        assert_eq!(sshd.processes, vec!["sshd"]);
+
+        let dns = parse_ss_line("udp UNCONN 0 0 127.0.0.1:53 0.0.0.0:*", &addresses).unwrap();
+        assert_eq!(dns.exposure, Exposure::Loopback);
+        assert!(dns.processes.is_empty());
+
+        let web = parse_ss_line("tcp LISTEN 0 511 192.168.1.5:80 0.0.0.0:*", &addresses).unwrap();
+        assert_eq!(web.exposure, Exposure::Interfaces(vec!["eth0".to_string()]));
+
+        let v6 = parse_ss_line("tcp LISTEN 0 128 [::]:22 [::]:*", &addresses).unwrap();
+        assert_eq!(v6.exposure, Exposure::AllInterfaces);
+    }
+}
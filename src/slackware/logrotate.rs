@@ -0,0 +1,262 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::slackware::audit;
+use crate::slackware::config;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+pub const LOGROTATE_CONF: &str = "/etc/logrotate.conf";
+pub const LOGROTATE_DIR: &str = "/etc/logrotate.d";
+
+const FREQUENCIES: &[&str] = &["hourly", "daily", "weekly", "monthly", "yearly"];
+
+/// The rotation settings this tool shows and edits
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Directives {
+    pub frequency: Option<String>,
+    pub rotate: Option<u32>,
+    pub size: Option<String>,
+    pub compress: Option<bool>,
+}
+
+impl Directives {
+    fn apply(&mut self, line: &str) {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some(word) if FREQUENCIES.contains(&word) => self.frequency = Some(word.to_string()),
+            Some("rotate") => self.rotate = words.next().and_then(|n| n.parse().ok()),
+            Some("size") => self.size = words.next().map(String::from),
+            Some("compress") => self.compress = Some(true),
+            Some("nocompress") => self.compress = Some(false),
+            _ => {}
+        }
+    }
+
+    /// Fill unset settings from the global defaults
+    pub fn or(&self, defaults: &Directives) -> Directives {
+        Directives {
+            frequency: self
+                .frequency
+                .clone()
+                .or_else(|| defaults.frequency.clone()),
+            rotate: self.rotate.or(defaults.rotate),
+            size: self.size.clone().or_else(|| defaults.size.clone()),
+            compress: self.compress.or(defaults.compress),
+        }
+    }
+}
+
+/// A `path... { ... }` block of a logrotate config file
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotationPolicy {
+    pub source: PathBuf,
+    pub logs: Vec<String>,
+    pub directives: Directives,
+    /// Lines of the opening "{" and the closing "}" in the source file
+    open_line: usize,
+    close_line: usize,
+}
+
+/// A change to one policy
+#[derive(Debug, Clone, PartialEq)]
+pub enum Setting {
+    Rotate(u32),
+    /// Rotate once the log grows past this size (e.g. "10M"); None removes it
+    Size(Option<String>),
+    Compress(bool),
+}
+
+/// Parse one config file into its global directives and log blocks
+pub fn parse(source: &Path, content: &str) -> (Directives, Vec<RotationPolicy>) {
+    let mut globals = Directives::default();
+    let mut policies = Vec::new();
+    let mut current: Option<RotationPolicy> = None;
+    let mut in_script = false;
+    // Log names may come on the lines before the "{"
+    let mut pending_logs: Vec<String> = Vec::new();
+
+    for (i, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if in_script {
+            in_script = line != "endscript";
+            continue;
+        }
+        match &mut current {
+            Some(policy) => {
+                if line == "}" {
+                    policy.close_line = i;
+                    policies.extend(current.take());
+                } else if matches!(
+                    line,
+                    "postrotate" | "prerotate" | "firstaction" | "lastaction" | "preremove"
+                ) {
+                    in_script = true;
+                } else {
+                    policy.directives.apply(line);
+                }
+            }
+            None => {
+                if let Some(names) = line.strip_suffix('{') {
+                    pending_logs.extend(names.split_whitespace().map(String::from));
+                    current = Some(RotationPolicy {
+                        source: source.to_path_buf(),
+                        logs: std::mem::take(&mut pending_logs),
+                        directives: Directives::default(),
+                        open_line: i,
+                        close_line: i,
+                    });
+                } else if line.starts_with('/') {
+                    pending_logs.extend(line.split_whitespace().map(String::from));
+                } else {
+                    globals.apply(line);
+                }
+            }
+        }
+    }
+    (globals, policies)
+}
+
+/// Global defaults from logrotate.conf and every policy on the system
+pub fn load() -> (Directives, Vec<RotationPolicy>) {
+    let main = Path::new(LOGROTATE_CONF);
+    let (globals, mut policies) = parse(main, &fs::read_to_string(main).unwrap_or_default());
+
+    let mut files: Vec<PathBuf> = fs::read_dir(LOGROTATE_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && !is_ignored(p))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    for file in files {
+        if let Ok(content) = fs::read_to_string(&file) {
+            policies.extend(parse(&file, &content).1);
+        }
+    }
+    (globals, policies)
+}
+
+/// Files logrotate skips when including a directory (its "tabooext" list)
+fn is_ignored(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    name.ends_with('~')
+        || [".swp", ".tmp", ".new", ".orig", ".disabled"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+}
+
+/// Change a setting of a policy in its config file
+pub fn apply(policy: &RotationPolicy, setting: &Setting) -> Result<PathBuf> {
+    let content = fs::read_to_string(&policy.source)?;
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    // The file may have changed since it was parsed
+    let current = parse(&policy.source, &content)
+        .1
+        .into_iter()
+        .find(|p| p.logs == policy.logs)
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "{} no longer configures {}",
+                policy.source.display(),
+                policy.logs.join(" ")
+            ))
+        })?;
+
+    let (keywords, replacement): (&[&str], Option<String>) = match setting {
+        Setting::Rotate(count) => (&["rotate"], Some(format!("rotate {}", count))),
+        Setting::Size(size) => (&["size"], size.as_ref().map(|s| format!("size {}", s))),
+        Setting::Compress(on) => (
+            &["compress", "nocompress"],
+            Some(if *on { "compress" } else { "nocompress" }.to_string()),
+        ),
+    };
+
+    let body = current.open_line + 1..current.close_line;
+    let indent = lines[body.clone()]
+        .iter()
+        .find(|l| !l.trim().is_empty())
+        .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+        .unwrap_or_else(|| "    ".to_string());
+    let existing: Vec<usize> = body
+        .filter(|&i| {
+            lines[i]
+                .split_whitespace()
+                .next()
+                .is_some_and(|w| keywords.contains(&w))
+        })
+        .collect();
+
+    // Rewrite the first occurrence in place and drop any duplicates
+    let remove = match (existing.first(), replacement) {
+        (Some(&first), Some(line)) => {
+            lines[first] = format!("{}{}", indent, line);
+            &existing[1..]
+        }
+        (None, Some(line)) => {
+            lines.insert(current.close_line, format!("{}{}", indent, line));
+            &existing[..]
+        }
+        (_, None) => &existing[..],
+    };
+    for &i in remove.iter().rev() {
+        lines.remove(i);
+    }
+
+    let path = policy.source.as_path();
+    let content = lines.join("\n") + "\n";
+    if path.starts_with(LOGROTATE_DIR) {
+        // logrotate would read a ".bak" copy in its include directory
+        let mut backup = path.as_os_str().to_owned();
+        backup.push("~");
+        config::write_with_backup_to(path, &content, PathBuf::from(backup))
+    } else {
+        config::write_with_backup(path, &content)
+    }
+}
+
+/// Run logrotate the way the daily cron job does, returning its output
+pub fn run_now() -> Result<String> {
+    let output = audit::output(root::command("logrotate").arg(LOGROTATE_CONF))?;
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!("logrotate: {}", stderr)));
+    }
+    Ok(stderr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_policies() {
+        let content = "weekly\nrotate 4\ncompress\n\n\
+            /var/log/wtmp {\n    monthly\n    rotate 1\n}\n\n\
+            /var/log/messages /var/log/syslog {\n    size 10M\n    nocompress\n    \
+            postrotate\n        /bin/kill -HUP 1\n    endscript\n}\n";
+        let (globals, policies) = parse(Path::new(LOGROTATE_CONF), content);
+        assert_eq!(globals.frequency.as_deref(), Some("weekly"));
+        assert_eq!(globals.rotate, Some(4));
+        assert_eq!(policies.len(), 2);
+        assert_eq!(policies[0].directives.rotate, Some(1));
+        assert_eq!(
+            policies[1].logs,
+            vec!["/var/log/messages", "/var/log/syslog"]
+        );
+
+        let effective = policies[1].directives.or(&globals);
+        assert_eq!(effective.rotate, Some(4));
+        assert_eq!(effective.size.as_deref(), Some("10M"));
+        assert_eq!(effective.compress, Some(false));
+    }
+}
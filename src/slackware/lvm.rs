@@ -0,0 +1,286 @@
+use crate::slackware::audit;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// A physical volume
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalVolume {
+    pub name: String,
+    /// Empty when the PV is not in a volume group yet
+    pub vg: String,
+    pub size: u64,
+    pub free: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeGroup {
+    pub name: String,
+    pub size: u64,
+    pub free: u64,
+    pub extent_size: u64,
+    pub free_extents: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicalVolume {
+    pub vg: String,
+    pub name: String,
+    pub size: u64,
+    /// lv_attr, e.g. "-wi-ao----"; the fifth character is 'a' when active
+    /// and the sixth 'o' when open (mounted or in use)
+    pub attr: String,
+}
+
+impl LogicalVolume {
+    pub fn path(&self) -> String {
+        format!("/dev/{}/{}", self.vg, self.name)
+    }
+
+    /// The node under /dev/mapper, which is what df and /proc/mounts show
+    pub fn mapper_path(&self) -> String {
+        format!("/dev/mapper/{}", dm_name(&self.vg, &self.name))
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.attr.chars().nth(5) == Some('o')
+    }
+}
+
+/// Device-mapper name of an LV: hyphens inside the names are doubled
+pub fn dm_name(vg: &str, lv: &str) -> String {
+    format!("{}-{}", vg.replace('-', "--"), lv.replace('-', "--"))
+}
+
+/// Everything LVM knows about, as the lvm2 reporting tools print it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Layout {
+    pub pvs: Vec<PhysicalVolume>,
+    pub vgs: Vec<VolumeGroup>,
+    pub lvs: Vec<LogicalVolume>,
+}
+
+impl Layout {
+    /// "vg/lv" for a /dev/mapper node or lsblk name that is an LV
+    pub fn lv_label(&self, device: &str) -> Option<String> {
+        let name = device.trim_start_matches("/dev/mapper/");
+        self.lvs
+            .iter()
+            .find(|lv| dm_name(&lv.vg, &lv.name) == name)
+            .map(|lv| format!("{}/{}", lv.vg, lv.name))
+    }
+}
+
+/// Split `--noheadings --separator '|'` report rows into fields
+fn rows(output: &str) -> Vec<Vec<&str>> {
+    output
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.trim().split('|').map(str::trim).collect())
+        .collect()
+}
+
+fn number(field: &str) -> u64 {
+    field.parse().unwrap_or(0)
+}
+
+pub fn parse_pvs(output: &str) -> Vec<PhysicalVolume> {
+    rows(output)
+        .into_iter()
+        .filter_map(|f| match f[..] {
+            [name, vg, size, free] => Some(PhysicalVolume {
+                name: name.to_string(),
+                vg: vg.to_string(),
+                size: number(size),
+                free: number(free),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn parse_vgs(output: &str) -> Vec<VolumeGroup> {
+    rows(output)
+        .into_iter()
+        .filter_map(|f| match f[..] {
+            [name, size, free, extent_size, free_extents] => Some(VolumeGroup {
+                name: name.to_string(),
+                size: number(size),
+                free: number(free),
+                extent_size: number(extent_size),
+                free_extents: number(free_extents),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn parse_lvs(output: &str) -> Vec<LogicalVolume> {
+    rows(output)
+        .into_iter()
+        .filter_map(|f| match f[..] {
+            [vg, name, size, attr] => Some(LogicalVolume {
+                vg: vg.to_string(),
+                name: name.to_string(),
+                size: number(size),
+                attr: attr.to_string(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn report(cmd: &str, fields: &str) -> Result<String> {
+    let output = root::command(cmd)
+        .args([
+            "--noheadings",
+            "--units",
+            "b",
+            "--nosuffix",
+            "--separator",
+            "|",
+            "-o",
+            fields,
+        ])
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// PVs, VGs and LVs; empty when lvm2 is missing or nothing is set up
+pub fn load() -> Layout {
+    let read = |cmd, fields| report(cmd, fields).unwrap_or_default();
+    Layout {
+        pvs: parse_pvs(&read("pvs", "pv_name,vg_name,pv_size,pv_free")),
+        vgs: parse_vgs(&read(
+            "vgs",
+            "vg_name,vg_size,vg_free,vg_extent_size,vg_free_count",
+        )),
+        lvs: parse_lvs(&read("lvs", "vg_name,lv_name,lv_size,lv_attr")),
+    }
+}
+
+/// Whether lvcreate/lvextend take `size`: a number with an optional
+/// k/m/g/t unit for -L, or "N%FREE" / "N%VG" for -l
+pub fn is_valid_size(size: &str) -> bool {
+    let size = size.trim();
+    if let Some((percent, of)) = size.split_once('%') {
+        return matches!(of, "FREE" | "VG")
+            && percent.parse::<u32>().is_ok_and(|p| p > 0 && p <= 100);
+    }
+    let number = size.trim_end_matches(|c: char| "kKmMgGtT".contains(c));
+    size.len() - number.len() <= 1 && number.parse::<f64>().is_ok_and(|n| n > 0.0)
+}
+
+/// LV names lvm accepts, minus the reserved ones
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+_.-".contains(c))
+}
+
+/// The size argument pair for a size the user typed
+fn size_args(size: &str, grow: bool) -> [String; 2] {
+    let size = size.trim().trim_start_matches('+');
+    let prefix = if grow { "+" } else { "" };
+    if size.contains('%') {
+        ["-l".to_string(), format!("{}{}", prefix, size)]
+    } else {
+        ["-L".to_string(), format!("{}{}", prefix, size)]
+    }
+}
+
+/// A change to the LVM layout
+#[derive(Debug, Clone, PartialEq)]
+pub enum LvmOp {
+    Create {
+        vg: String,
+        name: String,
+        size: String,
+    },
+    /// Grow an LV by `size` and the filesystem on it along with it
+    Extend { lv: String, size: String },
+}
+
+impl LvmOp {
+    pub fn command(&self) -> (&'static str, Vec<String>) {
+        match self {
+            LvmOp::Create { vg, name, size } => {
+                let mut args = vec!["-n".to_string(), name.clone()];
+                args.extend(size_args(size, false));
+                args.push(vg.clone());
+                ("lvcreate", args)
+            }
+            LvmOp::Extend { lv, size } => {
+                // -r runs fsadm to resize the filesystem, online where it can
+                let mut args = vec!["-r".to_string()];
+                args.extend(size_args(size, true));
+                args.push(lv.clone());
+                ("lvextend", args)
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let (cmd, args) = self.command();
+        format!("{} {}", cmd, args.join(" "))
+    }
+}
+
+pub fn apply(op: &LvmOp) -> Result<()> {
+    let (cmd, args) = op.command();
+    let output = audit::output(root::command(cmd).args(&args))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let vgs = parse_vgs("  vg0|499826819072|107374182400|4194304|25600\n");
+        assert_eq!(vgs.len(), 1);
+        assert_eq!(vgs[0].free_extents, 25600);
+        let lvs = parse_lvs(
+            "  vg0|root|42949672960|-wi-ao----\n  vg0|scratch-space|1073741824|-wi-a-----\n",
+        );
+        assert!(lvs[0].is_open());
+        assert!(!lvs[1].is_open());
+        assert_eq!(lvs[1].mapper_path(), "/dev/mapper/vg0-scratch--space");
+        let layout = Layout {
+            lvs,
+            ..Layout::default()
+        };
+        assert_eq!(
+            layout.lv_label("/dev/mapper/vg0-scratch--space"),
+            Some("vg0/scratch-space".to_string())
+        );
+
+        assert!(is_valid_size("20G"));
+        assert!(is_valid_size("100%FREE"));
+        assert!(!is_valid_size("20GB"));
+        assert!(!is_valid_size("150%VG"));
+        let op = LvmOp::Extend {
+            lv: "/dev/vg0/home".to_string(),
+            size: "+5G".to_string(),
+        };
+        assert_eq!(op.describe(), "lvextend -r -L +5G /dev/vg0/home");
+    }
+}
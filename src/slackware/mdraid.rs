@@ -0,0 +1,177 @@
+use std::fs;
+
+use regex::Regex;
+
+const MDSTAT: &str = "/proc/mdstat";
+
+/// A member device of an array
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub device: String,
+    /// Slot number in the array
+    pub slot: u32,
+    pub faulty: bool,
+    pub spare: bool,
+}
+
+/// A resync, recovery, reshape or check in progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sync {
+    pub action: String,
+    pub percent: f32,
+    /// Estimated time left as mdstat prints it, e.g. "81.3min"
+    pub finish: String,
+    pub speed: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Array {
+    pub name: String,
+    /// "active" or "inactive", plus "(read-only)" and the like
+    pub state: String,
+    pub level: String,
+    pub members: Vec<Member>,
+    /// Devices the array wants and has, from "[2/1]"
+    pub wanted: u32,
+    pub working: u32,
+    /// Per-slot up/down map, e.g. "[U_]"
+    pub map: String,
+    pub sync: Option<Sync>,
+}
+
+impl Array {
+    pub fn is_degraded(&self) -> bool {
+        self.working < self.wanted || self.members.iter().any(|m| m.faulty)
+    }
+
+    /// Whether the slot of a member is up in the status map
+    pub fn member_up(&self, member: &Member) -> bool {
+        !member.faulty
+            && !member.spare
+            && self
+                .map
+                .trim_matches(|c| c == '[' || c == ']')
+                .chars()
+                .nth(member.slot as usize)
+                .map_or(true, |c| c == 'U')
+    }
+}
+
+/// Arrays in /proc/mdstat content
+pub fn parse(mdstat: &str) -> Vec<Array> {
+    let member_re = Regex::new(r"^(\S+)\[(\d+)\](\(\w\))*$").expect("valid regex");
+    let count_re = Regex::new(r"\[(\d+)/(\d+)\]\s+(\[[U_]+\])").expect("valid regex");
+    let sync_re = Regex::new(
+        r"(resync|recovery|reshape|check|repair)\s*=\s*([\d.]+)%.*?finish=(\S+)\s+speed=(\S+)",
+    )
+    .expect("valid regex");
+
+    let mut arrays: Vec<Array> = Vec::new();
+    for line in mdstat.lines() {
+        if let Some((name, rest)) = line.split_once(" : ") {
+            if !name.starts_with("md") {
+                continue;
+            }
+            let words: Vec<&str> = rest.split_whitespace().collect();
+            let state_end = words
+                .iter()
+                .position(|w| w.starts_with("raid") || *w == "linear" || w.contains('['))
+                .unwrap_or(words.len());
+            let level = words
+                .get(state_end)
+                .filter(|w| !w.contains('['))
+                .map(|w| w.to_string())
+                .unwrap_or_default();
+            let members = words
+                .iter()
+                .filter_map(|word| {
+                    let c = member_re.captures(word)?;
+                    Some(Member {
+                        device: c[1].to_string(),
+                        slot: c[2].parse().ok()?,
+                        faulty: word.contains("(F)"),
+                        spare: word.contains("(S)"),
+                    })
+                })
+                .collect();
+            arrays.push(Array {
+                name: name.trim().to_string(),
+                state: words[..state_end].join(" "),
+                level,
+                members,
+                wanted: 0,
+                working: 0,
+                map: String::new(),
+                sync: None,
+            });
+        } else if let Some(array) = arrays.last_mut() {
+            if let Some(c) = count_re.captures(line) {
+                array.wanted = c[1].parse().unwrap_or(0);
+                array.working = c[2].parse().unwrap_or(0);
+                array.map = c[3].to_string();
+            } else if let Some(c) = sync_re.captures(line) {
+                array.sync = Some(Sync {
+                    action: c[1].to_string(),
+                    percent: c[2].parse().unwrap_or(0.0),
+                    finish: c[3].to_string(),
+                    speed: c[4].to_string(),
+                });
+            }
+        }
+    }
+    arrays
+}
+
+/// Arrays on this machine; empty without md support
+pub fn arrays() -> Vec<Array> {
+    fs::read_to_string(MDSTAT)
+        .map(|content| parse(&content))
+        .unwrap_or_default()
+}
+
+/// Names of the arrays that are missing or have failed devices
+pub fn degraded() -> Vec<String> {
+    arrays()
+        .into_iter()
+        .filter(Array::is_degraded)
+        .map(|a| a.name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let arrays = parse(
+            "Personalities : [raid1] [raid6] [raid5] [raid4]\n\
+             md0 : active raid1 sdb1[1] sda1[0](F)\n\
+             \x20     976630464 blocks super 1.2 [2/1] [_U]\n\
+             \x20     [===>.................]  recovery = 17.4% (170278528/976630464) \
+             finish=81.3min speed=165128K/sec\n\
+             \x20     bitmap: 2/8 pages [8KB], 65536KB chunk\n\
+             \n\
+             md1 : active (auto-read-only) raid5 sde1[3] sdd1[1] sdc1[0] sdf1[4](S)\n\
+             \x20     1953262592 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/3] [UUU]\n\
+             \n\
+             unused devices: <none>\n",
+        );
+        assert_eq!(arrays.len(), 2);
+        let md0 = &arrays[0];
+        assert_eq!(md0.level, "raid1");
+        assert!(md0.is_degraded());
+        assert!(md0.members[1].faulty);
+        assert!(md0.member_up(&md0.members[0]));
+        let sync = md0.sync.as_ref().unwrap();
+        assert_eq!(sync.action, "recovery");
+        assert_eq!(sync.finish, "81.3min");
+
+        let md1 = &arrays[1];
+        assert_eq!(md1.state, "active (auto-read-only)");
+        assert_eq!(md1.level, "raid5");
+        assert_eq!(md1.members.len(), 4);
+        assert!(md1.members[3].spare);
+        assert!(!md1.is_degraded());
+    }
+}
@@ -0,0 +1,233 @@
+use std::fs;
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+use std::path::{Path, PathBuf};
+
+use nix::sys::stat;
+
+use tokio::sync::mpsc;
+
+use crate::slackware::commands;
+use crate::slackware::cron::RunEvent;
+use crate::utils::error::{AppError, Result};
+
+/// Filesystems the format wizard can create
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filesystem {
+    Ext4,
+    Xfs,
+    Btrfs,
+    Fat,
+}
+
+impl Filesystem {
+    pub const ALL: [Filesystem; 4] = [
+        Filesystem::Ext4,
+        Filesystem::Xfs,
+        Filesystem::Btrfs,
+        Filesystem::Fat,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Filesystem::Ext4 => "ext4",
+            Filesystem::Xfs => "xfs",
+            Filesystem::Btrfs => "btrfs",
+            Filesystem::Fat => "fat",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Filesystem::Ext4 => "Slackware's default, good for / and /home",
+            Filesystem::Xfs => "Large files and volumes; cannot be shrunk",
+            Filesystem::Btrfs => "Snapshots, checksums and subvolumes",
+            Filesystem::Fat => "FAT32, for EFI system partitions and USB sticks",
+        }
+    }
+
+    /// Longest label the filesystem stores
+    pub fn max_label(&self) -> usize {
+        match self {
+            Filesystem::Ext4 => 16,
+            Filesystem::Xfs => 12,
+            Filesystem::Btrfs => 255,
+            Filesystem::Fat => 11,
+        }
+    }
+
+    /// The mkfs program and its arguments. They all refuse to overwrite an
+    /// existing filesystem without being forced, which the wizard has
+    /// already asked about.
+    pub fn command(&self, device: &str, label: &str) -> (&'static str, Vec<String>) {
+        let mut args: Vec<String> = match self {
+            Filesystem::Ext4 => vec!["-F".into()],
+            Filesystem::Xfs | Filesystem::Btrfs => vec!["-f".into()],
+            Filesystem::Fat => vec!["-F".into(), "32".into()],
+        };
+        if !label.is_empty() {
+            args.push(if *self == Filesystem::Fat { "-n" } else { "-L" }.into());
+            args.push(label.to_string());
+        }
+        args.push(device.to_string());
+        let program = match self {
+            Filesystem::Ext4 => "mkfs.ext4",
+            Filesystem::Xfs => "mkfs.xfs",
+            Filesystem::Btrfs => "mkfs.btrfs",
+            Filesystem::Fat => "mkfs.fat",
+        };
+        (program, args)
+    }
+}
+
+/// Check a label against what the filesystem accepts
+pub fn validate_label(fs: Filesystem, label: &str) -> std::result::Result<(), String> {
+    if label.len() > fs.max_label() {
+        return Err(format!(
+            "{} labels are at most {} bytes",
+            fs.name(),
+            fs.max_label()
+        ));
+    }
+    if fs == Filesystem::Fat
+        && label
+            .chars()
+            .any(|c| !c.is_ascii() || "\"*+,./:;<=>?[\\]|".contains(c))
+    {
+        return Err("FAT labels are plain ASCII without punctuation".to_string());
+    }
+    Ok(())
+}
+
+/// The "major:minor" number of a block device, which names it the same
+/// whichever /dev path (or /dev/root) it is reached by
+fn device_number(path: &str) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    metadata
+        .file_type()
+        .is_block_device()
+        .then(|| format!("{}:{}", stat::major(metadata.rdev()), stat::minor(metadata.rdev())))
+}
+
+/// Whether /proc/self/mountinfo lists `device`, by the device number in
+/// its third field or by the mount source after the "-". btrfs and others
+/// report an anonymous 0:NN number that only the source gives away.
+fn is_mounted(mountinfo: &str, number: &str, device: &Path) -> bool {
+    let device = fs::canonicalize(device).ok();
+    mountinfo.lines().any(|line| {
+        let mut fields = line.split_whitespace();
+        if fields.nth(2) == Some(number) {
+            return true;
+        }
+        let source = fields.skip_while(|f| *f != "-").nth(2);
+        device.is_some() && source.and_then(|s| fs::canonicalize(s).ok()) == device
+    })
+}
+
+/// Why `device` must not be formatted right now, if it mustn't: mounted,
+/// in use as swap, held by LVM, md or dm-crypt, or a whole disk that holds
+/// partitions. Devices are compared by number, and mounts by their
+/// resolved source as well.
+pub fn check_unused(device: &str) -> Result<()> {
+    let number = device_number(device)
+        .ok_or_else(|| AppError::Config(format!("{} is not a block device", device)))?;
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo").unwrap_or_default();
+    if is_mounted(&mountinfo, &number, Path::new(device)) {
+        return Err(AppError::Config(format!("{} is mounted", device)));
+    }
+    let swaps = fs::read_to_string("/proc/swaps").unwrap_or_default();
+    let swapping = swaps
+        .lines()
+        .skip(1)
+        .filter_map(|l| l.split_whitespace().next())
+        .any(|swap| device_number(swap).as_deref() == Some(number.as_str()));
+    if swapping {
+        return Err(AppError::Config(format!("{} is active swap", device)));
+    }
+
+    let sys = PathBuf::from(format!("/sys/dev/block/{}", number));
+    let holders: Vec<String> = fs::read_dir(sys.join("holders"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    if !holders.is_empty() {
+        return Err(AppError::Config(format!(
+            "{} is in use by {}",
+            device,
+            holders.join(", ")
+        )));
+    }
+    // The kernel's name for the device, which its partitions start with
+    let name = fs::canonicalize(&sys)
+        .ok()
+        .and_then(|path| path.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    let has_partitions = !name.is_empty()
+        && fs::read_dir(&sys)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .any(|e| e.file_name().to_string_lossy().starts_with(&name))
+            })
+            .unwrap_or(false);
+    if has_partitions {
+        return Err(AppError::Config(format!(
+            "{} holds partitions; format one of them instead",
+            device
+        )));
+    }
+    Ok(())
+}
+
+/// Run mkfs on `device`, streaming its output as it goes
+pub fn format(fs: Filesystem, device: &str, label: &str) -> mpsc::UnboundedReceiver<RunEvent> {
+    let (program, args) = fs.command(device, label);
+    commands::stream(program, &args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command() {
+        let (program, args) = Filesystem::Ext4.command("/dev/sdb1", "backup");
+        assert_eq!(program, "mkfs.ext4");
+        assert_eq!(args, ["-F", "-L", "backup", "/dev/sdb1"]);
+        let (program, args) = Filesystem::Fat.command("/dev/sdc1", "");
+        assert_eq!(program, "mkfs.fat");
+        assert_eq!(args, ["-F", "32", "/dev/sdc1"]);
+
+        assert!(validate_label(Filesystem::Ext4, "home").is_ok());
+        assert!(validate_label(Filesystem::Xfs, "a-much-too-long-label").is_err());
+        assert!(validate_label(Filesystem::Fat, "EFI").is_ok());
+        assert!(validate_label(Filesystem::Fat, "my.stick").is_err());
+    }
+
+    #[test]
+    fn test_is_mounted() {
+        let mountinfo = "22 1 8:2 / / rw,relatime shared:1 - ext4 /dev/root rw\n\
+                         25 22 0:21 / /proc rw - proc proc rw\n\
+                         31 22 253:0 / /home rw - xfs /dev/mapper/vg-home rw\n";
+        let unused = Path::new("/dev/null");
+        assert!(is_mounted(mountinfo, "8:2", unused));
+        assert!(is_mounted(mountinfo, "253:0", unused));
+        assert!(!is_mounted(mountinfo, "8:17", unused));
+        assert!(check_unused("/dev/null").is_err());
+
+        // btrfs reports an anonymous device number; the source names the disk
+        let dir = crate::utils::scratch::private_dir("mkfs-test").unwrap();
+        let disk = dir.join("sdb1");
+        fs::write(&disk, "").unwrap();
+        let btrfs = format!(
+            "40 22 0:45 /@home /home rw,relatime shared:5 - btrfs {}/./sdb1 rw,ssd,subvol=/@home\n",
+            dir.display()
+        );
+        assert!(is_mounted(&btrfs, "8:17", &disk));
+        assert!(!is_mounted(&btrfs, "8:17", unused));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
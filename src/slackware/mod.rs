@@ -1,7 +1,43 @@
+pub mod at;
+pub mod audit;
+pub mod blkid;
+pub mod changelog;
 pub mod commands;
 pub mod config;
+pub mod cron;
+pub mod cronexpr;
+pub mod cronlog;
+pub mod dhcp;
+pub mod diskstats;
+pub mod efi;
+pub mod firewall;
+pub mod fsck;
+pub mod fsusage;
+pub mod grub;
+pub mod hosts;
+pub mod inet1;
+pub mod initrd;
+pub mod kconfig;
+pub mod kernels;
+pub mod lilo;
+pub mod listeners;
+pub mod logrotate;
+pub mod lvm;
+pub mod mdraid;
+pub mod mkfs;
+pub mod netdev;
 pub mod packages;
+pub mod partitions;
+pub mod proxy;
+pub mod rclocal;
+pub mod remote;
+pub mod removable;
+pub mod resolv;
+pub mod routes;
+pub mod smart;
+pub mod timeconfig;
 pub mod version;
+pub mod wireless;
 
 pub use commands::CommandExecutor;
 pub use config::Bootloader;
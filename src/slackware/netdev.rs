@@ -0,0 +1,330 @@
+use std::fs;
+use std::process::Command;
+
+use crate::slackware::audit;
+use crate::slackware::inet1::Inet1Config;
+use crate::slackware::rclocal;
+use crate::slackware::version::{detect_version, SlackwareVersion};
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// rc.local block recreating virtual interfaces rc.inet1 cannot set up
+const NETDEV_BLOCK: &str = "virtual-interfaces";
+
+/// An 802.1q VLAN on top of a physical interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vlan {
+    pub parent: String,
+    pub id: u16,
+}
+
+impl Vlan {
+    /// Parse "eth0.10" or "eth0 10"
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        let (parent, id) = spec
+            .split_once(char::is_whitespace)
+            .or_else(|| spec.rsplit_once('.'))?;
+        let id: u16 = id
+            .trim()
+            .parse()
+            .ok()
+            .filter(|id| (1..=4094).contains(id))?;
+        let parent = parent.trim();
+        if parent.is_empty() || parent.contains(char::is_whitespace) {
+            return None;
+        }
+        Some(Self {
+            parent: parent.to_string(),
+            id,
+        })
+    }
+
+    /// Interface name in the "parent.id" form rc.inet1 recognises
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.parent, self.id)
+    }
+
+    fn commands(&self) -> Vec<Vec<String>> {
+        let name = self.name();
+        vec![
+            words(&format!(
+                "ip link add link {} name {} type vlan id {}",
+                self.parent, name, self.id
+            )),
+            words(&format!("ip link set {} up", name)),
+        ]
+    }
+}
+
+/// A software bridge joining one or more interfaces, e.g. for KVM guests
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bridge {
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+impl Bridge {
+    /// Parse "br0 eth0 [eth1...]"
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut words = spec.split_whitespace().map(String::from);
+        let name = words.next()?;
+        let members: Vec<String> = words.collect();
+        if members.is_empty() {
+            return None;
+        }
+        Some(Self { name, members })
+    }
+
+    fn commands(&self) -> Vec<Vec<String>> {
+        let mut commands = vec![words(&format!(
+            "ip link add name {} type bridge",
+            self.name
+        ))];
+        for member in &self.members {
+            commands.push(words(&format!(
+                "ip link set {} master {}",
+                member, self.name
+            )));
+            commands.push(words(&format!("ip link set {} up", member)));
+        }
+        commands.push(words(&format!("ip link set {} up", self.name)));
+        commands
+    }
+}
+
+/// Error counters and link parameters of an interface, for spotting
+/// cabling or driver trouble
+#[derive(Debug, Clone, Default)]
+pub struct LinkStats {
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+    pub rx_crc_errors: u64,
+    pub rx_missed_errors: u64,
+    pub tx_carrier_errors: u64,
+    pub collisions: u64,
+    /// e.g. "1000Mb/s"; None when the driver does not report it
+    pub speed: Option<String>,
+    pub duplex: Option<String>,
+    pub link_detected: Option<bool>,
+    pub driver: Option<String>,
+}
+
+impl LinkStats {
+    pub fn load(iface: &str) -> Self {
+        let counter = |name: &str| {
+            fs::read_to_string(format!("/sys/class/net/{}/statistics/{}", iface, name))
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(0)
+        };
+        let mut stats = Self {
+            rx_packets: counter("rx_packets"),
+            tx_packets: counter("tx_packets"),
+            rx_errors: counter("rx_errors"),
+            tx_errors: counter("tx_errors"),
+            rx_dropped: counter("rx_dropped"),
+            tx_dropped: counter("tx_dropped"),
+            rx_crc_errors: counter("rx_crc_errors"),
+            rx_missed_errors: counter("rx_missed_errors"),
+            tx_carrier_errors: counter("tx_carrier_errors"),
+            collisions: counter("collisions"),
+            ..Self::default()
+        };
+
+        if let Some(output) = Command::new("ethtool")
+            .arg(iface)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+        {
+            stats.apply_ethtool(&String::from_utf8_lossy(&output.stdout));
+        } else {
+            // sysfs reports -1 / "unknown" for virtual devices and down links
+            let sys = |name: &str| {
+                fs::read_to_string(format!("/sys/class/net/{}/{}", iface, name))
+                    .ok()
+                    .map(|v| v.trim().to_string())
+            };
+            stats.speed = sys("speed")
+                .filter(|s| s.parse::<i64>().is_ok_and(|n| n > 0))
+                .map(|s| format!("{}Mb/s", s));
+            stats.duplex = sys("duplex").filter(|d| d != "unknown");
+            stats.link_detected = sys("carrier").map(|c| c == "1");
+        }
+        stats.driver = fs::read_link(format!("/sys/class/net/{}/device/driver", iface))
+            .ok()
+            .and_then(|p| p.file_name().map(|f| f.to_string_lossy().to_string()));
+        stats
+    }
+
+    /// Pick the link settings out of `ethtool <iface>` output
+    fn apply_ethtool(&mut self, output: &str) {
+        for line in output.lines() {
+            let Some((key, value)) = line.trim().split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            let known = !value.is_empty() && !value.starts_with("Unknown");
+            match key {
+                "Speed" if known => self.speed = Some(value.to_string()),
+                "Duplex" if known => self.duplex = Some(value.to_string()),
+                "Link detected" => self.link_detected = Some(value == "yes"),
+                _ => {}
+            }
+        }
+    }
+
+    /// Errors and drops as a share of all packets, in percent
+    pub fn error_rate(&self) -> f64 {
+        let packets = self.rx_packets + self.tx_packets;
+        if packets == 0 {
+            return 0.0;
+        }
+        let bad = self.rx_errors + self.tx_errors + self.rx_dropped + self.tx_dropped;
+        bad as f64 * 100.0 / packets as f64
+    }
+}
+
+/// Check for a unicast "aa:bb:cc:dd:ee:ff" hardware address
+pub fn is_valid_mac(mac: &str) -> bool {
+    let octets: Vec<&str> = mac.split(':').collect();
+    octets.len() == 6
+        && octets
+            .iter()
+            .all(|o| o.len() == 2 && u8::from_str_radix(o, 16).is_ok())
+        && u8::from_str_radix(octets[0], 16).is_ok_and(|first| first & 1 == 0)
+}
+
+/// Change the MTU of a running interface
+pub fn set_mtu(iface: &str, mtu: u32) -> Result<()> {
+    run(&[words(&format!("ip link set dev {} mtu {}", iface, mtu))])
+}
+
+/// Change the hardware address of an interface. Most drivers refuse while
+/// the link is up, so it is cycled down and back up.
+pub fn set_mac(iface: &str, mac: &str) -> Result<()> {
+    run(&[
+        words(&format!("ip link set dev {} down", iface)),
+        words(&format!("ip link set dev {} address {}", iface, mac)),
+        words(&format!("ip link set dev {} up", iface)),
+    ])
+}
+
+fn words(command: &str) -> Vec<String> {
+    command.split_whitespace().map(String::from).collect()
+}
+
+fn run(commands: &[Vec<String>]) -> Result<()> {
+    for args in commands {
+        let output = audit::output(root::command(&args[0]).args(&args[1..]))?;
+        if !output.status.success() {
+            return Err(AppError::CommandFailed(format!(
+                "{}: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// rc.inet1 learned to create "ethN.M" VLAN interfaces in Slackware 15.0
+fn inet1_supports_vlans() -> bool {
+    matches!(
+        detect_version(),
+        Ok(SlackwareVersion::V15_0) | Ok(SlackwareVersion::Current)
+    )
+}
+
+/// Append commands to the rc.local block, skipping ones already there
+fn persist_in_rc_local(commands: &[Vec<String>]) -> Result<()> {
+    let mut block = rclocal::managed_block(NETDEV_BLOCK);
+    for command in commands.iter().map(|c| c.join(" ")) {
+        if !block.contains(&command) {
+            block.push(command);
+        }
+    }
+    rclocal::set_managed_block(NETDEV_BLOCK, &block)
+}
+
+/// Create a VLAN interface now and configure it for boot. Where rc.inet1
+/// handles VLANs it gets an IFNAME[n] block (addressing is then set like
+/// any other interface); older releases get an rc.local snippet.
+///
+/// Returns where the interface was persisted.
+pub fn create_vlan(spec: &str) -> Result<(Vlan, &'static str)> {
+    let vlan = Vlan::parse(spec).ok_or_else(|| {
+        AppError::Config("Expected '<parent>.<id>' with an id between 1 and 4094".to_string())
+    })?;
+    run(&vlan.commands())?;
+
+    if inet1_supports_vlans() {
+        let mut config = Inet1Config::load()?;
+        config.index_or_insert(&vlan.name());
+        config.save()?;
+        Ok((vlan, "rc.inet1.conf"))
+    } else {
+        persist_in_rc_local(&vlan.commands())?;
+        Ok((vlan, "rc.local"))
+    }
+}
+
+/// Create a bridge now and add it to rc.inet1.conf with BRNICS set to its
+/// members. The members' own address settings are cleared, since rc.inet1
+/// only brings them up as bridge ports.
+pub fn create_bridge(spec: &str) -> Result<Bridge> {
+    let bridge = Bridge::parse(spec).ok_or_else(|| {
+        AppError::Config("Expected '<bridge> <interface> [interface...]'".to_string())
+    })?;
+    if bridge.members.contains(&bridge.name) {
+        return Err(AppError::Config(
+            "A bridge cannot be a member of itself".to_string(),
+        ));
+    }
+    run(&bridge.commands())?;
+
+    let mut config = Inet1Config::load()?;
+    for member in &bridge.members {
+        if let Some(idx) = config.index_for(member) {
+            for key in ["IPADDR", "NETMASK", "USE_DHCP"] {
+                if config.get(key, Some(idx)).is_some() {
+                    config.set(key, Some(idx), "");
+                }
+            }
+        }
+    }
+    let idx = config.index_or_insert(&bridge.name);
+    config.set("BRNICS", Some(idx), &bridge.members.join(" "));
+    config.save()?;
+    Ok(bridge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_link_settings() {
+        assert_eq!(
+            Vlan::parse("eth0.10").map(|v| v.name()),
+            Some("eth0.10".to_string())
+        );
+        assert_eq!(Vlan::parse("eth1 200").map(|v| v.id), Some(200));
+        assert!(Vlan::parse("eth0.0").is_none());
+        assert!(Vlan::parse("eth0.5000").is_none());
+        assert!(Vlan::parse("eth0").is_none());
+
+        let bridge = Bridge::parse("br0 eth0 eth1").unwrap();
+        assert_eq!(bridge.members, vec!["eth0", "eth1"]);
+        assert!(Bridge::parse("br0").is_none());
+
+        assert!(is_valid_mac("02:1a:2b:3c:4d:5e"));
+        assert!(!is_valid_mac("01:1a:2b:3c:4d:5e"));
+        assert!(!is_valid_mac("02:1a:2b:3c:4d"));
+    }
+}
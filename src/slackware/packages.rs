@@ -1,4 +1,4 @@
-use super::commands::{CommandExecutor, CommandResult};
+use super::commands::CommandExecutor;
 
 /// Package information from SlackBuilds
 #[derive(Debug, Clone)]
@@ -6,8 +6,6 @@ pub struct PackageInfo {
     pub name: String,
     pub category: String,
     pub description: String,
-    pub version: Option<String>,
-    pub installed: bool,
 }
 
 /// Package manager for SlackBuilds.org packages
@@ -49,8 +47,6 @@ impl PackageManager {
                         name: current_name.clone(),
                         category: current_category.clone(),
                         description: current_description.clone(),
-                        version: None,
-                        installed: false,
                     });
                     current_name.clear();
                     current_category.clear();
@@ -82,29 +78,11 @@ impl PackageManager {
                 name: current_name,
                 category: current_category,
                 description: current_description,
-                version: None,
-                installed: false,
             });
         }
 
         packages
     }
-
-    /// Install a package using sboinstall
-    pub async fn install(&self, package_name: &str) -> CommandResult {
-        self.executor.sboinstall(package_name).await
-    }
-
-    /// Get info about a specific package
-    pub async fn get_info(&self, package_name: &str) -> Option<String> {
-        let result = self.executor.sbofind(package_name).await;
-
-        if result.success {
-            Some(result.stdout)
-        } else {
-            None
-        }
-    }
 }
 
 impl Default for PackageManager {
@@ -0,0 +1,378 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use chrono::Local;
+use serde::Deserialize;
+
+use crate::slackware::audit;
+use crate::slackware::mkfs;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// Where `sfdisk --dump` copies of a table go before it is changed
+const BACKUP_DIR: &str = "/var/backups/slackware-cli-manager/partitions";
+
+/// New partitions start on 1 MiB boundaries, like fdisk and parted do
+const ALIGN: u64 = 2048;
+
+/// Partition types offered when creating a partition or changing its
+/// type, as sfdisk shortcuts that work for both GPT and MBR tables
+pub const KINDS: [(&str, &str); 5] = [
+    ("L", "Linux filesystem"),
+    ("S", "Linux swap"),
+    ("U", "EFI system"),
+    ("V", "Linux LVM"),
+    ("R", "Linux RAID"),
+];
+
+#[derive(Debug, Deserialize)]
+struct Dump {
+    partitiontable: PartitionTable,
+}
+
+/// A disk's partition table as `sfdisk --json` prints it
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PartitionTable {
+    /// "gpt" or "dos"
+    pub label: String,
+    pub device: String,
+    #[serde(default)]
+    pub firstlba: Option<u64>,
+    #[serde(default)]
+    pub lastlba: Option<u64>,
+    #[serde(default = "default_sector_size")]
+    pub sectorsize: u64,
+    #[serde(default)]
+    pub partitions: Vec<Partition>,
+    /// Size of the disk in sectors; MBR dumps don't say, so it comes
+    /// from sysfs
+    #[serde(skip)]
+    pub disk_sectors: u64,
+}
+
+fn default_sector_size() -> u64 {
+    512
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Partition {
+    pub node: String,
+    pub start: u64,
+    pub size: u64,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub bootable: bool,
+}
+
+impl Partition {
+    /// The partition number sfdisk wants: trailing digits of the node
+    pub fn number(&self) -> Option<u32> {
+        let digits = self.node.len()
+            - self
+                .node
+                .chars()
+                .rev()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+        self.node[digits..].parse().ok()
+    }
+
+    /// Human name of the type for the common GPT GUIDs and MBR ids
+    pub fn kind_name(&self) -> &str {
+        match self.kind.to_ascii_uppercase().as_str() {
+            "0FC63DAF-8483-4772-8E79-3D69D8477DE4" | "83" => "Linux filesystem",
+            "0657FD6D-A4AB-43C4-84E5-0933C84B4F4F" | "82" => "Linux swap",
+            "C12A7328-F81F-11D2-BA4B-00A0C93EC93B" | "EF" => "EFI system",
+            "21686148-6449-6E6F-744E-656564454649" => "BIOS boot",
+            "E6D6D379-F507-44C2-A23C-238F2A3DF928" | "8E" => "Linux LVM",
+            "A19D880F-05FC-4D3B-A006-743F0F84911E" | "FD" => "Linux RAID",
+            "4F68BCE3-E8CD-4DB1-96E7-FBCAF984B709" => "Linux root (x86-64)",
+            "933AC7E1-2EB4-4F13-B844-0E14E2AEF915" => "Linux home",
+            "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7" | "7" => "Microsoft basic data",
+            "B" | "C" => "FAT32",
+            "5" | "F" => "Extended",
+            _ => &self.kind,
+        }
+    }
+}
+
+/// A stretch of the disk: a partition or unallocated space
+#[derive(Debug, Clone, PartialEq)]
+pub enum Slot {
+    Used(Partition),
+    /// Aligned start and length in sectors
+    Free {
+        start: u64,
+        size: u64,
+    },
+}
+
+impl PartitionTable {
+    /// Partitions in disk order with the gaps of at least 1 MiB between them
+    pub fn layout(&self) -> Vec<Slot> {
+        let mut partitions = self.partitions.clone();
+        partitions.sort_by_key(|p| p.start);
+        let end = match self.lastlba {
+            Some(last) => last + 1,
+            None => self.disk_sectors,
+        };
+        let mut cursor = self.firstlba.unwrap_or(ALIGN);
+        let mut slots = Vec::new();
+        let push_gap = |slots: &mut Vec<Slot>, from: u64, to: u64| {
+            let start = (from + ALIGN - 1) / ALIGN * ALIGN;
+            if to > start && to - start >= ALIGN {
+                slots.push(Slot::Free {
+                    start,
+                    size: to - start,
+                });
+            }
+        };
+        for partition in partitions {
+            push_gap(&mut slots, cursor, partition.start);
+            // Logical partitions sit inside their extended one
+            cursor = cursor.max(partition.start + partition.size);
+            slots.push(Slot::Used(partition));
+        }
+        push_gap(&mut slots, cursor, end);
+        slots
+    }
+
+    pub fn bytes(&self, sectors: u64) -> u64 {
+        sectors * self.sectorsize
+    }
+}
+
+pub fn parse(json: &str) -> Result<PartitionTable> {
+    serde_json::from_str::<Dump>(json)
+        .map(|d| d.partitiontable)
+        .map_err(|e| AppError::Config(format!("Unreadable sfdisk output: {}", e)))
+}
+
+/// Read the partition table of a disk such as /dev/sda
+pub fn read(disk: &str) -> Result<PartitionTable> {
+    let output = root::command("sfdisk").args(["--json", disk]).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::CommandFailed(
+            if stderr.contains("does not contain") {
+                format!("{} has no partition table", disk)
+            } else {
+                format!("sfdisk: {}", stderr.trim())
+            },
+        ));
+    }
+    let mut table = parse(&String::from_utf8_lossy(&output.stdout))?;
+    // sysfs counts 512-byte sectors whatever the logical sector size
+    let name = disk.trim_start_matches("/dev/");
+    table.disk_sectors = fs::read_to_string(format!("/sys/block/{}/size", name))
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(|s| s * 512 / table.sectorsize)
+        .unwrap_or(0);
+    Ok(table)
+}
+
+/// Sectors in a size such as "512M", "20G" or "1.5T"; a bare number is MiB
+pub fn parse_size(text: &str, sector_size: u64) -> Option<u64> {
+    let text = text.trim().trim_start_matches('+');
+    let (number, unit) = match text.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => text.split_at(i),
+        None => (text, "M"),
+    };
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().trim_end_matches("IB") {
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        "T" => 1 << 40,
+        _ => return None,
+    };
+    let bytes = number.trim().parse::<f64>().ok()? * multiplier as f64;
+    let sectors = (bytes / sector_size as f64) as u64;
+    // Round down to whole MiB so the next partition stays aligned
+    let sectors = sectors / ALIGN * ALIGN;
+    (sectors > 0).then_some(sectors)
+}
+
+/// A change to a partition table
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartitionOp {
+    Create {
+        disk: String,
+        start: u64,
+        size: u64,
+        kind: &'static str,
+    },
+    Delete {
+        disk: String,
+        partition: String,
+        number: u32,
+    },
+    SetType {
+        disk: String,
+        partition: String,
+        number: u32,
+        kind: &'static str,
+    },
+}
+
+impl PartitionOp {
+    pub fn disk(&self) -> &str {
+        match self {
+            PartitionOp::Create { disk, .. }
+            | PartitionOp::Delete { disk, .. }
+            | PartitionOp::SetType { disk, .. } => disk,
+        }
+    }
+
+    /// The sfdisk arguments and, for a new partition, its script line
+    pub fn command(&self) -> (Vec<String>, Option<String>) {
+        match self {
+            PartitionOp::Create {
+                disk,
+                start,
+                size,
+                kind,
+            } => (
+                vec!["--append".into(), disk.clone()],
+                Some(format!("start={}, size={}, type={}", start, size, kind)),
+            ),
+            PartitionOp::Delete { disk, number, .. } => (
+                vec!["--delete".into(), disk.clone(), number.to_string()],
+                None,
+            ),
+            PartitionOp::SetType {
+                disk, number, kind, ..
+            } => (
+                vec![
+                    "--part-type".into(),
+                    disk.clone(),
+                    number.to_string(),
+                    kind.to_string(),
+                ],
+                None,
+            ),
+        }
+    }
+
+    /// The command as the user would type it
+    pub fn describe(&self) -> String {
+        let (args, script) = self.command();
+        match script {
+            Some(script) => format!("echo '{}' | sfdisk {}", script, args.join(" ")),
+            None => format!("sfdisk {}", args.join(" ")),
+        }
+    }
+}
+
+/// Save `sfdisk --dump` of a disk so the table can be restored with
+/// `sfdisk <disk> < backup`
+fn backup(disk: &str) -> Result<PathBuf> {
+    let output = root::command("sfdisk").args(["--dump", disk]).output()?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "sfdisk --dump: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    root::create_dir_all(Path::new(BACKUP_DIR))?;
+    let name = format!(
+        "{}-{}.sfdisk",
+        disk.trim_start_matches("/dev/"),
+        Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = Path::new(BACKUP_DIR).join(name);
+    root::write_file(&path, &output.stdout)?;
+    Ok(path)
+}
+
+/// Back the table up and apply `op`, returning where the backup went
+pub fn apply(op: &PartitionOp) -> Result<PathBuf> {
+    match op {
+        PartitionOp::Delete { partition, .. } | PartitionOp::SetType { partition, .. } => {
+            mkfs::check_unused(partition)?
+        }
+        PartitionOp::Create { .. } => {}
+    }
+    let backup = backup(op.disk())?;
+    let (args, script) = op.command();
+    let mut command = root::command("sfdisk");
+    command
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(format!("{}\n", script.unwrap_or_default()).as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    audit::record_command(&command, output.status.code());
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "sfdisk: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let mut table = parse(
+            r#"{
+               "partitiontable": {
+                  "label": "gpt",
+                  "id": "3A0C5D2E-4B1F-4E52-9D0A-7C58F1B2E6A1",
+                  "device": "/dev/nvme0n1",
+                  "unit": "sectors",
+                  "firstlba": 34,
+                  "lastlba": 20971486,
+                  "sectorsize": 512,
+                  "partitions": [
+                     {"node": "/dev/nvme0n1p2", "start": 1050624, "size": 8388608,
+                      "type": "0FC63DAF-8483-4772-8E79-3D69D8477DE4", "name": "root"},
+                     {"node": "/dev/nvme0n1p1", "start": 2048, "size": 1048576,
+                      "type": "C12A7328-F81F-11D2-BA4B-00A0C93EC93B"}
+                  ]
+               }
+            }"#,
+        )
+        .unwrap();
+        table.disk_sectors = 20971520;
+        let slots = table.layout();
+        assert_eq!(slots.len(), 3);
+        let Slot::Used(efi) = &slots[0] else {
+            panic!("expected a partition first");
+        };
+        assert_eq!(efi.number(), Some(1));
+        assert_eq!(efi.kind_name(), "EFI system");
+        assert_eq!(
+            slots[2],
+            Slot::Free {
+                start: 9439232,
+                size: 11532255
+            }
+        );
+
+        assert_eq!(parse_size("512M", 512), Some(1048576));
+        assert_eq!(parse_size("+1G", 512), Some(2097152));
+        assert_eq!(parse_size("100", 512), Some(204800));
+        assert_eq!(parse_size("lots", 512), None);
+
+        let op = PartitionOp::Delete {
+            disk: "/dev/sda".to_string(),
+            partition: "/dev/sda3".to_string(),
+            number: 3,
+        };
+        assert_eq!(op.describe(), "sfdisk --delete /dev/sda 3");
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::Path;
+
+use crate::slackware::config;
+use crate::utils::error::Result;
+use crate::utils::root;
+
+/// Sourced by login shells through /etc/profile
+pub const PROFILE_SH: &str = "/etc/profile.d/proxy.sh";
+/// Sourced by csh/tcsh login shells through /etc/csh.login
+pub const PROFILE_CSH: &str = "/etc/profile.d/proxy.csh";
+pub const WGETRC: &str = "/etc/wgetrc";
+
+/// System-wide proxy settings
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProxySettings {
+    pub http: String,
+    pub https: String,
+    /// Comma separated hosts and domains to reach directly
+    pub no_proxy: String,
+}
+
+impl ProxySettings {
+    /// Read the settings from the generated profile script
+    pub fn load() -> Self {
+        fs::read_to_string(PROFILE_SH)
+            .map(|c| Self::parse(&c))
+            .unwrap_or_default()
+    }
+
+    /// Parse `export name="value"` lines
+    pub fn parse(content: &str) -> Self {
+        let mut settings = Self::default();
+        for line in content.lines() {
+            let Some((name, value)) = line
+                .trim()
+                .strip_prefix("export ")
+                .and_then(|l| l.split_once('='))
+            else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"').to_string();
+            match name.trim() {
+                "http_proxy" => settings.http = value,
+                "https_proxy" => settings.https = value,
+                "no_proxy" => settings.no_proxy = value,
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.http.is_empty() && self.https.is_empty()
+    }
+
+    /// Variables to set for child processes, in both the lower case form
+    /// wget and curl read and the upper case form other tools expect
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        [
+            ("http_proxy", &self.http),
+            ("https_proxy", &self.https),
+            ("no_proxy", &self.no_proxy),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .flat_map(|(name, value)| {
+            [
+                (name.to_string(), value.clone()),
+                (name.to_uppercase(), value.clone()),
+            ]
+        })
+        .collect()
+    }
+
+    /// Write the profile scripts and point wget at the proxy.
+    /// An empty configuration removes the scripts and turns wget's proxy off.
+    pub fn save(&self) -> Result<()> {
+        let vars = self.env_vars();
+        if vars.is_empty() {
+            for path in [PROFILE_SH, PROFILE_CSH] {
+                if Path::new(path).exists() {
+                    root::remove(Path::new(path))?;
+                }
+            }
+        } else {
+            let header = "# Proxy settings, generated by slackware-cli-manager\n";
+            let sh: String = vars
+                .iter()
+                .map(|(name, value)| format!("export {}=\"{}\"\n", name, value))
+                .collect();
+            let csh: String = vars
+                .iter()
+                .map(|(name, value)| format!("setenv {} \"{}\"\n", name, value))
+                .collect();
+            for (path, body) in [(PROFILE_SH, sh), (PROFILE_CSH, csh)] {
+                let path = Path::new(path);
+                config::write_with_backup(path, &format!("{}{}", header, body))?;
+                root::set_mode(path, 0o755)?;
+            }
+        }
+        self.update_wgetrc()
+    }
+
+    fn update_wgetrc(&self) -> Result<()> {
+        let content = fs::read_to_string(WGETRC).unwrap_or_default();
+        let settings = [
+            ("http_proxy", self.http.clone()),
+            ("https_proxy", self.https.clone()),
+            ("no_proxy", self.no_proxy.clone()),
+            (
+                "use_proxy",
+                if self.is_empty() { "off" } else { "on" }.to_string(),
+            ),
+        ];
+
+        let key_of = |line: &str| {
+            let line = line.trim().trim_start_matches('#').trim_start();
+            line.split_once('=').map(|(k, _)| k.trim().to_string())
+        };
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        for (key, value) in settings {
+            let line = if value.is_empty() {
+                format!("#{} = ", key)
+            } else {
+                format!("{} = {}", key, value)
+            };
+            // Reuse the active line, then a commented example, then append
+            let active = lines.iter().position(|l| {
+                !l.trim_start().starts_with('#') && key_of(l).as_deref() == Some(key)
+            });
+            let commented = lines
+                .iter()
+                .position(|l| l.trim_start().starts_with('#') && key_of(l).as_deref() == Some(key));
+            match active.or(commented) {
+                Some(i) => lines[i] = line,
+                None if !value.is_empty() => lines.push(line),
+                None => {}
+            }
+        }
+        config::write_with_backup(Path::new(WGETRC), &(lines.join("\n") + "\n"))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+
+use crate::slackware::config;
+use crate::utils::error::Result;
+use crate::utils::root;
+
+/// Run at the end of boot by rc.M when executable
+pub const RC_LOCAL: &str = "/etc/rc.d/rc.local";
+
+fn markers(name: &str) -> (String, String) {
+    (
+        format!("# BEGIN slackware-cli-manager {}", name),
+        format!("# END slackware-cli-manager {}", name),
+    )
+}
+
+/// Lines of a named block this tool maintains in rc.local
+pub fn managed_block(name: &str) -> Vec<String> {
+    let content = fs::read_to_string(RC_LOCAL).unwrap_or_default();
+    let (begin, end) = markers(name);
+    content
+        .lines()
+        .skip_while(|l| l.trim() != begin)
+        .skip(1)
+        .take_while(|l| l.trim() != end)
+        .map(String::from)
+        .collect()
+}
+
+/// Replace (or remove, when `lines` is empty) a named block in rc.local,
+/// leaving everything else in the file untouched
+pub fn set_managed_block(name: &str, lines: &[String]) -> Result<()> {
+    let content = fs::read_to_string(RC_LOCAL).unwrap_or_else(|_| {
+        "#!/bin/sh\n#\n# /etc/rc.d/rc.local:  Local system initialization script.\n".to_string()
+    });
+    let (begin, end) = markers(name);
+
+    let mut result: Vec<String> = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        if line.trim() == begin {
+            in_block = true;
+        } else if in_block && line.trim() == end {
+            in_block = false;
+        } else if !in_block {
+            result.push(line.to_string());
+        }
+    }
+    while result.last().is_some_and(|l| l.trim().is_empty()) {
+        result.pop();
+    }
+    if !lines.is_empty() {
+        result.push(String::new());
+        result.push(begin);
+        result.extend(lines.iter().cloned());
+        result.push(end);
+    }
+
+    let path = Path::new(RC_LOCAL);
+    config::write_with_backup(path, &(result.join("\n") + "\n"))?;
+    root::set_mode(path, 0o755)?;
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::audit::shell_quote;
+use crate::utils::error::{AppError, Result};
+
+/// Argument that makes the manager run one command on a remote host and
+/// exit, see `exec`
+pub const EXEC_ARG: &str = "--remote-exec";
+
+/// Gives up on a host that does not answer within ten seconds
+const CONNECT_TIMEOUT: &str = "ConnectTimeout=10";
+
+/// A Slackware machine administered over SSH, from `[[remote_hosts]]` in
+/// the settings file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteHost {
+    /// Shown in the host list and the header
+    pub name: String,
+    /// `user@host`, or a Host from ~/.ssh/config. Logging in as root saves
+    /// sudo on the other end, which cannot ask for a password here.
+    pub target: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Private key to log in with; the agent and ~/.ssh/config otherwise
+    #[serde(default)]
+    pub identity: Option<String>,
+}
+
+impl RemoteHost {
+    /// ssh with its options, up to and including the target. BatchMode
+    /// makes it fail rather than ask on the terminal the interface draws on.
+    pub fn ssh_argv(&self) -> Vec<String> {
+        let mut argv = vec![
+            "ssh".to_string(),
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+            "-o".to_string(),
+            CONNECT_TIMEOUT.to_string(),
+        ];
+        if let Some(port) = self.port {
+            argv.push("-p".to_string());
+            argv.push(port.to_string());
+        }
+        if let Some(identity) = &self.identity {
+            argv.push("-i".to_string());
+            argv.push(identity.clone());
+        }
+        argv.push(self.target.clone());
+        argv
+    }
+
+    /// `program` to be run on the host, ready for its arguments.
+    ///
+    /// ssh hands the remote shell its words joined with spaces, which would
+    /// split arguments holding spaces, so the manager sits in between: it
+    /// is started again with `EXEC_ARG` and quotes each word once the
+    /// caller has added them all.
+    pub fn command(&self, program: &str, env: &[(String, String)]) -> Command {
+        let exe = std::env::current_exe().unwrap_or_else(|_| env!("CARGO_PKG_NAME").into());
+        let mut command = Command::new(exe);
+        command.arg(EXEC_ARG).args(self.ssh_argv()).arg("--");
+        if !env.is_empty() {
+            command.arg("env");
+            command.args(env.iter().map(|(key, value)| format!("{}={}", key, value)));
+        }
+        command.arg(program);
+        command
+    }
+
+    /// Whether the host lets us in without asking
+    pub fn check(&self) -> Result<()> {
+        let argv = self.ssh_argv();
+        let output = Command::new(&argv[0])
+            .args(&argv[1..])
+            .arg("true")
+            .stdin(Stdio::null())
+            .output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(AppError::CommandFailed(format!(
+                "{}: {}",
+                self.name,
+                stderr.lines().last().unwrap_or("ssh failed")
+            )))
+        }
+    }
+}
+
+/// Host commands and privileged file access go to; `None` for this machine
+static CURRENT: RwLock<Option<RemoteHost>> = RwLock::new(None);
+
+pub fn set_current(host: Option<RemoteHost>) {
+    if let Ok(mut current) = CURRENT.write() {
+        *current = host;
+    }
+}
+
+pub fn current() -> Option<RemoteHost> {
+    CURRENT.read().ok().and_then(|host| host.clone())
+}
+
+/// The ssh command line for `args`, ssh's own arguments then "--" then the
+/// command to run remotely, as `command` passes them
+pub fn ssh_command_line(args: &[String]) -> Vec<String> {
+    let split = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    let mut argv = args[..split].to_vec();
+    let remote: Vec<String> = args
+        .get(split + 1..)
+        .unwrap_or_default()
+        .iter()
+        .map(|arg| shell_quote(arg))
+        .collect();
+    argv.push(remote.join(" "));
+    argv
+}
+
+/// Replace this process with ssh running the command in `args`, which
+/// follow `EXEC_ARG`. Only returns if ssh could not be started.
+pub fn exec(args: &[String]) -> io::Error {
+    let argv = ssh_command_line(args);
+    match argv.split_first() {
+        Some((program, rest)) => Command::new(program).args(rest).exec(),
+        None => io::Error::new(io::ErrorKind::InvalidInput, "no ssh command given"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssh_command_line() {
+        let host = RemoteHost {
+            name: "web".to_string(),
+            target: "root@web1".to_string(),
+            port: Some(2222),
+            identity: None,
+        };
+        let command = host.command("usermod", &[]);
+        let mut args: Vec<String> = command
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(args[0], EXEC_ARG);
+        args.extend([
+            "-c".to_string(),
+            "Jane O'Neil".to_string(),
+            "jane".to_string(),
+        ]);
+        assert_eq!(
+            ssh_command_line(&args[1..]),
+            vec![
+                "ssh",
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ConnectTimeout=10",
+                "-p",
+                "2222",
+                "root@web1",
+                "usermod -c 'Jane O'\\''Neil' jane"
+            ]
+        );
+    }
+}
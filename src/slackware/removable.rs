@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::Path;
+
+use crate::slackware::audit;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// The whole disk a block device such as "sdb1" lives on
+pub fn parent_disk(name: &str) -> Option<String> {
+    let class = Path::new("/sys/class/block").join(name);
+    if !class.join("partition").exists() {
+        return class.exists().then(|| name.to_string());
+    }
+    let target = fs::canonicalize(&class).ok()?;
+    Some(target.parent()?.file_name()?.to_string_lossy().to_string())
+}
+
+/// Whether the disk is removable media or hangs off USB, where people
+/// expect to pull it out
+pub fn is_removable(name: &str) -> bool {
+    let Some(disk) = parent_disk(name) else {
+        return false;
+    };
+    let sys = Path::new("/sys/block").join(&disk);
+    let flagged = fs::read_to_string(sys.join("removable")).is_ok_and(|s| s.trim() == "1");
+    let usb = fs::canonicalize(&sys).is_ok_and(|p| p.to_string_lossy().contains("/usb"));
+    flagged || usb
+}
+
+/// Names under /sys/class/block, which change when something is
+/// plugged in or pulled out
+pub fn block_devices() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir("/sys/class/block")
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+fn run(cmd: &str, args: &[&str]) -> Result<()> {
+    let output = audit::output(root::command(cmd).args(args))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Whether `device` is `disk` itself or one of its partitions, e.g.
+/// /dev/sdb1 on sdb or /dev/mmcblk0p1 on mmcblk0
+fn on_disk(device: &str, disk: &str) -> bool {
+    let Some(rest) = device
+        .strip_prefix("/dev/")
+        .and_then(|d| d.strip_prefix(disk))
+    else {
+        return false;
+    };
+    // Disks whose names end in a digit put a 'p' before the partition number
+    let number = if disk.ends_with(|c: char| c.is_ascii_digit()) {
+        rest.strip_prefix('p').unwrap_or(rest)
+    } else {
+        rest
+    };
+    (rest.is_empty() || !number.is_empty()) && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Mount points of filesystems on `disk` in /proc/mounts content,
+/// innermost first so they can be unmounted in order
+pub fn mount_points(disk: &str, mounts: &str) -> Vec<String> {
+    let mut points: Vec<String> = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let mount_point = fields.next()?;
+            on_disk(device, disk).then(|| mount_point.replace("\\040", " "))
+        })
+        .collect();
+    points.reverse();
+    points
+}
+
+/// Unmount everything on `disk`, flush it and eject it so it can be
+/// pulled out safely
+pub fn eject(disk: &str) -> Result<()> {
+    let mounts = fs::read_to_string("/proc/mounts")?;
+    for mount_point in mount_points(disk, &mounts) {
+        run("umount", &[&mount_point])?;
+    }
+    run("sync", &[])?;
+    run("eject", &[&format!("/dev/{}", disk)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mount_points() {
+        let mounts = "/dev/sda2 / ext4 rw,relatime 0 0\n\
+                      /dev/sdb1 /mnt/usb vfat rw 0 0\n\
+                      /dev/sdb2 /mnt/usb/My\\040Files ext4 rw 0 0\n\
+                      /dev/sdb10 /mnt/other ext4 rw 0 0\n\
+                      /dev/sdbb1 /mnt/not-sdb ext4 rw 0 0\n\
+                      /dev/mmcblk0p1 /mnt/sd vfat rw 0 0\n";
+        assert_eq!(
+            mount_points("sdb", mounts),
+            ["/mnt/other", "/mnt/usb/My Files", "/mnt/usb"]
+        );
+        assert_eq!(mount_points("mmcblk0", mounts), ["/mnt/sd"]);
+        assert!(mount_points("sdc", mounts).is_empty());
+    }
+}
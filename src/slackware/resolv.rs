@@ -0,0 +1,92 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::slackware::config;
+use crate::slackware::inet1::Inet1Config;
+use crate::utils::error::Result;
+
+pub const RESOLV_CONF: &str = "/etc/resolv.conf";
+const DHCPCD_CONF: &str = "/etc/dhcpcd.conf";
+
+/// Nameserver and search domain settings from /etc/resolv.conf
+#[derive(Debug, Clone, Default)]
+pub struct ResolvConf {
+    pub nameservers: Vec<String>,
+    pub search: Vec<String>,
+    /// Options, comments and anything else, kept verbatim
+    other: Vec<String>,
+}
+
+impl ResolvConf {
+    pub fn load() -> Self {
+        fs::read_to_string(RESOLV_CONF)
+            .map(|c| Self::parse(&c))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let mut conf = Self::default();
+        for line in content.lines() {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("nameserver") => conf.nameservers.extend(words.next().map(String::from)),
+                // "domain" and "search" are mutually exclusive; the last one wins
+                Some("search") | Some("domain") => {
+                    conf.search = words.map(String::from).collect();
+                }
+                _ => conf.other.push(line.to_string()),
+            }
+        }
+        while conf.other.last().is_some_and(|l| l.trim().is_empty()) {
+            conf.other.pop();
+        }
+        conf
+    }
+
+    pub fn to_content(&self) -> String {
+        let mut lines = self.other.clone();
+        if !self.search.is_empty() {
+            lines.push(format!("search {}", self.search.join(" ")));
+        }
+        lines.extend(
+            self.nameservers
+                .iter()
+                .map(|ns| format!("nameserver {}", ns)),
+        );
+        lines.join("\n") + "\n"
+    }
+
+    /// Write /etc/resolv.conf, returning the path of the backup copy
+    pub fn save(&self) -> Result<PathBuf> {
+        config::write_with_backup(Path::new(RESOLV_CONF), &self.to_content())
+    }
+}
+
+/// Interfaces whose DHCP client will replace /etc/resolv.conf on the next
+/// lease, as (rc.inet1.conf index, interface name)
+pub fn dhcp_overwriters(config: &Inet1Config) -> Vec<(usize, String)> {
+    let dhcpcd_conf = fs::read_to_string(DHCPCD_CONF).unwrap_or_default();
+    let hook_disabled = dhcpcd_conf
+        .lines()
+        .any(|l| l.trim().starts_with("nohook") && l.contains("resolv.conf"));
+    if hook_disabled {
+        return Vec::new();
+    }
+
+    config
+        .dhcp_indices()
+        .into_iter()
+        .filter(|i| {
+            !config
+                .get("DHCP_KEEPRESOLV", Some(*i))
+                .is_some_and(|v| v.eq_ignore_ascii_case("yes"))
+        })
+        .map(|i| {
+            let name = config
+                .get("IFNAME", Some(i))
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("eth{}", i));
+            (i, name)
+        })
+        .collect()
+}
@@ -0,0 +1,153 @@
+use std::process::Command;
+
+use crate::slackware::audit;
+use crate::slackware::inet1::Inet1Config;
+use crate::slackware::rclocal;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// rc.local block holding persisted static routes
+const ROUTES_BLOCK: &str = "static-routes";
+
+/// An entry of the kernel routing table
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    pub destination: String,
+    pub gateway: Option<String>,
+    pub dev: Option<String>,
+    pub proto: Option<String>,
+    pub metric: Option<u32>,
+}
+
+impl Route {
+    /// Parse a line of `ip route show`
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut words = line.split_whitespace();
+        let mut route = Route {
+            destination: words.next()?.to_string(),
+            gateway: None,
+            dev: None,
+            proto: None,
+            metric: None,
+        };
+        while let Some(word) = words.next() {
+            match word {
+                "via" => route.gateway = words.next().map(String::from),
+                "dev" => route.dev = words.next().map(String::from),
+                "proto" => route.proto = words.next().map(String::from),
+                "metric" => route.metric = words.next().and_then(|m| m.parse().ok()),
+                _ => {}
+            }
+        }
+        Some(route)
+    }
+
+    /// Arguments identifying the route to `ip route add/del`
+    pub fn spec(&self) -> Vec<String> {
+        let mut spec = vec![self.destination.clone()];
+        if let Some(gw) = &self.gateway {
+            spec.extend(["via".to_string(), gw.clone()]);
+        }
+        if let Some(dev) = &self.dev {
+            spec.extend(["dev".to_string(), dev.clone()]);
+        }
+        if let Some(metric) = self.metric {
+            spec.extend(["metric".to_string(), metric.to_string()]);
+        }
+        spec
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.destination == "default"
+    }
+
+    /// Routes added by the kernel for connected subnets are not "static"
+    pub fn is_kernel(&self) -> bool {
+        self.proto.as_deref() == Some("kernel")
+    }
+
+    fn command(&self) -> String {
+        format!("ip route add {}", self.spec().join(" "))
+    }
+}
+
+/// Current IPv4 routing table
+pub fn list() -> Result<Vec<Route>> {
+    let output = Command::new("ip").args(["route", "show"]).output()?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(Route::parse)
+        .collect())
+}
+
+fn run_ip(action: &str, route: &Route) -> Result<()> {
+    let output = audit::output(
+        root::command("ip")
+            .args(["route", action])
+            .args(route.spec()),
+    )?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "ip route {}: {}",
+            action,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Add a route given as `ip route` arguments, e.g. "10.0.0.0/8 via 192.168.1.254"
+pub fn add(spec: &str) -> Result<Route> {
+    let route = Route::parse(spec)
+        .filter(|r| r.gateway.is_some() || r.dev.is_some())
+        .ok_or_else(|| {
+            AppError::Config("Expected '<destination> via <gateway> [dev <iface>]'".to_string())
+        })?;
+    run_ip("add", &route)?;
+    Ok(route)
+}
+
+pub fn delete(route: &Route) -> Result<()> {
+    run_ip("del", route)
+}
+
+/// Whether a route is restored at boot, either as the rc.inet1.conf
+/// GATEWAY or through the rc.local routes block
+pub fn is_persisted(route: &Route) -> bool {
+    if route.is_default() {
+        return route.gateway.is_some()
+            && Inet1Config::load()
+                .ok()
+                .and_then(|c| c.get("GATEWAY", None))
+                == route.gateway;
+    }
+    rclocal::managed_block(ROUTES_BLOCK).contains(&route.command())
+}
+
+/// Persist or forget a route. The default route maps onto rc.inet1.conf's
+/// GATEWAY; anything else, which rc.inet1 cannot express, goes to rc.local.
+pub fn set_persisted(route: &Route, persist: bool) -> Result<()> {
+    if route.is_default() {
+        let mut config = Inet1Config::load()?;
+        let gateway = if persist {
+            route.gateway.clone().unwrap_or_default()
+        } else {
+            String::new()
+        };
+        config.set("GATEWAY", None, &gateway);
+        return config.save().map(|_| ());
+    }
+
+    let command = route.command();
+    let mut block = rclocal::managed_block(ROUTES_BLOCK);
+    block.retain(|l| *l != command);
+    if persist {
+        block.push(command);
+    }
+    rclocal::set_managed_block(ROUTES_BLOCK, &block)
+}
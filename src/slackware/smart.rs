@@ -0,0 +1,395 @@
+use std::process::Command;
+
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// smartctl exit status bits that mean it couldn't talk to the drive at
+/// all; the others report what it found
+const SMARTCTL_FATAL: i32 = 0b11;
+
+/// A row of the ATA attribute table
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub id: u32,
+    pub name: String,
+    /// Normalized value, worst seen and failure threshold
+    pub value: u32,
+    pub worst: u32,
+    pub thresh: u32,
+    pub raw: String,
+}
+
+/// A self-test log entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTest {
+    pub description: String,
+    pub status: String,
+    pub lifetime_hours: String,
+}
+
+impl SelfTest {
+    pub fn passed(&self) -> bool {
+        self.status.starts_with("Completed without error") || self.status == "Completed"
+    }
+}
+
+/// What `smartctl -i -H -A -l selftest` says about a drive
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SmartReport {
+    pub model: String,
+    pub serial: String,
+    /// The overall health assessment, `None` when SMART is unavailable
+    pub healthy: Option<bool>,
+    pub attributes: Vec<Attribute>,
+    /// "Name: value" lines of the NVMe health log
+    pub nvme: Vec<(String, String)>,
+    /// Newest first
+    pub self_tests: Vec<SelfTest>,
+    /// "Self-test routine in progress..." and similar
+    pub test_in_progress: Option<String>,
+}
+
+/// A headline figure and whether it is a warning sign
+#[derive(Debug, Clone, PartialEq)]
+pub struct Highlight {
+    pub label: &'static str,
+    pub value: String,
+    pub warn: bool,
+}
+
+fn raw_number(raw: &str) -> Option<u64> {
+    raw.split_whitespace().next()?.parse().ok()
+}
+
+impl SmartReport {
+    pub fn parse(output: &str) -> Self {
+        let mut report = Self::default();
+        let mut section = "";
+        for line in output.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("ID# ATTRIBUTE_NAME") {
+                section = "attributes";
+                continue;
+            }
+            if trimmed.starts_with("Num") && trimmed.contains("Test_Description") {
+                section = "selftest";
+                continue;
+            }
+            if trimmed.starts_with("SMART/Health Information") {
+                section = "nvme";
+                continue;
+            }
+            if trimmed.is_empty() {
+                section = "";
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                match key.trim() {
+                    "Device Model" | "Model Number" | "Product" => {
+                        report.model = value.to_string();
+                    }
+                    "Serial Number" | "Serial number" => report.serial = value.to_string(),
+                    "SMART overall-health self-assessment test result" | "SMART Health Status" => {
+                        report.healthy = Some(value == "PASSED" || value == "OK");
+                    }
+                    _ => {}
+                }
+            }
+            if trimmed.contains("Self-test routine in progress")
+                || trimmed.starts_with("Self-test execution status")
+                    && trimmed.contains("in progress")
+            {
+                report.test_in_progress = Some(trimmed.to_string());
+            }
+
+            match section {
+                "attributes" => {
+                    let fields: Vec<&str> = trimmed.split_whitespace().collect();
+                    if fields.len() >= 10 {
+                        if let (Ok(id), Ok(value), Ok(worst), Ok(thresh)) = (
+                            fields[0].parse(),
+                            fields[3].parse(),
+                            fields[4].parse(),
+                            fields[5].parse(),
+                        ) {
+                            report.attributes.push(Attribute {
+                                id,
+                                name: fields[1].to_string(),
+                                value,
+                                worst,
+                                thresh,
+                                raw: fields[9..].join(" "),
+                            });
+                        }
+                    }
+                }
+                "selftest" if trimmed.starts_with('#') => {
+                    // Columns are separated by runs of spaces; the status
+                    // itself may contain single spaces
+                    let columns: Vec<&str> = trimmed
+                        .split("  ")
+                        .map(str::trim)
+                        .filter(|c| !c.is_empty())
+                        .collect();
+                    if columns.len() >= 4 {
+                        let status = columns[2].to_string();
+                        // ATA logs put "Remaining" between status and hours
+                        let hours = if columns[3].ends_with('%') && columns.len() > 4 {
+                            columns[4]
+                        } else {
+                            columns[3]
+                        };
+                        report.self_tests.push(SelfTest {
+                            description: columns[1].to_string(),
+                            status,
+                            lifetime_hours: hours.to_string(),
+                        });
+                    }
+                }
+                "nvme" => {
+                    if let Some((key, value)) = trimmed.split_once(':') {
+                        report
+                            .nvme
+                            .push((key.trim().to_string(), value.trim().to_string()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        report
+    }
+
+    pub fn attribute(&self, id: u32) -> Option<&Attribute> {
+        self.attributes.iter().find(|a| a.id == id)
+    }
+
+    fn nvme_value(&self, key: &str) -> Option<&str> {
+        self.nvme
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// How much of its rated endurance an SSD has used, in percent
+    pub fn wear_used(&self) -> Option<u32> {
+        if let Some(used) = self.nvme_value("Percentage Used") {
+            return used.trim_end_matches('%').parse().ok();
+        }
+        // Samsung, Intel and others report remaining life as the
+        // normalized value, counting down from 100
+        [177, 233, 231, 202]
+            .iter()
+            .find_map(|id| self.attribute(*id))
+            .map(|a| 100u32.saturating_sub(a.value.min(100)))
+    }
+
+    /// Reallocated and pending sectors, wear, temperature and power-on time
+    pub fn highlights(&self) -> Vec<Highlight> {
+        let mut highlights = Vec::new();
+        let mut counter = |label: &'static str, value: Option<u64>| {
+            if let Some(value) = value {
+                highlights.push(Highlight {
+                    label,
+                    value: value.to_string(),
+                    warn: value > 0,
+                });
+            }
+        };
+        counter(
+            "Reallocated sectors",
+            self.attribute(5).and_then(|a| raw_number(&a.raw)),
+        );
+        counter(
+            "Pending sectors",
+            self.attribute(197).and_then(|a| raw_number(&a.raw)),
+        );
+        counter(
+            "Uncorrectable",
+            self.attribute(198).and_then(|a| raw_number(&a.raw)),
+        );
+        counter(
+            "Media errors",
+            self.nvme_value("Media and Data Integrity Errors")
+                .and_then(|v| v.replace(',', "").parse().ok()),
+        );
+
+        if let Some(used) = self.wear_used() {
+            highlights.push(Highlight {
+                label: "Wear level",
+                value: format!("{}% used", used),
+                warn: used >= 90,
+            });
+        }
+        let temperature = self
+            .nvme_value("Temperature")
+            .and_then(raw_number)
+            .or_else(|| {
+                [194, 190]
+                    .iter()
+                    .find_map(|id| self.attribute(*id))
+                    .and_then(|a| raw_number(&a.raw))
+            });
+        if let Some(celsius) = temperature {
+            highlights.push(Highlight {
+                label: "Temperature",
+                value: format!("{} °C", celsius),
+                warn: celsius >= 60,
+            });
+        }
+        let hours = self
+            .nvme_value("Power On Hours")
+            .and_then(|v| v.replace(',', "").parse().ok())
+            .or_else(|| self.attribute(9).and_then(|a| raw_number(&a.raw)));
+        if let Some(hours) = hours {
+            highlights.push(Highlight {
+                label: "Power-on time",
+                value: format!("{} h", hours),
+                warn: false,
+            });
+        }
+        highlights
+    }
+}
+
+/// Whole disks, as opposed to partitions, loop devices and optical drives
+pub fn physical_drives() -> Vec<String> {
+    let Ok(output) = Command::new("lsblk")
+        .args(["-d", "-n", "-o", "NAME,TYPE"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            let is_disk = fields.next() == Some("disk");
+            (is_disk && !name.starts_with("zram")).then(|| format!("/dev/{}", name))
+        })
+        .collect()
+}
+
+fn smartctl(args: &[&str]) -> Result<String> {
+    let output = root::command("smartctl").args(args).output().map_err(|e| {
+        AppError::CommandFailed(format!("smartctl: {} (is smartmontools installed?)", e))
+    })?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if output
+        .status
+        .code()
+        .map_or(true, |c| c & SMARTCTL_FATAL != 0)
+    {
+        let reason = stdout
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("failed");
+        return Err(AppError::CommandFailed(format!(
+            "smartctl {}: {}",
+            args.join(" "),
+            reason.trim()
+        )));
+    }
+    Ok(stdout)
+}
+
+pub fn report(device: &str) -> Result<SmartReport> {
+    Ok(SmartReport::parse(&smartctl(&[
+        "-i", "-H", "-A", "-l", "selftest", device,
+    ])?))
+}
+
+/// Start a short or extended self-test, returning smartctl's estimate of
+/// when it finishes
+pub fn start_self_test(device: &str, long: bool) -> Result<String> {
+    let kind = if long { "long" } else { "short" };
+    let output = smartctl(&["-t", kind, device])?;
+    Ok(output
+        .lines()
+        .find(|l| l.starts_with("Please wait"))
+        .unwrap_or("Self-test started")
+        .trim()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ata() {
+        let report = SmartReport::parse(
+            "=== START OF INFORMATION SECTION ===\n\
+             Device Model:     Samsung SSD 860 EVO 500GB\n\
+             Serial Number:    S3Z9NB0K123456\n\
+             \n\
+             === START OF READ SMART DATA SECTION ===\n\
+             SMART overall-health self-assessment test result: PASSED\n\
+             \n\
+             ID# ATTRIBUTE_NAME          FLAG     VALUE WORST THRESH TYPE      UPDATED  \
+             WHEN_FAILED RAW_VALUE\n  \
+             5 Reallocated_Sector_Ct   0x0033   100   100   010    Pre-fail  Always       \
+             -       8\n  \
+             9 Power_On_Hours          0x0032   095   095   000    Old_age   Always       \
+             -       21000\n\
+             177 Wear_Leveling_Count     0x0013   094   094   000    Pre-fail  Always       \
+             -       87\n\
+             194 Temperature_Celsius     0x0022   067   052   000    Old_age   Always       \
+             -       33 (Min/Max 18/48)\n\
+             \n\
+             SMART Self-test log structure revision number 1\n\
+             Num  Test_Description    Status                  Remaining  LifeTime(hours)  \
+             LBA_of_first_error\n\
+             # 1  Short offline       Completed without error       00%     20999         -\n\
+             # 2  Extended offline    Completed: read failure       90%     20000         \
+             123456\n",
+        );
+        assert_eq!(report.model, "Samsung SSD 860 EVO 500GB");
+        assert_eq!(report.healthy, Some(true));
+        assert_eq!(report.attributes.len(), 4);
+        assert_eq!(report.attribute(194).unwrap().raw, "33 (Min/Max 18/48)");
+        assert_eq!(report.wear_used(), Some(6));
+        assert_eq!(report.self_tests.len(), 2);
+        assert!(report.self_tests[0].passed());
+        assert_eq!(report.self_tests[1].status, "Completed: read failure");
+        assert_eq!(report.self_tests[1].lifetime_hours, "20000");
+
+        let highlights = report.highlights();
+        assert_eq!(highlights[0].label, "Reallocated sectors");
+        assert!(highlights[0].warn);
+    }
+
+    #[test]
+    fn test_parse_nvme() {
+        let report = SmartReport::parse(
+            "Model Number:                       Samsung SSD 970 EVO Plus 1TB\n\
+             \n\
+             SMART overall-health self-assessment test result: PASSED\n\
+             \n\
+             SMART/Health Information (NVMe Log 0x02)\n\
+             Critical Warning:                   0x00\n\
+             Temperature:                        38 Celsius\n\
+             Percentage Used:                    2%\n\
+             Power On Hours:                     1,234\n\
+             Media and Data Integrity Errors:    0\n",
+        );
+        assert_eq!(report.wear_used(), Some(2));
+        let highlights = report.highlights();
+        let labels: Vec<(&str, &str)> = highlights
+            .iter()
+            .map(|h| (h.label, h.value.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("Media errors", "0"),
+                ("Wear level", "2% used"),
+                ("Temperature", "38 °C"),
+                ("Power-on time", "1234 h"),
+            ]
+        );
+    }
+}
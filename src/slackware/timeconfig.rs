@@ -0,0 +1,213 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Command;
+
+use crate::slackware::audit;
+use crate::slackware::config::Transaction;
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+const ZONEINFO: &str = "/usr/share/zoneinfo";
+const LOCALTIME: &str = "/etc/localtime";
+/// Older timeconfig copied the zone file and recorded where it came from here
+const LOCALTIME_COPIED_FROM: &str = "/etc/localtime-copied-from";
+const HARDWARECLOCK: &str = "/etc/hardwareclock";
+const LANG_SH: &str = "/etc/profile.d/lang.sh";
+const LANG_CSH: &str = "/etc/profile.d/lang.csh";
+
+/// How the hardware clock keeps time, as rc.S reads it from /etc/hardwareclock
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HardwareClock {
+    Utc,
+    Localtime,
+}
+
+impl HardwareClock {
+    /// rc.S treats anything but a "localtime" line as UTC
+    pub fn parse(content: &str) -> Self {
+        let localtime = content
+            .lines()
+            .map(str::trim)
+            .any(|line| line.starts_with("localtime"));
+        if localtime {
+            HardwareClock::Localtime
+        } else {
+            HardwareClock::Utc
+        }
+    }
+
+    pub fn load() -> Self {
+        Self::parse(&fs::read_to_string(HARDWARECLOCK).unwrap_or_default())
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HardwareClock::Utc => "UTC",
+            HardwareClock::Localtime => "Local time",
+        }
+    }
+
+    /// Record the mode the way timeconfig does and write the system time
+    /// to the hardware clock in it, so the clock is right on next boot
+    pub fn save(&self) -> Result<()> {
+        let (value, flag) = match self {
+            HardwareClock::Utc => ("UTC", "--utc"),
+            HardwareClock::Localtime => ("localtime", "--localtime"),
+        };
+        Transaction::begin(format!("Keep the hardware clock in {}", value)).write(
+            Path::new(HARDWARECLOCK),
+            format!(
+                "# /etc/hardwareclock\n#\n# Tells how the hardware clock time is stored.\n\
+                 # You should run timeconfig to edit this.\n\n{}\n",
+                value
+            ),
+        )?;
+        run("hwclock", &["--systohc", flag])
+    }
+}
+
+fn run(cmd: &str, args: &[&str]) -> Result<()> {
+    let output = audit::output(root::command(cmd).args(args))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// The zone /etc/localtime points at, e.g. "Europe/Berlin"
+pub fn timezone() -> Option<String> {
+    let target = fs::read_link(LOCALTIME)
+        .or_else(|_| fs::read_link(LOCALTIME_COPIED_FROM))
+        .ok()?;
+    let zone = target.strip_prefix(ZONEINFO).ok()?;
+    Some(zone.to_string_lossy().to_string())
+}
+
+/// Whether `zone` names a zone file, like "America/New_York" or "UTC"
+pub fn is_timezone(zone: &str) -> bool {
+    !zone.is_empty()
+        && !zone.starts_with('/')
+        && !zone.split('/').any(|part| part == "..")
+        && fs::read(Path::new(ZONEINFO).join(zone)).is_ok_and(|data| data.starts_with(b"TZif"))
+}
+
+/// Point /etc/localtime at a zone, as timeconfig does
+pub fn set_timezone(zone: &str) -> Result<()> {
+    if !is_timezone(zone) {
+        return Err(AppError::Config(format!("Unknown timezone: {}", zone)));
+    }
+    for path in [LOCALTIME, LOCALTIME_COPIED_FROM] {
+        match root::remove(Path::new(path)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+    root::symlink(&Path::new(ZONEINFO).join(zone), Path::new(LOCALTIME))?;
+    Ok(())
+}
+
+/// LANG as set by the first active `export LANG=` line of lang.sh content
+pub fn parse_lang(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("export LANG=")?;
+        Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+    })
+}
+
+/// `content` with the first line starting with `prefix` replaced by
+/// `line`, which is appended when there is none. Commented-out lines
+/// are left alone.
+pub fn replace_line(content: &str, prefix: &str, line: &str) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+    match lines
+        .iter()
+        .position(|l| l.trim_start().starts_with(prefix))
+    {
+        Some(i) => lines[i] = line,
+        None => lines.push(line),
+    }
+    lines.join("\n") + "\n"
+}
+
+pub fn lang() -> Option<String> {
+    parse_lang(&fs::read_to_string(LANG_SH).ok()?)
+}
+
+/// Locales `locale -a` knows, e.g. "en_US.utf8"
+pub fn locales() -> Vec<String> {
+    Command::new("locale")
+        .arg("-a")
+        .output()
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// "en_US.UTF-8" and "en_US.utf8" are the same locale
+fn normalize_locale(name: &str) -> String {
+    name.to_lowercase().replace('-', "")
+}
+
+/// Set the login LANG in lang.sh, and lang.csh when there is one
+pub fn set_lang(lang: &str) -> Result<()> {
+    let available = locales();
+    if lang.contains(char::is_whitespace)
+        || !available
+            .iter()
+            .any(|l| normalize_locale(l) == normalize_locale(lang))
+    {
+        return Err(AppError::Config(format!("Unknown locale: {}", lang)));
+    }
+    let sh = fs::read_to_string(LANG_SH).unwrap_or_default();
+    let mut transaction = Transaction::begin(format!("Set LANG to {}", lang));
+    transaction.write(
+        Path::new(LANG_SH),
+        replace_line(&sh, "export LANG=", &format!("export LANG={}", lang)),
+    )?;
+    if let Ok(csh) = fs::read_to_string(LANG_CSH) {
+        transaction.write(
+            Path::new(LANG_CSH),
+            replace_line(&csh, "setenv LANG ", &format!("setenv LANG {}", lang)),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lang_and_clock() {
+        let sh = "#!/bin/sh\n# en_US is the Slackware default locale:\n\
+                  #export LANG=en_US\nexport LANG=en_US.UTF-8\n\nexport LC_COLLATE=C\n";
+        assert_eq!(parse_lang(sh).as_deref(), Some("en_US.UTF-8"));
+        let updated = replace_line(sh, "export LANG=", "export LANG=de_DE.UTF-8");
+        assert_eq!(parse_lang(&updated).as_deref(), Some("de_DE.UTF-8"));
+        assert!(updated.contains("#export LANG=en_US\n"));
+        assert!(updated.ends_with("export LC_COLLATE=C\n"));
+        assert_eq!(
+            replace_line("", "export LANG=", "export LANG=C"),
+            "export LANG=C\n"
+        );
+
+        assert_eq!(
+            HardwareClock::parse("# /etc/hardwareclock\n\nlocaltime\n"),
+            HardwareClock::Localtime
+        );
+        assert_eq!(
+            HardwareClock::parse("# localtime\nUTC\n"),
+            HardwareClock::Utc
+        );
+        assert_eq!(HardwareClock::parse(""), HardwareClock::Utc);
+    }
+}
@@ -0,0 +1,354 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::slackware::audit;
+use crate::slackware::config;
+use crate::slackware::inet1::{Inet1Config, INET1_CONF};
+use crate::utils::error::{AppError, Result};
+use crate::utils::root;
+
+/// wpa_supplicant configuration used by rc.inet1 and rc.wireless
+pub const WPA_SUPPLICANT_CONF: &str = "/etc/wpa_supplicant.conf";
+
+const WPA_SUPPLICANT_HEADER: &str =
+    "ctrl_interface=/var/run/wpa_supplicant\nctrl_interface_group=0\nupdate_config=1\n";
+
+/// A network found by a wireless scan
+#[derive(Debug, Clone, PartialEq)]
+pub struct WirelessNetwork {
+    pub ssid: String,
+    pub bssid: String,
+    pub signal_dbm: i32,
+    pub frequency: String,
+    pub secured: bool,
+}
+
+impl WirelessNetwork {
+    /// Rough 0-100 link quality derived from the signal level
+    pub fn quality(&self) -> u8 {
+        signal_quality(self.signal_dbm)
+    }
+}
+
+/// Current association of a wireless interface
+#[derive(Debug, Clone)]
+pub struct WirelessLink {
+    pub ssid: String,
+    pub signal_dbm: i32,
+}
+
+pub fn signal_quality(dbm: i32) -> u8 {
+    ((dbm + 100) * 2).clamp(0, 100) as u8
+}
+
+/// Network interfaces with wireless extensions
+pub fn wireless_interfaces() -> Vec<String> {
+    let mut ifaces: Vec<String> = fs::read_dir("/sys/class/net")
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| {
+                    e.path().join("wireless").exists() || e.path().join("phy80211").exists()
+                })
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    ifaces.sort();
+    ifaces
+}
+
+/// Scan for networks with iw, falling back to iwlist
+pub fn scan(iface: &str) -> Result<Vec<WirelessNetwork>> {
+    // The interface has to be up for either tool to scan
+    let _ = audit::output(root::command("ip").args(["link", "set", iface, "up"]));
+
+    let mut networks = match root::command("iw").args(["dev", iface, "scan"]).output() {
+        Ok(output) if output.status.success() => {
+            parse_iw_scan(&String::from_utf8_lossy(&output.stdout))
+        }
+        iw_result => {
+            let output = root::command("iwlist")
+                .args([iface, "scan"])
+                .output()
+                .map_err(|e| AppError::CommandFailed(format!("iw/iwlist not available: {}", e)))?;
+            if !output.status.success() {
+                let stderr = match iw_result {
+                    Ok(o) => String::from_utf8_lossy(&o.stderr).trim().to_string(),
+                    Err(_) => String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                };
+                return Err(AppError::CommandFailed(format!("Scan failed: {}", stderr)));
+            }
+            parse_iwlist_scan(&String::from_utf8_lossy(&output.stdout))
+        }
+    };
+
+    // Keep the strongest access point for each SSID
+    networks.sort_by_key(|n| std::cmp::Reverse(n.signal_dbm));
+    let mut seen = Vec::new();
+    networks.retain(|n| {
+        if n.ssid.is_empty() || seen.contains(&n.ssid) {
+            return false;
+        }
+        seen.push(n.ssid.clone());
+        true
+    });
+    Ok(networks)
+}
+
+fn parse_iw_scan(output: &str) -> Vec<WirelessNetwork> {
+    let mut networks = Vec::new();
+    let mut current: Option<WirelessNetwork> = None;
+
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("BSS ") {
+            networks.extend(current.take());
+            current = Some(WirelessNetwork {
+                ssid: String::new(),
+                bssid: rest.chars().take(17).collect(),
+                signal_dbm: -100,
+                frequency: String::new(),
+                secured: false,
+            });
+            continue;
+        }
+        let Some(net) = current.as_mut() else {
+            continue;
+        };
+        let line = line.trim();
+        if let Some(ssid) = line.strip_prefix("SSID: ") {
+            net.ssid = ssid.to_string();
+        } else if let Some(signal) = line.strip_prefix("signal: ") {
+            net.signal_dbm = signal
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse::<f32>().ok())
+                .map(|s| s as i32)
+                .unwrap_or(-100);
+        } else if let Some(freq) = line.strip_prefix("freq: ") {
+            net.frequency = format!("{} MHz", freq.trim());
+        } else if line.starts_with("RSN:")
+            || line.starts_with("WPA:")
+            || (line.starts_with("capability:") && line.contains("Privacy"))
+        {
+            net.secured = true;
+        }
+    }
+    networks.extend(current);
+    networks
+}
+
+fn parse_iwlist_scan(output: &str) -> Vec<WirelessNetwork> {
+    let mut networks = Vec::new();
+    let mut current: Option<WirelessNetwork> = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with("Cell ") {
+            networks.extend(current.take());
+            current = Some(WirelessNetwork {
+                ssid: String::new(),
+                bssid: line.rsplit("Address: ").next().unwrap_or("").to_string(),
+                signal_dbm: -100,
+                frequency: String::new(),
+                secured: false,
+            });
+            continue;
+        }
+        let Some(net) = current.as_mut() else {
+            continue;
+        };
+        if let Some(essid) = line.strip_prefix("ESSID:") {
+            net.ssid = essid.trim_matches('"').to_string();
+        } else if let Some(level) = line.split("Signal level=").nth(1) {
+            net.signal_dbm = level
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(-100);
+        } else if let Some(freq) = line.strip_prefix("Frequency:") {
+            net.frequency = freq.split(" (").next().unwrap_or(freq).to_string();
+        } else if line == "Encryption key:on" {
+            net.secured = true;
+        }
+    }
+    networks.extend(current);
+    networks
+}
+
+/// Network the interface is currently associated with
+pub fn link_status(iface: &str) -> Option<WirelessLink> {
+    let output = Command::new("iw")
+        .args(["dev", iface, "link"])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut link = WirelessLink {
+        ssid: String::new(),
+        signal_dbm: -100,
+    };
+    for line in stdout.lines().map(str::trim) {
+        if let Some(ssid) = line.strip_prefix("SSID: ") {
+            link.ssid = ssid.to_string();
+        } else if let Some(signal) = line.strip_prefix("signal: ") {
+            link.signal_dbm = signal
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(-100);
+        }
+    }
+    (!link.ssid.is_empty()).then_some(link)
+}
+
+/// Build a wpa_supplicant `network={}` block, hashing the passphrase with
+/// wpa_passphrase when it is available so it is not stored in clear text
+fn network_block(ssid: &str, passphrase: Option<&str>) -> Result<String> {
+    let Some(passphrase) = passphrase else {
+        return Ok(format!(
+            "network={{\n\tssid=\"{}\"\n\tkey_mgmt=NONE\n}}\n",
+            ssid
+        ));
+    };
+    if !(8..=63).contains(&passphrase.len()) {
+        return Err(AppError::Config(
+            "WPA passphrase must be 8 to 63 characters".to_string(),
+        ));
+    }
+
+    match Command::new("wpa_passphrase")
+        .args([ssid, passphrase])
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            let block: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|l| !l.trim_start().starts_with("#psk="))
+                .map(|l| l.to_string())
+                .collect();
+            Ok(block.join("\n") + "\n")
+        }
+        _ => Ok(format!(
+            "network={{\n\tssid=\"{}\"\n\tpsk=\"{}\"\n}}\n",
+            ssid, passphrase
+        )),
+    }
+}
+
+/// Remove existing `network={}` blocks for an SSID
+fn remove_network(content: &str, ssid: &str) -> String {
+    let needle = format!("ssid=\"{}\"", ssid);
+    let mut result = Vec::new();
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in content.lines() {
+        if let Some(lines) = block.as_mut() {
+            lines.push(line);
+            if line.trim() == "}" {
+                let lines = block.take().unwrap_or_default();
+                if !lines.iter().any(|l| l.trim() == needle) {
+                    result.extend(lines);
+                }
+            }
+        } else if line.trim_start().starts_with("network={") {
+            block = Some(vec![line]);
+        } else {
+            result.push(line);
+        }
+    }
+    result.extend(block.unwrap_or_default());
+    result.join("\n")
+}
+
+/// Store credentials for a network in wpa_supplicant.conf
+///
+/// Returns the path of the backup copy of the previous file.
+pub fn configure_network(ssid: &str, passphrase: Option<&str>) -> Result<PathBuf> {
+    let block = network_block(ssid, passphrase)?;
+    let existing = fs::read_to_string(WPA_SUPPLICANT_CONF)
+        .unwrap_or_else(|_| WPA_SUPPLICANT_HEADER.to_string());
+    let mut content = remove_network(&existing, ssid).trim_end().to_string();
+    content.push_str("\n\n");
+    content.push_str(&block);
+    config::write_with_backup(Path::new(WPA_SUPPLICANT_CONF), &content)
+}
+
+/// Make rc.inet1 bring this interface up through wpa_supplicant at boot
+pub fn enable_at_boot(iface: &str) -> Result<()> {
+    let mut config = Inet1Config::load()?;
+    let index = config.index_or_insert(iface);
+    if config.get("WLAN_WPA", Some(index)).as_deref() == Some("wpa_supplicant") {
+        return Ok(());
+    }
+    config.set("WLAN_WPA", Some(index), "wpa_supplicant");
+    let has_static = config
+        .get("IPADDR", Some(index))
+        .is_some_and(|ip| !ip.is_empty());
+    if !has_static {
+        config.set("USE_DHCP", Some(index), "yes");
+    }
+    config
+        .save()
+        .map(|_| ())
+        .map_err(|e| AppError::FileOperation(format!("Failed to update {}: {}", INET1_CONF, e)))
+}
+
+/// Associate using the stored wpa_supplicant configuration
+pub fn associate(iface: &str) -> Result<()> {
+    let reconfigured = audit::output(root::command("wpa_cli").args(["-i", iface, "reconfigure"]))
+        .map(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).contains("OK"))
+        .unwrap_or(false);
+
+    if !reconfigured {
+        let output = audit::output(root::command("wpa_supplicant").args([
+            "-B",
+            "-i",
+            iface,
+            "-c",
+            WPA_SUPPLICANT_CONF,
+        ]))
+        .map_err(|e| AppError::CommandFailed(format!("wpa_supplicant: {}", e)))?;
+        if !output.status.success() {
+            return Err(AppError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+    }
+
+    // Ask for a lease; harmless if dhcpcd is already managing the interface
+    let _ = audit::output(root::command("dhcpcd").args(["-n", iface]));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iw_scan() {
+        let output = "BSS 00:11:22:33:44:55(on wlan0)\n\
+\tfreq: 2437\n\
+\tsignal: -48.00 dBm\n\
+\tcapability: ESS Privacy ShortSlotTime (0x0411)\n\
+\tSSID: HomeNet\n\
+BSS 66:77:88:99:aa:bb(on wlan0)\n\
+\tsignal: -80.00 dBm\n\
+\tcapability: ESS (0x0401)\n\
+\tSSID: Cafe\n";
+        let networks = parse_iw_scan(output);
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].ssid, "HomeNet");
+        assert_eq!(networks[0].signal_dbm, -48);
+        assert!(networks[0].secured);
+        assert!(!networks[1].secured);
+    }
+
+    #[test]
+    fn test_remove_network() {
+        let content = "update_config=1\n\nnetwork={\n\tssid=\"Old\"\n\tpsk=\"x\"\n}\n\nnetwork={\n\tssid=\"Keep\"\n}\n";
+        let result = remove_network(content, "Old");
+        assert!(!result.contains("Old"));
+        assert!(result.contains("ssid=\"Keep\""));
+    }
+}
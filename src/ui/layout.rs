@@ -21,6 +21,11 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// Smallest terminal the layout is drawn in; below it only a notice is
+/// shown, as the tabs' panes would overlap
+pub const MIN_WIDTH: u16 = 80;
+pub const MIN_HEIGHT: u16 = 24;
+
 /// Main application layout with header, content, and status bar
 pub struct AppLayout {
     pub header: Rect,
@@ -30,6 +35,11 @@ pub struct AppLayout {
 }
 
 impl AppLayout {
+    /// Whether `area` is big enough for the layout
+    pub fn fits(area: Rect) -> bool {
+        area.width >= MIN_WIDTH && area.height >= MIN_HEIGHT
+    }
+
     pub fn new(area: Rect) -> Self {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -49,43 +59,3 @@ impl AppLayout {
         }
     }
 }
-
-/// Two-column layout for forms
-pub struct FormLayout {
-    pub left: Rect,
-    pub right: Rect,
-}
-
-impl FormLayout {
-    pub fn new(area: Rect) -> Self {
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(area);
-
-        Self {
-            left: chunks[0],
-            right: chunks[1],
-        }
-    }
-}
-
-/// Layout with a main content area and output panel
-pub struct ContentWithOutput {
-    pub main: Rect,
-    pub output: Rect,
-}
-
-impl ContentWithOutput {
-    pub fn new(area: Rect, output_height: u16) -> Self {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Min(5), Constraint::Length(output_height)])
-            .split(area);
-
-        Self {
-            main: chunks[0],
-            output: chunks[1],
-        }
-    }
-}
@@ -1,5 +1,12 @@
+pub mod clipboard;
 pub mod layout;
+pub mod mouse;
+/// Rendering against ratatui's TestBackend, and snapshots of what was
+/// drawn, for the UI tests
+#[cfg(test)]
+pub mod testing;
 pub mod theme;
+pub mod toast;
 pub mod widgets;
 
 pub use layout::centered_rect;
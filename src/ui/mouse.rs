@@ -0,0 +1,32 @@
+use ratatui::buffer::Buffer;
+
+/// What lists mark their selected row with
+const HIGHLIGHT_SYMBOLS: &[&str] = &["▶", "→"];
+
+const BORDER: &str = "│";
+
+/// How many rows below (or above, when negative) the selected row of a
+/// list a click landed. Lists are found in the last drawn frame by their
+/// highlight symbol just inside the left border, so every tab's lists
+/// take clicks without tracking where they were drawn.
+pub fn rows_from_selection(frame: &Buffer, column: u16, row: u16) -> Option<i32> {
+    let area = frame.area;
+    let symbol = |x: u16, y: u16| frame.cell((x, y)).map_or("", |cell| cell.symbol());
+    let mut best: Option<(u16, i32)> = None;
+    for y in area.top()..area.bottom() {
+        for x in area.left() + 1..column.saturating_add(1).min(area.right()) {
+            if !HIGHLIGHT_SYMBOLS.contains(&symbol(x, y)) || symbol(x - 1, y) != BORDER {
+                continue;
+            }
+            // The click has to be inside the same bordered block
+            let (top, bottom) = (y.min(row), y.max(row));
+            let same_block = (top..=bottom).all(|r| symbol(x - 1, r) == BORDER)
+                && (x..column).all(|c| symbol(c, row) != BORDER);
+            // The innermost list wins when blocks sit side by side
+            if same_block && best.map_or(true, |(bx, _)| x > bx) {
+                best = Some((x, row as i32 - y as i32));
+            }
+        }
+    }
+    best.map(|(_, rows)| rows)
+}
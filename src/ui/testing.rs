@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::PathBuf;
+
+use ratatui::{backend::TestBackend, buffer::Buffer, text::Span, Frame, Terminal};
+
+/// Set to write the snapshots afresh instead of comparing against them
+const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// Draw one frame of `width` x `height` cells and return it
+pub fn render(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(draw).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// The text of `buffer`, one line per row without trailing blanks. Styles
+/// are left out: the themes and NO_COLOR change them.
+pub fn text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            // The cells a wide character covers hold nothing of their own
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer[(x, y)].symbol();
+            skip = Span::raw(symbol).width().saturating_sub(1);
+            line.push_str(symbol);
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Compare `buffer` with the snapshot `name` in src/ui/snapshots. A
+/// missing snapshot is written, as are all of them with UPDATE_SNAPSHOTS
+/// set; check the new files before committing them.
+pub fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/snapshots")
+        .join(format!("{}.txt", name));
+    let actual = text(buffer);
+    if std::env::var_os(UPDATE_VAR).is_some() || !path.exists() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap();
+    assert!(
+        expected == actual,
+        "snapshot {} differs, rerun with {}=1 to accept\n--- expected\n{}--- actual\n{}",
+        name,
+        UPDATE_VAR,
+        expected,
+        actual
+    );
+}
@@ -1,115 +1,340 @@
+use std::sync::RwLock;
+
+use ratatui::buffer::Buffer;
 use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+/// The theme picked in Settings; components draw with it from the next frame on
+static ACTIVE: RwLock<ThemeChoice> = RwLock::new(ThemeChoice::Default);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ThemeChoice {
+    Default,
+    Dark,
+    Light,
+    Solarized,
+    Nord,
+    Dracula,
+    /// No colors at all: state shows through symbols, bold, underline and
+    /// reverse video, for colorblind users and consoles without color
+    Monochrome,
+}
+
+impl ThemeChoice {
+    pub fn all() -> Vec<ThemeChoice> {
+        vec![
+            ThemeChoice::Default,
+            ThemeChoice::Dark,
+            ThemeChoice::Light,
+            ThemeChoice::Solarized,
+            ThemeChoice::Nord,
+            ThemeChoice::Dracula,
+            ThemeChoice::Monochrome,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemeChoice::Default => "Default",
+            ThemeChoice::Dark => "Dark",
+            ThemeChoice::Light => "Light",
+            ThemeChoice::Solarized => "Solarized",
+            ThemeChoice::Nord => "Nord",
+            ThemeChoice::Dracula => "Dracula",
+            ThemeChoice::Monochrome => "High Contrast",
+        }
+    }
+
+    pub fn colors(&self) -> ThemeColors {
+        match self {
+            // Drawn with the plain colors, which `Theme::restyle` then turns
+            // into attributes by role
+            ThemeChoice::Default | ThemeChoice::Monochrome => ThemeColors {
+                primary: Color::Cyan,
+                secondary: Color::Yellow,
+                success: Color::Green,
+                error: Color::Red,
+                warning: Color::Yellow,
+                muted: Color::DarkGray,
+                background: Color::Reset,
+                foreground: Color::White,
+            },
+            ThemeChoice::Dark => ThemeColors {
+                primary: Color::Blue,
+                secondary: Color::Magenta,
+                success: Color::Green,
+                error: Color::Red,
+                warning: Color::Yellow,
+                muted: Color::DarkGray,
+                background: Color::Rgb(30, 30, 30),
+                foreground: Color::White,
+            },
+            ThemeChoice::Light => ThemeColors {
+                primary: Color::Blue,
+                secondary: Color::Magenta,
+                success: Color::Green,
+                error: Color::Red,
+                warning: Color::Rgb(200, 150, 0),
+                muted: Color::Gray,
+                background: Color::White,
+                foreground: Color::Black,
+            },
+            ThemeChoice::Solarized => ThemeColors {
+                primary: Color::Rgb(38, 139, 210),   // Blue
+                secondary: Color::Rgb(211, 54, 130), // Magenta
+                success: Color::Rgb(133, 153, 0),    // Green
+                error: Color::Rgb(220, 50, 47),      // Red
+                warning: Color::Rgb(181, 137, 0),    // Yellow
+                muted: Color::Rgb(88, 110, 117),     // Base01
+                background: Color::Rgb(0, 43, 54),   // Base03
+                foreground: Color::Rgb(131, 148, 150), // Base0
+            },
+            ThemeChoice::Nord => ThemeColors {
+                primary: Color::Rgb(136, 192, 208),  // Nord8
+                secondary: Color::Rgb(180, 142, 173), // Nord15
+                success: Color::Rgb(163, 190, 140),  // Nord14
+                error: Color::Rgb(191, 97, 106),     // Nord11
+                warning: Color::Rgb(235, 203, 139),  // Nord13
+                muted: Color::Rgb(76, 86, 106),      // Nord3
+                background: Color::Rgb(46, 52, 64),  // Nord0
+                foreground: Color::Rgb(236, 239, 244), // Nord6
+            },
+            ThemeChoice::Dracula => ThemeColors {
+                primary: Color::Rgb(139, 233, 253),  // Cyan
+                secondary: Color::Rgb(255, 121, 198), // Pink
+                success: Color::Rgb(80, 250, 123),   // Green
+                error: Color::Rgb(255, 85, 85),      // Red
+                warning: Color::Rgb(241, 250, 140),  // Yellow
+                muted: Color::Rgb(98, 114, 164),     // Comment
+                background: Color::Rgb(40, 42, 54),  // Background
+                foreground: Color::Rgb(248, 248, 242), // Foreground
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeColors {
+    pub primary: Color,
+    pub secondary: Color,
+    pub success: Color,
+    pub error: Color,
+    pub warning: Color,
+    pub muted: Color,
+    pub background: Color,
+    pub foreground: Color,
+}
 
 /// Application theme colors and styles
 pub struct Theme;
 
 impl Theme {
-    // Colors
-    pub const BG: Color = Color::Reset;
-    pub const FG: Color = Color::White;
-    pub const ACCENT: Color = Color::Cyan;
-    pub const SUCCESS: Color = Color::Green;
-    pub const ERROR: Color = Color::Red;
-    pub const WARNING: Color = Color::Yellow;
-    pub const MUTED: Color = Color::DarkGray;
-    pub const HIGHLIGHT_BG: Color = Color::DarkGray;
+    /// Switch the whole application to another theme; with NO_COLOR set
+    /// it stays monochrome whatever was picked
+    pub fn set(choice: ThemeChoice) {
+        let choice = if Self::no_color() {
+            ThemeChoice::Monochrome
+        } else {
+            choice
+        };
+        if let Ok(mut active) = ACTIVE.write() {
+            *active = choice;
+        }
+    }
+
+    /// Whether the NO_COLOR environment variable asks for no color
+    /// (https://no-color.org: set and not empty)
+    pub fn no_color() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+    }
+
+    pub fn current() -> ThemeChoice {
+        ACTIVE.read().map(|c| *c).unwrap_or(ThemeChoice::Default)
+    }
+
+    pub fn colors() -> ThemeColors {
+        Self::current().colors()
+    }
+
+    fn fg() -> Color {
+        Self::colors().foreground
+    }
+
+    fn bg() -> Color {
+        Self::colors().background
+    }
+
+    fn accent() -> Color {
+        Self::colors().primary
+    }
+
+    fn muted_color() -> Color {
+        Self::colors().muted
+    }
+
+    /// Finish a drawn frame for the active theme. Components draw with
+    /// the palette's role colors already; what is left in the terminal's
+    /// own colors gets the palette's foreground and background, so the
+    /// Default theme leaves the terminal alone.
+    pub fn restyle(buf: &mut Buffer) {
+        match Self::current() {
+            ThemeChoice::Default => {}
+            ThemeChoice::Monochrome => Self::strip_colors(buf),
+            choice => Self::fill_reset(buf, choice.colors()),
+        }
+    }
+
+    /// Text and background are filled in separately: a light theme's
+    /// background is White, which as text would vanish on it
+    fn fill_reset(buf: &mut Buffer, colors: ThemeColors) {
+        for cell in buf.content.iter_mut() {
+            if cell.fg == Color::Reset {
+                cell.fg = colors.foreground;
+            }
+            if cell.bg == Color::Reset {
+                cell.bg = colors.background;
+            }
+        }
+    }
+
+    /// Drop every color of a drawn frame, keeping what it meant: anything
+    /// on a background (selections, the status bar, input fields) turns to
+    /// reverse video, errors are bold and underlined, warnings and
+    /// headings bold, muted text dim
+    fn strip_colors(buf: &mut Buffer) {
+        for cell in buf.content.iter_mut() {
+            let mut modifier = cell.modifier;
+            // Already reversed on a background, like the status bar key
+            // hints, it stands out by being the one cell not reversed
+            if cell.bg != Color::Reset {
+                modifier.toggle(Modifier::REVERSED);
+            }
+            modifier |= match cell.fg {
+                Color::Red | Color::LightRed => Modifier::BOLD | Modifier::UNDERLINED,
+                Color::Yellow | Color::LightYellow => Modifier::BOLD,
+                Color::Cyan | Color::LightCyan | Color::Blue | Color::LightBlue => Modifier::BOLD,
+                Color::DarkGray | Color::Gray => Modifier::DIM,
+                _ => Modifier::empty(),
+            };
+            cell.modifier = modifier;
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+        }
+    }
 
     // Styles
     pub fn default() -> Style {
-        Style::default().fg(Self::FG).bg(Self::BG)
+        Style::default().fg(Self::fg()).bg(Self::bg())
     }
 
     pub fn title() -> Style {
         Style::default()
-            .fg(Self::ACCENT)
+            .fg(Self::accent())
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn highlight() -> Style {
         Style::default()
-            .bg(Self::HIGHLIGHT_BG)
+            .bg(Self::muted_color())
             .add_modifier(Modifier::BOLD)
     }
 
-    pub fn selected() -> Style {
-        Style::default()
-            .fg(Color::Black)
-            .bg(Self::ACCENT)
-            .add_modifier(Modifier::BOLD)
+    /// Field names and other labels in front of a value
+    pub fn label() -> Style {
+        Style::default().fg(Self::accent())
     }
 
     pub fn tab_active() -> Style {
         Style::default()
-            .fg(Self::ACCENT)
+            .fg(Self::accent())
             .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
     }
 
     pub fn tab_inactive() -> Style {
-        Style::default().fg(Self::MUTED)
+        Style::default().fg(Self::muted_color())
     }
 
     pub fn success() -> Style {
-        Style::default().fg(Self::SUCCESS)
+        Style::default().fg(Self::colors().success)
     }
 
     pub fn error() -> Style {
-        Style::default().fg(Self::ERROR)
+        Style::default().fg(Self::colors().error)
     }
 
     pub fn warning() -> Style {
-        Style::default().fg(Self::WARNING)
+        Style::default().fg(Self::colors().warning)
     }
 
     pub fn muted() -> Style {
-        Style::default().fg(Self::MUTED)
+        Style::default().fg(Self::muted_color())
     }
 
     pub fn status_bar() -> Style {
-        Style::default().bg(Color::DarkGray).fg(Color::White)
+        Style::default().bg(Self::muted_color()).fg(Self::fg())
     }
 
     pub fn key_hint() -> Style {
         Style::default()
-            .fg(Self::ACCENT)
+            .fg(Self::accent())
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn progress_complete() -> Style {
-        Style::default().fg(Self::SUCCESS)
+        Style::default().fg(Self::colors().success)
     }
 
     pub fn progress_pending() -> Style {
-        Style::default().fg(Self::MUTED)
+        Style::default().fg(Self::muted_color())
     }
 
     pub fn progress_running() -> Style {
         Style::default()
-            .fg(Self::WARNING)
+            .fg(Self::colors().warning)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn input_active() -> Style {
         Style::default()
-            .fg(Self::FG)
-            .bg(Color::DarkGray)
+            .fg(Self::fg())
+            .bg(Self::muted_color())
     }
 
     pub fn input_inactive() -> Style {
-        Style::default().fg(Self::MUTED)
+        Style::default().fg(Self::muted_color())
     }
 
     pub fn border() -> Style {
-        Style::default().fg(Self::MUTED)
+        Style::default().fg(Self::muted_color())
     }
 
     pub fn border_focused() -> Style {
-        Style::default().fg(Self::ACCENT)
+        Style::default().fg(Self::accent())
     }
 
     pub fn list_selected() -> Style {
         Style::default()
             .fg(Color::Black)
-            .bg(Self::ACCENT)
+            .bg(Self::accent())
             .add_modifier(Modifier::BOLD)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_fill_reset_light() {
+        let colors = ThemeChoice::Light.colors();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buf[(1, 0)].set_style(Style::default().fg(colors.error).bg(colors.background));
+        buf[(2, 0)].set_style(Style::default().fg(colors.foreground).bg(colors.primary));
+        Theme::fill_reset(&mut buf, colors);
+
+        assert_eq!((buf[(0, 0)].fg, buf[(0, 0)].bg), (Color::Black, Color::White));
+        assert_eq!((buf[(1, 0)].fg, buf[(1, 0)].bg), (Color::Red, Color::White));
+        assert_eq!((buf[(2, 0)].fg, buf[(2, 0)].bg), (Color::Black, Color::Blue));
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::theme::{Theme, ThemeChoice};
+
+/// How long a toast stays up; errors and warnings get longer to be read
+const SHOW_FOR: Duration = Duration::from_secs(5);
+const SHOW_PROBLEM_FOR: Duration = Duration::from_secs(10);
+/// Toasts on screen at once; the rest wait their turn
+const VISIBLE: usize = 3;
+const WIDTH: u16 = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    /// Marks the level where the border color cannot
+    fn symbol(&self) -> &'static str {
+        match self {
+            ToastLevel::Info => "i",
+            ToastLevel::Success => "✓",
+            ToastLevel::Warning => "!",
+            ToastLevel::Error => "✗",
+        }
+    }
+
+    fn style(&self) -> Style {
+        match self {
+            ToastLevel::Info => Theme::title(),
+            ToastLevel::Success => Theme::success(),
+            ToastLevel::Warning => Theme::warning(),
+            ToastLevel::Error => Theme::error(),
+        }
+    }
+}
+
+struct Toast {
+    message: String,
+    level: ToastLevel,
+    /// When it came on screen; `None` while queued
+    shown: Option<Instant>,
+}
+
+/// Notifications from background work, shown over whatever tab is open
+#[derive(Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: ToastLevel, message: impl Into<String>) {
+        self.queue.push_back(Toast {
+            message: message.into(),
+            level,
+            shown: None,
+        });
+    }
+
+    /// Drop the toasts that have been up long enough and bring on waiting ones
+    pub fn on_tick(&mut self) {
+        self.queue.retain(|toast| {
+            let limit = match toast.level {
+                ToastLevel::Info | ToastLevel::Success => SHOW_FOR,
+                ToastLevel::Warning | ToastLevel::Error => SHOW_PROBLEM_FOR,
+            };
+            toast.shown.map_or(true, |shown| shown.elapsed() < limit)
+        });
+        for toast in self.queue.iter_mut().take(VISIBLE) {
+            toast.shown.get_or_insert_with(Instant::now);
+        }
+    }
+
+    /// Stack the visible toasts in the top right corner of `area`
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = WIDTH.min(area.width);
+        let mut y = area.y;
+        let monochrome = Theme::current() == ThemeChoice::Monochrome;
+        for toast in self.queue.iter().filter(|t| t.shown.is_some()) {
+            let message = if monochrome {
+                format!("{} {}", toast.level.symbol(), toast.message)
+            } else {
+                toast.message.clone()
+            };
+            // Borders plus the wrapped message
+            let text_width = width.saturating_sub(2).max(1) as usize;
+            let rows = message.chars().count().max(1).saturating_sub(1) / text_width + 1;
+            let height = (rows as u16 + 2).min(area.bottom().saturating_sub(y));
+            if height < 3 {
+                break;
+            }
+            let toast_area = Rect::new(area.right() - width, y, width, height);
+            frame.render_widget(Clear, toast_area);
+            let text = Paragraph::new(Line::from(Span::raw(message)))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(toast.level.style()),
+                );
+            frame.render_widget(text, toast_area);
+            y += height;
+        }
+    }
+}
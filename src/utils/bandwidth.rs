@@ -0,0 +1,131 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// Number of rate samples kept per interface
+const HISTORY_LEN: usize = 120;
+
+/// Recent receive/transmit rates of one interface, in bytes per second
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceRates {
+    pub rx: VecDeque<u64>,
+    pub tx: VecDeque<u64>,
+}
+
+impl InterfaceRates {
+    pub fn current(&self) -> (u64, u64) {
+        (
+            self.rx.back().copied().unwrap_or(0),
+            self.tx.back().copied().unwrap_or(0),
+        )
+    }
+
+    fn push(&mut self, rx: u64, tx: u64) {
+        if self.rx.len() == HISTORY_LEN {
+            self.rx.pop_front();
+            self.tx.pop_front();
+        }
+        self.rx.push_back(rx);
+        self.tx.push_back(tx);
+    }
+}
+
+/// Samples /sys/class/net/*/statistics to derive per-interface throughput
+pub struct BandwidthMonitor {
+    interval: Duration,
+    last_sample: Option<Instant>,
+    counters: BTreeMap<String, (u64, u64)>,
+    rates: BTreeMap<String, InterfaceRates>,
+}
+
+impl BandwidthMonitor {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_sample: None,
+            counters: BTreeMap::new(),
+            rates: BTreeMap::new(),
+        }
+    }
+
+    /// Take a sample if the interval has elapsed; returns whether it did
+    pub fn sample_if_due(&mut self) -> bool {
+        if self
+            .last_sample
+            .is_some_and(|t| t.elapsed() < self.interval)
+        {
+            return false;
+        }
+        self.sample();
+        true
+    }
+
+    pub fn sample(&mut self) {
+        let now = Instant::now();
+        let elapsed = self
+            .last_sample
+            .map(|t| now.duration_since(t).as_secs_f64());
+        let counters = Self::read_counters();
+
+        for (name, (rx, tx)) in &counters {
+            if let (Some(elapsed), Some((last_rx, last_tx))) = (elapsed, self.counters.get(name)) {
+                if elapsed > 0.0 {
+                    // Counters reset when a driver reloads; treat that as idle
+                    let rate =
+                        |now: u64, last: u64| (now.saturating_sub(last) as f64 / elapsed) as u64;
+                    self.rates
+                        .entry(name.clone())
+                        .or_default()
+                        .push(rate(*rx, *last_rx), rate(*tx, *last_tx));
+                }
+            }
+        }
+        self.rates.retain(|name, _| counters.contains_key(name));
+        self.counters = counters;
+        self.last_sample = Some(now);
+    }
+
+    fn read_counters() -> BTreeMap<String, (u64, u64)> {
+        let read = |name: &str, counter: &str| {
+            fs::read_to_string(format!("/sys/class/net/{}/statistics/{}", name, counter))
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+        };
+        fs::read_dir("/sys/class/net")
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .filter_map(|e| {
+                        let name = e.file_name().to_string_lossy().to_string();
+                        let rx = read(&name, "rx_bytes")?;
+                        let tx = read(&name, "tx_bytes")?;
+                        Some((name, (rx, tx)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn rates(&self, iface: &str) -> Option<&InterfaceRates> {
+        self.rates.get(iface)
+    }
+
+    pub fn all_rates(&self) -> impl Iterator<Item = (&String, &InterfaceRates)> {
+        self.rates.iter()
+    }
+}
+
+/// Human readable transfer rate, e.g. "1.2 MB/s"
+pub fn format_rate(bytes_per_sec: u64) -> String {
+    const KB: f64 = 1024.0;
+    let rate = bytes_per_sec as f64;
+    if rate >= KB * KB * KB {
+        format!("{:.1} GB/s", rate / (KB * KB * KB))
+    } else if rate >= KB * KB {
+        format!("{:.1} MB/s", rate / (KB * KB))
+    } else if rate >= KB {
+        format!("{:.1} KB/s", rate / KB)
+    } else {
+        format!("{} B/s", bytes_per_sec)
+    }
+}
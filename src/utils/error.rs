@@ -19,9 +19,6 @@ pub enum AppError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
-
-    #[error("User cancelled operation")]
-    UserCancelled,
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
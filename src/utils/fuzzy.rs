@@ -0,0 +1,60 @@
+/// How well `query` matches `text`, higher is better, or None when it
+/// does not. Every word of the query has to appear in order as a
+/// subsequence of the text, but the words themselves may come in any
+/// order: "restart network" finds "Network: Restart". Consecutive
+/// letters and letters starting a word count extra.
+pub fn score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    query
+        .split_whitespace()
+        .map(|word| word_score(&word.to_lowercase().chars().collect::<Vec<_>>(), &text))
+        .sum()
+}
+
+/// Best score of `word` as a subsequence of `text`, trying every place
+/// its first letter occurs
+fn word_score(word: &[char], text: &[char]) -> Option<u32> {
+    let first = *word.first()?;
+    (0..text.len())
+        .filter(|&i| text[i] == first)
+        .filter_map(|start| {
+            let mut score = 0;
+            let mut previous: Option<usize> = None;
+            let mut position = start;
+            for &c in word {
+                let found = position + text[position..].iter().position(|&t| t == c)?;
+                score += 1;
+                if previous.is_some_and(|p| p + 1 == found) {
+                    score += 3;
+                }
+                if found == 0 || !text[found - 1].is_alphanumeric() {
+                    score += 5;
+                }
+                previous = Some(found);
+                position = found + 1;
+            }
+            Some(score)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert!(score("restart network", "Network: Restart").is_some());
+        assert!(score("rstrt", "Services: Restart").is_some());
+        assert_eq!(score("xyz", "Services: Restart"), None);
+        assert_eq!(
+            score("network", "Network: Restart"),
+            score("NETWORK", "network")
+        );
+        // A word start beats letters scattered through the text
+        let backup = score("cb", "Backup: Create backup").unwrap();
+        let scattered = score("cb", "Cron: Add job").unwrap_or(0);
+        assert!(backup > scattered);
+        assert_eq!(score("", "anything"), Some(0));
+    }
+}
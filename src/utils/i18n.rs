@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::RwLock;
+
+use crate::components::settings;
+
+/// Interface languages: code in the settings file, and name in itself
+pub const LANGUAGES: [(&str, &str); 4] = [
+    ("en", "English"),
+    ("de", "Deutsch"),
+    ("es", "Español"),
+    ("pt", "Português"),
+];
+
+/// Catalogs shipped with the program. Each maps an English string, as
+/// written in the source, to its translation; missing ones stay English.
+const CATALOGS: [(&str, &str); 3] = [
+    ("de", include_str!("../../locales/de.toml")),
+    ("es", include_str!("../../locales/es.toml")),
+    ("pt", include_str!("../../locales/pt.toml")),
+];
+
+/// Directory under each config directory with catalogs that add to or
+/// correct the shipped ones, e.g. locales/de.toml
+const LOCALES_DIR: &str = "locales";
+
+/// Translations of the current language. Values are leaked so `tr` can
+/// hand out strings as long-lived as the English ones; that happens once
+/// per language change.
+static CATALOG: RwLock<Option<HashMap<String, &'static str>>> = RwLock::new(None);
+
+/// Switch the interface to `code`, one of `LANGUAGES`; English otherwise
+pub fn set_language(code: &str) {
+    let mut catalog = HashMap::new();
+    if let Some((_, shipped)) = CATALOGS.iter().find(|(c, _)| *c == code) {
+        catalog.extend(parse(shipped));
+    }
+    for dir in settings::config_dirs() {
+        let path = dir.join(LOCALES_DIR).join(format!("{}.toml", code));
+        if let Ok(content) = fs::read_to_string(path) {
+            catalog.extend(parse(&content));
+        }
+    }
+    let catalog = catalog
+        .into_iter()
+        .map(|(english, translated)| (english, &*Box::leak(translated.into_boxed_str())))
+        .collect();
+    if let Ok(mut current) = CATALOG.write() {
+        *current = Some(catalog);
+    }
+}
+
+/// `text` in the interface language
+pub fn tr(text: &str) -> &str {
+    CATALOG
+        .read()
+        .ok()
+        .and_then(|catalog| catalog.as_ref()?.get(text).copied())
+        .unwrap_or(text)
+}
+
+/// `template` in the interface language with each "{}" replaced by the
+/// next of `args`, for strings where the words go around a value
+pub fn trf(template: &str, args: &[&str]) -> String {
+    let mut args = args.iter();
+    let parts: Vec<&str> = tr(template).split("{}").collect();
+    let mut text = parts[0].to_string();
+    for part in &parts[1..] {
+        text.push_str(args.next().copied().unwrap_or(""));
+        text.push_str(part);
+    }
+    text
+}
+
+/// Name of the language with `code`, for the Settings tab
+pub fn language_name(code: &str) -> &str {
+    LANGUAGES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map_or(code, |(_, name)| name)
+}
+
+/// Entries of a catalog; one that does not parse counts as empty
+fn parse(content: &str) -> HashMap<String, String> {
+    toml::from_str(content).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs() {
+        // A shipped catalog that fails to parse would silently be English
+        for (code, content) in CATALOGS {
+            assert!(
+                toml::from_str::<HashMap<String, String>>(content).is_ok(),
+                "locales/{}.toml",
+                code
+            );
+            assert!(parse(content).contains_key("Quit"), "locales/{}.toml", code);
+        }
+        // Every catalog translates the same strings, so none falls back to English alone
+        let keys = |content| {
+            let mut keys: Vec<String> = parse(content).into_keys().collect();
+            keys.sort();
+            keys
+        };
+        let (first, reference) = CATALOGS[0];
+        for (code, content) in &CATALOGS[1..] {
+            assert_eq!(keys(content), keys(reference), "locales/{}.toml vs {}", code, first);
+        }
+        assert!(parse("\"Quit\" = \"Beenden\"\n")["Quit"] == "Beenden");
+        assert_eq!(trf("Go to {}", &["Kernel"]), "Go to Kernel");
+        assert_eq!(language_name("pt"), "Português");
+        assert_eq!(language_name("xx"), "xx");
+    }
+}
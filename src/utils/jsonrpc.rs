@@ -0,0 +1,115 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Error codes from the JSON-RPC 2.0 specification
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// The method ran and failed, e.g. a service script that is not there
+pub const SERVER_ERROR: i64 = -32000;
+
+/// A request as sent on the daemon socket, one per line
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub jsonrpc: String,
+    /// Echoed in the response; `None` for a notification, which gets none
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl Request {
+    /// Parameter `name` as a string, from named or positional parameters
+    pub fn str_param(&self, name: &str, position: usize) -> Option<&str> {
+        match &self.params {
+            Value::Object(params) => params.get(name)?.as_str(),
+            Value::Array(params) => params.get(position)?.as_str(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub code: i64,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Parse one line, or the error response to send back for it
+pub fn parse(line: &str) -> Result<Request, String> {
+    let value: Value = serde_json::from_str(line)
+        .map_err(|e| error_response(&Value::Null, &Error::new(PARSE_ERROR, e.to_string())))?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request: Request = serde_json::from_value(value)
+        .map_err(|e| error_response(&id, &Error::new(INVALID_REQUEST, e.to_string())))?;
+    if request.jsonrpc != "2.0" {
+        return Err(error_response(
+            &id,
+            &Error::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+        ));
+    }
+    Ok(request)
+}
+
+/// The response line for the request with `id`
+pub fn response(id: &Value, result: Result<Value, Error>) -> String {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }).to_string(),
+        Err(e) => error_response(id, &e),
+    }
+}
+
+fn error_response(id: &Value, error: &Error) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let request = parse(
+            r#"{"jsonrpc":"2.0","id":7,"method":"service","params":{"action":"restart","name":"sshd"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(request.str_param("action", 0), Some("restart"));
+        let positional =
+            parse(r#"{"jsonrpc":"2.0","id":1,"method":"service","params":["stop","httpd"]}"#)
+                .unwrap();
+        assert_eq!(positional.str_param("name", 1), Some("httpd"));
+        let notification = parse(r#"{"jsonrpc":"2.0","method":"update_status"}"#).unwrap();
+        assert_eq!(notification.id, None);
+
+        let error: Value = serde_json::from_str(&parse("{not json").unwrap_err()).unwrap();
+        assert_eq!(error["error"]["code"], json!(PARSE_ERROR));
+        let error: Value =
+            serde_json::from_str(&parse(r#"{"id":"a","method":"x"}"#).unwrap_err()).unwrap();
+        assert_eq!(error["id"], json!("a"));
+        assert_eq!(error["error"]["code"], json!(INVALID_REQUEST));
+
+        assert_eq!(
+            response(&json!(1), Ok(json!(["bash"]))),
+            r#"{"id":1,"jsonrpc":"2.0","result":["bash"]}"#
+        );
+    }
+}
@@ -0,0 +1,417 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::components::{custom_tab, Tab};
+use crate::utils::i18n::{tr, trf};
+
+/// Tab switching actions with their config names and default keys
+const TAB_ACTIONS: [(Tab, &str, &str); 17] = [
+    (Tab::Updater, "tab-updater", "f1"),
+    (Tab::Sbotools, "tab-sbotools", "f2"),
+    (Tab::UserSetup, "tab-users", "f3"),
+    (Tab::Mirror, "tab-mirrors", "f4"),
+    (Tab::Packages, "tab-search", "f5"),
+    (Tab::Config, "tab-config", "f6"),
+    (Tab::SysInfo, "tab-sysinfo", "f7"),
+    (Tab::Services, "tab-services", "f8"),
+    (Tab::PackageBrowser, "tab-packages", "f9"),
+    (Tab::Backup, "tab-backup", "f10"),
+    (Tab::Network, "tab-network", "f11"),
+    (Tab::Logs, "tab-logs", "f12"),
+    (Tab::Kernel, "tab-kernel", "ctrl+k"),
+    (Tab::Cron, "tab-cron", "ctrl+j"),
+    (Tab::Disks, "tab-disks", "ctrl+d"),
+    (Tab::Settings, "tab-settings", "ctrl+s"),
+    (Tab::Tasks, "tab-tasks", "ctrl+t"),
+];
+
+/// Something a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    NextTab,
+    PrevTab,
+    /// Passed on to the tab as F5
+    Refresh,
+    /// Passed on to the tab as 'y'
+    Confirm,
+    /// Passed on to the tab as '/'
+    Search,
+    /// Opens the command palette
+    Palette,
+    /// Searches the packages, services, config files, cron jobs and logs
+    GlobalSearch,
+    /// Shows the keys of the current tab
+    Help,
+    /// Puts back the files of the last config change
+    Undo,
+    /// Picks the machine to administer, this one or a remote host
+    Hosts,
+    /// Shows a second tab beside the current one, or closes it
+    Split,
+    /// Gives the keys to the other tab of the split
+    SwitchPane,
+    Goto(Tab),
+}
+
+impl Action {
+    pub fn all() -> Vec<Action> {
+        let mut actions = vec![
+            Action::Quit,
+            Action::NextTab,
+            Action::PrevTab,
+            Action::Refresh,
+            Action::Confirm,
+            Action::Search,
+            Action::Palette,
+            Action::GlobalSearch,
+            Action::Help,
+            Action::Undo,
+            Action::Hosts,
+            Action::Split,
+            Action::SwitchPane,
+        ];
+        actions.extend(TAB_ACTIONS.iter().map(|(tab, _, _)| Action::Goto(*tab)));
+        actions.extend(Tab::custom_tabs().into_iter().map(Action::Goto));
+        actions
+    }
+
+    /// Name of the action in the [keys] table of the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::NextTab => "next-tab",
+            Action::PrevTab => "prev-tab",
+            Action::Refresh => "refresh",
+            Action::Confirm => "confirm",
+            Action::Search => "search",
+            Action::Palette => "palette",
+            Action::GlobalSearch => "global-search",
+            Action::Help => "help",
+            Action::Undo => "undo",
+            Action::Hosts => "hosts",
+            Action::Split => "split",
+            Action::SwitchPane => "switch-pane",
+            Action::Goto(Tab::Custom(i)) => custom_tab::definitions()[*i].action.as_str(),
+            Action::Goto(tab) => Self::tab_entry(*tab).1,
+        }
+    }
+
+    /// What the action does, for the help overlay
+    pub fn description(&self) -> String {
+        match self {
+            Action::Quit => tr("Quit").to_string(),
+            Action::NextTab => tr("Next tab").to_string(),
+            Action::PrevTab => tr("Previous tab").to_string(),
+            Action::Refresh => tr("Refresh the tab").to_string(),
+            Action::Confirm => tr("Confirm").to_string(),
+            Action::Search => tr("Search in the tab").to_string(),
+            Action::Palette => tr("Command palette").to_string(),
+            Action::GlobalSearch => tr("Search everything").to_string(),
+            Action::Help => tr("This help").to_string(),
+            Action::Undo => tr("Undo last change").to_string(),
+            Action::Hosts => tr("Switch host").to_string(),
+            Action::Split => tr("Split the screen").to_string(),
+            Action::SwitchPane => tr("Switch pane").to_string(),
+            Action::Goto(tab) => trf("Go to {}", &[tr(tab.title())]),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Self::all().into_iter().find(|a| a.name() == name)
+    }
+
+    pub fn default_keys(&self) -> &'static str {
+        match self {
+            Action::Quit => "ctrl+q, ctrl+c",
+            Action::NextTab => "alt+right",
+            Action::PrevTab => "alt+left",
+            Action::Refresh => "f5",
+            Action::Confirm => "y",
+            Action::Search => "/",
+            Action::Palette => "ctrl+p",
+            Action::GlobalSearch => "ctrl+f",
+            Action::Help => "?",
+            Action::Undo => "ctrl+z",
+            Action::Hosts => "ctrl+o",
+            Action::Split => "ctrl+w",
+            Action::SwitchPane => "ctrl+e",
+            Action::Goto(Tab::Custom(i)) => custom_tab::definitions()[*i]
+                .key
+                .as_deref()
+                .unwrap_or(""),
+            Action::Goto(tab) => Self::tab_entry(*tab).2,
+        }
+    }
+
+    /// The key a tab is sent in place of the bound one
+    pub fn forwarded_key(&self) -> Option<KeyEvent> {
+        let code = match self {
+            Action::Refresh => KeyCode::F(5),
+            Action::Confirm => KeyCode::Char('y'),
+            Action::Search => KeyCode::Char('/'),
+            _ => return None,
+        };
+        Some(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn tab_entry(tab: Tab) -> (Tab, &'static str, &'static str) {
+        TAB_ACTIONS
+            .into_iter()
+            .find(|(t, _, _)| *t == tab)
+            .expect("every tab has an action")
+    }
+}
+
+/// A key with its modifiers, written like "ctrl+q", "alt+left" or "f5"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn parse(text: &str) -> Result<Key, String> {
+        let text = text.trim();
+        let mut parts: Vec<&str> = text.split('+').collect();
+        // "ctrl++" and "+" bind the plus key itself
+        if text.ends_with("++") || text == "+" {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let name = parts.pop().filter(|n| !n.is_empty());
+        let name = name.ok_or_else(|| format!("No key in \"{}\"", text))?;
+        let mut modifiers = KeyModifiers::NONE;
+        for part in parts {
+            modifiers |= match part.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("Unknown modifier \"{}\"", other)),
+            };
+        }
+        let lower = name.to_lowercase();
+        let code = match lower.as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            f if f.len() > 1 && f.starts_with('f') => match f[1..].parse::<u8>() {
+                Ok(n @ 1..=12) => KeyCode::F(n),
+                _ => return Err(format!("Unknown key \"{}\"", name)),
+            },
+            _ if name.chars().count() == 1 => {
+                let c = name.chars().next().unwrap_or(' ');
+                // Terminals send control and alt letters in lower case
+                if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+                    KeyCode::Char(c.to_ascii_lowercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            _ => return Err(format!("Unknown key \"{}\"", name)),
+        };
+        Ok(Key { code, modifiers })
+    }
+
+    /// A comma-separated list of keys; empty leaves the action unbound
+    pub fn parse_list(text: &str) -> Result<Vec<Key>, String> {
+        text.split(',')
+            .filter(|k| !k.trim().is_empty())
+            .map(Key::parse)
+            .collect()
+    }
+
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let (code, mut modifiers) = (event.code, event.modifiers);
+        let code = match code {
+            KeyCode::Char(c) => {
+                // Shift is already in the character, e.g. 'Q' or '?'
+                modifiers.remove(KeyModifiers::SHIFT);
+                if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+                    KeyCode::Char(c.to_ascii_lowercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            other => other,
+        };
+        let mut wanted = self.modifiers;
+        if matches!(self.code, KeyCode::Char(_)) {
+            wanted.remove(KeyModifiers::SHIFT);
+        }
+        code == self.code && modifiers == wanted
+    }
+}
+
+impl fmt::Display for Key {
+    /// Short form for the tab bar and status bar, e.g. "^K", "Alt+→", "F5"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.code {
+            KeyCode::F(n) => format!("F{}", n),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                c.to_ascii_uppercase().to_string()
+            }
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            other => format!("{:?}", other),
+        };
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "^")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", name)
+    }
+}
+
+/// Which keys run which actions, from the defaults and the [keys] table
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<Key>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::load(&BTreeMap::new()).0
+    }
+}
+
+impl Keymap {
+    /// Apply the configured keys over the defaults. Unknown actions and
+    /// keys that don't parse are reported and the default kept.
+    pub fn load(config: &BTreeMap<String, String>) -> (Keymap, Vec<String>) {
+        let mut errors = Vec::new();
+        for name in config.keys() {
+            if Action::from_name(name).is_none() {
+                errors.push(format!("Unknown action \"{}\"", name));
+            }
+        }
+        let bindings = Action::all()
+            .into_iter()
+            .map(|action| {
+                let default = || Key::parse_list(action.default_keys()).unwrap_or_default();
+                let keys = match config.get(action.name()) {
+                    Some(text) => Key::parse_list(text).unwrap_or_else(|e| {
+                        errors.push(format!("{}: {}", action.name(), e));
+                        default()
+                    }),
+                    None => default(),
+                };
+                (action, keys)
+            })
+            .collect();
+        (Keymap { bindings }, errors)
+    }
+
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// The first key of an action for hints, empty when it is unbound
+    pub fn label(&self, action: Action) -> String {
+        self.keys(action)
+            .first()
+            .map(Key::to_string)
+            .unwrap_or_default()
+    }
+
+    /// Actions bound to a key press, in the order of `Action::all`
+    pub fn actions(&self, event: &KeyEvent) -> Vec<Action> {
+        self.bindings
+            .iter()
+            .filter(|(_, keys)| keys.iter().any(|k| k.matches(event)))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+
+    /// Keys bound to more than one action. Refresh may share a key with
+    /// a tab: it refreshes tabs that can, and switches from the others,
+    /// which is how F5 has always behaved.
+    pub fn conflicts(&self) -> Vec<(Key, Action, Action)> {
+        let mut conflicts = Vec::new();
+        for (i, (first, keys)) in self.bindings.iter().enumerate() {
+            for (second, other_keys) in &self.bindings[i + 1..] {
+                let allowed = matches!(
+                    (first, second),
+                    (Action::Refresh, Action::Goto(_)) | (Action::Goto(_), Action::Refresh)
+                );
+                for key in keys {
+                    if !allowed && other_keys.contains(key) {
+                        conflicts.push((*key, *first, *second));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keymap() {
+        let ctrl_q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        let keymap = Keymap::default();
+        assert_eq!(keymap.actions(&ctrl_q), [Action::Quit]);
+        assert!(keymap.conflicts().is_empty());
+        assert_eq!(keymap.label(Action::Goto(Tab::Disks)), "^D");
+        assert_eq!(keymap.label(Action::NextTab), "Alt+→");
+
+        let config = BTreeMap::from([
+            ("tab-disks".to_string(), "alt+d".to_string()),
+            ("next-tab".to_string(), "ctrl+n, ctrl+q".to_string()),
+            ("search".to_string(), "hyper+s".to_string()),
+            ("reload".to_string(), "r".to_string()),
+        ]);
+        let (keymap, errors) = Keymap::load(&config);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(keymap.label(Action::Search), "/");
+        let alt_d = KeyEvent::new(KeyCode::Char('D'), KeyModifiers::ALT | KeyModifiers::SHIFT);
+        assert_eq!(keymap.actions(&alt_d), [Action::Goto(Tab::Disks)]);
+        let conflicts = keymap.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            (conflicts[0].1, conflicts[0].2),
+            (Action::Quit, Action::NextTab)
+        );
+
+        assert_eq!(Key::parse("ctrl++").unwrap().code, KeyCode::Char('+'));
+        assert!(Key::parse("f13").is_err());
+    }
+}
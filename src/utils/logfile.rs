@@ -0,0 +1,493 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDateTime};
+use tokio::io::AsyncBufReadExt;
+use tokio::sync::mpsc;
+
+use super::root;
+
+/// How often a followed log is checked for new data
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Decompressors for the rotated log formats logrotate produces
+const DECOMPRESSORS: &[(&str, &str)] = &[("gz", "gzip"), ("xz", "xz"), ("bz2", "bzip2")];
+
+fn decompressor(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?;
+    DECOMPRESSORS
+        .iter()
+        .find(|(e, _)| *e == ext)
+        .map(|(_, tool)| *tool)
+}
+
+pub fn is_compressed(path: &Path) -> bool {
+    decompressor(path).is_some()
+}
+
+/// A log as every reader here opens it: directly when this user may, and
+/// through sudo or doas only when that is denied
+enum LogFile {
+    Direct(File),
+    Escalated(PathBuf),
+}
+
+impl LogFile {
+    fn open(path: &Path) -> io::Result<Self> {
+        match File::open(path) {
+            Ok(file) => Ok(Self::Direct(file)),
+            Err(e)
+                if e.kind() == io::ErrorKind::PermissionDenied && root::escalation().is_some() =>
+            {
+                Ok(Self::Escalated(path.to_path_buf()))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn len(&self) -> io::Result<u64> {
+        match self {
+            Self::Direct(file) => Ok(file.metadata()?.len()),
+            Self::Escalated(path) => {
+                let size =
+                    escalated_output("stat", &["-L", "-c", "%s", "--", &path.to_string_lossy()])?;
+                String::from_utf8_lossy(&size)
+                    .trim()
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+        }
+    }
+
+    /// `len` bytes from `start`, or everything from there when `None`
+    fn read_range(&mut self, start: u64, len: Option<u64>) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Direct(file) => {
+                file.seek(SeekFrom::Start(start))?;
+                let mut buf = Vec::new();
+                match len {
+                    Some(len) => {
+                        buf.resize(len as usize, 0);
+                        file.read_exact(&mut buf)?;
+                    }
+                    None => {
+                        file.read_to_end(&mut buf)?;
+                    }
+                }
+                Ok(buf)
+            }
+            Self::Escalated(path) => {
+                let mut args = vec![
+                    format!("if={}", path.display()),
+                    "iflag=skip_bytes,count_bytes".to_string(),
+                    format!("skip={}", start),
+                    "status=none".to_string(),
+                ];
+                if let Some(len) = len {
+                    args.push(format!("count={}", len));
+                }
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                let buf = escalated_output("dd", &args)?;
+                if len.is_some_and(|len| (buf.len() as u64) < len) {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                Ok(buf)
+            }
+        }
+    }
+
+    /// The command that decompresses the log with `tool`
+    fn decompress_command(&self, tool: &str) -> Command {
+        match self {
+            Self::Direct(_) => Command::new(tool),
+            Self::Escalated(_) => root::command(tool),
+        }
+    }
+}
+
+/// What `program` prints, run with root rights
+fn escalated_output(program: &str, args: &[&str]) -> io::Result<Vec<u8>> {
+    let output = root::command(program).args(args).output()?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "{}: {}",
+                program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ))
+    }
+}
+
+/// Read all lines of a log, decompressing it first when needed
+pub fn read_lines(path: &Path) -> io::Result<Vec<String>> {
+    let mut log = LogFile::open(path)?;
+    let Some(tool) = decompressor(path) else {
+        let content = log.read_range(0, None)?;
+        return Ok(String::from_utf8_lossy(&content)
+            .lines()
+            .map(String::from)
+            .collect());
+    };
+    let mut command = log.decompress_command(tool);
+    let output = command.arg("-dc").arg(path).output()?;
+    if !output.status.success() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{} -dc: {}",
+                tool,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect())
+}
+
+/// Read the last `count` lines of an uncompressed log that end before
+/// byte `end`, seeking backwards so the rest of the file is never read.
+/// Returns the lines and the offset at which the first of them starts.
+pub fn tail_lines(path: &Path, count: usize, end: u64) -> io::Result<(Vec<String>, u64)> {
+    const CHUNK: u64 = 64 * 1024;
+    let mut log = LogFile::open(path)?;
+    let end = end.min(log.len()?);
+    let mut start = end;
+    let mut buf: Vec<u8> = Vec::new();
+    let mut newlines = 0usize;
+    loop {
+        // A newline ending the window terminates its last line rather than
+        // starting a new one
+        let trailing = usize::from(buf.last() == Some(&b'\n'));
+        if start == 0 || newlines.saturating_sub(trailing) >= count {
+            break;
+        }
+        let len = CHUNK.min(start);
+        start -= len;
+        let mut chunk = log.read_range(start, Some(len))?;
+        newlines += chunk.iter().filter(|&&b| b == b'\n').count();
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+    }
+    if buf.is_empty() {
+        return Ok((Vec::new(), start));
+    }
+
+    let content = buf.strip_suffix(b"\n").unwrap_or(&buf);
+    // Before the start of the file, the first line is cut off by the seek
+    let mut starts: Vec<usize> = content
+        .iter()
+        .enumerate()
+        .filter(|(_, &b)| b == b'\n')
+        .map(|(i, _)| i + 1)
+        .collect();
+    if start == 0 {
+        starts.insert(0, 0);
+    }
+    let starts = &starts[starts.len().saturating_sub(count)..];
+    let lines = starts
+        .iter()
+        .enumerate()
+        .map(|(n, &from)| {
+            let to = starts.get(n + 1).map(|&next| next - 1).unwrap_or(content.len());
+            String::from_utf8_lossy(&content[from..to]).to_string()
+        })
+        .collect();
+    let offset = start + starts.first().copied().unwrap_or(content.len()) as u64;
+    Ok((lines, offset))
+}
+
+/// Lines printed by a command, for logs that are read through a tool
+pub fn command_lines(cmd: &str, args: &[&str]) -> io::Result<Vec<String>> {
+    let output = root::command(cmd).args(args).output()?;
+    if !output.status.success() && output.stdout.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "{}: {}",
+                cmd,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect())
+}
+
+/// The tool that prints a binary login record file, with its arguments:
+/// `last` for wtmp, `lastb` for btmp and `lastlog` for lastlog
+pub fn login_records_command(path: &Path) -> Option<(&'static str, Vec<String>)> {
+    if is_compressed(path) {
+        return None;
+    }
+    let name = path.file_name()?.to_string_lossy();
+    let file = path.to_string_lossy().to_string();
+    match live_name(&name) {
+        "wtmp" => Some(("last", vec!["-f".to_string(), file])),
+        "btmp" => Some(("lastb", vec!["-f".to_string(), file])),
+        // lastlog(8) always reads /var/log/lastlog
+        "lastlog" if path == Path::new("/var/log/lastlog") => Some(("lastlog", Vec::new())),
+        _ => None,
+    }
+}
+
+/// The file a rotated log was rotated from, e.g. "messages" for
+/// "messages.2.gz" or "messages-20240101.xz"
+pub fn live_name(name: &str) -> &str {
+    let mut name = name;
+    if let Some((base, ext)) = name.rsplit_once('.') {
+        if DECOMPRESSORS.iter().any(|(e, _)| *e == ext) {
+            name = base;
+        }
+    }
+    match name.rsplit_once(['.', '-']) {
+        Some((base, suffix))
+            if !base.is_empty()
+                && !suffix.is_empty()
+                && suffix.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            base
+        }
+        _ => name,
+    }
+}
+
+/// Rotated copies of `path` in the same directory, oldest first
+pub fn rotations(path: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut rotated: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let file_name = e.file_name();
+            let file_name = file_name.to_string_lossy();
+            file_name != name && live_name(&file_name) == name
+        })
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .collect();
+    rotated.sort();
+    rotated.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Tail `path` in a background task, sending each line appended after
+/// byte `offset`. The task ends when the receiver is dropped. When the log
+/// is truncated or rotated away it is reopened and read from the start.
+pub fn follow(path: &Path, offset: u64) -> mpsc::UnboundedReceiver<String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let path = path.to_path_buf();
+    tokio::spawn(async move {
+        let mut offset = offset;
+        let mut partial = String::new();
+        while !tx.is_closed() {
+            tokio::time::sleep(FOLLOW_INTERVAL).await;
+            let read = {
+                let path = path.clone();
+                tokio::task::spawn_blocking(move || read_appended(&path, offset)).await
+            };
+            let Ok(Ok((buf, restarted))) = read else {
+                continue;
+            };
+            if restarted {
+                offset = 0;
+                partial.clear();
+            }
+            offset += buf.len() as u64;
+            partial.push_str(&String::from_utf8_lossy(&buf));
+            // Hold back an unterminated last line until it is complete
+            while let Some(end) = partial.find('\n') {
+                let line: String = partial.drain(..=end).collect();
+                if tx
+                    .send(line.trim_end_matches(['\n', '\r']).to_string())
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    });
+    rx
+}
+
+/// What was appended to `path` after byte `offset`, or all of it when it
+/// shrank below that (truncated or rotated), and whether it started over
+fn read_appended(path: &Path, offset: u64) -> io::Result<(Vec<u8>, bool)> {
+    let mut log = LogFile::open(path)?;
+    let len = log.len()?;
+    let restarted = len < offset;
+    let start = if restarted { 0 } else { offset };
+    if len == start {
+        return Ok((Vec::new(), restarted));
+    }
+    Ok((log.read_range(start, None)?, restarted))
+}
+
+/// Stream the output of a long-running command such as `dmesg -W`. The
+/// command is killed once the receiver is dropped.
+pub fn follow_command(cmd: &str, args: &[&str]) -> mpsc::UnboundedReceiver<String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut command = tokio::process::Command::from(root::command(cmd));
+    command
+        .args(args)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true);
+    tokio::spawn(async move {
+        let Ok(mut child) = command.spawn() else {
+            return;
+        };
+        let Some(stdout) = child.stdout.take() else {
+            return;
+        };
+        let mut lines = tokio::io::BufReader::new(stdout).lines();
+        loop {
+            tokio::select! {
+                _ = tx.closed() => break,
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => {
+                        if tx.send(line).is_err() {
+                            break;
+                        }
+                    }
+                    _ => break,
+                },
+            }
+        }
+    });
+    rx
+}
+
+/// Timestamp at the start of a log line: classic syslog ("Jan  2 15:04:05",
+/// no year, assumed to be within the last twelve months) or ISO 8601
+pub fn parse_timestamp(line: &str) -> Option<NaiveDateTime> {
+    if let Some(iso) = line.get(..19) {
+        let iso = iso.replacen('T', " ", 1);
+        if let Ok(ts) = NaiveDateTime::parse_from_str(&iso, "%Y-%m-%d %H:%M:%S") {
+            return Some(ts);
+        }
+    }
+
+    let stamp = line.get(..15)?;
+    let now = Local::now().naive_local();
+    let with_year = |year: i32| {
+        NaiveDateTime::parse_from_str(&format!("{} {}", year, stamp), "%Y %b %e %H:%M:%S").ok()
+    };
+    let ts = with_year(now.year())?;
+    // A December line read in January belongs to last year
+    if ts > now + chrono::Duration::days(1) {
+        return with_year(now.year() - 1);
+    }
+    Some(ts)
+}
+
+/// Interleave several logs by timestamp, prefixing each line with the tag
+/// of its source. Lines without a timestamp (stack traces, continuation
+/// lines) stay attached to the line before them.
+pub fn merge_timeline(sources: &[(String, Vec<String>)]) -> Vec<String> {
+    let width = sources.iter().map(|(tag, _)| tag.len()).max().unwrap_or(0);
+    let mut entries: Vec<(Option<NaiveDateTime>, usize, usize, String)> = Vec::new();
+    for (source, (tag, lines)) in sources.iter().enumerate() {
+        let mut last = None;
+        for (index, line) in lines.iter().enumerate() {
+            if let Some(ts) = parse_timestamp(line) {
+                last = Some(ts);
+            }
+            entries.push((
+                last,
+                source,
+                index,
+                format!("{:<width$} | {}", tag, line, width = width),
+            ));
+        }
+    }
+    // Ties keep each file's own order; undated leading lines sort first
+    entries.sort_by_key(|(ts, source, index, _)| (*ts, *source, *index));
+    entries.into_iter().map(|(_, _, _, line)| line).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_name() {
+        assert_eq!(live_name("messages.1"), "messages");
+        assert_eq!(live_name("messages.2.gz"), "messages");
+        assert_eq!(live_name("syslog-20240101.xz"), "syslog");
+        assert_eq!(live_name("Xorg.0.log"), "Xorg.0.log");
+        assert_eq!(live_name("secure"), "secure");
+    }
+
+    #[test]
+    fn test_read_lines() {
+        let path = std::env::temp_dir().join(format!("read-lines-{}.log", std::process::id()));
+        fs::write(&path, "first\nsecond\n").unwrap();
+        assert_eq!(read_lines(&path).unwrap(), ["first", "second"]);
+        let status = Command::new("gzip").arg(&path).status().unwrap();
+        assert!(status.success());
+        let gz = path.with_extension("log.gz");
+        assert_eq!(read_lines(&gz).unwrap(), ["first", "second"]);
+        fs::remove_file(gz).unwrap();
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        let path = std::env::temp_dir().join(format!("tail-lines-{}.log", std::process::id()));
+        let content: String = (1..=100_000).map(|n| format!("line {}\n", n)).collect();
+        fs::write(&path, content).unwrap();
+
+        let (lines, offset) = tail_lines(&path, 3, u64::MAX).unwrap();
+        assert_eq!(lines, vec!["line 99998", "line 99999", "line 100000"]);
+        let (earlier, start) = tail_lines(&path, 2, offset).unwrap();
+        assert_eq!(earlier, vec!["line 99996", "line 99997"]);
+        let (all, start) = tail_lines(&path, 200_000, start).unwrap();
+        assert_eq!((all.len(), all[0].as_str(), start), (99_995, "line 1", 0));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_appended() {
+        let path = std::env::temp_dir().join(format!("read-appended-{}.log", std::process::id()));
+        fs::write(&path, "one\ntwo\n").unwrap();
+        assert_eq!(read_appended(&path, 4).unwrap(), (b"two\n".to_vec(), false));
+        assert_eq!(read_appended(&path, 8).unwrap(), (Vec::new(), false));
+        // Rotated away and started anew
+        fs::write(&path, "new\n").unwrap();
+        assert_eq!(read_appended(&path, 8).unwrap(), (b"new\n".to_vec(), true));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_merge_timeline() {
+        let syslog = vec![
+            "2024-03-01T10:00:00 host kernel: eth0 link down".to_string(),
+            "2024-03-01T10:00:05 host kernel: eth0 link up".to_string(),
+        ];
+        let secure = vec![
+            "2024-03-01T10:00:02 host sshd[42]: Connection closed".to_string(),
+            "  continuation".to_string(),
+        ];
+        let merged = merge_timeline(&[
+            ("syslog".to_string(), syslog),
+            ("secure".to_string(), secure),
+        ]);
+        assert!(merged[0].ends_with("link down"));
+        assert!(merged[1].starts_with("secure | "));
+        assert!(merged[2].ends_with("continuation"));
+        assert!(merged[3].ends_with("link up"));
+    }
+}
@@ -1,4 +1,12 @@
+pub mod bandwidth;
 pub mod error;
+pub mod fuzzy;
+pub mod i18n;
+pub mod jsonrpc;
+pub mod keymap;
+pub mod logfile;
 pub mod root;
+pub mod scratch;
+pub mod session;
 
 pub use root::check_root;
@@ -1,6 +1,14 @@
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::RwLock;
+
 use nix::unistd::Uid;
+use serde::{Deserialize, Serialize};
 
 use super::error::{AppError, Result};
+use crate::slackware::remote;
 
 /// Check if the current process is running as root (UID 0)
 pub fn check_root() -> Result<()> {
@@ -15,3 +23,418 @@ pub fn check_root() -> Result<()> {
 pub fn is_root() -> bool {
     Uid::effective().is_root()
 }
+
+/// Tool that runs privileged commands when the manager is started as a
+/// normal user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Escalation {
+    #[default]
+    Sudo,
+    Doas,
+}
+
+impl Escalation {
+    pub fn program(&self) -> &'static str {
+        match self {
+            Escalation::Sudo => "sudo",
+            Escalation::Doas => "doas",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Escalation::Sudo => Escalation::Doas,
+            Escalation::Doas => Escalation::Sudo,
+        }
+    }
+
+    /// Program and leading arguments that run the rest with root rights.
+    /// `-n` makes both fail rather than prompt on the terminal the interface
+    /// draws on; the password is asked for up front, see `authenticate`.
+    /// They also clear the environment, so `env` is passed through `env`.
+    pub fn wrap(&self, program: &str, env: &[(String, String)]) -> Vec<String> {
+        let mut argv = vec![
+            self.program().to_string(),
+            "-n".to_string(),
+            "--".to_string(),
+        ];
+        if !env.is_empty() {
+            argv.push("env".to_string());
+            argv.extend(env.iter().map(|(key, value)| format!("{}={}", key, value)));
+        }
+        argv.push(program.to_string());
+        argv
+    }
+}
+
+/// How privileged commands are run: `None` as root, when they run as they are
+static ESCALATION: RwLock<Option<Escalation>> = RwLock::new(None);
+
+/// Use `tool` for privileged commands, unless already running as root
+pub fn set_escalation(tool: Escalation) {
+    if let Ok(mut current) = ESCALATION.write() {
+        *current = (!is_root()).then_some(tool);
+    }
+}
+
+pub fn escalation() -> Option<Escalation> {
+    ESCALATION.read().ok().and_then(|tool| *tool)
+}
+
+/// `program` as a command with root rights, ready for its arguments
+pub fn command(program: &str) -> Command {
+    command_with_env(program, &[])
+}
+
+/// `program` with root rights and `env` set, which sudo and doas would
+/// otherwise drop. With a remote host picked it runs there instead.
+pub fn command_with_env(program: &str, env: &[(String, String)]) -> Command {
+    if let Some(host) = remote::current() {
+        return host.command(program, env);
+    }
+    match escalation() {
+        Some(tool) => {
+            let argv = tool.wrap(program, env);
+            let mut command = Command::new(&argv[0]);
+            command.args(&argv[1..]);
+            command
+        }
+        None => {
+            let mut command = Command::new(program);
+            command.envs(env.iter().map(|(key, value)| (key, value)));
+            command
+        }
+    }
+}
+
+/// The program and arguments of a command made by `command`, without the
+/// sudo, doas or ssh it goes through and the environment passed along
+pub fn unwrapped_argv(command: &Command) -> Vec<String> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let args: Vec<String> = command
+        .get_args()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect();
+    let wrapped = match args.first().map(String::as_str) {
+        Some(remote::EXEC_ARG) => true,
+        Some("-n") => ["sudo", "doas"]
+            .iter()
+            .any(|tool| Path::new(&program).ends_with(tool)),
+        _ => false,
+    };
+    let Some(start) = args.iter().position(|arg| arg == "--").filter(|_| wrapped) else {
+        return std::iter::once(program).chain(args).collect();
+    };
+    let mut rest = &args[start + 1..];
+    if rest.first().is_some_and(|word| word == "env") {
+        let settings = rest[1..]
+            .iter()
+            .take_while(|word| word.contains('='))
+            .count();
+        rest = &rest[1 + settings..];
+    }
+    rest.to_vec()
+}
+
+/// Whether privileged commands can run now without a password
+pub fn authenticated() -> bool {
+    match escalation() {
+        Some(tool) => Command::new(tool.program())
+            .args(["-n", "true"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success()),
+        None => true,
+    }
+}
+
+/// Whether privileged commands can run now: as root, or through a tool
+/// that no longer asks for a password
+pub fn can_escalate() -> bool {
+    is_root() || (escalation().is_some() && authenticated())
+}
+
+/// Whether `authenticate` can unlock the escalation tool; doas only takes
+/// passwords from the terminal
+pub fn takes_password() -> bool {
+    escalation() == Some(Escalation::Sudo)
+}
+
+/// Give sudo the password, so it runs privileged commands for a while
+/// without asking. doas only reads passwords from the terminal, so it needs
+/// a `nopass` rule in /etc/doas.conf instead.
+pub fn authenticate(password: &str) -> Result<()> {
+    match escalation() {
+        None => Ok(()),
+        Some(Escalation::Doas) => Err(AppError::Config(
+            "doas cannot be given a password here; permit this user with nopass in \
+             /etc/doas.conf"
+                .to_string(),
+        )),
+        Some(Escalation::Sudo) => {
+            let mut child = Command::new("sudo")
+                .args(["-S", "-p", "", "-v"])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = writeln!(stdin, "{}", password);
+            }
+            let output = child.wait_with_output()?;
+            if output.status.success() {
+                Ok(())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(AppError::CommandFailed(
+                    stderr
+                        .lines()
+                        .last()
+                        .unwrap_or("sudo refused the password")
+                        .to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// Extend sudo's grace period while the manager is open, so a long session
+/// never stops halfway to ask again
+pub fn keep_alive() {
+    if escalation() == Some(Escalation::Sudo) {
+        let _ = Command::new("sudo")
+            .args(["-n", "-v"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// Run a privileged command to completion, feeding it `input`
+fn run(program: &str, args: &[&str], input: &[u8]) -> io::Result<Vec<u8>> {
+    let mut child = command(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Written from the side, so a command echoing its input (tee) can never
+    // block on a full stdout nobody reads yet
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_vec();
+        std::thread::spawn(move || stdin.write_all(&input))
+    });
+    let output = child.wait_with_output()?;
+    if let Some(writer) = writer {
+        writer.join().unwrap_or(Ok(()))?;
+    }
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(io::Error::new(
+            ErrorKind::PermissionDenied,
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// `direct`, or when that was not allowed and privileged commands are
+/// available, `escalated`. On a remote host only `escalated` can reach
+/// the files.
+pub fn or_escalated<T>(
+    direct: impl FnOnce() -> io::Result<T>,
+    escalated: impl FnOnce() -> io::Result<T>,
+) -> io::Result<T> {
+    if remote::current().is_some() {
+        return escalated();
+    }
+    match direct() {
+        Err(e) if e.kind() == ErrorKind::PermissionDenied && escalation().is_some() => escalated(),
+        result => result,
+    }
+}
+
+/// Read a file, through `cat` with root rights if need be
+pub fn read_file(path: &Path) -> io::Result<Vec<u8>> {
+    or_escalated(|| fs::read(path), || {
+        run("cat", &["--", &path.to_string_lossy()], &[])
+    })
+}
+
+/// Whether a file exists, asking the remote host when one is managed
+pub fn exists(path: &Path) -> bool {
+    or_escalated(|| Ok(path.exists()), || {
+        run("test", &["-e", &path.to_string_lossy()], &[]).map(|_| true)
+    })
+    .unwrap_or(false)
+}
+
+/// Write a file, through `tee` with root rights if need be
+pub fn write_file(path: &Path, content: &[u8]) -> io::Result<()> {
+    or_escalated(|| fs::write(path, content), || {
+        run("tee", &["--", &path.to_string_lossy()], content).map(|_| ())
+    })
+}
+
+/// Append to a file, creating it readable by root only when it is new
+pub fn append_private(path: &Path, content: &[u8]) -> io::Result<()> {
+    let direct = || {
+        use std::os::unix::fs::OpenOptionsExt;
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .mode(0o600)
+            .open(path)?
+            .write_all(content)
+    };
+    or_escalated(direct, || {
+        let script = "umask 077 && cat >> \"$1\"";
+        run("sh", &["-c", script, "sh", &path.to_string_lossy()], content).map(|_| ())
+    })
+}
+
+/// Create a new file only its owner can read, failing when the name is
+/// already taken rather than following whatever is there
+pub fn write_private(path: &Path, content: &[u8]) -> io::Result<()> {
+    let direct = || {
+        use std::os::unix::fs::OpenOptionsExt;
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)?
+            .write_all(content)
+    };
+    or_escalated(direct, || {
+        // noclobber makes the shell open the file with O_EXCL
+        let script = "umask 077 && set -C && cat > \"$1\"";
+        run("sh", &["-c", script, "sh", &path.to_string_lossy()], content).map(|_| ())
+    })
+}
+
+/// Copy a file, keeping its mode
+pub fn copy(from: &Path, to: &Path) -> io::Result<()> {
+    or_escalated(|| fs::copy(from, to).map(|_| ()), || {
+        run("cp", &["-p", "--", &from.to_string_lossy(), &to.to_string_lossy()], &[]).map(|_| ())
+    })
+}
+
+/// Move a file over another in one step
+pub fn rename(from: &Path, to: &Path) -> io::Result<()> {
+    or_escalated(|| fs::rename(from, to), || {
+        run("mv", &["-f", "--", &from.to_string_lossy(), &to.to_string_lossy()], &[]).map(|_| ())
+    })
+}
+
+/// Make `link` a symlink pointing at `target`
+pub fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    or_escalated(|| std::os::unix::fs::symlink(target, link), || {
+        run("ln", &["-s", "--", &target.to_string_lossy(), &link.to_string_lossy()], &[])
+            .map(|_| ())
+    })
+}
+
+pub fn create_dir_all(path: &Path) -> io::Result<()> {
+    or_escalated(|| fs::create_dir_all(path), || {
+        run("mkdir", &["-p", "--", &path.to_string_lossy()], &[]).map(|_| ())
+    })
+}
+
+/// Create a directory, and any missing parents, only its owner can enter
+pub fn create_private_dir(path: &Path) -> io::Result<()> {
+    create_dir_all(path)?;
+    set_mode(path, 0o700)
+}
+
+/// Names of the entries in a directory, through `ls` with root rights if
+/// need be
+pub fn read_dir_names(path: &Path) -> io::Result<Vec<String>> {
+    let direct = || {
+        fs::read_dir(path)?
+            .map(|e| e.map(|e| e.file_name().to_string_lossy().to_string()))
+            .collect()
+    };
+    or_escalated(direct, || {
+        let listing = run("ls", &["-1A", "--", &path.to_string_lossy()], &[])?;
+        Ok(String::from_utf8_lossy(&listing).lines().map(str::to_string).collect())
+    })
+}
+
+/// Remove a file or a whole directory
+pub fn remove(path: &Path) -> io::Result<()> {
+    let direct = || {
+        if path.is_dir() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        }
+    };
+    or_escalated(direct, || {
+        run("rm", &["-rf", "--", &path.to_string_lossy()], &[]).map(|_| ())
+    })
+}
+
+pub fn set_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    or_escalated(
+        || fs::set_permissions(path, fs::Permissions::from_mode(mode)),
+        || {
+            let mode = format!("{:o}", mode & 0o7777);
+            run("chmod", &[&mode, "--", &path.to_string_lossy()], &[]).map(|_| ())
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            Escalation::Sudo.wrap("removepkg", &[]),
+            vec!["sudo", "-n", "--", "removepkg"]
+        );
+        let env = vec![("http_proxy".to_string(), "http://proxy:3128".to_string())];
+        assert_eq!(
+            Escalation::Doas.wrap("slackpkg", &env),
+            vec![
+                "doas",
+                "-n",
+                "--",
+                "env",
+                "http_proxy=http://proxy:3128",
+                "slackpkg"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unwrapped_argv() {
+        let wrapped = |argv: &[&str]| {
+            let mut command = Command::new(argv[0]);
+            command.args(&argv[1..]);
+            unwrapped_argv(&command)
+        };
+        assert_eq!(
+            wrapped(&["/usr/bin/sudo", "-n", "--", "removepkg", "foo"]),
+            vec!["removepkg", "foo"]
+        );
+        assert_eq!(
+            wrapped(&["doas", "-n", "--", "env", "http_proxy=p:3128", "slackpkg", "update"]),
+            vec!["slackpkg", "update"]
+        );
+        assert_eq!(
+            wrapped(&["manager", remote::EXEC_ARG, "ssh", "box", "--", "lilo"]),
+            vec!["lilo"]
+        );
+        assert_eq!(
+            wrapped(&["umount", "--", "/mnt"]),
+            vec!["umount", "--", "/mnt"]
+        );
+    }
+}
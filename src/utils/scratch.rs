@@ -0,0 +1,28 @@
+use std::io;
+use std::path::PathBuf;
+
+use nix::unistd;
+
+/// A new directory under the temp dir that only this user can enter,
+/// named `<prefix>-` and random letters so nobody can plant it first.
+/// Files made inside it are out of reach of other local users.
+pub fn private_dir(prefix: &str) -> io::Result<PathBuf> {
+    let template = std::env::temp_dir().join(format!("{}-XXXXXX", prefix));
+    unistd::mkdtemp(&template).map_err(io::Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_private_dir() {
+        let dir = private_dir("scratch-test").unwrap();
+        let other = private_dir("scratch-test").unwrap();
+        assert_ne!(dir, other);
+        assert_eq!(dir.metadata().unwrap().permissions().mode() & 0o777, 0o700);
+        std::fs::remove_dir(dir).unwrap();
+        std::fs::remove_dir(other).unwrap();
+    }
+}
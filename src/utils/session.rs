@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::ViewState;
+
+/// Where the interface stood when the program last exited
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Id of the tab that was open
+    pub tab: String,
+    /// Where each tab stood, by tab id; tabs left as they start are omitted
+    pub tabs: BTreeMap<String, ViewState>,
+}
+
+/// $XDG_STATE_HOME/slackware-cli-manager/state.toml, or under
+/// ~/.local/state
+pub fn path() -> Option<PathBuf> {
+    let var = |name| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let base = var("XDG_STATE_HOME")
+        .or_else(|| var("HOME").map(|home| home.join(".local").join("state")))?;
+    Some(base.join("slackware-cli-manager").join("state.toml"))
+}
+
+impl Session {
+    /// The last session; empty when there was none or it can't be read
+    pub fn load() -> Self {
+        path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        toml::from_str(content).ok()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = path().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "no home directory")
+        })?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = toml::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+
+    /// Keep `state` for `tab`, unless there is nothing to restore in it
+    pub fn set(&mut self, tab: &str, state: ViewState) {
+        if state == ViewState::default() {
+            self.tabs.remove(tab);
+        } else {
+            self.tabs.insert(tab.to_string(), state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut session = Session {
+            tab: "logs".to_string(),
+            ..Session::default()
+        };
+        session.set(
+            "logs",
+            ViewState {
+                selected: Some(3),
+                query: "error".to_string(),
+                open: Some("/var/log/messages".to_string()),
+                scroll: 120,
+            },
+        );
+        session.set("services", ViewState::default());
+        assert_eq!(session.tabs.len(), 1);
+
+        let content = toml::to_string(&session).unwrap();
+        assert_eq!(Session::parse(&content), Some(session));
+    }
+
+    #[test]
+    fn test_parse_partial() {
+        let session = Session::parse("tab = \"cron\"\n[tabs.cron]\nselected = 2\n").unwrap();
+        assert_eq!(session.tab, "cron");
+        assert_eq!(session.tabs["cron"].selected, Some(2));
+        assert_eq!(session.tabs["cron"].query, "");
+        assert_eq!(Session::parse("tab = 5"), None);
+    }
+}